@@ -0,0 +1,524 @@
+//! Aider 配置读写
+//!
+//! Aider 依次从主目录、git 仓库根目录和当前目录读取配置，cc-switch 只管理主目录下的三个文件：
+//! - `.aider.conf.yml`：命令行参数的 YAML 形式（`model` / `weak-model` / `editor-model` / `openai-api-base` 等）
+//! - `.aider.model.settings.yml`：按模型名定义的高级设置（`edit_format`、`extra_params` 等）
+//! - `.env`：各家 API Key（`OPENAI_API_KEY` / `ANTHROPIC_API_KEY` / `OPENROUTER_API_KEY`）
+//!
+//! 供应商的 settings_config 结构为：
+//!
+//! ```json
+//! {
+//!   "env": { "OPENAI_API_KEY": "sk-..." },
+//!   "config": {
+//!     "model": "openai/deepseek-chat",
+//!     "weak-model": "openai/deepseek-chat",
+//!     "openai-api-base": "https://api.deepseek.com/v1"
+//!   },
+//!   "modelSettings": [{ "name": "openai/deepseek-chat", "edit_format": "diff" }]
+//! }
+//! ```
+//!
+//! 主目录的 `.env` 往往由多个工具共用，写入时只改动 [`AIDER_ENV_KEYS`] 中的变量，
+//! 其余行（包括注释）原样保留。
+
+use crate::config::{get_home_dir, live_path_exists, read_live_to_string, write_text_file};
+use crate::error::AppError;
+use crate::gemini_config::parse_env_file;
+use crate::hermes_config::{json_to_yaml, yaml_to_json};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 由 cc-switch 管理的 `.env` 变量，切换供应商时未提供的会被移除
+pub const AIDER_ENV_KEYS: &[&str] = &["OPENAI_API_KEY", "ANTHROPIC_API_KEY", "OPENROUTER_API_KEY"];
+
+/// 由 cc-switch 管理的 `.aider.conf.yml` 字段，切换供应商时未提供的会被移除
+pub const AIDER_MANAGED_CONFIG_KEYS: &[&str] =
+    &["model", "weak-model", "editor-model", "openai-api-base"];
+
+/// 获取 Aider 配置目录（支持设置覆盖，默认为主目录）
+pub fn get_aider_dir() -> PathBuf {
+    if let Some(custom) = crate::settings::get_aider_override_dir() {
+        return custom;
+    }
+
+    get_home_dir()
+}
+
+/// 获取 `.aider.conf.yml` 路径
+pub fn get_aider_config_path() -> PathBuf {
+    get_aider_dir().join(".aider.conf.yml")
+}
+
+/// 获取 `.aider.model.settings.yml` 路径
+pub fn get_aider_model_settings_path() -> PathBuf {
+    get_aider_dir().join(".aider.model.settings.yml")
+}
+
+/// 获取 Aider 读取的 `.env` 路径
+pub fn get_aider_env_path() -> PathBuf {
+    get_aider_dir().join(".env")
+}
+
+fn read_yaml(path: &Path) -> Result<Option<serde_yaml::Value>, AppError> {
+    if !live_path_exists(path) {
+        return Ok(None);
+    }
+
+    let content = read_live_to_string(path).map_err(|e| AppError::io(path, e))?;
+    if content.trim().is_empty() {
+        return Ok(Some(serde_yaml::Value::Null));
+    }
+
+    serde_yaml::from_str(&content).map(Some).map_err(|e| {
+        AppError::Config(format!(
+            "Failed to parse Aider config {}: {e}",
+            path.display()
+        ))
+    })
+}
+
+fn write_yaml(path: &Path, value: &serde_yaml::Value) -> Result<(), AppError> {
+    let content = serde_yaml::to_string(value)
+        .map_err(|e| AppError::Config(format!("Failed to serialize Aider config: {e}")))?;
+    write_text_file(path, &content)
+}
+
+/// 读取 `.aider.conf.yml`，文件不存在或为空时返回空 Mapping
+pub fn read_aider_config() -> Result<serde_yaml::Mapping, AppError> {
+    let path = get_aider_config_path();
+    match read_yaml(&path)? {
+        None | Some(serde_yaml::Value::Null) => Ok(serde_yaml::Mapping::new()),
+        Some(serde_yaml::Value::Mapping(map)) => Ok(map),
+        Some(_) => Err(AppError::Config(format!(
+            "Aider config must be a YAML mapping: {}",
+            path.display()
+        ))),
+    }
+}
+
+/// 读取 `.aider.model.settings.yml`，文件不存在或为空时返回空列表
+pub fn read_aider_model_settings() -> Result<Vec<serde_yaml::Value>, AppError> {
+    let path = get_aider_model_settings_path();
+    match read_yaml(&path)? {
+        None | Some(serde_yaml::Value::Null) => Ok(Vec::new()),
+        Some(serde_yaml::Value::Sequence(items)) => Ok(items),
+        Some(_) => Err(AppError::Config(format!(
+            "Aider model settings must be a YAML list: {}",
+            path.display()
+        ))),
+    }
+}
+
+/// 读取 `.env` 中由 cc-switch 管理的变量（其他变量不导入供应商）
+pub fn read_aider_env() -> Result<HashMap<String, String>, AppError> {
+    let path = get_aider_env_path();
+    if !live_path_exists(&path) {
+        return Ok(HashMap::new());
+    }
+
+    let content = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    Ok(parse_env_file(&content)
+        .into_iter()
+        .filter(|(key, _)| AIDER_ENV_KEYS.contains(&key.as_str()))
+        .collect())
+}
+
+/// 只更新 `.env` 中的指定变量：`Some` 覆盖或追加，`None` 删除，其余行原样保留
+fn update_env_content(content: &str, updates: &[(&str, Option<&str>)]) -> String {
+    let mut written = Vec::new();
+    let mut lines = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let assignment = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+        let key = assignment
+            .split_once('=')
+            .map(|(key, _)| key.trim())
+            .filter(|_| !trimmed.starts_with('#'));
+
+        match key.and_then(|key| updates.iter().find(|(name, _)| *name == key)) {
+            Some((name, Some(value))) if !written.contains(name) => {
+                lines.push(format!("{name}={value}"));
+                written.push(*name);
+            }
+            Some(_) => {}
+            None => lines.push(line.to_string()),
+        }
+    }
+
+    for (name, value) in updates {
+        if let (Some(value), false) = (value, written.contains(name)) {
+            lines.push(format!("{name}={value}"));
+        }
+    }
+
+    let mut result = lines.join("\n");
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result
+}
+
+/// 更新 `.env` 中由 cc-switch 管理的变量（env 中未提供的管理变量会被移除）
+pub fn write_aider_env(env: &HashMap<String, String>) -> Result<(), AppError> {
+    let path = get_aider_env_path();
+    let existing = if live_path_exists(&path) {
+        read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?
+    } else {
+        String::new()
+    };
+
+    let updates: Vec<(&str, Option<&str>)> = AIDER_ENV_KEYS
+        .iter()
+        .map(|key| {
+            let value = env.get(*key).map(|v| v.trim()).filter(|v| !v.is_empty());
+            (*key, value)
+        })
+        .collect();
+    write_text_file(&path, &update_env_content(&existing, &updates))?;
+
+    // 设置文件权限为 600（仅所有者可读写）
+    #[cfg(unix)]
+    if !crate::config::is_staging_writes() {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&path)
+            .map_err(|e| AppError::io(&path, e))?
+            .permissions();
+        perms.set_mode(0o600);
+        std::fs::set_permissions(&path, perms).map_err(|e| AppError::io(&path, e))?;
+    }
+
+    Ok(())
+}
+
+fn env_from_settings(settings: &Value) -> HashMap<String, String> {
+    settings
+        .get("env")
+        .and_then(Value::as_object)
+        .map(|env| {
+            env.iter()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn config_str<'a>(settings: &'a Value, key: &str) -> Option<&'a str> {
+    settings
+        .get("config")
+        .and_then(|config| config.get(key))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// 验证 Aider 配置的基本结构
+///
+/// 不强制要求模型与 API Key，允许先创建供应商、稍后再填写。
+pub fn validate_aider_settings(settings: &Value) -> Result<(), AppError> {
+    if let Some(env) = settings.get("env") {
+        if !env.is_object() {
+            return Err(AppError::localized(
+                "aider.validation.invalid_env",
+                "Aider 配置格式错误: env 必须是对象",
+                "Aider config invalid: env must be an object",
+            ));
+        }
+    }
+
+    if let Some(config) = settings.get("config") {
+        if !(config.is_object() || config.is_null()) {
+            return Err(AppError::localized(
+                "aider.validation.invalid_config",
+                "Aider 配置格式错误: config 必须是对象",
+                "Aider config invalid: config must be an object",
+            ));
+        }
+    }
+
+    if let Some(model_settings) = settings.get("modelSettings").filter(|v| !v.is_null()) {
+        let valid = model_settings.as_array().is_some_and(|items| {
+            items.iter().all(|item| {
+                item.get("name")
+                    .and_then(Value::as_str)
+                    .is_some_and(|name| !name.trim().is_empty())
+            })
+        });
+        if !valid {
+            return Err(AppError::localized(
+                "aider.validation.invalid_model_settings",
+                "Aider 配置格式错误: modelSettings 必须是带 name 字段的对象数组",
+                "Aider config invalid: modelSettings must be an array of objects with a name",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// 严格验证 Aider 配置（切换供应商时使用）：必须指定 `model`
+pub fn validate_aider_settings_strict(settings: &Value) -> Result<(), AppError> {
+    validate_aider_settings(settings)?;
+
+    if config_str(settings, "model").is_none() {
+        return Err(AppError::localized(
+            "aider.validation.missing_model",
+            "Aider 配置缺少必需字段: model",
+            "Aider config missing required field: model",
+        ));
+    }
+
+    Ok(())
+}
+
+/// 将供应商 config 合并进现有 `.aider.conf.yml`
+///
+/// 管理字段以供应商为准（未提供则移除），其他字段由供应商覆盖或保持不变。
+fn merge_aider_config(
+    mut existing: serde_yaml::Mapping,
+    config: Option<&Map<String, Value>>,
+) -> Result<serde_yaml::Mapping, AppError> {
+    for key in AIDER_MANAGED_CONFIG_KEYS {
+        existing.remove(*key);
+    }
+
+    if let Some(config) = config {
+        for (key, value) in config {
+            if value.is_null() {
+                existing.remove(key.as_str());
+                continue;
+            }
+            existing.insert(serde_yaml::Value::from(key.as_str()), json_to_yaml(value)?);
+        }
+    }
+
+    Ok(existing)
+}
+
+fn model_setting_name(item: &serde_yaml::Value) -> Option<&str> {
+    item.get("name").and_then(serde_yaml::Value::as_str)
+}
+
+/// 将供应商的模型设置合并进 `.aider.model.settings.yml`：同名条目替换，其余条目保留
+fn merge_model_settings(
+    mut existing: Vec<serde_yaml::Value>,
+    entries: &[Value],
+) -> Result<Vec<serde_yaml::Value>, AppError> {
+    for entry in entries {
+        let Some(name) = entry.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let value = json_to_yaml(entry)?;
+        match existing
+            .iter_mut()
+            .find(|item| model_setting_name(item) == Some(name))
+        {
+            Some(slot) => *slot = value,
+            None => existing.push(value),
+        }
+    }
+
+    Ok(existing)
+}
+
+/// 读取 Aider live 配置，返回 `{ "env": {...}, "config": {...}, "modelSettings": [...] }`
+///
+/// modelSettings 只保留 config 中引用到的模型（model / weak-model / editor-model）。
+pub fn read_aider_live_settings() -> Result<Value, AppError> {
+    let config_path = get_aider_config_path();
+    if !live_path_exists(&config_path) {
+        return Err(AppError::localized(
+            "aider.live.missing",
+            "Aider 配置文件不存在",
+            "Aider configuration file is missing",
+        ));
+    }
+
+    let config = yaml_to_json(&serde_yaml::Value::Mapping(read_aider_config()?))?;
+    let referenced: Vec<&str> = ["model", "weak-model", "editor-model"]
+        .iter()
+        .filter_map(|key| config.get(*key).and_then(Value::as_str))
+        .collect();
+
+    let mut model_settings = Vec::new();
+    for item in read_aider_model_settings()? {
+        if model_setting_name(&item).is_some_and(|name| referenced.contains(&name)) {
+            model_settings.push(yaml_to_json(&item)?);
+        }
+    }
+
+    let env: Map<String, Value> = read_aider_env()?
+        .into_iter()
+        .map(|(key, value)| (key, Value::String(value)))
+        .collect();
+
+    let mut result = json!({ "env": env, "config": config });
+    if !model_settings.is_empty() {
+        result["modelSettings"] = Value::Array(model_settings);
+    }
+    Ok(result)
+}
+
+/// 将供应商配置写入 Aider live 文件
+pub fn write_aider_live(settings_config: &Value) -> Result<(), AppError> {
+    validate_aider_settings_strict(settings_config)?;
+
+    let config = merge_aider_config(
+        read_aider_config()?,
+        settings_config.get("config").and_then(Value::as_object),
+    )?;
+    write_yaml(
+        &get_aider_config_path(),
+        &serde_yaml::Value::Mapping(config),
+    )?;
+
+    if let Some(entries) = settings_config
+        .get("modelSettings")
+        .and_then(Value::as_array)
+        .filter(|entries| !entries.is_empty())
+    {
+        let merged = merge_model_settings(read_aider_model_settings()?, entries)?;
+        write_yaml(
+            &get_aider_model_settings_path(),
+            &serde_yaml::Value::Sequence(merged),
+        )?;
+    }
+
+    write_aider_env(&env_from_settings(settings_config))
+}
+
+/// 读取代理接管涉及的连接字段：`{ "env": {...}, "config": { "openai-api-base": ... } }`
+pub fn read_aider_endpoint() -> Result<Value, AppError> {
+    let config = read_aider_config()?;
+    let mut endpoint = Map::new();
+    if let Some(base) = config.get("openai-api-base") {
+        endpoint.insert("openai-api-base".to_string(), yaml_to_json(base)?);
+    }
+
+    let env: Map<String, Value> = read_aider_env()?
+        .into_iter()
+        .map(|(key, value)| (key, Value::String(value)))
+        .collect();
+
+    Ok(json!({ "env": env, "config": endpoint }))
+}
+
+/// 写回连接字段（代理接管 / 恢复使用），模型等其他设置保持不变
+pub fn write_aider_endpoint(endpoint: &Value) -> Result<(), AppError> {
+    let mut config = read_aider_config()?;
+    match config_str(endpoint, "openai-api-base") {
+        Some(base) => {
+            config.insert(
+                serde_yaml::Value::from("openai-api-base"),
+                serde_yaml::Value::from(base),
+            );
+        }
+        None => {
+            config.remove("openai-api-base");
+        }
+    }
+    write_yaml(
+        &get_aider_config_path(),
+        &serde_yaml::Value::Mapping(config),
+    )?;
+
+    write_aider_env(&env_from_settings(endpoint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_validation_requires_model() {
+        let valid = json!({
+            "env": { "OPENAI_API_KEY": "sk-test" },
+            "config": { "model": "openai/deepseek-chat" },
+            "modelSettings": [{ "name": "openai/deepseek-chat", "edit_format": "diff" }]
+        });
+        assert!(validate_aider_settings_strict(&valid).is_ok());
+
+        let missing = json!({ "env": {}, "config": { "weak-model": "gpt-4o-mini" } });
+        assert!(validate_aider_settings(&missing).is_ok());
+        assert!(validate_aider_settings_strict(&missing).is_err());
+
+        assert!(validate_aider_settings(&json!({ "env": "invalid" })).is_err());
+        assert!(
+            validate_aider_settings(&json!({ "modelSettings": [{ "edit_format": "diff" }] }))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn merge_config_replaces_managed_keys_and_keeps_others() {
+        let existing: serde_yaml::Mapping = serde_yaml::from_str(
+            "model: gpt-4o\nweak-model: gpt-4o-mini\ndark-mode: true\nauto-commits: false\n",
+        )
+        .unwrap();
+        let config = json!({ "model": "openai/deepseek-chat", "auto-commits": true });
+
+        let merged = merge_aider_config(existing, config.as_object()).unwrap();
+
+        assert_eq!(
+            merged.get("model").and_then(|v| v.as_str()),
+            Some("openai/deepseek-chat")
+        );
+        assert!(
+            merged.get("weak-model").is_none(),
+            "stale managed keys are removed"
+        );
+        assert_eq!(
+            merged.get("dark-mode").and_then(|v| v.as_bool()),
+            Some(true)
+        );
+        assert_eq!(
+            merged.get("auto-commits").and_then(|v| v.as_bool()),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn merge_model_settings_replaces_same_name_entries() {
+        let existing: Vec<serde_yaml::Value> = serde_yaml::from_str(
+            "- name: openai/deepseek-chat\n  edit_format: whole\n- name: gpt-4o\n  edit_format: diff\n",
+        )
+        .unwrap();
+        let entries = vec![
+            json!({ "name": "openai/deepseek-chat", "edit_format": "diff" }),
+            json!({ "name": "openai/deepseek-reasoner", "use_temperature": false }),
+        ];
+
+        let merged = merge_model_settings(existing, &entries).unwrap();
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(
+            merged[0].get("edit_format").and_then(|v| v.as_str()),
+            Some("diff")
+        );
+        assert_eq!(model_setting_name(&merged[1]), Some("gpt-4o"));
+        assert_eq!(
+            model_setting_name(&merged[2]),
+            Some("openai/deepseek-reasoner")
+        );
+    }
+
+    #[test]
+    fn env_update_touches_only_listed_keys() {
+        let content = "# shared secrets\nGITHUB_TOKEN=ghp\nexport OPENAI_API_KEY=old\nANTHROPIC_API_KEY=sk-ant\n";
+        let updated = update_env_content(
+            content,
+            &[
+                ("OPENAI_API_KEY", Some("sk-new")),
+                ("ANTHROPIC_API_KEY", None),
+                ("OPENROUTER_API_KEY", Some("sk-or")),
+            ],
+        );
+
+        assert_eq!(
+            updated,
+            "# shared secrets\nGITHUB_TOKEN=ghp\nOPENAI_API_KEY=sk-new\nOPENROUTER_API_KEY=sk-or\n"
+        );
+    }
+}
@@ -17,6 +17,12 @@ pub struct McpApps {
     pub opencode: bool,
     #[serde(default)]
     pub hermes: bool,
+    #[serde(default)]
+    pub qwen: bool,
+    #[serde(default)]
+    pub goose: bool,
+    #[serde(default)]
+    pub crush: bool,
 }
 
 impl McpApps {
@@ -29,6 +35,10 @@ impl McpApps {
             AppType::OpenCode => self.opencode,
             AppType::OpenClaw => false, // OpenClaw doesn't support MCP
             AppType::Hermes => self.hermes,
+            AppType::Qwen => self.qwen,
+            AppType::Goose => self.goose,
+            AppType::Crush => self.crush,
+            AppType::Aider => false, // Aider doesn't support MCP
             AppType::ClaudeDesktop => false,
         }
     }
@@ -42,6 +52,10 @@ impl McpApps {
             AppType::OpenCode => self.opencode = enabled,
             AppType::OpenClaw => {} // OpenClaw doesn't support MCP, ignore
             AppType::Hermes => self.hermes = enabled,
+            AppType::Qwen => self.qwen = enabled,
+            AppType::Goose => self.goose = enabled,
+            AppType::Crush => self.crush = enabled,
+            AppType::Aider => {}         // Aider doesn't support MCP, ignore
             AppType::ClaudeDesktop => {} // Claude Desktop 3P provider config doesn't support MCP here
         }
     }
@@ -64,12 +78,28 @@ impl McpApps {
         if self.hermes {
             apps.push(AppType::Hermes);
         }
+        if self.qwen {
+            apps.push(AppType::Qwen);
+        }
+        if self.goose {
+            apps.push(AppType::Goose);
+        }
+        if self.crush {
+            apps.push(AppType::Crush);
+        }
         apps
     }
 
     /// 检查是否所有应用都未启用
     pub fn is_empty(&self) -> bool {
-        !self.claude && !self.codex && !self.gemini && !self.opencode && !self.hermes
+        !self.claude
+            && !self.codex
+            && !self.gemini
+            && !self.opencode
+            && !self.hermes
+            && !self.qwen
+            && !self.goose
+            && !self.crush
     }
 }
 
@@ -98,6 +128,9 @@ impl SkillApps {
             AppType::OpenCode => self.opencode,
             AppType::Hermes => self.hermes,
             AppType::OpenClaw => false, // OpenClaw doesn't support Skills
+            AppType::Qwen => false,     // Qwen Code has no skills directory
+            AppType::Goose | AppType::Crush => false, // Goose/Crush have no skills directory
+            AppType::Aider => false,    // Aider has no skills directory
             AppType::ClaudeDesktop => false,
         }
     }
@@ -111,6 +144,9 @@ impl SkillApps {
             AppType::OpenCode => self.opencode = enabled,
             AppType::Hermes => self.hermes = enabled,
             AppType::OpenClaw => {} // OpenClaw doesn't support Skills, ignore
+            AppType::Qwen => {}     // Qwen Code has no skills directory, ignore
+            AppType::Goose | AppType::Crush => {} // Goose/Crush have no skills directory, ignore
+            AppType::Aider => {}    // Aider has no skills directory, ignore
             AppType::ClaudeDesktop => {} // Claude Desktop 3P profiles don't use CC Switch skill sync
         }
     }
@@ -163,6 +199,71 @@ impl SkillApps {
     }
 }
 
+/// 共享提示词应用启用状态（标记提示词写入哪些客户端的提示词文件）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PromptApps {
+    #[serde(default)]
+    pub claude: bool,
+    #[serde(default)]
+    pub codex: bool,
+    #[serde(default)]
+    pub gemini: bool,
+    #[serde(default)]
+    pub opencode: bool,
+    #[serde(default)]
+    pub openclaw: bool,
+    #[serde(default)]
+    pub hermes: bool,
+    #[serde(default)]
+    pub qwen: bool,
+}
+
+impl PromptApps {
+    /// 检查指定应用是否启用
+    pub fn is_enabled_for(&self, app: &AppType) -> bool {
+        match app {
+            AppType::Claude => self.claude,
+            AppType::Codex => self.codex,
+            AppType::Gemini => self.gemini,
+            AppType::OpenCode => self.opencode,
+            AppType::OpenClaw => self.openclaw,
+            AppType::Hermes => self.hermes,
+            AppType::Qwen => self.qwen,
+            AppType::Goose | AppType::Crush => false, // Goose/Crush prompts are not managed
+            AppType::Aider => false,                  // Aider conventions files are not managed
+            AppType::ClaudeDesktop => false,          // Claude Desktop doesn't support Prompts
+        }
+    }
+
+    /// 设置指定应用的启用状态
+    pub fn set_enabled_for(&mut self, app: &AppType, enabled: bool) {
+        match app {
+            AppType::Claude => self.claude = enabled,
+            AppType::Codex => self.codex = enabled,
+            AppType::Gemini => self.gemini = enabled,
+            AppType::OpenCode => self.opencode = enabled,
+            AppType::OpenClaw => self.openclaw = enabled,
+            AppType::Hermes => self.hermes = enabled,
+            AppType::Qwen => self.qwen = enabled,
+            AppType::Goose | AppType::Crush => {} // Goose/Crush prompts are not managed, ignore
+            AppType::Aider => {}                  // Aider conventions files are not managed, ignore
+            AppType::ClaudeDesktop => {}          // Claude Desktop doesn't support Prompts, ignore
+        }
+    }
+
+    /// 获取所有启用的应用列表
+    pub fn enabled_apps(&self) -> Vec<AppType> {
+        AppType::all()
+            .filter(|app| self.is_enabled_for(app))
+            .collect()
+    }
+
+    /// 检查是否所有应用都未启用
+    pub fn is_empty(&self) -> bool {
+        self.enabled_apps().is_empty()
+    }
+}
+
 /// 已安装的 Skill（v3.10.0+ 统一结构）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -198,6 +299,38 @@ pub struct InstalledSkill {
     /// 最近更新时间（Unix 时间戳，0 = 从未更新）
     #[serde(default)]
     pub updated_at: i64,
+    /// 安装来源（任意 git 仓库或本地目录；GitHub 仓库安装时为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SkillSource>,
+}
+
+/// Skill 来源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillSourceKind {
+    Git,
+    Local,
+}
+
+/// Skill 安装来源
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillSource {
+    pub kind: SkillSourceKind,
+    /// git 仓库地址或本地目录
+    pub url: String,
+    /// 仓库内的 Skill 目录（相对路径）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// 安装时指定的分支、tag 或 commit
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// 是否固定在 tag / commit（固定后更新检测只提示，不自动跟随）
+    #[serde(default)]
+    pub pinned: bool,
+    /// 实际安装的 commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_commit: Option<String>,
 }
 
 /// 未管理的 Skill（在应用目录中发现但未被 CC Switch 管理）
@@ -280,6 +413,18 @@ pub struct McpRoot {
     /// Hermes MCP 配置（实际使用 config.yaml）
     #[serde(default, skip_serializing_if = "McpConfig::is_empty")]
     pub hermes: McpConfig,
+    /// Qwen Code MCP 配置（实际使用 ~/.qwen/settings.json）
+    #[serde(default, skip_serializing_if = "McpConfig::is_empty")]
+    pub qwen: McpConfig,
+    /// Goose MCP 配置（实际使用 config.yaml 的 extensions）
+    #[serde(default, skip_serializing_if = "McpConfig::is_empty")]
+    pub goose: McpConfig,
+    /// Crush MCP 配置（实际使用 crush.json 的 mcp）
+    #[serde(default, skip_serializing_if = "McpConfig::is_empty")]
+    pub crush: McpConfig,
+    /// Aider 不支持 MCP，仅保持结构一致
+    #[serde(default, skip_serializing_if = "McpConfig::is_empty")]
+    pub aider: McpConfig,
 }
 
 impl Default for McpRoot {
@@ -295,6 +440,10 @@ impl Default for McpRoot {
             opencode: McpConfig::default(),
             openclaw: McpConfig::default(),
             hermes: McpConfig::default(),
+            qwen: McpConfig::default(),
+            goose: McpConfig::default(),
+            crush: McpConfig::default(),
+            aider: McpConfig::default(),
         }
     }
 }
@@ -328,6 +477,14 @@ pub struct PromptRoot {
     pub openclaw: PromptConfig,
     #[serde(default)]
     pub hermes: PromptConfig,
+    #[serde(default)]
+    pub qwen: PromptConfig,
+    #[serde(default)]
+    pub goose: PromptConfig,
+    #[serde(default)]
+    pub crush: PromptConfig,
+    #[serde(default)]
+    pub aider: PromptConfig,
 }
 
 use crate::config::{copy_file, get_app_config_dir, get_app_config_path, write_json_file};
@@ -351,6 +508,10 @@ pub enum AppType {
     OpenCode,
     OpenClaw,
     Hermes,
+    Qwen,
+    Goose,
+    Crush,
+    Aider,
 }
 
 impl AppType {
@@ -363,17 +524,25 @@ impl AppType {
             AppType::OpenCode => "opencode",
             AppType::OpenClaw => "openclaw",
             AppType::Hermes => "hermes",
+            AppType::Qwen => "qwen",
+            AppType::Goose => "goose",
+            AppType::Crush => "crush",
+            AppType::Aider => "aider",
         }
     }
 
     /// Check if this app uses additive mode
     ///
-    /// - Switch mode (false): Only the current provider is written to live config (Claude, Codex, Gemini)
-    /// - Additive mode (true): All providers are written to live config (OpenCode, OpenClaw, Hermes)
+    /// - Switch mode (false): Only the current provider is written to live config (Claude, Codex, Gemini, Qwen, Aider)
+    /// - Additive mode (true): All providers are written to live config (OpenCode, OpenClaw, Hermes, Goose, Crush)
     pub fn is_additive_mode(&self) -> bool {
         matches!(
             self,
-            AppType::OpenCode | AppType::OpenClaw | AppType::Hermes
+            AppType::OpenCode
+                | AppType::OpenClaw
+                | AppType::Hermes
+                | AppType::Goose
+                | AppType::Crush
         )
     }
 
@@ -387,6 +556,10 @@ impl AppType {
             AppType::OpenCode,
             AppType::OpenClaw,
             AppType::Hermes,
+            AppType::Qwen,
+            AppType::Goose,
+            AppType::Crush,
+            AppType::Aider,
         ]
         .into_iter()
     }
@@ -405,10 +578,14 @@ impl FromStr for AppType {
             "opencode" => Ok(AppType::OpenCode),
             "openclaw" => Ok(AppType::OpenClaw),
             "hermes" => Ok(AppType::Hermes),
+            "qwen" => Ok(AppType::Qwen),
+            "goose" => Ok(AppType::Goose),
+            "crush" => Ok(AppType::Crush),
+            "aider" => Ok(AppType::Aider),
             other => Err(AppError::localized(
                 "unsupported_app",
-                format!("不支持的应用标识: '{other}'。可选值: claude, claude-desktop, codex, gemini, opencode, openclaw, hermes。"),
-                format!("Unsupported app id: '{other}'. Allowed: claude, claude-desktop, codex, gemini, opencode, openclaw, hermes."),
+                format!("不支持的应用标识: '{other}'。可选值: claude, claude-desktop, codex, gemini, opencode, openclaw, hermes, qwen, goose, crush, aider。"),
+                format!("Unsupported app id: '{other}'. Allowed: claude, claude-desktop, codex, gemini, opencode, openclaw, hermes, qwen, goose, crush, aider."),
             )),
         }
     }
@@ -434,6 +611,18 @@ pub struct CommonConfigSnippets {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub hermes: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qwen: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goose: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crush: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aider: Option<String>,
 }
 
 impl CommonConfigSnippets {
@@ -447,6 +636,10 @@ impl CommonConfigSnippets {
             AppType::OpenCode => self.opencode.as_ref(),
             AppType::OpenClaw => self.openclaw.as_ref(),
             AppType::Hermes => self.hermes.as_ref(),
+            AppType::Qwen => self.qwen.as_ref(),
+            AppType::Goose => self.goose.as_ref(),
+            AppType::Crush => self.crush.as_ref(),
+            AppType::Aider => self.aider.as_ref(),
         }
     }
 
@@ -460,6 +653,10 @@ impl CommonConfigSnippets {
             AppType::OpenCode => self.opencode = snippet,
             AppType::OpenClaw => self.openclaw = snippet,
             AppType::Hermes => self.hermes = snippet,
+            AppType::Qwen => self.qwen = snippet,
+            AppType::Goose => self.goose = snippet,
+            AppType::Crush => self.crush = snippet,
+            AppType::Aider => self.aider = snippet,
         }
     }
 }
@@ -503,6 +700,10 @@ impl Default for MultiAppConfig {
         apps.insert("opencode".to_string(), ProviderManager::default());
         apps.insert("openclaw".to_string(), ProviderManager::default());
         apps.insert("hermes".to_string(), ProviderManager::default());
+        apps.insert("qwen".to_string(), ProviderManager::default());
+        apps.insert("goose".to_string(), ProviderManager::default());
+        apps.insert("crush".to_string(), ProviderManager::default());
+        apps.insert("aider".to_string(), ProviderManager::default());
 
         Self {
             version: 2,
@@ -665,6 +866,10 @@ impl MultiAppConfig {
             AppType::OpenCode => &self.mcp.opencode,
             AppType::OpenClaw => &self.mcp.openclaw,
             AppType::Hermes => &self.mcp.hermes,
+            AppType::Qwen => &self.mcp.qwen,
+            AppType::Goose => &self.mcp.goose,
+            AppType::Crush => &self.mcp.crush,
+            AppType::Aider => &self.mcp.aider,
         }
     }
 
@@ -678,6 +883,10 @@ impl MultiAppConfig {
             AppType::OpenCode => &mut self.mcp.opencode,
             AppType::OpenClaw => &mut self.mcp.openclaw,
             AppType::Hermes => &mut self.mcp.hermes,
+            AppType::Qwen => &mut self.mcp.qwen,
+            AppType::Goose => &mut self.mcp.goose,
+            AppType::Crush => &mut self.mcp.crush,
+            AppType::Aider => &mut self.mcp.aider,
         }
     }
 
@@ -694,6 +903,7 @@ impl MultiAppConfig {
         Self::auto_import_prompt_if_exists(&mut config, AppType::OpenCode)?;
         Self::auto_import_prompt_if_exists(&mut config, AppType::OpenClaw)?;
         Self::auto_import_prompt_if_exists(&mut config, AppType::Hermes)?;
+        Self::auto_import_prompt_if_exists(&mut config, AppType::Qwen)?;
 
         Ok(config)
     }
@@ -717,6 +927,7 @@ impl MultiAppConfig {
             || !self.prompts.opencode.prompts.is_empty()
             || !self.prompts.openclaw.prompts.is_empty()
             || !self.prompts.hermes.prompts.is_empty()
+            || !self.prompts.qwen.prompts.is_empty()
         {
             return Ok(false);
         }
@@ -731,6 +942,7 @@ impl MultiAppConfig {
             AppType::OpenCode,
             AppType::OpenClaw,
             AppType::Hermes,
+            AppType::Qwen,
         ] {
             // 复用已有的单应用导入逻辑
             if Self::auto_import_prompt_if_exists(self, app)? {
@@ -804,6 +1016,10 @@ impl MultiAppConfig {
             AppType::OpenCode => &mut config.prompts.opencode.prompts,
             AppType::OpenClaw => &mut config.prompts.openclaw.prompts,
             AppType::Hermes => &mut config.prompts.hermes.prompts,
+            AppType::Qwen => &mut config.prompts.qwen.prompts,
+            AppType::Goose => &mut config.prompts.goose.prompts,
+            AppType::Crush => &mut config.prompts.crush.prompts,
+            AppType::Aider => &mut config.prompts.aider.prompts,
         };
 
         prompts.insert(id, prompt);
@@ -846,6 +1062,9 @@ impl MultiAppConfig {
                 AppType::OpenCode => &self.mcp.opencode.servers,
                 AppType::OpenClaw => continue, // OpenClaw MCP is still in development, skip
                 AppType::Hermes => continue,   // Hermes didn't exist in v3.6.x, skip
+                AppType::Qwen => continue,     // Qwen Code didn't exist in v3.6.x, skip
+                AppType::Goose | AppType::Crush => continue, // Not supported in v3.6.x, skip
+                AppType::Aider => continue,    // Aider has no MCP support, skip
             };
 
             for (id, entry) in old_servers {
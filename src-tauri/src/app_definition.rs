@@ -0,0 +1,804 @@
+//! 声明式应用定义
+//!
+//! 通过 `~/.cc-switch/apps/*.toml` 描述一个 CLI 工具：配置文件位置与格式、
+//! base URL / API Key / 模型所在的字段、MCP 段位置和提示词文件。
+//! 由 `CustomAppService` 基于定义完成 live 写入、从 live 导入、MCP 同步和提示词写入，
+//! 新增工具无需改代码；内置应用仍保持硬编码。
+//!
+//! ## 定义示例
+//!
+//! ```toml
+//! id = "iflow"
+//! name = "iFlow CLI"
+//!
+//! [config]
+//! path = "~/.iflow/settings.json"  # 格式按扩展名推断，也可显式指定 format
+//!
+//! [fields]                          # JSON Pointer，env 格式为 "/KEY"
+//! base_url = "/baseUrl"
+//! api_key = "/apiKey"
+//! model = "/modelName"
+//!
+//! [mcp]
+//! pointer = "/mcpServers"           # path / format 缺省与 [config] 相同
+//! inherit = "claude"                # 同步在该内置应用下启用的 MCP 服务器
+//!
+//! [mcp.keys]                        # 可选：规范字段名 -> 该工具使用的键名，空字符串表示不写
+//! url = "httpUrl"
+//! type = ""
+//!
+//! [prompt]
+//! path = "~/.iflow/IFLOW.md"
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use toml_edit::{ArrayOfTables, DocumentMut, InlineTable, Item, Table, TableLike};
+
+use crate::app_config::AppType;
+use crate::config::{
+    get_app_config_dir, get_home_dir, live_path_exists, read_live_to_string, write_text_file,
+};
+use crate::error::AppError;
+use crate::gemini_config::{parse_env_file, serialize_env_file};
+
+/// 应用定义目录（位于 cc-switch 配置目录下）
+pub const APP_DEFINITIONS_DIR: &str = "apps";
+
+const MAX_ID_LEN: usize = 32;
+
+/// 配置文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+    Env,
+}
+
+impl ConfigFormat {
+    /// 按扩展名推断格式（`.env` 文件名也视为 env）
+    fn infer(path: &str) -> Option<Self> {
+        let name = Path::new(path)
+            .file_name()?
+            .to_string_lossy()
+            .to_lowercase();
+        if name == ".env" || name.ends_with(".env") {
+            return Some(Self::Env);
+        }
+        match Path::new(&name).extension()?.to_str()? {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    fn parse(self, path: &Path, text: &str) -> Result<Value, AppError> {
+        if text.trim().is_empty() {
+            return Ok(Value::Object(Map::new()));
+        }
+        match self {
+            Self::Json => serde_json::from_str(text).map_err(|e| AppError::json(path, e)),
+            Self::Toml => {
+                let table: toml::Table =
+                    toml::from_str(text).map_err(|e| AppError::toml(path, e))?;
+                serde_json::to_value(table).map_err(|e| AppError::JsonSerialize { source: e })
+            }
+            Self::Yaml => serde_yaml::from_str(text)
+                .map_err(|e| AppError::Config(format!("解析 YAML 失败: {}: {e}", path.display()))),
+            Self::Env => Ok(Value::Object(
+                parse_env_file(text)
+                    .into_iter()
+                    .map(|(key, value)| (key, Value::String(value)))
+                    .collect(),
+            )),
+        }
+    }
+
+    /// 序列化为文本；`existing` 为原文件内容，TOML 在其上原地修改
+    fn serialize(self, path: &Path, existing: &str, value: &Value) -> Result<String, AppError> {
+        match self {
+            Self::Json => serde_json::to_string_pretty(value)
+                .map(|text| text + "\n")
+                .map_err(|e| AppError::JsonSerialize { source: e }),
+            Self::Toml => {
+                let mut doc = existing.parse::<DocumentMut>().map_err(|e| {
+                    AppError::Config(format!("解析 TOML 失败: {}: {e}", path.display()))
+                })?;
+                let map = value.as_object().ok_or_else(|| {
+                    AppError::Config(format!("TOML 顶层必须是表: {}", path.display()))
+                })?;
+                sync_toml_table(doc.as_table_mut(), map);
+                Ok(doc.to_string())
+            }
+            Self::Yaml => {
+                // serde_yaml 不保留注释，宁可拒绝也不静默丢弃用户的注释
+                if yaml_has_comments(existing) {
+                    return Err(AppError::localized(
+                        "app_definition.yaml_comments",
+                        format!(
+                            "{} 含有注释，写回会丢失这些注释，已取消写入；请先移除注释",
+                            path.display()
+                        ),
+                        format!(
+                            "{} contains comments that would be lost on write; remove them first",
+                            path.display()
+                        ),
+                    ));
+                }
+                serde_yaml::to_string(value).map_err(|e| {
+                    AppError::Config(format!("序列化 YAML 失败: {}: {e}", path.display()))
+                })
+            }
+            Self::Env => {
+                let map: HashMap<String, String> = value
+                    .as_object()
+                    .map(|obj| {
+                        obj.iter()
+                            .map(|(key, value)| {
+                                let value = match value {
+                                    Value::String(s) => s.clone(),
+                                    other => other.to_string(),
+                                };
+                                (key.clone(), value)
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Ok(serialize_env_file(&map))
+            }
+        }
+    }
+}
+
+/// 配置文件位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigFileSpec {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<ConfigFormat>,
+}
+
+/// 供应商字段在配置文件中的位置（JSON Pointer）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldSpec {
+    #[serde(default, alias = "base_url", skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(default, alias = "api_key", skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl FieldSpec {
+    /// (供应商 settingsConfig 中的键, JSON Pointer)
+    pub fn entries(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("baseUrl", self.base_url.as_deref()),
+            ("apiKey", self.api_key.as_deref()),
+            ("model", self.model.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(key, pointer)| pointer.map(|pointer| (key, pointer)))
+    }
+}
+
+/// MCP 段位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSpec {
+    /// 缺省与 `[config].path` 相同
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<ConfigFormat>,
+    /// MCP 服务器对象所在的 JSON Pointer（以服务器 ID 为键）
+    pub pointer: String,
+    /// 同步在该内置应用下启用的 MCP 服务器
+    #[serde(default = "default_mcp_inherit")]
+    pub inherit: String,
+    /// 服务器字段重命名：规范字段名（`type` / `command` / `args` / `env` / `url` / `headers` 等）
+    /// -> 该工具使用的键名；空字符串表示不写该字段。未列出的字段按规范形状原样写入
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, String>,
+}
+
+fn default_mcp_inherit() -> String {
+    AppType::Claude.as_str().to_string()
+}
+
+impl McpSpec {
+    /// 把规范形状的服务器定义转换为该工具使用的键名
+    pub fn map_server(&self, spec: &Value) -> Value {
+        let Some(obj) = spec.as_object().filter(|_| !self.keys.is_empty()) else {
+            return spec.clone();
+        };
+        let mapped = obj
+            .iter()
+            .filter_map(|(key, value)| match self.keys.get(key) {
+                Some(target) if target.is_empty() => None,
+                Some(target) => Some((target.clone(), value.clone())),
+                None => Some((key.clone(), value.clone())),
+            })
+            .collect();
+        Value::Object(mapped)
+    }
+}
+
+/// 提示词文件位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptSpec {
+    pub path: String,
+}
+
+/// 一个声明式应用定义
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDefinition {
+    pub id: String,
+    pub name: String,
+    pub config: ConfigFileSpec,
+    #[serde(default)]
+    pub fields: FieldSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp: Option<McpSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<PromptSpec>,
+    /// 定义文件路径（加载时填充）
+    #[serde(default, skip_deserializing)]
+    pub source: String,
+}
+
+impl AppDefinition {
+    /// 解析并校验 TOML 定义
+    pub fn parse(text: &str) -> Result<Self, AppError> {
+        let definition: Self =
+            toml::from_str(text).map_err(|e| AppError::Config(format!("应用定义格式错误: {e}")))?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    fn validate(&self) -> Result<(), AppError> {
+        let id_ok = !self.id.is_empty()
+            && self.id.len() <= MAX_ID_LEN
+            && self.id.starts_with(|c: char| c.is_ascii_lowercase())
+            && self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !id_ok {
+            return Err(AppError::localized(
+                "app_definition.invalid_id",
+                format!(
+                    "应用 ID '{}' 无效：需以小写字母开头，仅含小写字母、数字、- 和 _，且不超过 {MAX_ID_LEN} 个字符",
+                    self.id
+                ),
+                format!(
+                    "Invalid app id '{}': must start with a lowercase letter, contain only lowercase letters, digits, '-' and '_', and be at most {MAX_ID_LEN} characters",
+                    self.id
+                ),
+            ));
+        }
+        if AppType::from_str(&self.id).is_ok() {
+            return Err(AppError::localized(
+                "app_definition.builtin_id",
+                format!("应用 ID '{}' 与内置应用冲突", self.id),
+                format!("App id '{}' conflicts with a built-in app", self.id),
+            ));
+        }
+        if self.name.trim().is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "应用 '{}' 缺少 name",
+                self.id
+            )));
+        }
+        if self.config.path.trim().is_empty() {
+            return Err(AppError::InvalidInput(format!(
+                "应用 '{}' 缺少 config.path",
+                self.id
+            )));
+        }
+        let config_format = self.config_format()?;
+
+        let mut pointers: Vec<&str> = self.fields.entries().map(|(_, p)| p).collect();
+        if let Some(mcp) = &self.mcp {
+            pointers.push(&mcp.pointer);
+            self.mcp_format()?;
+            let inherit = AppType::from_str(&mcp.inherit)?;
+            if matches!(inherit, AppType::ClaudeDesktop | AppType::OpenClaw) {
+                return Err(AppError::InvalidInput(format!(
+                    "应用 '{}' 的 mcp.inherit 不支持 {}",
+                    self.id,
+                    inherit.as_str()
+                )));
+            }
+            let mut targets: Vec<&str> = Vec::new();
+            for target in mcp.keys.values().filter(|target| !target.is_empty()) {
+                if targets.contains(&target.as_str()) {
+                    return Err(AppError::InvalidInput(format!(
+                        "应用 '{}' 的 mcp.keys 中有多个字段映射到 '{target}'",
+                        self.id
+                    )));
+                }
+                targets.push(target);
+            }
+        }
+        for pointer in pointers {
+            if !pointer.starts_with('/') {
+                return Err(AppError::InvalidInput(format!(
+                    "应用 '{}' 的字段位置 '{pointer}' 必须是以 / 开头的 JSON Pointer",
+                    self.id
+                )));
+            }
+        }
+        if config_format == ConfigFormat::Env {
+            if let Some((_, pointer)) = self
+                .fields
+                .entries()
+                .find(|(_, pointer)| pointer[1..].contains('/'))
+            {
+                return Err(AppError::InvalidInput(format!(
+                    "应用 '{}' 使用 env 格式，字段位置 '{pointer}' 只能有一层",
+                    self.id
+                )));
+            }
+        }
+        if let Some(prompt) = &self.prompt {
+            if prompt.path.trim().is_empty() {
+                return Err(AppError::InvalidInput(format!(
+                    "应用 '{}' 缺少 prompt.path",
+                    self.id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        resolve_path(&self.config.path)
+    }
+
+    pub fn config_format(&self) -> Result<ConfigFormat, AppError> {
+        resolve_format(&self.id, &self.config.path, self.config.format)
+    }
+
+    pub fn mcp_path(&self) -> Option<PathBuf> {
+        let mcp = self.mcp.as_ref()?;
+        Some(resolve_path(
+            mcp.path.as_deref().unwrap_or(&self.config.path),
+        ))
+    }
+
+    pub fn mcp_format(&self) -> Result<ConfigFormat, AppError> {
+        match &self.mcp {
+            Some(McpSpec {
+                path: Some(path),
+                format,
+                ..
+            }) => resolve_format(&self.id, path, *format),
+            Some(McpSpec {
+                format: Some(format),
+                ..
+            }) => Ok(*format),
+            _ => self.config_format(),
+        }
+    }
+
+    pub fn prompt_path(&self) -> Option<PathBuf> {
+        self.prompt
+            .as_ref()
+            .map(|prompt| resolve_path(&prompt.path))
+    }
+}
+
+fn resolve_format(
+    id: &str,
+    path: &str,
+    explicit: Option<ConfigFormat>,
+) -> Result<ConfigFormat, AppError> {
+    explicit
+        .or_else(|| ConfigFormat::infer(path))
+        .ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "应用 '{id}' 无法从 '{path}' 推断配置格式，请指定 format（json/toml/yaml/env）"
+            ))
+        })
+}
+
+/// 解析路径，支持 `~` 开头
+fn resolve_path(raw: &str) -> PathBuf {
+    let raw = raw.trim();
+    if raw == "~" {
+        return get_home_dir();
+    }
+    if let Some(stripped) = raw.strip_prefix("~/").or_else(|| raw.strip_prefix("~\\")) {
+        return get_home_dir().join(stripped);
+    }
+    PathBuf::from(raw)
+}
+
+/// 应用定义目录
+pub fn get_app_definitions_dir() -> PathBuf {
+    get_app_config_dir().join(APP_DEFINITIONS_DIR)
+}
+
+/// 加载目录下的全部定义；无效或重复的定义跳过并返回错误信息
+pub fn load_app_definitions(dir: &Path) -> (Vec<AppDefinition>, Vec<String>) {
+    let mut definitions: Vec<AppDefinition> = Vec::new();
+    let mut errors = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (definitions, errors);
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| AppError::io(&path, e))
+            .and_then(|text| AppDefinition::parse(&text));
+        match parsed {
+            Ok(mut definition) => {
+                if definitions.iter().any(|d| d.id == definition.id) {
+                    errors.push(format!(
+                        "{}: 应用 ID '{}' 重复",
+                        path.display(),
+                        definition.id
+                    ));
+                    continue;
+                }
+                definition.source = path.to_string_lossy().to_string();
+                definitions.push(definition);
+            }
+            Err(e) => errors.push(format!("{}: {e}", path.display())),
+        }
+    }
+
+    (definitions, errors)
+}
+
+// ============================================================================
+// 通用文档读写
+// ============================================================================
+
+/// 读取配置文件为 JSON 值；文件不存在时返回空对象
+pub fn read_document(path: &Path, format: ConfigFormat) -> Result<Value, AppError> {
+    if !live_path_exists(path) {
+        return Ok(Value::Object(Map::new()));
+    }
+    let text = read_live_to_string(path).map_err(|e| AppError::io(path, e))?;
+    format.parse(path, &text)
+}
+
+/// 按格式写回配置文件（原子写入）；内容未变化时不写入
+///
+/// TOML 在原文档上逐键更新，注释与未改动的键原样保留；
+/// YAML 经结构化序列化写回，原文件含注释时拒绝写入。
+pub fn write_document(path: &Path, format: ConfigFormat, value: &Value) -> Result<(), AppError> {
+    let existing = if live_path_exists(path) {
+        read_live_to_string(path).map_err(|e| AppError::io(path, e))?
+    } else {
+        String::new()
+    };
+    if format.parse(path, &existing).ok().as_ref() == Some(value) {
+        return Ok(());
+    }
+    let text = format.serialize(path, &existing, value)?;
+    write_text_file(path, &text)
+}
+
+/// 让 TOML 表与 JSON 对象一致：删除多余的键，只改写取值变化的键（沿用原行的注释与空白）
+fn sync_toml_table(target: &mut dyn TableLike, source: &Map<String, Value>) {
+    let stale: Vec<String> = target
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| source.get(key).is_none_or(Value::is_null))
+        .collect();
+    for key in stale {
+        target.remove(&key);
+    }
+
+    for (key, value) in source {
+        if let (Value::Object(map), Some(existing)) =
+            (value, target.get_mut(key).and_then(Item::as_table_like_mut))
+        {
+            sync_toml_table(existing, map);
+            continue;
+        }
+        let Some(item) = json_to_toml_item(value) else {
+            continue;
+        };
+        match (target.get_mut(key).and_then(Item::as_value_mut), item) {
+            (Some(existing), Item::Value(mut new_value)) => {
+                if !same_toml_value(existing, &new_value) {
+                    *new_value.decor_mut() = existing.decor().clone();
+                    *existing = new_value;
+                }
+            }
+            (_, item) => {
+                target.insert(key, item);
+            }
+        }
+    }
+}
+
+/// JSON 转 TOML：对象写为表，全部由对象组成的数组写为表数组，null 视为缺失
+fn json_to_toml_item(value: &Value) -> Option<Item> {
+    match value {
+        Value::Object(map) => {
+            let mut table = Table::new();
+            // 只含子表时不输出空的表头
+            table.set_implicit(true);
+            sync_toml_table(&mut table, map);
+            Some(Item::Table(table))
+        }
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object) => {
+            let mut tables = ArrayOfTables::new();
+            for map in items.iter().filter_map(Value::as_object) {
+                let mut table = Table::new();
+                sync_toml_table(&mut table, map);
+                tables.push(table);
+            }
+            Some(Item::ArrayOfTables(tables))
+        }
+        other => json_to_toml_value(other).map(Item::Value),
+    }
+}
+
+fn json_to_toml_value(value: &Value) -> Option<toml_edit::Value> {
+    Some(match value {
+        Value::Null => return None,
+        Value::Bool(b) => (*b).into(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into(),
+            None => n.as_f64()?.into(),
+        },
+        Value::String(s) => s.as_str().into(),
+        Value::Array(items) => {
+            toml_edit::Value::Array(items.iter().filter_map(json_to_toml_value).collect())
+        }
+        Value::Object(map) => {
+            let mut table = InlineTable::new();
+            for (key, value) in map {
+                if let Some(value) = json_to_toml_value(value) {
+                    table.insert(key, value);
+                }
+            }
+            toml_edit::Value::InlineTable(table)
+        }
+    })
+}
+
+/// 按取值比较，忽略引号风格、空白与注释
+fn same_toml_value(a: &toml_edit::Value, b: &toml_edit::Value) -> bool {
+    use toml_edit::Value as V;
+    match (a, b) {
+        (V::String(a), V::String(b)) => a.value() == b.value(),
+        (V::Integer(a), V::Integer(b)) => a.value() == b.value(),
+        (V::Float(a), V::Float(b)) => a.value() == b.value(),
+        (V::Boolean(a), V::Boolean(b)) => a.value() == b.value(),
+        (V::Datetime(a), V::Datetime(b)) => a.value() == b.value(),
+        (V::Array(a), V::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| same_toml_value(a, b))
+        }
+        (V::InlineTable(a), V::InlineTable(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| same_toml_value(a, b)))
+        }
+        _ => false,
+    }
+}
+
+/// 粗略判断 YAML 文本是否含注释（行首或空白后、引号外的 `#`）
+fn yaml_has_comments(text: &str) -> bool {
+    text.lines().any(|line| {
+        let mut quote = None;
+        let mut prev = ' ';
+        for c in line.chars() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '\'' || c == '"' => quote = Some(c),
+                None if c == '#' && prev.is_whitespace() => return true,
+                None => {}
+            }
+            prev = c;
+        }
+        false
+    })
+}
+
+fn unescape_segment(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// 按 JSON Pointer 写入值，逐级创建缺失的对象
+pub fn pointer_set(root: &mut Value, pointer: &str, value: Value) -> Result<(), AppError> {
+    let segments: Vec<String> = pointer
+        .trim_start_matches('/')
+        .split('/')
+        .map(unescape_segment)
+        .collect();
+    let (last, parents) = segments
+        .split_last()
+        .filter(|(last, _)| !last.is_empty())
+        .ok_or_else(|| AppError::InvalidInput(format!("无效的字段位置: '{pointer}'")))?;
+
+    let mut current = root;
+    for segment in parents {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        current = current
+            .as_object_mut()
+            .expect("just ensured object")
+            .entry(segment.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    if !current.is_object() {
+        *current = Value::Object(Map::new());
+    }
+    current
+        .as_object_mut()
+        .expect("just ensured object")
+        .insert(last.clone(), value);
+    Ok(())
+}
+
+/// 按 JSON Pointer 删除值
+pub fn pointer_remove(root: &mut Value, pointer: &str) -> Option<Value> {
+    let (parent, last) = pointer.rsplit_once('/')?;
+    root.pointer_mut(parent)?
+        .as_object_mut()?
+        .remove(&unescape_segment(last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const IFLOW: &str = r#"
+id = "iflow"
+name = "iFlow CLI"
+
+[config]
+path = "~/.iflow/settings.json"
+
+[fields]
+base_url = "/baseUrl"
+api_key = "/apiKey"
+model = "/modelName"
+
+[mcp]
+pointer = "/mcpServers"
+
+[prompt]
+path = "~/.iflow/IFLOW.md"
+"#;
+
+    #[test]
+    fn parses_definition_with_defaults() {
+        let definition = AppDefinition::parse(IFLOW).expect("parse definition");
+        assert_eq!(definition.config_format().unwrap(), ConfigFormat::Json);
+        assert_eq!(definition.mcp_format().unwrap(), ConfigFormat::Json);
+        assert_eq!(definition.mcp.as_ref().unwrap().inherit, "claude");
+        assert_eq!(
+            definition.fields.entries().collect::<Vec<_>>(),
+            vec![
+                ("baseUrl", "/baseUrl"),
+                ("apiKey", "/apiKey"),
+                ("model", "/modelName"),
+            ]
+        );
+        assert!(definition.config_path().ends_with(".iflow/settings.json"));
+    }
+
+    #[test]
+    fn mcp_keys_rename_and_drop_canonical_fields() {
+        let text = format!("{IFLOW}\n[mcp.keys]\nurl = \"httpUrl\"\ntype = \"\"\n");
+        let definition = AppDefinition::parse(&text).expect("parse definition");
+        let mcp = definition.mcp.as_ref().unwrap();
+        assert_eq!(
+            mcp.map_server(&json!({ "type": "http", "url": "https://x", "headers": {} })),
+            json!({ "httpUrl": "https://x", "headers": {} })
+        );
+
+        let clash = format!("{IFLOW}\n[mcp.keys]\nurl = \"endpoint\"\ncommand = \"endpoint\"\n");
+        assert!(AppDefinition::parse(&clash).is_err());
+    }
+
+    #[test]
+    fn rejects_builtin_ids_and_nested_env_pointers() {
+        let builtin = IFLOW.replace("id = \"iflow\"", "id = \"qwen\"");
+        assert!(AppDefinition::parse(&builtin).is_err());
+
+        let env = r#"
+id = "tool"
+name = "Tool"
+[config]
+path = "~/.tool/.env"
+[fields]
+api_key = "/nested/KEY"
+"#;
+        assert!(AppDefinition::parse(env).is_err());
+    }
+
+    #[test]
+    fn pointer_set_creates_parents_and_remove_cleans_up() {
+        let mut root = json!({ "model": "keep-me" });
+        pointer_set(&mut root, "/env/OPENAI_API_KEY", json!("sk-1")).unwrap();
+        pointer_set(&mut root, "/model/name", json!("qwen3")).unwrap();
+        assert_eq!(
+            root,
+            json!({ "env": { "OPENAI_API_KEY": "sk-1" }, "model": { "name": "qwen3" } })
+        );
+        assert_eq!(
+            pointer_remove(&mut root, "/env/OPENAI_API_KEY"),
+            Some(json!("sk-1"))
+        );
+        assert_eq!(pointer_remove(&mut root, "/missing/key"), None);
+    }
+
+    #[test]
+    fn env_and_toml_documents_round_trip() {
+        let path = Path::new("test");
+        let env = ConfigFormat::Env
+            .parse(
+                path,
+                "# comment\nOPENAI_API_KEY=sk-1\nOPENAI_BASE_URL=https://x\n",
+            )
+            .unwrap();
+        assert_eq!(env["OPENAI_API_KEY"], json!("sk-1"));
+        let text = ConfigFormat::Env.serialize(path, "", &env).unwrap();
+        assert!(text.contains("OPENAI_BASE_URL=https://x"));
+
+        let toml_doc = ConfigFormat::Toml
+            .parse(path, "[model]\nname = \"a\"\n")
+            .unwrap();
+        assert_eq!(toml_doc, json!({ "model": { "name": "a" } }));
+        let text = ConfigFormat::Toml.serialize(path, "", &toml_doc).unwrap();
+        assert_eq!(ConfigFormat::Toml.parse(path, &text).unwrap(), toml_doc);
+    }
+
+    #[test]
+    fn toml_writes_keep_comments_and_untouched_lines() {
+        let path = Path::new("test");
+        let original = "# managed by hand\nmodel = 'a' # pinned\nkeep = 1\n\n[server]\n# endpoint\nurl = \"https://x\"\nold = true\n";
+        let mut doc = ConfigFormat::Toml.parse(path, original).unwrap();
+        doc["server"]["url"] = json!("https://y");
+        pointer_remove(&mut doc, "/server/old");
+        pointer_set(&mut doc, "/mcpServers/fs", json!({ "command": "npx" })).unwrap();
+
+        let text = ConfigFormat::Toml.serialize(path, original, &doc).unwrap();
+        assert!(text.starts_with("# managed by hand\nmodel = 'a' # pinned\nkeep = 1\n"));
+        assert!(text.contains("# endpoint\nurl = \"https://y\"\n"));
+        assert!(!text.contains("old"));
+        assert_eq!(ConfigFormat::Toml.parse(path, &text).unwrap(), doc);
+    }
+
+    #[test]
+    fn yaml_writes_refuse_to_drop_comments() {
+        let path = Path::new("test");
+        let value = json!({ "model": "b" });
+        assert!(ConfigFormat::Yaml
+            .serialize(path, "# keep me\nmodel: a\n", &value)
+            .is_err());
+        assert!(ConfigFormat::Yaml
+            .serialize(path, "model: a\nurl: \"https://x/#frag\"\n", &value)
+            .is_ok());
+    }
+}
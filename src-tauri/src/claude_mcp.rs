@@ -4,7 +4,10 @@ use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::{atomic_write, get_claude_mcp_path, get_default_claude_mcp_path};
+use crate::config::{
+    atomic_write, create_live_dir, get_claude_mcp_path, get_default_claude_mcp_path,
+    is_staging_writes, live_path_exists, read_live_to_string,
+};
 use crate::error::AppError;
 
 /// 需要在 Windows 上用 cmd /c 包装的命令
@@ -108,7 +111,7 @@ fn ensure_mcp_override_migrated() {
     }
 
     let new_path = get_claude_mcp_path();
-    if new_path.exists() {
+    if live_path_exists(&new_path) {
         return;
     }
 
@@ -117,6 +120,14 @@ fn ensure_mcp_override_migrated() {
         return;
     }
 
+    // 预览时只暂存复制结果，不落盘
+    if is_staging_writes() {
+        if let Ok(bytes) = fs::read(&legacy_path) {
+            let _ = atomic_write(&new_path, &bytes);
+        }
+        return;
+    }
+
     if let Some(parent) = new_path.parent() {
         if let Err(err) = fs::create_dir_all(parent) {
             log::warn!("创建 MCP 目录失败: {err}");
@@ -144,17 +155,17 @@ fn ensure_mcp_override_migrated() {
 }
 
 fn read_json_value(path: &Path) -> Result<Value, AppError> {
-    if !path.exists() {
+    if !live_path_exists(path) {
         return Ok(serde_json::json!({}));
     }
-    let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    let content = read_live_to_string(path).map_err(|e| AppError::io(path, e))?;
     let value: Value = serde_json::from_str(&content).map_err(|e| AppError::json(path, e))?;
     Ok(value)
 }
 
 fn write_json_value(path: &Path, value: &Value) -> Result<(), AppError> {
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        create_live_dir(parent)?;
     }
     let json =
         serde_json::to_string_pretty(value).map_err(|e| AppError::JsonSerialize { source: e })?;
@@ -163,7 +174,7 @@ fn write_json_value(path: &Path, value: &Value) -> Result<(), AppError> {
 
 pub fn get_mcp_status() -> Result<McpStatus, AppError> {
     let path = user_config_path();
-    let (exists, count) = if path.exists() {
+    let (exists, count) = if live_path_exists(&path) {
         let v = read_json_value(&path)?;
         let servers = v.get("mcpServers").and_then(|x| x.as_object());
         (true, servers.map(|m| m.len()).unwrap_or(0))
@@ -180,10 +191,10 @@ pub fn get_mcp_status() -> Result<McpStatus, AppError> {
 
 pub fn read_mcp_json() -> Result<Option<String>, AppError> {
     let path = user_config_path();
-    if !path.exists() {
+    if !live_path_exists(&path) {
         return Ok(None);
     }
-    let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let content = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
     Ok(Some(content))
 }
 
@@ -191,7 +202,7 @@ pub fn read_mcp_json() -> Result<Option<String>, AppError> {
 /// 仅增量写入该字段，其他字段保持不变
 pub fn set_has_completed_onboarding() -> Result<bool, AppError> {
     let path = user_config_path();
-    let mut root = if path.exists() {
+    let mut root = if live_path_exists(&path) {
         read_json_value(&path)?
     } else {
         serde_json::json!({})
@@ -218,7 +229,7 @@ pub fn set_has_completed_onboarding() -> Result<bool, AppError> {
 /// 仅增量删除该字段，其他字段保持不变
 pub fn clear_has_completed_onboarding() -> Result<bool, AppError> {
     let path = user_config_path();
-    if !path.exists() {
+    if !live_path_exists(&path) {
         return Ok(false);
     }
 
@@ -279,7 +290,7 @@ pub fn upsert_mcp_server(id: &str, spec: Value) -> Result<bool, AppError> {
     }
 
     let path = user_config_path();
-    let mut root = if path.exists() {
+    let mut root = if live_path_exists(&path) {
         read_json_value(&path)?
     } else {
         serde_json::json!({})
@@ -300,7 +311,7 @@ pub fn upsert_mcp_server(id: &str, spec: Value) -> Result<bool, AppError> {
         servers.insert(id.to_string(), spec);
     }
 
-    if before == root && path.exists() {
+    if before == root && live_path_exists(&path) {
         return Ok(false);
     }
 
@@ -313,7 +324,7 @@ pub fn delete_mcp_server(id: &str) -> Result<bool, AppError> {
         return Err(AppError::InvalidInput("MCP 服务器 ID 不能为空".into()));
     }
     let path = user_config_path();
-    if !path.exists() {
+    if !live_path_exists(&path) {
         return Ok(false);
     }
     let mut root = read_json_value(&path)?;
@@ -368,7 +379,7 @@ pub fn validate_command_in_path(cmd: &str) -> Result<bool, AppError> {
 /// 读取 ~/.claude.json 中的 mcpServers 映射
 pub fn read_mcp_servers_map() -> Result<std::collections::HashMap<String, Value>, AppError> {
     let path = user_config_path();
-    if !path.exists() {
+    if !live_path_exists(&path) {
         return Ok(std::collections::HashMap::new());
     }
 
@@ -388,7 +399,7 @@ pub fn set_mcp_servers_map(
     servers: &std::collections::HashMap<String, Value>,
 ) -> Result<(), AppError> {
     let path = user_config_path();
-    let mut root = if path.exists() {
+    let mut root = if live_path_exists(&path) {
         read_json_value(&path)?
     } else {
         serde_json::json!({})
@@ -2,8 +2,8 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use crate::config::{
-    atomic_write, delete_file, get_home_dir, read_json_file, sanitize_provider_name,
-    write_json_file, write_text_file,
+    atomic_write, create_live_dir, delete_file, get_home_dir, live_path_exists, read_json_file,
+    read_live_bytes, read_live_to_string, sanitize_provider_name, write_json_file, write_text_file,
 };
 use crate::error::AppError;
 use serde_json::{json, Value};
@@ -89,17 +89,17 @@ pub fn write_codex_live_atomic(
     let config_path = get_codex_config_path();
 
     if let Some(parent) = auth_path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        create_live_dir(parent)?;
     }
 
     // 读取旧内容用于回滚
-    let old_auth = if auth_path.exists() {
-        Some(fs::read(&auth_path).map_err(|e| AppError::io(&auth_path, e))?)
+    let old_auth = if live_path_exists(&auth_path) {
+        Some(read_live_bytes(&auth_path).map_err(|e| AppError::io(&auth_path, e))?)
     } else {
         None
     };
-    let _old_config = if config_path.exists() {
-        Some(fs::read(&config_path).map_err(|e| AppError::io(&config_path, e))?)
+    let _old_config = if live_path_exists(&config_path) {
+        Some(read_live_bytes(&config_path).map_err(|e| AppError::io(&config_path, e))?)
     } else {
         None
     };
@@ -133,8 +133,8 @@ pub fn write_codex_live_atomic(
 /// 读取 `~/.codex/config.toml`，若不存在返回空字符串
 pub fn read_codex_config_text() -> Result<String, AppError> {
     let path = get_codex_config_path();
-    if path.exists() {
-        std::fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))
+    if live_path_exists(&path) {
+        read_live_to_string(&path).map_err(|e| AppError::io(&path, e))
     } else {
         Ok(String::new())
     }
@@ -411,11 +411,11 @@ fn find_codex_model_template(catalog: &Value) -> Option<Value> {
 
 fn load_codex_model_template_from_cache() -> Result<Option<Value>, AppError> {
     let path = get_codex_config_dir().join("models_cache.json");
-    if !path.exists() {
+    if !live_path_exists(&path) {
         return Ok(None);
     }
 
-    let text = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let text = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
     let catalog: Value = serde_json::from_str(&text).map_err(|e| AppError::json(&path, e))?;
     Ok(find_codex_model_template(&catalog))
 }
@@ -754,10 +754,10 @@ pub fn read_codex_model_catalog_simplified_from_live() -> Result<Option<Value>,
     let Some(catalog_path) = resolve_cc_switch_catalog_path(&config_text, &generated_path) else {
         return Ok(None);
     };
-    if !catalog_path.exists() {
+    if !live_path_exists(&catalog_path) {
         return Ok(None);
     }
-    let Ok(catalog_text) = fs::read_to_string(&catalog_path) else {
+    let Ok(catalog_text) = read_live_to_string(&catalog_path) else {
         return Ok(None);
     };
     Ok(build_simplified_catalog_from_texts(
@@ -1026,7 +1026,7 @@ fn remove_codex_experimental_bearer_token(config_text: &str) -> Result<String, A
 /// is still importable; both files empty is treated as "no live install".
 pub fn read_codex_live_settings() -> Result<Value, AppError> {
     let auth_path = get_codex_auth_path();
-    let auth_present = auth_path.exists();
+    let auth_present = live_path_exists(&auth_path);
     let auth: Value = if auth_present {
         read_json_file(&auth_path)?
     } else {
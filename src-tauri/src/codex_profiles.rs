@@ -0,0 +1,1101 @@
+//! Codex `config.toml` 运行策略的结构化读写
+//!
+//! 建模顶层的 `profile` / `model_reasoning_effort` / `sandbox_mode` / `approval_policy`、
+//! `[shell_environment_policy]` 与 `[profiles.*]`。写入基于 `toml_edit` 原地修改，
+//! 只触碰这里建模的键；其余键、注释与格式原样保留，取值未变化的行不会被重写。
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, TableLike, Value};
+
+use crate::codex_config::{
+    get_codex_config_path, is_custom_codex_model_provider_id, read_and_validate_codex_config_text,
+};
+use crate::config::write_text_file;
+use crate::error::AppError;
+use crate::services::live_history::LiveWriteScope;
+
+/// profile 名称的最大长度
+const MAX_PROFILE_NAME_LEN: usize = 64;
+
+/// 以字符串形式写入 config.toml 的枚举
+///
+/// 每个枚举都带有 `Other(String)`：Codex 新增而这里尚未建模的取值原样保留，
+/// 读取时不报错，写回时不丢失；但只能保留文件中已有的取值，不能由前端新写入。
+trait TomlEnum: Clone + PartialEq + 'static {
+    /// 已知取值及其在 TOML 中的写法
+    const VARIANTS: &'static [(Self, &'static str)];
+
+    fn other(raw: String) -> Self;
+
+    fn as_other(&self) -> Option<&str>;
+
+    fn toml_str(&self) -> &str {
+        Self::VARIANTS
+            .iter()
+            .find(|(variant, _)| variant == self)
+            .map(|(_, raw)| *raw)
+            .or_else(|| self.as_other())
+            .unwrap_or_default()
+    }
+
+    fn parse(raw: &str) -> Self {
+        Self::VARIANTS
+            .iter()
+            .find(|(_, name)| *name == raw)
+            .map(|(variant, _)| variant.clone())
+            .unwrap_or_else(|| Self::other(raw.to_string()))
+    }
+}
+
+/// 定义 [`TomlEnum`]：已知取值与 TOML 写法一一对应，并追加 `Other(String)` 兜底变体
+macro_rules! toml_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($variant:ident => $raw:tt),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+        pub enum $name {
+            $(
+                #[serde(rename = $raw)]
+                $variant,
+            )+
+            /// 未识别的取值（只读保留）
+            #[serde(untagged)]
+            Other(String),
+        }
+
+        impl TomlEnum for $name {
+            const VARIANTS: &'static [(Self, &'static str)] = &[$((Self::$variant, $raw)),+];
+
+            fn other(raw: String) -> Self {
+                Self::Other(raw)
+            }
+
+            fn as_other(&self) -> Option<&str> {
+                match self {
+                    Self::Other(raw) => Some(raw.as_str()),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+toml_enum! {
+    /// 推理强度（`model_reasoning_effort`）
+    pub enum CodexReasoningEffort {
+        None => "none",
+        Minimal => "minimal",
+        Low => "low",
+        Medium => "medium",
+        High => "high",
+        Xhigh => "xhigh",
+    }
+}
+
+toml_enum! {
+    /// 沙箱模式（`sandbox_mode`）
+    pub enum CodexSandboxMode {
+        ReadOnly => "read-only",
+        WorkspaceWrite => "workspace-write",
+        DangerFullAccess => "danger-full-access",
+    }
+}
+
+toml_enum! {
+    /// 命令审批策略（`approval_policy`）
+    pub enum CodexApprovalPolicy {
+        Untrusted => "untrusted",
+        OnFailure => "on-failure",
+        OnRequest => "on-request",
+        Never => "never",
+    }
+}
+
+toml_enum! {
+    /// 子进程环境变量的继承范围（`shell_environment_policy.inherit`）
+    pub enum CodexShellEnvInherit {
+        All => "all",
+        Core => "core",
+        None => "none",
+    }
+}
+
+/// 配置中未识别的枚举取值，供界面提示
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexUnknownValue {
+    /// 键路径，如 `profiles.fast.sandbox_mode`
+    pub path: String,
+    pub value: String,
+}
+
+fn unsupported_value(path: &str, raw: &str) -> AppError {
+    AppError::localized(
+        "codex.config.unsupported_value",
+        format!("不支持的 {path} 取值: {raw}"),
+        format!("Unsupported value for {path}: {raw}"),
+    )
+}
+
+/// 运行策略：可出现在顶层，也可出现在 `[profiles.<name>]` 内
+///
+/// 同时用作供应商级默认策略（`ProviderMeta.codexDefaults`）。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexPolicySettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_reasoning_effort: Option<CodexReasoningEffort>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_mode: Option<CodexSandboxMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_policy: Option<CodexApprovalPolicy>,
+}
+
+impl CodexPolicySettings {
+    pub fn is_empty(&self) -> bool {
+        self.entries().iter().all(|(_, raw)| raw.is_none())
+    }
+
+    fn entries(&self) -> [(&'static str, Option<&str>); 3] {
+        [
+            (
+                "model_reasoning_effort",
+                self.model_reasoning_effort.as_ref().map(TomlEnum::toml_str),
+            ),
+            (
+                "sandbox_mode",
+                self.sandbox_mode.as_ref().map(TomlEnum::toml_str),
+            ),
+            (
+                "approval_policy",
+                self.approval_policy.as_ref().map(TomlEnum::toml_str),
+            ),
+        ]
+    }
+
+    fn unknown_values(&self, prefix: &str) -> impl Iterator<Item = CodexUnknownValue> + '_ {
+        let prefix = prefix.to_string();
+        [
+            (
+                "model_reasoning_effort",
+                self.model_reasoning_effort
+                    .as_ref()
+                    .and_then(TomlEnum::as_other),
+            ),
+            (
+                "sandbox_mode",
+                self.sandbox_mode.as_ref().and_then(TomlEnum::as_other),
+            ),
+            (
+                "approval_policy",
+                self.approval_policy.as_ref().and_then(TomlEnum::as_other),
+            ),
+        ]
+        .into_iter()
+        .filter_map(move |(key, raw)| {
+            Some(CodexUnknownValue {
+                path: format!("{prefix}{key}"),
+                value: raw?.to_string(),
+            })
+        })
+    }
+
+    /// 拒绝未识别的取值（供应商默认策略完全由前端提交，不存在需要保留的原值）
+    pub fn ensure_known(&self) -> Result<(), AppError> {
+        match self.unknown_values("").next() {
+            Some(unknown) => Err(unsupported_value(&unknown.path, &unknown.value)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 单个 `[profiles.<name>]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
+    #[serde(flatten)]
+    pub policy: CodexPolicySettings,
+}
+
+/// `[shell_environment_policy]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexShellEnvironmentPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inherit: Option<CodexShellEnvInherit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_default_excludes: Option<bool>,
+    /// 需要剔除的变量名 glob
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// 非空时只保留匹配的变量名 glob
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_only: Vec<String>,
+    /// 额外注入的环境变量
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub set: IndexMap<String, String>,
+}
+
+/// config.toml 中由结构化编辑器管理的部分
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexConfigSettings {
+    /// 默认启用的 profile（顶层 `profile`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// 顶层运行策略
+    #[serde(flatten)]
+    pub policy: CodexPolicySettings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell_environment_policy: Option<CodexShellEnvironmentPolicy>,
+    #[serde(default)]
+    pub profiles: IndexMap<String, CodexProfile>,
+    /// 读取时发现的未识别取值（只读，写入时忽略）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unknown_values: Vec<CodexUnknownValue>,
+}
+
+impl CodexConfigSettings {
+    fn collect_unknown_values(&self) -> Vec<CodexUnknownValue> {
+        let mut unknown: Vec<CodexUnknownValue> = self.policy.unknown_values("").collect();
+        if let Some(raw) = self
+            .shell_environment_policy
+            .as_ref()
+            .and_then(|policy| policy.inherit.as_ref())
+            .and_then(TomlEnum::as_other)
+        {
+            unknown.push(CodexUnknownValue {
+                path: "shell_environment_policy.inherit".to_string(),
+                value: raw.to_string(),
+            });
+        }
+        for (name, profile) in &self.profiles {
+            unknown.extend(profile.policy.unknown_values(&format!("profiles.{name}.")));
+        }
+        unknown
+    }
+}
+
+// ============================================================================
+// 解析
+// ============================================================================
+
+fn parse_document(text: &str) -> Result<DocumentMut, AppError> {
+    if text.trim().is_empty() {
+        return Ok(DocumentMut::new());
+    }
+    text.parse::<DocumentMut>()
+        .map_err(|e| AppError::Config(format!("解析 Codex config.toml 失败: {e}")))
+}
+
+fn invalid_value(path: &str, raw: &str) -> AppError {
+    AppError::localized(
+        "codex.config.invalid_value",
+        format!("config.toml 中 {path} 的取值无效: {raw}"),
+        format!("Invalid value for {path} in config.toml: {raw}"),
+    )
+}
+
+fn item_text(item: &Item) -> String {
+    item.to_string().trim().to_string()
+}
+
+fn read_string(table: &dyn TableLike, prefix: &str, key: &str) -> Result<Option<String>, AppError> {
+    let Some(item) = table.get(key) else {
+        return Ok(None);
+    };
+    let raw = item
+        .as_str()
+        .ok_or_else(|| invalid_value(&format!("{prefix}{key}"), &item_text(item)))?;
+    let trimmed = raw.trim();
+    Ok((!trimmed.is_empty()).then(|| trimmed.to_string()))
+}
+
+/// 非字符串取值视为无效；未识别的字符串保留为 `Other` 并记录警告
+fn read_enum<T: TomlEnum>(
+    table: &dyn TableLike,
+    prefix: &str,
+    key: &str,
+) -> Result<Option<T>, AppError> {
+    let Some(item) = table.get(key) else {
+        return Ok(None);
+    };
+    let raw = item
+        .as_str()
+        .ok_or_else(|| invalid_value(&format!("{prefix}{key}"), &item_text(item)))?
+        .trim();
+    let value = T::parse(raw);
+    if value.as_other().is_some() {
+        log::warn!("config.toml 中 {prefix}{key} 的取值未识别，将原样保留: {raw}");
+    }
+    Ok(Some(value))
+}
+
+fn read_string_array(
+    table: &dyn TableLike,
+    prefix: &str,
+    key: &str,
+) -> Result<Vec<String>, AppError> {
+    let Some(item) = table.get(key) else {
+        return Ok(Vec::new());
+    };
+    let invalid = || invalid_value(&format!("{prefix}{key}"), &item_text(item));
+    item.as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|value| value.as_str().map(str::to_string).ok_or_else(invalid))
+        .collect()
+}
+
+fn read_policy(table: &dyn TableLike, prefix: &str) -> Result<CodexPolicySettings, AppError> {
+    Ok(CodexPolicySettings {
+        model_reasoning_effort: read_enum(table, prefix, "model_reasoning_effort")?,
+        sandbox_mode: read_enum(table, prefix, "sandbox_mode")?,
+        approval_policy: read_enum(table, prefix, "approval_policy")?,
+    })
+}
+
+fn read_profile(table: &dyn TableLike, prefix: &str) -> Result<CodexProfile, AppError> {
+    Ok(CodexProfile {
+        model: read_string(table, prefix, "model")?,
+        model_provider: read_string(table, prefix, "model_provider")?,
+        policy: read_policy(table, prefix)?,
+    })
+}
+
+fn read_shell_environment_policy(
+    table: &dyn TableLike,
+) -> Result<CodexShellEnvironmentPolicy, AppError> {
+    let prefix = "shell_environment_policy.";
+    let ignore_default_excludes = match table.get("ignore_default_excludes") {
+        Some(item) => Some(item.as_bool().ok_or_else(|| {
+            invalid_value(
+                "shell_environment_policy.ignore_default_excludes",
+                &item_text(item),
+            )
+        })?),
+        None => None,
+    };
+
+    let mut set = IndexMap::new();
+    if let Some(item) = table.get("set") {
+        let entries = item
+            .as_table_like()
+            .ok_or_else(|| invalid_value("shell_environment_policy.set", &item_text(item)))?;
+        for (name, value) in entries.iter() {
+            let value = value.as_str().ok_or_else(|| {
+                invalid_value(
+                    &format!("shell_environment_policy.set.{name}"),
+                    &item_text(value),
+                )
+            })?;
+            set.insert(name.to_string(), value.to_string());
+        }
+    }
+
+    Ok(CodexShellEnvironmentPolicy {
+        inherit: read_enum(table, prefix, "inherit")?,
+        ignore_default_excludes,
+        exclude: read_string_array(table, prefix, "exclude")?,
+        include_only: read_string_array(table, prefix, "include_only")?,
+        set,
+    })
+}
+
+fn read_settings(doc: &DocumentMut) -> Result<CodexConfigSettings, AppError> {
+    let root = doc.as_table();
+
+    let shell_environment_policy = match root.get("shell_environment_policy") {
+        Some(item) => Some(read_shell_environment_policy(
+            item.as_table_like()
+                .ok_or_else(|| invalid_value("shell_environment_policy", &item_text(item)))?,
+        )?),
+        None => None,
+    };
+
+    let mut profiles = IndexMap::new();
+    if let Some(item) = root.get("profiles") {
+        let table = item
+            .as_table_like()
+            .ok_or_else(|| invalid_value("profiles", &item_text(item)))?;
+        for (name, item) in table.iter() {
+            let prefix = format!("profiles.{name}.");
+            let profile_table = item
+                .as_table_like()
+                .ok_or_else(|| invalid_value(&format!("profiles.{name}"), &item_text(item)))?;
+            profiles.insert(name.to_string(), read_profile(profile_table, &prefix)?);
+        }
+    }
+
+    let mut settings = CodexConfigSettings {
+        profile: read_string(root, "", "profile")?,
+        policy: read_policy(root, "")?,
+        shell_environment_policy,
+        profiles,
+        unknown_values: Vec::new(),
+    };
+    settings.unknown_values = settings.collect_unknown_values();
+    Ok(settings)
+}
+
+/// 从 config.toml 文本解析结构化设置
+pub fn parse_codex_config_settings(text: &str) -> Result<CodexConfigSettings, AppError> {
+    read_settings(&parse_document(text)?)
+}
+
+// ============================================================================
+// 校验
+// ============================================================================
+
+fn validate_profile_name(name: &str) -> Result<(), AppError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_PROFILE_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::localized(
+            "codex.profile.invalid_name",
+            format!("profile 名称无效: {name}（仅允许字母、数字、- 与 _，最长 {MAX_PROFILE_NAME_LEN} 个字符）"),
+            format!("Invalid profile name: {name} (letters, digits, - and _ only, up to {MAX_PROFILE_NAME_LEN} characters)"),
+        ))
+    }
+}
+
+fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn defined_model_providers(doc: &DocumentMut) -> Vec<String> {
+    doc.get("model_providers")
+        .and_then(Item::as_table_like)
+        .map(|table| table.iter().map(|(id, _)| id.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// 校验设置的语义：profile 名称、引用关系与环境变量策略
+fn validate_settings(settings: &CodexConfigSettings, doc: &DocumentMut) -> Result<(), AppError> {
+    // 未识别的取值只能原样保留，不能新写入或挪到别的键
+    let existing = read_settings(doc)
+        .map(|current| current.unknown_values)
+        .unwrap_or_default();
+    if let Some(unknown) = settings
+        .collect_unknown_values()
+        .into_iter()
+        .find(|unknown| !existing.contains(unknown))
+    {
+        return Err(unsupported_value(&unknown.path, &unknown.value));
+    }
+
+    let model_providers = defined_model_providers(doc);
+
+    for (name, profile) in &settings.profiles {
+        validate_profile_name(name)?;
+
+        // 内置供应商无需定义；自定义供应商必须存在于 [model_providers]
+        if let Some(provider_id) = profile.model_provider.as_deref() {
+            if is_custom_codex_model_provider_id(provider_id)
+                && !model_providers.iter().any(|id| id == provider_id)
+            {
+                return Err(AppError::localized(
+                    "codex.profile.unknown_model_provider",
+                    format!("profile {name} 引用的 model_provider 未在 [model_providers] 中定义: {provider_id}"),
+                    format!("Profile {name} references model_provider {provider_id}, which is not defined in [model_providers]"),
+                ));
+            }
+        }
+    }
+
+    if let Some(active) = settings.profile.as_deref() {
+        if !settings.profiles.contains_key(active) {
+            return Err(AppError::localized(
+                "codex.profile.active_missing",
+                format!("默认 profile 不存在: {active}"),
+                format!("Default profile does not exist: {active}"),
+            ));
+        }
+    }
+
+    if let Some(policy) = &settings.shell_environment_policy {
+        if let Some(name) = policy.set.keys().find(|name| !is_valid_env_name(name)) {
+            return Err(AppError::localized(
+                "codex.shell_env.invalid_name",
+                format!("环境变量名无效: {name}"),
+                format!("Invalid environment variable name: {name}"),
+            ));
+        }
+        let blank_pattern = policy
+            .exclude
+            .iter()
+            .chain(&policy.include_only)
+            .any(|pattern| pattern.trim().is_empty());
+        if blank_pattern {
+            return Err(AppError::localized(
+                "codex.shell_env.empty_pattern",
+                "环境变量匹配规则不能为空",
+                "Environment variable patterns must not be empty",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// 去掉首尾空白，并把空字符串视为未设置
+fn normalize_settings(settings: &CodexConfigSettings) -> CodexConfigSettings {
+    fn clean(value: &Option<String>) -> Option<String> {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    }
+
+    let mut normalized = settings.clone();
+    normalized.profile = clean(&settings.profile);
+    normalized.profiles = settings
+        .profiles
+        .iter()
+        .map(|(name, profile)| {
+            let profile = CodexProfile {
+                model: clean(&profile.model),
+                model_provider: clean(&profile.model_provider),
+                policy: profile.policy.clone(),
+            };
+            (name.trim().to_string(), profile)
+        })
+        .collect();
+    if let Some(policy) = normalized.shell_environment_policy.as_mut() {
+        policy.set = policy
+            .set
+            .iter()
+            .map(|(name, value)| (name.trim().to_string(), value.clone()))
+            .collect();
+    }
+    normalized
+}
+
+// ============================================================================
+// 写入
+// ============================================================================
+
+/// 写入或移除单个值；取值未变化时保持原行不动，变化时沿用原行的注释与空白
+fn set_value(table: &mut dyn TableLike, key: &str, new_value: Option<Value>) {
+    let Some(mut new_value) = new_value else {
+        table.remove(key);
+        return;
+    };
+    match table.get_mut(key).and_then(Item::as_value_mut) {
+        Some(existing) => {
+            let unchanged = existing.clone().decorated("", "").to_string()
+                == new_value.clone().decorated("", "").to_string();
+            if !unchanged {
+                *new_value.decor_mut() = existing.decor().clone();
+                *existing = new_value;
+            }
+        }
+        None => {
+            table.insert(key, Item::Value(new_value));
+        }
+    }
+}
+
+fn string_value(raw: Option<&str>) -> Option<Value> {
+    raw.map(Value::from)
+}
+
+fn string_array_value(items: &[String]) -> Option<Value> {
+    (!items.is_empty()).then(|| Value::Array(items.iter().map(String::as_str).collect::<Array>()))
+}
+
+fn write_policy(table: &mut dyn TableLike, policy: &CodexPolicySettings) {
+    for (key, raw) in policy.entries() {
+        set_value(table, key, string_value(raw));
+    }
+}
+
+fn write_profile(table: &mut dyn TableLike, profile: &CodexProfile) {
+    set_value(table, "model", string_value(profile.model.as_deref()));
+    set_value(
+        table,
+        "model_provider",
+        string_value(profile.model_provider.as_deref()),
+    );
+    write_policy(table, &profile.policy);
+}
+
+/// `set` 已存在时逐项更新（保留其表/内联表形式），否则写为内联表
+fn write_env_set(table: &mut dyn TableLike, set: &IndexMap<String, String>) {
+    if set.is_empty() {
+        table.remove("set");
+        return;
+    }
+    if let Some(existing) = table.get_mut("set").and_then(Item::as_table_like_mut) {
+        let stale: Vec<String> = existing
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .filter(|name| !set.contains_key(name))
+            .collect();
+        for name in stale {
+            existing.remove(&name);
+        }
+        for (name, value) in set {
+            set_value(existing, name, Some(Value::from(value.as_str())));
+        }
+        return;
+    }
+    let mut inline = InlineTable::new();
+    for (name, value) in set {
+        inline.insert(name.as_str(), Value::from(value.as_str()));
+    }
+    table.insert("set", Item::Value(Value::InlineTable(inline)));
+}
+
+fn write_shell_environment_policy(root: &mut Table, policy: Option<&CodexShellEnvironmentPolicy>) {
+    let Some(policy) = policy else {
+        root.remove("shell_environment_policy");
+        return;
+    };
+    let table = root
+        .entry("shell_environment_policy")
+        .or_insert_with(toml_edit::table);
+    let Some(table) = table.as_table_like_mut() else {
+        return;
+    };
+    set_value(
+        table,
+        "inherit",
+        string_value(policy.inherit.as_ref().map(TomlEnum::toml_str)),
+    );
+    set_value(
+        table,
+        "ignore_default_excludes",
+        policy.ignore_default_excludes.map(Value::from),
+    );
+    set_value(table, "exclude", string_array_value(&policy.exclude));
+    set_value(
+        table,
+        "include_only",
+        string_array_value(&policy.include_only),
+    );
+    write_env_set(table, &policy.set);
+}
+
+fn write_profiles(root: &mut Table, profiles: &IndexMap<String, CodexProfile>) {
+    if profiles.is_empty() {
+        root.remove("profiles");
+        return;
+    }
+    let item = root.entry("profiles").or_insert_with(|| {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    let Some(table) = item.as_table_like_mut() else {
+        return;
+    };
+
+    let stale: Vec<String> = table
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .filter(|name| !profiles.contains_key(name))
+        .collect();
+    for name in stale {
+        table.remove(&name);
+    }
+
+    for (name, profile) in profiles {
+        if table.get(name).and_then(Item::as_table_like).is_none() {
+            table.insert(name, toml_edit::table());
+        }
+        if let Some(profile_table) = table.get_mut(name).and_then(Item::as_table_like_mut) {
+            write_profile(profile_table, profile);
+        }
+    }
+}
+
+/// 将结构化设置写回 config.toml 文本
+///
+/// 设置中缺失的 profile 会被删除；保留下来的 profile 原地更新，未建模的键不受影响。
+pub fn apply_codex_config_settings(
+    text: &str,
+    settings: &CodexConfigSettings,
+) -> Result<String, AppError> {
+    let mut doc = parse_document(text)?;
+    let settings = normalize_settings(settings);
+    validate_settings(&settings, &doc)?;
+
+    let root = doc.as_table_mut();
+    set_value(root, "profile", string_value(settings.profile.as_deref()));
+    write_policy(root, &settings.policy);
+    write_shell_environment_policy(root, settings.shell_environment_policy.as_ref());
+    write_profiles(root, &settings.profiles);
+
+    Ok(doc.to_string())
+}
+
+fn profile_not_found(name: &str) -> AppError {
+    AppError::localized(
+        "codex.profile.not_found",
+        format!("profile 不存在: {name}"),
+        format!("Profile not found: {name}"),
+    )
+}
+
+/// 新增或更新单个 profile
+pub fn upsert_profile_in_text(
+    text: &str,
+    name: &str,
+    profile: &CodexProfile,
+) -> Result<String, AppError> {
+    let mut settings = parse_codex_config_settings(text)?;
+    settings
+        .profiles
+        .insert(name.trim().to_string(), profile.clone());
+    apply_codex_config_settings(text, &settings)
+}
+
+/// 删除单个 profile；若它是默认 profile，同时清除顶层 `profile`
+pub fn delete_profile_in_text(text: &str, name: &str) -> Result<String, AppError> {
+    let mut settings = parse_codex_config_settings(text)?;
+    if settings.profiles.shift_remove(name).is_none() {
+        return Err(profile_not_found(name));
+    }
+    if settings.profile.as_deref() == Some(name) {
+        settings.profile = None;
+    }
+    apply_codex_config_settings(text, &settings)
+}
+
+/// 重命名 profile：整表搬移（含未建模的键），并同步顶层 `profile`
+pub fn rename_profile_in_text(
+    text: &str,
+    old_name: &str,
+    new_name: &str,
+) -> Result<String, AppError> {
+    let new_name = new_name.trim();
+    validate_profile_name(new_name)?;
+    if old_name == new_name {
+        return Ok(text.to_string());
+    }
+
+    let mut doc = parse_document(text)?;
+    let profiles = doc
+        .get_mut("profiles")
+        .and_then(Item::as_table_like_mut)
+        .ok_or_else(|| profile_not_found(old_name))?;
+    if profiles.contains_key(new_name) {
+        return Err(AppError::localized(
+            "codex.profile.already_exists",
+            format!("profile 已存在: {new_name}"),
+            format!("Profile already exists: {new_name}"),
+        ));
+    }
+    let item = profiles
+        .remove(old_name)
+        .ok_or_else(|| profile_not_found(old_name))?;
+    profiles.insert(new_name, item);
+
+    if doc.get("profile").and_then(Item::as_str) == Some(old_name) {
+        set_value(doc.as_table_mut(), "profile", Some(Value::from(new_name)));
+    }
+
+    Ok(doc.to_string())
+}
+
+// ============================================================================
+// 供应商级默认策略
+// ============================================================================
+
+/// 将供应商默认策略补入 config 文本：只填充未显式设置的顶层键
+pub fn apply_policy_defaults(
+    config_text: &str,
+    defaults: &CodexPolicySettings,
+) -> Result<String, AppError> {
+    if defaults.is_empty() {
+        return Ok(config_text.to_string());
+    }
+    let mut doc = parse_document(config_text)?;
+    let root = doc.as_table_mut();
+    for (key, raw) in defaults.entries() {
+        if let Some(raw) = raw {
+            if !root.contains_key(key) {
+                root.insert(key, toml_edit::value(raw));
+            }
+        }
+    }
+    Ok(doc.to_string())
+}
+
+/// 回填前移除与默认策略取值相同的顶层键，避免默认值固化进供应商配置
+pub fn strip_policy_defaults(
+    config_text: &str,
+    defaults: &CodexPolicySettings,
+) -> Result<String, AppError> {
+    if defaults.is_empty() {
+        return Ok(config_text.to_string());
+    }
+    let mut doc = parse_document(config_text)?;
+    let root = doc.as_table_mut();
+    for (key, raw) in defaults.entries() {
+        if raw.is_some() && root.get(key).and_then(Item::as_str) == raw {
+            root.remove(key);
+        }
+    }
+    Ok(doc.to_string())
+}
+
+// ============================================================================
+// Live 配置读写
+// ============================================================================
+
+/// 读取 `~/.codex/config.toml` 的结构化设置
+pub fn read_codex_config_settings() -> Result<CodexConfigSettings, AppError> {
+    parse_codex_config_settings(&read_and_validate_codex_config_text()?)
+}
+
+fn update_live_config(
+    update: impl FnOnce(&str) -> Result<String, AppError>,
+) -> Result<CodexConfigSettings, AppError> {
+    let _scope = LiveWriteScope::enter("codexConfig");
+    let text = read_and_validate_codex_config_text()?;
+    let updated = update(&text)?;
+    if updated != text {
+        write_text_file(&get_codex_config_path(), &updated)?;
+    }
+    parse_codex_config_settings(&updated)
+}
+
+/// 保存顶层策略、环境变量策略与全部 profile
+pub fn save_codex_config_settings(
+    settings: &CodexConfigSettings,
+) -> Result<CodexConfigSettings, AppError> {
+    update_live_config(|text| apply_codex_config_settings(text, settings))
+}
+
+pub fn upsert_codex_profile(
+    name: &str,
+    profile: &CodexProfile,
+) -> Result<CodexConfigSettings, AppError> {
+    update_live_config(|text| upsert_profile_in_text(text, name, profile))
+}
+
+pub fn delete_codex_profile(name: &str) -> Result<CodexConfigSettings, AppError> {
+    update_live_config(|text| delete_profile_in_text(text, name))
+}
+
+pub fn rename_codex_profile(
+    old_name: &str,
+    new_name: &str,
+) -> Result<CodexConfigSettings, AppError> {
+    update_live_config(|text| rename_profile_in_text(text, old_name, new_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"# managed by hand
+model_provider = "custom"
+model = "gpt-5"
+approval_policy = "on-request" # keep asking
+unknown_top = 1
+
+[model_providers.custom]
+name = "custom"
+base_url = "https://api.example.com/v1"
+
+[shell_environment_policy]
+inherit = "core"
+exclude = ["AWS_*"]
+
+[shell_environment_policy.set]
+CI = "1"
+
+# fast profile for quick edits
+[profiles.fast]
+model = "gpt-5-mini"
+model_reasoning_effort = "low"
+model_verbosity = "low"
+"#;
+
+    #[test]
+    fn parses_typed_sections() {
+        let settings = parse_codex_config_settings(SAMPLE).expect("parse");
+        assert_eq!(
+            settings.policy.approval_policy,
+            Some(CodexApprovalPolicy::OnRequest)
+        );
+        let shell = settings.shell_environment_policy.expect("shell policy");
+        assert_eq!(shell.inherit, Some(CodexShellEnvInherit::Core));
+        assert_eq!(shell.exclude, vec!["AWS_*".to_string()]);
+        assert_eq!(shell.set.get("CI").map(String::as_str), Some("1"));
+        let fast = settings.profiles.get("fast").expect("fast profile");
+        assert_eq!(fast.model.as_deref(), Some("gpt-5-mini"));
+        assert_eq!(
+            fast.policy.model_reasoning_effort,
+            Some(CodexReasoningEffort::Low)
+        );
+    }
+
+    #[test]
+    fn unknown_enum_values_round_trip() {
+        let text = "sandbox_mode = \"yolo\"\nmodel = \"gpt-5\"\n";
+        let settings = parse_codex_config_settings(text).expect("parse");
+        assert_eq!(
+            settings.policy.sandbox_mode,
+            Some(CodexSandboxMode::Other("yolo".to_string()))
+        );
+        assert_eq!(
+            apply_codex_config_settings(text, &settings).expect("apply"),
+            text
+        );
+
+        assert_eq!(
+            settings.unknown_values,
+            vec![CodexUnknownValue {
+                path: "sandbox_mode".to_string(),
+                value: "yolo".to_string(),
+            }]
+        );
+
+        let json = serde_json::to_value(&settings).unwrap();
+        assert_eq!(json["sandboxMode"], "yolo");
+        let back: CodexConfigSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(back, settings);
+
+        // 前端不能写入新的未识别取值，也不能把已有的挪到别的键
+        let mut typo = settings.clone();
+        typo.policy.approval_policy = Some(CodexApprovalPolicy::Other("on-reqest".to_string()));
+        let err = apply_codex_config_settings(text, &typo).unwrap_err();
+        assert!(err.to_string().contains("approval_policy"), "{err}");
+        let moved = CodexProfile {
+            policy: CodexPolicySettings {
+                sandbox_mode: Some(CodexSandboxMode::Other("yolo".to_string())),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(upsert_profile_in_text(text, "fast", &moved).is_err());
+        assert!(moved.policy.ensure_known().is_err());
+
+        let err = parse_codex_config_settings("sandbox_mode = 1\n").unwrap_err();
+        assert!(err.to_string().contains("sandbox_mode"), "{err}");
+    }
+
+    #[test]
+    fn apply_preserves_comments_and_unknown_keys() {
+        let mut settings = parse_codex_config_settings(SAMPLE).expect("parse");
+        settings.policy.sandbox_mode = Some(CodexSandboxMode::WorkspaceWrite);
+        settings
+            .profiles
+            .get_mut("fast")
+            .unwrap()
+            .policy
+            .model_reasoning_effort = Some(CodexReasoningEffort::Minimal);
+
+        let updated = apply_codex_config_settings(SAMPLE, &settings).expect("apply");
+        assert!(updated.contains("# managed by hand"));
+        assert!(updated.contains("approval_policy = \"on-request\" # keep asking"));
+        assert!(updated.contains("unknown_top = 1"));
+        assert!(updated.contains("# fast profile for quick edits"));
+        assert!(updated.contains("model_verbosity = \"low\""));
+        assert!(updated.contains("sandbox_mode = \"workspace-write\""));
+        assert!(updated.contains("model_reasoning_effort = \"minimal\""));
+        assert!(updated.contains("[shell_environment_policy.set]"));
+
+        let reparsed = parse_codex_config_settings(&updated).expect("reparse");
+        assert_eq!(reparsed, settings);
+    }
+
+    #[test]
+    fn apply_without_changes_is_identity() {
+        let settings = parse_codex_config_settings(SAMPLE).expect("parse");
+        let updated = apply_codex_config_settings(SAMPLE, &settings).expect("apply");
+        assert_eq!(updated, SAMPLE);
+    }
+
+    #[test]
+    fn validation_rejects_bad_references() {
+        let mut settings = parse_codex_config_settings(SAMPLE).expect("parse");
+        settings.profile = Some("missing".to_string());
+        assert!(apply_codex_config_settings(SAMPLE, &settings).is_err());
+
+        let profile = CodexProfile {
+            model_provider: Some("not-defined".to_string()),
+            ..Default::default()
+        };
+        assert!(upsert_profile_in_text(SAMPLE, "broken", &profile).is_err());
+
+        // 内置供应商无需在 [model_providers] 中定义
+        let profile = CodexProfile {
+            model_provider: Some("openai".to_string()),
+            ..Default::default()
+        };
+        assert!(upsert_profile_in_text(SAMPLE, "official", &profile).is_ok());
+
+        assert!(upsert_profile_in_text(SAMPLE, "bad name", &CodexProfile::default()).is_err());
+    }
+
+    #[test]
+    fn upsert_new_profile_uses_dotted_header() {
+        let profile = CodexProfile {
+            model: Some("o3".to_string()),
+            policy: CodexPolicySettings {
+                sandbox_mode: Some(CodexSandboxMode::ReadOnly),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let updated =
+            upsert_profile_in_text("model = \"gpt-5\"\n", "review", &profile).expect("upsert");
+        assert!(updated.contains("[profiles.review]"), "{updated}");
+        assert!(!updated.contains("[profiles]\n"), "{updated}");
+        assert!(updated.contains("sandbox_mode = \"read-only\""));
+    }
+
+    #[test]
+    fn rename_and_delete_track_active_profile() {
+        let text = format!("profile = \"fast\"\n{SAMPLE}");
+        let renamed = rename_profile_in_text(&text, "fast", "quick").expect("rename");
+        assert!(renamed.contains("profile = \"quick\""));
+        assert!(renamed.contains("[profiles.quick]"));
+        assert!(renamed.contains("model_verbosity = \"low\""));
+
+        let deleted = delete_profile_in_text(&renamed, "quick").expect("delete");
+        let settings = parse_codex_config_settings(&deleted).expect("parse");
+        assert!(settings.profile.is_none());
+        assert!(settings.profiles.is_empty());
+        assert!(delete_profile_in_text(&deleted, "quick").is_err());
+    }
+
+    #[test]
+    fn policy_defaults_fill_missing_keys_and_strip_on_backfill() {
+        let defaults = CodexPolicySettings {
+            model_reasoning_effort: Some(CodexReasoningEffort::High),
+            sandbox_mode: Some(CodexSandboxMode::WorkspaceWrite),
+            approval_policy: None,
+        };
+        let text = "model = \"gpt-5\"\nmodel_reasoning_effort = \"low\"\n";
+
+        let applied = apply_policy_defaults(text, &defaults).expect("apply");
+        assert!(applied.contains("model_reasoning_effort = \"low\""));
+        assert!(applied.contains("sandbox_mode = \"workspace-write\""));
+
+        let stripped = strip_policy_defaults(&applied, &defaults).expect("strip");
+        assert_eq!(stripped, text);
+    }
+}
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+use tauri::State;
+
+use crate::services::bundle::{
+    BundleExportOptions, BundleImportRecord, BundleImportResult, BundlePreview, BundleSecretInput,
+    TeamBundle,
+};
+use crate::services::BundleService;
+use crate::store::AppState;
+
+/// 导出团队配置包（供应商密钥会被清空）
+#[tauri::command]
+pub async fn export_team_bundle(
+    options: BundleExportOptions,
+    #[allow(non_snake_case)] filePath: String,
+    state: State<'_, AppState>,
+) -> Result<TeamBundle, String> {
+    BundleService::export(&state, &options, &PathBuf::from(filePath)).map_err(|e| e.to_string())
+}
+
+/// 预览团队配置包的导入变更
+#[tauri::command]
+pub async fn preview_team_bundle(
+    #[allow(non_snake_case)] filePath: String,
+    secrets: Option<BundleSecretInput>,
+    state: State<'_, AppState>,
+) -> Result<BundlePreview, String> {
+    let bundle = BundleService::read(&PathBuf::from(filePath)).map_err(|e| e.to_string())?;
+    BundleService::preview(&state, &bundle, &secrets.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+/// 导入团队配置包，并与上次导入的版本做三方合并
+///
+/// 未经受信任发布者签名的配置包需要传入 `acceptUntrusted: true`。
+#[tauri::command]
+pub async fn import_team_bundle(
+    #[allow(non_snake_case)] filePath: String,
+    secrets: Option<BundleSecretInput>,
+    #[allow(non_snake_case)] acceptUntrusted: Option<bool>,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<BundleImportResult, String> {
+    let bundle = BundleService::read(&PathBuf::from(filePath)).map_err(|e| e.to_string())?;
+    let result = BundleService::import(
+        &state,
+        &bundle,
+        &secrets.unwrap_or_default(),
+        acceptUntrusted.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())?;
+    crate::tray::refresh_tray_menu(&app);
+    Ok(result)
+}
+
+/// 获取已导入的团队配置包
+#[tauri::command]
+pub async fn list_team_bundle_imports(
+    state: State<'_, AppState>,
+) -> Result<Vec<BundleImportRecord>, String> {
+    BundleService::list_imports(&state).map_err(|e| e.to_string())
+}
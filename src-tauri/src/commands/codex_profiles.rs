@@ -0,0 +1,39 @@
+use crate::codex_profiles::{self, CodexConfigSettings, CodexProfile};
+
+/// 读取 Codex config.toml 中的 profile、运行策略与环境变量策略
+#[tauri::command]
+pub fn get_codex_config_settings() -> Result<CodexConfigSettings, String> {
+    codex_profiles::read_codex_config_settings().map_err(|e| e.to_string())
+}
+
+/// 保存结构化设置（未出现在设置中的 profile 会被删除），返回写入后的设置
+#[tauri::command]
+pub fn save_codex_config_settings(
+    settings: CodexConfigSettings,
+) -> Result<CodexConfigSettings, String> {
+    codex_profiles::save_codex_config_settings(&settings).map_err(|e| e.to_string())
+}
+
+/// 新增或更新单个 profile
+#[tauri::command]
+pub fn upsert_codex_profile(
+    name: String,
+    profile: CodexProfile,
+) -> Result<CodexConfigSettings, String> {
+    codex_profiles::upsert_codex_profile(&name, &profile).map_err(|e| e.to_string())
+}
+
+/// 删除单个 profile
+#[tauri::command]
+pub fn delete_codex_profile(name: String) -> Result<CodexConfigSettings, String> {
+    codex_profiles::delete_codex_profile(&name).map_err(|e| e.to_string())
+}
+
+/// 重命名 profile，默认 profile 指向它时一并更新
+#[tauri::command]
+pub fn rename_codex_profile(
+    #[allow(non_snake_case)] oldName: String,
+    #[allow(non_snake_case)] newName: String,
+) -> Result<CodexConfigSettings, String> {
+    codex_profiles::rename_codex_profile(&oldName, &newName).map_err(|e| e.to_string())
+}
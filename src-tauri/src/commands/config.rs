@@ -126,6 +126,42 @@ pub async fn get_config_status(
 
             Ok(ConfigStatus { exists, path })
         }
+        AppType::Qwen => {
+            let env_path = crate::qwen_config::get_qwen_env_path();
+            let exists = env_path.exists();
+            let path = crate::qwen_config::get_qwen_dir()
+                .to_string_lossy()
+                .to_string();
+
+            Ok(ConfigStatus { exists, path })
+        }
+        AppType::Goose => {
+            let config_path = crate::goose_config::get_goose_config_path();
+            let exists = config_path.exists();
+            let path = crate::goose_config::get_goose_dir()
+                .to_string_lossy()
+                .to_string();
+
+            Ok(ConfigStatus { exists, path })
+        }
+        AppType::Crush => {
+            let config_path = crate::crush_config::get_crush_config_path();
+            let exists = config_path.exists();
+            let path = crate::crush_config::get_crush_dir()
+                .to_string_lossy()
+                .to_string();
+
+            Ok(ConfigStatus { exists, path })
+        }
+        AppType::Aider => {
+            let config_path = crate::aider_config::get_aider_config_path();
+            let exists = config_path.exists();
+            let path = crate::aider_config::get_aider_dir()
+                .to_string_lossy()
+                .to_string();
+
+            Ok(ConfigStatus { exists, path })
+        }
     }
 }
 
@@ -146,6 +182,10 @@ pub async fn get_config_dir(app: String) -> Result<String, String> {
         AppType::OpenCode => crate::opencode_config::get_opencode_dir(),
         AppType::OpenClaw => crate::openclaw_config::get_openclaw_dir(),
         AppType::Hermes => crate::hermes_config::get_hermes_dir(),
+        AppType::Qwen => crate::qwen_config::get_qwen_dir(),
+        AppType::Goose => crate::goose_config::get_goose_dir(),
+        AppType::Crush => crate::crush_config::get_crush_dir(),
+        AppType::Aider => crate::aider_config::get_aider_dir(),
     };
 
     Ok(dir.to_string_lossy().to_string())
@@ -163,6 +203,10 @@ pub async fn open_config_folder(handle: AppHandle, app: String) -> Result<bool,
         AppType::OpenCode => crate::opencode_config::get_opencode_dir(),
         AppType::OpenClaw => crate::openclaw_config::get_openclaw_dir(),
         AppType::Hermes => crate::hermes_config::get_hermes_dir(),
+        AppType::Qwen => crate::qwen_config::get_qwen_dir(),
+        AppType::Goose => crate::goose_config::get_goose_dir(),
+        AppType::Crush => crate::crush_config::get_crush_dir(),
+        AppType::Aider => crate::aider_config::get_aider_dir(),
     };
 
     if !config_dir.exists() {
@@ -317,6 +361,7 @@ pub async fn set_common_config_snippet(
 
     if matches!(app_type.as_str(), "claude" | "codex" | "gemini") {
         let app = AppType::from_str(&app_type).map_err(|e| e.to_string())?;
+        let _scope = crate::services::live_history::LiveWriteScope::enter("commonConfig");
         crate::services::provider::ProviderService::sync_current_provider_for_app(
             state.inner(),
             app,
@@ -0,0 +1,22 @@
+use tauri::State;
+
+use crate::crush_config;
+use crate::store::AppState;
+
+/// Import providers from Crush live config to database.
+///
+/// Crush uses additive mode — users may already have providers
+/// configured in crush.json.
+#[tauri::command]
+pub fn import_crush_providers_from_live(state: State<'_, AppState>) -> Result<usize, String> {
+    crate::services::provider::import_crush_providers_from_live(state.inner())
+        .map_err(|e| e.to_string())
+}
+
+/// Get provider ids in the Crush live config.
+#[tauri::command]
+pub fn get_crush_live_provider_ids() -> Result<Vec<String>, String> {
+    crush_config::get_providers()
+        .map(|providers| providers.keys().cloned().collect())
+        .map_err(|e| e.to_string())
+}
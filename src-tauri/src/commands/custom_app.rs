@@ -0,0 +1,117 @@
+use indexmap::IndexMap;
+use tauri::State;
+
+use crate::app_definition::AppDefinition;
+use crate::prompt::Prompt;
+use crate::provider::Provider;
+use crate::services::custom_app::AppDefinitionLoadReport;
+use crate::services::CustomAppService;
+use crate::store::AppState;
+
+/// 列出已加载的声明式应用定义
+#[tauri::command]
+pub fn list_app_definitions() -> Result<Vec<AppDefinition>, String> {
+    Ok(CustomAppService::list())
+}
+
+/// 重新加载定义目录，并把 MCP 同步到新加载的应用
+#[tauri::command]
+pub fn reload_app_definitions(
+    state: State<'_, AppState>,
+) -> Result<AppDefinitionLoadReport, String> {
+    let report = CustomAppService::reload();
+    CustomAppService::sync_all_mcp(&state);
+    Ok(report)
+}
+
+#[tauri::command]
+pub fn get_custom_app_providers(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<IndexMap<String, Provider>, String> {
+    CustomAppService::get_providers(&state, &app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_custom_app_current_provider(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Option<String>, String> {
+    CustomAppService::get_current_provider(&state, &app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn upsert_custom_app_provider(
+    state: State<'_, AppState>,
+    app: String,
+    provider: Provider,
+) -> Result<(), String> {
+    CustomAppService::upsert_provider(&state, &app, provider).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_custom_app_provider(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<(), String> {
+    CustomAppService::delete_provider(&state, &app, &id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn switch_custom_app_provider(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<(), String> {
+    CustomAppService::switch(&state, &app, &id).map_err(|e| e.to_string())
+}
+
+/// 从 live 配置导入供应商；已有供应商时返回 false
+#[tauri::command]
+pub fn import_custom_app_from_live(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<bool, String> {
+    CustomAppService::import_from_live(&state, &app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn sync_custom_app_mcp(state: State<'_, AppState>, app: String) -> Result<(), String> {
+    CustomAppService::sync_mcp(&state, &app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_custom_app_prompts(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<IndexMap<String, Prompt>, String> {
+    CustomAppService::get_prompts(&state, &app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn upsert_custom_app_prompt(
+    state: State<'_, AppState>,
+    app: String,
+    prompt: Prompt,
+) -> Result<(), String> {
+    CustomAppService::upsert_prompt(&state, &app, prompt).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_custom_app_prompt(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<(), String> {
+    CustomAppService::delete_prompt(&state, &app, &id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn enable_custom_app_prompt(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<(), String> {
+    CustomAppService::enable_prompt(&state, &app, &id).map_err(|e| e.to_string())
+}
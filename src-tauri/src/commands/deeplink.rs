@@ -1,15 +1,24 @@
 use crate::deeplink::{
     import_mcp_from_deeplink, import_prompt_from_deeplink, import_provider_from_deeplink,
-    import_skill_from_deeplink, parse_deeplink_url, DeepLinkImportRequest,
+    import_skill_from_deeplink, parse_deeplink_url_with_trust, DeepLinkImportRequest,
 };
 use crate::store::AppState;
 use tauri::State;
 
 /// Parse a deep link URL and return the parsed request for frontend confirmation
+///
+/// The signature is resolved against the trusted publisher list; tampered links are rejected.
 #[tauri::command]
-pub fn parse_deeplink(url: String) -> Result<DeepLinkImportRequest, String> {
+pub fn parse_deeplink(
+    state: State<AppState>,
+    url: String,
+) -> Result<DeepLinkImportRequest, String> {
     log::info!("Parsing deep link URL: {url}");
-    parse_deeplink_url(&url).map_err(|e| e.to_string())
+    let trusted = state
+        .db
+        .get_trusted_publishers()
+        .map_err(|e| e.to_string())?;
+    parse_deeplink_url_with_trust(&url, &trusted).map_err(|e| e.to_string())
 }
 
 /// Merge configuration from Base64/URL into a deep link request
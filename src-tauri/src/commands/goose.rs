@@ -0,0 +1,22 @@
+use tauri::State;
+
+use crate::goose_config;
+use crate::store::AppState;
+
+/// Import providers from Goose custom providers to database.
+///
+/// Goose uses additive mode — users may already have declarative providers
+/// under `~/.config/goose/custom_providers/`.
+#[tauri::command]
+pub fn import_goose_providers_from_live(state: State<'_, AppState>) -> Result<usize, String> {
+    crate::services::provider::import_goose_providers_from_live(state.inner())
+        .map_err(|e| e.to_string())
+}
+
+/// Get provider ids of the Goose custom providers on disk.
+#[tauri::command]
+pub fn get_goose_live_provider_ids() -> Result<Vec<String>, String> {
+    goose_config::get_providers()
+        .map(|providers| providers.keys().cloned().collect())
+        .map_err(|e| e.to_string())
+}
@@ -2,12 +2,16 @@
 
 use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::time::Duration;
 
 use serde::Serialize;
 use tauri::State;
 
 use crate::app_config::AppType;
 use crate::claude_mcp;
+use crate::mcp::{
+    self, McpGateway, McpGatewayConfig, McpProbeResult, McpSecretInfo, McpSecretReport,
+};
 use crate::services::McpService;
 use crate::store::AppState;
 
@@ -194,6 +198,109 @@ pub async fn toggle_mcp_app(
     McpService::toggle_app(&state, &server_id, app_ty, enabled).map_err(|e| e.to_string())
 }
 
+fn probe_timeout(timeout_secs: Option<u64>) -> Duration {
+    Duration::from_secs(
+        timeout_secs
+            .filter(|secs| *secs > 0)
+            .unwrap_or(mcp::DEFAULT_PROBE_TIMEOUT_SECS),
+    )
+}
+
+/// 探测已保存的 MCP 服务器（握手并列出 tools / prompts / resources），结果会被记录
+#[tauri::command]
+pub async fn probe_mcp_server(
+    state: State<'_, AppState>,
+    id: String,
+    timeoutSecs: Option<u64>,
+) -> Result<McpProbeResult, String> {
+    McpService::probe_server(&state, &id, probe_timeout(timeoutSecs))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 探测尚未保存的 MCP 服务器连接定义（编辑表单中使用，不落库）
+#[tauri::command]
+pub async fn probe_mcp_spec(
+    state: State<'_, AppState>,
+    spec: serde_json::Value,
+    timeoutSecs: Option<u64>,
+) -> Result<McpProbeResult, String> {
+    let spec = state
+        .db
+        .mcp_secret_resolver()
+        .resolve_for_write("<draft>", &spec);
+    Ok(mcp::probe_server(&spec, probe_timeout(timeoutSecs)).await)
+}
+
+/// 获取所有 MCP 服务器的最近一次探测结果
+#[tauri::command]
+pub async fn get_mcp_probe_results(
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, McpProbeResult>, String> {
+    McpService::get_probe_results(&state).map_err(|e| e.to_string())
+}
+
+/// 获取内置 MCP 网关配置
+#[tauri::command]
+pub async fn get_mcp_gateway_config(
+    state: State<'_, AppState>,
+) -> Result<McpGatewayConfig, String> {
+    state.db.get_mcp_gateway_config().map_err(|e| e.to_string())
+}
+
+/// 保存内置 MCP 网关配置，并重新写入各应用的 MCP 配置
+#[tauri::command]
+pub async fn save_mcp_gateway_config(
+    state: State<'_, AppState>,
+    config: McpGatewayConfig,
+) -> Result<(), String> {
+    McpService::save_gateway_config(&state, config).map_err(|e| e.to_string())
+}
+
+/// 预览网关向指定应用暴露的工具（已加命名空间并按规则过滤）
+#[tauri::command]
+pub async fn list_mcp_gateway_tools(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Vec<serde_json::Value>, String> {
+    let app_ty = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let gateway = McpGateway::new(state.db.clone());
+    let tools = gateway.list_tools(&app_ty).await;
+    gateway.reset().await;
+    tools
+}
+
+/// 列出本地 MCP 密钥（不返回值）
+#[tauri::command]
+pub async fn list_mcp_secrets(state: State<'_, AppState>) -> Result<Vec<McpSecretInfo>, String> {
+    McpService::list_secrets(&state).map_err(|e| e.to_string())
+}
+
+/// 新增或更新本地 MCP 密钥，供 `${secret:name}` 引用
+#[tauri::command]
+pub async fn set_mcp_secret(
+    state: State<'_, AppState>,
+    name: String,
+    value: String,
+    description: Option<String>,
+) -> Result<(), String> {
+    McpService::set_secret(&state, &name, &value, description.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 删除本地 MCP 密钥
+#[tauri::command]
+pub async fn delete_mcp_secret(state: State<'_, AppState>, name: String) -> Result<bool, String> {
+    McpService::delete_secret(&state, &name).map_err(|e| e.to_string())
+}
+
+/// 按应用列出无法解析的 `${secret:...}` / `${env:...}` 引用
+#[tauri::command]
+pub async fn validate_mcp_secret_refs(
+    state: State<'_, AppState>,
+) -> Result<McpSecretReport, String> {
+    McpService::validate_secret_refs(&state).map_err(|e| e.to_string())
+}
+
 /// 从所有应用导入 MCP 服务器（复用已有的导入逻辑）
 #[tauri::command]
 pub async fn import_mcp_from_apps(state: State<'_, AppState>) -> Result<usize, String> {
@@ -203,5 +310,8 @@ pub async fn import_mcp_from_apps(state: State<'_, AppState>) -> Result<usize, S
     total += McpService::import_from_gemini(&state).unwrap_or(0);
     total += McpService::import_from_opencode(&state).unwrap_or(0);
     total += McpService::import_from_hermes(&state).unwrap_or(0);
+    total += McpService::import_from_qwen(&state).unwrap_or(0);
+    total += McpService::import_from_goose(&state).unwrap_or(0);
+    total += McpService::import_from_crush(&state).unwrap_or(0);
     Ok(total)
 }
@@ -111,8 +111,9 @@ pub struct ToolVersion {
     wsl_distro: Option<String>,
 }
 
-const VALID_TOOLS: [&str; 6] = [
-    "claude", "codex", "gemini", "opencode", "openclaw", "hermes",
+const VALID_TOOLS: [&str; 10] = [
+    "claude", "codex", "gemini", "opencode", "openclaw", "hermes", "qwen", "goose", "crush",
+    "aider",
 ];
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -427,6 +428,10 @@ fn tool_display_name(tool: &str) -> &'static str {
         "opencode" => "OpenCode",
         "openclaw" => "OpenClaw",
         "hermes" => "Hermes",
+        "qwen" => "Qwen Code",
+        "goose" => "Goose",
+        "crush" => "Crush",
+        "aider" => "Aider",
         _ => "Unknown",
     }
 }
@@ -447,6 +452,16 @@ const OPENCODE_INSTALL_UNIX: &str =
 /// 真正的 Python 版本问题盖成 "python command exists in these Python versions"。
 const HERMES_INSTALL_UNIX: &str =
     "bash -c 'tmp=$(mktemp) && curl -fsSL https://raw.githubusercontent.com/NousResearch/hermes-agent/main/scripts/install.sh -o $tmp && bash $tmp; status=$?; rm -f $tmp; exit $status'";
+/// Goose 官方 installer 默认会进入交互式 `goose configure`，`CONFIGURE=false` 跳过。
+/// Goose 没有 npm 包；Windows 原生只提供 PowerShell installer，暂不在此支持。
+const GOOSE_INSTALL_UNIX: &str =
+    "bash -c 'tmp=$(mktemp) && curl -fsSL https://github.com/block/goose/releases/download/stable/download_cli.sh -o $tmp && CONFIGURE=false bash $tmp; status=$?; rm -f $tmp; exit $status'";
+const GOOSE_UPDATE_UNIX: &str = "goose update";
+/// Aider 官方 installer 基于 uv 安装独立的 Python 环境，不依赖系统 Python 版本。
+/// 已安装时用 `aider --upgrade` 在其自身环境内升级；Windows 原生暂不支持。
+const AIDER_INSTALL_UNIX: &str =
+    "bash -c 'tmp=$(mktemp) && curl -LsSf https://aider.chat/install.sh -o $tmp && sh $tmp; status=$?; rm -f $tmp; exit $status'";
+const AIDER_UPDATE_UNIX: &str = "aider --upgrade";
 const HERMES_UPDATE_UNIX: &str =
     "hermes update || bash -c 'tmp=$(mktemp) && curl -fsSL https://raw.githubusercontent.com/NousResearch/hermes-agent/main/scripts/install.sh -o $tmp && bash $tmp; status=$?; rm -f $tmp; exit $status'";
 
@@ -494,6 +509,8 @@ fn npm_install_command_for(tool: &str) -> Option<&'static str> {
         "gemini" => Some("npm i -g @google/gemini-cli@latest"),
         "opencode" => Some("npm i -g opencode-ai@latest"),
         "openclaw" => Some("npm i -g openclaw@latest"),
+        "qwen" => Some("npm i -g @qwen-code/qwen-code@latest"),
+        "crush" => Some("npm i -g @charmland/crush@latest"),
         _ => None,
     }
 }
@@ -552,6 +569,30 @@ fn tool_action_shell_command_for_shell(
         );
     }
 
+    if tool == "goose" {
+        return match (action, shell) {
+            (ToolLifecycleAction::Install, LifecycleCommandShell::Posix) => {
+                Some(GOOSE_INSTALL_UNIX.to_string())
+            }
+            (ToolLifecycleAction::Update, LifecycleCommandShell::Posix) => {
+                Some(GOOSE_UPDATE_UNIX.to_string())
+            }
+            (_, LifecycleCommandShell::WindowsBatch) => None,
+        };
+    }
+
+    if tool == "aider" {
+        return match (action, shell) {
+            (ToolLifecycleAction::Install, LifecycleCommandShell::Posix) => {
+                Some(AIDER_INSTALL_UNIX.to_string())
+            }
+            (ToolLifecycleAction::Update, LifecycleCommandShell::Posix) => {
+                Some(AIDER_UPDATE_UNIX.to_string())
+            }
+            (_, LifecycleCommandShell::WindowsBatch) => None,
+        };
+    }
+
     let install = npm_install_command_for(tool)?;
     match action {
         ToolLifecycleAction::Install => Some(install.to_string()),
@@ -772,7 +813,11 @@ async fn get_single_tool_version_impl(
             }
         }
         "openclaw" => fetch_npm_latest_for_tool(&client, "openclaw", tool, local).await,
+        "qwen" => fetch_npm_latest_for_tool(&client, "@qwen-code/qwen-code", tool, local).await,
+        "crush" => fetch_npm_latest_for_tool(&client, "@charmland/crush", tool, local).await,
+        "goose" => fetch_github_latest_version(&client, "block/goose").await,
         "hermes" => fetch_pypi_latest_version(&client, "hermes-agent").await,
+        "aider" => fetch_pypi_latest_version(&client, "aider-chat").await,
         _ => None,
     };
 
@@ -1814,6 +1859,8 @@ fn npm_package_for(tool: &str) -> Option<&'static str> {
         "gemini" => Some("@google/gemini-cli"),
         "opencode" => Some("opencode-ai"),
         "openclaw" => Some("openclaw"),
+        "qwen" => Some("@qwen-code/qwen-code"),
+        "crush" => Some("@charmland/crush"),
         _ => None,
     }
 }
@@ -2238,6 +2285,8 @@ fn posix_install_command_for(tool: &str) -> String {
         "claude" => installer_with_npm_fallback(CLAUDE_INSTALL_UNIX, tool),
         "opencode" => installer_with_npm_fallback(OPENCODE_INSTALL_UNIX, tool),
         "hermes" => HERMES_INSTALL_UNIX.to_string(),
+        "goose" => GOOSE_INSTALL_UNIX.to_string(),
+        "aider" => AIDER_INSTALL_UNIX.to_string(),
         _ => static_fallback_command_for(tool, ToolLifecycleAction::Install),
     }
 }
@@ -2349,6 +2398,10 @@ fn wsl_distro_for_tool(tool: &str) -> Option<String> {
         "opencode" => crate::settings::get_opencode_override_dir(),
         "openclaw" => crate::settings::get_openclaw_override_dir(),
         "hermes" => crate::settings::get_hermes_override_dir(),
+        "qwen" => crate::settings::get_qwen_override_dir(),
+        "goose" => crate::settings::get_goose_override_dir(),
+        "crush" => crate::settings::get_crush_override_dir(),
+        "aider" => crate::settings::get_aider_override_dir(),
         _ => None,
     }?;
 
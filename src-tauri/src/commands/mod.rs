@@ -2,14 +2,19 @@
 
 mod auth;
 mod balance;
+mod bundle;
 mod codex_oauth;
+mod codex_profiles;
 mod coding_plan;
 mod config;
 mod copilot;
+mod crush;
+mod custom_app;
 mod deeplink;
 mod env;
 mod failover;
 mod global_proxy;
+mod goose;
 mod hermes;
 mod import_export;
 mod mcp;
@@ -18,11 +23,14 @@ mod model_fetch;
 mod omo;
 mod openclaw;
 mod plugin;
+mod profile;
+mod project;
 mod prompt;
 mod provider;
 mod proxy;
 mod session_manager;
 mod settings;
+mod signing;
 pub mod skill;
 mod stream_check;
 mod subscription;
@@ -36,14 +44,19 @@ mod workspace;
 
 pub use auth::*;
 pub use balance::*;
+pub use bundle::*;
 pub use codex_oauth::*;
+pub use codex_profiles::*;
 pub use coding_plan::*;
 pub use config::*;
 pub use copilot::*;
+pub use crush::*;
+pub use custom_app::*;
 pub use deeplink::*;
 pub use env::*;
 pub use failover::*;
 pub use global_proxy::*;
+pub use goose::*;
 pub use hermes::*;
 pub use import_export::*;
 pub use mcp::*;
@@ -52,11 +65,14 @@ pub use model_fetch::*;
 pub use omo::*;
 pub use openclaw::*;
 pub use plugin::*;
+pub use profile::*;
+pub use project::*;
 pub use prompt::*;
 pub use provider::*;
 pub use proxy::*;
 pub use session_manager::*;
 pub use settings::*;
+pub use signing::*;
 pub use skill::*;
 pub use stream_check::*;
 pub use subscription::*;
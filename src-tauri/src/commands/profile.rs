@@ -0,0 +1,41 @@
+use tauri::State;
+
+use crate::services::profile::{Profile, ProfileSwitchResult};
+use crate::services::ProfileService;
+use crate::store::AppState;
+
+/// 获取所有配置档案
+#[tauri::command]
+pub async fn list_profiles(state: State<'_, AppState>) -> Result<Vec<Profile>, String> {
+    ProfileService::list(&state).map_err(|e| e.to_string())
+}
+
+/// 新增或更新配置档案（不会立即切换）
+#[tauri::command]
+pub async fn save_profile(profile: Profile, state: State<'_, AppState>) -> Result<Profile, String> {
+    ProfileService::save(&state, profile).map_err(|e| e.to_string())
+}
+
+/// 删除配置档案
+#[tauri::command]
+pub async fn delete_profile(id: String, state: State<'_, AppState>) -> Result<bool, String> {
+    ProfileService::delete(&state, &id).map_err(|e| e.to_string())
+}
+
+/// 获取与当前状态一致的配置档案
+#[tauri::command]
+pub async fn get_current_profile(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    ProfileService::current(&state).map_err(|e| e.to_string())
+}
+
+/// 切换到配置档案，失败时自动回滚
+#[tauri::command]
+pub async fn apply_profile(
+    id: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ProfileSwitchResult, String> {
+    let result = ProfileService::apply(&state, &id).map_err(|e| e.to_string())?;
+    crate::tray::refresh_tray_menu(&app);
+    Ok(result)
+}
@@ -0,0 +1,54 @@
+use tauri::State;
+
+use crate::services::project::{ProjectDrift, ProjectFileChange, ProjectPreview, ProjectScope};
+use crate::services::ProjectService;
+use crate::store::AppState;
+
+/// 获取所有登记的项目
+#[tauri::command]
+pub async fn list_projects(state: State<'_, AppState>) -> Result<Vec<ProjectScope>, String> {
+    ProjectService::list(&state).map_err(|e| e.to_string())
+}
+
+/// 新增或更新项目（不会立即写入项目文件）
+#[tauri::command]
+pub async fn save_project(
+    project: ProjectScope,
+    state: State<'_, AppState>,
+) -> Result<ProjectScope, String> {
+    ProjectService::save(&state, project).map_err(|e| e.to_string())
+}
+
+/// 删除项目登记
+#[tauri::command]
+pub async fn delete_project(id: String, state: State<'_, AppState>) -> Result<bool, String> {
+    ProjectService::delete(&state, &id).map_err(|e| e.to_string())
+}
+
+/// 预览写入项目文件的差异
+#[tauri::command]
+pub async fn preview_project_sync(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<ProjectPreview, String> {
+    ProjectService::preview(&state, &id).map_err(|e| e.to_string())
+}
+
+/// 写入项目文件；存在漂移时需要 force
+#[tauri::command]
+pub async fn apply_project_sync(
+    id: String,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProjectFileChange>, String> {
+    ProjectService::apply(&state, &id, force.unwrap_or(false)).map_err(|e| e.to_string())
+}
+
+/// 检查项目文件在上次写入后是否被改动
+#[tauri::command]
+pub async fn check_project_drift(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProjectDrift>, String> {
+    ProjectService::check_drift(&state, &id).map_err(|e| e.to_string())
+}
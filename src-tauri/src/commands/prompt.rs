@@ -1,10 +1,12 @@
 use indexmap::IndexMap;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use tauri::State;
 
 use crate::app_config::AppType;
-use crate::prompt::Prompt;
+use crate::prompt::{Prompt, SharedPrompt};
+use crate::services::prompt::{PromptStackConfig, SharedPromptSyncResult};
 use crate::services::PromptService;
 use crate::store::AppState;
 
@@ -62,3 +64,70 @@ pub async fn get_current_prompt_file_content(app: String) -> Result<Option<Strin
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
     PromptService::get_current_file_content(app_type).map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn get_prompt_stack_config(
+    state: State<'_, AppState>,
+) -> Result<PromptStackConfig, String> {
+    PromptService::get_stack_config(&state).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_prompt_stack(
+    app: String,
+    ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptService::set_prompt_stack(&state, app_type, ids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn save_prompt_variables(
+    variables: BTreeMap<String, String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    PromptService::save_variables(&state, variables).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn preview_prompt_stack(
+    app: String,
+    ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptService::render_stack(&state, app_type, &ids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_shared_prompts(
+    state: State<'_, AppState>,
+) -> Result<IndexMap<String, SharedPrompt>, String> {
+    PromptService::get_shared_prompts(&state).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn upsert_shared_prompt(
+    prompt: SharedPrompt,
+    activate: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<SharedPromptSyncResult, String> {
+    PromptService::upsert_shared_prompt(&state, prompt, activate.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_shared_prompt(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    PromptService::delete_shared_prompt(&state, &id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn sync_shared_prompt(
+    id: String,
+    activate: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<SharedPromptSyncResult, String> {
+    PromptService::sync_shared_prompt(&state, &id, activate.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
@@ -5,8 +5,11 @@ use crate::app_config::AppType;
 use crate::commands::copilot::CopilotAuthState;
 use crate::error::AppError;
 use crate::provider::{ClaudeDesktopMode, Provider};
+use crate::services::live_drift::{LiveDrift, LiveDriftAction};
+use crate::services::live_history::{LiveHistoryEntry, LiveHistoryFile};
 use crate::services::{
-    EndpointLatency, ProviderService, ProviderSortUpdate, SpeedtestService, SwitchResult,
+    EndpointLatency, LiveDriftService, LiveHistoryService, ProviderService, ProviderSortUpdate,
+    SpeedtestService, SwitchPreview, SwitchResult,
 };
 use crate::store::AppState;
 use std::str::FromStr;
@@ -109,6 +112,17 @@ pub fn switch_provider(
     switch_provider_internal(&state, app_type, &id).map_err(|e| e.to_string())
 }
 
+/// 预览切换供应商：不写入任何文件，返回各 live 文件的 diff（密钥已遮蔽）
+#[tauri::command]
+pub fn preview_switch_provider(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<SwitchPreview, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderService::preview_switch(&state, app_type, &id).map_err(|e| e.to_string())
+}
+
 fn import_default_config_internal(state: &AppState, app_type: AppType) -> Result<bool, AppError> {
     let imported = ProviderService::import_default_config(state, app_type.clone())?;
 
@@ -685,6 +699,57 @@ pub fn read_live_provider_settings(app: String) -> Result<serde_json::Value, Str
     ProviderService::read_live_settings(app_type).map_err(|e| e.to_string())
 }
 
+/// 检测各应用 live 配置相对 cc-switch 上次写入的外部修改
+#[tauri::command]
+pub fn get_live_config_drifts(state: State<'_, AppState>) -> Result<Vec<LiveDrift>, String> {
+    LiveDriftService::check_all(&state).map_err(|e| e.to_string())
+}
+
+/// 处理 live 配置漂移：回填到供应商、重新写入或接受外部修改
+#[tauri::command]
+pub fn resolve_live_config_drift(
+    state: State<'_, AppState>,
+    app: String,
+    action: LiveDriftAction,
+) -> Result<(), String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    LiveDriftService::resolve(&state, app_type, action).map_err(|e| e.to_string())
+}
+
+/// 列出有写入历史的 live 配置文件
+#[tauri::command]
+pub fn list_live_history_files() -> Result<Vec<LiveHistoryFile>, String> {
+    LiveHistoryService::list_files().map_err(|e| e.to_string())
+}
+
+/// 列出某个 live 配置文件的历史版本（新到旧）
+#[tauri::command]
+pub fn list_live_history_versions(path: String) -> Result<Vec<LiveHistoryEntry>, String> {
+    LiveHistoryService::list_versions(&path).map_err(|e| e.to_string())
+}
+
+/// 对比两个历史版本；`toId` 为空时与磁盘上的当前内容对比
+#[tauri::command]
+pub fn diff_live_history(
+    path: String,
+    #[allow(non_snake_case)] fromId: String,
+    #[allow(non_snake_case)] toId: Option<String>,
+) -> Result<String, String> {
+    LiveHistoryService::diff(&path, &fromId, toId.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 将 live 配置文件回滚到指定历史版本
+#[tauri::command]
+pub fn restore_live_history(path: String, id: String) -> Result<(), String> {
+    LiveHistoryService::restore(&path, &id).map_err(|e| e.to_string())
+}
+
+/// 设置某个 live 配置文件保留的历史版本数
+#[tauri::command]
+pub fn set_live_history_retention(path: String, limit: usize) -> Result<(), String> {
+    LiveHistoryService::set_retention(&path, limit).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn test_api_endpoints(
     urls: Vec<String>,
@@ -22,6 +22,8 @@ pub async fn stop_proxy_server(state: tauri::State<'_, AppState>) -> Result<(),
     if takeover.claude
         || takeover.codex
         || takeover.gemini
+        || takeover.qwen
+        || takeover.aider
         || takeover.opencode
         || takeover.openclaw
     {
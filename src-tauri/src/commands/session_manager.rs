@@ -1,13 +1,45 @@
 #![allow(non_snake_case)]
 
+use crate::services::session_archive::{
+    ArchiveSessionOutcome, ArchivedSession, RetentionReport, RetentionState, SessionArchiveService,
+    SessionRetentionConfig,
+};
+use crate::services::session_cost::{self, SessionCostQuery, SessionCostReport};
 use crate::session_manager;
+use crate::store::AppState;
+use tauri::State;
 
 #[tauri::command]
-pub async fn list_sessions() -> Result<Vec<session_manager::SessionMeta>, String> {
-    let sessions = tauri::async_runtime::spawn_blocking(session_manager::scan_sessions)
-        .await
-        .map_err(|e| format!("Failed to scan sessions: {e}"))?;
-    Ok(sessions)
+pub async fn list_sessions(
+    state: State<'_, AppState>,
+) -> Result<Vec<session_manager::SessionMeta>, String> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut sessions = session_manager::scan_sessions();
+        // 费用关联失败不影响会话列表本身
+        match db.get_session_usage_map(None, None, None) {
+            Ok(usage) => session_cost::attach_session_usage(&mut sessions, &usage),
+            Err(e) => log::warn!("Failed to load session usage: {e}"),
+        }
+        sessions
+    })
+    .await
+    .map_err(|e| format!("Failed to scan sessions: {e}"))
+}
+
+/// 最贵会话 + 按项目的费用汇总
+#[tauri::command]
+pub async fn get_session_cost_report(
+    state: State<'_, AppState>,
+    query: Option<SessionCostQuery>,
+) -> Result<SessionCostReport, String> {
+    let db = state.db.clone();
+    let query = query.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        session_cost::get_session_cost_report(&db, &query).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Failed to build session cost report: {e}"))?
 }
 
 #[tauri::command]
@@ -83,3 +115,89 @@ pub async fn delete_sessions(
         .await
         .map_err(|e| format!("Failed to delete sessions: {e}"))
 }
+
+/// 归档会话（压缩后从原位置移除）
+#[tauri::command]
+pub async fn archive_sessions(
+    state: State<'_, AppState>,
+    items: Vec<session_manager::DeleteSessionRequest>,
+) -> Result<Vec<ArchiveSessionOutcome>, String> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        SessionArchiveService::archive_sessions(&db, &items)
+    })
+    .await
+    .map_err(|e| format!("Failed to archive sessions: {e}"))
+}
+
+#[tauri::command]
+pub async fn list_session_archives(
+    state: State<'_, AppState>,
+) -> Result<Vec<ArchivedSession>, String> {
+    state.db.list_session_archives().map_err(|e| e.to_string())
+}
+
+/// 恢复归档会话到原生位置
+#[tauri::command]
+pub async fn restore_session_archive(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<ArchivedSession, String> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        SessionArchiveService::restore_archive(&db, &id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Failed to restore session archive: {e}"))?
+}
+
+/// 永久删除归档
+#[tauri::command]
+pub async fn delete_session_archive(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, String> {
+    SessionArchiveService::delete_archive(&state.db, &id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_session_retention_config(
+    state: State<'_, AppState>,
+) -> Result<SessionRetentionConfig, String> {
+    state
+        .db
+        .get_session_retention_config()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn save_session_retention_config(
+    state: State<'_, AppState>,
+    config: SessionRetentionConfig,
+) -> Result<(), String> {
+    state
+        .db
+        .save_session_retention_config(&config)
+        .map_err(|e| e.to_string())
+}
+
+/// 立即执行保留策略；`dryRun` 为 true 时只返回将被归档的会话
+#[tauri::command]
+pub async fn run_session_retention(
+    state: State<'_, AppState>,
+    dryRun: Option<bool>,
+) -> Result<RetentionReport, String> {
+    let db = state.db.clone();
+    let dry_run = dryRun.unwrap_or(true);
+    tauri::async_runtime::spawn_blocking(move || {
+        SessionArchiveService::run_retention(&db, dry_run).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Failed to run session retention: {e}"))?
+}
+
+/// 获取定时保留策略的上次执行状态
+#[tauri::command]
+pub async fn get_session_retention_state() -> Result<RetentionState, String> {
+    Ok(SessionArchiveService::load_state())
+}
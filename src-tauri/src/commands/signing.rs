@@ -0,0 +1,46 @@
+use tauri::State;
+
+use crate::signing::{self, SigningKeyPair, TrustedPublisher};
+use crate::store::AppState;
+
+/// 获取受信任的签名发布者
+#[tauri::command]
+pub async fn get_trusted_publishers(
+    state: State<'_, AppState>,
+) -> Result<Vec<TrustedPublisher>, String> {
+    signing::get_trusted_publishers(&state.db).map_err(|e| e.to_string())
+}
+
+/// 添加受信任的签名发布者
+#[tauri::command]
+pub async fn add_trusted_publisher(
+    name: String,
+    #[allow(non_snake_case)] publicKey: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<TrustedPublisher>, String> {
+    signing::add_trusted_publisher(&state.db, &name, &publicKey).map_err(|e| e.to_string())
+}
+
+/// 移除受信任的签名发布者
+#[tauri::command]
+pub async fn remove_trusted_publisher(
+    #[allow(non_snake_case)] publicKey: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<TrustedPublisher>, String> {
+    signing::remove_trusted_publisher(&state.db, &publicKey).map_err(|e| e.to_string())
+}
+
+/// 生成发布者密钥对（私钥不会被保存）
+#[tauri::command]
+pub async fn generate_publisher_key() -> Result<SigningKeyPair, String> {
+    signing::generate_keypair().map_err(|e| e.to_string())
+}
+
+/// 使用发布者私钥为深链接签名
+#[tauri::command]
+pub async fn sign_deeplink(
+    url: String,
+    #[allow(non_snake_case)] privateKey: String,
+) -> Result<String, String> {
+    crate::deeplink::sign_deeplink_url(&url, &privateKey).map_err(|e| e.to_string())
+}
@@ -8,9 +8,12 @@ use crate::app_config::{AppType, InstalledSkill, UnmanagedSkill};
 use crate::error::format_skill_error;
 use crate::services::skill::{
     DiscoverableSkill, ImportSkillSelection, MigrationResult, Skill, SkillBackupEntry, SkillRepo,
-    SkillService, SkillStorageLocation, SkillUninstallResult, SkillUpdateInfo,
+    SkillService, SkillSourceRequest, SkillStorageLocation, SkillUninstallResult, SkillUpdateInfo,
     SkillsShSearchResult,
 };
+use crate::services::skill_lock::{
+    SkillLockDrift, SkillLockImportResult, SkillLockService, SkillsLock,
+};
 use crate::store::AppState;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -48,10 +51,12 @@ pub fn delete_skill_backup(backup_id: String) -> Result<bool, String> {
 /// 参数：
 /// - skill: 从发现列表获取的技能信息
 /// - current_app: 当前选中的应用，安装后默认启用该应用
+/// - acknowledge_warnings: 用户已确认安装前检查的警告
 #[tauri::command]
 pub async fn install_skill_unified(
     skill: DiscoverableSkill,
     current_app: String,
+    acknowledge_warnings: Option<bool>,
     service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
 ) -> Result<InstalledSkill, String> {
@@ -59,7 +64,12 @@ pub async fn install_skill_unified(
 
     service
         .0
-        .install(&app_state.db, &skill, &app_type)
+        .install(
+            &app_state.db,
+            &skill,
+            &app_type,
+            acknowledge_warnings.unwrap_or(false),
+        )
         .await
         .map_err(|e| e.to_string())
 }
@@ -144,19 +154,55 @@ pub async fn check_skill_updates(
 }
 
 /// 更新单个 Skill
+///
+/// - acknowledge_warnings: 用户已确认更新内容检查出的警告
 #[tauri::command]
 pub async fn update_skill(
     id: String,
+    acknowledge_warnings: Option<bool>,
     service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
 ) -> Result<InstalledSkill, String> {
     service
         .0
-        .update_skill(&app_state.db, &id)
+        .update_skill(&app_state.db, &id, acknowledge_warnings.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 从任意 git 仓库（可指定分支 / tag / commit）或本地目录安装 Skills
+#[tauri::command]
+pub async fn install_skill_from_source(
+    request: SkillSourceRequest,
+    current_app: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<InstalledSkill>, String> {
+    let app_type = parse_app_type(&current_app)?;
+    SkillService::install_from_source(&app_state.db, request, &app_type)
         .await
         .map_err(|e| e.to_string())
 }
 
+/// 把 git 来源的 Skill 固定到指定 ref；ref 为空时改为跟随默认分支
+///
+/// - acknowledge_warnings: 用户已确认新内容检查出的警告
+#[tauri::command]
+pub async fn pin_skill(
+    id: String,
+    git_ref: Option<String>,
+    acknowledge_warnings: Option<bool>,
+    app_state: State<'_, AppState>,
+) -> Result<InstalledSkill, String> {
+    SkillService::pin_skill(
+        &app_state.db,
+        &id,
+        git_ref,
+        acknowledge_warnings.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
 /// 迁移 Skill 存储位置
 #[tauri::command]
 pub async fn migrate_skill_storage(
@@ -254,7 +300,7 @@ pub async fn install_skill_for_app(
 
     service
         .0
-        .install(&app_state.db, &skill, &app_type)
+        .install(&app_state.db, &skill, &app_type, false)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -327,10 +373,56 @@ pub fn remove_skill_repo(
 pub fn install_skills_from_zip(
     file_path: String,
     current_app: String,
+    acknowledge_warnings: Option<bool>,
     app_state: State<'_, AppState>,
 ) -> Result<Vec<InstalledSkill>, String> {
     let app_type = parse_app_type(&current_app)?;
     let path = std::path::Path::new(&file_path);
 
-    SkillService::install_from_zip(&app_state.db, path, &app_type).map_err(|e| e.to_string())
+    SkillService::install_from_zip(
+        &app_state.db,
+        path,
+        &app_type,
+        acknowledge_warnings.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// ========== skills.lock ==========
+
+/// 导出 skills.lock
+#[tauri::command]
+pub fn export_skills_lock(
+    file_path: String,
+    app_state: State<'_, AppState>,
+) -> Result<SkillsLock, String> {
+    SkillLockService::export(&app_state.db, std::path::Path::new(&file_path))
+        .map_err(|e| e.to_string())
+}
+
+/// 检查本地 Skill 与 skills.lock 的差异
+#[tauri::command]
+pub fn check_skills_lock(
+    file_path: String,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<SkillLockDrift>, String> {
+    let lock =
+        SkillLockService::read(std::path::Path::new(&file_path)).map_err(|e| e.to_string())?;
+    SkillLockService::check(&app_state.db, &lock).map_err(|e| e.to_string())
+}
+
+/// 按 skills.lock 复现 Skill 集合
+#[tauri::command]
+pub async fn import_skills_lock(
+    file_path: String,
+    current_app: String,
+    service: State<'_, SkillServiceState>,
+    app_state: State<'_, AppState>,
+) -> Result<SkillLockImportResult, String> {
+    let app_type = parse_app_type(&current_app)?;
+    let lock =
+        SkillLockService::read(std::path::Path::new(&file_path)).map_err(|e| e.to_string())?;
+    SkillLockService::import(&app_state.db, &service.0, &lock, &app_type)
+        .await
+        .map_err(|e| e.to_string())
 }
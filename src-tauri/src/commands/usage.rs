@@ -1,6 +1,14 @@
 //! 使用统计相关命令
 
 use crate::error::AppError;
+use crate::services::budget_alerts::{self, BudgetAlert, BudgetAlertConfig, BudgetUsage};
+use crate::services::pricing_catalog::{
+    self, PricingImportOptions, PricingImportReport, ProviderModelPricing, UnpricedModel,
+};
+use crate::services::usage_export::{
+    self, UsageExportDataset, UsageExportFilter, UsageExportFormat, UsageExportResult,
+    UsageReportSchedule, UsageReportState,
+};
 use crate::services::usage_stats::*;
 use crate::store::AppState;
 use rust_decimal::Decimal;
@@ -114,12 +122,14 @@ pub fn get_model_pricing(state: State<'_, AppState>) -> Result<Vec<ModelPricingI
 
     let mut stmt = conn.prepare(
         "SELECT model_id, display_name, input_cost_per_million, output_cost_per_million,
-                cache_read_cost_per_million, cache_creation_cost_per_million
+                cache_read_cost_per_million, cache_creation_cost_per_million,
+                pricing_tiers, source
          FROM model_pricing
          ORDER BY display_name",
     )?;
 
     let rows = stmt.query_map([], |row| {
+        let tiers: Option<String> = row.get(6)?;
         Ok(ModelPricingInfo {
             model_id: row.get(0)?,
             display_name: row.get(1)?,
@@ -127,6 +137,10 @@ pub fn get_model_pricing(state: State<'_, AppState>) -> Result<Vec<ModelPricingI
             output_cost_per_million: row.get(3)?,
             cache_read_cost_per_million: row.get(4)?,
             cache_creation_cost_per_million: row.get(5)?,
+            pricing_tiers: tiers
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            source: row.get(7)?,
         })
     })?;
 
@@ -192,11 +206,19 @@ pub fn update_model_pricing(
 
     {
         let conn = crate::database::lock_conn!(db.conn);
+        // 手动编辑标记为 manual，目录导入默认不会覆盖；保留已有的分档定价
         conn.execute(
-            "INSERT OR REPLACE INTO model_pricing (
+            "INSERT INTO model_pricing (
                 model_id, display_name, input_cost_per_million, output_cost_per_million,
-                cache_read_cost_per_million, cache_creation_cost_per_million
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                cache_read_cost_per_million, cache_creation_cost_per_million, source
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'manual')
+            ON CONFLICT(model_id) DO UPDATE SET
+                display_name = excluded.display_name,
+                input_cost_per_million = excluded.input_cost_per_million,
+                output_cost_per_million = excluded.output_cost_per_million,
+                cache_read_cost_per_million = excluded.cache_read_cost_per_million,
+                cache_creation_cost_per_million = excluded.cache_creation_cost_per_million,
+                source = 'manual'",
             rusqlite::params![
                 model_id,
                 display_name,
@@ -242,6 +264,169 @@ pub fn delete_model_pricing(state: State<'_, AppState>, model_id: String) -> Res
     Ok(())
 }
 
+/// 导入外部定价目录（LiteLLM 格式）：优先本地文件，否则从 URL（默认 LiteLLM 官方）下载
+#[tauri::command]
+pub async fn import_model_pricing_catalog(
+    state: State<'_, AppState>,
+    file_path: Option<String>,
+    url: Option<String>,
+    options: Option<PricingImportOptions>,
+) -> Result<PricingImportReport, AppError> {
+    let json = match file_path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => std::fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?,
+        None => pricing_catalog::fetch_catalog(url.as_deref()).await?,
+    };
+    let options = options.unwrap_or_default();
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        pricing_catalog::import_catalog_json(&db, &json, &options)
+    })
+    .await
+    .map_err(|e| AppError::Message(format!("导入定价目录失败: {e}")))?
+}
+
+/// 获取有用量但没有定价的模型
+#[tauri::command]
+pub fn get_unpriced_models(state: State<'_, AppState>) -> Result<Vec<UnpricedModel>, AppError> {
+    state.db.get_unpriced_models()
+}
+
+/// 获取供应商定价覆盖
+#[tauri::command]
+pub fn get_provider_model_pricing(
+    state: State<'_, AppState>,
+    provider_id: Option<String>,
+    app_type: Option<String>,
+) -> Result<Vec<ProviderModelPricing>, AppError> {
+    state
+        .db
+        .list_provider_model_pricing(provider_id.as_deref(), app_type.as_deref())
+}
+
+/// 新增或更新供应商定价覆盖
+#[tauri::command]
+pub fn upsert_provider_model_pricing(
+    state: State<'_, AppState>,
+    pricing: ProviderModelPricing,
+) -> Result<(), AppError> {
+    state.db.upsert_provider_model_pricing(&pricing)?;
+    if let Err(e) = state.db.backfill_missing_usage_costs() {
+        log::warn!("供应商定价覆盖更新后回填历史用量成本失败: {e}");
+    }
+    Ok(())
+}
+
+/// 删除供应商定价覆盖
+#[tauri::command]
+pub fn delete_provider_model_pricing(
+    state: State<'_, AppState>,
+    provider_id: String,
+    app_type: String,
+    model_pattern: String,
+) -> Result<bool, AppError> {
+    state
+        .db
+        .delete_provider_model_pricing(&provider_id, &app_type, &model_pattern)
+}
+
+/// 导出用量数据（请求日志 / 日聚合 / 花费汇总）到 CSV 或 NDJSON 文件
+#[tauri::command]
+pub async fn export_usage_data(
+    state: State<'_, AppState>,
+    dataset: UsageExportDataset,
+    format: UsageExportFormat,
+    file_path: String,
+    filter: Option<UsageExportFilter>,
+) -> Result<UsageExportResult, AppError> {
+    let db = state.db.clone();
+    let filter = filter.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        usage_export::export_usage(
+            &db,
+            dataset,
+            format,
+            &filter,
+            std::path::Path::new(&file_path),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Message(format!("导出用量数据失败: {e}")))?
+}
+
+/// 获取定期用量报表配置
+#[tauri::command]
+pub fn get_usage_report_schedule(
+    state: State<'_, AppState>,
+) -> Result<UsageReportSchedule, AppError> {
+    state.db.get_usage_report_schedule()
+}
+
+/// 保存定期用量报表配置
+#[tauri::command]
+pub fn save_usage_report_schedule(
+    state: State<'_, AppState>,
+    schedule: UsageReportSchedule,
+) -> Result<(), AppError> {
+    state.db.save_usage_report_schedule(&schedule)
+}
+
+/// 立即生成上一完整周期的用量报表
+#[tauri::command]
+pub async fn run_usage_report_now(
+    state: State<'_, AppState>,
+) -> Result<Vec<UsageExportResult>, AppError> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || usage_export::run_report_now(&db))
+        .await
+        .map_err(|e| AppError::Message(format!("生成用量报表失败: {e}")))?
+}
+
+/// 获取定期用量报表的上次执行状态
+#[tauri::command]
+pub fn get_usage_report_state() -> Result<UsageReportState, AppError> {
+    Ok(usage_export::load_report_state())
+}
+
+/// 获取预算告警配置
+#[tauri::command]
+pub fn get_budget_alert_config(state: State<'_, AppState>) -> Result<BudgetAlertConfig, AppError> {
+    state.db.get_budget_alert_config()
+}
+
+/// 保存预算告警配置
+#[tauri::command]
+pub fn save_budget_alert_config(
+    state: State<'_, AppState>,
+    config: BudgetAlertConfig,
+) -> Result<(), AppError> {
+    state.db.save_budget_alert_config(&config)
+}
+
+/// 获取各预算在当前周期内的用量
+#[tauri::command]
+pub fn get_budget_usages(state: State<'_, AppState>) -> Result<Vec<BudgetUsage>, AppError> {
+    let config = state.db.get_budget_alert_config()?;
+    state
+        .db
+        .get_budget_usages(&config, chrono::Local::now().date_naive())
+}
+
+/// 获取最近触发的预算告警
+#[tauri::command]
+pub fn get_budget_alert_history() -> Result<Vec<BudgetAlert>, AppError> {
+    Ok(budget_alerts::load_state().history)
+}
+
+/// 向 webhook 发送一条测试消息
+#[tauri::command]
+pub async fn test_budget_webhook(url: String) -> Result<(), AppError> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Err(AppError::InvalidInput("Webhook URL 不能为空".to_string()));
+    }
+    budget_alerts::send_webhook(url, "[CC Switch] Budget alert webhook test").await
+}
+
 /// 手动触发会话日志同步
 #[tauri::command]
 pub fn sync_session_usage(
@@ -310,4 +495,10 @@ pub struct ModelPricingInfo {
     pub output_cost_per_million: String,
     pub cache_read_cost_per_million: String,
     pub cache_creation_cost_per_million: String,
+    /// 分档定价（长上下文）
+    #[serde(default)]
+    pub pricing_tiers: Vec<crate::proxy::usage::calculator::PricingTier>,
+    /// 定价来源：builtin / catalog / manual
+    #[serde(default)]
+    pub source: String,
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -149,13 +151,116 @@ pub fn get_provider_config_path(provider_id: &str, provider_name: Option<&str>)
     get_claude_config_dir().join(format!("settings-{base_name}.json"))
 }
 
+// ============================================================================
+// 写入暂存（dry-run）
+// ============================================================================
+
+/// 暂存的写入：`None` 表示文件被删除
+pub(crate) type StagedFiles = BTreeMap<PathBuf, Option<Vec<u8>>>;
+
+thread_local! {
+    static STAGED_WRITES: RefCell<Option<StagedFiles>> = const { RefCell::new(None) };
+}
+
+/// 在当前线程开启写入暂存：期间 `atomic_write` / `delete_file` 只记录在内存中，
+/// 通过 `live_path_exists` / `read_live_*` 读取时优先返回暂存内容。
+///
+/// 用于在不落盘的前提下复用真实写入流程，guard 释放时丢弃暂存内容。
+pub(crate) struct StagedWrites(());
+
+impl StagedWrites {
+    pub(crate) fn begin() -> Self {
+        STAGED_WRITES.with(|staged| *staged.borrow_mut() = Some(BTreeMap::new()));
+        Self(())
+    }
+
+    /// 结束暂存并取出所有写入
+    pub(crate) fn finish(self) -> StagedFiles {
+        STAGED_WRITES
+            .with(|staged| staged.borrow_mut().take())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for StagedWrites {
+    fn drop(&mut self) {
+        STAGED_WRITES.with(|staged| staged.borrow_mut().take());
+    }
+}
+
+/// 当前线程是否处于写入暂存中
+pub(crate) fn is_staging_writes() -> bool {
+    STAGED_WRITES.with(|staged| staged.borrow().is_some())
+}
+
+/// 暂存中时记录写入并返回 true
+fn stage_write(path: &Path, data: Option<&[u8]>) -> bool {
+    STAGED_WRITES.with(|staged| match staged.borrow_mut().as_mut() {
+        Some(files) => {
+            files.insert(path.to_path_buf(), data.map(<[u8]>::to_vec));
+            true
+        }
+        None => false,
+    })
+}
+
+/// 暂存中的文件内容；`Some(None)` 表示已暂存删除，`None` 表示未暂存
+fn staged_content(path: &Path) -> Option<Option<Vec<u8>>> {
+    STAGED_WRITES.with(|staged| {
+        staged
+            .borrow()
+            .as_ref()
+            .and_then(|files| files.get(path).cloned())
+    })
+}
+
+/// 文件或目录是否存在（考虑暂存的写入）
+pub(crate) fn live_path_exists(path: &Path) -> bool {
+    let staged = STAGED_WRITES.with(|staged| {
+        staged.borrow().as_ref().and_then(|files| {
+            if let Some(content) = files.get(path) {
+                return Some(content.is_some());
+            }
+            // 暂存写入的父目录在真实写入时会被创建
+            files
+                .iter()
+                .any(|(file, content)| content.is_some() && file.starts_with(path))
+                .then_some(true)
+        })
+    });
+    staged.unwrap_or_else(|| path.exists())
+}
+
+/// 读取文件字节（考虑暂存的写入）
+pub(crate) fn read_live_bytes(path: &Path) -> std::io::Result<Vec<u8>> {
+    match staged_content(path) {
+        Some(Some(bytes)) => Ok(bytes),
+        Some(None) => Err(std::io::Error::from(std::io::ErrorKind::NotFound)),
+        None => fs::read(path),
+    }
+}
+
+/// 读取文本文件（考虑暂存的写入）
+pub(crate) fn read_live_to_string(path: &Path) -> std::io::Result<String> {
+    let bytes = read_live_bytes(path)?;
+    String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// 创建目录；写入暂存期间不落盘
+pub(crate) fn create_live_dir(dir: &Path) -> Result<(), AppError> {
+    if is_staging_writes() {
+        return Ok(());
+    }
+    fs::create_dir_all(dir).map_err(|e| AppError::io(dir, e))
+}
+
 /// 读取 JSON 配置文件
 pub fn read_json_file<T: for<'a> Deserialize<'a>>(path: &Path) -> Result<T, AppError> {
-    if !path.exists() {
+    if !live_path_exists(path) {
         return Err(AppError::Config(format!("文件不存在: {}", path.display())));
     }
 
-    let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    let content = read_live_to_string(path).map_err(|e| AppError::io(path, e))?;
 
     serde_json::from_str(&content).map_err(|e| AppError::json(path, e))
 }
@@ -181,7 +286,7 @@ fn sort_json_keys(value: &Value) -> Value {
 pub fn write_json_file<T: Serialize>(path: &Path, data: &T) -> Result<(), AppError> {
     // 确保目录存在
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        create_live_dir(parent)?;
     }
 
     let value = serde_json::to_value(data).map_err(|e| AppError::JsonSerialize { source: e })?;
@@ -195,13 +300,17 @@ pub fn write_json_file<T: Serialize>(path: &Path, data: &T) -> Result<(), AppErr
 /// 原子写入文本文件（用于 TOML/纯文本）
 pub fn write_text_file(path: &Path, data: &str) -> Result<(), AppError> {
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        create_live_dir(parent)?;
     }
     atomic_write(path, data.as_bytes())
 }
 
 /// 原子写入：写入临时文件后 rename 替换，避免半写状态
 pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
+    if stage_write(path, Some(data)) {
+        return Ok(());
+    }
+
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
@@ -236,6 +345,8 @@ pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
         }
     }
 
+    crate::services::live_history::record_external_change(path);
+
     #[cfg(windows)]
     {
         // Windows 上 rename 目标存在会失败，先移除再重命名（尽量接近原子性）
@@ -255,6 +366,9 @@ pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
             source: e,
         })?;
     }
+
+    crate::services::live_drift::note_self_write(path, Some(data));
+    crate::services::live_history::record_write(path, Some(data));
     Ok(())
 }
 
@@ -401,8 +515,17 @@ pub fn copy_file(from: &Path, to: &Path) -> Result<(), AppError> {
 
 /// 删除文件
 pub fn delete_file(path: &Path) -> Result<(), AppError> {
+    if is_staging_writes() {
+        if live_path_exists(path) {
+            stage_write(path, None);
+        }
+        return Ok(());
+    }
     if path.exists() {
+        crate::services::live_history::record_external_change(path);
         fs::remove_file(path).map_err(|e| AppError::io(path, e))?;
+        crate::services::live_drift::note_self_write(path, None);
+        crate::services::live_history::record_write(path, None);
     }
     Ok(())
 }
@@ -0,0 +1,331 @@
+//! Crush 配置文件读写模块
+//!
+//! 处理 `~/.config/crush/crush.json` 配置文件的读写操作（JSON 格式）。
+//! Crush（Charm）使用累加式供应商管理，所有供应商共存于 `providers` 段，
+//! 切换时只改写 `models.large` 指向的供应商与模型。
+//!
+//! ## 配置结构示例
+//!
+//! ```json
+//! {
+//!   "$schema": "https://charm.land/crush.json",
+//!   "providers": {
+//!     "deepseek": {
+//!       "name": "DeepSeek",
+//!       "type": "openai-compat",
+//!       "base_url": "https://api.deepseek.com/v1",
+//!       "api_key": "$DEEPSEEK_API_KEY",
+//!       "models": [{ "id": "deepseek-chat", "name": "DeepSeek V3", "context_window": 64000 }]
+//!     }
+//!   },
+//!   "models": {
+//!     "large": { "model": "deepseek-chat", "provider": "deepseek" }
+//!   },
+//!   "mcp": {
+//!     "filesystem": { "type": "stdio", "command": "npx", "args": ["-y", "..."] }
+//!   }
+//! }
+//! ```
+
+use crate::config::{get_home_dir, live_path_exists, read_live_to_string, write_json_file};
+use crate::error::AppError;
+use crate::settings::get_crush_override_dir;
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+/// Crush 供应商支持的协议类型
+pub const CRUSH_PROVIDER_TYPES: &[&str] = &[
+    "openai",
+    "openai-compat",
+    "anthropic",
+    "gemini",
+    "azure",
+    "vertexai",
+];
+
+// ============================================================================
+// Path Functions
+// ============================================================================
+
+/// 获取 Crush 配置目录
+///
+/// 默认路径: `~/.config/crush/`
+/// 可通过 settings.crush_config_dir 覆盖
+pub fn get_crush_dir() -> PathBuf {
+    if let Some(override_dir) = get_crush_override_dir() {
+        return override_dir;
+    }
+
+    get_home_dir().join(".config").join("crush")
+}
+
+/// 获取 Crush 配置文件路径
+///
+/// 返回 `~/.config/crush/crush.json`
+pub fn get_crush_config_path() -> PathBuf {
+    get_crush_dir().join("crush.json")
+}
+
+/// 获取 Crush 数据目录
+///
+/// 优先级: XDG_DATA_HOME > ~/.local/share/crush；`projects.json` 记录了各项目的
+/// `.crush` 数据目录（会话数据库 crush.db 所在位置）。
+pub fn get_crush_data_dir() -> PathBuf {
+    if let Ok(xdg_data) = std::env::var("XDG_DATA_HOME") {
+        if !xdg_data.is_empty() {
+            return PathBuf::from(xdg_data).join("crush");
+        }
+    }
+
+    get_home_dir().join(".local").join("share").join("crush")
+}
+
+fn crush_write_lock() -> &'static Mutex<()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+}
+
+// ============================================================================
+// Core Read / Write
+// ============================================================================
+
+/// 读取 crush.json，文件不存在时返回仅含 `$schema` 的对象
+pub fn read_crush_config() -> Result<Value, AppError> {
+    let path = get_crush_config_path();
+    if !live_path_exists(&path) {
+        return Ok(json!({ "$schema": "https://charm.land/crush.json" }));
+    }
+
+    let content = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    if content.trim().is_empty() {
+        return Ok(json!({ "$schema": "https://charm.land/crush.json" }));
+    }
+
+    let value: Value = serde_json::from_str(&content).map_err(|e| AppError::json(&path, e))?;
+    if !value.is_object() {
+        return Err(AppError::Config(format!(
+            "Crush config must be a JSON object: {}",
+            path.display()
+        )));
+    }
+    Ok(value)
+}
+
+/// 在写锁内读改写 crush.json，内容未变化时不写盘
+fn update_crush_config<F>(updater: F) -> Result<(), AppError>
+where
+    F: FnOnce(&mut Map<String, Value>) -> Result<(), AppError>,
+{
+    let _guard = crush_write_lock().lock()?;
+    let mut config = read_crush_config()?;
+    let before = config.clone();
+    if let Some(obj) = config.as_object_mut() {
+        updater(obj)?;
+    }
+    if config == before {
+        return Ok(());
+    }
+
+    let path = get_crush_config_path();
+    write_json_file(&path, &config)?;
+    log::debug!("Crush config written to {path:?}");
+    Ok(())
+}
+
+fn section_mut<'a>(config: &'a mut Map<String, Value>, key: &str) -> &'a mut Map<String, Value> {
+    let entry = config
+        .entry(key.to_string())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !entry.is_object() {
+        *entry = Value::Object(Map::new());
+    }
+    match entry {
+        Value::Object(map) => map,
+        _ => unreachable!("section was just normalized to an object"),
+    }
+}
+
+fn read_section(key: &str) -> Result<Map<String, Value>, AppError> {
+    Ok(read_crush_config()?
+        .get(key)
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// 合并供应商条目：UI 提交的字段覆盖，磁盘上 UI 未涉及的字段保留
+/// （如 `extra_headers`、`disable_stream`）
+fn merge_provider_entry(existing: Option<&Value>, incoming: &Value) -> Value {
+    let mut merged = existing
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    if let Some(obj) = incoming.as_object() {
+        for (key, value) in obj {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    Value::Object(merged)
+}
+
+/// 验证 Crush 供应商配置的基本结构
+pub fn validate_crush_settings(settings: &Value) -> Result<(), AppError> {
+    let obj = settings.as_object().ok_or_else(|| {
+        AppError::localized(
+            "crush.validation.not_object",
+            "Crush 配置必须是 JSON 对象",
+            "Crush configuration must be a JSON object",
+        )
+    })?;
+
+    if let Some(kind) = obj.get("type") {
+        let valid = kind
+            .as_str()
+            .map(|t| CRUSH_PROVIDER_TYPES.contains(&t))
+            .unwrap_or(false);
+        if !valid {
+            return Err(AppError::localized(
+                "crush.validation.invalid_type",
+                format!(
+                    "Crush 配置格式错误: type 必须是 {}",
+                    CRUSH_PROVIDER_TYPES.join(" / ")
+                ),
+                format!(
+                    "Crush config invalid: type must be one of {}",
+                    CRUSH_PROVIDER_TYPES.join(", ")
+                ),
+            ));
+        }
+    }
+
+    if let Some(models) = obj.get("models") {
+        if !models.is_array() {
+            return Err(AppError::localized(
+                "crush.validation.invalid_models",
+                "Crush 配置格式错误: models 必须是数组",
+                "Crush config invalid: models must be an array",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Provider Functions
+// ============================================================================
+
+/// 读取 `providers` 段（键为供应商 id）
+pub fn get_providers() -> Result<Map<String, Value>, AppError> {
+    read_section("providers")
+}
+
+/// 写入（新增或更新）一个供应商
+pub fn set_provider(id: &str, settings: &Value) -> Result<(), AppError> {
+    update_crush_config(|config| {
+        let providers = section_mut(config, "providers");
+        let merged = merge_provider_entry(providers.get(id), settings);
+        providers.insert(id.to_string(), merged);
+        Ok(())
+    })
+}
+
+/// 删除一个供应商；`models.*` 中仍引用它的条目保持不变，由 Crush 自行回退
+pub fn remove_provider(id: &str) -> Result<(), AppError> {
+    update_crush_config(|config| {
+        if let Some(providers) = config.get_mut("providers").and_then(Value::as_object_mut) {
+            providers.remove(id);
+        }
+        Ok(())
+    })
+}
+
+/// 切换供应商时更新 `models.large`
+///
+/// 新供应商未声明模型时保留原模型 id；`models.large` 中的其他字段
+/// （如 `reasoning_effort`、`max_tokens`）保持不变。
+pub fn apply_switch_defaults(provider_id: &str, settings: &Value) -> Result<(), AppError> {
+    let first_model = settings
+        .get("models")
+        .and_then(Value::as_array)
+        .and_then(|models| models.first())
+        .and_then(|m| m.get("id"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+
+    update_crush_config(|config| {
+        let models = section_mut(config, "models");
+        let large = section_mut(models, "large");
+        large.insert("provider".to_string(), json!(provider_id));
+        if let Some(model) = first_model {
+            large.insert("model".to_string(), json!(model));
+        }
+        Ok(())
+    })
+}
+
+/// 当前 `models.large.provider`
+pub fn get_active_provider() -> Result<Option<String>, AppError> {
+    Ok(read_crush_config()?
+        .pointer("/models/large/provider")
+        .and_then(Value::as_str)
+        .map(str::to_string))
+}
+
+// ============================================================================
+// MCP Section Access
+// ============================================================================
+
+/// 读取 `mcp` 段（键为服务器 id）
+pub fn get_mcp_servers() -> Result<Map<String, Value>, AppError> {
+    read_section("mcp")
+}
+
+/// 在写锁内读改写 `mcp` 段
+pub fn update_mcp_servers<F>(updater: F) -> Result<(), AppError>
+where
+    F: FnOnce(&mut Map<String, Value>) -> Result<(), AppError>,
+{
+    update_crush_config(|config| updater(section_mut(config, "mcp")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_provider_entry_keeps_unknown_fields() {
+        let existing = json!({
+            "type": "openai",
+            "base_url": "https://old.example/v1",
+            "extra_headers": { "X-Org": "acme" },
+            "disable_stream": true
+        });
+        let incoming = json!({ "type": "openai-compat", "base_url": "https://new.example/v1" });
+        let merged = merge_provider_entry(Some(&existing), &incoming);
+
+        assert_eq!(merged["type"], "openai-compat");
+        assert_eq!(merged["base_url"], "https://new.example/v1");
+        assert_eq!(merged["extra_headers"]["X-Org"], "acme");
+        assert_eq!(merged["disable_stream"], true);
+    }
+
+    #[test]
+    fn section_mut_replaces_non_object_sections() {
+        let mut config = Map::new();
+        config.insert("models".to_string(), json!("broken"));
+        section_mut(&mut config, "models").insert("large".to_string(), json!({}));
+        assert_eq!(Value::Object(config), json!({ "models": { "large": {} } }));
+    }
+
+    #[test]
+    fn validate_checks_type_and_models() {
+        assert!(validate_crush_settings(&json!({ "type": "openai-compat", "models": [] })).is_ok());
+        assert!(validate_crush_settings(&json!({ "type": "bedrock-ish" })).is_err());
+        assert!(validate_crush_settings(&json!({ "models": {} })).is_err());
+        assert!(validate_crush_settings(&json!([])).is_err());
+    }
+}
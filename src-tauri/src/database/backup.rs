@@ -22,6 +22,10 @@ const SYNC_SKIP_TABLES: &[&str] = &[
     "provider_health",
     "proxy_live_backup",
     "usage_daily_rollups",
+    "session_archives",
+    "mcp_secrets",
+    "projects",
+    "profile_switch_backup",
 ];
 
 /// Tables whose local data is preserved (restored from local snapshot) during WebDAV import.
@@ -31,8 +35,15 @@ const SYNC_PRESERVE_TABLES: &[&str] = &[
     "stream_check_logs",
     "proxy_live_backup",
     "usage_daily_rollups",
+    "session_archives",
+    "mcp_secrets",
+    "projects",
+    "profile_switch_backup",
 ];
 
+/// 本机密钥表：任何导出都不写入其数据，导入时保留本地内容
+const LOCAL_SECRET_TABLES: &[&str] = &["mcp_secrets"];
+
 /// A database backup entry for the UI
 #[derive(Debug, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -43,10 +54,10 @@ pub struct BackupEntry {
 }
 
 impl Database {
-    /// 导出为 SQLite 兼容的 SQL 文本（内存字符串，完整导出）
+    /// 导出为 SQLite 兼容的 SQL 文本（内存字符串，除本机密钥外完整导出）
     pub fn export_sql_string(&self) -> Result<String, AppError> {
         let snapshot = self.snapshot_to_memory()?;
-        Self::dump_sql(&snapshot, &[])
+        Self::dump_sql(&snapshot, LOCAL_SECRET_TABLES)
     }
 
     /// Export SQL for sync (WebDAV), skipping local-only tables' data
@@ -82,7 +93,7 @@ impl Database {
 
     /// 从 SQL 字符串导入，返回生成的备份 ID（若无备份则为空字符串）
     pub fn import_sql_string(&self, sql_raw: &str) -> Result<String, AppError> {
-        self.import_sql_string_inner(sql_raw, &[])
+        self.import_sql_string_inner(sql_raw, LOCAL_SECRET_TABLES)
     }
 
     /// Import SQL generated for sync, then restore local-only tables from the
@@ -781,6 +792,29 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn mcp_secrets_are_never_exported_and_survive_import() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        db.set_mcp_secret("github_token", "ghp_secret_value", None)?;
+
+        let sync_sql = db.export_sql_string_for_sync()?;
+        let full_sql = db.export_sql_string()?;
+        assert!(!sync_sql.contains("ghp_secret_value"));
+        assert!(!full_sql.contains("ghp_secret_value"));
+
+        let remote_db = Database::memory()?;
+        let remote_sql = remote_db.export_sql_string_for_sync()?;
+        db.import_sql_string_for_sync(&remote_sql)?;
+        db.import_sql_string(&full_sql)?;
+
+        let values = db.get_mcp_secret_values()?;
+        assert_eq!(
+            values.get("github_token").map(String::as_str),
+            Some("ghp_secret_value")
+        );
+        Ok(())
+    }
+
     #[test]
     #[serial]
     fn periodic_maintenance_runs_even_when_auto_backup_disabled() -> Result<(), AppError> {
@@ -0,0 +1,165 @@
+//! 团队配置包 DAO
+//!
+//! bundle_imports 记录每个配置包上次导入的内容，用作再次导入时三方合并的基线。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::bundle::{BundleImportRecord, BundleModelPricing};
+use rusqlite::{params, OptionalExtension};
+
+/// 配置包导入的定价来源标记
+pub const PRICING_SOURCE_BUNDLE: &str = "bundle";
+
+impl Database {
+    /// 获取全部配置包导入记录
+    pub fn list_bundle_imports(&self) -> Result<Vec<BundleImportRecord>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn.prepare(
+            "SELECT bundle_id, name, version, publisher, imported_at
+             FROM bundle_imports ORDER BY name ASC, bundle_id ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(BundleImportRecord {
+                bundle_id: row.get(0)?,
+                name: row.get(1)?,
+                version: row.get(2)?,
+                publisher: row.get(3)?,
+                imported_at: row.get(4)?,
+            })
+        })?;
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(row?);
+        }
+        Ok(records)
+    }
+
+    /// 获取配置包上次导入的原始内容
+    pub fn get_bundle_import_content(&self, bundle_id: &str) -> Result<Option<String>, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT content FROM bundle_imports WHERE bundle_id = ?1",
+            params![bundle_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 记录配置包导入
+    pub fn save_bundle_import(
+        &self,
+        record: &BundleImportRecord,
+        content: &str,
+    ) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO bundle_imports
+                (bundle_id, name, version, publisher, content, imported_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                record.bundle_id,
+                record.name,
+                record.version,
+                record.publisher,
+                content,
+                record.imported_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 读取模型定价（用于导出到配置包）
+    pub fn get_bundle_model_pricing(
+        &self,
+        model_id: &str,
+    ) -> Result<Option<BundleModelPricing>, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT model_id, display_name, input_cost_per_million, output_cost_per_million,
+                    cache_read_cost_per_million, cache_creation_cost_per_million, pricing_tiers
+             FROM model_pricing WHERE model_id = ?1",
+            params![model_id],
+            |row| {
+                let tiers: Option<String> = row.get(6)?;
+                Ok(BundleModelPricing {
+                    model_id: row.get(0)?,
+                    display_name: row.get(1)?,
+                    input_cost_per_million: row.get(2)?,
+                    output_cost_per_million: row.get(3)?,
+                    cache_read_cost_per_million: row.get(4)?,
+                    cache_creation_cost_per_million: row.get(5)?,
+                    pricing_tiers: tiers
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 写入配置包中的模型定价（来源标记为 bundle）
+    pub fn upsert_bundle_model_pricing(
+        &self,
+        pricing: &BundleModelPricing,
+    ) -> Result<(), AppError> {
+        let tiers_json = if pricing.pricing_tiers.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::to_string(&pricing.pricing_tiers)
+                    .map_err(|e| AppError::Message(format!("序列化分档定价失败: {e}")))?,
+            )
+        };
+        {
+            let conn = lock_conn!(self.conn);
+            conn.execute(
+                "INSERT INTO model_pricing (
+                    model_id, display_name, input_cost_per_million, output_cost_per_million,
+                    cache_read_cost_per_million, cache_creation_cost_per_million,
+                    pricing_tiers, source
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                ON CONFLICT(model_id) DO UPDATE SET
+                    display_name = excluded.display_name,
+                    input_cost_per_million = excluded.input_cost_per_million,
+                    output_cost_per_million = excluded.output_cost_per_million,
+                    cache_read_cost_per_million = excluded.cache_read_cost_per_million,
+                    cache_creation_cost_per_million = excluded.cache_creation_cost_per_million,
+                    pricing_tiers = excluded.pricing_tiers,
+                    source = excluded.source",
+                params![
+                    pricing.model_id,
+                    pricing.display_name,
+                    pricing.input_cost_per_million,
+                    pricing.output_cost_per_million,
+                    pricing.cache_read_cost_per_million,
+                    pricing.cache_creation_cost_per_million,
+                    tiers_json,
+                    PRICING_SOURCE_BUNDLE,
+                ],
+            )
+            .map_err(|e| AppError::Database(format!("写入模型定价失败: {e}")))?;
+        }
+
+        if let Err(e) = self.backfill_missing_usage_costs_for_model(&pricing.model_id) {
+            log::warn!(
+                "配置包定价导入后回填历史用量成本失败 (model_id={}): {e}",
+                pricing.model_id
+            );
+        }
+        Ok(())
+    }
+
+    /// 删除模型定价
+    pub fn delete_bundle_model_pricing(&self, model_id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "DELETE FROM model_pricing WHERE model_id = ?1",
+            params![model_id],
+        )
+        .map_err(|e| AppError::Database(format!("删除模型定价失败: {e}")))?;
+        Ok(())
+    }
+}
@@ -5,15 +5,17 @@
 use crate::app_config::{McpApps, McpServer};
 use crate::database::{lock_conn, Database};
 use crate::error::AppError;
+use crate::mcp::{McpProbeResult, McpSecretInfo, SecretResolver};
 use indexmap::IndexMap;
 use rusqlite::params;
+use std::collections::HashMap;
 
 impl Database {
     /// 获取所有 MCP 服务器
     pub fn get_all_mcp_servers(&self) -> Result<IndexMap<String, McpServer>, AppError> {
         let conn = lock_conn!(self.conn);
         let mut stmt = conn.prepare(
-            "SELECT id, name, server_config, description, homepage, docs, tags, enabled_claude, enabled_codex, enabled_gemini, enabled_opencode, enabled_hermes
+            "SELECT id, name, server_config, description, homepage, docs, tags, enabled_claude, enabled_codex, enabled_gemini, enabled_opencode, enabled_hermes, enabled_qwen, enabled_goose, enabled_crush
              FROM mcp_servers
              ORDER BY name ASC, id ASC"
         ).map_err(|e| AppError::Database(e.to_string()))?;
@@ -32,6 +34,9 @@ impl Database {
                 let enabled_gemini: bool = row.get(9)?;
                 let enabled_opencode: bool = row.get(10)?;
                 let enabled_hermes: bool = row.get(11)?;
+                let enabled_qwen: bool = row.get(12)?;
+                let enabled_goose: bool = row.get(13)?;
+                let enabled_crush: bool = row.get(14)?;
 
                 let server = serde_json::from_str(&server_config_str).unwrap_or_default();
                 let tags = serde_json::from_str(&tags_str).unwrap_or_default();
@@ -48,6 +53,9 @@ impl Database {
                             gemini: enabled_gemini,
                             opencode: enabled_opencode,
                             hermes: enabled_hermes,
+                            qwen: enabled_qwen,
+                            goose: enabled_goose,
+                            crush: enabled_crush,
                         },
                         description,
                         homepage,
@@ -69,11 +77,30 @@ impl Database {
     /// 保存 MCP 服务器
     pub fn save_mcp_server(&self, server: &McpServer) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
+        // 保留探测结果；连接定义变化时旧的探测结果不再可信，一并清空
         conn.execute(
-            "INSERT OR REPLACE INTO mcp_servers (
+            "INSERT INTO mcp_servers (
                 id, name, server_config, description, homepage, docs, tags,
-                enabled_claude, enabled_codex, enabled_gemini, enabled_opencode, enabled_hermes
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                enabled_claude, enabled_codex, enabled_gemini, enabled_opencode, enabled_hermes,
+                enabled_qwen, enabled_goose, enabled_crush
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                last_probe = CASE WHEN mcp_servers.server_config = excluded.server_config
+                    THEN mcp_servers.last_probe ELSE NULL END,
+                server_config = excluded.server_config,
+                description = excluded.description,
+                homepage = excluded.homepage,
+                docs = excluded.docs,
+                tags = excluded.tags,
+                enabled_claude = excluded.enabled_claude,
+                enabled_codex = excluded.enabled_codex,
+                enabled_gemini = excluded.enabled_gemini,
+                enabled_opencode = excluded.enabled_opencode,
+                enabled_hermes = excluded.enabled_hermes,
+                enabled_qwen = excluded.enabled_qwen,
+                enabled_goose = excluded.enabled_goose,
+                enabled_crush = excluded.enabled_crush",
             params![
                 server.id,
                 server.name,
@@ -90,6 +117,9 @@ impl Database {
                 server.apps.gemini,
                 server.apps.opencode,
                 server.apps.hermes,
+                server.apps.qwen,
+                server.apps.goose,
+                server.apps.crush,
             ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
@@ -103,4 +133,136 @@ impl Database {
             .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(())
     }
+
+    /// 获取所有 MCP 服务器的最近一次探测结果
+    pub fn get_mcp_probe_results(&self) -> Result<HashMap<String, McpProbeResult>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare("SELECT id, last_probe FROM mcp_servers WHERE last_probe IS NOT NULL")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut results = HashMap::new();
+        for row in rows {
+            let (id, json) = row.map_err(|e| AppError::Database(e.to_string()))?;
+            // 解析失败（旧格式等）视为未探测
+            if let Ok(result) = serde_json::from_str(&json) {
+                results.insert(id, result);
+            }
+        }
+        Ok(results)
+    }
+
+    /// 保存 MCP 服务器的探测结果
+    pub fn save_mcp_probe_result(
+        &self,
+        id: &str,
+        result: &McpProbeResult,
+    ) -> Result<bool, AppError> {
+        let json = serde_json::to_string(result)
+            .map_err(|e| AppError::Database(format!("Failed to serialize probe result: {e}")))?;
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute(
+                "UPDATE mcp_servers SET last_probe = ?2 WHERE id = ?1",
+                params![id, json],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
+    // ========================================================================
+    // 本地密钥库（mcp_secrets 表不参与同步与导出）
+    // ========================================================================
+
+    /// 列出本地密钥（不含值）
+    pub fn list_mcp_secrets(&self) -> Result<Vec<McpSecretInfo>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT name, description, created_at, updated_at
+                 FROM mcp_secrets ORDER BY name ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(McpSecretInfo {
+                    name: row.get(0)?,
+                    description: row.get(1)?,
+                    created_at: row.get(2)?,
+                    updated_at: row.get(3)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut secrets = Vec::new();
+        for row in rows {
+            secrets.push(row.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(secrets)
+    }
+
+    /// 读取全部密钥值（仅供写入 live 配置时解析占位符）
+    pub fn get_mcp_secret_values(&self) -> Result<HashMap<String, String>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare("SELECT name, value FROM mcp_secrets")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut values = HashMap::new();
+        for row in rows {
+            let (name, value) = row.map_err(|e| AppError::Database(e.to_string()))?;
+            values.insert(name, value);
+        }
+        Ok(values)
+    }
+
+    /// 基于当前密钥库构建占位符解析器；读取失败时按空密钥库处理
+    pub fn mcp_secret_resolver(&self) -> SecretResolver {
+        let secrets = self.get_mcp_secret_values().unwrap_or_else(|e| {
+            log::warn!("读取 MCP 密钥库失败，密钥引用将无法解析: {e}");
+            HashMap::new()
+        });
+        SecretResolver::new(secrets)
+    }
+
+    /// 新增或更新密钥
+    pub fn set_mcp_secret(
+        &self,
+        name: &str,
+        value: &str,
+        description: Option<&str>,
+    ) -> Result<(), AppError> {
+        let now = chrono::Utc::now().timestamp();
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO mcp_secrets (name, value, description, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(name) DO UPDATE SET
+                value = excluded.value,
+                description = excluded.description,
+                updated_at = excluded.updated_at",
+            params![name, value, description, now],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除密钥，返回是否存在
+    pub fn delete_mcp_secret(&self, name: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute("DELETE FROM mcp_secrets WHERE name = ?1", params![name])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
 }
@@ -2,12 +2,16 @@
 //!
 //! Database access operations for each domain
 
+pub mod bundles;
 pub mod failover;
 pub mod mcp;
+pub mod profiles;
+pub mod projects;
 pub mod prompts;
 pub mod providers;
 pub mod providers_seed;
 pub mod proxy;
+pub mod session_archives;
 pub mod settings;
 pub mod skills;
 pub mod stream_check;
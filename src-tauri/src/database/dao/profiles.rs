@@ -0,0 +1,141 @@
+//! 配置档案 DAO
+//!
+//! profiles 表参与同步；profile_switch_backup 只保存本机切换进行中的回滚快照。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::profile::{Profile, ProfileSelections};
+use rusqlite::params;
+
+fn row_to_profile(row: &rusqlite::Row<'_>) -> rusqlite::Result<Profile> {
+    let id: String = row.get(0)?;
+    let selections: String = row.get(2)?;
+    // 解析失败视为空档案，避免单个坏行影响整个列表
+    let apps = serde_json::from_str(&selections).unwrap_or_else(|e| {
+        log::warn!("解析配置档案 {id} 的选择失败: {e}");
+        Default::default()
+    });
+    Ok(Profile {
+        id,
+        name: row.get(1)?,
+        apps,
+        sort_index: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+impl Database {
+    /// 获取全部配置档案（按排序号、名称排序）
+    pub fn list_profiles(&self) -> Result<Vec<Profile>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn.prepare(
+            "SELECT id, name, selections, sort_index, created_at, updated_at
+             FROM profiles ORDER BY COALESCE(sort_index, 999999), name ASC, id ASC",
+        )?;
+        let rows = stmt.query_map([], row_to_profile)?;
+        let mut profiles = Vec::new();
+        for row in rows {
+            profiles.push(row?);
+        }
+        Ok(profiles)
+    }
+
+    /// 按 ID 获取配置档案
+    pub fn get_profile(&self, id: &str) -> Result<Option<Profile>, AppError> {
+        let conn = lock_conn!(self.conn);
+        match conn.query_row(
+            "SELECT id, name, selections, sort_index, created_at, updated_at
+             FROM profiles WHERE id = ?1",
+            params![id],
+            row_to_profile,
+        ) {
+            Ok(profile) => Ok(Some(profile)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(AppError::Database(e.to_string())),
+        }
+    }
+
+    /// 新增或更新配置档案
+    pub fn save_profile(&self, profile: &Profile) -> Result<(), AppError> {
+        let selections = serde_json::to_string(&profile.apps)
+            .map_err(|e| AppError::Database(format!("Failed to serialize profile: {e}")))?;
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO profiles (id, name, selections, sort_index, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                profile.id,
+                profile.name,
+                selections,
+                profile.sort_index,
+                profile.created_at,
+                profile.updated_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除配置档案
+    pub fn delete_profile(&self, id: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute("DELETE FROM profiles WHERE id = ?1", params![id])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
+    // ==================== Switch Backup ====================
+
+    /// 保存切换前的状态快照（同一时间只有一次切换）
+    pub fn save_profile_switch_backup(
+        &self,
+        profile_id: &str,
+        snapshot: &ProfileSelections,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(snapshot)
+            .map_err(|e| AppError::Database(format!("Failed to serialize snapshot: {e}")))?;
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO profile_switch_backup (id, profile_id, snapshot, backed_up_at)
+             VALUES (1, ?1, ?2, ?3)",
+            params![profile_id, json, chrono::Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 获取未完成切换留下的快照
+    pub fn get_profile_switch_backup(
+        &self,
+    ) -> Result<Option<(String, ProfileSelections)>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let row: Option<(String, String)> = match conn.query_row(
+            "SELECT profile_id, snapshot FROM profile_switch_backup WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ) {
+            Ok(row) => Some(row),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(AppError::Database(e.to_string())),
+        };
+        match row {
+            Some((profile_id, json)) => {
+                let snapshot = serde_json::from_str(&json).map_err(|e| {
+                    AppError::Database(format!("Failed to parse profile switch backup: {e}"))
+                })?;
+                Ok(Some((profile_id, snapshot)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// 删除切换快照
+    pub fn delete_profile_switch_backup(&self) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute("DELETE FROM profile_switch_backup", [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! 项目作用域 DAO
+//!
+//! 项目目录是本机路径，projects 表不参与云同步。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::project::{ProjectAppliedState, ProjectScope};
+use rusqlite::params;
+
+fn row_to_project(row: &rusqlite::Row<'_>) -> rusqlite::Result<(ProjectScope, String)> {
+    Ok((
+        ProjectScope {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            path: row.get(2)?,
+            apps: Default::default(),
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        },
+        row.get(3)?,
+    ))
+}
+
+fn with_selections((mut project, selections): (ProjectScope, String)) -> ProjectScope {
+    // 解析失败视为未选择任何内容，避免单个坏行影响整个列表
+    match serde_json::from_str(&selections) {
+        Ok(apps) => project.apps = apps,
+        Err(e) => log::warn!("解析项目 {} 的选择失败: {e}", project.id),
+    }
+    project
+}
+
+impl Database {
+    /// 获取全部项目（按名称排序）
+    pub fn list_projects(&self) -> Result<Vec<ProjectScope>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn.prepare(
+            "SELECT id, name, path, selections, created_at, updated_at
+             FROM projects ORDER BY name ASC, id ASC",
+        )?;
+        let rows = stmt.query_map([], row_to_project)?;
+        let mut projects = Vec::new();
+        for row in rows {
+            projects.push(with_selections(row?));
+        }
+        Ok(projects)
+    }
+
+    /// 按 ID 获取项目
+    pub fn get_project(&self, id: &str) -> Result<Option<ProjectScope>, AppError> {
+        let conn = lock_conn!(self.conn);
+        match conn.query_row(
+            "SELECT id, name, path, selections, created_at, updated_at
+             FROM projects WHERE id = ?1",
+            params![id],
+            row_to_project,
+        ) {
+            Ok(row) => Ok(Some(with_selections(row))),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(AppError::Database(e.to_string())),
+        }
+    }
+
+    /// 新增或更新项目（保留上次写入记录）
+    pub fn save_project(&self, project: &ProjectScope) -> Result<(), AppError> {
+        let selections = serde_json::to_string(&project.apps)
+            .map_err(|e| AppError::Database(format!("Failed to serialize project: {e}")))?;
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO projects (id, name, path, selections, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                path = excluded.path,
+                selections = excluded.selections,
+                updated_at = excluded.updated_at",
+            params![
+                project.id,
+                project.name,
+                project.path,
+                selections,
+                project.created_at,
+                project.updated_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除项目记录（不会改动项目目录中的文件）
+    pub fn delete_project(&self, id: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute("DELETE FROM projects WHERE id = ?1", params![id])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
+    /// 读取项目上次写入的文件记录
+    pub fn get_project_applied_state(
+        &self,
+        id: &str,
+    ) -> Result<Option<ProjectAppliedState>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let json: Option<String> = match conn.query_row(
+            "SELECT applied_state FROM projects WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        ) {
+            Ok(json) => json,
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(AppError::Database(e.to_string())),
+        };
+        Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    /// 记录项目本次写入的文件
+    pub fn save_project_applied_state(
+        &self,
+        id: &str,
+        state: &ProjectAppliedState,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(state)
+            .map_err(|e| AppError::Database(format!("Failed to serialize applied state: {e}")))?;
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "UPDATE projects SET applied_state = ?2 WHERE id = ?1",
+            params![id, json],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -1,13 +1,48 @@
 //! 提示词数据访问对象
 //!
-//! 提供提示词（Prompt）的 CRUD 操作。
+//! 提供提示词（Prompt）与跨应用共享提示词（SharedPrompt）的 CRUD 操作。
 
+use crate::app_config::PromptApps;
 use crate::database::{lock_conn, Database};
 use crate::error::AppError;
-use crate::prompt::Prompt;
+use crate::prompt::{Prompt, SharedPrompt};
+use crate::services::prompt::PromptStackConfig;
 use indexmap::IndexMap;
 use rusqlite::params;
 
+const PROMPT_STACK_CONFIG_KEY: &str = "prompt_stack_config";
+
+const SHARED_PROMPT_COLUMNS: &str = "id, name, content, description, enabled_claude, enabled_codex,
+    enabled_gemini, enabled_opencode, enabled_openclaw, enabled_hermes, enabled_qwen,
+    overrides, created_at, updated_at";
+
+fn row_to_shared_prompt(row: &rusqlite::Row<'_>) -> rusqlite::Result<SharedPrompt> {
+    let id: String = row.get(0)?;
+    let overrides: String = row.get(11)?;
+    let overrides = serde_json::from_str(&overrides).unwrap_or_else(|e| {
+        log::warn!("解析共享提示词 {id} 的应用专属段落失败: {e}");
+        Default::default()
+    });
+    Ok(SharedPrompt {
+        id,
+        name: row.get(1)?,
+        content: row.get(2)?,
+        description: row.get(3)?,
+        apps: PromptApps {
+            claude: row.get(4)?,
+            codex: row.get(5)?,
+            gemini: row.get(6)?,
+            opencode: row.get(7)?,
+            openclaw: row.get(8)?,
+            hermes: row.get(9)?,
+            qwen: row.get(10)?,
+        },
+        overrides,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+    })
+}
+
 impl Database {
     /// 获取指定应用类型的所有提示词
     pub fn get_prompts(&self, app_type: &str) -> Result<IndexMap<String, Prompt>, AppError> {
@@ -85,4 +120,90 @@ impl Database {
         .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(())
     }
+
+    /// 获取多片段提示词配置（各应用的片段顺序与自定义模板变量）
+    pub fn get_prompt_stack_config(&self) -> Result<PromptStackConfig, AppError> {
+        match self.get_setting(PROMPT_STACK_CONFIG_KEY)? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Message(format!("解析提示词片段配置失败: {e}"))),
+            None => Ok(PromptStackConfig::default()),
+        }
+    }
+
+    /// 保存多片段提示词配置
+    pub fn save_prompt_stack_config(&self, config: &PromptStackConfig) -> Result<(), AppError> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Message(format!("序列化提示词片段配置失败: {e}")))?;
+        self.set_setting(PROMPT_STACK_CONFIG_KEY, &json)
+    }
+
+    /// 获取所有共享提示词
+    pub fn get_shared_prompts(&self) -> Result<IndexMap<String, SharedPrompt>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {SHARED_PROMPT_COLUMNS} FROM shared_prompts
+             ORDER BY created_at ASC, id ASC"
+        ))?;
+        let rows = stmt.query_map([], row_to_shared_prompt)?;
+        let mut prompts = IndexMap::new();
+        for row in rows {
+            let prompt = row?;
+            prompts.insert(prompt.id.clone(), prompt);
+        }
+        Ok(prompts)
+    }
+
+    /// 按 ID 获取共享提示词
+    pub fn get_shared_prompt(&self, id: &str) -> Result<Option<SharedPrompt>, AppError> {
+        let conn = lock_conn!(self.conn);
+        match conn.query_row(
+            &format!("SELECT {SHARED_PROMPT_COLUMNS} FROM shared_prompts WHERE id = ?1"),
+            params![id],
+            row_to_shared_prompt,
+        ) {
+            Ok(prompt) => Ok(Some(prompt)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(AppError::Database(e.to_string())),
+        }
+    }
+
+    /// 保存共享提示词
+    pub fn save_shared_prompt(&self, prompt: &SharedPrompt) -> Result<(), AppError> {
+        let overrides = serde_json::to_string(&prompt.overrides)
+            .map_err(|e| AppError::Database(format!("Failed to serialize overrides: {e}")))?;
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            &format!(
+                "INSERT OR REPLACE INTO shared_prompts ({SHARED_PROMPT_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)"
+            ),
+            params![
+                prompt.id,
+                prompt.name,
+                prompt.content,
+                prompt.description,
+                prompt.apps.claude,
+                prompt.apps.codex,
+                prompt.apps.gemini,
+                prompt.apps.opencode,
+                prompt.apps.openclaw,
+                prompt.apps.hermes,
+                prompt.apps.qwen,
+                overrides,
+                prompt.created_at,
+                prompt.updated_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除共享提示词
+    pub fn delete_shared_prompt(&self, id: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute("DELETE FROM shared_prompts WHERE id = ?1", params![id])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
 }
@@ -0,0 +1,111 @@
+//! 会话归档索引 DAO
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::session_archive::{ArchivedSession, SessionRetentionConfig};
+use rusqlite::params;
+
+const RETENTION_CONFIG_KEY: &str = "session_retention_config";
+
+fn row_to_archived_session(row: &rusqlite::Row<'_>) -> rusqlite::Result<ArchivedSession> {
+    Ok(ArchivedSession {
+        id: row.get(0)?,
+        provider_id: row.get(1)?,
+        session_id: row.get(2)?,
+        title: row.get(3)?,
+        project_dir: row.get(4)?,
+        source_path: row.get(5)?,
+        archive_path: row.get(6)?,
+        original_size: row.get::<_, i64>(7)? as u64,
+        archived_size: row.get::<_, i64>(8)? as u64,
+        last_active_at: row.get(9)?,
+        archived_at: row.get(10)?,
+        reason: row.get(11)?,
+    })
+}
+
+const ARCHIVE_COLUMNS: &str = "id, provider_id, session_id, title, project_dir, source_path,
+    archive_path, original_size, archived_size, last_active_at, archived_at, reason";
+
+impl Database {
+    /// 获取全部归档会话（按归档时间倒序）
+    pub fn list_session_archives(&self) -> Result<Vec<ArchivedSession>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let sql = format!(
+            "SELECT {ARCHIVE_COLUMNS} FROM session_archives ORDER BY archived_at DESC, id ASC"
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map([], row_to_archived_session)?;
+        let mut archives = Vec::new();
+        for row in rows {
+            archives.push(row?);
+        }
+        Ok(archives)
+    }
+
+    /// 按 ID 获取归档会话
+    pub fn get_session_archive(&self, id: &str) -> Result<Option<ArchivedSession>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let sql = format!("SELECT {ARCHIVE_COLUMNS} FROM session_archives WHERE id = ?1");
+        match conn.query_row(&sql, params![id], row_to_archived_session) {
+            Ok(archive) => Ok(Some(archive)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(AppError::Database(e.to_string())),
+        }
+    }
+
+    /// 写入归档索引
+    pub fn insert_session_archive(&self, archive: &ArchivedSession) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO session_archives (
+                id, provider_id, session_id, title, project_dir, source_path,
+                archive_path, original_size, archived_size, last_active_at, archived_at, reason
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                archive.id,
+                archive.provider_id,
+                archive.session_id,
+                archive.title,
+                archive.project_dir,
+                archive.source_path,
+                archive.archive_path,
+                archive.original_size as i64,
+                archive.archived_size as i64,
+                archive.last_active_at,
+                archive.archived_at,
+                archive.reason,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除归档索引
+    pub fn delete_session_archive(&self, id: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute("DELETE FROM session_archives WHERE id = ?1", params![id])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
+    /// 获取会话保留策略配置
+    pub fn get_session_retention_config(&self) -> Result<SessionRetentionConfig, AppError> {
+        match self.get_setting(RETENTION_CONFIG_KEY)? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Message(format!("解析会话保留策略失败: {e}"))),
+            None => Ok(SessionRetentionConfig::default()),
+        }
+    }
+
+    /// 保存会话保留策略配置
+    pub fn save_session_retention_config(
+        &self,
+        config: &SessionRetentionConfig,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Message(format!("序列化会话保留策略失败: {e}")))?;
+        self.set_setting(RETENTION_CONFIG_KEY, &json)
+    }
+}
@@ -324,4 +324,53 @@ impl Database {
             .map_err(|e| AppError::Database(format!("序列化日志配置失败: {e}")))?;
         self.set_setting("log_config", &json)
     }
+
+    // --- 受信任发布者 ---
+
+    /// 获取受信任的签名发布者
+    pub fn get_trusted_publishers(
+        &self,
+    ) -> Result<Vec<crate::signing::TrustedPublisher>, AppError> {
+        match self.get_setting("trusted_publishers")? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Database(format!("解析受信任发布者失败: {e}"))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// 更新受信任的签名发布者
+    pub fn set_trusted_publishers(
+        &self,
+        publishers: &[crate::signing::TrustedPublisher],
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(publishers)
+            .map_err(|e| AppError::Database(format!("序列化受信任发布者失败: {e}")))?;
+        self.set_setting("trusted_publishers", &json)
+    }
+
+    // --- Live 配置漂移基线 ---
+
+    /// 获取应用 live 配置的漂移检测基线
+    pub fn get_live_drift_baseline(
+        &self,
+        app_type: &str,
+    ) -> Result<Option<crate::services::live_drift::LiveBaseline>, AppError> {
+        match self.get_setting(&format!("live_drift_baseline_{app_type}"))? {
+            Some(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| AppError::Database(format!("解析 live 配置基线失败: {e}"))),
+            None => Ok(None),
+        }
+    }
+
+    /// 保存应用 live 配置的漂移检测基线
+    pub fn save_live_drift_baseline(
+        &self,
+        app_type: &str,
+        baseline: &crate::services::live_drift::LiveBaseline,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(baseline)
+            .map_err(|e| AppError::Database(format!("序列化 live 配置基线失败: {e}")))?;
+        self.set_setting(&format!("live_drift_baseline_{app_type}"), &json)
+    }
 }
@@ -20,6 +20,7 @@
 //!     ├── mcp.rs
 //!     ├── prompts.rs
 //!     ├── skills.rs
+//!     ├── session_archives.rs
 //!     └── settings.rs
 //! ```
 
@@ -49,7 +50,7 @@ use std::sync::Mutex;
 
 /// 当前 Schema 版本号
 /// 每次修改表结构时递增，并在 schema.rs 中添加相应的迁移逻辑
-pub(crate) const SCHEMA_VERSION: i32 = 11;
+pub(crate) const SCHEMA_VERSION: i32 = 23;
 
 /// 安全地序列化 JSON，避免 unwrap panic
 pub(crate) fn to_json_string<T: Serialize>(value: &T) -> Result<String, AppError> {
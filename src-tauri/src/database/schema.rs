@@ -66,7 +66,9 @@ impl Database {
             description TEXT, homepage TEXT, docs TEXT, tags TEXT NOT NULL DEFAULT '[]',
             enabled_claude BOOLEAN NOT NULL DEFAULT 0, enabled_codex BOOLEAN NOT NULL DEFAULT 0,
             enabled_gemini BOOLEAN NOT NULL DEFAULT 0, enabled_opencode BOOLEAN NOT NULL DEFAULT 0,
-            enabled_hermes BOOLEAN NOT NULL DEFAULT 0
+            enabled_hermes BOOLEAN NOT NULL DEFAULT 0, enabled_qwen BOOLEAN NOT NULL DEFAULT 0,
+            enabled_goose BOOLEAN NOT NULL DEFAULT 0, enabled_crush BOOLEAN NOT NULL DEFAULT 0,
+            last_probe TEXT
         )",
             [],
         )
@@ -97,7 +99,8 @@ impl Database {
             enabled_hermes BOOLEAN NOT NULL DEFAULT 0,
             installed_at INTEGER NOT NULL DEFAULT 0,
             content_hash TEXT,
-            updated_at INTEGER NOT NULL DEFAULT 0
+            updated_at INTEGER NOT NULL DEFAULT 0,
+            source TEXT
         )",
             [],
         )
@@ -120,9 +123,9 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
-        // 8. Proxy Config 表（三行结构，app_type 主键）
+        // 8. Proxy Config 表（每应用一行，app_type 主键）
         conn.execute("CREATE TABLE IF NOT EXISTS proxy_config (
-            app_type TEXT PRIMARY KEY CHECK (app_type IN ('claude','codex','gemini')),
+            app_type TEXT PRIMARY KEY CHECK (app_type IN ('claude','codex','gemini','qwen','aider')),
             proxy_enabled INTEGER NOT NULL DEFAULT 0, listen_address TEXT NOT NULL DEFAULT '127.0.0.1',
             listen_port INTEGER NOT NULL DEFAULT 15721, enable_logging INTEGER NOT NULL DEFAULT 1,
             enabled INTEGER NOT NULL DEFAULT 0, auto_failover_enabled INTEGER NOT NULL DEFAULT 0,
@@ -169,6 +172,26 @@ impl Database {
                 [],
             )
             .map_err(|e| AppError::Database(e.to_string()))?;
+            // 旧表的 CHECK 不含 qwen 时会被 OR IGNORE 跳过，由 v20 -> v21 迁移重建后补齐
+            conn.execute(
+                "INSERT OR IGNORE INTO proxy_config (app_type, max_retries,
+                streaming_first_byte_timeout, streaming_idle_timeout, non_streaming_timeout,
+                circuit_failure_threshold, circuit_success_threshold, circuit_timeout_seconds,
+                circuit_error_rate_threshold, circuit_min_requests)
+                VALUES ('qwen', 3, 60, 120, 600, 4, 2, 60, 0.6, 10)",
+                [],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+            // 同理，不含 aider 的旧表由 v22 -> v23 迁移补齐
+            conn.execute(
+                "INSERT OR IGNORE INTO proxy_config (app_type, max_retries,
+                streaming_first_byte_timeout, streaming_idle_timeout, non_streaming_timeout,
+                circuit_failure_threshold, circuit_success_threshold, circuit_timeout_seconds,
+                circuit_error_rate_threshold, circuit_min_requests)
+                VALUES ('aider', 3, 60, 120, 600, 4, 2, 60, 0.6, 10)",
+                [],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
         }
 
         // 9. Provider Health 表
@@ -225,7 +248,9 @@ impl Database {
             model_id TEXT PRIMARY KEY, display_name TEXT NOT NULL,
             input_cost_per_million TEXT NOT NULL, output_cost_per_million TEXT NOT NULL,
             cache_read_cost_per_million TEXT NOT NULL DEFAULT '0',
-            cache_creation_cost_per_million TEXT NOT NULL DEFAULT '0'
+            cache_creation_cost_per_million TEXT NOT NULL DEFAULT '0',
+            pricing_tiers TEXT,
+            source TEXT NOT NULL DEFAULT 'builtin'
         )",
             [],
         )
@@ -295,6 +320,132 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        // 19. Session Archives 表 (会话归档索引)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS session_archives (
+                id TEXT PRIMARY KEY,
+                provider_id TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                title TEXT,
+                project_dir TEXT,
+                source_path TEXT NOT NULL,
+                archive_path TEXT NOT NULL,
+                original_size INTEGER NOT NULL DEFAULT 0,
+                archived_size INTEGER NOT NULL DEFAULT 0,
+                last_active_at INTEGER,
+                archived_at INTEGER NOT NULL,
+                reason TEXT NOT NULL DEFAULT 'manual'
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 20. Provider Model Pricing 表 (供应商级模型定价覆盖)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_model_pricing (
+                provider_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                model_pattern TEXT NOT NULL,
+                input_cost_per_million TEXT NOT NULL,
+                output_cost_per_million TEXT NOT NULL,
+                cache_read_cost_per_million TEXT NOT NULL DEFAULT '0',
+                cache_creation_cost_per_million TEXT NOT NULL DEFAULT '0',
+                PRIMARY KEY (provider_id, app_type, model_pattern)
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 21. MCP Secrets 表 (本地密钥库，不参与同步与导出)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS mcp_secrets (
+                name TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                description TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 22. Projects 表 (项目级 MCP / 提示词 / Skills，本机路径不参与同步)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS projects (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                path TEXT NOT NULL UNIQUE,
+                selections TEXT NOT NULL DEFAULT '{}',
+                applied_state TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 23. Shared Prompts 表 (跨应用共享的提示词)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS shared_prompts (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                content TEXT NOT NULL,
+                description TEXT,
+                enabled_claude BOOLEAN NOT NULL DEFAULT 0,
+                enabled_codex BOOLEAN NOT NULL DEFAULT 0,
+                enabled_gemini BOOLEAN NOT NULL DEFAULT 0,
+                enabled_opencode BOOLEAN NOT NULL DEFAULT 0,
+                enabled_openclaw BOOLEAN NOT NULL DEFAULT 0,
+                enabled_hermes BOOLEAN NOT NULL DEFAULT 0,
+                enabled_qwen BOOLEAN NOT NULL DEFAULT 0,
+                overrides TEXT NOT NULL DEFAULT '{}',
+                created_at INTEGER,
+                updated_at INTEGER
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 24. Profiles 表 (一键切换供应商 / MCP / 提示词 / Skills 的组合)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS profiles (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                selections TEXT NOT NULL DEFAULT '{}',
+                sort_index INTEGER,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 25. Profile Switch Backup 表 (切换进行中的回滚快照，本机状态不参与同步)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS profile_switch_backup (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                profile_id TEXT NOT NULL,
+                snapshot TEXT NOT NULL,
+                backed_up_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 26. Bundle Imports 表 (团队配置包上次导入的内容，作为三方合并的基线)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bundle_imports (
+                bundle_id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                version TEXT NOT NULL,
+                publisher TEXT,
+                content TEXT NOT NULL,
+                imported_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
         // 尝试添加 live_takeover_active 列到 proxy_config 表
         let _ = conn.execute(
             "ALTER TABLE proxy_config ADD COLUMN live_takeover_active INTEGER NOT NULL DEFAULT 0",
@@ -444,6 +595,66 @@ impl Database {
                         Self::migrate_v10_to_v11(conn)?;
                         Self::set_user_version(conn, 11)?;
                     }
+                    11 => {
+                        log::info!("迁移数据库从 v11 到 v12（会话归档索引）");
+                        Self::migrate_v11_to_v12(conn)?;
+                        Self::set_user_version(conn, 12)?;
+                    }
+                    12 => {
+                        log::info!("迁移数据库从 v12 到 v13（定价目录与供应商定价覆盖）");
+                        Self::migrate_v12_to_v13(conn)?;
+                        Self::set_user_version(conn, 13)?;
+                    }
+                    13 => {
+                        log::info!("迁移数据库从 v13 到 v14（MCP 服务器探测结果）");
+                        Self::migrate_v13_to_v14(conn)?;
+                        Self::set_user_version(conn, 14)?;
+                    }
+                    14 => {
+                        log::info!("迁移数据库从 v14 到 v15（MCP 本地密钥库）");
+                        Self::migrate_v14_to_v15(conn)?;
+                        Self::set_user_version(conn, 15)?;
+                    }
+                    15 => {
+                        log::info!("迁移数据库从 v15 到 v16（项目作用域）");
+                        Self::migrate_v15_to_v16(conn)?;
+                        Self::set_user_version(conn, 16)?;
+                    }
+                    16 => {
+                        log::info!("迁移数据库从 v16 到 v17（跨应用共享提示词）");
+                        Self::migrate_v16_to_v17(conn)?;
+                        Self::set_user_version(conn, 17)?;
+                    }
+                    17 => {
+                        log::info!("迁移数据库从 v17 到 v18（配置档案）");
+                        Self::migrate_v17_to_v18(conn)?;
+                        Self::set_user_version(conn, 18)?;
+                    }
+                    18 => {
+                        log::info!("迁移数据库从 v18 到 v19（团队配置包）");
+                        Self::migrate_v18_to_v19(conn)?;
+                        Self::set_user_version(conn, 19)?;
+                    }
+                    19 => {
+                        log::info!("迁移数据库从 v19 到 v20（Skill 来源固定）");
+                        Self::migrate_v19_to_v20(conn)?;
+                        Self::set_user_version(conn, 20)?;
+                    }
+                    20 => {
+                        log::info!("迁移数据库从 v20 到 v21（Qwen Code 支持）");
+                        Self::migrate_v20_to_v21(conn)?;
+                        Self::set_user_version(conn, 21)?;
+                    }
+                    21 => {
+                        log::info!("迁移数据库从 v21 到 v22（Goose / Crush MCP 支持）");
+                        Self::migrate_v21_to_v22(conn)?;
+                        Self::set_user_version(conn, 22)?;
+                    }
+                    22 => {
+                        log::info!("迁移数据库从 v22 到 v23（Aider 支持）");
+                        Self::migrate_v22_to_v23(conn)?;
+                        Self::set_user_version(conn, 23)?;
+                    }
                     _ => {
                         return Err(AppError::Database(format!(
                             "未知的数据库版本 {version}，无法迁移到 {SCHEMA_VERSION}"
@@ -1270,6 +1481,344 @@ impl Database {
         Ok(())
     }
 
+    /// v11 -> v12：会话归档索引表
+    fn migrate_v11_to_v12(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS session_archives (
+                id TEXT PRIMARY KEY,
+                provider_id TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                title TEXT,
+                project_dir TEXT,
+                source_path TEXT NOT NULL,
+                archive_path TEXT NOT NULL,
+                original_size INTEGER NOT NULL DEFAULT 0,
+                archived_size INTEGER NOT NULL DEFAULT 0,
+                last_active_at INTEGER,
+                archived_at INTEGER NOT NULL,
+                reason TEXT NOT NULL DEFAULT 'manual'
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 session_archives 表失败: {e}")))?;
+
+        log::info!("v11 -> v12 迁移完成：已添加 session_archives 表");
+        Ok(())
+    }
+
+    /// v12 -> v13：model_pricing 增加分档定价与来源列，新增供应商定价覆盖表
+    fn migrate_v12_to_v13(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "model_pricing")? {
+            Self::add_column_if_missing(conn, "model_pricing", "pricing_tiers", "TEXT")?;
+            Self::add_column_if_missing(
+                conn,
+                "model_pricing",
+                "source",
+                "TEXT NOT NULL DEFAULT 'builtin'",
+            )?;
+        }
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_model_pricing (
+                provider_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                model_pattern TEXT NOT NULL,
+                input_cost_per_million TEXT NOT NULL,
+                output_cost_per_million TEXT NOT NULL,
+                cache_read_cost_per_million TEXT NOT NULL DEFAULT '0',
+                cache_creation_cost_per_million TEXT NOT NULL DEFAULT '0',
+                PRIMARY KEY (provider_id, app_type, model_pattern)
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 provider_model_pricing 表失败: {e}")))?;
+
+        log::info!("v12 -> v13 迁移完成：已添加定价分档与供应商定价覆盖");
+        Ok(())
+    }
+
+    /// v13 -> v14：mcp_servers 增加最近一次探测结果列
+    fn migrate_v13_to_v14(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "mcp_servers")? {
+            Self::add_column_if_missing(conn, "mcp_servers", "last_probe", "TEXT")?;
+        }
+
+        log::info!("v13 -> v14 迁移完成：已添加 MCP 探测结果列");
+        Ok(())
+    }
+
+    /// v14 -> v15：MCP 本地密钥库表
+    fn migrate_v14_to_v15(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS mcp_secrets (
+                name TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                description TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 mcp_secrets 表失败: {e}")))?;
+
+        log::info!("v14 -> v15 迁移完成：已添加 mcp_secrets 表");
+        Ok(())
+    }
+
+    /// v15 -> v16：项目作用域表
+    fn migrate_v15_to_v16(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS projects (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                path TEXT NOT NULL UNIQUE,
+                selections TEXT NOT NULL DEFAULT '{}',
+                applied_state TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 projects 表失败: {e}")))?;
+
+        log::info!("v15 -> v16 迁移完成：已添加 projects 表");
+        Ok(())
+    }
+
+    /// v16 -> v17：跨应用共享提示词表
+    fn migrate_v16_to_v17(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS shared_prompts (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                content TEXT NOT NULL,
+                description TEXT,
+                enabled_claude BOOLEAN NOT NULL DEFAULT 0,
+                enabled_codex BOOLEAN NOT NULL DEFAULT 0,
+                enabled_gemini BOOLEAN NOT NULL DEFAULT 0,
+                enabled_opencode BOOLEAN NOT NULL DEFAULT 0,
+                enabled_openclaw BOOLEAN NOT NULL DEFAULT 0,
+                enabled_hermes BOOLEAN NOT NULL DEFAULT 0,
+                overrides TEXT NOT NULL DEFAULT '{}',
+                created_at INTEGER,
+                updated_at INTEGER
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 shared_prompts 表失败: {e}")))?;
+
+        log::info!("v16 -> v17 迁移完成：已添加 shared_prompts 表");
+        Ok(())
+    }
+
+    /// v17 -> v18：配置档案及切换回滚快照表
+    fn migrate_v17_to_v18(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS profiles (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                selections TEXT NOT NULL DEFAULT '{}',
+                sort_index INTEGER,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 profiles 表失败: {e}")))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS profile_switch_backup (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                profile_id TEXT NOT NULL,
+                snapshot TEXT NOT NULL,
+                backed_up_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 profile_switch_backup 表失败: {e}")))?;
+
+        log::info!("v17 -> v18 迁移完成：已添加 profiles 与 profile_switch_backup 表");
+        Ok(())
+    }
+
+    /// v18 -> v19：团队配置包导入记录表
+    fn migrate_v18_to_v19(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bundle_imports (
+                bundle_id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                version TEXT NOT NULL,
+                publisher TEXT,
+                content TEXT NOT NULL,
+                imported_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建 bundle_imports 表失败: {e}")))?;
+
+        log::info!("v18 -> v19 迁移完成：已添加 bundle_imports 表");
+        Ok(())
+    }
+
+    /// v19 -> v20：skills 增加安装来源列（任意 git 仓库 / 本地目录、固定的 ref 与 commit）
+    fn migrate_v19_to_v20(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "skills")? {
+            Self::add_column_if_missing(conn, "skills", "source", "TEXT")?;
+        }
+
+        log::info!("v19 -> v20 迁移完成：已添加 Skill 来源列");
+        Ok(())
+    }
+
+    /// v20 -> v21：添加 Qwen Code 支持
+    ///
+    /// proxy_config 的 app_type CHECK 约束只允许 claude/codex/gemini，
+    /// SQLite 无法修改约束，需重建表（按列名拷贝已有行）后再补 qwen 行。
+    fn migrate_v20_to_v21(conn: &Connection) -> Result<(), AppError> {
+        Self::add_column_if_missing(
+            conn,
+            "mcp_servers",
+            "enabled_qwen",
+            "BOOLEAN NOT NULL DEFAULT 0",
+        )?;
+        if Self::table_exists(conn, "shared_prompts")? {
+            Self::add_column_if_missing(
+                conn,
+                "shared_prompts",
+                "enabled_qwen",
+                "BOOLEAN NOT NULL DEFAULT 0",
+            )?;
+        }
+
+        if Self::table_exists(conn, "proxy_config")?
+            && Self::has_column(conn, "proxy_config", "app_type")?
+        {
+            let table_sql: String = conn
+                .query_row(
+                    "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'proxy_config'",
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(|e| AppError::Database(format!("读取 proxy_config 结构失败: {e}")))?;
+
+            if !table_sql.contains("'qwen'") {
+                Self::rebuild_proxy_config_app_types(conn)?;
+            }
+
+            conn.execute(
+                "INSERT OR IGNORE INTO proxy_config (app_type, max_retries,
+                streaming_first_byte_timeout, streaming_idle_timeout, non_streaming_timeout,
+                circuit_failure_threshold, circuit_success_threshold, circuit_timeout_seconds,
+                circuit_error_rate_threshold, circuit_min_requests)
+                VALUES ('qwen', 3, 60, 120, 600, 4, 2, 60, 0.6, 10)",
+                [],
+            )
+            .map_err(|e| AppError::Database(format!("插入 qwen 代理配置失败: {e}")))?;
+        }
+
+        log::info!("v20 -> v21 迁移完成：已添加 Qwen Code 支持");
+        Ok(())
+    }
+
+    /// v21 -> v22：mcp_servers 添加 Goose / Crush 启用列
+    fn migrate_v21_to_v22(conn: &Connection) -> Result<(), AppError> {
+        for column in ["enabled_goose", "enabled_crush"] {
+            Self::add_column_if_missing(conn, "mcp_servers", column, "BOOLEAN NOT NULL DEFAULT 0")?;
+        }
+
+        log::info!("v21 -> v22 迁移完成：已添加 Goose / Crush MCP 支持");
+        Ok(())
+    }
+
+    /// v22 -> v23：添加 Aider 支持
+    ///
+    /// Aider 没有 MCP / 提示词，只需放开 proxy_config 的 app_type 约束并补 aider 行。
+    fn migrate_v22_to_v23(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "proxy_config")?
+            && Self::has_column(conn, "proxy_config", "app_type")?
+        {
+            let table_sql: String = conn
+                .query_row(
+                    "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'proxy_config'",
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(|e| AppError::Database(format!("读取 proxy_config 结构失败: {e}")))?;
+
+            if !table_sql.contains("'aider'") {
+                Self::rebuild_proxy_config_app_types(conn)?;
+            }
+
+            conn.execute(
+                "INSERT OR IGNORE INTO proxy_config (app_type, max_retries,
+                streaming_first_byte_timeout, streaming_idle_timeout, non_streaming_timeout,
+                circuit_failure_threshold, circuit_success_threshold, circuit_timeout_seconds,
+                circuit_error_rate_threshold, circuit_min_requests)
+                VALUES ('aider', 3, 60, 120, 600, 4, 2, 60, 0.6, 10)",
+                [],
+            )
+            .map_err(|e| AppError::Database(format!("插入 aider 代理配置失败: {e}")))?;
+        }
+
+        log::info!("v22 -> v23 迁移完成：已添加 Aider 支持");
+        Ok(())
+    }
+
+    /// 按当前 app_type 约束重建 proxy_config（SQLite 无法直接修改 CHECK）
+    fn rebuild_proxy_config_app_types(conn: &Connection) -> Result<(), AppError> {
+        let old_columns: Vec<String> = {
+            let mut stmt = conn
+                .prepare("PRAGMA table_info(proxy_config)")
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            let rows = stmt
+                .query_map([], |row| row.get::<_, String>(1))
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            rows.collect::<Result<_, _>>()
+                .map_err(|e| AppError::Database(e.to_string()))?
+        };
+
+        conn.execute("DROP TABLE IF EXISTS proxy_config_new", [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute("CREATE TABLE proxy_config_new (
+            app_type TEXT PRIMARY KEY CHECK (app_type IN ('claude','codex','gemini','qwen','aider')),
+            proxy_enabled INTEGER NOT NULL DEFAULT 0, listen_address TEXT NOT NULL DEFAULT '127.0.0.1',
+            listen_port INTEGER NOT NULL DEFAULT 15721, enable_logging INTEGER NOT NULL DEFAULT 1,
+            enabled INTEGER NOT NULL DEFAULT 0, auto_failover_enabled INTEGER NOT NULL DEFAULT 0,
+            max_retries INTEGER NOT NULL DEFAULT 3, streaming_first_byte_timeout INTEGER NOT NULL DEFAULT 60,
+            streaming_idle_timeout INTEGER NOT NULL DEFAULT 120, non_streaming_timeout INTEGER NOT NULL DEFAULT 600,
+            circuit_failure_threshold INTEGER NOT NULL DEFAULT 4, circuit_success_threshold INTEGER NOT NULL DEFAULT 2,
+            circuit_timeout_seconds INTEGER NOT NULL DEFAULT 60, circuit_error_rate_threshold REAL NOT NULL DEFAULT 0.6,
+            circuit_min_requests INTEGER NOT NULL DEFAULT 10,
+            default_cost_multiplier TEXT NOT NULL DEFAULT '1',
+            pricing_model_source TEXT NOT NULL DEFAULT 'response',
+            live_takeover_active INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')), updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )", []).map_err(|e| AppError::Database(format!("创建 proxy_config_new 失败: {e}")))?;
+
+        let mut shared = Vec::new();
+        for column in &old_columns {
+            if Self::has_column(conn, "proxy_config_new", column)? {
+                shared.push(column.as_str());
+            }
+        }
+        let column_list = shared.join(", ");
+        conn.execute(
+            &format!(
+                "INSERT INTO proxy_config_new ({column_list}) SELECT {column_list} FROM proxy_config"
+            ),
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("拷贝 proxy_config 数据失败: {e}")))?;
+
+        conn.execute("DROP TABLE proxy_config", [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute("ALTER TABLE proxy_config_new RENAME TO proxy_config", [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        log::info!("proxy_config 已重建，app_type 约束已更新");
+        Ok(())
+    }
+
     /// 插入默认模型定价数据
     /// 格式: (model_id, display_name, input, output, cache_read, cache_creation)
     /// 注意: model_id 使用短横线格式（如 claude-haiku-4-5），与 API 返回的模型名称标准化后一致
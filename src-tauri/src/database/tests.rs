@@ -473,6 +473,129 @@ fn schema_create_tables_repairs_legacy_proxy_config_singleton_to_per_app() {
         .expect("query by app_type");
 }
 
+#[test]
+fn migration_v20_to_v21_rebuilds_proxy_config_to_allow_qwen() {
+    let conn = Connection::open_in_memory().expect("open memory db");
+    conn.execute_batch(
+        r#"
+        CREATE TABLE mcp_servers (
+            id TEXT PRIMARY KEY, name TEXT NOT NULL, server_config TEXT NOT NULL
+        );
+        CREATE TABLE proxy_config (
+            app_type TEXT PRIMARY KEY CHECK (app_type IN ('claude','codex','gemini')),
+            enabled INTEGER NOT NULL DEFAULT 0,
+            max_retries INTEGER NOT NULL DEFAULT 3,
+            listen_port INTEGER NOT NULL DEFAULT 15721
+        );
+        INSERT INTO proxy_config (app_type, enabled, max_retries, listen_port)
+            VALUES ('claude', 1, 9, 16000);
+        "#,
+    )
+    .expect("seed v20 proxy_config");
+    Database::set_user_version(&conn, 20).expect("set user_version");
+
+    Database::apply_schema_migrations_on_conn(&conn).expect("migrate to v21");
+
+    assert!(
+        Database::has_column(&conn, "mcp_servers", "enabled_qwen").expect("check column"),
+        "mcp_servers should gain enabled_qwen"
+    );
+    let (enabled, retries, port): (i64, i64, i64) = conn
+        .query_row(
+            "SELECT enabled, max_retries, listen_port FROM proxy_config WHERE app_type = 'claude'",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .expect("claude row preserved");
+    assert_eq!((enabled, retries, port), (1, 9, 16000));
+
+    let qwen_rows: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM proxy_config WHERE app_type = 'qwen'",
+            [],
+            |r| r.get(0),
+        )
+        .expect("count qwen rows");
+    assert_eq!(qwen_rows, 1, "qwen proxy_config row should be seeded");
+
+    // 重复执行迁移不应再次重建或报错
+    Database::set_user_version(&conn, 20).expect("reset user_version");
+    Database::apply_schema_migrations_on_conn(&conn).expect("migration is idempotent");
+}
+
+#[test]
+fn migration_v21_to_v22_adds_goose_and_crush_mcp_columns() {
+    let conn = Connection::open_in_memory().expect("open memory db");
+    conn.execute_batch(
+        r#"
+        CREATE TABLE mcp_servers (
+            id TEXT PRIMARY KEY, name TEXT NOT NULL, server_config TEXT NOT NULL,
+            enabled_qwen BOOLEAN NOT NULL DEFAULT 0
+        );
+        INSERT INTO mcp_servers (id, name, server_config, enabled_qwen)
+            VALUES ('fetch', 'fetch', '{}', 1);
+        "#,
+    )
+    .expect("seed v21 mcp_servers");
+    Database::set_user_version(&conn, 21).expect("set user_version");
+
+    Database::apply_schema_migrations_on_conn(&conn).expect("migrate to v22");
+
+    let (qwen, goose, crush): (bool, bool, bool) = conn
+        .query_row(
+            "SELECT enabled_qwen, enabled_goose, enabled_crush FROM mcp_servers WHERE id = 'fetch'",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .expect("query migrated row");
+    assert_eq!((qwen, goose, crush), (true, false, false));
+
+    Database::set_user_version(&conn, 21).expect("reset user_version");
+    Database::apply_schema_migrations_on_conn(&conn).expect("migration is idempotent");
+}
+
+#[test]
+fn migration_v22_to_v23_rebuilds_proxy_config_to_allow_aider() {
+    let conn = Connection::open_in_memory().expect("open memory db");
+    conn.execute_batch(
+        r#"
+        CREATE TABLE proxy_config (
+            app_type TEXT PRIMARY KEY CHECK (app_type IN ('claude','codex','gemini','qwen')),
+            enabled INTEGER NOT NULL DEFAULT 0,
+            max_retries INTEGER NOT NULL DEFAULT 3,
+            listen_port INTEGER NOT NULL DEFAULT 15721
+        );
+        INSERT INTO proxy_config (app_type, enabled, max_retries, listen_port)
+            VALUES ('qwen', 1, 5, 16000);
+        "#,
+    )
+    .expect("seed v22 proxy_config");
+    Database::set_user_version(&conn, 22).expect("set user_version");
+
+    Database::apply_schema_migrations_on_conn(&conn).expect("migrate to v23");
+
+    let (enabled, retries, port): (i64, i64, i64) = conn
+        .query_row(
+            "SELECT enabled, max_retries, listen_port FROM proxy_config WHERE app_type = 'qwen'",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .expect("qwen row preserved");
+    assert_eq!((enabled, retries, port), (1, 5, 16000));
+
+    let aider_rows: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM proxy_config WHERE app_type = 'aider'",
+            [],
+            |r| r.get(0),
+        )
+        .expect("count aider rows");
+    assert_eq!(aider_rows, 1, "aider proxy_config row should be seeded");
+
+    Database::set_user_version(&conn, 22).expect("reset user_version");
+    Database::apply_schema_migrations_on_conn(&conn).expect("migration is idempotent");
+}
+
 #[test]
 fn migration_from_v3_8_schema_v1_to_current_schema_v3() {
     let conn = Connection::open_in_memory().expect("open memory db");
@@ -831,3 +954,39 @@ fn ensure_incremental_auto_vacuum_rebuilds_existing_file_db() {
         "file db should persist INCREMENTAL auto_vacuum after VACUUM rebuild"
     );
 }
+
+#[test]
+fn shared_prompts_roundtrip_apps_and_overrides() {
+    use crate::app_config::PromptApps;
+    use crate::prompt::SharedPrompt;
+
+    let db = Database::memory().expect("memory db");
+    let prompt = SharedPrompt {
+        id: "team".to_string(),
+        name: "Team guidelines".to_string(),
+        content: "# Team\n".to_string(),
+        description: Some("shared".to_string()),
+        apps: PromptApps {
+            claude: true,
+            openclaw: true,
+            ..Default::default()
+        },
+        overrides: [("claude".to_string(), "Prefer Edit tool.".to_string())]
+            .into_iter()
+            .collect(),
+        created_at: Some(1),
+        updated_at: Some(2),
+    };
+    db.save_shared_prompt(&prompt).expect("save shared prompt");
+
+    let loaded = db
+        .get_shared_prompt("team")
+        .expect("load shared prompt")
+        .expect("shared prompt exists");
+    assert_eq!(loaded.apps, prompt.apps);
+    assert_eq!(loaded.overrides, prompt.overrides);
+    assert_eq!(db.get_shared_prompts().expect("list").len(), 1);
+
+    assert!(db.delete_shared_prompt("team").expect("delete"));
+    assert!(db.get_shared_prompt("team").expect("reload").is_none());
+}
@@ -168,6 +168,9 @@ pub(crate) fn parse_mcp_apps(apps_str: &str) -> Result<McpApps, AppError> {
         gemini: false,
         opencode: false,
         hermes: false,
+        qwen: false,
+        goose: false,
+        crush: false,
     };
 
     for app in apps_str.split(',') {
@@ -181,6 +184,9 @@ pub(crate) fn parse_mcp_apps(apps_str: &str) -> Result<McpApps, AppError> {
                 log::debug!("OpenClaw doesn't support MCP, ignoring in apps parameter");
             }
             "hermes" => apps.hermes = true,
+            "qwen" => apps.qwen = true,
+            "goose" => apps.goose = true,
+            "crush" => apps.crush = true,
             other => {
                 return Err(AppError::InvalidInput(format!(
                     "Invalid app in 'apps': {other}"
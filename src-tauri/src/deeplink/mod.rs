@@ -7,6 +7,8 @@
 //! - Prompts
 //! - Skills
 //!
+//! Links may carry an Ed25519 publisher signature (`pubkey` + `sig` parameters),
+//! see [`crate::signing`].
 
 mod mcp;
 mod parser;
@@ -20,9 +22,11 @@ mod tests;
 
 use serde::{Deserialize, Serialize};
 
+use crate::signing::SignatureStatus;
+
 // Re-export public API
 pub use mcp::import_mcp_from_deeplink;
-pub use parser::parse_deeplink_url;
+pub use parser::{parse_deeplink_url, parse_deeplink_url_with_trust, sign_deeplink_url};
 pub use prompt::import_prompt_from_deeplink;
 pub use provider::{import_provider_from_deeplink, parse_and_merge_config};
 pub use skill::import_skill_from_deeplink;
@@ -135,4 +139,10 @@ pub struct DeepLinkImportRequest {
     /// Auto query interval in minutes (0 to disable)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage_auto_interval: Option<u64>,
+
+    // ============ Signature fields ============
+    /// Publisher signature status, filled in by the parser after verification.
+    /// Tampered links are rejected while parsing and never reach this point.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<SignatureStatus>,
 }
@@ -5,14 +5,31 @@
 use super::utils::validate_url;
 use super::DeepLinkImportRequest;
 use crate::error::AppError;
+use crate::signing::{self, SignatureStatus, TrustedPublisher};
 use std::collections::HashMap;
 use url::Url;
 
+/// Query parameter carrying the signer's Ed25519 public key
+const PUBLIC_KEY_PARAM: &str = "pubkey";
+/// Query parameter carrying the Ed25519 signature
+const SIGNATURE_PARAM: &str = "sig";
+
 /// Parse a ccswitch:// URL into a DeepLinkImportRequest
 ///
 /// Expected format:
 /// ccswitch://v1/import?resource={type}&...
+///
+/// Signed links are verified for integrity, but without a trusted publisher list
+/// every valid signature is reported as untrusted.
 pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppError> {
+    parse_deeplink_url_with_trust(url_str, &[])
+}
+
+/// Parse a ccswitch:// URL and resolve its signature against trusted publishers
+pub fn parse_deeplink_url_with_trust(
+    url_str: &str,
+    trusted: &[TrustedPublisher],
+) -> Result<DeepLinkImportRequest, AppError> {
     // Parse URL
     let url = Url::parse(url_str)
         .map_err(|e| AppError::InvalidInput(format!("Invalid deep link URL: {e}")))?;
@@ -46,6 +63,9 @@ pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppErr
         )));
     }
 
+    // Reject tampered links before looking at any parameter
+    let signature = verify_signature(&url, trusted)?;
+
     // Parse query parameters
     let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
 
@@ -56,7 +76,7 @@ pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppErr
         .clone();
 
     // Dispatch to appropriate parser based on resource type
-    match resource.as_str() {
+    let mut request = match resource.as_str() {
         "provider" => parse_provider_deeplink(&params, version, resource),
         "prompt" => parse_prompt_deeplink(&params, version, resource),
         "mcp" => parse_mcp_deeplink(&params, version, resource),
@@ -64,7 +84,80 @@ pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppErr
         _ => Err(AppError::InvalidInput(format!(
             "Unsupported resource type: {resource}"
         ))),
+    }?;
+    request.signature = Some(signature);
+    Ok(request)
+}
+
+/// Build the signed payload: every query parameter except `sig`, sorted and
+/// re-encoded, so parameter order and percent-encoding style do not matter.
+fn signing_payload(url: &Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .into_owned()
+        .filter(|(key, _)| key != SIGNATURE_PARAM)
+        .collect();
+    pairs.sort();
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish();
+    format!(
+        "{}://{}{}?{query}",
+        url.scheme(),
+        url.host_str().unwrap_or_default(),
+        url.path()
+    )
+}
+
+fn verify_signature(url: &Url, trusted: &[TrustedPublisher]) -> Result<SignatureStatus, AppError> {
+    let find = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    match (find(PUBLIC_KEY_PARAM), find(SIGNATURE_PARAM)) {
+        (None, None) => Ok(SignatureStatus::Unsigned),
+        (Some(public_key), Some(signature)) => signing::check(
+            trusted,
+            &public_key,
+            signing_payload(url).as_bytes(),
+            &signature,
+        )
+        .map_err(|e| AppError::InvalidInput(format!("Deep link signature rejected: {e}"))),
+        _ => Err(AppError::InvalidInput(format!(
+            "Incomplete deep link signature: both '{PUBLIC_KEY_PARAM}' and '{SIGNATURE_PARAM}' are required"
+        ))),
+    }
+}
+
+/// Sign a ccswitch:// URL with a PKCS#8 Ed25519 private key
+///
+/// Any existing signature is replaced; the returned URL carries `pubkey` and `sig`.
+pub fn sign_deeplink_url(url_str: &str, private_key: &str) -> Result<String, AppError> {
+    let mut url = Url::parse(url_str)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid deep link URL: {e}")))?;
+    if url.scheme() != "ccswitch" {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid scheme: expected 'ccswitch', got '{}'",
+            url.scheme()
+        )));
     }
+
+    let public_key = signing::public_key_of(private_key)?;
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .into_owned()
+        .filter(|(key, _)| key != PUBLIC_KEY_PARAM && key != SIGNATURE_PARAM)
+        .collect();
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair(PUBLIC_KEY_PARAM, &public_key);
+
+    let (_, signature) = signing::sign(private_key, signing_payload(&url).as_bytes())?;
+    url.query_pairs_mut()
+        .append_pair(SIGNATURE_PARAM, &signature);
+    Ok(url.to_string())
 }
 
 /// Parse provider deep link parameters
@@ -81,10 +174,19 @@ fn parse_provider_deeplink(
     // Validate app type
     if !matches!(
         app.as_str(),
-        "claude" | "codex" | "gemini" | "opencode" | "openclaw" | "hermes"
+        "claude"
+            | "codex"
+            | "gemini"
+            | "opencode"
+            | "openclaw"
+            | "hermes"
+            | "qwen"
+            | "goose"
+            | "crush"
+            | "aider"
     ) {
         return Err(AppError::InvalidInput(format!(
-            "Invalid app type: must be 'claude', 'codex', 'gemini', 'opencode', 'openclaw', or 'hermes', got '{app}'"
+            "Invalid app type: must be 'claude', 'codex', 'gemini', 'opencode', 'openclaw', 'hermes', 'qwen', 'goose', 'crush', or 'aider', got '{app}'"
         )));
     }
 
@@ -173,6 +275,7 @@ fn parse_provider_deeplink(
         usage_access_token,
         usage_user_id,
         usage_auto_interval,
+        signature: None,
     })
 }
 
@@ -190,10 +293,18 @@ fn parse_prompt_deeplink(
     // Validate app type
     if !matches!(
         app.as_str(),
-        "claude" | "codex" | "gemini" | "opencode" | "openclaw" | "hermes"
+        "claude"
+            | "codex"
+            | "gemini"
+            | "opencode"
+            | "openclaw"
+            | "hermes"
+            | "qwen"
+            | "goose"
+            | "crush"
     ) {
         return Err(AppError::InvalidInput(format!(
-            "Invalid app type: must be 'claude', 'codex', 'gemini', 'opencode', 'openclaw', or 'hermes', got '{app}'"
+            "Invalid app type: must be 'claude', 'codex', 'gemini', 'opencode', 'openclaw', 'hermes', 'qwen', 'goose', or 'crush', got '{app}'"
         )));
     }
 
@@ -243,6 +354,7 @@ fn parse_prompt_deeplink(
         usage_access_token: None,
         usage_user_id: None,
         usage_auto_interval: None,
+        signature: None,
     })
 }
 
@@ -262,10 +374,18 @@ fn parse_mcp_deeplink(
         let trimmed = app.trim();
         if !matches!(
             trimmed,
-            "claude" | "codex" | "gemini" | "opencode" | "openclaw" | "hermes"
+            "claude"
+                | "codex"
+                | "gemini"
+                | "opencode"
+                | "openclaw"
+                | "hermes"
+                | "qwen"
+                | "goose"
+                | "crush"
         ) {
             return Err(AppError::InvalidInput(format!(
-                "Invalid app in 'apps': must be 'claude', 'codex', 'gemini', 'opencode', 'openclaw', or 'hermes', got '{trimmed}'"
+                "Invalid app in 'apps': must be 'claude', 'codex', 'gemini', 'opencode', 'openclaw', 'hermes', 'qwen', 'goose', or 'crush', got '{trimmed}'"
             )));
         }
     }
@@ -308,6 +428,7 @@ fn parse_mcp_deeplink(
         usage_access_token: None,
         usage_user_id: None,
         usage_auto_interval: None,
+        signature: None,
     })
 }
 
@@ -363,5 +484,6 @@ fn parse_skill_deeplink(
         usage_access_token: None,
         usage_user_id: None,
         usage_auto_interval: None,
+        signature: None,
     })
 }
@@ -148,6 +148,10 @@ pub(crate) fn build_provider_from_request(
         AppType::OpenCode => build_opencode_settings(request),
         AppType::OpenClaw => build_additive_app_settings(request),
         AppType::Hermes => build_hermes_settings(request),
+        AppType::Qwen => build_qwen_settings(request),
+        AppType::Goose => build_goose_settings(request),
+        AppType::Crush => build_crush_settings(request),
+        AppType::Aider => build_aider_settings(request),
     };
 
     // Build usage script configuration if provided
@@ -410,6 +414,53 @@ fn build_gemini_settings(request: &DeepLinkImportRequest) -> serde_json::Value {
     json!({ "env": env })
 }
 
+/// Build Qwen Code settings configuration
+///
+/// Qwen Code talks to OpenAI-compatible endpoints, so credentials live in
+/// `~/.qwen/.env` as `OPENAI_*` variables.
+fn build_qwen_settings(request: &DeepLinkImportRequest) -> serde_json::Value {
+    let mut env = serde_json::Map::new();
+    env.insert("OPENAI_API_KEY".to_string(), json!(request.api_key));
+    env.insert(
+        "OPENAI_BASE_URL".to_string(),
+        json!(get_primary_endpoint(request)),
+    );
+
+    if let Some(model) = &request.model {
+        env.insert("OPENAI_MODEL".to_string(), json!(model));
+    }
+
+    json!({ "env": env, "config": {} })
+}
+
+/// Build Aider settings configuration
+///
+/// Deeplink providers are OpenAI-compatible endpoints, so the model is routed
+/// through LiteLLM's `openai/` prefix and the endpoint goes to `openai-api-base`.
+fn build_aider_settings(request: &DeepLinkImportRequest) -> serde_json::Value {
+    let endpoint = get_primary_endpoint(request);
+
+    let mut env = serde_json::Map::new();
+    if let Some(api_key) = &request.api_key {
+        env.insert("OPENAI_API_KEY".to_string(), json!(api_key));
+    }
+
+    let mut config = serde_json::Map::new();
+    if let Some(model) = request.model.as_deref().filter(|s| !s.is_empty()) {
+        let model = if model.starts_with("openai/") {
+            model.to_string()
+        } else {
+            format!("openai/{model}")
+        };
+        config.insert("model".to_string(), json!(model));
+    }
+    if !endpoint.is_empty() {
+        config.insert("openai-api-base".to_string(), json!(endpoint));
+    }
+
+    json!({ "env": env, "config": config })
+}
+
 /// Build OpenCode settings configuration
 fn build_opencode_settings(request: &DeepLinkImportRequest) -> serde_json::Value {
     let endpoint = get_primary_endpoint(request);
@@ -505,6 +556,66 @@ fn build_hermes_settings(request: &DeepLinkImportRequest) -> serde_json::Value {
     json!(config)
 }
 
+/// Build Goose custom provider settings.
+///
+/// Mirrors a `custom_providers/{id}.json` file minus the managed `name` /
+/// `api_key_env` fields; the API key itself is moved to `secrets.yaml` by
+/// `goose_config::set_provider`. Deep links only describe OpenAI-compatible
+/// endpoints, so the engine is always `openai`.
+fn build_goose_settings(request: &DeepLinkImportRequest) -> serde_json::Value {
+    let endpoint = get_primary_endpoint(request);
+
+    let mut config = serde_json::Map::new();
+    config.insert("engine".to_string(), json!("openai"));
+
+    if let Some(name) = request.name.as_deref().filter(|s| !s.is_empty()) {
+        config.insert("display_name".to_string(), json!(name));
+    }
+
+    if !endpoint.is_empty() {
+        config.insert("base_url".to_string(), json!(endpoint));
+    }
+
+    if let Some(api_key) = &request.api_key {
+        config.insert("api_key".to_string(), json!(api_key));
+    }
+
+    if let Some(model) = &request.model {
+        config.insert("models".to_string(), json!([{ "name": model }]));
+    }
+
+    json!(config)
+}
+
+/// Build Crush provider settings (an entry of `providers` in crush.json).
+fn build_crush_settings(request: &DeepLinkImportRequest) -> serde_json::Value {
+    let endpoint = get_primary_endpoint(request);
+
+    let mut config = serde_json::Map::new();
+    config.insert("type".to_string(), json!("openai-compat"));
+
+    if let Some(name) = request.name.as_deref().filter(|s| !s.is_empty()) {
+        config.insert("name".to_string(), json!(name));
+    }
+
+    if !endpoint.is_empty() {
+        config.insert("base_url".to_string(), json!(endpoint));
+    }
+
+    if let Some(api_key) = &request.api_key {
+        config.insert("api_key".to_string(), json!(api_key));
+    }
+
+    if let Some(model) = &request.model {
+        config.insert(
+            "models".to_string(),
+            json!([{ "id": model, "name": model }]),
+        );
+    }
+
+    json!(config)
+}
+
 // =============================================================================
 // Config Merge Logic
 // =============================================================================
@@ -566,8 +677,10 @@ pub fn parse_and_merge_config(
         "claude" => merge_claude_config(&mut merged, &config_value)?,
         "codex" => merge_codex_config(&mut merged, &config_value)?,
         "gemini" => merge_gemini_config(&mut merged, &config_value)?,
+        "qwen" => merge_qwen_config(&mut merged, &config_value)?,
+        "aider" => merge_aider_config(&mut merged, &config_value)?,
         // Additive mode apps use JSON config directly; pass through as-is
-        "openclaw" | "opencode" | "hermes" => {
+        "openclaw" | "opencode" | "hermes" | "goose" | "crush" => {
             merge_additive_config(&mut merged, &config_value)?;
         }
         "" => {
@@ -740,6 +853,85 @@ fn merge_gemini_config(
     Ok(())
 }
 
+/// Merge Qwen Code configuration from config file
+fn merge_qwen_config(
+    request: &mut DeepLinkImportRequest,
+    config: &serde_json::Value,
+) -> Result<(), AppError> {
+    // Accept both a flat .env-style object and the `{ env: {...} }` provider shape
+    let env = config.get("env").unwrap_or(config);
+
+    if request.api_key.as_ref().is_none_or(|s| s.is_empty()) {
+        if let Some(api_key) = env.get("OPENAI_API_KEY").and_then(|v| v.as_str()) {
+            request.api_key = Some(api_key.to_string());
+        }
+    }
+
+    if request.endpoint.as_ref().is_none_or(|s| s.is_empty()) {
+        if let Some(base_url) = env.get("OPENAI_BASE_URL").and_then(|v| v.as_str()) {
+            request.endpoint = Some(base_url.to_string());
+        }
+    }
+
+    if request.model.is_none() {
+        request.model = env
+            .get("OPENAI_MODEL")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+    }
+
+    if request.homepage.as_ref().is_none_or(|s| s.is_empty()) {
+        if let Some(endpoint) = request.endpoint.as_ref().filter(|s| !s.is_empty()) {
+            request.homepage = infer_homepage_from_endpoint(endpoint);
+        }
+    }
+
+    Ok(())
+}
+
+/// Merge Aider configuration from config file
+///
+/// Accepts the provider shape `{ env: {...}, config: {...} }` as well as a flat
+/// `.aider.conf.yml`-style object.
+fn merge_aider_config(
+    request: &mut DeepLinkImportRequest,
+    config: &serde_json::Value,
+) -> Result<(), AppError> {
+    let env = config.get("env").unwrap_or(config);
+    let options = config.get("config").unwrap_or(config);
+
+    if request.api_key.as_ref().is_none_or(|s| s.is_empty()) {
+        if let Some(api_key) = env
+            .get("OPENAI_API_KEY")
+            .or_else(|| options.get("openai-api-key"))
+            .and_then(|v| v.as_str())
+        {
+            request.api_key = Some(api_key.to_string());
+        }
+    }
+
+    if request.endpoint.as_ref().is_none_or(|s| s.is_empty()) {
+        if let Some(base_url) = options.get("openai-api-base").and_then(|v| v.as_str()) {
+            request.endpoint = Some(base_url.to_string());
+        }
+    }
+
+    if request.model.is_none() {
+        request.model = options
+            .get("model")
+            .and_then(|v| v.as_str())
+            .map(|s| s.strip_prefix("openai/").unwrap_or(s).to_string());
+    }
+
+    if request.homepage.as_ref().is_none_or(|s| s.is_empty()) {
+        if let Some(endpoint) = request.endpoint.as_ref().filter(|s| !s.is_empty()) {
+            request.homepage = infer_homepage_from_endpoint(endpoint);
+        }
+    }
+
+    Ok(())
+}
+
 /// Merge configuration for additive mode apps (OpenClaw, OpenCode)
 ///
 /// These apps use JSON config directly, so we only extract common fields
@@ -861,6 +1053,55 @@ mod tests {
         assert_eq!(obj.get("api_mode").unwrap(), "chat_completions");
     }
 
+    #[test]
+    fn build_goose_and_crush_settings_use_native_field_names() {
+        let request = DeepLinkImportRequest {
+            resource: "provider".to_string(),
+            name: Some("Relay".to_string()),
+            endpoint: Some("https://api.example.com/v1".to_string()),
+            api_key: Some("sk-test".to_string()),
+            model: Some("deepseek-chat".to_string()),
+            ..Default::default()
+        };
+
+        let goose = build_goose_settings(&request);
+        assert_eq!(goose["engine"], "openai");
+        assert_eq!(goose["display_name"], "Relay");
+        assert_eq!(goose["base_url"], "https://api.example.com/v1");
+        assert_eq!(goose["models"][0]["name"], "deepseek-chat");
+        crate::goose_config::validate_goose_settings(&goose).expect("valid Goose settings");
+
+        let crush = build_crush_settings(&request);
+        assert_eq!(crush["type"], "openai-compat");
+        assert_eq!(crush["name"], "Relay");
+        assert_eq!(crush["api_key"], "sk-test");
+        assert_eq!(crush["models"][0]["id"], "deepseek-chat");
+        crate::crush_config::validate_crush_settings(&crush).expect("valid Crush settings");
+    }
+
+    #[test]
+    fn build_aider_settings_routes_model_through_openai_prefix() {
+        let request = DeepLinkImportRequest {
+            resource: "provider".to_string(),
+            app: Some("aider".to_string()),
+            name: Some("Relay".to_string()),
+            endpoint: Some("https://api.example.com/v1".to_string()),
+            api_key: Some("sk-test".to_string()),
+            model: Some("deepseek-chat".to_string()),
+            ..Default::default()
+        };
+
+        let settings = build_aider_settings(&request);
+        assert_eq!(settings["env"]["OPENAI_API_KEY"], "sk-test");
+        assert_eq!(settings["config"]["model"], "openai/deepseek-chat");
+        assert_eq!(
+            settings["config"]["openai-api-base"],
+            "https://api.example.com/v1"
+        );
+        crate::aider_config::validate_aider_settings_strict(&settings)
+            .expect("valid Aider settings");
+    }
+
     #[test]
     fn build_codex_settings_uses_custom_key_and_preserves_display_name() {
         let request = DeepLinkImportRequest {
@@ -1,11 +1,12 @@
 //! Deep link module tests
 
 use super::mcp::parse_mcp_apps;
-use super::parser::parse_deeplink_url;
+use super::parser::{parse_deeplink_url, parse_deeplink_url_with_trust, sign_deeplink_url};
 use super::prompt::import_prompt_from_deeplink;
 use super::provider::parse_and_merge_config;
 use super::utils::{infer_homepage_from_endpoint, validate_url};
 use super::DeepLinkImportRequest;
+use crate::signing::{self, SignatureStatus, TrustedPublisher};
 use crate::AppType;
 use crate::{store::AppState, Database};
 use base64::prelude::*;
@@ -157,6 +158,76 @@ fn test_infer_homepage() {
     );
 }
 
+// =============================================================================
+// Signature Tests
+// =============================================================================
+
+const SIGNED_TEST_URL: &str = "ccswitch://v1/import?resource=provider&app=claude&name=Team%20Relay&endpoint=https%3A%2F%2Frelay.example.com&apiKey=sk-test";
+
+#[test]
+fn test_parse_unsigned_deeplink_reports_unsigned() {
+    let request = parse_deeplink_url(SIGNED_TEST_URL).unwrap();
+    assert_eq!(request.signature, Some(SignatureStatus::Unsigned));
+}
+
+#[test]
+fn test_parse_signed_deeplink_resolves_trusted_publisher() {
+    let pair = signing::generate_keypair().unwrap();
+    let signed = sign_deeplink_url(SIGNED_TEST_URL, &pair.private_key).unwrap();
+
+    let request = parse_deeplink_url(&signed).unwrap();
+    assert!(matches!(
+        request.signature,
+        Some(SignatureStatus::Untrusted { .. })
+    ));
+    assert_eq!(
+        request.endpoint,
+        Some("https://relay.example.com".to_string())
+    );
+
+    let trusted = vec![TrustedPublisher {
+        name: "Acme".to_string(),
+        public_key: pair.public_key.clone(),
+        added_at: 0,
+    }];
+    let request = parse_deeplink_url_with_trust(&signed, &trusted).unwrap();
+    assert_eq!(
+        request.signature,
+        Some(SignatureStatus::Trusted {
+            publisher: "Acme".to_string(),
+            fingerprint: pair.fingerprint,
+        })
+    );
+
+    // Re-signing replaces the previous signature instead of appending another one
+    let resigned = sign_deeplink_url(&signed, &pair.private_key).unwrap();
+    assert_eq!(resigned.matches("sig=").count(), 1);
+    assert!(parse_deeplink_url_with_trust(&resigned, &trusted).is_ok());
+}
+
+#[test]
+fn test_parse_tampered_deeplink_is_rejected() {
+    let pair = signing::generate_keypair().unwrap();
+    let signed = sign_deeplink_url(SIGNED_TEST_URL, &pair.private_key).unwrap();
+
+    let tampered = signed.replace("relay.example.com", "evil.example.com");
+    let err = parse_deeplink_url(&tampered).unwrap_err().to_string();
+    assert!(err.contains("signature rejected"), "{err}");
+
+    // Adding a parameter after signing also breaks the signature
+    let appended = format!("{signed}&model=other");
+    assert!(parse_deeplink_url(&appended).is_err());
+
+    // A signature without its public key is not silently treated as unsigned
+    let without_key: String = signed
+        .split('&')
+        .filter(|part| !part.starts_with("pubkey="))
+        .collect::<Vec<_>>()
+        .join("&");
+    let err = parse_deeplink_url(&without_key).unwrap_err().to_string();
+    assert!(err.contains("Incomplete deep link signature"), "{err}");
+}
+
 // =============================================================================
 // Provider Tests
 // =============================================================================
@@ -196,6 +267,7 @@ fn test_build_gemini_provider_with_model() {
         usage_access_token: None,
         usage_user_id: None,
         usage_auto_interval: None,
+        signature: None,
     };
 
     let provider = build_provider_from_request(&AppType::Gemini, &request).unwrap();
@@ -249,6 +321,7 @@ fn test_build_gemini_provider_without_model() {
         usage_access_token: None,
         usage_user_id: None,
         usage_auto_interval: None,
+        signature: None,
     };
 
     let provider = build_provider_from_request(&AppType::Gemini, &request).unwrap();
@@ -297,6 +370,7 @@ fn test_parse_and_merge_config_claude() {
         usage_access_token: None,
         usage_user_id: None,
         usage_auto_interval: None,
+        signature: None,
     };
 
     let merged = parse_and_merge_config(&request).unwrap();
@@ -388,6 +462,7 @@ fn test_parse_and_merge_config_url_override() {
         usage_access_token: None,
         usage_user_id: None,
         usage_auto_interval: None,
+        signature: None,
     };
 
     let merged = parse_and_merge_config(&request).unwrap();
@@ -451,6 +526,7 @@ fn test_build_claude_provider_preserves_custom_env_fields() {
         usage_access_token: None,
         usage_user_id: None,
         usage_auto_interval: None,
+        signature: None,
     };
 
     let provider = build_provider_from_request(&AppType::Claude, &request).unwrap();
@@ -506,6 +582,7 @@ fn test_build_claude_provider_without_config_unchanged() {
         usage_access_token: None,
         usage_user_id: None,
         usage_auto_interval: None,
+        signature: None,
     };
 
     let provider = build_provider_from_request(&AppType::Claude, &request).unwrap();
@@ -1,9 +1,12 @@
-use crate::config::{get_home_dir, write_text_file};
+use crate::config::{
+    create_live_dir, get_home_dir, is_staging_writes, live_path_exists, read_live_to_string,
+    write_text_file,
+};
 use crate::error::AppError;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// 获取 Gemini 配置目录路径（支持设置覆盖）
 pub fn get_gemini_dir() -> PathBuf {
@@ -143,21 +146,27 @@ pub fn serialize_env_file(map: &HashMap<String, String>) -> String {
 pub fn read_gemini_env() -> Result<HashMap<String, String>, AppError> {
     let path = get_gemini_env_path();
 
-    if !path.exists() {
+    if !live_path_exists(&path) {
         return Ok(HashMap::new());
     }
 
-    let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let content = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
 
     Ok(parse_env_file(&content))
 }
 
 /// 写入 Gemini .env 文件（原子操作）
 pub fn write_gemini_env_atomic(map: &HashMap<String, String>) -> Result<(), AppError> {
-    let path = get_gemini_env_path();
+    write_env_file_atomic(&get_gemini_env_path(), map)
+}
 
-    // 确保目录存在
-    if let Some(parent) = path.parent() {
+/// 原子写入 .env 文件，目录权限 700、文件权限 600（Gemini 与 Qwen Code 共用）
+pub(crate) fn write_env_file_atomic(
+    path: &Path,
+    map: &HashMap<String, String>,
+) -> Result<(), AppError> {
+    // 确保目录存在（写入暂存期间不落盘）
+    if let Some(parent) = path.parent().filter(|_| !is_staging_writes()) {
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
 
         // 设置目录权限为 700（仅所有者可读写执行）
@@ -173,17 +182,17 @@ pub fn write_gemini_env_atomic(map: &HashMap<String, String>) -> Result<(), AppE
     }
 
     let content = serialize_env_file(map);
-    write_text_file(&path, &content)?;
+    write_text_file(path, &content)?;
 
     // 设置文件权限为 600（仅所有者可读写）
     #[cfg(unix)]
-    {
+    if !is_staging_writes() {
         use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&path)
-            .map_err(|e| AppError::io(&path, e))?
+        let mut perms = fs::metadata(path)
+            .map_err(|e| AppError::io(path, e))?
             .permissions();
         perms.set_mode(0o600);
-        fs::set_permissions(&path, perms).map_err(|e| AppError::io(&path, e))?;
+        fs::set_permissions(path, perms).map_err(|e| AppError::io(path, e))?;
     }
 
     Ok(())
@@ -296,13 +305,13 @@ fn update_selected_type(selected_type: &str) -> Result<(), AppError> {
 
     // 确保目录存在
     if let Some(parent) = settings_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        create_live_dir(parent)?;
     }
 
     // 读取现有的 settings.json（如果存在）
-    let mut settings_content = if settings_path.exists() {
+    let mut settings_content = if live_path_exists(&settings_path) {
         let content =
-            fs::read_to_string(&settings_path).map_err(|e| AppError::io(&settings_path, e))?;
+            read_live_to_string(&settings_path).map_err(|e| AppError::io(&settings_path, e))?;
         serde_json::from_str::<Value>(&content).unwrap_or_else(|_| serde_json::json!({}))
     } else {
         serde_json::json!({})
@@ -0,0 +1,542 @@
+//! Goose 配置文件读写模块
+//!
+//! Goose（Block）的配置分散在 `~/.config/goose/` 下的几个文件中：
+//! - `config.yaml`：全局设置（`GOOSE_PROVIDER` / `GOOSE_MODEL`）与 `extensions`（MCP）
+//! - `custom_providers/<id>.json`：声明式自定义供应商
+//! - `secrets.yaml`：禁用系统钥匙串时的密钥存储，供应商通过 `api_key_env` 引用
+//!
+//! Goose 使用累加式供应商管理：每个供应商对应一个 custom provider 文件，
+//! 切换时只改写 `config.yaml` 中的 `GOOSE_PROVIDER` / `GOOSE_MODEL`。
+//!
+//! ## 供应商 settings_config 结构
+//!
+//! ```json
+//! {
+//!   "engine": "openai",
+//!   "display_name": "DeepSeek",
+//!   "base_url": "https://api.deepseek.com",
+//!   "api_key": "sk-...",
+//!   "models": [{ "name": "deepseek-chat", "context_limit": 128000 }]
+//! }
+//! ```
+//!
+//! ## config.yaml 示例
+//!
+//! ```yaml
+//! GOOSE_PROVIDER: deepseek
+//! GOOSE_MODEL: deepseek-chat
+//! extensions:
+//!   developer:
+//!     enabled: true
+//!     name: developer
+//!     type: builtin
+//!   github:
+//!     cmd: npx
+//!     args: ["-y", "@modelcontextprotocol/server-github"]
+//!     enabled: true
+//!     envs: {}
+//!     name: github
+//!     timeout: 300
+//!     type: stdio
+//! ```
+
+use crate::config::{
+    delete_file, get_home_dir, live_path_exists, read_live_to_string, write_json_file,
+    write_text_file,
+};
+use crate::error::AppError;
+use crate::hermes_config::{json_to_yaml, yaml_to_json};
+use crate::settings::get_goose_override_dir;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Goose 声明式供应商支持的协议引擎
+pub const GOOSE_ENGINES: &[&str] = &["openai", "anthropic", "ollama"];
+
+/// 写入 custom provider 文件时由 cc-switch 管理的字段，读取时剥离
+const MANAGED_FILE_FIELDS: &[&str] = &["name", "api_key_env"];
+
+// ============================================================================
+// Path Functions
+// ============================================================================
+
+/// 获取 Goose 配置目录
+///
+/// 默认路径: `~/.config/goose/`
+/// 可通过 settings.goose_config_dir 覆盖
+pub fn get_goose_dir() -> PathBuf {
+    if let Some(override_dir) = get_goose_override_dir() {
+        return override_dir;
+    }
+
+    get_home_dir().join(".config").join("goose")
+}
+
+/// 获取 Goose 主配置文件路径（`config.yaml`）
+pub fn get_goose_config_path() -> PathBuf {
+    get_goose_dir().join("config.yaml")
+}
+
+/// 获取 Goose 文件密钥存储路径（`secrets.yaml`）
+pub fn get_goose_secrets_path() -> PathBuf {
+    get_goose_dir().join("secrets.yaml")
+}
+
+/// 获取 Goose 自定义供应商目录（`custom_providers/`）
+pub fn get_goose_custom_providers_dir() -> PathBuf {
+    get_goose_dir().join("custom_providers")
+}
+
+fn custom_provider_path(id: &str) -> PathBuf {
+    get_goose_custom_providers_dir().join(format!("{id}.json"))
+}
+
+fn goose_write_lock() -> &'static Mutex<()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+}
+
+// ============================================================================
+// YAML Read / Write
+// ============================================================================
+
+fn read_yaml_mapping(path: &Path) -> Result<serde_yaml::Mapping, AppError> {
+    if !live_path_exists(path) {
+        return Ok(serde_yaml::Mapping::new());
+    }
+
+    let content = read_live_to_string(path).map_err(|e| AppError::io(path, e))?;
+    if content.trim().is_empty() {
+        return Ok(serde_yaml::Mapping::new());
+    }
+
+    match serde_yaml::from_str::<serde_yaml::Value>(&content) {
+        Ok(serde_yaml::Value::Mapping(map)) => Ok(map),
+        Ok(serde_yaml::Value::Null) => Ok(serde_yaml::Mapping::new()),
+        Ok(_) => Err(AppError::Config(format!(
+            "Goose config must be a YAML mapping: {}",
+            path.display()
+        ))),
+        Err(e) => Err(AppError::Config(format!(
+            "Failed to parse Goose config {}: {e}",
+            path.display()
+        ))),
+    }
+}
+
+fn write_yaml_mapping(path: &Path, map: &serde_yaml::Mapping) -> Result<(), AppError> {
+    let content = serde_yaml::to_string(map)
+        .map_err(|e| AppError::Config(format!("Failed to serialize Goose config: {e}")))?;
+    write_text_file(path, &content)
+}
+
+/// 读取 `config.yaml`，文件不存在时返回空 Mapping
+pub fn read_goose_config() -> Result<serde_yaml::Mapping, AppError> {
+    read_yaml_mapping(&get_goose_config_path())
+}
+
+/// 在写锁内读改写 `config.yaml`
+///
+/// Goose 自己也以 serde_yaml 整体读写该文件，因此这里同样整体回写，
+/// 未知的顶层键按原顺序保留。
+fn update_goose_config<F>(updater: F) -> Result<(), AppError>
+where
+    F: FnOnce(&mut serde_yaml::Mapping) -> Result<(), AppError>,
+{
+    let _guard = goose_write_lock().lock()?;
+    let path = get_goose_config_path();
+    let mut config = read_yaml_mapping(&path)?;
+    let before = config.clone();
+    updater(&mut config)?;
+    if config == before {
+        return Ok(());
+    }
+    write_yaml_mapping(&path, &config)?;
+    log::debug!("Goose config written to {path:?}");
+    Ok(())
+}
+
+fn read_goose_secrets() -> Result<serde_yaml::Mapping, AppError> {
+    read_yaml_mapping(&get_goose_secrets_path())
+}
+
+fn update_goose_secrets<F>(updater: F) -> Result<(), AppError>
+where
+    F: FnOnce(&mut serde_yaml::Mapping),
+{
+    let path = get_goose_secrets_path();
+    let mut secrets = read_yaml_mapping(&path)?;
+    let before = secrets.clone();
+    updater(&mut secrets);
+    if secrets == before {
+        return Ok(());
+    }
+    write_yaml_mapping(&path, &secrets)
+}
+
+fn yaml_key(key: &str) -> serde_yaml::Value {
+    serde_yaml::Value::String(key.to_string())
+}
+
+// ============================================================================
+// Provider Conversion
+// ============================================================================
+
+/// 供应商 API Key 在 Goose 中对应的环境变量 / 密钥名
+///
+/// 例如 `deepseek-v3` -> `DEEPSEEK_V3_API_KEY`
+pub fn api_key_env_name(id: &str) -> String {
+    let upper: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{upper}_API_KEY")
+}
+
+/// 将 cc-switch 的 settings_config 转换为 custom provider 文件内容
+///
+/// `api_key` 不会写入文件，而是由调用方写入 `secrets.yaml`；
+/// `existing` 中 UI 未涉及的字段（如 `headers`、`timeout_seconds`）原样保留。
+fn provider_to_goose_file(id: &str, settings: &Value, existing: Option<&Value>) -> Value {
+    let mut file = existing
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+
+    if let Some(obj) = settings.as_object() {
+        for (key, value) in obj {
+            if key == "api_key" || MANAGED_FILE_FIELDS.contains(&key.as_str()) {
+                continue;
+            }
+            file.insert(key.clone(), value.clone());
+        }
+    }
+
+    file.insert("name".to_string(), json!(id));
+    file.insert("api_key_env".to_string(), json!(api_key_env_name(id)));
+    file.entry("engine".to_string())
+        .or_insert_with(|| json!("openai"));
+    file.entry("display_name".to_string())
+        .or_insert_with(|| json!(id));
+    file.entry("models".to_string())
+        .or_insert_with(|| json!([]));
+
+    Value::Object(file)
+}
+
+/// 将 custom provider 文件内容还原为 settings_config
+fn goose_file_to_provider(file: &Value, api_key: Option<&str>) -> Value {
+    let mut settings = Map::new();
+    if let Some(obj) = file.as_object() {
+        for (key, value) in obj {
+            if MANAGED_FILE_FIELDS.contains(&key.as_str()) {
+                continue;
+            }
+            settings.insert(key.clone(), value.clone());
+        }
+    }
+    if let Some(key) = api_key.filter(|k| !k.is_empty()) {
+        settings.insert("api_key".to_string(), json!(key));
+    }
+    Value::Object(settings)
+}
+
+fn read_custom_provider_file(path: &Path) -> Result<Value, AppError> {
+    let content = read_live_to_string(path).map_err(|e| AppError::io(path, e))?;
+    serde_json::from_str(&content).map_err(|e| AppError::json(path, e))
+}
+
+/// 验证 Goose 供应商配置的基本结构
+pub fn validate_goose_settings(settings: &Value) -> Result<(), AppError> {
+    let obj = settings.as_object().ok_or_else(|| {
+        AppError::localized(
+            "goose.validation.not_object",
+            "Goose 配置必须是 JSON 对象",
+            "Goose configuration must be a JSON object",
+        )
+    })?;
+
+    if let Some(engine) = obj.get("engine") {
+        let valid = engine
+            .as_str()
+            .map(|e| GOOSE_ENGINES.contains(&e))
+            .unwrap_or(false);
+        if !valid {
+            return Err(AppError::localized(
+                "goose.validation.invalid_engine",
+                format!(
+                    "Goose 配置格式错误: engine 必须是 {}",
+                    GOOSE_ENGINES.join(" / ")
+                ),
+                format!(
+                    "Goose config invalid: engine must be one of {}",
+                    GOOSE_ENGINES.join(", ")
+                ),
+            ));
+        }
+    }
+
+    if let Some(models) = obj.get("models") {
+        if !models.is_array() {
+            return Err(AppError::localized(
+                "goose.validation.invalid_models",
+                "Goose 配置格式错误: models 必须是数组",
+                "Goose config invalid: models must be an array",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Provider Functions
+// ============================================================================
+
+/// 列出 `custom_providers/` 下全部供应商文件（按文件名排序）
+pub fn get_custom_provider_paths() -> Result<Vec<PathBuf>, AppError> {
+    let dir = get_goose_custom_providers_dir();
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map_err(|e| AppError::io(&dir, e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+    entries.sort();
+    Ok(entries)
+}
+
+/// 读取全部自定义供应商（键为文件名 / 供应商 id）
+///
+/// API Key 从 `secrets.yaml` 中按 `api_key_env` 回填；存放在系统钥匙串中的密钥读不到，
+/// 此时 settings_config 不含 `api_key`。
+pub fn get_providers() -> Result<Map<String, Value>, AppError> {
+    let entries = get_custom_provider_paths()?;
+    let mut providers = Map::new();
+    if entries.is_empty() {
+        return Ok(providers);
+    }
+
+    let secrets = read_goose_secrets()?;
+    for path in entries {
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let file = match read_custom_provider_file(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                log::warn!("Skipping unreadable Goose provider '{id}': {e}");
+                continue;
+            }
+        };
+        let api_key = file
+            .get("api_key_env")
+            .and_then(Value::as_str)
+            .and_then(|env| secrets.get(yaml_key(env)))
+            .and_then(serde_yaml::Value::as_str);
+        providers.insert(id.to_string(), goose_file_to_provider(&file, api_key));
+    }
+
+    Ok(providers)
+}
+
+/// 写入（新增或更新）一个自定义供应商
+pub fn set_provider(id: &str, settings: &Value) -> Result<(), AppError> {
+    let _guard = goose_write_lock().lock()?;
+
+    let path = custom_provider_path(id);
+    let existing = if live_path_exists(&path) {
+        read_custom_provider_file(&path).ok()
+    } else {
+        None
+    };
+    let file = provider_to_goose_file(id, settings, existing.as_ref());
+    write_json_file(&path, &file)?;
+
+    let env_name = api_key_env_name(id);
+    let api_key = settings
+        .get("api_key")
+        .and_then(Value::as_str)
+        .filter(|k| !k.is_empty())
+        .map(str::to_string);
+    update_goose_secrets(|secrets| match api_key {
+        Some(key) => {
+            secrets.insert(yaml_key(&env_name), serde_yaml::Value::String(key));
+        }
+        None => {
+            secrets.remove(yaml_key(&env_name));
+        }
+    })?;
+
+    log::debug!("Goose provider '{id}' written to {path:?}");
+    Ok(())
+}
+
+/// 删除一个自定义供应商及其密钥
+pub fn remove_provider(id: &str) -> Result<(), AppError> {
+    let _guard = goose_write_lock().lock()?;
+
+    delete_file(&custom_provider_path(id))?;
+    let env_name = api_key_env_name(id);
+    update_goose_secrets(|secrets| {
+        secrets.remove(yaml_key(&env_name));
+    })
+}
+
+/// 切换供应商时更新 `GOOSE_PROVIDER` / `GOOSE_MODEL`
+///
+/// 新供应商未声明模型时保留原 `GOOSE_MODEL`。
+pub fn apply_switch_defaults(provider_id: &str, settings: &Value) -> Result<(), AppError> {
+    let first_model = settings
+        .get("models")
+        .and_then(Value::as_array)
+        .and_then(|models| models.first())
+        .and_then(|m| m.get("name"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+
+    update_goose_config(|config| {
+        config.insert(
+            yaml_key("GOOSE_PROVIDER"),
+            serde_yaml::Value::String(provider_id.to_string()),
+        );
+        if let Some(model) = first_model {
+            config.insert(yaml_key("GOOSE_MODEL"), serde_yaml::Value::String(model));
+        }
+        Ok(())
+    })
+}
+
+/// 当前 `GOOSE_PROVIDER`
+pub fn get_active_provider() -> Result<Option<String>, AppError> {
+    Ok(read_goose_config()?
+        .get(yaml_key("GOOSE_PROVIDER"))
+        .and_then(serde_yaml::Value::as_str)
+        .map(str::to_string))
+}
+
+/// 读取 `config.yaml` 与全部自定义供应商，作为 live 快照（JSON）
+pub fn read_goose_live_snapshot() -> Result<Value, AppError> {
+    let config = yaml_to_json(&serde_yaml::Value::Mapping(read_goose_config()?))?;
+    Ok(json!({
+        "config": config,
+        "custom_providers": get_providers()?,
+    }))
+}
+
+// ============================================================================
+// Extensions (MCP) Section Access
+// ============================================================================
+
+/// 读取 `extensions` 段（键为扩展 id）
+pub fn get_extensions() -> Result<Map<String, Value>, AppError> {
+    let config = read_goose_config()?;
+    let Some(extensions) = config.get(yaml_key("extensions")) else {
+        return Ok(Map::new());
+    };
+    Ok(yaml_to_json(extensions)?
+        .as_object()
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// 在写锁内读改写 `extensions` 段
+pub fn update_extensions<F>(updater: F) -> Result<(), AppError>
+where
+    F: FnOnce(&mut Map<String, Value>) -> Result<(), AppError>,
+{
+    update_goose_config(|config| {
+        let mut extensions = match config.get(yaml_key("extensions")) {
+            Some(value) => yaml_to_json(value)?
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+            None => Map::new(),
+        };
+        updater(&mut extensions)?;
+        config.insert(
+            yaml_key("extensions"),
+            json_to_yaml(&Value::Object(extensions))?,
+        );
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_key_env_name_is_upper_snake_case() {
+        assert_eq!(api_key_env_name("deepseek"), "DEEPSEEK_API_KEY");
+        assert_eq!(api_key_env_name("my-relay.v2"), "MY_RELAY_V2_API_KEY");
+    }
+
+    #[test]
+    fn provider_file_strips_api_key_and_sets_managed_fields() {
+        let settings = json!({
+            "engine": "openai",
+            "base_url": "https://api.deepseek.com",
+            "api_key": "sk-secret",
+            "models": [{ "name": "deepseek-chat", "context_limit": 128000 }]
+        });
+        let file = provider_to_goose_file("deepseek", &settings, None);
+
+        assert_eq!(file["name"], "deepseek");
+        assert_eq!(file["api_key_env"], "DEEPSEEK_API_KEY");
+        assert_eq!(file["display_name"], "deepseek");
+        assert_eq!(file["models"][0]["name"], "deepseek-chat");
+        assert!(file.get("api_key").is_none(), "key must live in secrets");
+    }
+
+    #[test]
+    fn provider_file_preserves_unknown_on_disk_fields() {
+        let existing = json!({
+            "name": "relay",
+            "engine": "anthropic",
+            "timeout_seconds": 600,
+            "headers": { "X-Org": "acme" }
+        });
+        let settings = json!({ "engine": "openai", "base_url": "https://relay.example" });
+        let file = provider_to_goose_file("relay", &settings, Some(&existing));
+
+        assert_eq!(file["engine"], "openai");
+        assert_eq!(file["timeout_seconds"], 600);
+        assert_eq!(file["headers"]["X-Org"], "acme");
+    }
+
+    #[test]
+    fn provider_round_trip_restores_api_key() {
+        let settings = json!({
+            "engine": "openai",
+            "display_name": "Relay",
+            "base_url": "https://relay.example",
+            "api_key": "sk-relay",
+            "models": []
+        });
+        let file = provider_to_goose_file("relay", &settings, None);
+        let restored = goose_file_to_provider(&file, Some("sk-relay"));
+        assert_eq!(restored, settings);
+    }
+
+    #[test]
+    fn validate_rejects_unknown_engine() {
+        assert!(validate_goose_settings(&json!({ "engine": "openai" })).is_ok());
+        assert!(validate_goose_settings(&json!({ "engine": "bedrock" })).is_err());
+        assert!(validate_goose_settings(&json!({ "models": "gpt-4o" })).is_err());
+        assert!(validate_goose_settings(&json!("nope")).is_err());
+    }
+}
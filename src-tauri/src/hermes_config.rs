@@ -30,7 +30,10 @@
 //!     args: ["-y", "@modelcontextprotocol/server-filesystem"]
 //! ```
 
-use crate::config::{atomic_write, get_app_config_dir};
+use crate::config::{
+    atomic_write, create_live_dir, get_app_config_dir, is_staging_writes, live_path_exists,
+    read_live_to_string,
+};
 use crate::error::AppError;
 use crate::settings::{effective_backup_retain_count, get_hermes_override_dir};
 use chrono::Local;
@@ -107,11 +110,11 @@ pub struct HermesModelConfig {
 /// 如果文件不存在，返回空 Mapping
 pub fn read_hermes_config() -> Result<serde_yaml::Value, AppError> {
     let path = get_hermes_config_path();
-    if !path.exists() {
+    if !live_path_exists(&path) {
         return Ok(serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
     }
 
-    let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let content = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
     if content.trim().is_empty() {
         return Ok(serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
     }
@@ -321,7 +324,11 @@ fn replace_yaml_section(
 // Backup & Cleanup
 // ============================================================================
 
-fn create_hermes_backup(source: &str) -> Result<PathBuf, AppError> {
+/// 写入前备份原配置；写入暂存（预览）期间不落盘，返回 `None`
+fn create_hermes_backup(source: &str) -> Result<Option<PathBuf>, AppError> {
+    if is_staging_writes() {
+        return Ok(None);
+    }
     let backup_dir = get_app_config_dir().join("backups").join("hermes");
     fs::create_dir_all(&backup_dir).map_err(|e| AppError::io(&backup_dir, e))?;
 
@@ -338,7 +345,7 @@ fn create_hermes_backup(source: &str) -> Result<PathBuf, AppError> {
 
     atomic_write(&backup_path, source.as_bytes())?;
     cleanup_hermes_backups(&backup_dir)?;
-    Ok(backup_path)
+    Ok(Some(backup_path))
 }
 
 fn cleanup_hermes_backups(dir: &Path) -> Result<(), AppError> {
@@ -395,8 +402,8 @@ fn write_yaml_section_to_config_locked(
     value: &serde_yaml::Value,
 ) -> Result<HermesWriteOutcome, AppError> {
     let config_path = get_hermes_config_path();
-    let raw = if config_path.exists() {
-        fs::read_to_string(&config_path).map_err(|e| AppError::io(&config_path, e))?
+    let raw = if live_path_exists(&config_path) {
+        read_live_to_string(&config_path).map_err(|e| AppError::io(&config_path, e))?
     } else {
         String::new()
     };
@@ -407,14 +414,14 @@ fn write_yaml_section_to_config_locked(
         return Ok(HermesWriteOutcome::default());
     }
 
-    let backup_path = if !raw.is_empty() {
-        Some(create_hermes_backup(&raw)?)
-    } else {
+    let backup_path = if raw.is_empty() {
         None
+    } else {
+        create_hermes_backup(&raw)?
     };
 
     if let Some(parent) = config_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        create_live_dir(parent)?;
     }
 
     atomic_write(&config_path, new_raw.as_bytes())?;
@@ -1,4 +1,6 @@
+mod aider_config;
 mod app_config;
+mod app_definition;
 mod app_store;
 mod auto_launch;
 mod claude_desktop_config;
@@ -6,13 +8,16 @@ mod claude_mcp;
 mod claude_plugin;
 mod codex_config;
 mod codex_history_migration;
+mod codex_profiles;
 mod commands;
 mod config;
+mod crush_config;
 mod database;
 mod deeplink;
 mod error;
 mod gemini_config;
 mod gemini_mcp;
+mod goose_config;
 pub mod hermes_config;
 mod init_status;
 mod lightweight;
@@ -24,19 +29,24 @@ mod opencode_config;
 mod panic_hook;
 mod prompt;
 mod prompt_files;
+mod prompt_template;
 mod provider;
 mod provider_defaults;
 mod proxy;
+mod qwen_config;
 mod services;
 mod session_manager;
 mod settings;
+mod signing;
 mod store;
 
 mod tray;
 mod usage_events;
 mod usage_script;
 
-pub use app_config::{AppType, InstalledSkill, McpApps, McpServer, MultiAppConfig, SkillApps};
+pub use app_config::{
+    AppType, InstalledSkill, McpApps, McpServer, MultiAppConfig, PromptApps, SkillApps,
+};
 pub use codex_config::{get_codex_auth_path, get_codex_config_path, write_codex_live_atomic};
 pub use commands::open_provider_terminal;
 pub use commands::*;
@@ -48,13 +58,14 @@ pub use mcp::{
     import_from_claude, import_from_codex, import_from_gemini, remove_server_from_claude,
     remove_server_from_codex, remove_server_from_gemini, sync_enabled_to_claude,
     sync_enabled_to_codex, sync_enabled_to_gemini, sync_single_server_to_claude,
-    sync_single_server_to_codex, sync_single_server_to_gemini,
+    sync_single_server_to_codex, sync_single_server_to_gemini, SecretResolver,
 };
 pub use provider::{Provider, ProviderMeta};
 pub use services::{
-    skill::{migrate_skills_to_ssot, ImportSkillSelection},
-    ConfigService, EndpointLatency, McpService, PromptService, ProviderService, ProxyService,
-    SkillService, SpeedtestService,
+    live_drift::{LiveDriftAction, LiveDriftCategory},
+    skill::{migrate_skills_to_ssot, ImportSkillSelection, SkillSourceRequest},
+    ConfigService, CustomAppService, EndpointLatency, LiveDriftService, LiveHistoryService,
+    McpService, PromptService, ProviderService, ProxyService, SkillService, SpeedtestService,
 };
 pub use settings::{update_settings, AppSettings};
 pub use store::AppState;
@@ -116,6 +127,19 @@ fn redact_url_for_log(url_str: &str) -> String {
     }
 }
 
+/// 读取受信任的签名发布者；数据库尚未就绪时按空列表处理（仍会校验签名完整性）
+fn load_trusted_publishers(app: &tauri::AppHandle) -> Vec<crate::signing::TrustedPublisher> {
+    app.try_state::<store::AppState>()
+        .and_then(|state| match state.db.get_trusted_publishers() {
+            Ok(publishers) => Some(publishers),
+            Err(e) => {
+                log::warn!("读取受信任发布者失败: {e}");
+                None
+            }
+        })
+        .unwrap_or_default()
+}
+
 /// 统一处理 ccswitch:// 深链接 URL
 ///
 /// - 解析 URL
@@ -135,7 +159,7 @@ fn handle_deeplink_url(
     log::info!("✓ Deep link URL detected from {source}: {redacted_url}");
     log::debug!("Deep link URL (raw) from {source}: {url_str}");
 
-    match crate::deeplink::parse_deeplink_url(url_str) {
+    match crate::deeplink::parse_deeplink_url_with_trust(url_str, &load_trusted_publishers(app)) {
         Ok(request) => {
             log::info!(
                 "✓ Successfully parsed deep link: resource={}, app={:?}, name={:?}",
@@ -216,6 +240,14 @@ fn macos_tray_icon() -> Option<Image<'static>> {
     }
 }
 
+/// MCP 网关 stdio 适配模式的命令行参数
+pub const MCP_GATEWAY_STDIO_ARG: &str = mcp::GATEWAY_STDIO_ARG;
+
+/// 以 stdio 适配模式运行 MCP 网关，返回进程退出码
+pub fn run_mcp_gateway_bridge(endpoint: &str) -> i32 {
+    mcp::run_stdio_bridge(endpoint)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // 设置 panic hook，在应用崩溃时记录日志到 <app_config_dir>/crash.log（默认 ~/.cc-switch/crash.log）
@@ -291,6 +323,7 @@ pub fn run() {
         })
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(
@@ -639,6 +672,20 @@ pub fn run() {
                 Ok(_) => log::debug!("○ No new Hermes providers to import"),
                 Err(e) => log::warn!("✗ Failed to import Hermes providers: {e}"),
             }
+            match crate::services::provider::import_goose_providers_from_live(&app_state) {
+                Ok(count) if count > 0 => {
+                    log::info!("✓ Imported {count} Goose provider(s) from live config");
+                }
+                Ok(_) => log::debug!("○ No new Goose providers to import"),
+                Err(e) => log::warn!("✗ Failed to import Goose providers: {e}"),
+            }
+            match crate::services::provider::import_crush_providers_from_live(&app_state) {
+                Ok(count) if count > 0 => {
+                    log::info!("✓ Imported {count} Crush provider(s) from live config");
+                }
+                Ok(_) => log::debug!("○ No new Crush providers to import"),
+                Err(e) => log::warn!("✗ Failed to import Crush providers: {e}"),
+            }
 
             // 2. OMO 配置导入（当数据库中无 OMO provider 时，从本地文件导入）
             {
@@ -734,6 +781,30 @@ pub fn run() {
                     Ok(_) => log::debug!("○ No Hermes MCP servers found to import"),
                     Err(e) => log::warn!("✗ Failed to import Hermes MCP: {e}"),
                 }
+
+                match crate::services::mcp::McpService::import_from_qwen(&app_state) {
+                    Ok(count) if count > 0 => {
+                        log::info!("✓ Imported {count} MCP server(s) from Qwen Code");
+                    }
+                    Ok(_) => log::debug!("○ No Qwen Code MCP servers found to import"),
+                    Err(e) => log::warn!("✗ Failed to import Qwen Code MCP: {e}"),
+                }
+
+                match crate::services::mcp::McpService::import_from_goose(&app_state) {
+                    Ok(count) if count > 0 => {
+                        log::info!("✓ Imported {count} MCP server(s) from Goose");
+                    }
+                    Ok(_) => log::debug!("○ No Goose MCP servers found to import"),
+                    Err(e) => log::warn!("✗ Failed to import Goose MCP: {e}"),
+                }
+
+                match crate::services::mcp::McpService::import_from_crush(&app_state) {
+                    Ok(count) if count > 0 => {
+                        log::info!("✓ Imported {count} MCP server(s) from Crush");
+                    }
+                    Ok(_) => log::debug!("○ No Crush MCP servers found to import"),
+                    Err(e) => log::warn!("✗ Failed to import Crush MCP: {e}"),
+                }
             }
 
             // 4. 导入提示词文件（表空时触发）
@@ -747,6 +818,7 @@ pub fn run() {
                     crate::app_config::AppType::OpenCode,
                     crate::app_config::AppType::OpenClaw,
                     crate::app_config::AppType::Hermes,
+                    crate::app_config::AppType::Qwen,
                 ] {
                     match crate::services::prompt::PromptService::import_from_file_on_first_launch(
                         &app_state,
@@ -761,6 +833,13 @@ pub fn run() {
                 }
             }
 
+            // 5. 上次配置档案切换中途退出时回滚到切换前状态
+            match crate::services::ProfileService::recover_interrupted_switch(&app_state) {
+                Ok(true) => log::info!("✓ Rolled back interrupted profile switch"),
+                Ok(false) => {}
+                Err(e) => log::warn!("✗ Failed to roll back interrupted profile switch: {e}"),
+            }
+
             // 迁移旧的 app_config_dir 配置到 Store
             if let Err(e) = app_store::migrate_app_config_dir_from_settings(app.handle()) {
                 log::warn!("迁移 app_config_dir 失败: {e}");
@@ -1013,6 +1092,24 @@ pub fn run() {
                     }
                 });
 
+                // 会话保留策略定时器（仅在配置启用且到期时执行）
+                crate::services::session_archive::SessionArchiveService::start_worker(
+                    state.db.clone(),
+                );
+
+                // 定期用量报表（仅在配置启用且上一周期尚未生成时执行）
+                crate::services::usage_export::start_worker(state.db.clone());
+
+                // Live 配置漂移检测（外部修改时通知前端）
+                crate::services::LiveDriftService::start_worker(app_handle.clone());
+
+                // 声明式应用定义（~/.cc-switch/apps/*.toml）
+                let app_definitions = crate::services::CustomAppService::reload();
+                if !app_definitions.apps.is_empty() {
+                    log::info!("已加载 {} 个声明式应用定义", app_definitions.apps.len());
+                    crate::services::CustomAppService::sync_all_mcp(&state);
+                }
+
                 // Session log usage sync: 启动时同步一次，之后每 60 秒检查
                 let db_for_session_sync = state.db.clone();
                 tauri::async_runtime::spawn(async move {
@@ -1131,6 +1228,20 @@ pub fn run() {
             commands::delete_provider,
             commands::remove_provider_from_live_config,
             commands::switch_provider,
+            commands::preview_switch_provider,
+            commands::list_app_definitions,
+            commands::reload_app_definitions,
+            commands::get_custom_app_providers,
+            commands::get_custom_app_current_provider,
+            commands::upsert_custom_app_provider,
+            commands::delete_custom_app_provider,
+            commands::switch_custom_app_provider,
+            commands::import_custom_app_from_live,
+            commands::sync_custom_app_mcp,
+            commands::get_custom_app_prompts,
+            commands::upsert_custom_app_prompt,
+            commands::delete_custom_app_prompt,
+            commands::enable_custom_app_prompt,
             commands::import_default_config,
             commands::get_claude_desktop_status,
             commands::get_claude_desktop_default_routes,
@@ -1154,6 +1265,13 @@ pub fn run() {
             commands::set_common_config_snippet,
             commands::extract_common_config_snippet,
             commands::read_live_provider_settings,
+            commands::get_live_config_drifts,
+            commands::resolve_live_config_drift,
+            commands::list_live_history_files,
+            commands::list_live_history_versions,
+            commands::diff_live_history,
+            commands::restore_live_history,
+            commands::set_live_history_retention,
             commands::get_settings,
             commands::save_settings,
             commands::get_rectifier_config,
@@ -1201,6 +1319,34 @@ pub fn run() {
             commands::delete_mcp_server,
             commands::toggle_mcp_app,
             commands::import_mcp_from_apps,
+            commands::probe_mcp_server,
+            commands::probe_mcp_spec,
+            commands::get_mcp_probe_results,
+            commands::get_mcp_gateway_config,
+            commands::save_mcp_gateway_config,
+            commands::list_mcp_gateway_tools,
+            commands::list_mcp_secrets,
+            commands::set_mcp_secret,
+            commands::delete_mcp_secret,
+            commands::validate_mcp_secret_refs,
+            // Team bundles
+            commands::export_team_bundle,
+            commands::preview_team_bundle,
+            commands::import_team_bundle,
+            commands::list_team_bundle_imports,
+            // Profiles
+            commands::list_profiles,
+            commands::save_profile,
+            commands::delete_profile,
+            commands::get_current_profile,
+            commands::apply_profile,
+            // Project scopes
+            commands::list_projects,
+            commands::save_project,
+            commands::delete_project,
+            commands::preview_project_sync,
+            commands::apply_project_sync,
+            commands::check_project_drift,
             // Prompt management
             commands::get_prompts,
             commands::upsert_prompt,
@@ -1208,6 +1354,14 @@ pub fn run() {
             commands::enable_prompt,
             commands::import_prompt_from_file,
             commands::get_current_prompt_file_content,
+            commands::get_prompt_stack_config,
+            commands::set_prompt_stack,
+            commands::save_prompt_variables,
+            commands::preview_prompt_stack,
+            commands::get_shared_prompts,
+            commands::upsert_shared_prompt,
+            commands::delete_shared_prompt,
+            commands::sync_shared_prompt,
             // model list fetch (OpenAI-compatible /v1/models)
             commands::fetch_models_for_config,
             // ours: endpoint speed test + custom endpoint management
@@ -1248,6 +1402,12 @@ pub fn run() {
             commands::merge_deeplink_config,
             commands::import_from_deeplink,
             commands::import_from_deeplink_unified,
+            // Signed imports
+            commands::get_trusted_publishers,
+            commands::add_trusted_publisher,
+            commands::remove_trusted_publisher,
+            commands::generate_publisher_key,
+            commands::sign_deeplink,
             update_tray_menu,
             // Environment variable management
             commands::check_env_conflicts,
@@ -1266,6 +1426,8 @@ pub fn run() {
             commands::discover_available_skills,
             commands::check_skill_updates,
             commands::update_skill,
+            commands::install_skill_from_source,
+            commands::pin_skill,
             commands::migrate_skill_storage,
             commands::search_skills_sh,
             // Skill management (legacy API compatibility)
@@ -1279,6 +1441,9 @@ pub fn run() {
             commands::add_skill_repo,
             commands::remove_skill_repo,
             commands::install_skills_from_zip,
+            commands::export_skills_lock,
+            commands::check_skills_lock,
+            commands::import_skills_lock,
             // Auto launch
             commands::set_auto_launch,
             commands::get_auto_launch_status,
@@ -1327,6 +1492,21 @@ pub fn run() {
             commands::get_model_pricing,
             commands::update_model_pricing,
             commands::delete_model_pricing,
+            commands::import_model_pricing_catalog,
+            commands::get_unpriced_models,
+            commands::get_provider_model_pricing,
+            commands::upsert_provider_model_pricing,
+            commands::delete_provider_model_pricing,
+            commands::export_usage_data,
+            commands::get_usage_report_schedule,
+            commands::save_usage_report_schedule,
+            commands::run_usage_report_now,
+            commands::get_usage_report_state,
+            commands::get_budget_alert_config,
+            commands::save_budget_alert_config,
+            commands::get_budget_usages,
+            commands::get_budget_alert_history,
+            commands::test_budget_webhook,
             commands::check_provider_limits,
             // Session usage sync
             commands::sync_session_usage,
@@ -1338,6 +1518,15 @@ pub fn run() {
             commands::save_stream_check_config,
             // Session manager
             commands::list_sessions,
+            commands::get_session_cost_report,
+            commands::archive_sessions,
+            commands::list_session_archives,
+            commands::restore_session_archive,
+            commands::delete_session_archive,
+            commands::get_session_retention_config,
+            commands::save_session_retention_config,
+            commands::run_session_retention,
+            commands::get_session_retention_state,
             commands::get_session_messages,
             commands::delete_session,
             commands::delete_sessions,
@@ -1382,6 +1571,17 @@ pub fn run() {
             commands::set_hermes_memory,
             commands::get_hermes_memory_limits,
             commands::set_hermes_memory_enabled,
+            // Codex config.toml profiles
+            commands::get_codex_config_settings,
+            commands::save_codex_config_settings,
+            commands::upsert_codex_profile,
+            commands::delete_codex_profile,
+            commands::rename_codex_profile,
+            // Goose / Crush specific
+            commands::import_goose_providers_from_live,
+            commands::get_goose_live_provider_ids,
+            commands::import_crush_providers_from_live,
+            commands::get_crush_live_provider_ids,
             // Global upstream proxy
             commands::get_global_proxy_url,
             commands::set_global_proxy_url,
@@ -1535,7 +1735,10 @@ pub fn run() {
                             }
 
                             // 解析并广播深链接事件，复用与 single_instance 相同的逻辑
-                            match crate::deeplink::parse_deeplink_url(&url_str) {
+                            match crate::deeplink::parse_deeplink_url_with_trust(
+                                &url_str,
+                                &load_trusted_publishers(app_handle),
+                            ) {
                                 Ok(request) => {
                                     log::info!(
                                         "Successfully parsed deep link from RunEvent::Opened: resource={}, app={:?}",
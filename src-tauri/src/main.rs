@@ -2,6 +2,20 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // MCP 网关 stdio 适配模式：由 Claude Code / Codex 等客户端以子进程方式启动，
+    // 不创建窗口，只在 stdin/stdout 与本地代理的 `/mcp/<app>` 端点之间转发。
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some(cc_switch_lib::MCP_GATEWAY_STDIO_ARG) {
+        let Some(endpoint) = args.get(2) else {
+            eprintln!(
+                "usage: cc-switch {} <endpoint>",
+                cc_switch_lib::MCP_GATEWAY_STDIO_ARG
+            );
+            std::process::exit(2);
+        };
+        std::process::exit(cc_switch_lib::run_mcp_gateway_bridge(endpoint));
+    }
+
     // 在 Linux 上设置 WebKit 环境变量以解决 DMA-BUF 渲染问题
     // 某些 Linux 系统（如 Debian 13.2、Nvidia GPU）上 WebKitGTK 的 DMA-BUF 渲染器可能导致白屏/黑屏
     // 参考: https://github.com/tauri-apps/tauri/issues/9394
@@ -6,12 +6,14 @@ use std::collections::HashMap;
 use crate::app_config::{McpApps, McpConfig, McpServer, MultiAppConfig};
 use crate::error::AppError;
 
+use super::secrets::SecretResolver;
 use super::validation::{extract_server_spec, validate_server_spec};
 
 fn should_sync_claude_mcp() -> bool {
     // Claude 未安装/未初始化时：通常 ~/.claude 目录与 ~/.claude.json 都不存在。
     // 按用户偏好：此时跳过写入/删除，不创建任何文件或目录。
-    crate::config::get_claude_config_dir().exists() || crate::config::get_claude_mcp_path().exists()
+    crate::config::live_path_exists(&crate::config::get_claude_config_dir())
+        || crate::config::live_path_exists(&crate::config::get_claude_mcp_path())
 }
 
 /// 返回已启用的 MCP 服务器（过滤 enabled==true）
@@ -93,6 +95,9 @@ pub fn import_from_claude(config: &mut MultiAppConfig) -> Result<usize, AppError
                         gemini: false,
                         opencode: false,
                         hermes: false,
+                        qwen: false,
+                        goose: false,
+                        crush: false,
                     },
                     description: None,
                     homepage: None,
@@ -117,10 +122,12 @@ pub fn sync_single_server_to_claude(
     _config: &MultiAppConfig,
     id: &str,
     server_spec: &Value,
+    secrets: &SecretResolver,
 ) -> Result<(), AppError> {
     if !should_sync_claude_mcp() {
         return Ok(());
     }
+    let server_spec = &secrets.resolve_for_write(id, server_spec);
     // 读取现有的 MCP 配置
     let current = crate::claude_mcp::read_mcp_servers_map()?;
 
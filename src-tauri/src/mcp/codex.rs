@@ -11,12 +11,13 @@ use std::collections::HashMap;
 use crate::app_config::{McpApps, McpConfig, McpServer, MultiAppConfig};
 use crate::error::AppError;
 
+use super::secrets::SecretResolver;
 use super::validation::{extract_server_spec, validate_server_spec};
 
 fn should_sync_codex_mcp() -> bool {
     // Codex 未安装/未初始化时：~/.codex 目录不存在。
     // 按用户偏好：目录缺失时跳过写入/删除，不创建任何文件或目录。
-    crate::codex_config::get_codex_config_dir().exists()
+    crate::config::live_path_exists(&crate::codex_config::get_codex_config_dir())
 }
 
 /// 返回已启用的 MCP 服务器（过滤 enabled==true）
@@ -237,6 +238,9 @@ pub fn import_from_codex(config: &mut MultiAppConfig) -> Result<usize, AppError>
                             gemini: false,
                             opencode: false,
                             hermes: false,
+                            qwen: false,
+                            goose: false,
+                            crush: false,
                         },
                         description: None,
                         homepage: None,
@@ -349,18 +353,20 @@ pub fn sync_single_server_to_codex(
     _config: &MultiAppConfig,
     id: &str,
     server_spec: &Value,
+    secrets: &SecretResolver,
 ) -> Result<(), AppError> {
     if !should_sync_codex_mcp() {
         return Ok(());
     }
+    let server_spec = &secrets.resolve_for_write(id, server_spec);
     use toml_edit::Item;
 
     // 读取现有的 config.toml
     let config_path = crate::codex_config::get_codex_config_path();
 
-    let mut doc = if config_path.exists() {
-        let content =
-            std::fs::read_to_string(&config_path).map_err(|e| AppError::io(&config_path, e))?;
+    let mut doc = if crate::config::live_path_exists(&config_path) {
+        let content = crate::config::read_live_to_string(&config_path)
+            .map_err(|e| AppError::io(&config_path, e))?;
         // 尝试解析现有配置，如果失败则创建新文档（容错处理）
         match content.parse::<toml_edit::DocumentMut>() {
             Ok(doc) => doc,
@@ -409,12 +415,12 @@ pub fn remove_server_from_codex(id: &str) -> Result<(), AppError> {
     }
     let config_path = crate::codex_config::get_codex_config_path();
 
-    if !config_path.exists() {
+    if !crate::config::live_path_exists(&config_path) {
         return Ok(()); // 文件不存在，无需删除
     }
 
-    let content =
-        std::fs::read_to_string(&config_path).map_err(|e| AppError::io(&config_path, e))?;
+    let content = crate::config::read_live_to_string(&config_path)
+        .map_err(|e| AppError::io(&config_path, e))?;
 
     // 尝试解析现有配置，如果失败则直接返回（无法删除不存在的内容）
     let mut doc = match content.parse::<toml_edit::DocumentMut>() {
@@ -559,7 +565,7 @@ fn json_value_to_toml_item(value: &Value, field_name: &str) -> Option<toml_edit:
 /// 1. 核心字段（type, command, args, url, headers, env, cwd）使用强类型处理
 /// 2. 扩展字段（timeout、retry 等）通过白名单列表自动转换
 /// 3. 其他未知字段使用通用转换器尝试转换
-fn json_server_to_toml_table(spec: &Value) -> Result<toml_edit::Table, AppError> {
+pub(crate) fn json_server_to_toml_table(spec: &Value) -> Result<toml_edit::Table, AppError> {
     use toml_edit::{Array, Item, Table};
 
     let mut t = Table::new();
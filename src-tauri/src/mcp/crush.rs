@@ -0,0 +1,283 @@
+//! Crush MCP sync and import module
+//!
+//! Handles conversion between CC Switch unified MCP format and the `mcp` section
+//! of `~/.config/crush/crush.json`.
+//!
+//! ## Format mapping
+//!
+//! | CC Switch unified (JSON)                                 | Crush `mcp` entry                         |
+//! |----------------------------------------------------------|-------------------------------------------|
+//! | `{"type":"stdio","command":"npx","args":[...],"env":{}}` | same keys, `type: "stdio"`                |
+//! | `{"type":"sse"/"http","url":"...","headers":{}}`         | same keys, `type: "sse"` / `type: "http"` |
+//!
+//! Crush's shape is almost identical to ours; the differences are that Crush
+//! has no `cwd`, and carries its own `timeout`, `disabled` and `disabled_tools`
+//! fields, which are preserved on merge-on-write and stripped on import.
+
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+use crate::app_config::{McpApps, McpServer, MultiAppConfig};
+use crate::crush_config;
+use crate::error::AppError;
+
+use super::secrets::SecretResolver;
+use super::validation::validate_server_spec;
+
+/// Crush-specific fields preserved on merge-on-write, stripped on import.
+const CRUSH_EXTRA_FIELDS: &[&str] = &["timeout", "disabled", "disabled_tools"];
+
+/// Core fields shared by both formats.
+const CRUSH_CORE_FIELDS: &[&str] = &["command", "args", "env", "url", "headers"];
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Check if Crush MCP sync should proceed
+fn should_sync_crush_mcp() -> bool {
+    crate::config::live_path_exists(&crush_config::get_crush_dir())
+}
+
+fn copy_core_fields(from: &Map<String, Value>, to: &mut Map<String, Value>) {
+    for &field in CRUSH_CORE_FIELDS {
+        let Some(value) = from.get(field) else {
+            continue;
+        };
+        let empty = match value {
+            Value::Array(a) => a.is_empty(),
+            Value::Object(o) => o.is_empty(),
+            _ => false,
+        };
+        if !empty {
+            to.insert(field.to_string(), value.clone());
+        }
+    }
+}
+
+// ============================================================================
+// Format Conversion
+// ============================================================================
+
+/// Convert CC Switch unified format to a Crush `mcp` entry
+fn convert_to_crush_format(spec: &Value) -> Result<Value, AppError> {
+    let obj = spec
+        .as_object()
+        .ok_or_else(|| AppError::McpValidation("MCP spec must be a JSON object".into()))?;
+
+    let typ = obj.get("type").and_then(|v| v.as_str()).unwrap_or("stdio");
+    if !matches!(typ, "stdio" | "sse" | "http") {
+        return Err(AppError::McpValidation(format!("Unknown MCP type: {typ}")));
+    }
+
+    let mut result = Map::new();
+    result.insert("type".into(), json!(typ));
+    copy_core_fields(obj, &mut result);
+    if obj.contains_key("cwd") {
+        log::debug!("Crush MCP entries have no 'cwd' field; dropping it");
+    }
+
+    Ok(Value::Object(result))
+}
+
+/// Convert a Crush `mcp` entry to CC Switch unified format
+fn convert_from_crush_format(id: &str, spec: &Value) -> Result<Value, AppError> {
+    let obj = spec
+        .as_object()
+        .ok_or_else(|| AppError::McpValidation("Crush MCP spec must be a JSON object".into()))?;
+
+    let typ = match obj.get("type").and_then(|v| v.as_str()) {
+        Some(t @ ("stdio" | "sse" | "http")) => t,
+        Some(other) => {
+            return Err(AppError::McpValidation(format!(
+                "Crush MCP server '{id}' has unsupported type '{other}'"
+            )));
+        }
+        // Crush defaults to stdio when `type` is omitted
+        None if obj.contains_key("command") => "stdio",
+        None => "http",
+    };
+
+    let mut result = Map::new();
+    result.insert("type".into(), json!(typ));
+    copy_core_fields(obj, &mut result);
+
+    Ok(Value::Object(result))
+}
+
+/// Merge new spec into existing Crush entry, preserving Crush-specific fields.
+fn merge_crush_spec(existing: &Value, new_spec: &Value) -> Value {
+    let mut result = new_spec.as_object().cloned().unwrap_or_default();
+    if let Some(existing_obj) = existing.as_object() {
+        for &field in CRUSH_EXTRA_FIELDS {
+            if let Some(val) = existing_obj.get(field) {
+                result.insert(field.to_string(), val.clone());
+            }
+        }
+    }
+    Value::Object(result)
+}
+
+// ============================================================================
+// Public API: Sync Functions
+// ============================================================================
+
+/// Sync a single MCP server to Crush live config (merge-on-write)
+pub fn sync_single_server_to_crush(
+    _config: &MultiAppConfig,
+    id: &str,
+    server_spec: &Value,
+    secrets: &SecretResolver,
+) -> Result<(), AppError> {
+    if !should_sync_crush_mcp() {
+        return Ok(());
+    }
+    let server_spec = secrets.resolve_for_write(id, server_spec);
+    let crush_spec = convert_to_crush_format(&server_spec)?;
+
+    crush_config::update_mcp_servers(|servers| {
+        let merged = match servers.get(id) {
+            Some(existing) => merge_crush_spec(existing, &crush_spec),
+            None => crush_spec,
+        };
+        servers.insert(id.to_string(), merged);
+        Ok(())
+    })
+}
+
+/// Remove a single MCP server from Crush live config
+pub fn remove_server_from_crush(id: &str) -> Result<(), AppError> {
+    if !should_sync_crush_mcp() {
+        return Ok(());
+    }
+
+    crush_config::update_mcp_servers(|servers| {
+        servers.remove(id);
+        Ok(())
+    })
+}
+
+/// Import MCP servers from Crush config to unified structure
+///
+/// Existing servers will have Crush app enabled without overwriting other fields.
+pub fn import_from_crush(config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    let crush_servers = crush_config::get_mcp_servers()?;
+    if crush_servers.is_empty() {
+        return Ok(0);
+    }
+
+    let servers = config.mcp.servers.get_or_insert_with(HashMap::new);
+
+    let mut changed = 0;
+    let mut errors = Vec::new();
+
+    for (id, spec) in &crush_servers {
+        let unified_spec = match convert_from_crush_format(id, spec) {
+            Ok(s) => s,
+            Err(e) => {
+                log::warn!("Skip invalid Crush MCP server '{id}': {e}");
+                errors.push(format!("{id}: {e}"));
+                continue;
+            }
+        };
+
+        if let Err(e) = validate_server_spec(&unified_spec) {
+            log::warn!("Skip invalid MCP server '{id}' after conversion: {e}");
+            errors.push(format!("{id}: {e}"));
+            continue;
+        }
+
+        if let Some(existing) = servers.get_mut(id) {
+            if !existing.apps.crush {
+                existing.apps.crush = true;
+                changed += 1;
+                log::info!("MCP server '{id}' enabled for Crush");
+            }
+        } else {
+            servers.insert(
+                id.clone(),
+                McpServer {
+                    id: id.clone(),
+                    name: id.clone(),
+                    server: unified_spec,
+                    apps: McpApps {
+                        crush: true,
+                        ..Default::default()
+                    },
+                    description: None,
+                    homepage: None,
+                    docs: None,
+                    tags: Vec::new(),
+                },
+            );
+            changed += 1;
+            log::info!("Imported new MCP server '{id}' from Crush");
+        }
+    }
+
+    if !errors.is_empty() {
+        log::warn!(
+            "Import completed with {} failures: {:?}",
+            errors.len(),
+            errors
+        );
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_stdio_to_crush_drops_cwd_and_empty_fields() {
+        let spec = json!({
+            "type": "stdio",
+            "command": "node",
+            "args": [],
+            "env": { "A": "1" },
+            "cwd": "/tmp"
+        });
+
+        let result = convert_to_crush_format(&spec).unwrap();
+        assert_eq!(
+            result,
+            json!({ "type": "stdio", "command": "node", "env": { "A": "1" } })
+        );
+    }
+
+    #[test]
+    fn test_convert_from_crush_infers_missing_type() {
+        let stdio = convert_from_crush_format("fs", &json!({ "command": "npx" })).unwrap();
+        assert_eq!(stdio["type"], "stdio");
+
+        let remote = convert_from_crush_format(
+            "gh",
+            &json!({ "url": "https://api.githubcopilot.com/mcp/", "timeout": 120 }),
+        )
+        .unwrap();
+        assert_eq!(remote["type"], "http");
+        assert!(remote.get("timeout").is_none());
+
+        assert!(convert_from_crush_format("x", &json!({ "type": "ws" })).is_err());
+    }
+
+    #[test]
+    fn test_merge_preserves_crush_specific_fields() {
+        let existing = json!({
+            "type": "stdio",
+            "command": "old",
+            "timeout": 120,
+            "disabled": true,
+            "disabled_tools": ["write"]
+        });
+        let new_spec = json!({ "type": "stdio", "command": "new" });
+        let merged = merge_crush_spec(&existing, &new_spec);
+
+        assert_eq!(merged["command"], "new");
+        assert_eq!(merged["timeout"], 120);
+        assert_eq!(merged["disabled"], true);
+        assert_eq!(merged["disabled_tools"][0], "write");
+    }
+}
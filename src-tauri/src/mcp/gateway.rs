@@ -0,0 +1,561 @@
+//! 内置 MCP 网关
+//!
+//! 把启用到某个应用的全部 MCP 服务器聚合成一个服务器：工具名加上
+//! `<服务器ID>__` 前缀，按应用的 allow / deny 列表过滤，`tools/call`
+//! 转发到对应的上游服务器。客户端配置里只需写入一个 `cc-switch-gateway` 条目，
+//! 之后增删服务器或调整工具集都不必再改各客户端的配置文件。
+//!
+//! 对外入口：
+//! - 代理上的 streamable HTTP 端点 `POST /mcp/<app>`
+//! - `cc-switch --mcp-gateway <url>` stdio 适配，把 stdin/stdout 转发到上述端点
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use super::probe::{list_all, Session, PROTOCOL_VERSION};
+use crate::app_config::{AppType, McpServer};
+use crate::database::Database;
+use crate::error::AppError;
+use crate::services::usage_stats::glob_match;
+
+/// 写入客户端配置的网关条目 ID
+pub const GATEWAY_SERVER_ID: &str = "cc-switch-gateway";
+
+/// 是否为 cc-switch 自己写入的网关条目（从 live 配置导入时需跳过）
+pub fn is_gateway_entry(id: &str) -> bool {
+    id == GATEWAY_SERVER_ID
+}
+
+/// 命名空间分隔符：`<服务器ID>__<工具名>`
+pub const TOOL_NAMESPACE_SEPARATOR: &str = "__";
+/// stdio 适配的命令行参数
+pub const GATEWAY_STDIO_ARG: &str = "--mcp-gateway";
+
+const GATEWAY_CONFIG_KEY: &str = "mcp_gateway_config";
+/// 连接上游服务器（含 initialize 握手）的超时
+const UPSTREAM_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+/// 单次上游请求的超时（工具调用可能较慢）
+const UPSTREAM_REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+const BRIDGE_REQUEST_TIMEOUT_SECS: u64 = 330;
+
+/// 单个应用的工具过滤规则，模式匹配带命名空间的工具名，支持 `*` / `?`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpToolPolicy {
+    /// 为空表示允许全部
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// 优先于 allow
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl McpToolPolicy {
+    pub fn permits(&self, tool: &str) -> bool {
+        if self.deny.iter().any(|p| glob_match(p, tool)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|p| glob_match(p, tool))
+    }
+}
+
+/// 客户端连接网关的方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GatewayTransport {
+    /// 写入 `cc-switch --mcp-gateway <url>`，兼容所有客户端
+    #[default]
+    Stdio,
+    /// 直接写入 HTTP 端点（客户端需支持 streamable HTTP）
+    Http,
+}
+
+/// MCP 网关配置（存储在 settings 表）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpGatewayConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub transport: GatewayTransport,
+    /// 应用 ID -> 工具过滤规则
+    #[serde(default)]
+    pub policies: HashMap<String, McpToolPolicy>,
+}
+
+impl McpGatewayConfig {
+    pub fn policy_for(&self, app: &AppType) -> McpToolPolicy {
+        self.policies.get(app.as_str()).cloned().unwrap_or_default()
+    }
+}
+
+impl Database {
+    /// 获取 MCP 网关配置
+    pub fn get_mcp_gateway_config(&self) -> Result<McpGatewayConfig, AppError> {
+        match self.get_setting(GATEWAY_CONFIG_KEY)? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Message(format!("解析 MCP 网关配置失败: {e}"))),
+            None => Ok(McpGatewayConfig::default()),
+        }
+    }
+
+    /// 保存 MCP 网关配置
+    pub fn save_mcp_gateway_config(&self, config: &McpGatewayConfig) -> Result<(), AppError> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Message(format!("序列化 MCP 网关配置失败: {e}")))?;
+        self.set_setting(GATEWAY_CONFIG_KEY, &json)
+    }
+}
+
+pub fn namespaced_tool_name(server_id: &str, tool: &str) -> String {
+    format!("{server_id}{TOOL_NAMESPACE_SEPARATOR}{tool}")
+}
+
+/// 按已知服务器 ID 拆分带命名空间的工具名
+///
+/// 服务器 ID 与工具名都可能包含分隔符，因此取能匹配的最长服务器 ID。
+pub fn split_tool_name<'a, 'b>(
+    name: &'a str,
+    server_ids: impl IntoIterator<Item = &'b str>,
+) -> Option<(&'a str, &'a str)> {
+    server_ids
+        .into_iter()
+        .filter(|id| !id.is_empty())
+        .filter_map(|id| {
+            let tool = name
+                .strip_prefix(id)?
+                .strip_prefix(TOOL_NAMESPACE_SEPARATOR)?;
+            (!tool.is_empty()).then_some((&name[..id.len()], tool))
+        })
+        .max_by_key(|(server, _)| server.len())
+}
+
+/// 网关在客户端配置中的连接定义
+pub fn gateway_server_spec(transport: GatewayTransport, endpoint: &str) -> Result<Value, AppError> {
+    Ok(match transport {
+        GatewayTransport::Http => json!({ "type": "http", "url": endpoint }),
+        GatewayTransport::Stdio => {
+            let exe = std::env::current_exe().map_err(|e| {
+                AppError::Message(format!("无法获取 cc-switch 可执行文件路径: {e}"))
+            })?;
+            json!({
+                "type": "stdio",
+                "command": exe.to_string_lossy(),
+                "args": [GATEWAY_STDIO_ARG, endpoint],
+            })
+        }
+    })
+}
+
+/// 网关条目（写入客户端配置用）
+pub fn gateway_server(spec: Value) -> McpServer {
+    McpServer {
+        id: GATEWAY_SERVER_ID.to_string(),
+        name: "CC Switch MCP Gateway".to_string(),
+        server: spec,
+        apps: Default::default(),
+        description: None,
+        homepage: None,
+        docs: None,
+        tags: Vec::new(),
+    }
+}
+
+fn rpc_result(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn rpc_error(id: &Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
+
+struct Upstream {
+    spec: Value,
+    session: Arc<Mutex<Session>>,
+}
+
+/// 聚合多个上游服务器的网关；上游连接按需建立并复用
+pub struct McpGateway {
+    db: Arc<Database>,
+    upstreams: Mutex<HashMap<String, Upstream>>,
+}
+
+impl McpGateway {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            db,
+            upstreams: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn servers_for_app(&self, app: &AppType) -> Result<Vec<McpServer>, String> {
+        Ok(self
+            .db
+            .get_all_mcp_servers()
+            .map_err(|e| e.to_string())?
+            .into_values()
+            .filter(|s| s.id != GATEWAY_SERVER_ID && s.apps.is_enabled_for(app))
+            .collect())
+    }
+
+    /// 获取（必要时建立）到上游服务器的连接；连接定义变化时重新连接
+    async fn upstream(&self, server: &McpServer) -> Result<Arc<Mutex<Session>>, String> {
+        // 比较展开后的 spec，密钥变化时也会重新连接
+        let spec = self
+            .db
+            .mcp_secret_resolver()
+            .resolve_for_write(&server.id, &server.server);
+        if let Some(existing) = self.upstreams.lock().await.get(&server.id) {
+            if existing.spec == spec {
+                return Ok(existing.session.clone());
+            }
+        }
+
+        // 连接与握手可能耗时较长，不持有全局锁，避免阻塞其它服务器的请求
+        let connect = async {
+            let mut session = Session::connect(&spec).await?;
+            session.initialize().await?;
+            Ok::<_, String>(session)
+        };
+        let session = tokio::time::timeout(UPSTREAM_CONNECT_TIMEOUT, connect)
+            .await
+            .map_err(|_| format!("连接 MCP 服务器 {} 超时", server.id))??;
+        let session = Arc::new(Mutex::new(session));
+        let mut upstreams = self.upstreams.lock().await;
+        // 并发请求可能已先建立了相同定义的连接，复用它并丢弃本次连接
+        if let Some(existing) = upstreams.get(&server.id) {
+            if existing.spec == spec {
+                return Ok(existing.session.clone());
+            }
+        }
+        upstreams.insert(
+            server.id.clone(),
+            Upstream {
+                spec,
+                session: session.clone(),
+            },
+        );
+        Ok(session)
+    }
+
+    /// 上游出错后丢弃连接，下次请求时重新建立
+    async fn drop_upstream(&self, id: &str) {
+        self.upstreams.lock().await.remove(id);
+    }
+
+    /// 关闭全部上游连接
+    pub async fn reset(&self) {
+        self.upstreams.lock().await.clear();
+    }
+
+    /// 列出应用可见的全部工具（已加命名空间并经过滤）
+    pub async fn list_tools(&self, app: &AppType) -> Result<Vec<Value>, String> {
+        let config = self.db.get_mcp_gateway_config().unwrap_or_default();
+        let policy = config.policy_for(app);
+        let mut tools = Vec::new();
+
+        for server in self.servers_for_app(app)? {
+            let listed = match self.upstream(&server).await {
+                Ok(session) => {
+                    let mut session = session.lock().await;
+                    tokio::time::timeout(
+                        UPSTREAM_REQUEST_TIMEOUT,
+                        list_all(&mut session, "tools/list", "tools"),
+                    )
+                    .await
+                    .unwrap_or_else(|_| Err("tools/list 超时".to_string()))
+                }
+                Err(e) => Err(e),
+            };
+
+            let listed = match listed {
+                Ok(listed) => listed,
+                Err(e) => {
+                    log::warn!("[MCP Gateway] 列出 {} 的工具失败: {e}", server.id);
+                    self.drop_upstream(&server.id).await;
+                    continue;
+                }
+            };
+
+            for mut tool in listed {
+                let Some(name) = tool.get("name").and_then(|n| n.as_str()) else {
+                    continue;
+                };
+                let namespaced = namespaced_tool_name(&server.id, name);
+                if !policy.permits(&namespaced) {
+                    continue;
+                }
+                tool["name"] = Value::String(namespaced);
+                tools.push(tool);
+            }
+        }
+        Ok(tools)
+    }
+
+    /// 调用带命名空间的工具
+    async fn call_tool(&self, app: &AppType, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(|n| n.as_str())
+            .ok_or((-32602, "缺少工具名".to_string()))?;
+        let config = self.db.get_mcp_gateway_config().unwrap_or_default();
+        if !config.policy_for(app).permits(name) {
+            return Err((-32602, format!("工具 {name} 未对 {} 开放", app.as_str())));
+        }
+        let servers = self.servers_for_app(app).map_err(|e| (-32603, e))?;
+        let (server_id, tool) = split_tool_name(name, servers.iter().map(|s| s.id.as_str()))
+            .ok_or((-32602, format!("未知工具: {name}")))?;
+        let server = servers
+            .into_iter()
+            .find(|s| s.id == server_id)
+            .ok_or((-32602, format!("未知工具: {name}")))?;
+
+        let session = self.upstream(&server).await.map_err(|e| (-32603, e))?;
+        let mut forwarded = params.clone();
+        forwarded["name"] = Value::String(tool.to_string());
+        let result = {
+            let mut session = session.lock().await;
+            tokio::time::timeout(
+                UPSTREAM_REQUEST_TIMEOUT,
+                session.request("tools/call", forwarded),
+            )
+            .await
+            .unwrap_or_else(|_| Err(format!("调用 {name} 超时")))
+        };
+        if result.is_err() {
+            self.drop_upstream(&server.id).await;
+        }
+        result.map_err(|e| (-32603, e))
+    }
+
+    /// 处理一条 JSON-RPC 消息；通知与响应不需要回复，返回 `None`
+    pub async fn handle_message(&self, app: &AppType, message: &Value) -> Option<Value> {
+        let method = message.get("method").and_then(|m| m.as_str())?;
+        let id = message.get("id")?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        Some(match method {
+            "initialize" => rpc_result(
+                id,
+                json!({
+                    "protocolVersion": params
+                        .get("protocolVersion")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(PROTOCOL_VERSION),
+                    "capabilities": { "tools": { "listChanged": false } },
+                    "serverInfo": {
+                        "name": "cc-switch-gateway",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }),
+            ),
+            "ping" => rpc_result(id, json!({})),
+            "tools/list" => match self.list_tools(app).await {
+                Ok(tools) => rpc_result(id, json!({ "tools": tools })),
+                Err(e) => rpc_error(id, -32603, e),
+            },
+            "tools/call" => match self.call_tool(app, &params).await {
+                Ok(result) => rpc_result(id, result),
+                Err((code, e)) => rpc_error(id, code, e),
+            },
+            _ => rpc_error(id, -32601, format!("Method not found: {method}")),
+        })
+    }
+
+    /// 处理一次 HTTP 请求体（单条消息或批量数组）
+    pub async fn handle_payload(&self, app: &AppType, payload: &Value) -> Option<Value> {
+        match payload {
+            Value::Array(batch) => {
+                let mut responses = Vec::new();
+                for message in batch {
+                    if let Some(response) = self.handle_message(app, message).await {
+                        responses.push(response);
+                    }
+                }
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            message => self.handle_message(app, message).await,
+        }
+    }
+}
+
+/// stdio 适配：逐行读取 stdin 上的 JSON-RPC 消息，POST 到网关端点并把响应写回 stdout
+///
+/// 返回进程退出码。代理未运行时对请求返回 JSON-RPC 错误，而不是直接退出，
+/// 方便客户端在界面上显示原因。
+pub fn run_stdio_bridge(endpoint: &str) -> i32 {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("cc-switch mcp gateway: failed to start runtime: {e}");
+            return 1;
+        }
+    };
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(BRIDGE_REQUEST_TIMEOUT_SECS))
+        .no_proxy()
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("cc-switch mcp gateway: failed to create HTTP client: {e}");
+            return 1;
+        }
+    };
+
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                let response = rpc_error(&Value::Null, -32700, format!("Parse error: {e}"));
+                let _ = writeln!(stdout, "{response}");
+                let _ = stdout.flush();
+                continue;
+            }
+        };
+
+        let forwarded = runtime.block_on(async {
+            let response = client.post(endpoint).json(&message).send().await?;
+            let status = response.status();
+            let body = response.text().await?;
+            Ok::<_, reqwest::Error>((status, body))
+        });
+
+        let output = match forwarded {
+            Ok((status, body)) if status.is_success() => {
+                (!body.trim().is_empty()).then_some(body.trim().to_string())
+            }
+            Ok((status, _)) => message.get("id").map(|id| {
+                rpc_error(
+                    id,
+                    -32603,
+                    format!("cc-switch MCP gateway returned HTTP {status}"),
+                )
+                .to_string()
+            }),
+            Err(e) => message.get("id").map(|id| {
+                rpc_error(
+                    id,
+                    -32603,
+                    format!("cc-switch proxy is not reachable ({e}); start the local proxy first"),
+                )
+                .to_string()
+            }),
+        };
+
+        if let Some(output) = output {
+            if writeln!(stdout, "{output}").is_err() || stdout.flush().is_err() {
+                break;
+            }
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_names_round_trip_through_namespace() {
+        let name = namespaced_tool_name("github", "create_issue");
+        assert_eq!(name, "github__create_issue");
+        let ids = ["github", "fs", "my", "my__srv"];
+        assert_eq!(
+            split_tool_name(&name, ids),
+            Some(("github", "create_issue"))
+        );
+        // 工具名自身含分隔符
+        assert_eq!(
+            split_tool_name("fs__read__file", ids),
+            Some(("fs", "read__file"))
+        );
+        // 服务器 ID 含分隔符时取最长匹配
+        assert_eq!(
+            split_tool_name("my__srv__run", ids),
+            Some(("my__srv", "run"))
+        );
+        assert_eq!(split_tool_name("my__run", ids), Some(("my", "run")));
+        assert_eq!(split_tool_name("__x", ids), None);
+        assert_eq!(split_tool_name("plain", ids), None);
+        assert_eq!(split_tool_name("unknown__tool", ids), None);
+    }
+
+    #[test]
+    fn deny_rules_override_allow_rules() {
+        let policy = McpToolPolicy {
+            allow: vec!["github__*".to_string(), "fs__read_*".to_string()],
+            deny: vec!["github__delete_*".to_string()],
+        };
+        assert!(policy.permits("github__create_issue"));
+        assert!(!policy.permits("github__delete_repo"));
+        assert!(policy.permits("fs__read_file"));
+        assert!(!policy.permits("fs__write_file"));
+        assert!(McpToolPolicy::default().permits("anything__goes"));
+    }
+
+    #[tokio::test]
+    async fn gateway_answers_initialize_and_ignores_notifications() {
+        let db = Arc::new(Database::memory().expect("memory db"));
+        let gateway = McpGateway::new(db);
+
+        let init = gateway
+            .handle_message(
+                &AppType::Claude,
+                &json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            )
+            .await
+            .expect("initialize response");
+        assert_eq!(init["result"]["serverInfo"]["name"], "cc-switch-gateway");
+        assert!(init["result"]["capabilities"]["tools"].is_object());
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(gateway
+            .handle_message(&AppType::Claude, &notification)
+            .await
+            .is_none());
+
+        // 没有启用任何服务器时工具列表为空
+        let list = gateway
+            .handle_message(
+                &AppType::Claude,
+                &json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+            )
+            .await
+            .expect("tools/list response");
+        assert_eq!(list["result"]["tools"], json!([]));
+
+        let call = gateway
+            .handle_message(
+                &AppType::Claude,
+                &json!({
+                    "jsonrpc": "2.0",
+                    "id": 3,
+                    "method": "tools/call",
+                    "params": { "name": "missing__tool", "arguments": {} },
+                }),
+            )
+            .await
+            .expect("tools/call response");
+        assert_eq!(call["error"]["code"], -32602);
+    }
+}
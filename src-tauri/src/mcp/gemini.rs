@@ -6,12 +6,13 @@ use std::collections::HashMap;
 use crate::app_config::{McpApps, McpConfig, McpServer, MultiAppConfig};
 use crate::error::AppError;
 
+use super::secrets::SecretResolver;
 use super::validation::{extract_server_spec, validate_server_spec};
 
 fn should_sync_gemini_mcp() -> bool {
     // Gemini 未安装/未初始化时：~/.gemini 目录不存在。
     // 按用户偏好：目录缺失时跳过写入/删除，不创建任何文件或目录。
-    crate::gemini_config::get_gemini_dir().exists()
+    crate::config::live_path_exists(&crate::gemini_config::get_gemini_dir())
 }
 
 /// 返回已启用的 MCP 服务器（过滤 enabled==true）
@@ -89,6 +90,9 @@ pub fn import_from_gemini(config: &mut MultiAppConfig) -> Result<usize, AppError
                         gemini: true,
                         opencode: false,
                         hermes: false,
+                        qwen: false,
+                        goose: false,
+                        crush: false,
                     },
                     description: None,
                     homepage: None,
@@ -113,10 +117,12 @@ pub fn sync_single_server_to_gemini(
     _config: &MultiAppConfig,
     id: &str,
     server_spec: &Value,
+    secrets: &SecretResolver,
 ) -> Result<(), AppError> {
     if !should_sync_gemini_mcp() {
         return Ok(());
     }
+    let server_spec = &secrets.resolve_for_write(id, server_spec);
     // 读取现有的 MCP 配置
     let mut current = crate::gemini_mcp::read_mcp_servers_map()?;
 
@@ -0,0 +1,383 @@
+//! Goose MCP sync and import module
+//!
+//! Handles conversion between CC Switch unified MCP format and Goose `extensions:`
+//! entries in `~/.config/goose/config.yaml`.
+//!
+//! ## Format mapping
+//!
+//! | CC Switch unified (JSON)                                  | Goose extension (YAML)                    |
+//! |-----------------------------------------------------------|-------------------------------------------|
+//! | `{"type":"stdio","command":"npx","args":[...],"env":{}}`  | `type: stdio`, `cmd`, `args`, `envs`      |
+//! | `{"type":"sse","url":"...","headers":{}}`                 | `type: sse`, `uri`, `headers`             |
+//! | `{"type":"http","url":"...","headers":{}}`                | `type: streamable_http`, `uri`, `headers` |
+//!
+//! Key differences from Claude format:
+//! - Goose requires `name`, `enabled` and `timeout` on every extension
+//! - Built-in extensions (`builtin`, `platform`, `frontend`, `inline_python`) are
+//!   not MCP servers CC Switch can manage and are skipped on import
+//! - Goose-specific fields are preserved on merge-on-write and stripped on import
+
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+use crate::app_config::{McpApps, McpServer, MultiAppConfig};
+use crate::error::AppError;
+use crate::goose_config;
+
+use super::secrets::SecretResolver;
+use super::validation::validate_server_spec;
+
+/// Default tool call timeout (seconds) Goose writes for new extensions.
+const GOOSE_DEFAULT_TIMEOUT: u64 = 300;
+
+/// Goose-specific fields preserved on merge-on-write, stripped on import.
+const GOOSE_EXTRA_FIELDS: &[&str] = &[
+    "enabled",
+    "timeout",
+    "description",
+    "bundled",
+    "available_tools",
+    "env_keys",
+    "display_name",
+];
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Check if Goose MCP sync should proceed
+fn should_sync_goose_mcp() -> bool {
+    crate::config::live_path_exists(&goose_config::get_goose_dir())
+}
+
+fn non_empty_object(value: Option<&Value>) -> Option<&Value> {
+    value.filter(|v| v.as_object().map(|o| !o.is_empty()).unwrap_or(false))
+}
+
+fn non_empty_array(value: Option<&Value>) -> Option<&Value> {
+    value.filter(|v| v.as_array().map(|a| !a.is_empty()).unwrap_or(false))
+}
+
+// ============================================================================
+// Format Conversion: CC Switch -> Goose
+// ============================================================================
+
+/// Convert CC Switch unified format to a Goose extension entry
+fn convert_to_goose_format(id: &str, spec: &Value) -> Result<Value, AppError> {
+    let obj = spec
+        .as_object()
+        .ok_or_else(|| AppError::McpValidation("MCP spec must be a JSON object".into()))?;
+
+    let typ = obj.get("type").and_then(|v| v.as_str()).unwrap_or("stdio");
+
+    let mut result = Map::new();
+    result.insert("name".into(), json!(id));
+
+    match typ {
+        "stdio" => {
+            result.insert("type".into(), json!("stdio"));
+            if let Some(command) = obj.get("command") {
+                result.insert("cmd".into(), command.clone());
+            }
+            result.insert(
+                "args".into(),
+                non_empty_array(obj.get("args"))
+                    .cloned()
+                    .unwrap_or_else(|| json!([])),
+            );
+            if let Some(env) = non_empty_object(obj.get("env")) {
+                result.insert("envs".into(), env.clone());
+            }
+            if let Some(cwd) = obj.get("cwd") {
+                result.insert("cwd".into(), cwd.clone());
+            }
+        }
+        "sse" | "http" => {
+            let goose_type = if typ == "http" {
+                "streamable_http"
+            } else {
+                "sse"
+            };
+            result.insert("type".into(), json!(goose_type));
+            if let Some(url) = obj.get("url") {
+                result.insert("uri".into(), url.clone());
+            }
+            if let Some(headers) = non_empty_object(obj.get("headers")) {
+                result.insert("headers".into(), headers.clone());
+            }
+        }
+        _ => {
+            return Err(AppError::McpValidation(format!("Unknown MCP type: {typ}")));
+        }
+    }
+
+    result.insert("enabled".into(), json!(true));
+    result.insert("timeout".into(), json!(GOOSE_DEFAULT_TIMEOUT));
+
+    Ok(Value::Object(result))
+}
+
+// ============================================================================
+// Format Conversion: Goose -> CC Switch
+// ============================================================================
+
+/// Convert a Goose extension entry to CC Switch unified format
+///
+/// Returns `Ok(None)` for built-in extensions that are not MCP servers.
+fn convert_from_goose_format(id: &str, spec: &Value) -> Result<Option<Value>, AppError> {
+    let obj = spec
+        .as_object()
+        .ok_or_else(|| AppError::McpValidation("Goose extension must be a YAML mapping".into()))?;
+
+    let typ = obj.get("type").and_then(|v| v.as_str()).unwrap_or("");
+    let mut result = Map::new();
+
+    match typ {
+        "stdio" => {
+            result.insert("type".into(), json!("stdio"));
+            if let Some(cmd) = obj.get("cmd") {
+                result.insert("command".into(), cmd.clone());
+            }
+            if let Some(args) = non_empty_array(obj.get("args")) {
+                result.insert("args".into(), args.clone());
+            }
+            if let Some(envs) = non_empty_object(obj.get("envs")) {
+                result.insert("env".into(), envs.clone());
+            }
+            if let Some(cwd) = obj.get("cwd") {
+                result.insert("cwd".into(), cwd.clone());
+            }
+        }
+        "sse" | "streamable_http" => {
+            let unified_type = if typ == "sse" { "sse" } else { "http" };
+            result.insert("type".into(), json!(unified_type));
+            if let Some(uri) = obj.get("uri") {
+                result.insert("url".into(), uri.clone());
+            }
+            if let Some(headers) = non_empty_object(obj.get("headers")) {
+                result.insert("headers".into(), headers.clone());
+            }
+        }
+        "builtin" | "platform" | "frontend" | "inline_python" => return Ok(None),
+        other => {
+            return Err(AppError::McpValidation(format!(
+                "Goose extension '{id}' has unsupported type '{other}'"
+            )));
+        }
+    }
+
+    Ok(Some(Value::Object(result)))
+}
+
+/// Merge new spec into existing Goose extension, preserving Goose-specific fields.
+fn merge_goose_spec(existing: &Value, new_spec: &Value) -> Value {
+    let mut result = Map::new();
+
+    if let Some(new_obj) = new_spec.as_object() {
+        for (key, val) in new_obj {
+            result.insert(key.clone(), val.clone());
+        }
+    }
+
+    // Existing Goose-specific fields take precedence over the defaults we fill in
+    if let Some(existing_obj) = existing.as_object() {
+        for &field in GOOSE_EXTRA_FIELDS {
+            if let Some(val) = existing_obj.get(field) {
+                result.insert(field.to_string(), val.clone());
+            }
+        }
+    }
+
+    Value::Object(result)
+}
+
+// ============================================================================
+// Public API: Sync Functions
+// ============================================================================
+
+/// Sync a single MCP server to Goose `extensions:` (merge-on-write)
+pub fn sync_single_server_to_goose(
+    _config: &MultiAppConfig,
+    id: &str,
+    server_spec: &Value,
+    secrets: &SecretResolver,
+) -> Result<(), AppError> {
+    if !should_sync_goose_mcp() {
+        return Ok(());
+    }
+    let server_spec = secrets.resolve_for_write(id, server_spec);
+    let goose_spec = convert_to_goose_format(id, &server_spec)?;
+
+    goose_config::update_extensions(|extensions| {
+        let merged = match extensions.get(id) {
+            Some(existing) => merge_goose_spec(existing, &goose_spec),
+            None => goose_spec,
+        };
+        extensions.insert(id.to_string(), merged);
+        Ok(())
+    })
+}
+
+/// Remove a single MCP server from Goose `extensions:`
+pub fn remove_server_from_goose(id: &str) -> Result<(), AppError> {
+    if !should_sync_goose_mcp() {
+        return Ok(());
+    }
+
+    goose_config::update_extensions(|extensions| {
+        extensions.remove(id);
+        Ok(())
+    })
+}
+
+/// Import MCP servers from Goose `extensions:` to unified structure
+///
+/// Existing servers will have Goose app enabled without overwriting other fields.
+pub fn import_from_goose(config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    let extensions = goose_config::get_extensions()?;
+    if extensions.is_empty() {
+        return Ok(0);
+    }
+
+    let servers = config.mcp.servers.get_or_insert_with(HashMap::new);
+
+    let mut changed = 0;
+    let mut errors = Vec::new();
+
+    for (id, spec) in &extensions {
+        let unified_spec = match convert_from_goose_format(id, spec) {
+            Ok(Some(s)) => s,
+            Ok(None) => continue,
+            Err(e) => {
+                log::warn!("Skip invalid Goose extension '{id}': {e}");
+                errors.push(format!("{id}: {e}"));
+                continue;
+            }
+        };
+
+        if let Err(e) = validate_server_spec(&unified_spec) {
+            log::warn!("Skip invalid MCP server '{id}' after conversion: {e}");
+            errors.push(format!("{id}: {e}"));
+            continue;
+        }
+
+        if let Some(existing) = servers.get_mut(id) {
+            if !existing.apps.goose {
+                existing.apps.goose = true;
+                changed += 1;
+                log::info!("MCP server '{id}' enabled for Goose");
+            }
+        } else {
+            servers.insert(
+                id.clone(),
+                McpServer {
+                    id: id.clone(),
+                    name: id.clone(),
+                    server: unified_spec,
+                    apps: McpApps {
+                        goose: true,
+                        ..Default::default()
+                    },
+                    description: None,
+                    homepage: None,
+                    docs: None,
+                    tags: Vec::new(),
+                },
+            );
+            changed += 1;
+            log::info!("Imported new MCP server '{id}' from Goose");
+        }
+    }
+
+    if !errors.is_empty() {
+        log::warn!(
+            "Import completed with {} failures: {:?}",
+            errors.len(),
+            errors
+        );
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_stdio_to_goose() {
+        let spec = json!({
+            "type": "stdio",
+            "command": "npx",
+            "args": ["-y", "@modelcontextprotocol/server-github"],
+            "env": { "GITHUB_TOKEN": "ghp_x" }
+        });
+
+        let result = convert_to_goose_format("github", &spec).unwrap();
+        assert_eq!(result["type"], "stdio");
+        assert_eq!(result["name"], "github");
+        assert_eq!(result["cmd"], "npx");
+        assert_eq!(result["args"][1], "@modelcontextprotocol/server-github");
+        assert_eq!(result["envs"]["GITHUB_TOKEN"], "ghp_x");
+        assert_eq!(result["enabled"], true);
+        assert_eq!(result["timeout"], GOOSE_DEFAULT_TIMEOUT);
+        assert!(result.get("command").is_none());
+    }
+
+    #[test]
+    fn test_convert_http_to_streamable_http() {
+        let spec = json!({
+            "type": "http",
+            "url": "https://mcp.example.com",
+            "headers": { "Authorization": "Bearer x" }
+        });
+
+        let result = convert_to_goose_format("remote", &spec).unwrap();
+        assert_eq!(result["type"], "streamable_http");
+        assert_eq!(result["uri"], "https://mcp.example.com");
+        assert_eq!(result["headers"]["Authorization"], "Bearer x");
+
+        let sse = convert_to_goose_format("events", &json!({ "type": "sse", "url": "u" })).unwrap();
+        assert_eq!(sse["type"], "sse");
+    }
+
+    #[test]
+    fn test_convert_goose_round_trip() {
+        let spec = json!({
+            "type": "stdio",
+            "command": "uvx",
+            "args": ["mcp-server-fetch"],
+            "env": { "A": "1" }
+        });
+        let goose = convert_to_goose_format("fetch", &spec).unwrap();
+        let back = convert_from_goose_format("fetch", &goose).unwrap().unwrap();
+        assert_eq!(back, spec);
+    }
+
+    #[test]
+    fn test_builtin_extensions_are_skipped_on_import() {
+        let builtin = json!({ "type": "builtin", "name": "developer", "enabled": true });
+        assert!(convert_from_goose_format("developer", &builtin)
+            .unwrap()
+            .is_none());
+        assert!(convert_from_goose_format("x", &json!({ "type": "grpc" })).is_err());
+    }
+
+    #[test]
+    fn test_merge_preserves_goose_specific_fields() {
+        let existing = json!({
+            "type": "stdio",
+            "cmd": "old",
+            "enabled": false,
+            "timeout": 900,
+            "available_tools": ["search"]
+        });
+        let new_spec = convert_to_goose_format("x", &json!({ "command": "new" })).unwrap();
+        let merged = merge_goose_spec(&existing, &new_spec);
+
+        assert_eq!(merged["cmd"], "new");
+        assert_eq!(merged["enabled"], false, "user disabled it in Goose");
+        assert_eq!(merged["timeout"], 900);
+        assert_eq!(merged["available_tools"][0], "search");
+    }
+}
@@ -21,6 +21,7 @@ use crate::app_config::{McpApps, McpServer, MultiAppConfig};
 use crate::error::AppError;
 use crate::hermes_config;
 
+use super::secrets::SecretResolver;
 use super::validation::validate_server_spec;
 
 /// Hermes-specific fields preserved on merge-on-write, stripped on import.
@@ -45,7 +46,7 @@ const HERMES_EXTRA_FIELDS: &[&str] = &[
 
 /// Check if Hermes MCP sync should proceed
 fn should_sync_hermes_mcp() -> bool {
-    hermes_config::get_hermes_dir().exists()
+    crate::config::live_path_exists(&hermes_config::get_hermes_dir())
 }
 
 // ============================================================================
@@ -177,10 +178,12 @@ pub fn sync_single_server_to_hermes(
     _config: &MultiAppConfig,
     id: &str,
     server_spec: &Value,
+    secrets: &SecretResolver,
 ) -> Result<(), AppError> {
     if !should_sync_hermes_mcp() {
         return Ok(());
     }
+    let server_spec = &secrets.resolve_for_write(id, server_spec);
 
     let hermes_spec = convert_to_hermes_format(server_spec)?;
     let id_owned = id.to_string();
@@ -317,6 +320,9 @@ pub fn import_from_hermes(config: &mut MultiAppConfig) -> Result<usize, AppError
                         gemini: false,
                         opencode: false,
                         hermes: true,
+                        qwen: false,
+                        goose: false,
+                        crush: false,
                     },
                     description: None,
                     homepage: None,
@@ -10,12 +10,24 @@
 //! - `gemini` - Gemini MCP 同步和导入
 //! - `opencode` - OpenCode MCP 同步和导入（含 local/remote 格式转换）
 //! - `hermes` - Hermes MCP 同步和导入
+//! - `qwen` - Qwen Code MCP 同步和导入（复用 Gemini 格式）
+//! - `goose` - Goose MCP 同步和导入（config.yaml 的 extensions）
+//! - `crush` - Crush MCP 同步和导入（crush.json 的 mcp）
+//! - `probe` - 启动 / 连接服务器并列出 tools、prompts、resources
+//! - `gateway` - 聚合全部启用服务器的内置 MCP 网关
+//! - `secrets` - `env` / `headers` 中的密钥占位符解析
 
 mod claude;
 mod codex;
+mod crush;
+mod gateway;
 mod gemini;
+mod goose;
 mod hermes;
 mod opencode;
+mod probe;
+mod qwen;
+mod secrets;
 mod validation;
 
 // 重新导出公共 API
@@ -23,14 +35,31 @@ pub use claude::{
     import_from_claude, remove_server_from_claude, sync_enabled_to_claude,
     sync_single_server_to_claude,
 };
+pub(crate) use codex::json_server_to_toml_table;
 pub use codex::{
     import_from_codex, remove_server_from_codex, sync_enabled_to_codex, sync_single_server_to_codex,
 };
+pub use crush::{import_from_crush, remove_server_from_crush, sync_single_server_to_crush};
+pub use gateway::{
+    gateway_server, gateway_server_spec, is_gateway_entry, run_stdio_bridge, GatewayTransport,
+    McpGateway, McpGatewayConfig, McpToolPolicy, GATEWAY_SERVER_ID, GATEWAY_STDIO_ARG,
+};
 pub use gemini::{
     import_from_gemini, remove_server_from_gemini, sync_enabled_to_gemini,
     sync_single_server_to_gemini,
 };
+pub use goose::{import_from_goose, remove_server_from_goose, sync_single_server_to_goose};
 pub use hermes::{import_from_hermes, remove_server_from_hermes, sync_single_server_to_hermes};
 pub use opencode::{
-    import_from_opencode, remove_server_from_opencode, sync_single_server_to_opencode,
+    convert_to_opencode_format, import_from_opencode, remove_server_from_opencode,
+    sync_single_server_to_opencode,
+};
+pub use probe::{
+    probe_server, McpProbeResult, McpProbeStatus, McpPromptInfo, McpResourceInfo, McpToolInfo,
+    DEFAULT_PROBE_TIMEOUT_SECS,
+};
+pub use qwen::{import_from_qwen, remove_server_from_qwen, sync_single_server_to_qwen};
+pub use secrets::{
+    find_secret_refs, validate_secret_name, McpSecretInfo, McpSecretReport, SecretRef,
+    SecretRefKind, SecretResolver, UnresolvedSecretRef,
 };
@@ -19,6 +19,7 @@ use crate::app_config::{McpApps, McpServer, MultiAppConfig};
 use crate::error::AppError;
 use crate::opencode_config;
 
+use super::secrets::SecretResolver;
 use super::validation::validate_server_spec;
 
 // ============================================================================
@@ -28,7 +29,7 @@ use super::validation::validate_server_spec;
 /// Check if OpenCode MCP sync should proceed
 fn should_sync_opencode_mcp() -> bool {
     // Skip if OpenCode config directory doesn't exist
-    opencode_config::get_opencode_dir().exists()
+    crate::config::live_path_exists(&opencode_config::get_opencode_dir())
 }
 
 // ============================================================================
@@ -185,10 +186,12 @@ pub fn sync_single_server_to_opencode(
     _config: &MultiAppConfig,
     id: &str,
     server_spec: &Value,
+    secrets: &SecretResolver,
 ) -> Result<(), AppError> {
     if !should_sync_opencode_mcp() {
         return Ok(());
     }
+    let server_spec = &secrets.resolve_for_write(id, server_spec);
 
     // Convert to OpenCode format
     let opencode_spec = convert_to_opencode_format(server_spec)?;
@@ -260,6 +263,9 @@ pub fn import_from_opencode(config: &mut MultiAppConfig) -> Result<usize, AppErr
                         gemini: false,
                         opencode: true,
                         hermes: false,
+                        qwen: false,
+                        goose: false,
+                        crush: false,
                     },
                     description: None,
                     homepage: None,
@@ -0,0 +1,806 @@
+//! MCP 服务器探测
+//!
+//! 按 `McpServer.server` 规范启动 stdio 服务器（或连接 http / sse 服务器），
+//! 完成 `initialize` 握手并列出 tools / prompts / resources，
+//! 用于在启用到 Claude / Codex 等应用之前发现命令错误、缺少环境变量等问题。
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use super::validation::validate_server_spec;
+
+/// 默认探测超时（npx 首次运行需要下载依赖，留足余量）
+pub const DEFAULT_PROBE_TIMEOUT_SECS: u64 = 30;
+pub(crate) const PROTOCOL_VERSION: &str = "2025-03-26";
+/// 保留的 stderr 尾部字节数
+const MAX_STDERR_BYTES: usize = 16 * 1024;
+/// 分页列表的最大页数
+const MAX_LIST_PAGES: usize = 20;
+/// 进程退出后等待 stderr 读完的时间
+const STDERR_DRAIN_WAIT: Duration = Duration::from_millis(500);
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum McpProbeStatus {
+    Ok,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpToolInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_schema: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpPromptInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResourceInfo {
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// 一次探测的结果（存储在 `mcp_servers.last_probe`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpProbeResult {
+    pub status: McpProbeStatus,
+    pub transport: String,
+    /// 探测时间（毫秒）
+    pub probed_at: i64,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
+    #[serde(default)]
+    pub tools: Vec<McpToolInfo>,
+    #[serde(default)]
+    pub prompts: Vec<McpPromptInfo>,
+    #[serde(default)]
+    pub resources: Vec<McpResourceInfo>,
+    /// 非致命问题（如 prompts/list 失败）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// stdio 服务器的 stderr 输出（截取尾部）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+}
+
+impl McpProbeResult {
+    fn new(transport: &str) -> Self {
+        Self {
+            status: McpProbeStatus::Error,
+            transport: transport.to_string(),
+            probed_at: chrono::Utc::now().timestamp_millis(),
+            duration_ms: 0,
+            server_name: None,
+            server_version: None,
+            protocol_version: None,
+            tools: Vec::new(),
+            prompts: Vec::new(),
+            resources: Vec::new(),
+            warnings: Vec::new(),
+            error: None,
+            stderr: None,
+        }
+    }
+}
+
+/// 探测 MCP 服务器；任何失败都体现在返回值的 `status` / `error` 中
+pub async fn probe_server(spec: &Value, timeout: Duration) -> McpProbeResult {
+    let transport = spec
+        .get("type")
+        .and_then(|t| t.as_str())
+        .unwrap_or("stdio")
+        .to_string();
+    let mut result = McpProbeResult::new(&transport);
+    let started = Instant::now();
+
+    if let Err(e) = validate_server_spec(spec) {
+        result.error = Some(e.to_string());
+        return result;
+    }
+
+    let session = match tokio::time::timeout(timeout, Session::connect(spec)).await {
+        Ok(session) => session,
+        Err(_) => Err(format!("连接服务器超时（{}s）", timeout.as_secs())),
+    };
+
+    match session {
+        Ok(mut session) => {
+            let outcome =
+                tokio::time::timeout(timeout, run_handshake(&mut session, &mut result)).await;
+            match outcome {
+                Ok(Ok(())) => result.status = McpProbeStatus::Ok,
+                Ok(Err(e)) => result.error = Some(e),
+                Err(_) => {
+                    result.error = Some(format!("握手超时（{}s）", timeout.as_secs()));
+                }
+            }
+            if let Session::Stdio(stdio) = session {
+                result.stderr = stdio.shutdown();
+            }
+        }
+        Err(e) => result.error = Some(e),
+    }
+
+    result.duration_ms = started.elapsed().as_millis() as u64;
+    result
+}
+
+async fn run_handshake(session: &mut Session, result: &mut McpProbeResult) -> Result<(), String> {
+    let init = session.initialize().await?;
+
+    result.protocol_version = init
+        .get("protocolVersion")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    if let Some(info) = init.get("serverInfo") {
+        result.server_name = info
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        result.server_version = info
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+    }
+    let capabilities = init.get("capabilities").cloned().unwrap_or(Value::Null);
+    let has = |key: &str| capabilities.get(key).is_some();
+
+    if has("tools") {
+        result.tools = parse_items(list_all(session, "tools/list", "tools").await?);
+    }
+    if has("prompts") {
+        match list_all(session, "prompts/list", "prompts").await {
+            Ok(items) => result.prompts = parse_items(items),
+            Err(e) => result.warnings.push(format!("prompts/list: {e}")),
+        }
+    }
+    if has("resources") {
+        match list_all(session, "resources/list", "resources").await {
+            Ok(items) => result.resources = parse_items(items),
+            Err(e) => result.warnings.push(format!("resources/list: {e}")),
+        }
+    }
+    Ok(())
+}
+
+fn parse_items<T: for<'de> Deserialize<'de>>(items: Vec<Value>) -> Vec<T> {
+    items
+        .into_iter()
+        .filter_map(|item| serde_json::from_value(item).ok())
+        .collect()
+}
+
+pub(crate) async fn list_all(
+    session: &mut Session,
+    method: &str,
+    key: &str,
+) -> Result<Vec<Value>, String> {
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+    for _ in 0..MAX_LIST_PAGES {
+        let params = match &cursor {
+            Some(c) => json!({ "cursor": c }),
+            None => json!({}),
+        };
+        let page = session.request(method, params).await?;
+        if let Some(list) = page.get(key).and_then(|v| v.as_array()) {
+            items.extend(list.iter().cloned());
+        }
+        cursor = page
+            .get("nextCursor")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        if cursor.is_none() {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+fn rpc_request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn rpc_notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// 若消息是对 `id` 的响应，返回结果或错误
+fn match_response(message: &Value, id: u64) -> Option<Result<Value, String>> {
+    if message.get("method").is_some() || message.get("id").and_then(|v| v.as_u64()) != Some(id) {
+        return None;
+    }
+    if let Some(error) = message.get("error") {
+        let code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
+        let msg = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("unknown error");
+        return Some(Err(format!("JSON-RPC 错误 {code}: {msg}")));
+    }
+    Some(Ok(message.get("result").cloned().unwrap_or(Value::Null)))
+}
+
+/// 服务器主动发来的请求（如 `ping`、`roots/list`）需要回复，否则部分服务器会卡住
+fn reply_to_server_request(message: &Value) -> Option<Value> {
+    let id = message.get("id")?.clone();
+    let method = message.get("method")?.as_str()?;
+    Some(match method {
+        "ping" => json!({ "jsonrpc": "2.0", "id": id, "result": {} }),
+        "roots/list" => json!({ "jsonrpc": "2.0", "id": id, "result": { "roots": [] } }),
+        _ => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32601, "message": "Method not found" },
+        }),
+    })
+}
+
+/// 到单个 MCP 服务器的连接（探测与网关共用）
+pub(crate) enum Session {
+    Stdio(StdioSession),
+    Http(HttpSession),
+    Sse(SseSession),
+}
+
+impl Session {
+    /// 按连接定义建立连接：stdio 启动子进程，sse 建立事件流，http 无需预连接
+    pub(crate) async fn connect(spec: &Value) -> Result<Self, String> {
+        match spec.get("type").and_then(|t| t.as_str()).unwrap_or("stdio") {
+            "http" => HttpSession::new(spec).map(Self::Http),
+            "sse" => SseSession::connect(spec).await.map(Self::Sse),
+            _ => StdioSession::spawn(spec).map(Self::Stdio),
+        }
+    }
+
+    /// 完成 `initialize` 握手，返回服务器的 initialize 结果
+    pub(crate) async fn initialize(&mut self) -> Result<Value, String> {
+        let init = self
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "cc-switch",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }),
+            )
+            .await?;
+        self.notify("notifications/initialized", json!({})).await?;
+        Ok(init)
+    }
+
+    pub(crate) async fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        match self {
+            Self::Stdio(s) => s.request(method, params).await,
+            Self::Http(s) => s.request(method, params).await,
+            Self::Sse(s) => s.request(method, params).await,
+        }
+    }
+
+    async fn notify(&mut self, method: &str, params: Value) -> Result<(), String> {
+        let message = rpc_notification(method, params);
+        match self {
+            Self::Stdio(s) => s.send(&message),
+            Self::Http(s) => s.post(&message).await.map(|_| ()),
+            Self::Sse(s) => s.post(&message).await,
+        }
+    }
+}
+
+pub(crate) struct StdioSession {
+    child: Child,
+    stdin: ChildStdin,
+    rx: mpsc::Receiver<Value>,
+    stderr: Arc<Mutex<Vec<u8>>>,
+    stderr_done: std::sync::mpsc::Receiver<()>,
+    next_id: u64,
+}
+
+impl StdioSession {
+    fn spawn(spec: &Value) -> Result<Self, String> {
+        let command = spec
+            .get("command")
+            .and_then(|c| c.as_str())
+            .unwrap_or_default();
+        let args: Vec<String> = spec
+            .get("args")
+            .and_then(|a| a.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Windows 上 npx / uvx 等是 .cmd 脚本，需要经 cmd /C 启动
+        #[cfg(windows)]
+        let mut cmd = {
+            use std::os::windows::process::CommandExt;
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C").arg(command).args(&args);
+            cmd.creation_flags(CREATE_NO_WINDOW);
+            cmd
+        };
+        #[cfg(not(windows))]
+        let mut cmd = {
+            let mut cmd = Command::new(command);
+            cmd.args(&args);
+            cmd
+        };
+
+        if let Some(env) = spec.get("env").and_then(|e| e.as_object()) {
+            for (key, value) in env {
+                if let Some(value) = value.as_str() {
+                    cmd.env(key, value);
+                }
+            }
+        }
+        if let Some(cwd) = spec
+            .get("cwd")
+            .and_then(|c| c.as_str())
+            .filter(|c| !c.is_empty())
+        {
+            cmd.current_dir(cwd);
+        }
+
+        let mut child = cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("启动命令 {command} 失败: {e}"))?;
+
+        let stdin = child.stdin.take().ok_or("无法获取子进程 stdin")?;
+        let stdout = child.stdout.take().ok_or("无法获取子进程 stdout")?;
+        let stderr_pipe = child.stderr.take().ok_or("无法获取子进程 stderr")?;
+
+        let (tx, rx) = mpsc::channel(64);
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                // 部分服务器会把日志打到 stdout，非 JSON 行直接忽略
+                let Ok(message) = serde_json::from_str::<Value>(line.trim()) else {
+                    continue;
+                };
+                if tx.blocking_send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let stderr = Arc::new(Mutex::new(Vec::new()));
+        let stderr_buf = stderr.clone();
+        let (done_tx, stderr_done) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let mut pipe = stderr_pipe;
+            let mut chunk = [0u8; 4096];
+            while let Ok(n) = pipe.read(&mut chunk) {
+                if n == 0 {
+                    break;
+                }
+                if let Ok(mut buf) = stderr_buf.lock() {
+                    buf.extend_from_slice(&chunk[..n]);
+                    if buf.len() > MAX_STDERR_BYTES {
+                        let excess = buf.len() - MAX_STDERR_BYTES;
+                        buf.drain(..excess);
+                    }
+                }
+            }
+            let _ = done_tx.send(());
+        });
+
+        Ok(Self {
+            child,
+            stdin,
+            rx,
+            stderr,
+            stderr_done,
+            next_id: 1,
+        })
+    }
+
+    fn send(&mut self, message: &Value) -> Result<(), String> {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| format!("写入服务器 stdin 失败: {e}"))
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&rpc_request(id, method, params))?;
+
+        while let Some(message) = self.rx.recv().await {
+            if let Some(response) = match_response(&message, id) {
+                return response;
+            }
+            if let Some(reply) = reply_to_server_request(&message) {
+                self.send(&reply)?;
+            }
+        }
+
+        let exit = self
+            .child
+            .try_wait()
+            .ok()
+            .flatten()
+            .map(|status| format!("（{status}）"))
+            .unwrap_or_default();
+        Err(format!("服务器在响应 {method} 前退出{exit}"))
+    }
+
+    /// 结束子进程并返回 stderr 尾部
+    fn shutdown(mut self) -> Option<String> {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = self.stderr_done.recv_timeout(STDERR_DRAIN_WAIT);
+        let buf = self.stderr.lock().ok()?;
+        let text = String::from_utf8_lossy(&buf).trim().to_string();
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+impl Drop for StdioSession {
+    fn drop(&mut self) {
+        // 网关中的长连接被替换或移除时同样需要结束子进程
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn build_headers(spec: &Value) -> Result<HeaderMap, String> {
+    let mut headers = HeaderMap::new();
+    if let Some(map) = spec.get("headers").and_then(|h| h.as_object()) {
+        for (key, value) in map {
+            let Some(value) = value.as_str() else {
+                continue;
+            };
+            let name = HeaderName::from_bytes(key.as_bytes())
+                .map_err(|e| format!("无效的请求头 {key}: {e}"))?;
+            let value =
+                HeaderValue::from_str(value).map_err(|e| format!("无效的请求头 {key}: {e}"))?;
+            headers.insert(name, value);
+        }
+    }
+    Ok(headers)
+}
+
+fn spec_url(spec: &Value) -> Result<reqwest::Url, String> {
+    let url = spec.get("url").and_then(|u| u.as_str()).unwrap_or_default();
+    reqwest::Url::parse(url.trim()).map_err(|e| format!("无效的 URL {url}: {e}"))
+}
+
+/// Streamable HTTP：每个请求一次 POST，响应为 JSON 或 SSE 流
+pub(crate) struct HttpSession {
+    client: reqwest::Client,
+    url: reqwest::Url,
+    headers: HeaderMap,
+    session_id: Option<String>,
+    next_id: u64,
+}
+
+impl HttpSession {
+    fn new(spec: &Value) -> Result<Self, String> {
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url: spec_url(spec)?,
+            headers: build_headers(spec)?,
+            session_id: None,
+            next_id: 1,
+        })
+    }
+
+    async fn post(&mut self, message: &Value) -> Result<Vec<Value>, String> {
+        let mut request = self
+            .client
+            .post(self.url.clone())
+            .headers(self.headers.clone())
+            .header(ACCEPT, "application/json, text/event-stream")
+            .json(message);
+        if let Some(session_id) = &self.session_id {
+            request = request.header("Mcp-Session-Id", session_id);
+        }
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        if let Some(session_id) = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+        let status = response.status();
+        let is_sse = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("text/event-stream"));
+        let body = response.text().await.map_err(|e| e.to_string())?;
+        if !status.is_success() {
+            let snippet: String = body.chars().take(200).collect();
+            return Err(format!("HTTP {status}: {snippet}"));
+        }
+        if body.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let payloads: Vec<String> = if is_sse {
+            let mut buf = body;
+            buf.push_str("\n\n");
+            parse_sse_events(&mut buf)
+                .into_iter()
+                .map(|event| event.data)
+                .collect()
+        } else {
+            vec![body]
+        };
+
+        let mut messages = Vec::new();
+        for payload in payloads {
+            match serde_json::from_str::<Value>(&payload) {
+                Ok(Value::Array(batch)) => messages.extend(batch),
+                Ok(message) => messages.push(message),
+                Err(_) => continue,
+            }
+        }
+        Ok(messages)
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let messages = self.post(&rpc_request(id, method, params)).await?;
+        messages
+            .iter()
+            .find_map(|m| match_response(m, id))
+            .unwrap_or_else(|| Err(format!("{method} 没有返回响应")))
+    }
+}
+
+/// 旧版 SSE 传输：GET 建立事件流，`endpoint` 事件给出 POST 地址，响应经事件流返回
+pub(crate) struct SseSession {
+    client: reqwest::Client,
+    post_url: reqwest::Url,
+    headers: HeaderMap,
+    rx: mpsc::Receiver<Value>,
+    reader: tokio::task::JoinHandle<()>,
+    next_id: u64,
+}
+
+impl SseSession {
+    async fn connect(spec: &Value) -> Result<Self, String> {
+        let url = spec_url(spec)?;
+        let headers = build_headers(spec)?;
+        let client = reqwest::Client::new();
+        let mut response = client
+            .get(url.clone())
+            .headers(headers.clone())
+            .header(ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+
+        let (endpoint_tx, endpoint_rx) = tokio::sync::oneshot::channel::<String>();
+        let (tx, rx) = mpsc::channel(64);
+        let reader = tokio::spawn(async move {
+            let mut endpoint_tx = Some(endpoint_tx);
+            let mut buf = String::new();
+            while let Ok(Some(chunk)) = response.chunk().await {
+                buf.push_str(&String::from_utf8_lossy(&chunk));
+                for event in parse_sse_events(&mut buf) {
+                    if event.event.as_deref() == Some("endpoint") {
+                        if let Some(sender) = endpoint_tx.take() {
+                            let _ = sender.send(event.data);
+                        }
+                    } else if let Ok(message) = serde_json::from_str::<Value>(&event.data) {
+                        if tx.send(message).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        let endpoint = endpoint_rx
+            .await
+            .map_err(|_| "SSE 流在返回 endpoint 事件前关闭".to_string())?;
+        let post_url = url
+            .join(endpoint.trim())
+            .map_err(|e| format!("无效的 endpoint {endpoint}: {e}"))?;
+
+        Ok(Self {
+            client,
+            post_url,
+            headers,
+            rx,
+            reader,
+            next_id: 1,
+        })
+    }
+
+    async fn post(&self, message: &Value) -> Result<(), String> {
+        let response = self
+            .client
+            .post(self.post_url.clone())
+            .headers(self.headers.clone())
+            .json(message)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        Ok(())
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.post(&rpc_request(id, method, params)).await?;
+
+        while let Some(message) = self.rx.recv().await {
+            if let Some(response) = match_response(&message, id) {
+                return response;
+            }
+            if let Some(reply) = reply_to_server_request(&message) {
+                self.post(&reply).await?;
+            }
+        }
+        Err(format!("SSE 流在响应 {method} 前关闭"))
+    }
+}
+
+impl Drop for SseSession {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SseEvent {
+    event: Option<String>,
+    data: String,
+}
+
+/// 从缓冲区取出所有完整的 SSE 事件，未完成的部分留在缓冲区
+fn parse_sse_events(buf: &mut String) -> Vec<SseEvent> {
+    if buf.contains('\r') {
+        *buf = buf.replace("\r\n", "\n");
+    }
+    let mut events = Vec::new();
+    while let Some(pos) = buf.find("\n\n") {
+        let block: String = buf.drain(..pos + 2).collect();
+        let mut event = None;
+        let mut data: Vec<&str> = Vec::new();
+        for line in block.lines() {
+            if let Some(value) = line.strip_prefix("event:") {
+                event = Some(value.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("data:") {
+                data.push(value.strip_prefix(' ').unwrap_or(value));
+            }
+        }
+        if !data.is_empty() {
+            events.push(SseEvent {
+                event,
+                data: data.join("\n"),
+            });
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_parser_keeps_incomplete_events_buffered() {
+        let mut buf =
+            "event: endpoint\ndata: /messages?s=1\n\ndata: {\"a\":1}\r\n\r\ndata: part".to_string();
+        let events = parse_sse_events(&mut buf);
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: Some("endpoint".to_string()),
+                    data: "/messages?s=1".to_string(),
+                },
+                SseEvent {
+                    event: None,
+                    data: "{\"a\":1}".to_string(),
+                },
+            ]
+        );
+        assert_eq!(buf, "data: part");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stdio_probe_lists_tools_and_captures_stderr() {
+        let script = r#"
+read line
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","capabilities":{"tools":{}},"serverInfo":{"name":"stub","version":"1.2.3"}}}'
+read line
+read line
+echo 'starting stub' >&2
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo","description":"Echo input","inputSchema":{"type":"object"}}]}}'
+sleep 5
+"#;
+        let spec = json!({ "type": "stdio", "command": "sh", "args": ["-c", script] });
+        let result = probe_server(&spec, Duration::from_secs(10)).await;
+
+        assert_eq!(result.status, McpProbeStatus::Ok, "{:?}", result.error);
+        assert_eq!(result.server_name.as_deref(), Some("stub"));
+        assert_eq!(result.server_version.as_deref(), Some("1.2.3"));
+        assert_eq!(result.tools.len(), 1);
+        assert_eq!(result.tools[0].name, "echo");
+        assert!(result.prompts.is_empty());
+        assert_eq!(result.stderr.as_deref(), Some("starting stub"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stdio_probe_reports_crash_with_stderr() {
+        let spec = json!({
+            "command": "sh",
+            "args": ["-c", "echo 'missing API_KEY' >&2; exit 3"],
+        });
+        let result = probe_server(&spec, Duration::from_secs(10)).await;
+
+        assert_eq!(result.status, McpProbeStatus::Error);
+        assert!(result.error.is_some());
+        assert_eq!(result.stderr.as_deref(), Some("missing API_KEY"));
+    }
+
+    #[tokio::test]
+    async fn invalid_spec_fails_without_spawning() {
+        let result = probe_server(&json!({ "type": "stdio" }), Duration::from_secs(1)).await;
+        assert_eq!(result.status, McpProbeStatus::Error);
+        assert!(result.error.unwrap().contains("command"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Qwen Code MCP 同步和导入模块
+//!
+//! Qwen Code 沿用 Gemini CLI 的 `mcpServers` 格式（httpUrl / timeout），
+//! 只是写入 `~/.qwen/settings.json`，因此直接复用 Gemini 的读写与格式转换。
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::app_config::{McpApps, McpServer, MultiAppConfig};
+use crate::error::AppError;
+use crate::gemini_mcp::{read_mcp_servers_map_at, set_mcp_servers_map_at};
+use crate::qwen_config::{get_qwen_dir, get_qwen_settings_path};
+
+use super::secrets::SecretResolver;
+use super::validation::validate_server_spec;
+
+fn should_sync_qwen_mcp() -> bool {
+    // Qwen Code 未安装/未初始化时 ~/.qwen 不存在，跳过写入，不创建任何文件或目录
+    crate::config::live_path_exists(&get_qwen_dir())
+}
+
+/// 从 Qwen Code MCP 配置导入到统一结构
+/// 已存在的服务器将启用 Qwen 应用，不覆盖其他字段和应用状态
+pub fn import_from_qwen(config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    let map = read_mcp_servers_map_at(&get_qwen_settings_path())?;
+    if map.is_empty() {
+        return Ok(0);
+    }
+
+    let servers = config.mcp.servers.get_or_insert_with(HashMap::new);
+
+    let mut changed = 0;
+    let mut errors = Vec::new();
+
+    for (id, spec) in map.iter() {
+        if let Err(e) = validate_server_spec(spec) {
+            log::warn!("跳过无效 MCP 服务器 '{id}': {e}");
+            errors.push(format!("{id}: {e}"));
+            continue;
+        }
+
+        if let Some(existing) = servers.get_mut(id) {
+            if !existing.apps.qwen {
+                existing.apps.qwen = true;
+                changed += 1;
+                log::info!("MCP 服务器 '{id}' 已启用 Qwen Code 应用");
+            }
+        } else {
+            servers.insert(
+                id.clone(),
+                McpServer {
+                    id: id.clone(),
+                    name: id.clone(),
+                    server: spec.clone(),
+                    apps: McpApps {
+                        qwen: true,
+                        ..Default::default()
+                    },
+                    description: None,
+                    homepage: None,
+                    docs: None,
+                    tags: Vec::new(),
+                },
+            );
+            changed += 1;
+            log::info!("导入新 MCP 服务器 '{id}'");
+        }
+    }
+
+    if !errors.is_empty() {
+        log::warn!("导入完成，但有 {} 项失败: {:?}", errors.len(), errors);
+    }
+
+    Ok(changed)
+}
+
+/// 将单个 MCP 服务器同步到 Qwen Code live 配置
+pub fn sync_single_server_to_qwen(
+    _config: &MultiAppConfig,
+    id: &str,
+    server_spec: &Value,
+    secrets: &SecretResolver,
+) -> Result<(), AppError> {
+    if !should_sync_qwen_mcp() {
+        return Ok(());
+    }
+    let server_spec = secrets.resolve_for_write(id, server_spec);
+    let path = get_qwen_settings_path();
+    let mut current = read_mcp_servers_map_at(&path)?;
+    current.insert(id.to_string(), server_spec);
+    set_mcp_servers_map_at(&path, &current)
+}
+
+/// 从 Qwen Code live 配置中移除单个 MCP 服务器
+pub fn remove_server_from_qwen(id: &str) -> Result<(), AppError> {
+    if !should_sync_qwen_mcp() {
+        return Ok(());
+    }
+    let path = get_qwen_settings_path();
+    let mut current = read_mcp_servers_map_at(&path)?;
+    if current.remove(id).is_none() {
+        return Ok(());
+    }
+    set_mcp_servers_map_at(&path, &current)
+}
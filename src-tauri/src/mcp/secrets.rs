@@ -0,0 +1,315 @@
+//! MCP 密钥引用
+//!
+//! `env` 与 `headers` 中的值可以写成占位符，而不是直接内联 token：
+//!
+//! - `${secret:name}`：从本地密钥库（`mcp_secrets` 表，不参与同步与导出）读取
+//! - `${env:VAR}`：从 CC Switch 进程的环境变量读取
+//!
+//! 数据库、同步与导出内容中只保存占位符，仅在写入各应用 live 配置（以及探测、网关连接）时展开。
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::app_config::{AppType, McpServer};
+use crate::error::AppError;
+
+/// 支持占位符的 spec 字段
+const SECRET_REF_FIELDS: [&str; 2] = ["env", "headers"];
+
+static SECRET_REF_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\$\{(secret|env):([A-Za-z0-9_.\-]+)\}").expect("Invalid secret ref regex")
+});
+
+/// 占位符来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretRefKind {
+    Secret,
+    Env,
+}
+
+/// spec 中的一处占位符引用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretRef {
+    pub kind: SecretRefKind,
+    pub name: String,
+    /// 引用所在字段，如 `env.GITHUB_TOKEN`、`headers.Authorization`
+    pub field: String,
+}
+
+/// 本地密钥库条目（对外不返回值本身）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSecretInfo {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 某个应用下未能解析的引用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedSecretRef {
+    pub server_id: String,
+    pub server_name: String,
+    #[serde(flatten)]
+    pub reference: SecretRef,
+}
+
+/// 密钥引用校验报告：按应用列出未解析的引用
+pub type McpSecretReport = BTreeMap<String, Vec<UnresolvedSecretRef>>;
+
+/// 校验密钥名称（与占位符语法保持一致）
+pub fn validate_secret_name(name: &str) -> Result<(), AppError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(format!(
+            "密钥名称只能包含字母、数字、下划线、点和短横线: {name}"
+        )))
+    }
+}
+
+/// 占位符解析器
+///
+/// 持有一次性读取的本地密钥快照；`${env:...}` 在解析时实时读取环境变量。
+#[derive(Debug, Clone, Default)]
+pub struct SecretResolver {
+    secrets: HashMap<String, String>,
+}
+
+impl SecretResolver {
+    pub fn new(secrets: HashMap<String, String>) -> Self {
+        Self { secrets }
+    }
+
+    fn lookup(&self, kind: SecretRefKind, name: &str) -> Option<String> {
+        match kind {
+            SecretRefKind::Secret => self.secrets.get(name).cloned(),
+            SecretRefKind::Env => std::env::var(name).ok(),
+        }
+    }
+
+    /// 展开 spec 中 `env` / `headers` 的占位符
+    ///
+    /// 返回展开后的 spec 与未能解析的引用；未解析的占位符原样保留。
+    pub fn resolve(&self, spec: &Value) -> (Value, Vec<SecretRef>) {
+        let mut resolved = spec.clone();
+        let mut unresolved = Vec::new();
+
+        for field in SECRET_REF_FIELDS {
+            let Some(map) = resolved.get_mut(field).and_then(Value::as_object_mut) else {
+                continue;
+            };
+            for (key, value) in map.iter_mut() {
+                let Some(raw) = value.as_str() else {
+                    continue;
+                };
+                if !SECRET_REF_RE.is_match(raw) {
+                    continue;
+                }
+                let expanded = SECRET_REF_RE.replace_all(raw, |caps: &Captures| {
+                    let (kind, name) = parse_captures(caps);
+                    match self.lookup(kind, name) {
+                        Some(v) => v,
+                        None => {
+                            unresolved.push(SecretRef {
+                                kind,
+                                name: name.to_string(),
+                                field: format!("{field}.{key}"),
+                            });
+                            caps[0].to_string()
+                        }
+                    }
+                });
+                *value = Value::String(expanded.into_owned());
+            }
+        }
+
+        (resolved, unresolved)
+    }
+
+    /// 写入 live 配置前展开占位符，未解析的引用仅记录警告
+    ///
+    /// 占位符只在这里（即各应用写入 live 配置时）展开：数据库、导出与同步中始终保留
+    /// 占位符原文，密钥明文只落到客户端自身的配置文件。所有 live 写入函数都必须经由此处。
+    pub fn resolve_for_write(&self, id: &str, spec: &Value) -> Value {
+        let (resolved, unresolved) = self.resolve(spec);
+        for r in &unresolved {
+            log::warn!(
+                "MCP 服务器 {id} 的 {} 引用了未定义的 {}:{}，已原样写入",
+                r.field,
+                kind_str(r.kind),
+                r.name
+            );
+        }
+        resolved
+    }
+
+    /// 按应用汇总未解析的引用（只包含启用了该应用且存在问题的服务器）
+    pub fn report(&self, servers: &IndexMap<String, McpServer>) -> McpSecretReport {
+        let mut report = McpSecretReport::new();
+        for app in AppType::all() {
+            if matches!(app, AppType::OpenClaw | AppType::ClaudeDesktop) {
+                continue;
+            }
+            let mut entries = Vec::new();
+            for server in servers.values() {
+                if !server.apps.is_enabled_for(&app) {
+                    continue;
+                }
+                let (_, unresolved) = self.resolve(&server.server);
+                entries.extend(unresolved.into_iter().map(|reference| UnresolvedSecretRef {
+                    server_id: server.id.clone(),
+                    server_name: server.name.clone(),
+                    reference,
+                }));
+            }
+            if !entries.is_empty() {
+                report.insert(app.as_str().to_string(), entries);
+            }
+        }
+        report
+    }
+}
+
+/// 列出 spec 中全部占位符引用（不做解析）
+pub fn find_secret_refs(spec: &Value) -> Vec<SecretRef> {
+    let mut refs = Vec::new();
+    for field in SECRET_REF_FIELDS {
+        let Some(map) = spec.get(field).and_then(Value::as_object) else {
+            continue;
+        };
+        for (key, value) in map {
+            let Some(raw) = value.as_str() else {
+                continue;
+            };
+            for caps in SECRET_REF_RE.captures_iter(raw) {
+                let (kind, name) = parse_captures(&caps);
+                refs.push(SecretRef {
+                    kind,
+                    name: name.to_string(),
+                    field: format!("{field}.{key}"),
+                });
+            }
+        }
+    }
+    refs
+}
+
+fn parse_captures<'a>(caps: &'a Captures<'_>) -> (SecretRefKind, &'a str) {
+    let kind = if &caps[1] == "secret" {
+        SecretRefKind::Secret
+    } else {
+        SecretRefKind::Env
+    };
+    (kind, caps.get(2).map_or("", |m| m.as_str()))
+}
+
+fn kind_str(kind: SecretRefKind) -> &'static str {
+    match kind {
+        SecretRefKind::Secret => "secret",
+        SecretRefKind::Env => "env",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::McpApps;
+    use serde_json::json;
+
+    fn resolver() -> SecretResolver {
+        SecretResolver::new(HashMap::from([(
+            "github_token".to_string(),
+            "ghp_abc".to_string(),
+        )]))
+    }
+
+    #[test]
+    fn resolves_secret_and_env_refs_in_env_and_headers() {
+        std::env::set_var("CC_SWITCH_SECRET_TEST_VAR", "from-env");
+        let spec = json!({
+            "type": "http",
+            "url": "https://example.com/${secret:github_token}",
+            "env": { "GITHUB_TOKEN": "${secret:github_token}", "PORT": 8080 },
+            "headers": { "Authorization": "Bearer ${env:CC_SWITCH_SECRET_TEST_VAR}" }
+        });
+
+        let (resolved, unresolved) = resolver().resolve(&spec);
+        assert!(unresolved.is_empty());
+        assert_eq!(resolved["env"]["GITHUB_TOKEN"], "ghp_abc");
+        assert_eq!(resolved["env"]["PORT"], 8080);
+        assert_eq!(resolved["headers"]["Authorization"], "Bearer from-env");
+        // 只展开 env / headers
+        assert_eq!(
+            resolved["url"],
+            "https://example.com/${secret:github_token}"
+        );
+        // 原 spec 不被修改
+        assert_eq!(spec["env"]["GITHUB_TOKEN"], "${secret:github_token}");
+    }
+
+    #[test]
+    fn unresolved_refs_are_kept_and_reported() {
+        let spec = json!({
+            "command": "npx",
+            "env": { "A": "${secret:missing}", "B": "x-${env:CC_SWITCH_SECRET_TEST_UNSET}" }
+        });
+
+        let (resolved, unresolved) = resolver().resolve(&spec);
+        assert_eq!(resolved["env"]["A"], "${secret:missing}");
+        assert_eq!(unresolved.len(), 2);
+        assert_eq!(unresolved[0].kind, SecretRefKind::Secret);
+        assert_eq!(unresolved[0].field, "env.A");
+        assert_eq!(unresolved[1].kind, SecretRefKind::Env);
+        assert_eq!(find_secret_refs(&spec).len(), 2);
+    }
+
+    #[test]
+    fn report_groups_unresolved_refs_by_enabled_app() {
+        let mut servers = IndexMap::new();
+        servers.insert(
+            "gh".to_string(),
+            McpServer {
+                id: "gh".to_string(),
+                name: "GitHub".to_string(),
+                server: json!({ "command": "gh-mcp", "env": { "T": "${secret:nope}" } }),
+                apps: McpApps {
+                    claude: true,
+                    codex: true,
+                    ..Default::default()
+                },
+                description: None,
+                homepage: None,
+                docs: None,
+                tags: Vec::new(),
+            },
+        );
+
+        let report = resolver().report(&servers);
+        assert_eq!(report.len(), 2);
+        assert_eq!(report["claude"][0].server_id, "gh");
+        assert_eq!(report["codex"][0].reference.name, "nope");
+        assert!(!report.contains_key("gemini"));
+    }
+
+    #[test]
+    fn secret_names_follow_placeholder_syntax() {
+        assert!(validate_secret_name("github_token.v2-prod").is_ok());
+        assert!(validate_secret_name("").is_err());
+        assert!(validate_secret_name("has space").is_err());
+        assert!(validate_secret_name("a}b").is_err());
+    }
+}
@@ -3,7 +3,9 @@
 //! 处理 `~/.openclaw/openclaw.json` 配置文件的读写操作（JSON5 格式）。
 //! OpenClaw 使用累加式供应商管理，所有供应商配置共存于同一配置文件中。
 
-use crate::config::{atomic_write, get_app_config_dir};
+use crate::config::{
+    atomic_write, get_app_config_dir, is_staging_writes, live_path_exists, read_live_to_string,
+};
 use crate::error::AppError;
 use crate::settings::{effective_backup_retain_count, get_openclaw_override_dir};
 use chrono::Local;
@@ -198,11 +200,11 @@ pub struct OpenClawToolsConfig {
 /// 支持 JSON5 格式，返回完整的配置 JSON 对象
 pub fn read_openclaw_config() -> Result<Value, AppError> {
     let path = get_openclaw_config_path();
-    if !path.exists() {
+    if !live_path_exists(&path) {
         return Ok(default_openclaw_config_value());
     }
 
-    let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let content = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
     json5::from_str(&content)
         .map_err(|e| AppError::Config(format!("Failed to parse OpenClaw config as JSON5: {e}")))
 }
@@ -236,8 +238,8 @@ struct OpenClawConfigDocument {
 impl OpenClawConfigDocument {
     fn load() -> Result<Self, AppError> {
         let path = get_openclaw_config_path();
-        let original_source = if path.exists() {
-            Some(fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?)
+        let original_source = if live_path_exists(&path) {
+            Some(read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?)
         } else {
             None
         };
@@ -324,8 +326,8 @@ impl OpenClawConfigDocument {
     fn save(self) -> Result<OpenClawWriteOutcome, AppError> {
         let _guard = openclaw_write_lock().lock()?;
 
-        let current_source = if self.path.exists() {
-            Some(fs::read_to_string(&self.path).map_err(|e| AppError::io(&self.path, e))?)
+        let current_source = if live_path_exists(&self.path) {
+            Some(read_live_to_string(&self.path).map_err(|e| AppError::io(&self.path, e))?)
         } else {
             None
         };
@@ -356,6 +358,7 @@ impl OpenClawConfigDocument {
             .as_ref()
             .map(|source| create_openclaw_backup(source))
             .transpose()?
+            .flatten()
             .map(|path| path.display().to_string());
 
         atomic_write(&self.path, next_source.as_bytes())?;
@@ -382,7 +385,11 @@ fn write_root_section(section: &str, value: &Value) -> Result<OpenClawWriteOutco
     document.save()
 }
 
-fn create_openclaw_backup(source: &str) -> Result<PathBuf, AppError> {
+/// 写入前备份原配置；写入暂存（预览）期间不落盘，返回 `None`
+fn create_openclaw_backup(source: &str) -> Result<Option<PathBuf>, AppError> {
+    if is_staging_writes() {
+        return Ok(None);
+    }
     let backup_dir = get_app_config_dir().join("backups").join("openclaw");
     fs::create_dir_all(&backup_dir).map_err(|e| AppError::io(&backup_dir, e))?;
 
@@ -399,7 +406,7 @@ fn create_openclaw_backup(source: &str) -> Result<PathBuf, AppError> {
 
     atomic_write(&backup_path, source.as_bytes())?;
     cleanup_openclaw_backups(&backup_dir)?;
-    Ok(backup_path)
+    Ok(Some(backup_path))
 }
 
 fn cleanup_openclaw_backups(dir: &Path) -> Result<(), AppError> {
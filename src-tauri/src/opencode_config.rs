@@ -1,4 +1,4 @@
-use crate::config::write_json_file;
+use crate::config::{live_path_exists, read_live_to_string, write_json_file};
 use crate::error::AppError;
 use crate::provider::OpenCodeProviderConfig;
 use crate::settings::get_opencode_override_dir;
@@ -88,13 +88,13 @@ pub fn get_opencode_env_path() -> PathBuf {
 pub fn read_opencode_config() -> Result<Value, AppError> {
     let path = get_opencode_config_path();
 
-    if !path.exists() {
+    if !live_path_exists(&path) {
         return Ok(json!({
             "$schema": "https://opencode.ai/config.json"
         }));
     }
 
-    let content = std::fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let content = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
     json5::from_str(&content).map_err(|e| {
         AppError::Config(format!(
             "Failed to parse OpenCode config: {}: {e}",
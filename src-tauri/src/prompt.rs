@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::app_config::{AppType, PromptApps};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Prompt {
@@ -14,3 +17,79 @@ pub struct Prompt {
     #[serde(rename = "updatedAt", skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<i64>,
 }
+
+/// 跨应用共享的提示词
+///
+/// 同一份内容按 `apps` 写入多个应用的提示词文件，`overrides` 中的应用专属段落追加在公共内容之后。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedPrompt {
+    pub id: String,
+    pub name: String,
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub apps: PromptApps,
+    /// 应用 ID -> 追加的专属段落
+    #[serde(default)]
+    pub overrides: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+}
+
+impl SharedPrompt {
+    /// 写入指定应用时的完整内容
+    pub fn content_for(&self, app: &AppType) -> String {
+        match self
+            .overrides
+            .get(app.as_str())
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+        {
+            Some(section) => format!("{}\n\n{section}\n", self.content.trim_end()),
+            None => self.content.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_prompt_appends_app_override_section() {
+        let prompt = SharedPrompt {
+            id: "team".to_string(),
+            name: "Team".to_string(),
+            content: "# Team rules\n".to_string(),
+            description: None,
+            apps: PromptApps {
+                claude: true,
+                codex: true,
+                ..Default::default()
+            },
+            overrides: BTreeMap::from([
+                (
+                    "codex".to_string(),
+                    "## Codex\nUse apply_patch.".to_string(),
+                ),
+                ("claude".to_string(), "   ".to_string()),
+            ]),
+            created_at: None,
+            updated_at: None,
+        };
+
+        assert_eq!(prompt.content_for(&AppType::Claude), "# Team rules\n");
+        assert_eq!(
+            prompt.content_for(&AppType::Codex),
+            "# Team rules\n\n## Codex\nUse apply_patch.\n"
+        );
+        assert_eq!(
+            prompt.apps.enabled_apps(),
+            vec![AppType::Claude, AppType::Codex]
+        );
+    }
+}
@@ -17,6 +17,13 @@ pub fn prompt_file_path(app: &AppType) -> Result<PathBuf, AppError> {
             "Claude Desktop does not support Prompts",
         ));
     }
+    if matches!(app, AppType::Goose | AppType::Crush | AppType::Aider) {
+        return Err(AppError::localized(
+            "prompts.app_unsupported",
+            format!("{} 的提示词文件暂不由 CC Switch 管理", app.as_str()),
+            format!("Prompts for {} are not managed by CC Switch", app.as_str()),
+        ));
+    }
 
     let base_dir: PathBuf = match app {
         AppType::Claude => get_base_dir_with_fallback(get_claude_settings_path(), ".claude")?,
@@ -25,15 +32,21 @@ pub fn prompt_file_path(app: &AppType) -> Result<PathBuf, AppError> {
         AppType::OpenCode => get_opencode_dir(),
         AppType::OpenClaw => get_openclaw_dir(),
         AppType::Hermes => crate::hermes_config::get_hermes_dir(),
-        AppType::ClaudeDesktop => unreachable!("handled above"),
+        AppType::Qwen => crate::qwen_config::get_qwen_dir(),
+        AppType::ClaudeDesktop | AppType::Goose | AppType::Crush | AppType::Aider => {
+            unreachable!("handled above")
+        }
     };
 
     let filename = match app {
         AppType::Claude => "CLAUDE.md",
         AppType::Codex => "AGENTS.md",
         AppType::Gemini => "GEMINI.md",
+        AppType::Qwen => "QWEN.md",
         AppType::OpenCode | AppType::OpenClaw | AppType::Hermes => "AGENTS.md",
-        AppType::ClaudeDesktop => unreachable!("handled above"),
+        AppType::ClaudeDesktop | AppType::Goose | AppType::Crush | AppType::Aider => {
+            unreachable!("handled above")
+        }
     };
 
     Ok(base_dir.join(filename))
@@ -0,0 +1,156 @@
+//! 提示词模板与托管区块
+//!
+//! 多个提示词片段按顺序渲染后写入目标文件中由标记包围的托管区块，区块以外的内容由用户维护，
+//! 写入时原样保留。片段中可以使用 `{{os}}`、`{{date}}`、`{{app}}`、`{{project_name}}`
+//! 以及自定义变量，未定义的变量保持原样。
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::collections::BTreeMap;
+
+use crate::app_config::AppType;
+
+pub const BLOCK_BEGIN: &str = "<!-- cc-switch:prompts:begin -->";
+pub const BLOCK_END: &str = "<!-- cc-switch:prompts:end -->";
+
+static VARIABLE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{\s*([A-Za-z0-9_.\-]+)\s*\}\}").expect("Invalid template variable regex")
+});
+
+/// 模板变量上下文
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    vars: BTreeMap<String, String>,
+}
+
+impl TemplateContext {
+    /// 内置变量 + 自定义变量（自定义变量可覆盖内置变量）
+    pub fn new(
+        app: &AppType,
+        project_name: Option<&str>,
+        custom: &BTreeMap<String, String>,
+    ) -> Self {
+        let mut vars = BTreeMap::new();
+        vars.insert("os".to_string(), std::env::consts::OS.to_string());
+        vars.insert(
+            "date".to_string(),
+            chrono::Local::now().format("%Y-%m-%d").to_string(),
+        );
+        vars.insert("app".to_string(), app.as_str().to_string());
+        if let Some(name) = project_name {
+            vars.insert("project_name".to_string(), name.to_string());
+        }
+        vars.extend(custom.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Self { vars }
+    }
+
+    pub fn render(&self, template: &str) -> String {
+        VARIABLE_RE
+            .replace_all(template, |caps: &Captures| {
+                self.vars
+                    .get(&caps[1])
+                    .cloned()
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    }
+
+    /// 依次渲染片段并以空行拼接
+    pub fn render_fragments<'a>(&self, fragments: impl IntoIterator<Item = &'a str>) -> String {
+        fragments
+            .into_iter()
+            .map(|f| self.render(f.trim()))
+            .filter(|f| !f.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// 定位托管区块（含结束标记后的换行）
+fn block_range(content: &str) -> Option<(usize, usize)> {
+    let start = content.find(BLOCK_BEGIN)?;
+    let end_marker = start + content[start..].find(BLOCK_END)?;
+    let mut end = end_marker + BLOCK_END.len();
+    if content[end..].starts_with("\r\n") {
+        end += 2;
+    } else if content[end..].starts_with('\n') {
+        end += 1;
+    }
+    Some((start, end))
+}
+
+pub fn has_managed_block(content: &str) -> bool {
+    block_range(content).is_some()
+}
+
+/// 将渲染结果写入托管区块；没有区块时追加到用户内容之后
+pub fn splice_managed_block(existing: Option<&str>, body: &str) -> String {
+    let block = format!("{BLOCK_BEGIN}\n{}\n{BLOCK_END}\n", body.trim_end());
+    let existing = existing.unwrap_or_default();
+
+    if let Some((start, end)) = block_range(existing) {
+        return format!("{}{block}{}", &existing[..start], &existing[end..]);
+    }
+    if existing.trim().is_empty() {
+        return block;
+    }
+    format!("{}\n\n{block}", existing.trim_end())
+}
+
+/// 移除托管区块，仅保留用户内容
+pub fn remove_managed_block(existing: &str) -> String {
+    match block_range(existing) {
+        Some((start, end)) => {
+            let before = existing[..start].trim_end();
+            let after = existing[end..].trim_start_matches(['\r', '\n']);
+            match (before.is_empty(), after.is_empty()) {
+                (true, _) => after.to_string(),
+                (false, true) => format!("{before}\n"),
+                (false, false) => format!("{before}\n\n{after}"),
+            }
+        }
+        None => existing.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_builtin_and_custom_variables() {
+        let custom = BTreeMap::from([("team".to_string(), "platform".to_string())]);
+        let ctx = TemplateContext::new(&AppType::Claude, Some("cc-switch"), &custom);
+        let out = ctx.render("{{ team }} @ {{project_name}} on {{os}} for {{app}}: {{unknown}}");
+        assert_eq!(
+            out,
+            format!(
+                "platform @ cc-switch on {} for claude: {{{{unknown}}}}",
+                std::env::consts::OS
+            )
+        );
+        assert_eq!(ctx.render_fragments(["# A\n", "", "  # B  "]), "# A\n\n# B");
+    }
+
+    #[test]
+    fn splice_preserves_user_content_outside_markers() {
+        let first = splice_managed_block(Some("# My notes\n"), "fragment one");
+        assert_eq!(
+            first,
+            format!("# My notes\n\n{BLOCK_BEGIN}\nfragment one\n{BLOCK_END}\n")
+        );
+
+        let edited = format!("{first}\n## Added later\n");
+        let second = splice_managed_block(Some(&edited), "fragment two");
+        assert_eq!(
+            second,
+            format!("# My notes\n\n{BLOCK_BEGIN}\nfragment two\n{BLOCK_END}\n\n## Added later\n")
+        );
+
+        assert_eq!(
+            remove_managed_block(&second),
+            "# My notes\n\n## Added later\n"
+        );
+        assert_eq!(remove_managed_block(&splice_managed_block(None, "x")), "");
+    }
+}
@@ -164,8 +164,33 @@ impl Provider {
                 let api_key = first_non_empty(env, &["GEMINI_API_KEY", "GOOGLE_API_KEY"]);
                 (base_url, api_key)
             }
-            // Hermes (config.yaml) flattens credentials at the top level, snake_case.
-            AppType::Hermes => (
+            // Qwen Code reads OpenAI-compatible credentials from its `.env` map.
+            AppType::Qwen => {
+                let env = settings.get("env");
+                (
+                    str_at(env.and_then(|e| e.get("OPENAI_BASE_URL"))),
+                    str_at(env.and_then(|e| e.get("OPENAI_API_KEY"))),
+                )
+            }
+            // Aider keeps keys in `.env` and the OpenAI-compatible base URL in
+            // `.aider.conf.yml` as `openai-api-base`.
+            AppType::Aider => {
+                let env = settings.get("env");
+                (
+                    str_at(
+                        settings
+                            .get("config")
+                            .and_then(|c| c.get("openai-api-base")),
+                    ),
+                    first_non_empty(
+                        env,
+                        &["OPENAI_API_KEY", "OPENROUTER_API_KEY", "ANTHROPIC_API_KEY"],
+                    ),
+                )
+            }
+            // Hermes (config.yaml), Goose (custom_providers/*.json) and Crush (crush.json)
+            // flatten credentials at the top level, snake_case.
+            AppType::Hermes | AppType::Goose | AppType::Crush => (
                 str_at(settings.get("base_url")),
                 str_at(settings.get("api_key")),
             ),
@@ -461,6 +486,9 @@ pub struct ProviderMeta {
     /// Codex Responses -> Chat Completions reasoning capability metadata.
     #[serde(rename = "codexChatReasoning", skip_serializing_if = "Option::is_none")]
     pub codex_chat_reasoning: Option<CodexChatReasoningConfig>,
+    /// Codex 供应商级默认策略（推理强度、沙箱、审批）；仅在供应商配置未显式设置时写入 live
+    #[serde(rename = "codexDefaults", skip_serializing_if = "Option::is_none")]
+    pub codex_defaults: Option<crate::codex_profiles::CodexPolicySettings>,
     /// Custom User-Agent for local proxy routing.
     #[serde(rename = "customUserAgent", skip_serializing_if = "Option::is_none")]
     pub custom_user_agent: Option<String>,
@@ -1382,6 +1410,41 @@ mod tests {
         assert_eq!(api_key, "g-real");
     }
 
+    #[test]
+    fn resolve_credentials_qwen_openai_env() {
+        let p = provider_with(json!({
+            "env": {
+                "OPENAI_BASE_URL": "https://dashscope.aliyuncs.com/compatible-mode/v1",
+                "OPENAI_API_KEY": "sk-qwen",
+            }
+        }));
+        assert_eq!(
+            p.resolve_usage_credentials(&AppType::Qwen),
+            (
+                "https://dashscope.aliyuncs.com/compatible-mode/v1".to_string(),
+                "sk-qwen".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn resolve_credentials_aider_env_and_api_base() {
+        let p = provider_with(json!({
+            "env": { "OPENROUTER_API_KEY": "sk-or" },
+            "config": {
+                "model": "openai/deepseek-chat",
+                "openai-api-base": "https://api.deepseek.com/v1"
+            }
+        }));
+        assert_eq!(
+            p.resolve_usage_credentials(&AppType::Aider),
+            (
+                "https://api.deepseek.com/v1".to_string(),
+                "sk-or".to_string()
+            )
+        );
+    }
+
     #[test]
     fn resolve_credentials_hermes_snake_case() {
         let p = provider_with(json!({
@@ -1397,6 +1460,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_credentials_goose_and_crush_snake_case() {
+        let p = provider_with(json!({
+            "engine": "openai",
+            "base_url": "https://api.deepseek.com/v1/",
+            "api_key": "sk-flat",
+        }));
+        for app in [AppType::Goose, AppType::Crush] {
+            assert_eq!(
+                p.resolve_usage_credentials(&app),
+                (
+                    "https://api.deepseek.com/v1".to_string(),
+                    "sk-flat".to_string()
+                )
+            );
+        }
+    }
+
     #[test]
     fn resolve_credentials_openclaw_camel_case() {
         let p = provider_with(json!({
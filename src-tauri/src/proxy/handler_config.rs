@@ -218,6 +218,24 @@ pub const CODEX_RESPONSES_HANDLER_CONFIG: HandlerConfig = HandlerConfig {
     parser_config: &CODEX_PARSER_CONFIG,
 };
 
+/// Qwen Code Chat Completions Handler 配置
+#[allow(dead_code)]
+pub const QWEN_CHAT_HANDLER_CONFIG: HandlerConfig = HandlerConfig {
+    app_type: AppType::Qwen,
+    tag: "Qwen",
+    app_type_str: "qwen",
+    parser_config: &OPENAI_PARSER_CONFIG,
+};
+
+/// Aider Chat Completions Handler 配置
+#[allow(dead_code)]
+pub const AIDER_CHAT_HANDLER_CONFIG: HandlerConfig = HandlerConfig {
+    app_type: AppType::Aider,
+    tag: "Aider",
+    app_type_str: "aider",
+    parser_config: &OPENAI_PARSER_CONFIG,
+};
+
 /// Gemini Handler 配置
 #[allow(dead_code)]
 pub const GEMINI_HANDLER_CONFIG: HandlerConfig = HandlerConfig {
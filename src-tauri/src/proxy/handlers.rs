@@ -63,6 +63,62 @@ pub async fn get_status(State(state): State<ProxyState>) -> Result<Json<ProxySta
     Ok(Json(status))
 }
 
+/// POST /mcp/:app — 内置 MCP 网关（streamable HTTP，仅 JSON 响应）
+///
+/// 网关会以本机用户身份调用上游工具，因此只接受来自回环地址的连接，
+/// 并拒绝带非本机 `Origin` 的浏览器请求（防止网页跨站调用）；两者均返回 403。
+/// 网关未启用或应用不存在时返回 404；只含通知的请求返回 202。
+pub async fn handle_mcp_gateway(
+    State(state): State<ProxyState>,
+    peer: Option<axum::extract::ConnectInfo<std::net::SocketAddr>>,
+    headers: axum::http::HeaderMap,
+    axum::extract::Path(app): axum::extract::Path<String>,
+    Json(payload): Json<Value>,
+) -> axum::response::Response {
+    if !peer.is_some_and(|axum::extract::ConnectInfo(addr)| addr.ip().is_loopback()) {
+        return (
+            StatusCode::FORBIDDEN,
+            "MCP gateway only accepts local connections",
+        )
+            .into_response();
+    }
+    if let Some(origin) = headers.get(axum::http::header::ORIGIN) {
+        if !origin.to_str().is_ok_and(is_local_origin) {
+            return (StatusCode::FORBIDDEN, "Origin not allowed").into_response();
+        }
+    }
+
+    let Ok(app_type) = app.parse::<AppType>() else {
+        return (StatusCode::NOT_FOUND, format!("Unknown app: {app}")).into_response();
+    };
+    let enabled = state
+        .db
+        .get_mcp_gateway_config()
+        .map(|c| c.enabled)
+        .unwrap_or(false);
+    if !enabled {
+        return (StatusCode::NOT_FOUND, "MCP gateway is disabled").into_response();
+    }
+
+    match state.mcp_gateway.handle_payload(&app_type, &payload).await {
+        Some(response) => Json(response).into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+/// 判断 `Origin` 是否指向本机（localhost / loopback）；`null` 等不透明来源视为非本机
+fn is_local_origin(origin: &str) -> bool {
+    let Ok(url) = url::Url::parse(origin) else {
+        return false;
+    };
+    match url.host() {
+        Some(url::Host::Domain(d)) => d.eq_ignore_ascii_case("localhost"),
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
 /// GET /v1/models — Codex model list (reachability check)
 ///
 /// Codex CLI probes this endpoint at startup and deserializes the response as a
@@ -576,6 +632,33 @@ fn endpoint_with_query(uri: &axum::http::Uri, endpoint: &str) -> String {
 pub async fn handle_chat_completions(
     State(state): State<ProxyState>,
     request: axum::extract::Request,
+) -> Result<axum::response::Response, ProxyError> {
+    forward_chat_completions(state, request, AppType::Codex, "Codex", "codex").await
+}
+
+/// 处理 /qwen/v1/chat/completions 请求（Qwen Code，OpenAI Chat Completions 格式）
+pub async fn handle_qwen_chat_completions(
+    State(state): State<ProxyState>,
+    request: axum::extract::Request,
+) -> Result<axum::response::Response, ProxyError> {
+    forward_chat_completions(state, request, AppType::Qwen, "Qwen", "qwen").await
+}
+
+/// 处理 /aider/v1/chat/completions 请求（Aider，OpenAI Chat Completions 格式）
+pub async fn handle_aider_chat_completions(
+    State(state): State<ProxyState>,
+    request: axum::extract::Request,
+) -> Result<axum::response::Response, ProxyError> {
+    forward_chat_completions(state, request, AppType::Aider, "Aider", "aider").await
+}
+
+/// Chat Completions 的公共转发流程，按应用区分供应商队列与统计
+async fn forward_chat_completions(
+    state: ProxyState,
+    request: axum::extract::Request,
+    app_type: AppType,
+    tag: &'static str,
+    app_type_str: &'static str,
 ) -> Result<axum::response::Response, ProxyError> {
     let (parts, req_body) = request.into_parts();
     let method = parts.method.clone();
@@ -591,7 +674,7 @@ pub async fn handle_chat_completions(
         .map_err(|e| ProxyError::Internal(format!("Failed to parse request body: {e}")))?;
 
     let mut ctx =
-        RequestContext::new(&state, &body, &headers, AppType::Codex, "Codex", "codex").await?;
+        RequestContext::new(&state, &body, &headers, app_type.clone(), tag, app_type_str).await?;
     let endpoint = endpoint_with_query(&uri, "/chat/completions");
 
     let is_stream = body
@@ -602,7 +685,7 @@ pub async fn handle_chat_completions(
     let forwarder = ctx.create_forwarder(&state);
     let mut result = match forwarder
         .forward_with_retry(
-            &AppType::Codex,
+            &app_type,
             method,
             &endpoint,
             body,
@@ -1998,11 +2081,22 @@ async fn log_usage(
 mod tests {
     use super::{
         body_looks_like_sse, body_snippet, chat_sse_to_response_value, codex_proxy_error_json,
-        responses_sse_to_response_value, should_use_claude_transform_streaming, transform,
-        upstream_body_parse_error,
+        is_local_origin, responses_sse_to_response_value, should_use_claude_transform_streaming,
+        transform, upstream_body_parse_error,
     };
     use crate::proxy::ProxyError;
 
+    #[test]
+    fn mcp_gateway_only_accepts_local_origins() {
+        assert!(is_local_origin("http://localhost:3000"));
+        assert!(is_local_origin("http://127.0.0.1:15721"));
+        assert!(is_local_origin("http://[::1]:8080"));
+        assert!(!is_local_origin("https://evil.example.com"));
+        assert!(!is_local_origin("http://127.0.0.1.evil.com"));
+        assert!(!is_local_origin("http://192.168.1.10"));
+        assert!(!is_local_origin("null"));
+    }
+
     #[test]
     fn body_looks_like_sse_detects_unlabeled_sse_prefixes() {
         assert!(body_looks_like_sse("data: {\"id\":\"1\"}\n\n"));
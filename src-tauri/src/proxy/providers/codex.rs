@@ -504,6 +504,16 @@ impl ProviderAdapter for CodexAdapter {
                 return Ok(url.trim_end_matches('/').to_string());
             }
 
+            // Aider 的 `.aider.conf.yml` 使用 `openai-api-base`
+            if let Some(url) = config
+                .get("openai-api-base")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+            {
+                return Ok(url.trim_end_matches('/').to_string());
+            }
+
             // 尝试解析 TOML 字符串格式
             if let Some(config_str) = config.as_str() {
                 if let Some(start) = config_str.find("base_url = \"") {
@@ -521,6 +531,18 @@ impl ProviderAdapter for CodexAdapter {
             }
         }
 
+        // 4. 尝试从 env 中获取（Qwen Code 的 .env 格式）
+        if let Some(url) = provider
+            .settings_config
+            .get("env")
+            .and_then(|env| env.get("OPENAI_BASE_URL"))
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+        {
+            return Ok(url.trim_end_matches('/').to_string());
+        }
+
         Err(ProxyError::ConfigError(
             "Codex Provider 缺少 base_url 配置".to_string(),
         ))
@@ -609,6 +631,36 @@ mod tests {
         assert_eq!(url, "https://api.openai.com/v1");
     }
 
+    #[test]
+    fn test_extract_base_url_from_env() {
+        let adapter = CodexAdapter::new();
+        let provider = create_provider(json!({
+            "env": {
+                "OPENAI_API_KEY": "sk-qwen",
+                "OPENAI_BASE_URL": "https://dashscope.aliyuncs.com/compatible-mode/v1/"
+            },
+            "config": {}
+        }));
+
+        let url = adapter.extract_base_url(&provider).unwrap();
+        assert_eq!(url, "https://dashscope.aliyuncs.com/compatible-mode/v1");
+    }
+
+    #[test]
+    fn test_extract_base_url_from_aider_config() {
+        let adapter = CodexAdapter::new();
+        let provider = create_provider(json!({
+            "env": { "OPENAI_API_KEY": "sk-aider" },
+            "config": {
+                "model": "openai/deepseek-chat",
+                "openai-api-base": "https://api.deepseek.com/v1/"
+            }
+        }));
+
+        let url = adapter.extract_base_url(&provider).unwrap();
+        assert_eq!(url, "https://api.deepseek.com/v1");
+    }
+
     #[test]
     fn test_extract_auth_from_auth_field() {
         let adapter = CodexAdapter::new();
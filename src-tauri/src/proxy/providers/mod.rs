@@ -185,7 +185,13 @@ impl ProviderType {
                 }
                 ProviderType::Gemini
             }
-            AppType::OpenCode | AppType::OpenClaw | AppType::Hermes => {
+            // Qwen Code / Aider 走 OpenAI Chat Completions，与 Codex 共用适配器
+            AppType::Qwen | AppType::Aider => ProviderType::Codex,
+            AppType::OpenCode
+            | AppType::OpenClaw
+            | AppType::Hermes
+            | AppType::Goose
+            | AppType::Crush => {
                 // These apps don't support proxy, fallback to Codex-like type
                 ProviderType::Codex
             }
@@ -239,7 +245,12 @@ pub fn get_adapter(app_type: &AppType) -> Box<dyn ProviderAdapter> {
         AppType::Claude | AppType::ClaudeDesktop => Box::new(ClaudeAdapter::new()),
         AppType::Codex => Box::new(CodexAdapter::new()),
         AppType::Gemini => Box::new(GeminiAdapter::new()),
-        AppType::OpenCode | AppType::OpenClaw | AppType::Hermes => {
+        AppType::Qwen | AppType::Aider => Box::new(CodexAdapter::new()),
+        AppType::OpenCode
+        | AppType::OpenClaw
+        | AppType::Hermes
+        | AppType::Goose
+        | AppType::Crush => {
             // These apps don't support proxy, fallback to Codex adapter
             Box::new(CodexAdapter::new())
         }
@@ -18,6 +18,7 @@ use super::{
     ProxyError,
 };
 use crate::database::Database;
+use crate::mcp::McpGateway;
 use axum::{
     extract::DefaultBodyLimit,
     routing::{any, get, post},
@@ -48,6 +49,8 @@ pub struct ProxyState {
     pub app_handle: Option<tauri::AppHandle>,
     /// 故障转移切换管理器
     pub failover_manager: Arc<FailoverSwitchManager>,
+    /// 内置 MCP 网关（上游连接随代理生命周期复用）
+    pub mcp_gateway: Arc<McpGateway>,
 }
 
 /// 代理HTTP服务器
@@ -69,6 +72,7 @@ impl ProxyServer {
         let provider_router = Arc::new(ProviderRouter::new(db.clone()));
         // 创建故障转移切换管理器
         let failover_manager = Arc::new(FailoverSwitchManager::new(db.clone()));
+        let mcp_gateway = Arc::new(McpGateway::new(db.clone()));
 
         let state = ProxyState {
             db,
@@ -81,6 +85,7 @@ impl ProxyServer {
             codex_chat_history: Arc::new(CodexChatHistoryStore::default()),
             app_handle,
             failover_manager,
+            mcp_gateway,
         };
 
         Self {
@@ -143,7 +148,7 @@ impl ProxyServer {
             loop {
                 tokio::select! {
                     result = listener.accept() => {
-                        let (stream, _remote_addr) = match result {
+                        let (stream, remote_addr) = match result {
                             Ok(v) => v,
                             Err(e) => {
                                 log::error!("[{SRV}] accept 失败: {e}", SRV = log_srv::ACCEPT_ERR);
@@ -184,6 +189,8 @@ impl ProxyServer {
 
                                     // Insert our own header case map alongside hyper's internal one
                                     parts.extensions.insert(cases);
+                                    // 记录对端地址，供 MCP 网关等仅限本机的端点校验
+                                    parts.extensions.insert(axum::extract::ConnectInfo(remote_addr));
 
                                     let body = axum::body::Body::new(body);
                                     let axum_req = http::Request::from_parts(parts, body);
@@ -229,6 +236,8 @@ impl ProxyServer {
         } else {
             return Err(ProxyError::NotRunning);
         }
+        // 关闭 MCP 网关持有的上游服务器进程
+        self.state.mcp_gateway.reset().await;
 
         // 2. 等待服务器任务结束（带 5 秒超时保护）
         if let Some(handle) = self.server_handle.write().await.take() {
@@ -319,6 +328,24 @@ impl ProxyServer {
                 "/codex/v1/chat/completions",
                 post(handlers::handle_chat_completions),
             )
+            // Qwen Code（OpenAI Chat Completions 格式，独立供应商队列）
+            .route(
+                "/qwen/v1/chat/completions",
+                post(handlers::handle_qwen_chat_completions),
+            )
+            .route(
+                "/qwen/chat/completions",
+                post(handlers::handle_qwen_chat_completions),
+            )
+            // Aider（OpenAI Chat Completions 格式，独立供应商队列）
+            .route(
+                "/aider/v1/chat/completions",
+                post(handlers::handle_aider_chat_completions),
+            )
+            .route(
+                "/aider/chat/completions",
+                post(handlers::handle_aider_chat_completions),
+            )
             // OpenAI Models API (Codex CLI reachability check)
             .route("/models", get(handlers::handle_models))
             .route("/v1/models", get(handlers::handle_models))
@@ -354,6 +381,8 @@ impl ProxyServer {
             .route("/gemini/v1beta/*path", any(handlers::handle_gemini))
             // Gemini 的 GA 版本也叫 /v1，给原 SDK 留一条出口
             .route("/gemini/v1/*path", any(handlers::handle_gemini))
+            // 内置 MCP 网关（聚合所有启用到该应用的 MCP 服务器）
+            .route("/mcp/:app", post(handlers::handle_mcp_gateway))
             // 提高默认请求体大小限制（避免 413 Payload Too Large）
             .layer(DefaultBodyLimit::max(200 * 1024 * 1024))
             .with_state(self.state.clone())
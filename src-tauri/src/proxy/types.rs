@@ -113,6 +113,10 @@ pub struct ProxyTakeoverStatus {
     pub claude: bool,
     pub codex: bool,
     pub gemini: bool,
+    #[serde(default)]
+    pub qwen: bool,
+    #[serde(default)]
+    pub aider: bool,
     pub opencode: bool,
     pub openclaw: bool,
 }
@@ -4,6 +4,7 @@
 
 use super::parser::TokenUsage;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 /// 成本明细
@@ -23,6 +24,26 @@ pub struct ModelPricing {
     pub output_cost_per_million: Decimal,
     pub cache_read_cost_per_million: Decimal,
     pub cache_creation_cost_per_million: Decimal,
+    /// 分档定价（按 `above_tokens` 升序）：提示 token 超过阈值时整次请求按该档计费
+    pub tiers: Vec<(u64, ModelPricing)>,
+}
+
+/// 分档定价（`model_pricing.pricing_tiers` 列的 JSON 元素）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingTier {
+    /// 提示 token（输入 + 缓存读写）超过该值时生效
+    pub above_tokens: u64,
+    pub input_cost_per_million: String,
+    pub output_cost_per_million: String,
+    #[serde(default = "zero_price")]
+    pub cache_read_cost_per_million: String,
+    #[serde(default = "zero_price")]
+    pub cache_creation_cost_per_million: String,
+}
+
+fn zero_price() -> String {
+    "0".to_string()
 }
 
 /// 成本计算器
@@ -76,6 +97,16 @@ impl CostCalculator {
     ) -> CostBreakdown {
         let million = Decimal::from(1_000_000);
 
+        // 分档按整次请求的提示 token 总量选择（与 Anthropic/Gemini 长上下文计费一致）
+        let prompt_tokens = if input_includes_cache_read {
+            u64::from(usage.input_tokens) + u64::from(usage.cache_creation_tokens)
+        } else {
+            u64::from(usage.input_tokens)
+                + u64::from(usage.cache_read_tokens)
+                + u64::from(usage.cache_creation_tokens)
+        };
+        let pricing = pricing.for_prompt_tokens(prompt_tokens);
+
         // OpenAI/Gemini 风格的 input_tokens 包含缓存命中，需要扣除后再按输入价计费；
         // Claude/Anthropic 风格的 input_tokens 已经是 fresh input，不能再次扣减。
         let billable_input_tokens = if input_includes_cache_read {
@@ -141,8 +172,49 @@ impl ModelPricing {
             output_cost_per_million: Decimal::from_str(output)?,
             cache_read_cost_per_million: Decimal::from_str(cache_read)?,
             cache_creation_cost_per_million: Decimal::from_str(cache_creation)?,
+            tiers: Vec::new(),
         })
     }
+
+    /// 附加分档定价（`pricing_tiers` 列 JSON）；解析失败的档位被忽略
+    pub fn with_tiers_json(mut self, tiers_json: Option<&str>) -> Self {
+        let Some(json) = tiers_json.filter(|s| !s.trim().is_empty()) else {
+            return self;
+        };
+        let tiers: Vec<PricingTier> = match serde_json::from_str(json) {
+            Ok(tiers) => tiers,
+            Err(e) => {
+                log::warn!("分档定价解析失败，已忽略: {e}");
+                return self;
+            }
+        };
+        let mut parsed: Vec<(u64, ModelPricing)> = tiers
+            .iter()
+            .filter_map(|tier| {
+                ModelPricing::from_strings(
+                    &tier.input_cost_per_million,
+                    &tier.output_cost_per_million,
+                    &tier.cache_read_cost_per_million,
+                    &tier.cache_creation_cost_per_million,
+                )
+                .ok()
+                .map(|pricing| (tier.above_tokens, pricing))
+            })
+            .collect();
+        parsed.sort_by_key(|(above, _)| *above);
+        self.tiers = parsed;
+        self
+    }
+
+    /// 按提示 token 总量选择生效的定价档位
+    pub fn for_prompt_tokens(&self, prompt_tokens: u64) -> &ModelPricing {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|(above, _)| prompt_tokens > *above)
+            .map(|(_, pricing)| pricing)
+            .unwrap_or(self)
+    }
 }
 
 #[cfg(test)]
@@ -230,6 +302,41 @@ mod tests {
         assert_eq!(cost.total_cost, Decimal::from_str("0.0045").unwrap());
     }
 
+    #[test]
+    fn test_tiered_pricing_applies_above_threshold() {
+        let pricing = ModelPricing::from_strings("3.0", "15.0", "0.3", "3.75")
+            .unwrap()
+            .with_tiers_json(Some(
+                r#"[{"aboveTokens":200000,"inputCostPerMillion":"6.0","outputCostPerMillion":"22.5"}]"#,
+            ));
+        let multiplier = Decimal::from_str("1.0").unwrap();
+
+        let small = TokenUsage {
+            input_tokens: 100_000,
+            output_tokens: 0,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            model: None,
+            message_id: None,
+        };
+        let cost = CostCalculator::calculate(&small, &pricing, multiplier);
+        assert_eq!(cost.input_cost, Decimal::from_str("0.3").unwrap());
+
+        // 150k fresh + 100k cache read > 200k：整次请求按长上下文档计费
+        let large = TokenUsage {
+            input_tokens: 150_000,
+            output_tokens: 1_000,
+            cache_read_tokens: 100_000,
+            cache_creation_tokens: 0,
+            model: None,
+            message_id: None,
+        };
+        let cost = CostCalculator::calculate(&large, &pricing, multiplier);
+        assert_eq!(cost.input_cost, Decimal::from_str("0.9").unwrap());
+        assert_eq!(cost.output_cost, Decimal::from_str("0.0225").unwrap());
+        assert_eq!(cost.cache_read_cost, Decimal::ZERO);
+    }
+
     #[test]
     fn test_unknown_model_handling() {
         let usage = TokenUsage {
@@ -4,7 +4,9 @@ use super::calculator::{CostBreakdown, CostCalculator, ModelPricing};
 use super::parser::TokenUsage;
 use crate::database::{Database, PRICING_SOURCE_REQUEST, PRICING_SOURCE_RESPONSE};
 use crate::error::AppError;
-use crate::services::usage_stats::{find_model_pricing_row, is_placeholder_pricing_model};
+use crate::services::usage_stats::{
+    find_provider_model_pricing, is_placeholder_pricing_model, lookup_model_pricing,
+};
 use rust_decimal::Decimal;
 use std::str::FromStr;
 
@@ -196,15 +198,25 @@ impl<'a> UsageLogger<'a> {
     /// 获取模型定价
     pub fn get_model_pricing(&self, model_id: &str) -> Result<Option<ModelPricing>, AppError> {
         let conn = crate::database::lock_conn!(self.db.conn);
-        let row = find_model_pricing_row(&conn, model_id)?;
-        match row {
-            Some((input, output, cache_read, cache_creation)) => {
-                ModelPricing::from_strings(&input, &output, &cache_read, &cache_creation)
-                    .map(Some)
-                    .map_err(|e| AppError::Database(format!("解析定价数据失败: {e}")))
+        lookup_model_pricing(&conn, model_id)
+    }
+
+    /// 获取模型定价：供应商级覆盖优先，未覆盖时回退全局定价
+    pub fn get_provider_model_pricing(
+        &self,
+        provider_id: &str,
+        app_type: &str,
+        model_id: &str,
+    ) -> Result<Option<ModelPricing>, AppError> {
+        {
+            let conn = crate::database::lock_conn!(self.db.conn);
+            if let Some(pricing) =
+                find_provider_model_pricing(&conn, provider_id, app_type, model_id)?
+            {
+                return Ok(Some(pricing));
             }
-            None => Ok(None),
         }
+        self.get_model_pricing(model_id)
     }
 
     /// 获取有效的倍率与计费模式来源（供应商优先，未配置则回退全局默认）
@@ -321,7 +333,7 @@ impl<'a> UsageLogger<'a> {
         provider_type: Option<String>,
         is_streaming: bool,
     ) -> Result<(), AppError> {
-        let pricing = self.get_model_pricing(&pricing_model)?;
+        let pricing = self.get_provider_model_pricing(&provider_id, &app_type, &pricing_model)?;
 
         let has_usage = usage.input_tokens > 0
             || usage.output_tokens > 0
@@ -0,0 +1,265 @@
+//! Qwen Code 配置读写
+//!
+//! Qwen Code 是 Gemini CLI 的分支，配置布局与之相同：
+//! - `~/.qwen/.env`：OpenAI 兼容接口的凭据（`OPENAI_API_KEY` / `OPENAI_BASE_URL` / `OPENAI_MODEL`）
+//! - `~/.qwen/settings.json`：其余设置（认证方式、mcpServers 等）
+//!
+//! 供应商的 settings_config 结构为 `{ "env": {...}, "config": {...} }`。
+
+use crate::config::{get_home_dir, live_path_exists, read_live_to_string, write_json_file};
+use crate::error::AppError;
+use crate::gemini_config::{env_to_json, json_to_env, parse_env_file, write_env_file_atomic};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// API Key 模式（OpenAI 兼容接口）
+pub const QWEN_AUTH_OPENAI: &str = "openai";
+/// Qwen 官方 OAuth 登录
+pub const QWEN_AUTH_OAUTH: &str = "qwen-oauth";
+
+/// 获取 Qwen Code 配置目录路径（支持设置覆盖）
+pub fn get_qwen_dir() -> PathBuf {
+    if let Some(custom) = crate::settings::get_qwen_override_dir() {
+        return custom;
+    }
+
+    get_home_dir().join(".qwen")
+}
+
+/// 获取 Qwen Code .env 文件路径
+pub fn get_qwen_env_path() -> PathBuf {
+    get_qwen_dir().join(".env")
+}
+
+/// 获取 Qwen Code settings.json 文件路径
+pub fn get_qwen_settings_path() -> PathBuf {
+    get_qwen_dir().join("settings.json")
+}
+
+/// 读取 Qwen Code .env 文件
+pub fn read_qwen_env() -> Result<HashMap<String, String>, AppError> {
+    let path = get_qwen_env_path();
+
+    if !live_path_exists(&path) {
+        return Ok(HashMap::new());
+    }
+
+    let content = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+
+    Ok(parse_env_file(&content))
+}
+
+/// 写入 Qwen Code .env 文件（原子操作）
+pub fn write_qwen_env_atomic(map: &HashMap<String, String>) -> Result<(), AppError> {
+    write_env_file_atomic(&get_qwen_env_path(), map)
+}
+
+fn read_qwen_settings_json() -> Result<Option<Value>, AppError> {
+    let path = get_qwen_settings_path();
+    if !live_path_exists(&path) {
+        return Ok(None);
+    }
+    let content = read_live_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let value = serde_json::from_str(&content).map_err(|e| AppError::json(&path, e))?;
+    Ok(Some(value))
+}
+
+/// 验证 Qwen Code 配置的基本结构
+///
+/// 不强制要求 OPENAI_API_KEY，允许先创建供应商、稍后再填写。
+pub fn validate_qwen_settings(settings: &Value) -> Result<(), AppError> {
+    if let Some(env) = settings.get("env") {
+        if !env.is_object() {
+            return Err(AppError::localized(
+                "qwen.validation.invalid_env",
+                "Qwen Code 配置格式错误: env 必须是对象",
+                "Qwen Code config invalid: env must be an object",
+            ));
+        }
+    }
+
+    if let Some(config) = settings.get("config") {
+        if !(config.is_object() || config.is_null()) {
+            return Err(AppError::localized(
+                "qwen.validation.invalid_config",
+                "Qwen Code 配置格式错误: config 必须是对象",
+                "Qwen Code config invalid: config must be an object",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// 严格验证 Qwen Code 配置（切换供应商时使用）
+///
+/// env 为空表示使用 Qwen OAuth 登录；否则必须提供 OPENAI_API_KEY。
+pub fn validate_qwen_settings_strict(settings: &Value) -> Result<(), AppError> {
+    validate_qwen_settings(settings)?;
+
+    let env_map = json_to_env(settings)?;
+    if env_map.is_empty() {
+        return Ok(());
+    }
+
+    if env_map
+        .get("OPENAI_API_KEY")
+        .is_none_or(|key| key.trim().is_empty())
+    {
+        return Err(AppError::localized(
+            "qwen.validation.missing_api_key",
+            "Qwen Code 配置缺少必需字段: OPENAI_API_KEY",
+            "Qwen Code config missing required field: OPENAI_API_KEY",
+        ));
+    }
+
+    Ok(())
+}
+
+/// 根据 env 推断 `security.auth.selectedType`：有 API Key 走 OpenAI 兼容接口，否则走 OAuth
+pub(crate) fn detect_qwen_auth_type(env: &HashMap<String, String>) -> &'static str {
+    if env
+        .get("OPENAI_API_KEY")
+        .is_some_and(|key| !key.trim().is_empty())
+    {
+        QWEN_AUTH_OPENAI
+    } else {
+        QWEN_AUTH_OAUTH
+    }
+}
+
+/// 将供应商 config 合并进现有 settings.json，并写入认证方式
+///
+/// 只覆盖供应商提供的顶层字段，mcpServers 等其他字段保持不变。
+fn merge_qwen_settings(existing: Option<Value>, config: Option<&Value>, auth_type: &str) -> Value {
+    let mut merged = match existing {
+        Some(Value::Object(map)) => Value::Object(map),
+        _ => json!({}),
+    };
+
+    if let (Some(merged_obj), Some(config_obj)) =
+        (merged.as_object_mut(), config.and_then(Value::as_object))
+    {
+        for (key, value) in config_obj {
+            merged_obj.insert(key.clone(), value.clone());
+        }
+    }
+
+    if let Some(obj) = merged.as_object_mut() {
+        let security = obj.entry("security").or_insert_with(|| json!({}));
+        if !security.is_object() {
+            *security = json!({});
+        }
+        if let Some(security_obj) = security.as_object_mut() {
+            let auth = security_obj.entry("auth").or_insert_with(|| json!({}));
+            if !auth.is_object() {
+                *auth = json!({});
+            }
+            if let Some(auth_obj) = auth.as_object_mut() {
+                auth_obj.insert("selectedType".to_string(), json!(auth_type));
+            }
+        }
+    }
+
+    merged
+}
+
+/// 读取 Qwen Code live 配置，返回 `{ "env": {...}, "config": {...} }`
+pub fn read_qwen_live_settings() -> Result<Value, AppError> {
+    let env_path = get_qwen_env_path();
+    let settings = read_qwen_settings_json()?;
+    if !live_path_exists(&env_path) && settings.is_none() {
+        return Err(AppError::localized(
+            "qwen.live.missing",
+            "Qwen Code 配置文件不存在",
+            "Qwen Code configuration file is missing",
+        ));
+    }
+
+    let env_json = env_to_json(&read_qwen_env()?);
+    let env_obj = env_json.get("env").cloned().unwrap_or_else(|| json!({}));
+
+    Ok(json!({
+        "env": env_obj,
+        "config": settings.unwrap_or_else(|| json!({})),
+    }))
+}
+
+/// 将供应商配置写入 Qwen Code live 文件（.env 整体覆盖，settings.json 合并）
+pub fn write_qwen_live(settings_config: &Value) -> Result<(), AppError> {
+    validate_qwen_settings_strict(settings_config)?;
+
+    let env_map = json_to_env(settings_config)?;
+    let config = settings_config.get("config").filter(|v| !v.is_null());
+    let merged = merge_qwen_settings(
+        read_qwen_settings_json()?,
+        config,
+        detect_qwen_auth_type(&env_map),
+    );
+
+    write_qwen_env_atomic(&env_map)?;
+    write_json_file(&get_qwen_settings_path(), &merged)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_validation_requires_api_key_unless_env_empty() {
+        let oauth = json!({ "env": {}, "config": {} });
+        assert!(validate_qwen_settings_strict(&oauth).is_ok());
+
+        let api_key =
+            json!({ "env": { "OPENAI_API_KEY": "sk-test", "OPENAI_MODEL": "qwen3-coder-plus" } });
+        assert!(validate_qwen_settings_strict(&api_key).is_ok());
+
+        let missing = json!({ "env": { "OPENAI_MODEL": "qwen3-coder-plus" } });
+        assert!(validate_qwen_settings(&missing).is_ok());
+        assert!(validate_qwen_settings_strict(&missing).is_err());
+
+        assert!(validate_qwen_settings(&json!({ "env": "invalid" })).is_err());
+        assert!(validate_qwen_settings(&json!({ "config": [1] })).is_err());
+    }
+
+    #[test]
+    fn auth_type_follows_api_key_presence() {
+        let mut env = HashMap::new();
+        assert_eq!(detect_qwen_auth_type(&env), QWEN_AUTH_OAUTH);
+
+        env.insert("OPENAI_API_KEY".to_string(), "  ".to_string());
+        assert_eq!(detect_qwen_auth_type(&env), QWEN_AUTH_OAUTH);
+
+        env.insert("OPENAI_API_KEY".to_string(), "sk-test".to_string());
+        assert_eq!(detect_qwen_auth_type(&env), QWEN_AUTH_OPENAI);
+    }
+
+    #[test]
+    fn merge_preserves_mcp_servers_and_sets_auth_type() {
+        let existing = json!({
+            "mcpServers": { "fs": { "command": "npx" } },
+            "security": { "auth": { "selectedType": "qwen-oauth", "useExternal": false } },
+            "ui": { "theme": "dark" }
+        });
+        let config = json!({ "ui": { "theme": "light" }, "model": { "name": "qwen3-coder-plus" } });
+
+        let merged = merge_qwen_settings(Some(existing), Some(&config), QWEN_AUTH_OPENAI);
+
+        assert_eq!(merged["mcpServers"]["fs"]["command"], "npx");
+        assert_eq!(merged["ui"]["theme"], "light");
+        assert_eq!(merged["model"]["name"], "qwen3-coder-plus");
+        assert_eq!(merged["security"]["auth"]["selectedType"], "openai");
+        assert_eq!(merged["security"]["auth"]["useExternal"], false);
+    }
+
+    #[test]
+    fn merge_replaces_non_object_root() {
+        let merged = merge_qwen_settings(Some(json!([])), None, QWEN_AUTH_OAUTH);
+        assert_eq!(
+            merged,
+            json!({ "security": { "auth": { "selectedType": "qwen-oauth" } } })
+        );
+    }
+}
@@ -0,0 +1,670 @@
+//! 预算告警
+//!
+//! 在供应商级限额之外，支持全局 / 按应用的日、周、月预算（美元与 token 数），
+//! 达到预警阈值（默认 50% / 80% / 100%）时发送系统通知（窗口隐藏在托盘时同样可见），
+//! 同时向前端推送 `budget-alert` 事件用于应用内提示，并可选地推送到通用 webhook
+//! （兼容 Slack / 飞书的 JSON）。
+//!
+//! 评估由 `usage_events` 在 `usage-log-recorded` 防抖触发后调用；
+//! 同一预算的同一阈值在一个周期内只触发一次，触发记录单独落盘，
+//! 避免写 settings 表触发云同步。
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::config::{get_app_config_dir, read_json_file, write_json_file};
+use crate::database::Database;
+use crate::error::AppError;
+use crate::store::AppState;
+
+const BUDGET_ALERT_CONFIG_KEY: &str = "budget_alert_config";
+const BUDGET_ALERT_STATE_FILE: &str = "budget-alert-state.json";
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+/// 保留的告警历史条数
+const MAX_ALERT_HISTORY: usize = 200;
+
+/// 前端监听的事件名
+pub const EVENT_BUDGET_ALERT: &str = "budget-alert";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    #[default]
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetMetric {
+    Cost,
+    Tokens,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_thresholds() -> Vec<u32> {
+    vec![50, 80, 100]
+}
+
+/// 单条预算规则
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetRule {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 为空表示全局预算（所有应用合计）
+    #[serde(default)]
+    pub app_type: Option<String>,
+    #[serde(default)]
+    pub period: BudgetPeriod,
+    #[serde(default)]
+    pub limit_usd: Option<f64>,
+    #[serde(default)]
+    pub limit_tokens: Option<u64>,
+}
+
+/// 预算告警配置（存储在 settings 表）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetAlertConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub budgets: Vec<BudgetRule>,
+    /// 预警阈值（百分比）
+    #[serde(default = "default_thresholds")]
+    pub thresholds: Vec<u32>,
+    #[serde(default = "default_true")]
+    pub desktop_notification: bool,
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+impl Default for BudgetAlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            budgets: Vec::new(),
+            thresholds: default_thresholds(),
+            desktop_notification: true,
+            webhook_url: None,
+        }
+    }
+}
+
+impl BudgetAlertConfig {
+    /// 去重、排序后的有效阈值
+    fn normalized_thresholds(&self) -> Vec<u32> {
+        let mut thresholds: Vec<u32> = self.thresholds.iter().copied().filter(|t| *t > 0).collect();
+        thresholds.sort_unstable();
+        thresholds.dedup();
+        thresholds
+    }
+
+    fn webhook(&self) -> Option<&str> {
+        self.webhook_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+    }
+}
+
+/// 一次触发的预算告警
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetAlert {
+    pub budget_id: String,
+    pub budget_name: String,
+    #[serde(default)]
+    pub app_type: Option<String>,
+    pub period: BudgetPeriod,
+    pub period_key: String,
+    pub metric: BudgetMetric,
+    pub threshold: u32,
+    pub usage: f64,
+    pub limit: f64,
+    pub percent: f64,
+    pub triggered_at: i64,
+}
+
+impl BudgetAlert {
+    /// 人类可读的告警文本（用于 webhook）
+    pub fn message(&self) -> String {
+        let scope = self.app_type.as_deref().unwrap_or("all apps");
+        let (usage, limit) = match self.metric {
+            BudgetMetric::Cost => (format!("${:.2}", self.usage), format!("${:.2}", self.limit)),
+            BudgetMetric::Tokens => (
+                format!("{} tokens", self.usage as u64),
+                format!("{} tokens", self.limit as u64),
+            ),
+        };
+        format!(
+            "[CC Switch] Budget \"{}\" ({scope}, {}) reached {:.0}%: {usage} / {limit}",
+            self.budget_name, self.period_key, self.percent
+        )
+    }
+}
+
+/// 告警去重状态与历史（单独落盘）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetAlertState {
+    /// `预算ID:指标` -> 当前周期及已触发的阈值
+    #[serde(default)]
+    pub fired: BTreeMap<String, FiredThresholds>,
+    /// 最近的告警（新的在前）
+    #[serde(default)]
+    pub history: Vec<BudgetAlert>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FiredThresholds {
+    pub period_key: String,
+    #[serde(default)]
+    pub thresholds: Vec<u32>,
+}
+
+/// 某条预算在当前周期内的用量
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetUsage {
+    pub budget_id: String,
+    pub period_key: String,
+    pub cost_usd: f64,
+    pub tokens: u64,
+}
+
+/// 当前周期的标识与起点（本地时间）
+pub fn current_period(period: BudgetPeriod, today: NaiveDate) -> (String, NaiveDate) {
+    match period {
+        BudgetPeriod::Daily => (today.format("%Y-%m-%d").to_string(), today),
+        BudgetPeriod::Weekly => {
+            let monday =
+                today - ChronoDuration::days(i64::from(today.weekday().num_days_from_monday()));
+            let iso = monday.iso_week();
+            (format!("{}-W{:02}", iso.year(), iso.week()), monday)
+        }
+        BudgetPeriod::Monthly => (
+            today.format("%Y-%m").to_string(),
+            today.with_day(1).unwrap_or(today),
+        ),
+    }
+}
+
+fn local_midnight_ts(day: NaiveDate) -> Result<i64, AppError> {
+    let naive = day
+        .and_hms_opt(0, 0, 0)
+        .ok_or_else(|| AppError::Message(format!("无效日期: {day}")))?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.timestamp())
+        .ok_or_else(|| AppError::Message(format!("无法转换本地时间: {day}")))
+}
+
+/// 根据用量与已触发记录计算需要新触发的告警
+///
+/// 一次用量跨过多个阈值时只报告最高的那个，较低阈值同时记为已触发。
+/// 周期变化时自动清空该预算的已触发记录。
+pub fn plan_alerts(
+    config: &BudgetAlertConfig,
+    usages: &[BudgetUsage],
+    state: &mut BudgetAlertState,
+    now: DateTime<Local>,
+) -> Vec<BudgetAlert> {
+    let thresholds = config.normalized_thresholds();
+    let mut alerts = Vec::new();
+
+    for rule in config.budgets.iter().filter(|r| r.enabled) {
+        let Some(usage) = usages.iter().find(|u| u.budget_id == rule.id) else {
+            continue;
+        };
+        let metrics = [
+            (BudgetMetric::Cost, rule.limit_usd, usage.cost_usd),
+            (
+                BudgetMetric::Tokens,
+                rule.limit_tokens.map(|t| t as f64),
+                usage.tokens as f64,
+            ),
+        ];
+
+        for (metric, limit, used) in metrics {
+            let Some(limit) = limit.filter(|l| *l > 0.0) else {
+                continue;
+            };
+            let percent = used / limit * 100.0;
+            let key = format!("{}:{}", rule.id, metric_key(metric));
+            let fired = state.fired.entry(key).or_default();
+            if fired.period_key != usage.period_key {
+                *fired = FiredThresholds {
+                    period_key: usage.period_key.clone(),
+                    thresholds: Vec::new(),
+                };
+            }
+
+            let crossed: Vec<u32> = thresholds
+                .iter()
+                .copied()
+                .filter(|t| percent >= f64::from(*t) && !fired.thresholds.contains(t))
+                .collect();
+            let Some(&highest) = crossed.last() else {
+                continue;
+            };
+            fired.thresholds.extend(crossed);
+            fired.thresholds.sort_unstable();
+
+            alerts.push(BudgetAlert {
+                budget_id: rule.id.clone(),
+                budget_name: if rule.name.trim().is_empty() {
+                    rule.id.clone()
+                } else {
+                    rule.name.clone()
+                },
+                app_type: rule.app_type.clone(),
+                period: rule.period,
+                period_key: usage.period_key.clone(),
+                metric,
+                threshold: highest,
+                usage: used,
+                limit,
+                percent,
+                triggered_at: now.timestamp_millis(),
+            });
+        }
+    }
+
+    // 已删除的预算不再保留去重记录
+    state.fired.retain(|key, _| {
+        config
+            .budgets
+            .iter()
+            .any(|r| key.rsplit_once(':').map(|(id, _)| id) == Some(r.id.as_str()))
+    });
+
+    alerts
+}
+
+fn metric_key(metric: BudgetMetric) -> &'static str {
+    match metric {
+        BudgetMetric::Cost => "cost",
+        BudgetMetric::Tokens => "tokens",
+    }
+}
+
+/// Slack / 飞书兼容的 webhook 请求体
+///
+/// Slack 读取 `text`，飞书自定义机器人读取 `msg_type` + `content.text`，
+/// 两者都会忽略不认识的字段。
+pub fn webhook_payload(text: &str) -> serde_json::Value {
+    serde_json::json!({
+        "text": text,
+        "msg_type": "text",
+        "content": { "text": text },
+    })
+}
+
+/// 向 webhook 发送一条文本消息
+pub async fn send_webhook(url: &str, text: &str) -> Result<(), AppError> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| AppError::Message(format!("创建 HTTP 客户端失败: {e}")))?;
+    let response = client
+        .post(url)
+        .json(&webhook_payload(text))
+        .send()
+        .await
+        .map_err(|e| AppError::Message(format!("发送预算告警 webhook 失败: {e}")))?;
+    if !response.status().is_success() {
+        return Err(AppError::Message(format!(
+            "发送预算告警 webhook 失败: HTTP {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+fn state_path() -> std::path::PathBuf {
+    get_app_config_dir().join(BUDGET_ALERT_STATE_FILE)
+}
+
+/// 读取告警状态（文件缺失或损坏时返回空状态）
+pub fn load_state() -> BudgetAlertState {
+    let path = state_path();
+    if !path.exists() {
+        return BudgetAlertState::default();
+    }
+    read_json_file(&path).unwrap_or_else(|e| {
+        log::warn!("读取预算告警状态失败，将重新记录: {e}");
+        BudgetAlertState::default()
+    })
+}
+
+fn save_state(state: &BudgetAlertState) -> Result<(), AppError> {
+    write_json_file(&state_path(), state)
+}
+
+impl Database {
+    /// 获取预算告警配置
+    pub fn get_budget_alert_config(&self) -> Result<BudgetAlertConfig, AppError> {
+        match self.get_setting(BUDGET_ALERT_CONFIG_KEY)? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Message(format!("解析预算告警配置失败: {e}"))),
+            None => Ok(BudgetAlertConfig::default()),
+        }
+    }
+
+    /// 保存预算告警配置
+    pub fn save_budget_alert_config(&self, config: &BudgetAlertConfig) -> Result<(), AppError> {
+        for rule in &config.budgets {
+            if rule.id.trim().is_empty() {
+                return Err(AppError::InvalidInput("预算 ID 不能为空".to_string()));
+            }
+            if rule.limit_usd.is_none() && rule.limit_tokens.is_none() {
+                return Err(AppError::InvalidInput(format!(
+                    "预算 {} 至少需要设置金额或 token 上限",
+                    rule.id
+                )));
+            }
+        }
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Message(format!("序列化预算告警配置失败: {e}")))?;
+        self.set_setting(BUDGET_ALERT_CONFIG_KEY, &json)
+    }
+
+    /// 统计各预算在当前周期内的用量
+    pub fn get_budget_usages(
+        &self,
+        config: &BudgetAlertConfig,
+        today: NaiveDate,
+    ) -> Result<Vec<BudgetUsage>, AppError> {
+        let mut usages = Vec::with_capacity(config.budgets.len());
+        for rule in &config.budgets {
+            let (period_key, start) = current_period(rule.period, today);
+            let summary = self.get_usage_summary(
+                Some(local_midnight_ts(start)?),
+                None,
+                rule.app_type.as_deref(),
+            )?;
+            usages.push(BudgetUsage {
+                budget_id: rule.id.clone(),
+                period_key,
+                cost_usd: summary.total_cost.parse().unwrap_or(0.0),
+                tokens: summary.real_total_tokens,
+            });
+        }
+        Ok(usages)
+    }
+}
+
+/// 评估全部预算，返回本次新触发的告警并更新去重状态
+pub fn evaluate(db: &Database) -> Result<Vec<BudgetAlert>, AppError> {
+    let config = db.get_budget_alert_config()?;
+    if !config.enabled || config.budgets.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let now = Local::now();
+    let usages = db.get_budget_usages(&config, now.date_naive())?;
+    let mut state = load_state();
+    let alerts = plan_alerts(&config, &usages, &mut state, now);
+    if alerts.is_empty() {
+        return Ok(alerts);
+    }
+
+    for alert in alerts.iter().rev() {
+        state.history.insert(0, alert.clone());
+    }
+    state.history.truncate(MAX_ALERT_HISTORY);
+    save_state(&state)?;
+    Ok(alerts)
+}
+
+/// 在 `usage-log-recorded` 之后调用：评估预算并分发告警
+///
+/// 运行在 `usage_events` 的防抖线程中，失败只记录日志。
+pub fn evaluate_and_dispatch(handle: &AppHandle) {
+    let Some(state) = handle.try_state::<AppState>() else {
+        return;
+    };
+    let db = state.db.clone();
+
+    let alerts = match evaluate(&db) {
+        Ok(alerts) => alerts,
+        Err(e) => {
+            log::warn!("评估预算告警失败: {e}");
+            return;
+        }
+    };
+    if alerts.is_empty() {
+        return;
+    }
+
+    let config = match db.get_budget_alert_config() {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!("读取预算告警配置失败: {e}");
+            return;
+        }
+    };
+
+    for alert in &alerts {
+        log::info!("{}", alert.message());
+        if config.desktop_notification {
+            // 阈值已在 plan_alerts 中标记为已触发，必须走系统通知，不能依赖前端窗口可见
+            if let Err(e) = handle
+                .notification()
+                .builder()
+                .title("CC Switch")
+                .body(alert.message())
+                .show()
+            {
+                log::warn!("发送预算告警系统通知失败: {e}");
+            }
+            if let Err(e) = handle.emit(EVENT_BUDGET_ALERT, alert) {
+                log::warn!("emit {EVENT_BUDGET_ALERT} 失败: {e}");
+            }
+        }
+    }
+
+    if let Some(url) = config.webhook().map(str::to_string) {
+        let text = alerts
+            .iter()
+            .map(BudgetAlert::message)
+            .collect::<Vec<_>>()
+            .join("\n");
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = send_webhook(&url, &text).await {
+                log::warn!("{e}");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    fn rule(id: &str, limit_usd: Option<f64>, limit_tokens: Option<u64>) -> BudgetRule {
+        BudgetRule {
+            id: id.to_string(),
+            name: String::new(),
+            enabled: true,
+            app_type: None,
+            period: BudgetPeriod::Daily,
+            limit_usd,
+            limit_tokens,
+        }
+    }
+
+    fn usage(id: &str, period_key: &str, cost_usd: f64, tokens: u64) -> BudgetUsage {
+        BudgetUsage {
+            budget_id: id.to_string(),
+            period_key: period_key.to_string(),
+            cost_usd,
+            tokens,
+        }
+    }
+
+    #[test]
+    fn current_period_keys_and_starts() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        assert_eq!(
+            current_period(BudgetPeriod::Daily, day),
+            ("2026-10-15".to_string(), day)
+        );
+        assert_eq!(
+            current_period(BudgetPeriod::Weekly, day),
+            (
+                "2026-W42".to_string(),
+                NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()
+            )
+        );
+        assert_eq!(
+            current_period(BudgetPeriod::Monthly, day),
+            (
+                "2026-10".to_string(),
+                NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn each_threshold_fires_once_per_period() {
+        let config = BudgetAlertConfig {
+            enabled: true,
+            budgets: vec![rule("daily", Some(10.0), Some(1000))],
+            ..Default::default()
+        };
+        let mut state = BudgetAlertState::default();
+        let now = Local::now();
+
+        // 跨过 50% 与 80%：只报告 80%
+        let alerts = plan_alerts(
+            &config,
+            &[usage("daily", "2026-10-15", 8.5, 100)],
+            &mut state,
+            now,
+        );
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].metric, BudgetMetric::Cost);
+        assert_eq!(alerts[0].threshold, 80);
+
+        // 同周期内用量继续增长但未到 100%：不重复触发
+        let alerts = plan_alerts(
+            &config,
+            &[usage("daily", "2026-10-15", 9.0, 100)],
+            &mut state,
+            now,
+        );
+        assert!(alerts.is_empty());
+
+        // token 指标独立去重
+        let alerts = plan_alerts(
+            &config,
+            &[usage("daily", "2026-10-15", 10.0, 600)],
+            &mut state,
+            now,
+        );
+        let fired: Vec<(BudgetMetric, u32)> =
+            alerts.iter().map(|a| (a.metric, a.threshold)).collect();
+        assert_eq!(
+            fired,
+            vec![(BudgetMetric::Cost, 100), (BudgetMetric::Tokens, 50)]
+        );
+
+        // 新周期重新计数
+        let alerts = plan_alerts(
+            &config,
+            &[usage("daily", "2026-10-16", 5.0, 0)],
+            &mut state,
+            now,
+        );
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].threshold, 50);
+    }
+
+    #[test]
+    fn removed_budgets_drop_their_dedup_state() {
+        let mut config = BudgetAlertConfig {
+            enabled: true,
+            budgets: vec![rule("a", Some(1.0), None)],
+            ..Default::default()
+        };
+        let mut state = BudgetAlertState::default();
+        plan_alerts(
+            &config,
+            &[usage("a", "2026-10", 1.0, 0)],
+            &mut state,
+            Local::now(),
+        );
+        assert!(state.fired.contains_key("a:cost"));
+
+        config.budgets.clear();
+        plan_alerts(&config, &[], &mut state, Local::now());
+        assert!(state.fired.is_empty());
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_slack_and_feishu_compatible_body() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind stub");
+        let addr = listener.local_addr().expect("stub addr");
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut buf).expect("read request");
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|l| {
+                            let (name, value) = l.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .expect("write response");
+            let text = String::from_utf8_lossy(&request).into_owned();
+            let body = text.split_once("\r\n\r\n").map(|(_, b)| b.to_string());
+            body.unwrap_or_default()
+        });
+
+        send_webhook(&format!("http://{addr}/hook"), "budget hit")
+            .await
+            .expect("send webhook");
+
+        let body: serde_json::Value =
+            serde_json::from_str(&server.join().expect("stub thread")).expect("json body");
+        assert_eq!(body["text"], "budget hit");
+        assert_eq!(body["msg_type"], "text");
+        assert_eq!(body["content"]["text"], "budget hit");
+    }
+}
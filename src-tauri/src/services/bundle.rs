@@ -0,0 +1,1309 @@
+//! 团队配置包
+//!
+//! 团队把供应商（不含密钥）、MCP 服务器、提示词、Skill 仓库引用、模型定价与代理参数发布为
+//! 一个带版本号的 JSON 文件。成员导入前先预览差异，并为需要密钥的供应商填写自己的 API Key；
+//! 之后再导入新版本时，以上次导入的内容为基线，与本地修改做三方合并：
+//!
+//! - 本地未改动的条目直接更新为新版本
+//! - 新版本未改动的条目保留本地修改
+//! - 双方都改动时按字段合并，同一字段的冲突保留本地值并在预览中列出
+//!
+//! 配置包可由发布者用 Ed25519 私钥签名；未经受信任发布者签名的配置包需要成员显式确认才能导入。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::app_config::{AppType, McpServer};
+use crate::config::write_text_file;
+use crate::error::AppError;
+use crate::mcp::{find_secret_refs, SecretRefKind};
+use crate::prompt::Prompt;
+use crate::provider::Provider;
+use crate::proxy::types::AppProxyConfig;
+use crate::proxy::usage::calculator::PricingTier;
+use crate::services::skill::SkillRepo;
+use crate::services::text_diff::unified_diff;
+use crate::services::{McpService, PromptService, ProviderService};
+use crate::signing::{self, canonical_json, SignatureStatus};
+use crate::store::AppState;
+
+pub const BUNDLE_FORMAT: &str = "cc-switch-bundle";
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// 视为密钥的字段名（按结尾匹配，忽略大小写）
+pub(crate) static SECRET_KEY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)(api[_-]?key|auth[_-]?token|access[_-]?token|refresh[_-]?token|bearer[_-]?token|secret|password|authorization)$|^token$",
+    )
+    .expect("Invalid secret key regex")
+});
+
+// ============================================================================
+// 文件格式
+// ============================================================================
+
+/// 配置包中的供应商（密钥字段已清空）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleProvider {
+    pub app: String,
+    pub provider: Value,
+    /// 导入时需要成员自行填写的字段（JSON Pointer，相对于 provider）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_secrets: Vec<String>,
+}
+
+/// 配置包中的提示词（不含启用状态）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundlePrompt {
+    pub app: String,
+    pub prompt: Value,
+}
+
+/// 配置包中的模型定价
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleModelPricing {
+    pub model_id: String,
+    pub display_name: String,
+    pub input_cost_per_million: String,
+    pub output_cost_per_million: String,
+    pub cache_read_cost_per_million: String,
+    pub cache_creation_cost_per_million: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pricing_tiers: Vec<PricingTier>,
+}
+
+/// 配置包签名
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSignature {
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// 团队配置包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamBundle {
+    pub format: String,
+    pub format_version: u32,
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<BundleProvider>,
+    /// MCP 服务器；密钥以 `${secret:...}` 占位符发布，由成员在本地密钥库中填写
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mcp_servers: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prompts: Vec<BundlePrompt>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skill_repos: Vec<SkillRepo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_pricing: Vec<BundleModelPricing>,
+    /// 应用级代理参数（不含代理开关本身）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proxy_settings: Vec<Value>,
+    /// 发布者签名，覆盖除本字段外的全部内容（规范化 JSON）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<BundleSignature>,
+}
+
+// ============================================================================
+// 命令参数与结果
+// ============================================================================
+
+/// 导出选项：列出要发布的内容
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleExportOptions {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub publisher: Option<String>,
+    /// 应用 ID -> 供应商 ID
+    #[serde(default)]
+    pub providers: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+    /// 应用 ID -> 提示词 ID
+    #[serde(default)]
+    pub prompts: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub skill_repos: bool,
+    #[serde(default)]
+    pub model_pricing: Vec<String>,
+    /// 应用 ID
+    #[serde(default)]
+    pub proxy_settings: Vec<String>,
+    /// 发布者私钥（PKCS#8）；提供时对配置包签名，不会被保存
+    #[serde(default)]
+    pub signing_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BundleItemKind {
+    Provider,
+    McpServer,
+    Prompt,
+    SkillRepo,
+    ModelPricing,
+    ProxySettings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BundleItemAction {
+    Add,
+    Update,
+    Remove,
+    Unchanged,
+    /// 存在无法自动合并的修改，保留本地内容
+    Conflict,
+}
+
+/// 单个条目的导入变更
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleItemChange {
+    pub kind: BundleItemKind,
+    pub key: String,
+    pub action: BundleItemAction,
+    /// 本地内容 -> 导入后内容（密钥字段不出现在差异中）
+    pub diff: String,
+    /// 冲突字段（JSON Pointer），这些字段保留本地值
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+    /// 仍需填写的密钥：供应商为 JSON Pointer，MCP 为 `secret:<name>`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_secrets: Vec<String>,
+}
+
+/// 导入预览
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundlePreview {
+    pub bundle_id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    /// 上次导入的版本；首次导入为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_version: Option<String>,
+    pub signature: SignatureStatus,
+    pub changes: Vec<BundleItemChange>,
+}
+
+/// 导入时由成员填写的密钥
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSecretInput {
+    /// 供应商条目 key（`<app>/<id>`）-> JSON Pointer -> 值
+    #[serde(default)]
+    pub providers: BTreeMap<String, BTreeMap<String, String>>,
+    /// 写入本地 MCP 密钥库的值
+    #[serde(default)]
+    pub mcp_secrets: BTreeMap<String, String>,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleImportResult {
+    pub bundle_id: String,
+    pub version: String,
+    pub changes: Vec<BundleItemChange>,
+    /// 应用失败的条目；存在失败时不更新合并基线，修复后可重新导入
+    pub errors: Vec<String>,
+}
+
+/// 已导入的配置包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleImportRecord {
+    pub bundle_id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    pub imported_at: i64,
+}
+
+// ============================================================================
+// JSON 工具
+// ============================================================================
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// 清空密钥字段，返回其 JSON Pointer
+fn strip_secrets(value: &mut Value, path: &str, found: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                let child_path = format!("{path}/{}", escape_pointer(key));
+                match child {
+                    Value::String(s) if SECRET_KEY_RE.is_match(key) => {
+                        if !s.is_empty() {
+                            s.clear();
+                            found.push(child_path);
+                        }
+                    }
+                    _ => strip_secrets(child, &child_path, found),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter_mut().enumerate() {
+                strip_secrets(child, &format!("{path}/{index}"), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 把 MCP env / headers 中内联的密钥替换为本地密钥库占位符
+fn strip_mcp_secrets(server: &mut Value) {
+    let id = server
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let Some(spec) = server.get_mut("server") else {
+        return;
+    };
+    for field in ["env", "headers"] {
+        let Some(map) = spec.get_mut(field).and_then(Value::as_object_mut) else {
+            continue;
+        };
+        for (key, value) in map.iter_mut() {
+            let Some(raw) = value.as_str() else {
+                continue;
+            };
+            if !SECRET_KEY_RE.is_match(key) || raw.is_empty() || raw.contains("${") {
+                continue;
+            }
+            let name: String = format!("{id}.{key}")
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            let prefix = if raw.starts_with("Bearer ") {
+                "Bearer "
+            } else {
+                ""
+            };
+            *value = Value::String(format!("{prefix}${{secret:{name}}}"));
+        }
+    }
+}
+
+fn remove_keys(mut value: Value, keys: &[&str]) -> Value {
+    if let Some(map) = value.as_object_mut() {
+        for key in keys {
+            map.remove(*key);
+        }
+    }
+    value
+}
+
+/// 三方合并 JSON；冲突时保留本地值并记录路径
+fn merge_value(
+    base: Option<&Value>,
+    ours: &Value,
+    theirs: &Value,
+    path: &str,
+    conflicts: &mut Vec<String>,
+) -> Value {
+    if ours == theirs {
+        return ours.clone();
+    }
+    match base {
+        Some(b) if b == ours => return theirs.clone(),
+        Some(b) if b == theirs => return ours.clone(),
+        _ => {}
+    }
+
+    if let (Value::Object(o), Value::Object(t)) = (ours, theirs) {
+        let b = base.and_then(Value::as_object);
+        let keys: BTreeSet<&String> = o.keys().chain(t.keys()).collect();
+        let mut merged = Map::new();
+        for key in keys {
+            let child_path = format!("{path}/{}", escape_pointer(key));
+            let bv = b.and_then(|m| m.get(key));
+            match (o.get(key), t.get(key)) {
+                (Some(ov), Some(tv)) => {
+                    merged.insert(key.clone(), merge_value(bv, ov, tv, &child_path, conflicts));
+                }
+                // 新版本删除了该字段：本地未改动则跟随删除
+                (Some(ov), None) => match bv {
+                    Some(bv) if bv == ov => {}
+                    Some(_) => {
+                        conflicts.push(child_path);
+                        merged.insert(key.clone(), ov.clone());
+                    }
+                    None => {
+                        merged.insert(key.clone(), ov.clone());
+                    }
+                },
+                // 本地删除了该字段：新版本未改动则保持删除
+                (None, Some(tv)) => match bv {
+                    None => {
+                        merged.insert(key.clone(), tv.clone());
+                    }
+                    Some(bv) if bv == tv => {}
+                    Some(_) => conflicts.push(child_path),
+                },
+                (None, None) => {}
+            }
+        }
+        return Value::Object(merged);
+    }
+
+    conflicts.push(if path.is_empty() {
+        "/".to_string()
+    } else {
+        path.to_string()
+    });
+    ours.clone()
+}
+
+fn blank_pointers(value: &Value, pointers: &[String]) -> Value {
+    let mut value = value.clone();
+    for pointer in pointers {
+        if let Some(Value::String(s)) = value.pointer_mut(pointer) {
+            s.clear();
+        }
+    }
+    value
+}
+
+fn pretty(value: Option<&Value>) -> String {
+    value
+        .map(|v| serde_json::to_string_pretty(v).unwrap_or_default() + "\n")
+        .unwrap_or_default()
+}
+
+fn split_app_key(key: &str) -> Result<(AppType, &str), AppError> {
+    let (app, id) = key
+        .split_once('/')
+        .ok_or_else(|| AppError::InvalidInput(format!("无效的配置包条目: {key}")))?;
+    Ok((AppType::from_str(app)?, id))
+}
+
+fn from_value<T: serde::de::DeserializeOwned>(value: Value) -> Result<T, AppError> {
+    serde_json::from_value(value).map_err(|e| AppError::JsonSerialize { source: e })
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value, AppError> {
+    serde_json::to_value(value).map_err(|e| AppError::JsonSerialize { source: e })
+}
+
+// ============================================================================
+// 条目
+// ============================================================================
+
+type ItemKey = (BundleItemKind, String);
+
+#[derive(Debug, Clone)]
+struct BundleItem {
+    value: Value,
+    required_secrets: Vec<String>,
+}
+
+/// 供应商中与个人使用习惯相关、不参与发布的字段
+const PROVIDER_LOCAL_FIELDS: [&str; 3] = ["createdAt", "sortIndex", "inFailoverQueue"];
+const PROMPT_LOCAL_FIELDS: [&str; 3] = ["enabled", "createdAt", "updatedAt"];
+const PROXY_LOCAL_FIELDS: [&str; 2] = ["enabled", "autoFailoverEnabled"];
+
+fn bundle_items(bundle: &TeamBundle) -> Result<BTreeMap<ItemKey, BundleItem>, AppError> {
+    let mut items = BTreeMap::new();
+    let mut insert = |kind, key: String, value: Value, required_secrets: Vec<String>| {
+        items.insert(
+            (kind, key),
+            BundleItem {
+                value,
+                required_secrets,
+            },
+        );
+    };
+
+    for entry in &bundle.providers {
+        let app = AppType::from_str(&entry.app)?;
+        let id = entry
+            .provider
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AppError::InvalidInput("配置包中的供应商缺少 id".to_string()))?;
+        insert(
+            BundleItemKind::Provider,
+            format!("{}/{id}", app.as_str()),
+            remove_keys(entry.provider.clone(), &PROVIDER_LOCAL_FIELDS),
+            entry.required_secrets.clone(),
+        );
+    }
+    for server in &bundle.mcp_servers {
+        let id = server
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AppError::InvalidInput("配置包中的 MCP 服务器缺少 id".to_string()))?;
+        insert(
+            BundleItemKind::McpServer,
+            id.to_string(),
+            server.clone(),
+            Vec::new(),
+        );
+    }
+    for entry in &bundle.prompts {
+        let app = AppType::from_str(&entry.app)?;
+        let id = entry
+            .prompt
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AppError::InvalidInput("配置包中的提示词缺少 id".to_string()))?;
+        insert(
+            BundleItemKind::Prompt,
+            format!("{}/{id}", app.as_str()),
+            remove_keys(entry.prompt.clone(), &PROMPT_LOCAL_FIELDS),
+            Vec::new(),
+        );
+    }
+    for repo in &bundle.skill_repos {
+        insert(
+            BundleItemKind::SkillRepo,
+            format!("{}/{}", repo.owner, repo.name),
+            to_value(repo)?,
+            Vec::new(),
+        );
+    }
+    for pricing in &bundle.model_pricing {
+        insert(
+            BundleItemKind::ModelPricing,
+            pricing.model_id.clone(),
+            to_value(pricing)?,
+            Vec::new(),
+        );
+    }
+    for config in &bundle.proxy_settings {
+        let app = config
+            .get("appType")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AppError::InvalidInput("配置包中的代理参数缺少 appType".to_string()))?;
+        let app = AppType::from_str(app)?;
+        insert(
+            BundleItemKind::ProxySettings,
+            app.as_str().to_string(),
+            remove_keys(config.clone(), &PROXY_LOCAL_FIELDS),
+            Vec::new(),
+        );
+    }
+    Ok(items)
+}
+
+/// 规范化后的本地条目（与配置包同样去除个人字段，但保留密钥）
+fn local_value(
+    state: &AppState,
+    kind: BundleItemKind,
+    key: &str,
+) -> Result<Option<Value>, AppError> {
+    Ok(match kind {
+        BundleItemKind::Provider => {
+            let (app, id) = split_app_key(key)?;
+            state
+                .db
+                .get_provider_by_id(id, app.as_str())?
+                .map(|p| to_value(&p).map(|v| remove_keys(v, &PROVIDER_LOCAL_FIELDS)))
+                .transpose()?
+        }
+        BundleItemKind::McpServer => state
+            .db
+            .get_all_mcp_servers()?
+            .get(key)
+            .map(to_value)
+            .transpose()?,
+        BundleItemKind::Prompt => {
+            let (app, id) = split_app_key(key)?;
+            state
+                .db
+                .get_prompts(app.as_str())?
+                .get(id)
+                .map(|p| to_value(p).map(|v| remove_keys(v, &PROMPT_LOCAL_FIELDS)))
+                .transpose()?
+        }
+        BundleItemKind::SkillRepo => state
+            .db
+            .get_skill_repos()?
+            .iter()
+            .find(|r| format!("{}/{}", r.owner, r.name) == key)
+            .map(to_value)
+            .transpose()?,
+        BundleItemKind::ModelPricing => state
+            .db
+            .get_bundle_model_pricing(key)?
+            .as_ref()
+            .map(to_value)
+            .transpose()?,
+        BundleItemKind::ProxySettings => {
+            let config = futures::executor::block_on(state.db.get_proxy_config_for_app(key))?;
+            Some(remove_keys(to_value(&config)?, &PROXY_LOCAL_FIELDS))
+        }
+    })
+}
+
+struct PlannedChange {
+    kind: BundleItemKind,
+    key: String,
+    action: BundleItemAction,
+    /// 导入后的内容（含密钥，不对外展示）
+    result: Option<Value>,
+    change: BundleItemChange,
+}
+
+pub struct BundleService;
+
+impl BundleService {
+    /// 导出配置包到文件
+    pub fn export(
+        state: &AppState,
+        options: &BundleExportOptions,
+        path: &Path,
+    ) -> Result<TeamBundle, AppError> {
+        let mut bundle = Self::build(state, options)?;
+        if let Some(key) = options
+            .signing_key
+            .as_deref()
+            .filter(|k| !k.trim().is_empty())
+        {
+            Self::sign(&mut bundle, key)?;
+        }
+        let json = serde_json::to_string_pretty(&bundle)
+            .map_err(|e| AppError::JsonSerialize { source: e })?;
+        write_text_file(path, &format!("{json}\n"))?;
+        log::info!(
+            "已导出配置包 {} v{} 到 {}",
+            bundle.id,
+            bundle.version,
+            path.display()
+        );
+        Ok(bundle)
+    }
+
+    fn build(state: &AppState, options: &BundleExportOptions) -> Result<TeamBundle, AppError> {
+        for (label, value) in [
+            ("ID", &options.id),
+            ("名称", &options.name),
+            ("版本", &options.version),
+        ] {
+            if value.trim().is_empty() {
+                return Err(AppError::InvalidInput(format!("配置包{label}不能为空")));
+            }
+        }
+
+        let mut bundle = TeamBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            format_version: BUNDLE_FORMAT_VERSION,
+            id: options.id.trim().to_string(),
+            name: options.name.trim().to_string(),
+            version: options.version.trim().to_string(),
+            description: options.description.clone(),
+            publisher: options.publisher.clone(),
+            created_at: chrono::Utc::now().timestamp(),
+            providers: Vec::new(),
+            mcp_servers: Vec::new(),
+            prompts: Vec::new(),
+            skill_repos: Vec::new(),
+            model_pricing: Vec::new(),
+            proxy_settings: Vec::new(),
+            signature: None,
+        };
+
+        for (app_key, ids) in &options.providers {
+            let app = AppType::from_str(app_key)?;
+            for id in ids {
+                let provider = state
+                    .db
+                    .get_provider_by_id(id, app.as_str())?
+                    .ok_or_else(|| AppError::InvalidInput(format!("供应商不存在: {id}")))?;
+                let mut value = remove_keys(to_value(&provider)?, &PROVIDER_LOCAL_FIELDS);
+                let mut required_secrets = Vec::new();
+                strip_secrets(&mut value, "", &mut required_secrets);
+                bundle.providers.push(BundleProvider {
+                    app: app.as_str().to_string(),
+                    provider: value,
+                    required_secrets,
+                });
+            }
+        }
+
+        let servers = state.db.get_all_mcp_servers()?;
+        for id in &options.mcp_servers {
+            let server = servers
+                .get(id)
+                .ok_or_else(|| AppError::InvalidInput(format!("MCP 服务器不存在: {id}")))?;
+            let mut value = to_value(server)?;
+            strip_mcp_secrets(&mut value);
+            bundle.mcp_servers.push(value);
+        }
+
+        for (app_key, ids) in &options.prompts {
+            let app = AppType::from_str(app_key)?;
+            let prompts = state.db.get_prompts(app.as_str())?;
+            for id in ids {
+                let prompt = prompts
+                    .get(id)
+                    .ok_or_else(|| AppError::InvalidInput(format!("提示词不存在: {id}")))?;
+                bundle.prompts.push(BundlePrompt {
+                    app: app.as_str().to_string(),
+                    prompt: remove_keys(to_value(prompt)?, &PROMPT_LOCAL_FIELDS),
+                });
+            }
+        }
+
+        if options.skill_repos {
+            bundle.skill_repos = state.db.get_skill_repos()?;
+        }
+
+        for model_id in &options.model_pricing {
+            let pricing = state
+                .db
+                .get_bundle_model_pricing(model_id)?
+                .ok_or_else(|| AppError::InvalidInput(format!("模型定价不存在: {model_id}")))?;
+            bundle.model_pricing.push(pricing);
+        }
+
+        for app_key in &options.proxy_settings {
+            let app = AppType::from_str(app_key)?;
+            let config =
+                futures::executor::block_on(state.db.get_proxy_config_for_app(app.as_str()))?;
+            bundle
+                .proxy_settings
+                .push(remove_keys(to_value(&config)?, &PROXY_LOCAL_FIELDS));
+        }
+
+        Ok(bundle)
+    }
+
+    /// 签名覆盖去掉 signature 字段后的规范化 JSON
+    fn sign(bundle: &mut TeamBundle, private_key: &str) -> Result<(), AppError> {
+        bundle.signature = None;
+        let payload = canonical_json(&to_value(bundle)?);
+        let (public_key, signature) = signing::sign(private_key, payload.as_bytes())?;
+        bundle.signature = Some(BundleSignature {
+            public_key,
+            signature,
+        });
+        Ok(())
+    }
+
+    /// 读取并校验配置包文件
+    pub fn read(path: &Path) -> Result<TeamBundle, AppError> {
+        let content = std::fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+        Self::parse(&content)
+    }
+
+    /// 解析配置包；带签名时校验完整性，被篡改的配置包直接拒绝
+    pub fn parse(content: &str) -> Result<TeamBundle, AppError> {
+        let bundle = Self::parse_unverified(content)?;
+        if let Some(signature) = &bundle.signature {
+            let mut value: Value = serde_json::from_str(content)
+                .map_err(|e| AppError::InvalidInput(format!("无法解析配置包: {e}")))?;
+            if let Some(map) = value.as_object_mut() {
+                map.remove("signature");
+            }
+            signing::verify(
+                &signature.public_key,
+                canonical_json(&value).as_bytes(),
+                &signature.signature,
+            )
+            .map_err(|e| AppError::InvalidInput(format!("配置包{e}")))?;
+        }
+        Ok(bundle)
+    }
+
+    /// 信任状态；调用方需确保配置包已经过 [`Self::parse`] 校验
+    fn signature_status(
+        state: &AppState,
+        bundle: &TeamBundle,
+    ) -> Result<SignatureStatus, AppError> {
+        match &bundle.signature {
+            Some(signature) => {
+                signing::status_for_key(&state.db.get_trusted_publishers()?, &signature.public_key)
+            }
+            None => Ok(SignatureStatus::Unsigned),
+        }
+    }
+
+    fn parse_unverified(content: &str) -> Result<TeamBundle, AppError> {
+        let bundle: TeamBundle = serde_json::from_str(content)
+            .map_err(|e| AppError::InvalidInput(format!("无法解析配置包: {e}")))?;
+        if bundle.format != BUNDLE_FORMAT {
+            return Err(AppError::InvalidInput(format!(
+                "不是 CC Switch 配置包: {}",
+                bundle.format
+            )));
+        }
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(AppError::InvalidInput(format!(
+                "配置包格式版本 {} 过新，请升级 CC Switch",
+                bundle.format_version
+            )));
+        }
+        if bundle.id.trim().is_empty() || bundle.version.trim().is_empty() {
+            return Err(AppError::InvalidInput(
+                "配置包缺少 id 或 version".to_string(),
+            ));
+        }
+        Ok(bundle)
+    }
+
+    pub fn list_imports(state: &AppState) -> Result<Vec<BundleImportRecord>, AppError> {
+        state.db.list_bundle_imports()
+    }
+
+    /// 预览导入变更（不写入任何内容）
+    pub fn preview(
+        state: &AppState,
+        bundle: &TeamBundle,
+        secrets: &BundleSecretInput,
+    ) -> Result<BundlePreview, AppError> {
+        let signature = Self::signature_status(state, bundle)?;
+        let previous = Self::previous(state, &bundle.id)?;
+        let changes = Self::plan(state, bundle, previous.as_ref(), secrets)?
+            .into_iter()
+            .map(|c| c.change)
+            .collect();
+        Ok(BundlePreview {
+            bundle_id: bundle.id.clone(),
+            name: bundle.name.clone(),
+            version: bundle.version.clone(),
+            publisher: bundle.publisher.clone(),
+            previous_version: previous.map(|p| p.version),
+            signature,
+            changes,
+        })
+    }
+
+    /// 导入配置包：应用合并结果并记录为下次合并的基线
+    ///
+    /// 未经受信任发布者签名的配置包需要 `accept_untrusted` 明确确认。
+    pub fn import(
+        state: &AppState,
+        bundle: &TeamBundle,
+        secrets: &BundleSecretInput,
+        accept_untrusted: bool,
+    ) -> Result<BundleImportResult, AppError> {
+        if !accept_untrusted && !Self::signature_status(state, bundle)?.is_trusted() {
+            return Err(AppError::InvalidInput(
+                "配置包未经受信任的发布者签名，请在预览中确认来源后再导入".to_string(),
+            ));
+        }
+
+        for (name, value) in &secrets.mcp_secrets {
+            if !value.is_empty() {
+                McpService::set_secret(
+                    state,
+                    name,
+                    value,
+                    Some(&format!("来自配置包 {}", bundle.name)),
+                )?;
+            }
+        }
+
+        let previous = Self::previous(state, &bundle.id)?;
+        let plan = Self::plan(state, bundle, previous.as_ref(), secrets)?;
+
+        let mut changes = Vec::new();
+        let mut errors = Vec::new();
+        for planned in plan {
+            let applies = matches!(
+                planned.action,
+                BundleItemAction::Add | BundleItemAction::Update | BundleItemAction::Remove
+            );
+            if !applies {
+                changes.push(planned.change);
+                continue;
+            }
+            match Self::apply_item(state, planned.kind, &planned.key, planned.result) {
+                Ok(()) => changes.push(planned.change),
+                Err(e) => {
+                    log::warn!("导入配置包条目 {} 失败: {e}", planned.key);
+                    errors.push(format!("{}: {e}", planned.key));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            let content =
+                serde_json::to_string(bundle).map_err(|e| AppError::JsonSerialize { source: e })?;
+            state.db.save_bundle_import(
+                &BundleImportRecord {
+                    bundle_id: bundle.id.clone(),
+                    name: bundle.name.clone(),
+                    version: bundle.version.clone(),
+                    publisher: bundle.publisher.clone(),
+                    imported_at: chrono::Utc::now().timestamp(),
+                },
+                &content,
+            )?;
+        }
+
+        log::info!(
+            "已导入配置包 {} v{}（失败 {} 项）",
+            bundle.id,
+            bundle.version,
+            errors.len()
+        );
+        Ok(BundleImportResult {
+            bundle_id: bundle.id.clone(),
+            version: bundle.version.clone(),
+            changes,
+            errors,
+        })
+    }
+
+    fn previous(state: &AppState, bundle_id: &str) -> Result<Option<TeamBundle>, AppError> {
+        match state.db.get_bundle_import_content(bundle_id)? {
+            // 基线来自本机数据库，无需再次校验签名
+            Some(content) => match Self::parse_unverified(&content) {
+                Ok(bundle) => Ok(Some(bundle)),
+                Err(e) => {
+                    log::warn!("上次导入的配置包 {bundle_id} 无法解析，按首次导入处理: {e}");
+                    Ok(None)
+                }
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn plan(
+        state: &AppState,
+        bundle: &TeamBundle,
+        previous: Option<&TeamBundle>,
+        secrets: &BundleSecretInput,
+    ) -> Result<Vec<PlannedChange>, AppError> {
+        let theirs = bundle_items(bundle)?;
+        let base = match previous {
+            Some(previous) => bundle_items(previous)?,
+            None => BTreeMap::new(),
+        };
+        let local_secret_names: BTreeSet<String> =
+            state.db.get_mcp_secret_values()?.into_keys().collect();
+
+        let keys: BTreeSet<&ItemKey> = theirs.keys().chain(base.keys()).collect();
+        let mut plan = Vec::new();
+        for item_key in keys {
+            let (kind, key) = item_key;
+            let their_item = theirs.get(item_key);
+            let base_item = base.get(item_key);
+
+            // 密钥字段不参与比较与差异展示
+            let secret_pointers: Vec<String> = their_item
+                .into_iter()
+                .chain(base_item)
+                .flat_map(|i| i.required_secrets.iter().cloned())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            let local_raw = local_value(state, *kind, key)?;
+            let local = local_raw
+                .as_ref()
+                .map(|v| blank_pointers(v, &secret_pointers));
+
+            let mut conflicts = Vec::new();
+            let (action, merged) = match (their_item, &local) {
+                (Some(t), None) => match base_item {
+                    None => (BundleItemAction::Add, Some(t.value.clone())),
+                    // 本地已删除：新版本未改动则保持删除，否则提示冲突
+                    Some(b) if b.value == t.value => (BundleItemAction::Unchanged, None),
+                    Some(_) => {
+                        conflicts.push("/".to_string());
+                        (BundleItemAction::Conflict, None)
+                    }
+                },
+                (None, Some(o)) => match base_item {
+                    Some(b) if b.value == *o => (BundleItemAction::Remove, None),
+                    _ => {
+                        conflicts.push("/".to_string());
+                        (BundleItemAction::Conflict, Some(o.clone()))
+                    }
+                },
+                (Some(t), Some(o)) => {
+                    let merged =
+                        merge_value(base_item.map(|b| &b.value), o, &t.value, "", &mut conflicts);
+                    let action = if merged != *o {
+                        BundleItemAction::Update
+                    } else if conflicts.is_empty() {
+                        BundleItemAction::Unchanged
+                    } else {
+                        BundleItemAction::Conflict
+                    };
+                    (action, Some(merged))
+                }
+                (None, None) => continue,
+            };
+
+            let diff = match action {
+                BundleItemAction::Add | BundleItemAction::Update | BundleItemAction::Remove => {
+                    let new = if action == BundleItemAction::Remove {
+                        None
+                    } else {
+                        merged.as_ref()
+                    };
+                    let old_label = if local.is_some() {
+                        "local"
+                    } else {
+                        "/dev/null"
+                    };
+                    let new_label = if new.is_some() { "bundle" } else { "/dev/null" };
+                    unified_diff(&pretty(local.as_ref()), &pretty(new), old_label, new_label)
+                }
+                _ => String::new(),
+            };
+
+            // 回填密钥：成员填写的值优先，其次保留本地已有的值
+            let mut missing_secrets = Vec::new();
+            let mut result = merged;
+            if let (Some(result), Some(t)) = (result.as_mut(), their_item) {
+                if *kind == BundleItemKind::Provider {
+                    let provided = secrets.providers.get(key);
+                    for pointer in &t.required_secrets {
+                        let value = provided
+                            .and_then(|m| m.get(pointer))
+                            .filter(|v| !v.is_empty())
+                            .cloned()
+                            .or_else(|| {
+                                local_raw
+                                    .as_ref()
+                                    .and_then(|l| l.pointer(pointer))
+                                    .and_then(Value::as_str)
+                                    .filter(|v| !v.is_empty())
+                                    .map(str::to_string)
+                            });
+                        match (value, result.pointer_mut(pointer)) {
+                            (Some(value), Some(slot)) => *slot = Value::String(value),
+                            _ => missing_secrets.push(pointer.clone()),
+                        }
+                    }
+                }
+                if *kind == BundleItemKind::McpServer {
+                    if let Some(spec) = result.get("server") {
+                        for r in find_secret_refs(spec) {
+                            let known = local_secret_names.contains(&r.name)
+                                || secrets
+                                    .mcp_secrets
+                                    .get(&r.name)
+                                    .is_some_and(|v| !v.is_empty());
+                            if r.kind == SecretRefKind::Secret && !known {
+                                missing_secrets.push(format!("secret:{}", r.name));
+                            }
+                        }
+                    }
+                }
+            }
+
+            plan.push(PlannedChange {
+                kind: *kind,
+                key: key.clone(),
+                action,
+                result,
+                change: BundleItemChange {
+                    kind: *kind,
+                    key: key.clone(),
+                    action,
+                    diff,
+                    conflicts,
+                    missing_secrets,
+                },
+            });
+        }
+        Ok(plan)
+    }
+
+    fn apply_item(
+        state: &AppState,
+        kind: BundleItemKind,
+        key: &str,
+        result: Option<Value>,
+    ) -> Result<(), AppError> {
+        match (kind, result) {
+            (BundleItemKind::Provider, Some(value)) => {
+                let (app, id) = split_app_key(key)?;
+                let mut provider: Provider = from_value(value)?;
+                match state.db.get_provider_by_id(id, app.as_str())? {
+                    Some(existing) => {
+                        provider.created_at = existing.created_at;
+                        provider.sort_index = existing.sort_index;
+                        provider.in_failover_queue = existing.in_failover_queue;
+                        ProviderService::update(state, app, None, provider)?;
+                    }
+                    None => {
+                        provider.created_at = Some(chrono::Utc::now().timestamp_millis());
+                        ProviderService::add(state, app, provider, false)?;
+                    }
+                }
+            }
+            (BundleItemKind::Provider, None) => {
+                let (app, id) = split_app_key(key)?;
+                ProviderService::delete(state, app, id)?;
+            }
+            (BundleItemKind::McpServer, Some(value)) => {
+                let server: McpServer = from_value(value)?;
+                McpService::upsert_server(state, server)?;
+            }
+            (BundleItemKind::McpServer, None) => {
+                McpService::delete_server(state, key)?;
+            }
+            (BundleItemKind::Prompt, Some(value)) => {
+                let (app, id) = split_app_key(key)?;
+                let mut prompt: Prompt = from_value(value)?;
+                let now = chrono::Utc::now().timestamp();
+                let existing = state.db.get_prompts(app.as_str())?.shift_remove(id);
+                prompt.enabled = existing.as_ref().is_some_and(|p| p.enabled);
+                prompt.created_at = existing.and_then(|p| p.created_at).or(Some(now));
+                prompt.updated_at = Some(now);
+                PromptService::upsert_prompt(state, app, id, prompt)?;
+            }
+            (BundleItemKind::Prompt, None) => {
+                let (app, id) = split_app_key(key)?;
+                PromptService::delete_prompt(state, app, id)?;
+            }
+            (BundleItemKind::SkillRepo, Some(value)) => {
+                let repo: SkillRepo = from_value(value)?;
+                state.db.save_skill_repo(&repo)?;
+            }
+            (BundleItemKind::SkillRepo, None) => {
+                let (owner, name) = key
+                    .split_once('/')
+                    .ok_or_else(|| AppError::InvalidInput(format!("无效的仓库: {key}")))?;
+                state.db.delete_skill_repo(owner, name)?;
+            }
+            (BundleItemKind::ModelPricing, Some(value)) => {
+                let pricing: BundleModelPricing = from_value(value)?;
+                state.db.upsert_bundle_model_pricing(&pricing)?;
+            }
+            (BundleItemKind::ModelPricing, None) => {
+                state.db.delete_bundle_model_pricing(key)?;
+            }
+            (BundleItemKind::ProxySettings, Some(value)) => {
+                // 保留本地的代理开关，只更新参数
+                let local = futures::executor::block_on(state.db.get_proxy_config_for_app(key))?;
+                let mut merged = to_value(&local)?;
+                if let (Some(target), Value::Object(fields)) = (merged.as_object_mut(), value) {
+                    for (field, v) in fields {
+                        if !PROXY_LOCAL_FIELDS.contains(&field.as_str()) {
+                            target.insert(field, v);
+                        }
+                    }
+                }
+                let config: AppProxyConfig = from_value(merged)?;
+                futures::executor::block_on(state.db.update_proxy_config_for_app(config))?;
+            }
+            // 代理参数是每个应用固有的配置，无法删除
+            (BundleItemKind::ProxySettings, None) => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn strips_provider_secrets_and_replaces_mcp_secrets_with_placeholders() {
+        let mut provider = json!({
+            "id": "team-relay",
+            "settingsConfig": {
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-live",
+                    "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                    "CLAUDE_CODE_MAX_OUTPUT_TOKENS": "32000"
+                }
+            }
+        });
+        let mut found = Vec::new();
+        strip_secrets(&mut provider, "", &mut found);
+        assert_eq!(found, vec!["/settingsConfig/env/ANTHROPIC_AUTH_TOKEN"]);
+        assert_eq!(
+            provider["settingsConfig"]["env"]["ANTHROPIC_AUTH_TOKEN"],
+            ""
+        );
+        assert_eq!(
+            provider["settingsConfig"]["env"]["CLAUDE_CODE_MAX_OUTPUT_TOKENS"],
+            "32000"
+        );
+
+        let mut server = json!({
+            "id": "github",
+            "server": {
+                "command": "gh-mcp",
+                "env": { "GITHUB_TOKEN": "${secret:gh}", "API_KEY": "abc" },
+                "headers": { "Authorization": "Bearer ghp_x" }
+            }
+        });
+        strip_mcp_secrets(&mut server);
+        assert_eq!(server["server"]["env"]["GITHUB_TOKEN"], "${secret:gh}");
+        assert_eq!(
+            server["server"]["env"]["API_KEY"],
+            "${secret:github.API_KEY}"
+        );
+        assert_eq!(
+            server["server"]["headers"]["Authorization"],
+            "Bearer ${secret:github.Authorization}"
+        );
+    }
+
+    #[test]
+    fn three_way_merge_keeps_local_edits_and_reports_conflicts() {
+        let base = json!({ "name": "Relay", "url": "https://a", "model": "x" });
+        let ours = json!({ "name": "My Relay", "url": "https://a", "model": "y" });
+        let theirs = json!({ "name": "Relay", "url": "https://b", "model": "z", "new": 1 });
+
+        let mut conflicts = Vec::new();
+        let merged = merge_value(Some(&base), &ours, &theirs, "", &mut conflicts);
+        assert_eq!(
+            merged,
+            json!({ "name": "My Relay", "url": "https://b", "model": "y", "new": 1 })
+        );
+        assert_eq!(conflicts, vec!["/model"]);
+
+        // 新版本删除、本地未改动的字段跟随删除
+        let mut conflicts = Vec::new();
+        let merged = merge_value(
+            Some(&json!({ "a": 1, "b": 2 })),
+            &json!({ "a": 1, "b": 3 }),
+            &json!({ "b": 2 }),
+            "",
+            &mut conflicts,
+        );
+        assert_eq!(merged, json!({ "b": 3 }));
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn reimport_merges_against_previous_bundle() -> Result<(), AppError> {
+        let state = AppState::new(Arc::new(Database::memory()?));
+        let pricing = |display: &str, input: &str| BundleModelPricing {
+            model_id: "team-model".to_string(),
+            display_name: display.to_string(),
+            input_cost_per_million: input.to_string(),
+            output_cost_per_million: "2".to_string(),
+            cache_read_cost_per_million: "0".to_string(),
+            cache_creation_cost_per_million: "0".to_string(),
+            pricing_tiers: Vec::new(),
+        };
+        let bundle = |version: &str, pricing: BundleModelPricing| TeamBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            format_version: BUNDLE_FORMAT_VERSION,
+            id: "acme".to_string(),
+            name: "Acme".to_string(),
+            version: version.to_string(),
+            description: None,
+            publisher: None,
+            created_at: 0,
+            providers: Vec::new(),
+            mcp_servers: Vec::new(),
+            prompts: Vec::new(),
+            skill_repos: Vec::new(),
+            model_pricing: vec![pricing],
+            proxy_settings: Vec::new(),
+            signature: None,
+        };
+        let no_secrets = BundleSecretInput::default();
+
+        let v1 = bundle("1.0.0", pricing("Team Model", "1"));
+        let result = BundleService::import(&state, &v1, &no_secrets, true)?;
+        assert!(result.errors.is_empty());
+        assert_eq!(result.changes[0].action, BundleItemAction::Add);
+
+        // 本地改名，新版本改价格：两处修改都应保留
+        state
+            .db
+            .upsert_bundle_model_pricing(&pricing("Our Model", "1"))?;
+        let v2 = bundle("1.1.0", pricing("Team Model", "1.5"));
+        let preview = BundleService::preview(&state, &v2, &no_secrets)?;
+        assert_eq!(preview.previous_version.as_deref(), Some("1.0.0"));
+        assert_eq!(preview.changes[0].action, BundleItemAction::Update);
+        assert!(preview.changes[0].conflicts.is_empty());
+        assert!(preview.changes[0]
+            .diff
+            .contains("+  \"inputCostPerMillion\": \"1.5\""));
+
+        BundleService::import(&state, &v2, &no_secrets, true)?;
+        let merged = state
+            .db
+            .get_bundle_model_pricing("team-model")?
+            .expect("pricing exists");
+        assert_eq!(merged, pricing("Our Model", "1.5"));
+        assert_eq!(BundleService::list_imports(&state)?[0].version, "1.1.0");
+        Ok(())
+    }
+
+    #[test]
+    fn signed_bundle_is_verified_and_untrusted_import_needs_confirmation() -> Result<(), AppError> {
+        let state = AppState::new(Arc::new(Database::memory()?));
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let path = dir.path().join("team.ccswitch.json");
+        let key = signing::generate_keypair()?;
+
+        let options = BundleExportOptions {
+            id: "acme".to_string(),
+            name: "Acme".to_string(),
+            version: "1.0.0".to_string(),
+            signing_key: Some(key.private_key.clone()),
+            ..Default::default()
+        };
+        BundleService::export(&state, &options, &path)?;
+        let bundle = BundleService::read(&path)?;
+        assert_eq!(
+            bundle.signature.as_ref().map(|s| s.public_key.as_str()),
+            Some(key.public_key.as_str())
+        );
+
+        // 签名后修改任意内容都会被拒绝
+        let content = std::fs::read_to_string(&path).expect("read bundle");
+        let tampered = content.replace("\"Acme\"", "\"Evil\"");
+        assert!(BundleService::parse(&tampered).is_err());
+
+        let preview = BundleService::preview(&state, &bundle, &BundleSecretInput::default())?;
+        assert!(matches!(
+            preview.signature,
+            SignatureStatus::Untrusted { .. }
+        ));
+        assert!(
+            BundleService::import(&state, &bundle, &BundleSecretInput::default(), false).is_err()
+        );
+
+        signing::add_trusted_publisher(&state.db, "Acme", &key.public_key)?;
+        BundleService::import(&state, &bundle, &BundleSecretInput::default(), false)?;
+        assert_eq!(BundleService::list_imports(&state)?[0].bundle_id, "acme");
+        Ok(())
+    }
+}
@@ -136,6 +136,15 @@ impl ConfigService {
             AppType::Hermes => {
                 // Hermes uses additive mode, no live sync needed
             }
+            AppType::Qwen => {
+                // Qwen Code 不存在于旧版 config.json 结构中，只走数据库同步流程
+            }
+            AppType::Goose | AppType::Crush => {
+                // Goose / Crush 同为累加模式，不存在于旧版 config.json 结构中
+            }
+            AppType::Aider => {
+                // Aider 不存在于旧版 config.json 结构中，只走数据库同步流程
+            }
         }
 
         Ok(())
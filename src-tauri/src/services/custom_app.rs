@@ -0,0 +1,336 @@
+//! 声明式应用服务
+//!
+//! 基于 `app_definition` 加载的定义，为未内置的 CLI 工具提供通用的
+//! 供应商管理（live 写入 / 从 live 导入）、MCP 同步和提示词写入。
+//! 供应商和提示词复用现有数据表，以定义中的应用 ID 作为 `app_type`。
+
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::app_config::AppType;
+use crate::app_definition::{
+    get_app_definitions_dir, load_app_definitions, pointer_remove, pointer_set, read_document,
+    write_document, AppDefinition,
+};
+use crate::config::write_text_file;
+use crate::error::AppError;
+use crate::prompt::Prompt;
+use crate::provider::Provider;
+use crate::services::live_history::LiveWriteScope;
+use crate::store::AppState;
+
+/// 从 live 导入时创建的供应商 ID
+const IMPORTED_PROVIDER_ID: &str = "default";
+
+static REGISTRY: OnceLock<RwLock<Vec<AppDefinition>>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<Vec<AppDefinition>> {
+    REGISTRY.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// 定义加载结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDefinitionLoadReport {
+    pub apps: Vec<AppDefinition>,
+    /// 被跳过的定义文件及原因
+    pub errors: Vec<String>,
+}
+
+pub struct CustomAppService;
+
+impl CustomAppService {
+    /// 重新加载定义目录
+    pub fn reload() -> AppDefinitionLoadReport {
+        let (apps, errors) = load_app_definitions(&get_app_definitions_dir());
+        for error in &errors {
+            log::warn!("跳过无效的应用定义: {error}");
+        }
+        *registry().write().unwrap_or_else(|e| e.into_inner()) = apps.clone();
+        AppDefinitionLoadReport { apps, errors }
+    }
+
+    pub fn list() -> Vec<AppDefinition> {
+        registry().read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn get(app_id: &str) -> Result<AppDefinition, AppError> {
+        Self::list()
+            .into_iter()
+            .find(|definition| definition.id == app_id)
+            .ok_or_else(|| {
+                AppError::localized(
+                    "app_definition.not_found",
+                    format!("未找到应用定义: {app_id}"),
+                    format!("App definition not found: {app_id}"),
+                )
+            })
+    }
+
+    // ========================================================================
+    // 供应商
+    // ========================================================================
+
+    pub fn get_providers(
+        state: &AppState,
+        app_id: &str,
+    ) -> Result<IndexMap<String, Provider>, AppError> {
+        Self::get(app_id)?;
+        state.db.get_all_providers(app_id)
+    }
+
+    pub fn get_current_provider(
+        state: &AppState,
+        app_id: &str,
+    ) -> Result<Option<String>, AppError> {
+        Self::get(app_id)?;
+        state.db.get_current_provider(app_id)
+    }
+
+    /// 新增或更新供应商；更新当前供应商时同步写入 live
+    pub fn upsert_provider(
+        state: &AppState,
+        app_id: &str,
+        provider: Provider,
+    ) -> Result<(), AppError> {
+        let definition = Self::get(app_id)?;
+        if !provider.settings_config.is_object() {
+            return Err(AppError::InvalidInput(
+                "供应商配置必须是 JSON 对象".to_string(),
+            ));
+        }
+        state.db.save_provider(app_id, &provider)?;
+
+        if state.db.get_current_provider(app_id)?.as_deref() == Some(provider.id.as_str()) {
+            let _scope = LiveWriteScope::enter("providerUpdate");
+            Self::write_live(&definition, &provider)?;
+        }
+        Ok(())
+    }
+
+    pub fn delete_provider(state: &AppState, app_id: &str, id: &str) -> Result<(), AppError> {
+        Self::get(app_id)?;
+        if state.db.get_current_provider(app_id)?.as_deref() == Some(id) {
+            return Err(AppError::localized(
+                "provider.delete.current",
+                "不能删除当前正在使用的供应商",
+                "Cannot delete the provider currently in use",
+            ));
+        }
+        state.db.delete_provider(app_id, id)
+    }
+
+    /// 切换供应商：写入 live 配置并同步 MCP
+    pub fn switch(state: &AppState, app_id: &str, id: &str) -> Result<(), AppError> {
+        let definition = Self::get(app_id)?;
+        let provider = state
+            .db
+            .get_provider_by_id(id, app_id)?
+            .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+
+        let _scope = LiveWriteScope::enter("switch");
+        Self::write_live(&definition, &provider)?;
+        state.db.set_current_provider(app_id, id)?;
+        Self::sync_mcp_for(state, &definition)
+    }
+
+    /// 把供应商字段写入 live 配置；供应商未设置的受管字段会被移除
+    fn write_live(definition: &AppDefinition, provider: &Provider) -> Result<(), AppError> {
+        let path = definition.config_path();
+        let format = definition.config_format()?;
+        let mut document = read_document(&path, format)?;
+
+        for (key, pointer) in definition.fields.entries() {
+            match provider
+                .settings_config
+                .get(key)
+                .filter(|value| !value.is_null())
+            {
+                Some(value) => pointer_set(&mut document, pointer, value.clone())?,
+                None => {
+                    pointer_remove(&mut document, pointer);
+                }
+            }
+        }
+
+        write_document(&path, format, &document)
+    }
+
+    /// 从现有 live 配置导入一个供应商（仅在尚无供应商时）
+    pub fn import_from_live(state: &AppState, app_id: &str) -> Result<bool, AppError> {
+        let definition = Self::get(app_id)?;
+        if state.db.has_any_provider_for_app(app_id)? {
+            return Ok(false);
+        }
+
+        let path = definition.config_path();
+        let document = read_document(&path, definition.config_format()?)?;
+        let mut settings = Map::new();
+        for (key, pointer) in definition.fields.entries() {
+            if let Some(value) = document.pointer(pointer).filter(|value| !value.is_null()) {
+                settings.insert(key.to_string(), value.clone());
+            }
+        }
+        if settings.is_empty() {
+            return Ok(false);
+        }
+
+        let provider = Provider::with_id(
+            IMPORTED_PROVIDER_ID.to_string(),
+            IMPORTED_PROVIDER_ID.to_string(),
+            Value::Object(settings),
+            None,
+        );
+        state.db.save_provider(app_id, &provider)?;
+        state
+            .db
+            .set_current_provider(app_id, IMPORTED_PROVIDER_ID)?;
+        Ok(true)
+    }
+
+    // ========================================================================
+    // MCP
+    // ========================================================================
+
+    /// 同步全部声明式应用的 MCP；单个应用失败只记录日志
+    pub fn sync_all_mcp(state: &AppState) {
+        let _scope = LiveWriteScope::enter("mcpSync");
+        for definition in Self::list() {
+            if let Err(e) = Self::sync_mcp_for(state, &definition) {
+                log::warn!("同步应用 {} 的 MCP 失败: {e}", definition.id);
+            }
+        }
+    }
+
+    /// 从全部声明式应用的 MCP 段移除已删除的服务器
+    pub fn remove_mcp_server(id: &str) {
+        let _scope = LiveWriteScope::enter("mcpSync");
+        for definition in Self::list() {
+            let (Some(mcp), Some(path)) = (&definition.mcp, definition.mcp_path()) else {
+                continue;
+            };
+            let removed = definition.mcp_format().and_then(|format| {
+                let mut document = read_document(&path, format)?;
+                let pointer = format!(
+                    "{}/{}",
+                    mcp.pointer,
+                    id.replace('~', "~0").replace('/', "~1")
+                );
+                if pointer_remove(&mut document, &pointer).is_none() {
+                    return Ok(());
+                }
+                write_document(&path, format, &document)
+            });
+            if let Err(e) = removed {
+                log::warn!("从应用 {} 移除 MCP 服务器 {id} 失败: {e}", definition.id);
+            }
+        }
+    }
+
+    pub fn sync_mcp(state: &AppState, app_id: &str) -> Result<(), AppError> {
+        let _scope = LiveWriteScope::enter("mcpSync");
+        Self::sync_mcp_for(state, &Self::get(app_id)?)
+    }
+
+    /// 把继承应用下启用的 MCP 服务器写入定义的 MCP 段。
+    ///
+    /// 服务器按规范形状写入，键名按定义的 `mcp.keys` 转换；
+    /// cc-switch 管理的服务器按启用状态写入或移除，MCP 段中用户自行添加的条目保留。
+    fn sync_mcp_for(state: &AppState, definition: &AppDefinition) -> Result<(), AppError> {
+        let (Some(mcp), Some(path)) = (&definition.mcp, definition.mcp_path()) else {
+            return Ok(());
+        };
+        let inherit = AppType::from_str(&mcp.inherit)?;
+        let format = definition.mcp_format()?;
+        let servers = state.db.get_all_mcp_servers()?;
+        let secrets = state.db.mcp_secret_resolver();
+
+        let mut document = read_document(&path, format)?;
+        let mut section = document
+            .pointer(&mcp.pointer)
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let before = section.clone();
+
+        for server in servers.values() {
+            if server.apps.is_enabled_for(&inherit) {
+                section.insert(
+                    server.id.clone(),
+                    mcp.map_server(&secrets.resolve_for_write(&server.id, &server.server)),
+                );
+            } else {
+                section.remove(&server.id);
+            }
+        }
+
+        if section == before && document.pointer(&mcp.pointer).is_some() {
+            return Ok(());
+        }
+        pointer_set(&mut document, &mcp.pointer, Value::Object(section))?;
+        write_document(&path, format, &document)
+    }
+
+    // ========================================================================
+    // 提示词
+    // ========================================================================
+
+    pub fn get_prompts(
+        state: &AppState,
+        app_id: &str,
+    ) -> Result<IndexMap<String, Prompt>, AppError> {
+        Self::get(app_id)?;
+        state.db.get_prompts(app_id)
+    }
+
+    /// 新增或更新提示词；更新已启用的提示词时同步写入文件
+    pub fn upsert_prompt(state: &AppState, app_id: &str, prompt: Prompt) -> Result<(), AppError> {
+        let definition = Self::get(app_id)?;
+        state.db.save_prompt(app_id, &prompt)?;
+        if prompt.enabled {
+            Self::write_prompt(&definition, &prompt)?;
+        }
+        Ok(())
+    }
+
+    pub fn delete_prompt(state: &AppState, app_id: &str, id: &str) -> Result<(), AppError> {
+        Self::get(app_id)?;
+        let prompts = state.db.get_prompts(app_id)?;
+        if prompts.get(id).is_some_and(|prompt| prompt.enabled) {
+            return Err(AppError::InvalidInput("无法删除已启用的提示词".to_string()));
+        }
+        state.db.delete_prompt(app_id, id)
+    }
+
+    /// 启用提示词并写入定义的提示词文件
+    pub fn enable_prompt(state: &AppState, app_id: &str, id: &str) -> Result<(), AppError> {
+        let definition = Self::get(app_id)?;
+        let mut prompts = state.db.get_prompts(app_id)?;
+        let target = prompts
+            .get(id)
+            .cloned()
+            .ok_or_else(|| AppError::InvalidInput(format!("提示词 {id} 不存在")))?;
+
+        Self::write_prompt(&definition, &target)?;
+        for (prompt_id, prompt) in prompts.iter_mut() {
+            let enabled = prompt_id == id;
+            if prompt.enabled != enabled {
+                prompt.enabled = enabled;
+                state.db.save_prompt(app_id, prompt)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_prompt(definition: &AppDefinition, prompt: &Prompt) -> Result<(), AppError> {
+        let _scope = LiveWriteScope::enter("promptSync");
+        let path = definition.prompt_path().ok_or_else(|| {
+            AppError::InvalidInput(format!("应用 '{}' 未定义提示词文件", definition.id))
+        })?;
+        write_text_file(&path, &prompt.content)
+    }
+}
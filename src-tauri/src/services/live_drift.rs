@@ -0,0 +1,742 @@
+//! Live 配置漂移检测
+//!
+//! 记录 cc-switch 最近一次写入各应用 live 配置后的内容（基线），
+//! 后台监听这些文件所在的目录；当用户或其他工具在外部修改后，按字段与基线比较，
+//! 区分供应商字段、通用配置与无关字段，并支持回填到供应商或重新写入。
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+
+use notify_debouncer_mini::notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::services::provider::{read_live_settings, strip_common_config_from_live_settings};
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+/// 文件事件去抖时间，合并一次保存产生的多个事件
+const DEBOUNCE_MILLIS: u64 = 500;
+
+/// 全量检查间隔：注册新出现的配置目录，并兜底 watcher 漏报的变化
+const RESCAN_INTERVAL_SECS: u64 = 60;
+
+/// 检测到新漂移时发送给前端的事件
+pub const EVENT_LIVE_DRIFT: &str = "live-config-drift";
+
+/// cc-switch 自身写入（或删除）过的文件及其内容哈希
+static SELF_WRITES: Lazy<Mutex<HashMap<PathBuf, Option<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 已通知过前端的漂移指纹，避免重复发送事件
+static REPORTED: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 变更字段的归类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LiveDriftCategory {
+    /// 属于当前供应商配置的字段
+    Provider,
+    /// 来自通用配置片段的字段
+    CommonConfig,
+    /// cc-switch 不管理的字段
+    Unrelated,
+}
+
+/// 单个字段的变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveFieldChange {
+    /// JSON Pointer 形式的字段路径（Codex 的 config.toml 位于 `/config` 下）
+    pub path: String,
+    pub category: LiveDriftCategory,
+    /// cc-switch 上次写入的值；新增字段为空
+    pub expected: Option<Value>,
+    /// 当前文件中的值；被删除的字段为空
+    pub actual: Option<Value>,
+}
+
+/// 某个应用的 live 配置漂移
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveDrift {
+    pub app: String,
+    /// 切换模式应用的当前供应商；累加模式应用为空
+    pub provider_id: Option<String>,
+    /// 被外部修改的文件
+    pub files: Vec<String>,
+    pub changes: Vec<LiveFieldChange>,
+    pub detected_at: i64,
+}
+
+/// 处理漂移的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LiveDriftAction {
+    /// 将外部修改回填到已保存的供应商
+    Backfill,
+    /// 用已保存的供应商重新写入 live 配置，覆盖外部修改
+    Reapply,
+    /// 保留外部修改，仅更新基线
+    Accept,
+}
+
+/// cc-switch 最近一次写入后的 live 配置基线
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveBaseline {
+    /// 文件路径 -> 内容哈希（文件不存在时为空）
+    pub files: BTreeMap<String, Option<String>>,
+    /// 归一化后的 live 配置
+    pub settings: Value,
+    pub recorded_at: i64,
+}
+
+/// 由 `config::atomic_write` / `config::delete_file` 调用，记录 cc-switch 自身的写入
+pub(crate) fn note_self_write(path: &Path, data: Option<&[u8]>) {
+    let hash = data.map(hash_bytes);
+    if let Ok(mut writes) = SELF_WRITES.lock() {
+        writes.insert(path.to_path_buf(), hash);
+    }
+}
+
+fn hash_bytes(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    std::fs::read(path).ok().map(|data| hash_bytes(&data))
+}
+
+/// 应用由 cc-switch 管理的 live 配置文件
+pub(crate) fn managed_paths(app_type: &AppType) -> Vec<PathBuf> {
+    match app_type {
+        AppType::Claude => vec![crate::config::get_claude_settings_path()],
+        AppType::Codex => vec![
+            crate::codex_config::get_codex_auth_path(),
+            crate::codex_config::get_codex_config_path(),
+        ],
+        AppType::Gemini => vec![
+            crate::gemini_config::get_gemini_env_path(),
+            crate::gemini_config::get_gemini_settings_path(),
+        ],
+        AppType::OpenCode => vec![crate::opencode_config::get_opencode_config_path()],
+        AppType::OpenClaw => vec![crate::openclaw_config::get_openclaw_config_path()],
+        AppType::Hermes => vec![crate::hermes_config::get_hermes_config_path()],
+        AppType::Qwen => vec![
+            crate::qwen_config::get_qwen_env_path(),
+            crate::qwen_config::get_qwen_settings_path(),
+        ],
+        AppType::Goose => {
+            let mut paths = vec![
+                crate::goose_config::get_goose_config_path(),
+                crate::goose_config::get_goose_secrets_path(),
+            ];
+            paths.extend(crate::goose_config::get_custom_provider_paths().unwrap_or_default());
+            paths
+        }
+        AppType::Crush => vec![crate::crush_config::get_crush_config_path()],
+        AppType::Aider => vec![
+            crate::aider_config::get_aider_config_path(),
+            crate::aider_config::get_aider_model_settings_path(),
+            crate::aider_config::get_aider_env_path(),
+        ],
+        // Claude Desktop 的 3P 配置由切换流程整体管理，不参与漂移检测
+        AppType::ClaudeDesktop => Vec::new(),
+    }
+}
+
+fn file_hashes(app_type: &AppType) -> BTreeMap<String, Option<String>> {
+    managed_paths(app_type)
+        .into_iter()
+        .map(|path| {
+            let hash = hash_file(&path);
+            (path.to_string_lossy().to_string(), hash)
+        })
+        .collect()
+}
+
+/// 累加模式应用中供应商条目所在的位置
+fn additive_provider_prefix(app_type: &AppType) -> &'static [&'static str] {
+    match app_type {
+        AppType::OpenCode => &["provider"],
+        AppType::OpenClaw => &["models", "providers"],
+        AppType::Hermes => &["custom_providers"],
+        AppType::Goose => &["custom_providers"],
+        AppType::Crush => &["providers"],
+        _ => &[],
+    }
+}
+
+/// 将 live 配置归一化为便于逐字段比较的 JSON
+///
+/// Codex 的 `config` 为 TOML 文本，解析为对象；Hermes 的 `custom_providers`
+/// 为数组，按 `name` 展开为对象，使每个供应商都有稳定路径。
+fn normalize_view(app_type: &AppType, settings: &Value) -> Value {
+    let mut view = settings.clone();
+    match app_type {
+        AppType::Codex => {
+            if let Some(obj) = view.as_object_mut() {
+                let parsed = obj
+                    .get("config")
+                    .and_then(Value::as_str)
+                    .and_then(|text| toml::from_str::<toml::Table>(text).ok())
+                    .and_then(|table| serde_json::to_value(table).ok());
+                if let Some(parsed) = parsed {
+                    obj.insert("config".to_string(), parsed);
+                }
+            }
+        }
+        AppType::Hermes => {
+            if let Some(Value::Array(items)) = view.get("custom_providers").cloned() {
+                let mut by_name = Map::new();
+                for (index, item) in items.into_iter().enumerate() {
+                    let name = item
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| index.to_string());
+                    by_name.insert(name, item);
+                }
+                view["custom_providers"] = Value::Object(by_name);
+            }
+        }
+        _ => {}
+    }
+    view
+}
+
+fn read_view(app_type: &AppType) -> Value {
+    read_live_settings(app_type.clone())
+        .map(|settings| normalize_view(app_type, &settings))
+        .unwrap_or(Value::Null)
+}
+
+/// 通用配置片段在归一化视图中的形状
+fn common_config_view(app_type: &AppType, snippet: &str) -> Value {
+    let trimmed = snippet.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    match app_type {
+        AppType::Claude => serde_json::from_str(trimmed).unwrap_or(Value::Null),
+        AppType::Codex => toml::from_str::<toml::Table>(trimmed)
+            .ok()
+            .and_then(|table| serde_json::to_value(table).ok())
+            .map(|config| serde_json::json!({ "config": config }))
+            .unwrap_or(Value::Null),
+        AppType::Gemini | AppType::Qwen => serde_json::from_str::<Value>(trimmed)
+            .map(|env| serde_json::json!({ "env": env }))
+            .unwrap_or(Value::Null),
+        _ => Value::Null,
+    }
+}
+
+/// 展开为叶子字段；数组整体视为一个叶子
+fn flatten(value: &Value, path: &mut Vec<String>, out: &mut BTreeMap<Vec<String>, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                path.push(key.clone());
+                flatten(child, path, out);
+                path.pop();
+            }
+        }
+        Value::Null if path.is_empty() => {}
+        other => {
+            out.insert(path.clone(), other.clone());
+        }
+    }
+}
+
+type FieldDiff = (Vec<String>, Option<Value>, Option<Value>);
+
+fn diff_values(expected: &Value, actual: &Value) -> Vec<FieldDiff> {
+    let mut before = BTreeMap::new();
+    let mut after = BTreeMap::new();
+    flatten(expected, &mut Vec::new(), &mut before);
+    flatten(actual, &mut Vec::new(), &mut after);
+
+    let mut diffs = Vec::new();
+    for (path, old) in &before {
+        match after.get(path) {
+            Some(new) if new == old => {}
+            new => diffs.push((path.clone(), Some(old.clone()), new.cloned())),
+        }
+    }
+    for (path, new) in &after {
+        if !before.contains_key(path) {
+            diffs.push((path.clone(), None, Some(new.clone())));
+        }
+    }
+    diffs.sort_by(|a, b| a.0.cmp(&b.0));
+    diffs
+}
+
+/// 路径是否落在给定视图管理的范围内（存在该字段，或位于某个叶子值之内）
+fn view_covers(view: &Value, path: &[String]) -> bool {
+    let mut current = view;
+    for segment in path {
+        match current {
+            Value::Object(map) => match map.get(segment) {
+                Some(next) => current = next,
+                None => return false,
+            },
+            Value::Null => return false,
+            _ => return true,
+        }
+    }
+    !current.is_null()
+}
+
+fn classify(
+    app_type: &AppType,
+    path: &[String],
+    provider_view: &Value,
+    common_view: &Value,
+) -> LiveDriftCategory {
+    if view_covers(common_view, path) {
+        return LiveDriftCategory::CommonConfig;
+    }
+    let prefix = additive_provider_prefix(app_type);
+    let is_provider = if prefix.is_empty() {
+        view_covers(provider_view, path)
+    } else {
+        path.len() > prefix.len() && path.iter().zip(prefix).all(|(a, b)| a == b)
+    };
+    if is_provider {
+        LiveDriftCategory::Provider
+    } else {
+        LiveDriftCategory::Unrelated
+    }
+}
+
+/// 将路径编码为 JSON Pointer
+fn to_pointer(path: &[String]) -> String {
+    path.iter()
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+fn from_pointer(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+pub struct LiveDriftService;
+
+impl LiveDriftService {
+    /// 以当前文件内容为准重新记录基线
+    pub fn record_baseline(db: &Database, app_type: &AppType) -> Result<(), AppError> {
+        let baseline = LiveBaseline {
+            files: file_hashes(app_type),
+            settings: read_view(app_type),
+            recorded_at: now_secs(),
+        };
+        db.save_live_drift_baseline(app_type.as_str(), &baseline)
+    }
+
+    /// 检测单个应用的漂移
+    ///
+    /// 没有基线、只有 cc-switch 自身的写入、或仅格式变化时，静默刷新基线并返回 `None`。
+    pub fn detect(state: &AppState, app_type: &AppType) -> Result<Option<LiveDrift>, AppError> {
+        if managed_paths(app_type).is_empty() {
+            return Ok(None);
+        }
+        // 代理接管期间 live 配置由代理管理，不视为漂移
+        if state
+            .proxy_service
+            .detect_takeover_in_live_config_for_app(app_type)
+        {
+            return Ok(None);
+        }
+
+        let Some(baseline) = state.db.get_live_drift_baseline(app_type.as_str())? else {
+            Self::record_baseline(&state.db, app_type)?;
+            return Ok(None);
+        };
+
+        let current = file_hashes(app_type);
+        let changed: Vec<String> = current
+            .iter()
+            .filter(|(path, hash)| baseline.files.get(*path) != Some(*hash))
+            .map(|(path, _)| path.clone())
+            .collect();
+        if changed.is_empty() {
+            return Ok(None);
+        }
+
+        let self_written = {
+            let writes = SELF_WRITES.lock().map_err(AppError::from)?;
+            changed.iter().all(|path| {
+                writes
+                    .get(Path::new(path))
+                    .is_some_and(|hash| Some(hash) == current.get(path))
+            })
+        };
+
+        let actual = read_view(app_type);
+        let diffs = diff_values(&baseline.settings, &actual);
+        if self_written || diffs.is_empty() {
+            Self::record_baseline(&state.db, app_type)?;
+            return Ok(None);
+        }
+
+        let provider_id = if app_type.is_additive_mode() {
+            None
+        } else {
+            crate::settings::get_effective_current_provider(&state.db, app_type)?
+        };
+        let provider_view = match &provider_id {
+            Some(id) => state
+                .db
+                .get_provider_by_id(id, app_type.as_str())?
+                .map(|provider| normalize_view(app_type, &provider.settings_config))
+                .unwrap_or(Value::Null),
+            None => Value::Null,
+        };
+        let common_view = state
+            .db
+            .get_config_snippet(app_type.as_str())?
+            .map(|snippet| common_config_view(app_type, &snippet))
+            .unwrap_or(Value::Null);
+
+        let changes = diffs
+            .into_iter()
+            .map(|(path, expected, actual)| LiveFieldChange {
+                category: classify(app_type, &path, &provider_view, &common_view),
+                path: to_pointer(&path),
+                expected,
+                actual,
+            })
+            .collect();
+
+        Ok(Some(LiveDrift {
+            app: app_type.as_str().to_string(),
+            provider_id,
+            files: changed,
+            changes,
+            detected_at: now_secs(),
+        }))
+    }
+
+    /// 检测全部应用的漂移
+    pub fn check_all(state: &AppState) -> Result<Vec<LiveDrift>, AppError> {
+        let mut drifts = Vec::new();
+        for app_type in AppType::all() {
+            if let Some(drift) = Self::detect(state, &app_type)? {
+                drifts.push(drift);
+            }
+        }
+        Ok(drifts)
+    }
+
+    /// 处理漂移：回填、重新写入或接受，完成后刷新基线
+    pub fn resolve(
+        state: &AppState,
+        app_type: AppType,
+        action: LiveDriftAction,
+    ) -> Result<(), AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("driftResolve");
+        match action {
+            LiveDriftAction::Backfill => {
+                if let Some(drift) = Self::detect(state, &app_type)? {
+                    Self::backfill(state, &app_type, &drift)?;
+                }
+            }
+            LiveDriftAction::Reapply => {
+                ProviderService::sync_current_provider_for_app(state, app_type.clone())?;
+            }
+            LiveDriftAction::Accept => {}
+        }
+
+        Self::record_baseline(&state.db, &app_type)?;
+        if let Ok(mut reported) = REPORTED.lock() {
+            reported.remove(app_type.as_str());
+        }
+        Ok(())
+    }
+
+    /// 将外部修改写回已保存的供应商
+    ///
+    /// 切换模式沿用切换时的回填逻辑：去除通用配置后整体保存；
+    /// 累加模式只更新有字段变化的供应商条目。
+    fn backfill(state: &AppState, app_type: &AppType, drift: &LiveDrift) -> Result<(), AppError> {
+        if !app_type.is_additive_mode() {
+            let Some(id) = drift.provider_id.as_deref() else {
+                return Ok(());
+            };
+            let Some(mut provider) = state.db.get_provider_by_id(id, app_type.as_str())? else {
+                return Ok(());
+            };
+            let live = read_live_settings(app_type.clone())?;
+            provider.settings_config = strip_common_config_from_live_settings(
+                state.db.as_ref(),
+                app_type,
+                &provider,
+                live,
+            );
+            return state.db.save_provider(app_type.as_str(), &provider);
+        }
+
+        let prefix_len = additive_provider_prefix(app_type).len();
+        let mut ids: Vec<String> = drift
+            .changes
+            .iter()
+            .filter(|change| change.category == LiveDriftCategory::Provider)
+            .filter_map(|change| from_pointer(&change.path).get(prefix_len).cloned())
+            .collect();
+        ids.dedup();
+
+        let live_providers = match app_type {
+            AppType::OpenCode => crate::opencode_config::get_providers()?,
+            AppType::OpenClaw => crate::openclaw_config::get_providers()?,
+            AppType::Hermes => crate::hermes_config::get_providers()?,
+            AppType::Goose => crate::goose_config::get_providers()?,
+            AppType::Crush => crate::crush_config::get_providers()?,
+            _ => Map::new(),
+        };
+        for id in ids {
+            let (Some(mut provider), Some(fragment)) = (
+                state.db.get_provider_by_id(&id, app_type.as_str())?,
+                live_providers.get(&id),
+            ) else {
+                continue;
+            };
+            provider.settings_config = fragment.clone();
+            state.db.save_provider(app_type.as_str(), &provider)?;
+        }
+        Ok(())
+    }
+
+    /// 启动后台监听，发现新的漂移时向前端发送 `live-config-drift` 事件
+    pub fn start_worker(handle: AppHandle) {
+        let spawned = std::thread::Builder::new()
+            .name("live-drift-watcher".to_string())
+            .spawn(move || Self::watch(&handle));
+        if let Err(e) = spawned {
+            log::warn!("启动 live 配置漂移监听失败: {e}");
+        }
+    }
+
+    /// 监听各应用 live 配置所在目录（非递归），去抖后只检查受影响的应用
+    fn watch(handle: &AppHandle) {
+        let (tx, rx) = mpsc::channel::<DebounceEventResult>();
+        let mut debouncer = match new_debouncer(Duration::from_millis(DEBOUNCE_MILLIS), tx) {
+            Ok(debouncer) => debouncer,
+            Err(e) => {
+                log::warn!("创建 live 配置文件监听失败: {e}");
+                return;
+            }
+        };
+        let mut watched: BTreeSet<PathBuf> = BTreeSet::new();
+
+        loop {
+            // 目录被删除后 watcher 会失效，移除后等它重新出现时再注册
+            watched.retain(|dir| {
+                let exists = dir.is_dir();
+                if !exists {
+                    let _ = debouncer.watcher().unwatch(dir);
+                }
+                exists
+            });
+            for dir in watch_dirs() {
+                if watched.contains(&dir) {
+                    continue;
+                }
+                match debouncer.watcher().watch(&dir, RecursiveMode::NonRecursive) {
+                    Ok(()) => {
+                        watched.insert(dir);
+                    }
+                    Err(e) => log::warn!("监听目录 {} 失败: {e}", dir.display()),
+                }
+            }
+            Self::poll(handle, None);
+
+            let deadline = Instant::now() + Duration::from_secs(RESCAN_INTERVAL_SECS);
+            loop {
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                match rx.recv_timeout(timeout) {
+                    Ok(Ok(events)) => {
+                        let changed: HashSet<PathBuf> =
+                            events.into_iter().map(|event| event.path).collect();
+                        Self::poll(handle, Some(&changed));
+                    }
+                    Ok(Err(e)) => log::warn!("live 配置文件监听出错: {e}"),
+                    Err(mpsc::RecvTimeoutError::Timeout) => break,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        }
+    }
+
+    /// 检查漂移并通知前端；`changed` 为空时检查所有配置目录存在的应用
+    fn poll(handle: &AppHandle, changed: Option<&HashSet<PathBuf>>) {
+        let Some(state) = handle.try_state::<AppState>() else {
+            return;
+        };
+        for app_type in AppType::all() {
+            let paths = managed_paths(&app_type);
+            let affected = match changed {
+                Some(changed) => paths.iter().any(|path| changed.contains(path)),
+                None => paths
+                    .iter()
+                    .any(|path| path.parent().is_some_and(Path::is_dir)),
+            };
+            if !affected {
+                continue;
+            }
+
+            let drift = match Self::detect(&state, &app_type) {
+                Ok(Some(drift)) => drift,
+                Ok(None) => {
+                    if let Ok(mut reported) = REPORTED.lock() {
+                        reported.remove(app_type.as_str());
+                    }
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!("检测 {} live 配置漂移失败: {e}", app_type.as_str());
+                    continue;
+                }
+            };
+
+            let fingerprint = serde_json::to_string(&drift.changes).unwrap_or_default();
+            let is_new = REPORTED
+                .lock()
+                .map(|mut reported| {
+                    reported.insert(drift.app.clone(), fingerprint.clone()) != Some(fingerprint)
+                })
+                .unwrap_or(true);
+            if is_new {
+                if let Err(e) = handle.emit(EVENT_LIVE_DRIFT, &drift) {
+                    log::warn!("emit {EVENT_LIVE_DRIFT} 失败: {e}");
+                }
+            }
+        }
+    }
+}
+
+/// 需要监听的目录：所有应用 live 配置文件的已存在父目录
+fn watch_dirs() -> BTreeSet<PathBuf> {
+    AppType::all()
+        .flat_map(|app_type| managed_paths(&app_type))
+        .filter_map(|path| path.parent().map(Path::to_path_buf))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn path(pointer: &str) -> Vec<String> {
+        from_pointer(pointer)
+    }
+
+    #[test]
+    fn diff_reports_changed_added_and_removed_leaves() {
+        let expected = json!({
+            "env": { "ANTHROPIC_BASE_URL": "https://a", "ANTHROPIC_MODEL": "m1" },
+            "includeCoAuthoredBy": false
+        });
+        let actual = json!({
+            "env": { "ANTHROPIC_BASE_URL": "https://b" },
+            "includeCoAuthoredBy": false,
+            "permissions": { "allow": ["Bash"] }
+        });
+
+        let diffs = diff_values(&expected, &actual);
+        let pointers: Vec<String> = diffs.iter().map(|(p, _, _)| to_pointer(p)).collect();
+        assert_eq!(
+            pointers,
+            vec![
+                "/env/ANTHROPIC_BASE_URL",
+                "/env/ANTHROPIC_MODEL",
+                "/permissions/allow"
+            ]
+        );
+        assert_eq!(diffs[1].2, None);
+        assert_eq!(diffs[2].1, None);
+        assert!(diff_values(&expected, &expected).is_empty());
+    }
+
+    #[test]
+    fn classify_separates_provider_common_and_unrelated_fields() {
+        let provider = json!({ "env": { "ANTHROPIC_BASE_URL": "https://a" } });
+        let common = common_config_view(&AppType::Claude, r#"{ "includeCoAuthoredBy": false }"#);
+
+        assert_eq!(
+            classify(
+                &AppType::Claude,
+                &path("/env/ANTHROPIC_BASE_URL"),
+                &provider,
+                &common
+            ),
+            LiveDriftCategory::Provider
+        );
+        assert_eq!(
+            classify(
+                &AppType::Claude,
+                &path("/includeCoAuthoredBy"),
+                &provider,
+                &common
+            ),
+            LiveDriftCategory::CommonConfig
+        );
+        assert_eq!(
+            classify(&AppType::Claude, &path("/permissions"), &provider, &common),
+            LiveDriftCategory::Unrelated
+        );
+
+        // 累加模式按供应商条目位置归类，键名中的 `/` 在路径中转义
+        let view = normalize_view(
+            &AppType::Hermes,
+            &json!({ "custom_providers": [{ "name": "a/b", "model": "x" }] }),
+        );
+        assert!(view["custom_providers"]["a/b"].is_object());
+        let segments = [
+            "custom_providers".to_string(),
+            "a/b".to_string(),
+            "model".to_string(),
+        ];
+        let pointer = to_pointer(&segments);
+        assert_eq!(pointer, "/custom_providers/a~1b/model");
+        assert_eq!(
+            classify(
+                &AppType::Hermes,
+                &path(&pointer),
+                &Value::Null,
+                &Value::Null
+            ),
+            LiveDriftCategory::Provider
+        );
+        assert_eq!(
+            classify(
+                &AppType::OpenCode,
+                &path("/model"),
+                &Value::Null,
+                &Value::Null
+            ),
+            LiveDriftCategory::Unrelated
+        );
+    }
+}
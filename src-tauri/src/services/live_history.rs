@@ -0,0 +1,450 @@
+//! Live 配置写入历史
+//!
+//! `config::atomic_write` / `config::delete_file` 每次写入受管理的 live 文件
+//! （供应商配置、MCP 配置、提示词文件）时，在 `~/.cc-switch/live-history/` 下保存一份
+//! 内容快照并记录触发动作；支持任意两个版本之间的 unified diff 与一键回滚，
+//! 每个文件按保留上限淘汰旧版本。写入前若磁盘内容与最近版本不同（被外部修改），
+//! 先将其记为 `external` 版本，确保外部修改也能回滚。
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::app_config::AppType;
+use crate::config::{
+    atomic_write, delete_file, get_app_config_dir, read_json_file, write_json_file,
+};
+use crate::error::AppError;
+use crate::services::text_diff::unified_diff;
+
+const HISTORY_DIR: &str = "live-history";
+const INDEX_FILE: &str = "index.json";
+const RETENTION_FILE: &str = "retention.json";
+
+/// 每个文件默认保留的版本数
+pub const DEFAULT_RETENTION: usize = 50;
+/// 允许设置的最大保留版本数
+const MAX_RETENTION: usize = 500;
+
+/// 未标注动作时的写入来源
+pub const ACTION_OTHER: &str = "other";
+/// 写入前发现的外部修改
+pub const ACTION_EXTERNAL: &str = "external";
+
+/// 串行化历史目录的读写，避免并发写入时索引互相覆盖
+static HISTORY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+thread_local! {
+    static CURRENT_ACTION: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// 标注当前线程上 live 写入的触发动作
+///
+/// 嵌套时以最外层为准：例如切换供应商过程中的 MCP 同步仍记为 `switch`。
+pub struct LiveWriteScope {
+    previous: Option<&'static str>,
+}
+
+impl LiveWriteScope {
+    pub fn enter(action: &'static str) -> Self {
+        let previous = CURRENT_ACTION.with(|current| {
+            let previous = current.get();
+            if previous.is_none() {
+                current.set(Some(action));
+            }
+            previous
+        });
+        Self { previous }
+    }
+}
+
+impl Drop for LiveWriteScope {
+    fn drop(&mut self) {
+        CURRENT_ACTION.with(|current| current.set(self.previous));
+    }
+}
+
+/// 单个历史版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveHistoryEntry {
+    pub id: String,
+    /// 触发动作，如 `switch`、`mcpSync`、`promptEnable`、`rollback`
+    pub action: String,
+    /// 毫秒时间戳
+    pub created_at: i64,
+    pub size: u64,
+    /// 该版本文件被删除
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+/// 有历史记录的文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveHistoryFile {
+    pub app: String,
+    pub path: String,
+    pub versions: usize,
+    pub latest_at: Option<i64>,
+    pub retention: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryIndex {
+    app: String,
+    path: String,
+    /// 按时间从旧到新
+    entries: Vec<LiveHistoryEntry>,
+}
+
+fn history_root() -> PathBuf {
+    get_app_config_dir().join(HISTORY_DIR)
+}
+
+/// 受管理的 live 文件：供应商配置、Claude MCP 配置与各应用提示词文件
+fn tracked_files() -> Vec<(AppType, PathBuf)> {
+    let mut files = Vec::new();
+    for app_type in AppType::all() {
+        for path in crate::services::live_drift::managed_paths(&app_type) {
+            files.push((app_type.clone(), path));
+        }
+        if let Ok(path) = crate::prompt_files::prompt_file_path(&app_type) {
+            files.push((app_type.clone(), path));
+        }
+    }
+    files.push((AppType::Claude, crate::config::get_claude_mcp_path()));
+    files
+}
+
+fn tracked_app(path: &Path) -> Option<AppType> {
+    tracked_files()
+        .into_iter()
+        .find(|(_, tracked)| tracked == path)
+        .map(|(app_type, _)| app_type)
+}
+
+/// 历史目录名：`<app>-<文件名>`，文件名去掉前导 `.`
+fn file_key(app_type: &AppType, path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().trim_start_matches('.').to_string())
+        .unwrap_or_default();
+    format!("{}-{name}", app_type.as_str())
+}
+
+fn file_dir(app_type: &AppType, path: &Path) -> PathBuf {
+    history_root().join(file_key(app_type, path))
+}
+
+fn load_index(dir: &Path) -> HistoryIndex {
+    let path = dir.join(INDEX_FILE);
+    if !path.exists() {
+        return HistoryIndex::default();
+    }
+    read_json_file(&path).unwrap_or_else(|e| {
+        log::warn!("读取 live 历史索引失败，将重新记录: {e}");
+        HistoryIndex::default()
+    })
+}
+
+fn load_retention() -> BTreeMap<String, usize> {
+    let path = history_root().join(RETENTION_FILE);
+    if !path.exists() {
+        return BTreeMap::new();
+    }
+    read_json_file(&path).unwrap_or_default()
+}
+
+fn retention_for(key: &str) -> usize {
+    load_retention()
+        .get(key)
+        .copied()
+        .unwrap_or(DEFAULT_RETENTION)
+}
+
+fn snapshot_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{id}.snap"))
+}
+
+fn write_snapshot(path: &Path, data: &[u8]) -> Result<(), AppError> {
+    fs::write(path, data).map_err(|e| AppError::io(path, e))?;
+    // 快照可能包含 API Key，仅所有者可读写
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+/// 由 `config::atomic_write` / `config::delete_file` 在写入前调用，记录外部修改后的磁盘内容
+pub(crate) fn record_external_change(path: &Path) {
+    let data = fs::read(path).ok();
+    record(path, data.as_deref(), ACTION_EXTERNAL);
+}
+
+/// 由 `config::atomic_write` / `config::delete_file` 调用；失败只记录日志，不影响写入本身
+pub(crate) fn record_write(path: &Path, data: Option<&[u8]>) {
+    let action = CURRENT_ACTION.with(Cell::get).unwrap_or(ACTION_OTHER);
+    record(path, data, action);
+}
+
+fn record(path: &Path, data: Option<&[u8]>, action: &str) {
+    if path.starts_with(history_root()) {
+        return;
+    }
+    let Some(app_type) = tracked_app(path) else {
+        return;
+    };
+    if let Err(e) = LiveHistoryService::record(&app_type, path, data, action) {
+        log::warn!("记录 live 配置历史失败 ({}): {e}", path.display());
+    }
+}
+
+pub struct LiveHistoryService;
+
+impl LiveHistoryService {
+    /// 内容与最近版本相同时跳过；没有历史时视为文件不存在
+    fn record(
+        app_type: &AppType,
+        path: &Path,
+        data: Option<&[u8]>,
+        action: &str,
+    ) -> Result<(), AppError> {
+        let _lock = HISTORY_LOCK.lock()?;
+        let dir = file_dir(app_type, path);
+        fs::create_dir_all(&dir).map_err(|e| AppError::io(&dir, e))?;
+
+        let mut index = load_index(&dir);
+        let hash = data.map(|data| format!("{:x}", Sha256::digest(data)));
+        let unchanged = match index.entries.last() {
+            Some(last) => last.hash == hash && last.deleted == data.is_none(),
+            None => data.is_none(),
+        };
+        if unchanged {
+            return Ok(());
+        }
+
+        let created_at = chrono::Utc::now().timestamp_millis();
+        let mut id = created_at.to_string();
+        let mut suffix = 1;
+        while index.entries.iter().any(|entry| entry.id == id) {
+            id = format!("{created_at}-{suffix}");
+            suffix += 1;
+        }
+        if let Some(data) = data {
+            write_snapshot(&snapshot_path(&dir, &id), data)?;
+        }
+
+        index.app = app_type.as_str().to_string();
+        index.path = path.to_string_lossy().to_string();
+        index.entries.push(LiveHistoryEntry {
+            id,
+            action: action.to_string(),
+            created_at,
+            size: data.map_or(0, |data| data.len() as u64),
+            deleted: data.is_none(),
+            hash,
+        });
+
+        let retention = retention_for(&file_key(app_type, path));
+        let excess = index.entries.len().saturating_sub(retention);
+        for entry in index.entries.drain(..excess) {
+            let _ = fs::remove_file(snapshot_path(&dir, &entry.id));
+        }
+
+        write_json_file(&dir.join(INDEX_FILE), &index)
+    }
+
+    fn locate(path: &str) -> Result<(AppType, PathBuf), AppError> {
+        let path = PathBuf::from(path);
+        let app_type = tracked_app(&path).ok_or_else(|| {
+            AppError::localized(
+                "live_history.untracked",
+                format!("{} 不是受管理的 live 配置文件", path.display()),
+                format!("{} is not a managed live config file", path.display()),
+            )
+        })?;
+        Ok((app_type, path))
+    }
+
+    fn read_version(dir: &Path, index: &HistoryIndex, id: &str) -> Result<String, AppError> {
+        let entry = index
+            .entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| {
+                AppError::localized(
+                    "live_history.version_not_found",
+                    format!("历史版本不存在: {id}"),
+                    format!("History version not found: {id}"),
+                )
+            })?;
+        if entry.deleted {
+            return Ok(String::new());
+        }
+        let snapshot = snapshot_path(dir, id);
+        fs::read_to_string(&snapshot).map_err(|e| AppError::io(&snapshot, e))
+    }
+
+    /// 列出有历史记录的文件
+    pub fn list_files() -> Result<Vec<LiveHistoryFile>, AppError> {
+        let retention = load_retention();
+        let mut files: Vec<LiveHistoryFile> = tracked_files()
+            .into_iter()
+            .filter_map(|(app_type, path)| {
+                let index = load_index(&file_dir(&app_type, &path));
+                if index.entries.is_empty() {
+                    return None;
+                }
+                let key = file_key(&app_type, &path);
+                Some(LiveHistoryFile {
+                    app: app_type.as_str().to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    versions: index.entries.len(),
+                    latest_at: index.entries.last().map(|entry| entry.created_at),
+                    retention: retention.get(&key).copied().unwrap_or(DEFAULT_RETENTION),
+                })
+            })
+            .collect();
+        files.sort_by(|a, b| b.latest_at.cmp(&a.latest_at));
+        Ok(files)
+    }
+
+    /// 列出文件的历史版本（新的在前）
+    pub fn list_versions(path: &str) -> Result<Vec<LiveHistoryEntry>, AppError> {
+        let (app_type, path) = Self::locate(path)?;
+        let mut entries = load_index(&file_dir(&app_type, &path)).entries;
+        entries.reverse();
+        Ok(entries)
+    }
+
+    /// 两个版本之间的 unified diff；`to_id` 为空时与当前文件内容比较
+    pub fn diff(path: &str, from_id: &str, to_id: Option<&str>) -> Result<String, AppError> {
+        let (app_type, path) = Self::locate(path)?;
+        let dir = file_dir(&app_type, &path);
+        let index = load_index(&dir);
+
+        let old = Self::read_version(&dir, &index, from_id)?;
+        let (new, new_label) = match to_id {
+            Some(id) => (Self::read_version(&dir, &index, id)?, id.to_string()),
+            None => (
+                fs::read_to_string(&path).unwrap_or_default(),
+                "current".to_string(),
+            ),
+        };
+        Ok(unified_diff(&old, &new, from_id, &new_label))
+    }
+
+    /// 恢复到指定版本；恢复本身也会作为 `rollback` 记录一个新版本
+    pub fn restore(path: &str, id: &str) -> Result<(), AppError> {
+        let (app_type, path) = Self::locate(path)?;
+        let dir = file_dir(&app_type, &path);
+        let index = load_index(&dir);
+        let deleted = index
+            .entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .is_some_and(|entry| entry.deleted);
+        let content = Self::read_version(&dir, &index, id)?;
+
+        let _scope = LiveWriteScope::enter("rollback");
+        if deleted {
+            delete_file(&path)
+        } else {
+            atomic_write(&path, content.as_bytes())
+        }
+    }
+
+    /// 设置单个文件保留的版本数，并立即淘汰超出的旧版本
+    pub fn set_retention(path: &str, limit: usize) -> Result<(), AppError> {
+        let (app_type, path) = Self::locate(path)?;
+        let limit = limit.clamp(1, MAX_RETENTION);
+        let key = file_key(&app_type, &path);
+
+        let _lock = HISTORY_LOCK.lock()?;
+        let mut retention = load_retention();
+        retention.insert(key, limit);
+        write_json_file(&history_root().join(RETENTION_FILE), &retention)?;
+
+        let dir = file_dir(&app_type, &path);
+        let mut index = load_index(&dir);
+        let excess = index.entries.len().saturating_sub(limit);
+        if excess == 0 {
+            return Ok(());
+        }
+        for entry in index.entries.drain(..excess) {
+            let _ = fs::remove_file(snapshot_path(&dir, &entry.id));
+        }
+        write_json_file(&dir.join(INDEX_FILE), &index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    fn outermost_scope_names_the_action() {
+        let current = || CURRENT_ACTION.with(Cell::get);
+        assert_eq!(current(), None);
+        {
+            let _outer = LiveWriteScope::enter("switch");
+            {
+                let _inner = LiveWriteScope::enter("mcpSync");
+                assert_eq!(current(), Some("switch"));
+            }
+            assert_eq!(current(), Some("switch"));
+        }
+        assert_eq!(current(), None);
+    }
+
+    #[test]
+    fn file_key_strips_leading_dot() {
+        assert_eq!(
+            file_key(&AppType::Gemini, Path::new("/home/u/.gemini/.env")),
+            "gemini-env"
+        );
+        assert_eq!(
+            file_key(&AppType::Codex, Path::new("/home/u/.codex/config.toml")),
+            "codex-config.toml"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn external_edits_between_writes_can_be_restored() {
+        let home = tempfile::tempdir().unwrap();
+        let original_home = std::env::var_os("CC_SWITCH_TEST_HOME");
+        std::env::set_var("CC_SWITCH_TEST_HOME", home.path());
+
+        let path = crate::config::get_claude_settings_path();
+        atomic_write(&path, br#"{"model":"a"}"#).unwrap();
+        fs::write(&path, r#"{"model":"edited"}"#).unwrap();
+        atomic_write(&path, br#"{"model":"b"}"#).unwrap();
+
+        let path_str = path.to_string_lossy().to_string();
+        let versions = LiveHistoryService::list_versions(&path_str).unwrap();
+        let actions: Vec<&str> = versions.iter().map(|v| v.action.as_str()).collect();
+        assert_eq!(actions, vec![ACTION_OTHER, ACTION_EXTERNAL, ACTION_OTHER]);
+
+        LiveHistoryService::restore(&path_str, &versions[1].id).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"model":"edited"}"#);
+
+        match original_home {
+            Some(value) => std::env::set_var("CC_SWITCH_TEST_HOME", value),
+            None => std::env::remove_var("CC_SWITCH_TEST_HOME"),
+        }
+    }
+}
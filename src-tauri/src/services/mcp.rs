@@ -1,9 +1,11 @@
 use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::time::Duration;
 
 use crate::app_config::{AppType, McpServer};
 use crate::error::AppError;
-use crate::mcp;
+use crate::mcp::{self, McpGatewayConfig, McpProbeResult, McpSecretInfo, McpSecretReport};
+use crate::services::custom_app::CustomAppService;
 use crate::store::AppState;
 
 /// MCP 相关业务逻辑（v3.7.0 统一结构）
@@ -17,6 +19,7 @@ impl McpService {
 
     /// 添加或更新 MCP 服务器
     pub fn upsert_server(state: &AppState, server: McpServer) -> Result<(), AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("mcpSync");
         // 读取旧状态：用于处理“编辑时取消勾选某个应用”的场景（需要从对应 live 配置中移除）
         let prev_apps = state
             .db
@@ -43,15 +46,26 @@ impl McpService {
         if prev_apps.hermes && !server.apps.hermes {
             Self::remove_server_from_app(state, &server.id, &AppType::Hermes)?;
         }
+        if prev_apps.qwen && !server.apps.qwen {
+            Self::remove_server_from_app(state, &server.id, &AppType::Qwen)?;
+        }
+        if prev_apps.goose && !server.apps.goose {
+            Self::remove_server_from_app(state, &server.id, &AppType::Goose)?;
+        }
+        if prev_apps.crush && !server.apps.crush {
+            Self::remove_server_from_app(state, &server.id, &AppType::Crush)?;
+        }
 
         // 同步到各个启用的应用
         Self::sync_server_to_apps(state, &server)?;
+        CustomAppService::sync_all_mcp(state);
 
         Ok(())
     }
 
     /// 删除 MCP 服务器
     pub fn delete_server(state: &AppState, id: &str) -> Result<bool, AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("mcpSync");
         let server = state.db.get_all_mcp_servers()?.shift_remove(id);
 
         if let Some(server) = server {
@@ -59,12 +73,41 @@ impl McpService {
 
             // 从所有应用的 live 配置中移除
             Self::remove_server_from_all_apps(state, id, &server)?;
+            CustomAppService::remove_mcp_server(id);
             Ok(true)
         } else {
             Ok(false)
         }
     }
 
+    /// 探测已保存的 MCP 服务器，并把结果记录到该服务器行
+    pub async fn probe_server(
+        state: &AppState,
+        id: &str,
+        timeout: Duration,
+    ) -> Result<McpProbeResult, AppError> {
+        let server = state
+            .db
+            .get_all_mcp_servers()?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::InvalidInput(format!("MCP 服务器不存在: {id}")))?;
+
+        let spec = state
+            .db
+            .mcp_secret_resolver()
+            .resolve_for_write(id, &server.server);
+        let result = mcp::probe_server(&spec, timeout).await;
+        state.db.save_mcp_probe_result(id, &result)?;
+        Ok(result)
+    }
+
+    /// 获取所有 MCP 服务器的最近一次探测结果
+    pub fn get_probe_results(
+        state: &AppState,
+    ) -> Result<HashMap<String, McpProbeResult>, AppError> {
+        state.db.get_mcp_probe_results()
+    }
+
     /// 切换指定应用的启用状态
     pub fn toggle_app(
         state: &AppState,
@@ -72,6 +115,7 @@ impl McpService {
         app: AppType,
         enabled: bool,
     ) -> Result<(), AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("mcpSync");
         let mut servers = state.db.get_all_mcp_servers()?;
 
         if let Some(server) = servers.get_mut(server_id) {
@@ -84,49 +128,76 @@ impl McpService {
             } else {
                 Self::remove_server_from_app(state, server_id, &app)?;
             }
+            CustomAppService::sync_all_mcp(state);
         }
 
         Ok(())
     }
 
     /// 将 MCP 服务器同步到所有启用的应用
-    fn sync_server_to_apps(_state: &AppState, server: &McpServer) -> Result<(), AppError> {
+    fn sync_server_to_apps(state: &AppState, server: &McpServer) -> Result<(), AppError> {
         for app in server.apps.enabled_apps() {
-            Self::sync_server_to_app_no_config(server, &app)?;
+            Self::sync_server_to_app(state, server, &app)?;
         }
 
         Ok(())
     }
 
     /// 将 MCP 服务器同步到指定应用
+    ///
+    /// 网关模式下客户端配置只保留网关条目，服务器本身由网关转发。
     fn sync_server_to_app(
-        _state: &AppState,
+        state: &AppState,
         server: &McpServer,
         app: &AppType,
     ) -> Result<(), AppError> {
-        Self::sync_server_to_app_no_config(server, app)
+        if Self::gateway_config(state).enabled {
+            return Self::refresh_gateway_entry(state, app);
+        }
+        let secrets = state.db.mcp_secret_resolver();
+        Self::sync_server_to_app_no_config(server, app, &secrets)
     }
 
-    fn sync_server_to_app_no_config(server: &McpServer, app: &AppType) -> Result<(), AppError> {
+    fn sync_server_to_app_no_config(
+        server: &McpServer,
+        app: &AppType,
+        secrets: &mcp::SecretResolver,
+    ) -> Result<(), AppError> {
         match app {
             AppType::Claude => {
-                mcp::sync_single_server_to_claude(&Default::default(), &server.id, &server.server)?;
+                mcp::sync_single_server_to_claude(
+                    &Default::default(),
+                    &server.id,
+                    &server.server,
+                    secrets,
+                )?;
             }
             AppType::ClaudeDesktop => {
                 log::debug!("Claude Desktop 3P profiles do not use CC Switch MCP sync, skipping");
             }
             AppType::Codex => {
                 // Codex uses TOML format, must use the correct function
-                mcp::sync_single_server_to_codex(&Default::default(), &server.id, &server.server)?;
+                mcp::sync_single_server_to_codex(
+                    &Default::default(),
+                    &server.id,
+                    &server.server,
+                    secrets,
+                )?;
             }
             AppType::Gemini => {
-                mcp::sync_single_server_to_gemini(&Default::default(), &server.id, &server.server)?;
+                mcp::sync_single_server_to_gemini(
+                    &Default::default(),
+                    &server.id,
+                    &server.server,
+                    secrets,
+                )?;
             }
             AppType::OpenCode => {
                 mcp::sync_single_server_to_opencode(
                     &Default::default(),
                     &server.id,
                     &server.server,
+                    secrets,
                 )?;
             }
             AppType::OpenClaw => {
@@ -135,7 +206,40 @@ impl McpService {
                 log::debug!("OpenClaw MCP support is still in development, skipping sync");
             }
             AppType::Hermes => {
-                mcp::sync_single_server_to_hermes(&Default::default(), &server.id, &server.server)?;
+                mcp::sync_single_server_to_hermes(
+                    &Default::default(),
+                    &server.id,
+                    &server.server,
+                    secrets,
+                )?;
+            }
+            AppType::Qwen => {
+                mcp::sync_single_server_to_qwen(
+                    &Default::default(),
+                    &server.id,
+                    &server.server,
+                    secrets,
+                )?;
+            }
+            AppType::Goose => {
+                mcp::sync_single_server_to_goose(
+                    &Default::default(),
+                    &server.id,
+                    &server.server,
+                    secrets,
+                )?;
+            }
+            AppType::Crush => {
+                mcp::sync_single_server_to_crush(
+                    &Default::default(),
+                    &server.id,
+                    &server.server,
+                    secrets,
+                )?;
+            }
+            AppType::Aider => {
+                // Aider has no MCP support
+                log::debug!("Aider does not support MCP, skipping sync");
             }
         }
         Ok(())
@@ -154,7 +258,15 @@ impl McpService {
         Ok(())
     }
 
-    fn remove_server_from_app(_state: &AppState, id: &str, app: &AppType) -> Result<(), AppError> {
+    fn remove_server_from_app(state: &AppState, id: &str, app: &AppType) -> Result<(), AppError> {
+        Self::remove_from_app_live(id, app)?;
+        if Self::gateway_config(state).enabled {
+            Self::refresh_gateway_entry(state, app)?;
+        }
+        Ok(())
+    }
+
+    fn remove_from_app_live(id: &str, app: &AppType) -> Result<(), AppError> {
         match app {
             AppType::Claude => mcp::remove_server_from_claude(id)?,
             AppType::ClaudeDesktop => {
@@ -172,19 +284,39 @@ impl McpService {
             AppType::Hermes => {
                 mcp::remove_server_from_hermes(id)?;
             }
+            AppType::Qwen => mcp::remove_server_from_qwen(id)?,
+            AppType::Goose => mcp::remove_server_from_goose(id)?,
+            AppType::Crush => mcp::remove_server_from_crush(id)?,
+            AppType::Aider => {
+                log::debug!("Aider does not support MCP, skipping remove");
+            }
         }
         Ok(())
     }
 
     /// 手动同步所有启用的 MCP 服务器到对应的应用
     pub fn sync_all_enabled(state: &AppState) -> Result<(), AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("mcpSync");
         let servers = Self::get_all_servers(state)?;
+        let gateway_enabled = Self::gateway_config(state).enabled;
 
         for app in AppType::all() {
-            if matches!(app, AppType::OpenClaw | AppType::ClaudeDesktop) {
+            if matches!(
+                app,
+                AppType::OpenClaw | AppType::ClaudeDesktop | AppType::Aider
+            ) {
                 continue;
             }
 
+            if gateway_enabled {
+                for server in servers.values() {
+                    Self::remove_from_app_live(&server.id, &app)?;
+                }
+                Self::refresh_gateway_entry(state, &app)?;
+                continue;
+            }
+            Self::remove_from_app_live(mcp::GATEWAY_SERVER_ID, &app)?;
+
             for server in servers.values() {
                 if server.apps.is_enabled_for(&app) {
                     Self::sync_server_to_app(state, server, &app)?;
@@ -193,10 +325,118 @@ impl McpService {
                 }
             }
         }
+        CustomAppService::sync_all_mcp(state);
 
         Ok(())
     }
 
+    // ========================================================================
+    // 内置 MCP 网关
+    // ========================================================================
+
+    fn gateway_config(state: &AppState) -> McpGatewayConfig {
+        state.db.get_mcp_gateway_config().unwrap_or_else(|e| {
+            log::warn!("读取 MCP 网关配置失败，按未启用处理: {e}");
+            McpGatewayConfig::default()
+        })
+    }
+
+    /// 网关在本地代理上的 HTTP 端点
+    fn gateway_endpoint(state: &AppState, app: &AppType) -> Result<String, AppError> {
+        let proxy = futures::executor::block_on(state.db.get_global_proxy_config())?;
+        let host = match proxy.listen_address.as_str() {
+            "" | "0.0.0.0" | "::" => "127.0.0.1".to_string(),
+            addr if addr.contains(':') => format!("[{addr}]"),
+            addr => addr.to_string(),
+        };
+        Ok(format!(
+            "http://{host}:{}/mcp/{}",
+            proxy.listen_port,
+            app.as_str()
+        ))
+    }
+
+    /// 按应用当前启用的服务器写入或移除网关条目
+    fn refresh_gateway_entry(state: &AppState, app: &AppType) -> Result<(), AppError> {
+        let has_servers = state
+            .db
+            .get_all_mcp_servers()?
+            .values()
+            .any(|s| s.id != mcp::GATEWAY_SERVER_ID && s.apps.is_enabled_for(app));
+        if !has_servers {
+            return Self::remove_from_app_live(mcp::GATEWAY_SERVER_ID, app);
+        }
+
+        let config = Self::gateway_config(state);
+        let endpoint = Self::gateway_endpoint(state, app)?;
+        let spec = mcp::gateway_server_spec(config.transport, &endpoint)?;
+        Self::sync_server_to_app_no_config(
+            &mcp::gateway_server(spec),
+            app,
+            &mcp::SecretResolver::default(),
+        )
+    }
+
+    /// 保存网关配置并重新同步所有应用的 MCP 配置
+    ///
+    /// 启用时各客户端只保留网关条目；停用时移除网关条目并恢复逐个写入。
+    pub fn save_gateway_config(state: &AppState, config: McpGatewayConfig) -> Result<(), AppError> {
+        state.db.save_mcp_gateway_config(&config)?;
+        Self::sync_all_enabled(state)
+    }
+
+    // ========================================================================
+    // 本地密钥库
+    // ========================================================================
+
+    /// 列出本地密钥（不含值）
+    pub fn list_secrets(state: &AppState) -> Result<Vec<McpSecretInfo>, AppError> {
+        state.db.list_mcp_secrets()
+    }
+
+    /// 新增或更新密钥，并重新同步引用了它的服务器
+    pub fn set_secret(
+        state: &AppState,
+        name: &str,
+        value: &str,
+        description: Option<&str>,
+    ) -> Result<(), AppError> {
+        mcp::validate_secret_name(name)?;
+        state.db.set_mcp_secret(name, value, description)?;
+        Self::resync_servers_referencing(state, name)
+    }
+
+    /// 删除密钥；引用它的服务器在下次写入时保留占位符
+    pub fn delete_secret(state: &AppState, name: &str) -> Result<bool, AppError> {
+        let deleted = state.db.delete_mcp_secret(name)?;
+        if deleted {
+            Self::resync_servers_referencing(state, name)?;
+        }
+        Ok(deleted)
+    }
+
+    /// 按应用列出未能解析的密钥引用
+    pub fn validate_secret_refs(state: &AppState) -> Result<McpSecretReport, AppError> {
+        let servers = Self::get_all_servers(state)?;
+        Ok(state.db.mcp_secret_resolver().report(&servers))
+    }
+
+    fn resync_servers_referencing(state: &AppState, name: &str) -> Result<(), AppError> {
+        // 网关模式下 live 配置不包含上游 spec，网关连接时自行解析
+        if Self::gateway_config(state).enabled {
+            return Ok(());
+        }
+        for server in Self::get_all_servers(state)?.values() {
+            let references = mcp::find_secret_refs(&server.server)
+                .iter()
+                .any(|r| r.kind == mcp::SecretRefKind::Secret && r.name == name);
+            if references {
+                Self::sync_server_to_apps(state, server)?;
+            }
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // 兼容层：支持旧的 v3.6.x 命令（已废弃，将在 v4.0 移除）
     // ========================================================================
@@ -247,189 +487,139 @@ impl McpService {
 
     /// 从 Claude 导入 MCP（v3.7.0 已更新为统一结构）
     pub fn import_from_claude(state: &AppState) -> Result<usize, AppError> {
-        // 创建临时 MultiAppConfig 用于导入
         let mut temp_config = crate::app_config::MultiAppConfig::default();
 
         // 调用原有的导入逻辑（从 mcp.rs）
         let count = crate::mcp::import_from_claude(&mut temp_config)?;
-
-        let mut new_count = 0;
-
-        // 如果有导入的服务器，保存到数据库
-        if count > 0 {
-            if let Some(servers) = &temp_config.mcp.servers {
-                let mut existing = state.db.get_all_mcp_servers()?;
-                for server in servers.values() {
-                    // 已存在：仅启用 Claude，不覆盖其他字段（与导入模块语义保持一致）
-                    let to_save = if let Some(existing_server) = existing.get(&server.id) {
-                        let mut merged = existing_server.clone();
-                        merged.apps.claude = true;
-                        merged
-                    } else {
-                        // 真正的新服务器
-                        new_count += 1;
-                        server.clone()
-                    };
-
-                    state.db.save_mcp_server(&to_save)?;
-                    existing.insert(to_save.id.clone(), to_save.clone());
-
-                    // 导入是读取已有配置，不应反向写回任何应用的 live 配置。
-                    // 显式编辑、启用/禁用或手动同步时再执行写回。
-                }
-            }
+        if count == 0 {
+            return Ok(0);
         }
 
-        Ok(new_count)
+        Self::save_imported_servers(state, &temp_config, &AppType::Claude)
     }
 
     /// 从 Codex 导入 MCP（v3.7.0 已更新为统一结构）
     pub fn import_from_codex(state: &AppState) -> Result<usize, AppError> {
-        // 创建临时 MultiAppConfig 用于导入
         let mut temp_config = crate::app_config::MultiAppConfig::default();
 
         // 调用原有的导入逻辑（从 mcp.rs）
         let count = crate::mcp::import_from_codex(&mut temp_config)?;
-
-        let mut new_count = 0;
-
-        // 如果有导入的服务器，保存到数据库
-        if count > 0 {
-            if let Some(servers) = &temp_config.mcp.servers {
-                let mut existing = state.db.get_all_mcp_servers()?;
-                for server in servers.values() {
-                    // 已存在：仅启用 Codex，不覆盖其他字段（与导入模块语义保持一致）
-                    let to_save = if let Some(existing_server) = existing.get(&server.id) {
-                        let mut merged = existing_server.clone();
-                        merged.apps.codex = true;
-                        merged
-                    } else {
-                        // 真正的新服务器
-                        new_count += 1;
-                        server.clone()
-                    };
-
-                    state.db.save_mcp_server(&to_save)?;
-                    existing.insert(to_save.id.clone(), to_save.clone());
-
-                    // 导入是读取已有配置，不应反向写回任何应用的 live 配置。
-                    // 显式编辑、启用/禁用或手动同步时再执行写回。
-                }
-            }
+        if count == 0 {
+            return Ok(0);
         }
 
-        Ok(new_count)
+        Self::save_imported_servers(state, &temp_config, &AppType::Codex)
     }
 
     /// 从 Gemini 导入 MCP（v3.7.0 已更新为统一结构）
     pub fn import_from_gemini(state: &AppState) -> Result<usize, AppError> {
-        // 创建临时 MultiAppConfig 用于导入
         let mut temp_config = crate::app_config::MultiAppConfig::default();
 
         // 调用原有的导入逻辑（从 mcp.rs）
         let count = crate::mcp::import_from_gemini(&mut temp_config)?;
-
-        let mut new_count = 0;
-
-        // 如果有导入的服务器，保存到数据库
-        if count > 0 {
-            if let Some(servers) = &temp_config.mcp.servers {
-                let mut existing = state.db.get_all_mcp_servers()?;
-                for server in servers.values() {
-                    // 已存在：仅启用 Gemini，不覆盖其他字段（与导入模块语义保持一致）
-                    let to_save = if let Some(existing_server) = existing.get(&server.id) {
-                        let mut merged = existing_server.clone();
-                        merged.apps.gemini = true;
-                        merged
-                    } else {
-                        // 真正的新服务器
-                        new_count += 1;
-                        server.clone()
-                    };
-
-                    state.db.save_mcp_server(&to_save)?;
-                    existing.insert(to_save.id.clone(), to_save.clone());
-
-                    // 导入是读取已有配置，不应反向写回任何应用的 live 配置。
-                    // 显式编辑、启用/禁用或手动同步时再执行写回。
-                }
-            }
+        if count == 0 {
+            return Ok(0);
         }
 
-        Ok(new_count)
+        Self::save_imported_servers(state, &temp_config, &AppType::Gemini)
     }
 
     /// 从 OpenCode 导入 MCP（v3.9.2+ 新增）
     pub fn import_from_opencode(state: &AppState) -> Result<usize, AppError> {
-        // 创建临时 MultiAppConfig 用于导入
         let mut temp_config = crate::app_config::MultiAppConfig::default();
 
         // 调用原有的导入逻辑（从 mcp/opencode.rs）
         let count = crate::mcp::import_from_opencode(&mut temp_config)?;
-
-        let mut new_count = 0;
-
-        // 如果有导入的服务器，保存到数据库
-        if count > 0 {
-            if let Some(servers) = &temp_config.mcp.servers {
-                let mut existing = state.db.get_all_mcp_servers()?;
-                for server in servers.values() {
-                    // 已存在：仅启用 OpenCode，不覆盖其他字段（与导入模块语义保持一致）
-                    let to_save = if let Some(existing_server) = existing.get(&server.id) {
-                        let mut merged = existing_server.clone();
-                        merged.apps.opencode = true;
-                        merged
-                    } else {
-                        // 真正的新服务器
-                        new_count += 1;
-                        server.clone()
-                    };
-
-                    state.db.save_mcp_server(&to_save)?;
-                    existing.insert(to_save.id.clone(), to_save.clone());
-
-                    // 导入是读取已有配置，不应反向写回任何应用的 live 配置。
-                    // 显式编辑、启用/禁用或手动同步时再执行写回。
-                }
-            }
+        if count == 0 {
+            return Ok(0);
         }
 
-        Ok(new_count)
+        Self::save_imported_servers(state, &temp_config, &AppType::OpenCode)
     }
 
     /// 从 Hermes 导入 MCP
     pub fn import_from_hermes(state: &AppState) -> Result<usize, AppError> {
-        // 创建临时 MultiAppConfig 用于导入
         let mut temp_config = crate::app_config::MultiAppConfig::default();
 
         // 调用导入逻辑（从 mcp/hermes.rs）
         let count = crate::mcp::import_from_hermes(&mut temp_config)?;
+        if count == 0 {
+            return Ok(0);
+        }
 
-        let mut new_count = 0;
+        Self::save_imported_servers(state, &temp_config, &AppType::Hermes)
+    }
 
-        // 如果有导入的服务器，保存到数据库
-        if count > 0 {
-            if let Some(servers) = &temp_config.mcp.servers {
-                let mut existing = state.db.get_all_mcp_servers()?;
-                for server in servers.values() {
-                    // 已存在：仅启用 Hermes，不覆盖其他字段（与导入模块语义保持一致）
-                    let to_save = if let Some(existing_server) = existing.get(&server.id) {
-                        let mut merged = existing_server.clone();
-                        merged.apps.hermes = true;
-                        merged
-                    } else {
-                        // 真正的新服务器
-                        new_count += 1;
-                        server.clone()
-                    };
-
-                    state.db.save_mcp_server(&to_save)?;
-                    existing.insert(to_save.id.clone(), to_save.clone());
-
-                    // 导入是读取已有配置，不应反向写回任何应用的 live 配置。
-                    // 显式编辑、启用/禁用或手动同步时再执行写回。
-                }
+    /// 从 Qwen Code 导入 MCP
+    pub fn import_from_qwen(state: &AppState) -> Result<usize, AppError> {
+        let mut temp_config = crate::app_config::MultiAppConfig::default();
+
+        // 调用导入逻辑（从 mcp/qwen.rs）
+        let count = crate::mcp::import_from_qwen(&mut temp_config)?;
+        if count == 0 {
+            return Ok(0);
+        }
+
+        Self::save_imported_servers(state, &temp_config, &AppType::Qwen)
+    }
+
+    /// 从 Goose 导入 MCP
+    pub fn import_from_goose(state: &AppState) -> Result<usize, AppError> {
+        let mut temp_config = crate::app_config::MultiAppConfig::default();
+
+        // 调用导入逻辑（从 mcp/goose.rs）
+        let count = crate::mcp::import_from_goose(&mut temp_config)?;
+        if count == 0 {
+            return Ok(0);
+        }
+
+        Self::save_imported_servers(state, &temp_config, &AppType::Goose)
+    }
+
+    /// 从 Crush 导入 MCP
+    pub fn import_from_crush(state: &AppState) -> Result<usize, AppError> {
+        let mut temp_config = crate::app_config::MultiAppConfig::default();
+
+        // 调用导入逻辑（从 mcp/crush.rs）
+        let count = crate::mcp::import_from_crush(&mut temp_config)?;
+        if count == 0 {
+            return Ok(0);
+        }
+
+        Self::save_imported_servers(state, &temp_config, &AppType::Crush)
+    }
+
+    /// 将临时配置中导入的服务器保存到数据库，返回真正新增的数量
+    ///
+    /// 已存在的服务器仅启用 `app`，不覆盖其他字段；cc-switch 写入的网关条目不作为普通
+    /// 服务器导入。导入是读取已有配置，不回写任何 live 配置，显式编辑、启用/禁用或手动
+    /// 同步时再执行写回。
+    fn save_imported_servers(
+        state: &AppState,
+        temp_config: &crate::app_config::MultiAppConfig,
+        app: &AppType,
+    ) -> Result<usize, AppError> {
+        let Some(servers) = &temp_config.mcp.servers else {
+            return Ok(0);
+        };
+
+        let mut new_count = 0;
+        let mut existing = state.db.get_all_mcp_servers()?;
+        for server in servers.values() {
+            if mcp::is_gateway_entry(&server.id) {
+                continue;
             }
+            let to_save = if let Some(existing_server) = existing.get(&server.id) {
+                let mut merged = existing_server.clone();
+                merged.apps.set_enabled_for(app, true);
+                merged
+            } else {
+                new_count += 1;
+                server.clone()
+            };
+
+            state.db.save_mcp_server(&to_save)?;
+            existing.insert(to_save.id.clone(), to_save);
         }
 
         Ok(new_count)
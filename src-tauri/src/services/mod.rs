@@ -1,39 +1,60 @@
 pub mod balance;
+pub mod budget_alerts;
+pub mod bundle;
 pub mod codex_oauth_models;
 pub mod coding_plan;
 pub mod config;
+pub mod custom_app;
 pub mod env_checker;
 pub mod env_manager;
+pub mod live_drift;
+pub mod live_history;
 pub mod mcp;
 pub mod model_fetch;
 pub mod omo;
+pub mod pricing_catalog;
+pub mod profile;
+pub mod project;
 pub mod prompt;
 pub mod provider;
 pub mod proxy;
 pub mod s3;
 pub mod s3_auto_sync;
 pub mod s3_sync;
+pub mod session_archive;
+pub mod session_cost;
 pub mod session_usage;
 pub mod session_usage_codex;
 pub mod session_usage_gemini;
 pub mod session_usage_opencode;
 pub mod skill;
+pub mod skill_git;
+pub mod skill_lint;
+pub mod skill_lock;
 pub mod speedtest;
 pub mod sql_helpers;
 pub mod stream_check;
 pub mod subscription;
 pub mod sync_protocol;
+pub mod text_diff;
 pub mod usage_cache;
+pub mod usage_export;
 pub mod usage_stats;
 pub mod webdav;
 pub mod webdav_auto_sync;
 pub mod webdav_sync;
 
+pub use bundle::BundleService;
 pub use config::ConfigService;
+pub use custom_app::CustomAppService;
+pub use live_drift::LiveDriftService;
+pub use live_history::LiveHistoryService;
 pub use mcp::McpService;
 pub use omo::OmoService;
+pub use profile::ProfileService;
+pub use project::ProjectService;
 pub use prompt::PromptService;
-pub use provider::{ProviderService, ProviderSortUpdate, SwitchResult};
+pub use provider::{ProviderService, ProviderSortUpdate, SwitchPreview, SwitchResult};
 pub use proxy::ProxyService;
 #[allow(unused_imports)]
 pub use skill::{DiscoverableSkill, Skill, SkillRepo, SkillService};
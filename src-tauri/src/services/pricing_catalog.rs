@@ -0,0 +1,672 @@
+//! 外部模型定价目录导入
+//!
+//! 支持 LiteLLM `model_prices_and_context_window.json` 格式（本地文件或 URL），
+//! 把按 token 计的价格换算为每百万 token 价格写入 `model_pricing`，
+//! 并提供供应商级定价覆盖与"未定价模型"报告。
+
+use std::collections::{BTreeSet, HashSet};
+use std::str::FromStr;
+use std::time::Duration;
+
+use rusqlite::{params, OptionalExtension};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::proxy::usage::calculator::PricingTier;
+use crate::services::usage_stats::{is_placeholder_pricing_model, lookup_model_pricing};
+
+/// LiteLLM 官方定价目录地址
+pub const LITELLM_CATALOG_URL: &str =
+    "https://raw.githubusercontent.com/BerriAI/litellm/main/model_prices_and_context_window.json";
+
+const FETCH_TIMEOUT_SECS: u64 = 30;
+
+/// 非文本生成类模型，不参与 token 计价
+const SKIPPED_MODES: &[&str] = &[
+    "embedding",
+    "image_generation",
+    "audio_transcription",
+    "audio_speech",
+    "moderation",
+    "rerank",
+];
+
+pub const PRICING_SOURCE_CATALOG: &str = "catalog";
+pub const PRICING_SOURCE_MANUAL: &str = "manual";
+
+/// 目录中解析出的一条定价（价格均为每百万 token，USD）
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogEntry {
+    pub model_id: String,
+    pub input: Decimal,
+    pub output: Decimal,
+    pub cache_read: Decimal,
+    pub cache_creation: Decimal,
+    pub tiers: Vec<PricingTier>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingImportOptions {
+    /// 覆盖已有的内置/目录定价（默认只补充缺失的模型）
+    #[serde(default)]
+    pub overwrite: bool,
+    /// 同时覆盖用户手动编辑过的定价
+    #[serde(default)]
+    pub overwrite_manual: bool,
+    /// 仅导入这些 `litellm_provider`（如 anthropic / openai / gemini）
+    #[serde(default)]
+    pub providers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingImportReport {
+    pub total_entries: usize,
+    pub inserted: usize,
+    pub updated: usize,
+    pub skipped_existing: usize,
+    pub skipped_invalid: usize,
+    /// 导入后回填的历史零成本请求数
+    pub backfilled: u64,
+}
+
+/// 供应商级定价覆盖
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderModelPricing {
+    pub provider_id: String,
+    pub app_type: String,
+    /// 模型 ID 或通配符模式（如 `claude-*`）
+    pub model_pattern: String,
+    pub input_cost_per_million: String,
+    pub output_cost_per_million: String,
+    #[serde(default = "zero_price")]
+    pub cache_read_cost_per_million: String,
+    #[serde(default = "zero_price")]
+    pub cache_creation_cost_per_million: String,
+}
+
+fn zero_price() -> String {
+    "0".to_string()
+}
+
+/// 有用量但没有定价的模型
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnpricedModel {
+    pub model: String,
+    pub app_type: String,
+    pub request_count: u64,
+    pub total_tokens: u64,
+    pub first_seen_at: i64,
+    pub last_seen_at: i64,
+}
+
+/// 把 JSON 数字（每 token 价格，可能是科学计数法）换算为每百万 token 价格
+fn per_token_to_per_million(value: &Value) -> Option<Decimal> {
+    let raw = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.trim().to_string(),
+        _ => return None,
+    };
+    let per_token = if raw.contains(['e', 'E']) {
+        Decimal::from_scientific(&raw).ok()?
+    } else {
+        Decimal::from_str(&raw).ok()?
+    };
+    if per_token < Decimal::ZERO {
+        return None;
+    }
+    Some((per_token * Decimal::from(1_000_000)).normalize())
+}
+
+fn price_field(spec: &serde_json::Map<String, Value>, key: &str) -> Option<Decimal> {
+    spec.get(key).and_then(per_token_to_per_million)
+}
+
+/// 解析 `*_above_{N}k_tokens` 形式的分档价格
+fn parse_tiers(spec: &serde_json::Map<String, Value>, base: &CatalogEntry) -> Vec<PricingTier> {
+    let mut thresholds = BTreeSet::new();
+    for key in spec.keys() {
+        if let Some(rest) = key.split("_above_").nth(1) {
+            if let Some(k) = rest
+                .strip_suffix("k_tokens")
+                .and_then(|n| n.parse::<u64>().ok())
+            {
+                thresholds.insert(k * 1000);
+            }
+        }
+    }
+
+    let mut tiers = Vec::new();
+    for above in thresholds {
+        let suffix = format!("_above_{}k_tokens", above / 1000);
+        let input = price_field(spec, &format!("input_cost_per_token{suffix}"));
+        let output = price_field(spec, &format!("output_cost_per_token{suffix}"));
+        if input.is_none() && output.is_none() {
+            continue;
+        }
+        let cache_read = price_field(spec, &format!("cache_read_input_token_cost{suffix}"));
+        let cache_creation = price_field(spec, &format!("cache_creation_input_token_cost{suffix}"));
+        tiers.push(PricingTier {
+            above_tokens: above,
+            input_cost_per_million: input.unwrap_or(base.input).to_string(),
+            output_cost_per_million: output.unwrap_or(base.output).to_string(),
+            cache_read_cost_per_million: cache_read.unwrap_or(base.cache_read).to_string(),
+            cache_creation_cost_per_million: cache_creation
+                .unwrap_or(base.cache_creation)
+                .to_string(),
+        });
+    }
+    tiers
+}
+
+/// 解析 LiteLLM 定价目录
+///
+/// 模型 ID 统一小写；带命名空间的键（`bedrock/...`、`openrouter/...`）只在
+/// 没有同名顶层条目时才取最后一段作为 ID，避免第三方转售价覆盖官方价。
+pub fn parse_litellm_catalog(
+    json: &str,
+    providers: Option<&[String]>,
+) -> Result<(Vec<CatalogEntry>, usize), AppError> {
+    let root: serde_json::Map<String, Value> = serde_json::from_str(json)
+        .map_err(|e| AppError::Message(format!("解析定价目录失败: {e}")))?;
+
+    let provider_filter: Option<HashSet<String>> =
+        providers.map(|list| list.iter().map(|p| p.to_ascii_lowercase()).collect());
+
+    let mut keys: Vec<&String> = root.keys().filter(|k| *k != "sample_spec").collect();
+    // 顶层键优先，其次按字典序保证结果稳定
+    keys.sort_by_key(|k| (k.contains('/'), k.as_str()));
+
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    let mut invalid = 0usize;
+
+    for key in keys {
+        let Some(spec) = root.get(key).and_then(Value::as_object) else {
+            invalid += 1;
+            continue;
+        };
+
+        if let Some(mode) = spec.get("mode").and_then(Value::as_str) {
+            if SKIPPED_MODES.contains(&mode) {
+                continue;
+            }
+        }
+        if let Some(filter) = &provider_filter {
+            let provider = spec
+                .get("litellm_provider")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            if !filter.contains(&provider) {
+                continue;
+            }
+        }
+
+        let model_id = key
+            .rsplit_once('/')
+            .map_or(key.as_str(), |(_, r)| r)
+            .trim()
+            .to_ascii_lowercase();
+        if is_placeholder_pricing_model(&model_id) || !seen.insert(model_id.clone()) {
+            continue;
+        }
+
+        let (Some(input), Some(output)) = (
+            price_field(spec, "input_cost_per_token"),
+            price_field(spec, "output_cost_per_token"),
+        ) else {
+            invalid += 1;
+            continue;
+        };
+
+        let mut entry = CatalogEntry {
+            model_id,
+            input,
+            output,
+            cache_read: price_field(spec, "cache_read_input_token_cost").unwrap_or_default(),
+            cache_creation: price_field(spec, "cache_creation_input_token_cost")
+                .unwrap_or_default(),
+            tiers: Vec::new(),
+        };
+        entry.tiers = parse_tiers(spec, &entry);
+        entries.push(entry);
+    }
+
+    Ok((entries, invalid))
+}
+
+/// 解析并导入定价目录 JSON
+pub fn import_catalog_json(
+    db: &Database,
+    json: &str,
+    options: &PricingImportOptions,
+) -> Result<PricingImportReport, AppError> {
+    let (entries, invalid) = parse_litellm_catalog(json, options.providers.as_deref())?;
+    let mut report = db.import_pricing_catalog(&entries, options)?;
+    report.skipped_invalid = invalid;
+    Ok(report)
+}
+
+/// 从 URL 下载定价目录
+pub async fn fetch_catalog(url: Option<&str>) -> Result<String, AppError> {
+    let url = url
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .unwrap_or(LITELLM_CATALOG_URL);
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| AppError::Message(format!("创建 HTTP 客户端失败: {e}")))?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| AppError::Message(format!("下载定价目录失败: {e}")))?;
+    if !response.status().is_success() {
+        return Err(AppError::Message(format!(
+            "下载定价目录失败: HTTP {}",
+            response.status()
+        )));
+    }
+    response
+        .text()
+        .await
+        .map_err(|e| AppError::Message(format!("读取定价目录失败: {e}")))
+}
+
+impl Database {
+    /// 将定价目录写入 model_pricing；默认只补充缺失模型，不动手动编辑的定价
+    pub fn import_pricing_catalog(
+        &self,
+        entries: &[CatalogEntry],
+        options: &PricingImportOptions,
+    ) -> Result<PricingImportReport, AppError> {
+        let mut report = PricingImportReport {
+            total_entries: entries.len(),
+            ..Default::default()
+        };
+
+        {
+            let conn = lock_conn!(self.conn);
+            let tx = conn
+                .unchecked_transaction()
+                .map_err(|e| AppError::Database(format!("启动定价导入事务失败: {e}")))?;
+
+            for entry in entries {
+                let existing_source: Option<String> = tx
+                    .query_row(
+                        "SELECT source FROM model_pricing WHERE model_id = ?1",
+                        params![entry.model_id],
+                        |row| row.get(0),
+                    )
+                    .optional()
+                    .map_err(|e| AppError::Database(format!("查询模型定价失败: {e}")))?;
+
+                let tiers_json = if entry.tiers.is_empty() {
+                    None
+                } else {
+                    Some(
+                        serde_json::to_string(&entry.tiers)
+                            .map_err(|e| AppError::Message(format!("序列化分档定价失败: {e}")))?,
+                    )
+                };
+
+                match existing_source.as_deref() {
+                    None => {
+                        tx.execute(
+                            "INSERT INTO model_pricing (
+                                model_id, display_name, input_cost_per_million,
+                                output_cost_per_million, cache_read_cost_per_million,
+                                cache_creation_cost_per_million, pricing_tiers, source
+                            ) VALUES (?1, ?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                            params![
+                                entry.model_id,
+                                entry.input.to_string(),
+                                entry.output.to_string(),
+                                entry.cache_read.to_string(),
+                                entry.cache_creation.to_string(),
+                                tiers_json,
+                                PRICING_SOURCE_CATALOG,
+                            ],
+                        )
+                        .map_err(|e| AppError::Database(format!("写入模型定价失败: {e}")))?;
+                        report.inserted += 1;
+                    }
+                    Some(source) => {
+                        let allowed = if source == PRICING_SOURCE_MANUAL {
+                            options.overwrite_manual
+                        } else {
+                            options.overwrite
+                        };
+                        if !allowed {
+                            report.skipped_existing += 1;
+                            continue;
+                        }
+                        tx.execute(
+                            "UPDATE model_pricing SET
+                                input_cost_per_million = ?2,
+                                output_cost_per_million = ?3,
+                                cache_read_cost_per_million = ?4,
+                                cache_creation_cost_per_million = ?5,
+                                pricing_tiers = ?6,
+                                source = ?7
+                             WHERE model_id = ?1",
+                            params![
+                                entry.model_id,
+                                entry.input.to_string(),
+                                entry.output.to_string(),
+                                entry.cache_read.to_string(),
+                                entry.cache_creation.to_string(),
+                                tiers_json,
+                                PRICING_SOURCE_CATALOG,
+                            ],
+                        )
+                        .map_err(|e| AppError::Database(format!("更新模型定价失败: {e}")))?;
+                        report.updated += 1;
+                    }
+                }
+            }
+
+            tx.commit()
+                .map_err(|e| AppError::Database(format!("提交定价导入事务失败: {e}")))?;
+        }
+
+        if report.inserted + report.updated > 0 {
+            report.backfilled = self.backfill_missing_usage_costs()?;
+        }
+
+        log::info!(
+            "定价目录导入完成: 新增 {}, 更新 {}, 跳过 {}",
+            report.inserted,
+            report.updated,
+            report.skipped_existing
+        );
+        Ok(report)
+    }
+
+    /// 列出供应商定价覆盖（可按供应商过滤）
+    pub fn list_provider_model_pricing(
+        &self,
+        provider_id: Option<&str>,
+        app_type: Option<&str>,
+    ) -> Result<Vec<ProviderModelPricing>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn.prepare(
+            "SELECT provider_id, app_type, model_pattern, input_cost_per_million,
+                    output_cost_per_million, cache_read_cost_per_million,
+                    cache_creation_cost_per_million
+             FROM provider_model_pricing
+             WHERE (?1 IS NULL OR provider_id = ?1) AND (?2 IS NULL OR app_type = ?2)
+             ORDER BY provider_id, app_type, model_pattern",
+        )?;
+        let rows = stmt.query_map(params![provider_id, app_type], |row| {
+            Ok(ProviderModelPricing {
+                provider_id: row.get(0)?,
+                app_type: row.get(1)?,
+                model_pattern: row.get(2)?,
+                input_cost_per_million: row.get(3)?,
+                output_cost_per_million: row.get(4)?,
+                cache_read_cost_per_million: row.get(5)?,
+                cache_creation_cost_per_million: row.get(6)?,
+            })
+        })?;
+        let mut overrides = Vec::new();
+        for row in rows {
+            overrides.push(row?);
+        }
+        Ok(overrides)
+    }
+
+    /// 新增或更新供应商定价覆盖
+    pub fn upsert_provider_model_pricing(
+        &self,
+        pricing: &ProviderModelPricing,
+    ) -> Result<(), AppError> {
+        for (label, value) in [
+            ("input", &pricing.input_cost_per_million),
+            ("output", &pricing.output_cost_per_million),
+            ("cache_read", &pricing.cache_read_cost_per_million),
+            ("cache_creation", &pricing.cache_creation_cost_per_million),
+        ] {
+            let parsed = Decimal::from_str(value.trim())
+                .map_err(|e| AppError::InvalidInput(format!("{label} 价格无效: {value} - {e}")))?;
+            if parsed < Decimal::ZERO {
+                return Err(AppError::InvalidInput(format!(
+                    "{label} 价格必须为非负数: {value}"
+                )));
+            }
+        }
+        let pattern = pricing.model_pattern.trim().to_ascii_lowercase();
+        if pattern.is_empty() {
+            return Err(AppError::InvalidInput("模型模式不能为空".to_string()));
+        }
+
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO provider_model_pricing (
+                provider_id, app_type, model_pattern, input_cost_per_million,
+                output_cost_per_million, cache_read_cost_per_million,
+                cache_creation_cost_per_million
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                pricing.provider_id,
+                pricing.app_type,
+                pattern,
+                pricing.input_cost_per_million.trim(),
+                pricing.output_cost_per_million.trim(),
+                pricing.cache_read_cost_per_million.trim(),
+                pricing.cache_creation_cost_per_million.trim(),
+            ],
+        )
+        .map_err(|e| AppError::Database(format!("保存供应商定价覆盖失败: {e}")))?;
+        Ok(())
+    }
+
+    /// 删除供应商定价覆盖
+    pub fn delete_provider_model_pricing(
+        &self,
+        provider_id: &str,
+        app_type: &str,
+        model_pattern: &str,
+    ) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute(
+                "DELETE FROM provider_model_pricing
+                 WHERE provider_id = ?1 AND app_type = ?2 AND model_pattern = ?3",
+                params![provider_id, app_type, model_pattern],
+            )
+            .map_err(|e| AppError::Database(format!("删除供应商定价覆盖失败: {e}")))?;
+        Ok(affected > 0)
+    }
+
+    /// 列出有用量但仍无法匹配到定价的模型
+    pub fn get_unpriced_models(&self) -> Result<Vec<UnpricedModel>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn.prepare(
+            "SELECT COALESCE(NULLIF(pricing_model, ''), model) AS m, app_type,
+                    COUNT(*),
+                    COALESCE(SUM(input_tokens + output_tokens
+                                 + cache_read_tokens + cache_creation_tokens), 0),
+                    MIN(created_at), MAX(created_at)
+             FROM proxy_request_logs
+             WHERE CAST(total_cost_usd AS REAL) <= 0
+               AND (input_tokens > 0 OR output_tokens > 0
+                    OR cache_read_tokens > 0 OR cache_creation_tokens > 0)
+             GROUP BY m, app_type
+             ORDER BY COUNT(*) DESC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(UnpricedModel {
+                model: row.get(0)?,
+                app_type: row.get(1)?,
+                request_count: row.get::<_, i64>(2)? as u64,
+                total_tokens: row.get::<_, i64>(3)? as u64,
+                first_seen_at: row.get(4)?,
+                last_seen_at: row.get(5)?,
+            })
+        })?;
+
+        let mut models = Vec::new();
+        for row in rows {
+            let model = row?;
+            if is_placeholder_pricing_model(&model.model) {
+                continue;
+            }
+            // 零成本但现在已有定价的（例如刚导入、尚未回填）不算未定价
+            if lookup_model_pricing(&conn, &model.model)?.is_some() {
+                continue;
+            }
+            models.push(model);
+        }
+        Ok(models)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::usage_stats::find_model_pricing;
+
+    const SAMPLE: &str = r#"{
+        "sample_spec": {"input_cost_per_token": 0},
+        "claude-new-model-5": {
+            "litellm_provider": "anthropic",
+            "mode": "chat",
+            "input_cost_per_token": 3e-06,
+            "output_cost_per_token": 1.5e-05,
+            "cache_read_input_token_cost": 3e-07,
+            "cache_creation_input_token_cost": 3.75e-06,
+            "input_cost_per_token_above_200k_tokens": 6e-06,
+            "output_cost_per_token_above_200k_tokens": 2.25e-05
+        },
+        "openrouter/anthropic/claude-new-model-5": {
+            "litellm_provider": "openrouter",
+            "input_cost_per_token": 9e-06,
+            "output_cost_per_token": 9e-05
+        },
+        "bedrock/vendor-only-model": {
+            "litellm_provider": "bedrock",
+            "input_cost_per_token": 1e-06,
+            "output_cost_per_token": 2e-06
+        },
+        "text-embedding-4": {
+            "mode": "embedding",
+            "input_cost_per_token": 1e-07,
+            "output_cost_per_token": 0
+        },
+        "broken-model": {"mode": "chat"}
+    }"#;
+
+    #[test]
+    fn parses_litellm_prices_per_million_with_tiers() {
+        let (entries, invalid) = parse_litellm_catalog(SAMPLE, None).expect("parse catalog");
+        assert_eq!(invalid, 1);
+        assert_eq!(entries.len(), 2);
+
+        let claude = entries
+            .iter()
+            .find(|e| e.model_id == "claude-new-model-5")
+            .expect("claude entry");
+        assert_eq!(claude.input.to_string(), "3");
+        assert_eq!(claude.output.to_string(), "15");
+        assert_eq!(claude.cache_read.to_string(), "0.3");
+        assert_eq!(claude.tiers.len(), 1);
+        assert_eq!(claude.tiers[0].above_tokens, 200_000);
+        assert_eq!(claude.tiers[0].input_cost_per_million, "6");
+        // 分档缺失的缓存价沿用基础价
+        assert_eq!(claude.tiers[0].cache_creation_cost_per_million, "3.75");
+
+        assert!(entries.iter().any(|e| e.model_id == "vendor-only-model"));
+    }
+
+    #[test]
+    fn provider_filter_limits_imported_entries() {
+        let providers = vec!["anthropic".to_string()];
+        let (entries, _) = parse_litellm_catalog(SAMPLE, Some(&providers)).expect("parse catalog");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].model_id, "claude-new-model-5");
+    }
+
+    #[test]
+    fn import_fills_missing_models_and_preserves_manual_prices() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        {
+            let conn = lock_conn!(db.conn);
+            conn.execute(
+                "INSERT OR REPLACE INTO model_pricing (
+                    model_id, display_name, input_cost_per_million, output_cost_per_million,
+                    source
+                ) VALUES ('vendor-only-model', 'Vendor', '42', '84', 'manual')",
+                [],
+            )?;
+        }
+
+        let (entries, _) = parse_litellm_catalog(SAMPLE, None)?;
+        let options = PricingImportOptions {
+            overwrite: true,
+            ..Default::default()
+        };
+        let report = db.import_pricing_catalog(&entries, &options)?;
+        assert_eq!(report.inserted, 1);
+        assert_eq!(report.skipped_existing, 1);
+
+        let conn = lock_conn!(db.conn);
+        let manual = find_model_pricing(&conn, "vendor-only-model").expect("manual pricing");
+        assert_eq!(manual.input_cost_per_million.to_string(), "42");
+        let imported = find_model_pricing(&conn, "claude-new-model-5").expect("imported");
+        assert_eq!(imported.tiers.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn provider_override_and_glob_patterns_take_effect() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        {
+            let conn = lock_conn!(db.conn);
+            conn.execute(
+                "INSERT INTO model_pricing (
+                    model_id, display_name, input_cost_per_million, output_cost_per_million
+                ) VALUES ('acme-*', 'Acme family', '1', '2')",
+                [],
+            )?;
+        }
+        db.upsert_provider_model_pricing(&ProviderModelPricing {
+            provider_id: "p1".to_string(),
+            app_type: "claude".to_string(),
+            model_pattern: "acme-large*".to_string(),
+            input_cost_per_million: "10".to_string(),
+            output_cost_per_million: "20".to_string(),
+            cache_read_cost_per_million: "0".to_string(),
+            cache_creation_cost_per_million: "0".to_string(),
+        })?;
+
+        let conn = lock_conn!(db.conn);
+        let global = find_model_pricing(&conn, "acme-large-2").expect("glob pricing");
+        assert_eq!(global.input_cost_per_million.to_string(), "1");
+
+        let overridden = crate::services::usage_stats::find_provider_model_pricing(
+            &conn,
+            "p1",
+            "claude",
+            "acme-large-2",
+        )?
+        .expect("override pricing");
+        assert_eq!(overridden.input_cost_per_million.to_string(), "10");
+        assert!(crate::services::usage_stats::find_provider_model_pricing(
+            &conn,
+            "p2",
+            "claude",
+            "acme-large-2"
+        )?
+        .is_none());
+        Ok(())
+    }
+}
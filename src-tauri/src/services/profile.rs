@@ -0,0 +1,706 @@
+//! 配置档案
+//!
+//! 一个档案按应用记录供应商、MCP 服务器、提示词与 Skills 的选择，切换时一次性应用。
+//! 应用前先把受影响部分的当前状态写入 `profile_switch_backup`（与 `proxy_live_backup`
+//! 相同的思路），任一步失败即按快照回滚；进程在切换中途退出时，下次启动会自动回滚。
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::prompt::Prompt;
+use crate::services::{McpService, PromptService, ProviderService, SkillService};
+use crate::store::AppState;
+
+/// 单个应用的选择；字段为 `None` 表示切换时保持不变
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileAppSelection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    /// 为该应用启用的 MCP 服务器，其余服务器会被关闭
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<Vec<String>>,
+    /// 一个 ID 时按单一提示词启用，多个时按顺序叠加为片段，空列表表示不启用提示词
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<Vec<String>>,
+    /// 为该应用启用的 Skills，其余 Skills 会被关闭
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills: Option<Vec<String>>,
+}
+
+/// 应用 ID -> 选择
+pub type ProfileSelections = BTreeMap<String, ProfileAppSelection>;
+
+/// 配置档案
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub apps: ProfileSelections,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_index: Option<i64>,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// 切换结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSwitchResult {
+    pub profile_id: String,
+    pub warnings: Vec<String>,
+}
+
+fn supports_mcp(app: &AppType) -> bool {
+    !matches!(
+        app,
+        AppType::OpenClaw | AppType::ClaudeDesktop | AppType::Aider
+    )
+}
+
+fn supports_prompts(app: &AppType) -> bool {
+    !matches!(
+        app,
+        AppType::ClaudeDesktop | AppType::Goose | AppType::Crush | AppType::Aider
+    )
+}
+
+fn supports_skills(app: &AppType) -> bool {
+    !matches!(
+        app,
+        AppType::OpenClaw
+            | AppType::ClaudeDesktop
+            | AppType::Qwen
+            | AppType::Goose
+            | AppType::Crush
+            | AppType::Aider
+    )
+}
+
+/// MCP 与 Skills 按集合比较，统一排序去重
+fn normalize_set(ids: &mut Option<Vec<String>>) {
+    if let Some(ids) = ids {
+        ids.sort();
+        ids.dedup();
+    }
+}
+
+pub struct ProfileService;
+
+impl ProfileService {
+    pub fn list(state: &AppState) -> Result<Vec<Profile>, AppError> {
+        state.db.list_profiles()
+    }
+
+    /// 新增或更新档案
+    pub fn save(state: &AppState, mut profile: Profile) -> Result<Profile, AppError> {
+        profile.name = profile.name.trim().to_string();
+        if profile.name.is_empty() {
+            return Err(AppError::InvalidInput("档案名称不能为空".to_string()));
+        }
+
+        // 统一应用键为规范 ID
+        let mut apps = ProfileSelections::new();
+        for (app_key, mut selection) in std::mem::take(&mut profile.apps) {
+            let app = AppType::from_str(&app_key)?;
+            normalize_set(&mut selection.mcp_servers);
+            normalize_set(&mut selection.skills);
+            apps.insert(app.as_str().to_string(), selection);
+        }
+        Self::validate(state, &apps)?;
+        profile.apps = apps;
+
+        let now = chrono::Utc::now().timestamp();
+        if profile.id.is_empty() {
+            profile.id = uuid::Uuid::new_v4().to_string();
+        }
+        profile.created_at = state
+            .db
+            .get_profile(&profile.id)?
+            .map(|p| p.created_at)
+            .unwrap_or(now);
+        profile.updated_at = now;
+
+        state.db.save_profile(&profile)?;
+        Ok(profile)
+    }
+
+    pub fn delete(state: &AppState, id: &str) -> Result<bool, AppError> {
+        state.db.delete_profile(id)
+    }
+
+    /// 当前状态与之完全一致的档案（用于界面与托盘勾选）
+    pub fn current(state: &AppState) -> Result<Option<String>, AppError> {
+        for profile in state.db.list_profiles()? {
+            if !profile.apps.is_empty() && Self::capture(state, &profile.apps)? == profile.apps {
+                return Ok(Some(profile.id));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 切换到指定档案；任一步失败时回滚到切换前状态
+    pub fn apply(state: &AppState, id: &str) -> Result<ProfileSwitchResult, AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("profile");
+        let profile = state
+            .db
+            .get_profile(id)?
+            .ok_or_else(|| AppError::InvalidInput(format!("配置档案不存在: {id}")))?;
+        // 先校验引用，避免切换到一半才发现目标不存在
+        Self::validate(state, &profile.apps)?;
+
+        let snapshot = Self::capture(state, &profile.apps)?;
+        state
+            .db
+            .save_profile_switch_backup(&profile.id, &snapshot)?;
+
+        let mut warnings = Vec::new();
+        if let Err(e) = Self::apply_selections(state, &profile.apps, &mut warnings) {
+            log::error!("切换配置档案 {} 失败，正在回滚: {e}", profile.name);
+            if let Err(rollback_err) = Self::rollback(state, &snapshot) {
+                log::error!("回滚配置档案切换失败: {rollback_err}");
+                return Err(AppError::Message(format!(
+                    "切换配置档案失败: {e}；回滚也失败: {rollback_err}（将在下次启动时重试）"
+                )));
+            }
+            return Err(e);
+        }
+
+        state.db.delete_profile_switch_backup()?;
+        log::info!("已切换到配置档案: {}", profile.name);
+        Ok(ProfileSwitchResult {
+            profile_id: profile.id,
+            warnings,
+        })
+    }
+
+    /// 启动时检查上次切换是否中途中断，若是则回滚到切换前状态
+    pub fn recover_interrupted_switch(state: &AppState) -> Result<bool, AppError> {
+        let Some((profile_id, snapshot)) = state.db.get_profile_switch_backup()? else {
+            return Ok(false);
+        };
+        log::warn!("检测到未完成的配置档案切换（{profile_id}），正在回滚");
+        Self::rollback(state, &snapshot)?;
+        Ok(true)
+    }
+
+    fn rollback(state: &AppState, snapshot: &ProfileSelections) -> Result<(), AppError> {
+        Self::apply_selections(state, snapshot, &mut Vec::new())?;
+        state.db.delete_profile_switch_backup()
+    }
+
+    fn validate(state: &AppState, selections: &ProfileSelections) -> Result<(), AppError> {
+        let servers = state.db.get_all_mcp_servers()?;
+        let skills = state.db.get_all_installed_skills()?;
+
+        for (app_key, selection) in selections {
+            let app = AppType::from_str(app_key)?;
+            let unsupported =
+                |what: &str| AppError::InvalidInput(format!("{} 不支持{what}", app.as_str()));
+
+            if let Some(provider_id) = &selection.provider_id {
+                if !state
+                    .db
+                    .get_all_providers(app.as_str())?
+                    .contains_key(provider_id)
+                {
+                    return Err(AppError::InvalidInput(format!(
+                        "{} 的供应商不存在: {provider_id}",
+                        app.as_str()
+                    )));
+                }
+            }
+            if let Some(ids) = &selection.mcp_servers {
+                if !supports_mcp(&app) {
+                    return Err(unsupported("MCP"));
+                }
+                if let Some(missing) = ids.iter().find(|id| !servers.contains_key(*id)) {
+                    return Err(AppError::InvalidInput(format!(
+                        "MCP 服务器不存在: {missing}"
+                    )));
+                }
+            }
+            if let Some(ids) = &selection.prompts {
+                if !supports_prompts(&app) {
+                    return Err(unsupported("提示词"));
+                }
+                let prompts = state.db.get_prompts(app.as_str())?;
+                for (index, id) in ids.iter().enumerate() {
+                    if !prompts.contains_key(id) {
+                        return Err(AppError::InvalidInput(format!(
+                            "{} 的提示词不存在: {id}",
+                            app.as_str()
+                        )));
+                    }
+                    if ids[..index].contains(id) {
+                        return Err(AppError::InvalidInput(format!("提示词 {id} 重复")));
+                    }
+                }
+            }
+            if let Some(ids) = &selection.skills {
+                if !supports_skills(&app) {
+                    return Err(unsupported("Skills"));
+                }
+                if let Some(missing) = ids.iter().find(|id| !skills.contains_key(*id)) {
+                    return Err(AppError::InvalidInput(format!("Skill 不存在: {missing}")));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 记录 `selections` 涉及部分的当前状态
+    fn capture(
+        state: &AppState,
+        selections: &ProfileSelections,
+    ) -> Result<ProfileSelections, AppError> {
+        let servers = state.db.get_all_mcp_servers()?;
+        let skills = state.db.get_all_installed_skills()?;
+        let stack_config = PromptService::get_stack_config(state)?;
+
+        let mut snapshot = ProfileSelections::new();
+        for (app_key, selection) in selections {
+            let app = AppType::from_str(app_key)?;
+            let mut current = ProfileAppSelection::default();
+
+            if selection.provider_id.is_some() {
+                current.provider_id =
+                    crate::settings::get_effective_current_provider(&state.db, &app)?;
+            }
+            if selection.mcp_servers.is_some() {
+                current.mcp_servers = Some(
+                    servers
+                        .values()
+                        .filter(|s| s.apps.is_enabled_for(&app))
+                        .map(|s| s.id.clone())
+                        .collect(),
+                );
+            }
+            if selection.prompts.is_some() {
+                current.prompts = Some(
+                    match stack_config
+                        .stacks
+                        .get(app.as_str())
+                        .filter(|ids| !ids.is_empty())
+                    {
+                        Some(stack) => stack.clone(),
+                        None => state
+                            .db
+                            .get_prompts(app.as_str())?
+                            .into_values()
+                            .filter(|p| p.enabled)
+                            .map(|p| p.id)
+                            .collect(),
+                    },
+                );
+            }
+            if selection.skills.is_some() {
+                current.skills = Some(
+                    skills
+                        .values()
+                        .filter(|s| s.apps.is_enabled_for(&app))
+                        .map(|s| s.id.clone())
+                        .collect(),
+                );
+            }
+
+            normalize_set(&mut current.mcp_servers);
+            normalize_set(&mut current.skills);
+            snapshot.insert(app_key.clone(), current);
+        }
+        Ok(snapshot)
+    }
+
+    fn apply_selections(
+        state: &AppState,
+        selections: &ProfileSelections,
+        warnings: &mut Vec<String>,
+    ) -> Result<(), AppError> {
+        for (app_key, selection) in selections {
+            let app = AppType::from_str(app_key)?;
+
+            if let Some(provider_id) = &selection.provider_id {
+                let current = crate::settings::get_effective_current_provider(&state.db, &app)?;
+                if current.as_deref() != Some(provider_id.as_str()) {
+                    let result = ProviderService::switch(state, app.clone(), provider_id)?;
+                    warnings.extend(result.warnings);
+                }
+            }
+
+            if let Some(ids) = &selection.mcp_servers {
+                for server in state.db.get_all_mcp_servers()?.values() {
+                    let wanted = ids.contains(&server.id);
+                    if server.apps.is_enabled_for(&app) != wanted {
+                        McpService::toggle_app(state, &server.id, app.clone(), wanted)?;
+                    }
+                }
+            }
+
+            if let Some(ids) = &selection.prompts {
+                Self::apply_prompts(state, &app, ids)?;
+            }
+
+            if let Some(ids) = &selection.skills {
+                for skill in state.db.get_all_installed_skills()?.values() {
+                    let wanted = ids.contains(&skill.id);
+                    if skill.apps.is_enabled_for(&app) != wanted {
+                        SkillService::toggle_app(&state.db, &skill.id, &app, wanted)
+                            .map_err(|e| AppError::Message(format!("{e:#}")))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_prompts(state: &AppState, app: &AppType, ids: &[String]) -> Result<(), AppError> {
+        let stack_config = PromptService::get_stack_config(state)?;
+        let stack = stack_config
+            .stacks
+            .get(app.as_str())
+            .filter(|ids| !ids.is_empty());
+
+        match ids {
+            [] => {
+                if stack.is_some() {
+                    PromptService::set_prompt_stack(state, app.clone(), Vec::new())?;
+                }
+                for prompt in state.db.get_prompts(app.as_str())?.into_values() {
+                    if prompt.enabled {
+                        let id = prompt.id.clone();
+                        PromptService::upsert_prompt(
+                            state,
+                            app.clone(),
+                            &id,
+                            Prompt {
+                                enabled: false,
+                                ..prompt
+                            },
+                        )?;
+                    }
+                }
+            }
+            [id] if stack.is_none() => {
+                let enabled = state
+                    .db
+                    .get_prompts(app.as_str())?
+                    .get(id)
+                    .is_some_and(|p| p.enabled);
+                if !enabled {
+                    PromptService::enable_prompt(state, app.clone(), id)?;
+                }
+            }
+            _ => {
+                if stack.map(Vec::as_slice) != Some(ids) {
+                    PromptService::set_prompt_stack(state, app.clone(), ids.to_vec())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::{InstalledSkill, McpApps, McpServer, SkillApps};
+    use crate::database::Database;
+    use crate::provider::Provider;
+    use serde_json::{json, Value};
+    use serial_test::serial;
+    use std::env;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// 临时 HOME，切换与回滚写入的 live 文件都落在这里
+    struct TempHome {
+        dir: TempDir,
+        original_home: Option<String>,
+        original_test_home: Option<String>,
+    }
+
+    impl TempHome {
+        fn new() -> Self {
+            let dir = TempDir::new().expect("failed to create temp home");
+            let original_home = env::var("HOME").ok();
+            let original_test_home = env::var("CC_SWITCH_TEST_HOME").ok();
+            env::set_var("HOME", dir.path());
+            env::set_var("CC_SWITCH_TEST_HOME", dir.path());
+            Self {
+                dir,
+                original_home,
+                original_test_home,
+            }
+        }
+    }
+
+    impl Drop for TempHome {
+        fn drop(&mut self) {
+            match &self.original_home {
+                Some(value) => env::set_var("HOME", value),
+                None => env::remove_var("HOME"),
+            }
+            match &self.original_test_home {
+                Some(value) => env::set_var("CC_SWITCH_TEST_HOME", value),
+                None => env::remove_var("CC_SWITCH_TEST_HOME"),
+            }
+        }
+    }
+
+    fn state_with_providers() -> Result<AppState, AppError> {
+        let db = Arc::new(Database::memory()?);
+        for id in ["work", "personal"] {
+            let provider = Provider::with_id(
+                id.to_string(),
+                id.to_string(),
+                json!({ "env": { "ANTHROPIC_BASE_URL": format!("https://{id}.example.com") } }),
+                None,
+            );
+            db.save_provider("claude", &provider)?;
+        }
+        Ok(AppState::new(db))
+    }
+
+    /// 当前状态：Claude 使用 work，启用 srv-a、提示词 p1；good / broken 两个 Skill 均未启用。
+    /// broken 在 SSOT 中没有目录，启用它会失败
+    fn seeded_state(home: &TempHome) -> Result<AppState, AppError> {
+        let state = state_with_providers()?;
+        std::fs::create_dir_all(home.dir.path().join(".claude"))
+            .map_err(|e| AppError::io(home.dir.path(), e))?;
+        ProviderService::switch(&state, AppType::Claude, "work")?;
+
+        for (id, claude) in [("srv-a", true), ("srv-b", false)] {
+            state.db.save_mcp_server(&McpServer {
+                id: id.to_string(),
+                name: id.to_string(),
+                server: json!({ "type": "stdio", "command": "echo" }),
+                apps: McpApps {
+                    claude,
+                    ..Default::default()
+                },
+                description: None,
+                homepage: None,
+                docs: None,
+                tags: Vec::new(),
+            })?;
+        }
+        for (id, enabled) in [("p1", true), ("p2", false)] {
+            state.db.save_prompt(
+                "claude",
+                &Prompt {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    content: format!("prompt {id}"),
+                    description: None,
+                    enabled,
+                    created_at: None,
+                    updated_at: None,
+                },
+            )?;
+        }
+
+        let ssot = SkillService::get_ssot_dir().map_err(|e| AppError::Message(e.to_string()))?;
+        let good = ssot.join("good");
+        std::fs::create_dir_all(&good).map_err(|e| AppError::io(&good, e))?;
+        std::fs::write(
+            good.join("SKILL.md"),
+            "---\nname: good\ndescription: d\n---\n",
+        )
+        .map_err(|e| AppError::io(&good, e))?;
+        for id in ["good", "broken"] {
+            state.db.save_skill(&InstalledSkill {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: None,
+                directory: id.to_string(),
+                repo_owner: None,
+                repo_name: None,
+                repo_branch: None,
+                readme_url: None,
+                apps: SkillApps::default(),
+                installed_at: 0,
+                content_hash: None,
+                updated_at: 0,
+                source: None,
+            })?;
+        }
+        Ok(state)
+    }
+
+    fn claude_state(state: &AppState) -> Result<ProfileAppSelection, AppError> {
+        let all = ProfileAppSelection {
+            provider_id: Some(String::new()),
+            mcp_servers: Some(Vec::new()),
+            prompts: Some(Vec::new()),
+            skills: Some(Vec::new()),
+        };
+        let mut captured =
+            ProfileService::capture(state, &BTreeMap::from([("claude".to_string(), all)]))?;
+        Ok(captured.remove("claude").expect("claude captured"))
+    }
+
+    fn live_base_url() -> Value {
+        let live: Value = crate::config::read_json_file(&crate::config::get_claude_settings_path())
+            .expect("read live settings");
+        live["env"]["ANTHROPIC_BASE_URL"].clone()
+    }
+
+    #[test]
+    fn save_normalizes_app_keys_and_rejects_unknown_references() -> Result<(), AppError> {
+        let state = state_with_providers()?;
+
+        let profile = Profile {
+            id: String::new(),
+            name: "  Work  ".to_string(),
+            apps: BTreeMap::from([(
+                "CLAUDE".to_string(),
+                ProfileAppSelection {
+                    provider_id: Some("work".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            sort_index: None,
+            created_at: 0,
+            updated_at: 0,
+        };
+        let saved = ProfileService::save(&state, profile.clone())?;
+        assert_eq!(saved.name, "Work");
+        assert!(saved.apps.contains_key("claude"));
+        assert!(!saved.id.is_empty());
+
+        let mut broken = profile;
+        broken.apps.insert(
+            "claude".to_string(),
+            ProfileAppSelection {
+                mcp_servers: Some(vec!["missing".to_string()]),
+                ..Default::default()
+            },
+        );
+        assert!(ProfileService::save(&state, broken).is_err());
+
+        let openclaw_mcp = Profile {
+            id: String::new(),
+            name: "OpenClaw".to_string(),
+            apps: BTreeMap::from([(
+                "openclaw".to_string(),
+                ProfileAppSelection {
+                    mcp_servers: Some(Vec::new()),
+                    ..Default::default()
+                },
+            )]),
+            sort_index: None,
+            created_at: 0,
+            updated_at: 0,
+        };
+        assert!(ProfileService::save(&state, openclaw_mcp).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn switch_backup_roundtrips_and_recovery_clears_it() -> Result<(), AppError> {
+        let state = state_with_providers()?;
+        let snapshot = BTreeMap::from([(
+            "claude".to_string(),
+            ProfileAppSelection {
+                mcp_servers: Some(Vec::new()),
+                ..Default::default()
+            },
+        )]);
+        state.db.save_profile_switch_backup("work", &snapshot)?;
+
+        let (profile_id, restored) = state
+            .db
+            .get_profile_switch_backup()?
+            .expect("backup exists");
+        assert_eq!(profile_id, "work");
+        assert_eq!(restored, snapshot);
+
+        assert!(ProfileService::recover_interrupted_switch(&state)?);
+        assert!(state.db.get_profile_switch_backup()?.is_none());
+        assert!(!ProfileService::recover_interrupted_switch(&state)?);
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn failed_apply_rolls_back_provider_mcp_prompt_and_skills() -> Result<(), AppError> {
+        let home = TempHome::new();
+        let state = seeded_state(&home)?;
+        let before = claude_state(&state)?;
+        assert_eq!(before.provider_id.as_deref(), Some("work"));
+
+        // Claude 的各项都能切换成功，随后 Codex 启用 broken 失败
+        let profile = ProfileService::save(
+            &state,
+            Profile {
+                id: String::new(),
+                name: "Broken".to_string(),
+                apps: BTreeMap::from([
+                    (
+                        "claude".to_string(),
+                        ProfileAppSelection {
+                            provider_id: Some("personal".to_string()),
+                            mcp_servers: Some(vec!["srv-b".to_string()]),
+                            prompts: Some(vec!["p2".to_string()]),
+                            skills: Some(vec!["good".to_string()]),
+                        },
+                    ),
+                    (
+                        "codex".to_string(),
+                        ProfileAppSelection {
+                            skills: Some(vec!["broken".to_string()]),
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+                sort_index: None,
+                created_at: 0,
+                updated_at: 0,
+            },
+        )?;
+
+        assert!(ProfileService::apply(&state, &profile.id).is_err());
+        assert_eq!(claude_state(&state)?, before);
+        assert_eq!(live_base_url(), json!("https://work.example.com"));
+        let skills = state.db.get_all_installed_skills()?;
+        assert!(!skills["good"].apps.is_enabled_for(&AppType::Claude));
+        assert!(!skills["broken"].apps.is_enabled_for(&AppType::Codex));
+        assert!(state.db.get_profile_switch_backup()?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn recovery_rolls_back_a_half_applied_switch() -> Result<(), AppError> {
+        let home = TempHome::new();
+        let state = seeded_state(&home)?;
+        let before = claude_state(&state)?;
+
+        // 模拟切换写入备份后、完成前进程退出
+        let snapshot = BTreeMap::from([("claude".to_string(), before.clone())]);
+        state.db.save_profile_switch_backup("crashed", &snapshot)?;
+        ProviderService::switch(&state, AppType::Claude, "personal")?;
+        McpService::toggle_app(&state, "srv-b", AppType::Claude, true)?;
+        PromptService::enable_prompt(&state, AppType::Claude, "p2")?;
+        SkillService::toggle_app(&state.db, "good", &AppType::Claude, true)
+            .map_err(|e| AppError::Message(e.to_string()))?;
+        assert_eq!(live_base_url(), json!("https://personal.example.com"));
+
+        assert!(ProfileService::recover_interrupted_switch(&state)?);
+        assert_eq!(claude_state(&state)?, before);
+        assert_eq!(live_base_url(), json!("https://work.example.com"));
+        assert!(!state.db.get_all_installed_skills()?["good"]
+            .apps
+            .is_enabled_for(&AppType::Claude));
+        assert!(state.db.get_profile_switch_backup()?.is_none());
+        Ok(())
+    }
+}
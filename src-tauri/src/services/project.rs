@@ -0,0 +1,893 @@
+//! 项目作用域
+//!
+//! 为登记的项目目录单独维护 MCP 服务器、提示词与 Skills 的启用集合，写入项目级文件
+//! （`.mcp.json`、`.codex/config.toml`、仓库根目录的 `CLAUDE.md` / `AGENTS.md` /
+//! `GEMINI.md`、`.claude/skills` 等），不影响用户级配置。
+//!
+//! 提示词写入文件中的托管区块，仓库已有的内容保持不变。
+//!
+//! 写入前可预览差异；每次写入都会记录文件内容哈希，之后若有人（例如队友提交）改动了
+//! 这些文件，会作为漂移报告出来，默认拒绝覆盖。仓库中已存在但尚未由 CC Switch 写入过的
+//! 提示词文件与 Skill 目录同样视为漂移，首次写入前需要确认。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::app_config::AppType;
+use crate::config::write_text_file;
+use crate::error::AppError;
+use crate::mcp;
+use crate::prompt_template::{splice_managed_block, TemplateContext};
+use crate::services::skill::SkillService;
+use crate::services::text_diff::unified_diff;
+use crate::store::AppState;
+
+/// 项目中某个应用的启用集合
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectAppSelection {
+    /// 启用的 MCP 服务器 ID
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+    /// 写入项目提示词文件的提示词 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_id: Option<String>,
+    /// 复制到项目 skills 目录的 Skill ID
+    #[serde(default)]
+    pub skills: Vec<String>,
+}
+
+/// 登记的项目目录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectScope {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// 项目根目录（绝对路径）
+    pub path: String,
+    /// 应用 ID -> 启用集合
+    #[serde(default)]
+    pub apps: BTreeMap<String, ProjectAppSelection>,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// 上次写入记录，用于漂移检测与清理
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectAppliedState {
+    pub applied_at: i64,
+    /// 相对路径 -> 写入后的内容哈希（Skill 目录为目录哈希）
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    /// MCP 配置文件相对路径 -> 由 CC Switch 写入的服务器 ID
+    #[serde(default)]
+    pub managed_mcp: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectFileKind {
+    Mcp,
+    Prompt,
+    Skill,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectFileAction {
+    Create,
+    Update,
+    Delete,
+    Unchanged,
+}
+
+/// 单个项目文件的计划变更
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFileChange {
+    /// 相对项目根目录的路径
+    pub path: String,
+    pub kind: ProjectFileKind,
+    pub action: ProjectFileAction,
+    /// 文本文件的 unified diff；Skill 目录为空
+    #[serde(default)]
+    pub diff: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectDriftKind {
+    /// 上次写入后内容被修改
+    Modified,
+    /// 上次写入后被删除
+    Missing,
+    /// 已存在于项目中，但尚未由 CC Switch 写入过
+    Untracked,
+}
+
+/// 上次写入后被外部改动的文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDrift {
+    pub path: String,
+    pub kind: ProjectDriftKind,
+}
+
+/// 写入预览
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectPreview {
+    pub project_id: String,
+    pub changes: Vec<ProjectFileChange>,
+    pub drift: Vec<ProjectDrift>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum McpFileFormat {
+    /// `.mcp.json`：`mcpServers`
+    Claude,
+    /// `.codex/config.toml`：`[mcp_servers]`
+    Codex,
+    /// `.gemini/settings.json` / `.qwen/settings.json`：`mcpServers`
+    Gemini,
+    /// `opencode.json`：`mcp`
+    OpenCode,
+}
+
+/// 各应用在项目中的文件位置
+struct ProjectLayout {
+    mcp: Option<(&'static str, McpFileFormat)>,
+    prompt: Option<&'static str>,
+    skills: Option<&'static str>,
+}
+
+fn project_layout(app: &AppType) -> Option<ProjectLayout> {
+    match app {
+        AppType::Claude => Some(ProjectLayout {
+            mcp: Some((".mcp.json", McpFileFormat::Claude)),
+            prompt: Some("CLAUDE.md"),
+            skills: Some(".claude/skills"),
+        }),
+        AppType::Codex => Some(ProjectLayout {
+            mcp: Some((".codex/config.toml", McpFileFormat::Codex)),
+            prompt: Some("AGENTS.md"),
+            skills: None,
+        }),
+        AppType::Gemini => Some(ProjectLayout {
+            mcp: Some((".gemini/settings.json", McpFileFormat::Gemini)),
+            prompt: Some("GEMINI.md"),
+            skills: None,
+        }),
+        AppType::OpenCode => Some(ProjectLayout {
+            mcp: Some(("opencode.json", McpFileFormat::OpenCode)),
+            prompt: Some("AGENTS.md"),
+            skills: None,
+        }),
+        AppType::Qwen => Some(ProjectLayout {
+            mcp: Some((".qwen/settings.json", McpFileFormat::Gemini)),
+            prompt: Some("QWEN.md"),
+            skills: None,
+        }),
+        AppType::ClaudeDesktop
+        | AppType::OpenClaw
+        | AppType::Hermes
+        | AppType::Goose
+        | AppType::Crush
+        | AppType::Aider => None,
+    }
+}
+
+enum PlannedContent {
+    Text(String),
+    /// 从 SSOT 复制的 Skill 目录
+    SkillDir {
+        source: PathBuf,
+        directory: String,
+    },
+    /// 不再启用、需要移除的 Skill 目录
+    Remove,
+}
+
+struct PlannedFile {
+    kind: ProjectFileKind,
+    content: PlannedContent,
+    warnings: Vec<String>,
+    /// MCP 文件中由 CC Switch 管理的服务器 ID
+    managed_mcp: Option<Vec<String>>,
+}
+
+type Plan = BTreeMap<String, PlannedFile>;
+
+fn hash_text(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// 计算路径当前内容哈希；不存在时返回 None
+fn hash_path(path: &Path) -> Option<String> {
+    if path.is_dir() {
+        SkillService::compute_dir_hash(path).ok()
+    } else {
+        fs::read_to_string(path).ok().map(|c| hash_text(&c))
+    }
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+pub struct ProjectService;
+
+impl ProjectService {
+    pub fn list(state: &AppState) -> Result<Vec<ProjectScope>, AppError> {
+        state.db.list_projects()
+    }
+
+    /// 新增或更新项目
+    pub fn save(state: &AppState, mut project: ProjectScope) -> Result<ProjectScope, AppError> {
+        project.name = project.name.trim().to_string();
+        project.path = project.path.trim().to_string();
+        if project.name.is_empty() {
+            return Err(AppError::InvalidInput("项目名称不能为空".to_string()));
+        }
+        let root = Path::new(&project.path);
+        if !root.is_absolute() || !root.is_dir() {
+            return Err(AppError::InvalidInput(format!(
+                "项目目录不存在或不是绝对路径: {}",
+                project.path
+            )));
+        }
+
+        // 统一应用键为规范 ID，写入时按 `AppType::as_str()` 查找
+        let mut apps = BTreeMap::new();
+        for (app_key, selection) in std::mem::take(&mut project.apps) {
+            let app = AppType::from_str(&app_key)?;
+            let layout = project_layout(&app).ok_or_else(|| {
+                AppError::InvalidInput(format!("{} 不支持项目级配置", app.as_str()))
+            })?;
+            if !selection.skills.is_empty() && layout.skills.is_none() {
+                return Err(AppError::InvalidInput(format!(
+                    "{} 不支持项目级 Skills",
+                    app.as_str()
+                )));
+            }
+            apps.insert(app.as_str().to_string(), selection);
+        }
+        project.apps = apps;
+
+        let existing = state.db.list_projects()?;
+        if existing
+            .iter()
+            .any(|p| p.path == project.path && p.id != project.id)
+        {
+            return Err(AppError::InvalidInput(format!(
+                "该目录已登记为项目: {}",
+                project.path
+            )));
+        }
+
+        let now = now_secs();
+        if project.id.is_empty() {
+            project.id = uuid::Uuid::new_v4().to_string();
+        }
+        project.created_at = existing
+            .iter()
+            .find(|p| p.id == project.id)
+            .map(|p| p.created_at)
+            .unwrap_or(now);
+        project.updated_at = now;
+
+        state.db.save_project(&project)?;
+        Ok(project)
+    }
+
+    /// 删除项目登记；项目目录中已写入的文件保持不变
+    pub fn delete(state: &AppState, id: &str) -> Result<bool, AppError> {
+        state.db.delete_project(id)
+    }
+
+    /// 预览写入项目文件的变更
+    pub fn preview(state: &AppState, id: &str) -> Result<ProjectPreview, AppError> {
+        let (project, root, applied) = Self::load(state, id)?;
+        let plan = Self::build_plan(state, &project, &root, &applied)?;
+        Ok(ProjectPreview {
+            project_id: project.id,
+            changes: Self::describe(&root, &plan),
+            drift: Self::detect_drift(&root, &applied, &plan),
+        })
+    }
+
+    /// 检查项目文件是否在上次写入后被改动
+    pub fn check_drift(state: &AppState, id: &str) -> Result<Vec<ProjectDrift>, AppError> {
+        let (project, root, applied) = Self::load(state, id)?;
+        let plan = Self::build_plan(state, &project, &root, &applied)?;
+        Ok(Self::detect_drift(&root, &applied, &plan))
+    }
+
+    /// 写入项目文件
+    ///
+    /// 存在漂移时需要 `force` 才会覆盖，避免冲掉他人的修改。
+    pub fn apply(
+        state: &AppState,
+        id: &str,
+        force: bool,
+    ) -> Result<Vec<ProjectFileChange>, AppError> {
+        let (project, root, applied) = Self::load(state, id)?;
+        let plan = Self::build_plan(state, &project, &root, &applied)?;
+        let drift = Self::detect_drift(&root, &applied, &plan);
+        if !force && !drift.is_empty() {
+            let paths: Vec<&str> = drift.iter().map(|d| d.path.as_str()).collect();
+            return Err(AppError::InvalidInput(format!(
+                "以下项目文件在上次写入后已被修改，请先预览差异再确认覆盖: {}",
+                paths.join(", ")
+            )));
+        }
+
+        let changes = Self::describe(&root, &plan);
+
+        let mut next = ProjectAppliedState {
+            applied_at: now_secs(),
+            ..Default::default()
+        };
+        for (rel, planned) in &plan {
+            let target = root.join(rel);
+            match &planned.content {
+                PlannedContent::Text(content) => {
+                    if fs::read_to_string(&target).ok().as_deref() != Some(content.as_str()) {
+                        write_text_file(&target, content)?;
+                    }
+                    next.files.insert(rel.clone(), hash_text(content));
+                }
+                PlannedContent::SkillDir { source, directory } => {
+                    if hash_path(&target) != hash_path(source) {
+                        SkillService::replace_dest_with_copy(source, &target, directory).map_err(
+                            |e| AppError::Message(format!("复制 Skill {directory} 失败: {e:#}")),
+                        )?;
+                    }
+                    if let Some(hash) = hash_path(&target) {
+                        next.files.insert(rel.clone(), hash);
+                    }
+                }
+                PlannedContent::Remove => {
+                    if target.exists() {
+                        SkillService::remove_path(&target)
+                            .map_err(|e| AppError::Message(format!("删除 {rel} 失败: {e:#}")))?;
+                    }
+                }
+            }
+            if let Some(ids) = planned.managed_mcp.as_ref().filter(|ids| !ids.is_empty()) {
+                next.managed_mcp.insert(rel.clone(), ids.clone());
+            }
+        }
+
+        state.db.save_project_applied_state(&project.id, &next)?;
+        log::info!(
+            "已写入项目 {} 的配置: {} 个文件",
+            project.name,
+            changes
+                .iter()
+                .filter(|c| c.action != ProjectFileAction::Unchanged)
+                .count()
+        );
+        Ok(changes)
+    }
+
+    fn load(
+        state: &AppState,
+        id: &str,
+    ) -> Result<(ProjectScope, PathBuf, ProjectAppliedState), AppError> {
+        let project = state
+            .db
+            .get_project(id)?
+            .ok_or_else(|| AppError::InvalidInput(format!("项目不存在: {id}")))?;
+        let root = PathBuf::from(&project.path);
+        if !root.is_dir() {
+            return Err(AppError::InvalidInput(format!(
+                "项目目录不存在: {}",
+                project.path
+            )));
+        }
+        let applied = state.db.get_project_applied_state(id)?.unwrap_or_default();
+        Ok((project, root, applied))
+    }
+
+    fn build_plan(
+        state: &AppState,
+        project: &ProjectScope,
+        root: &Path,
+        applied: &ProjectAppliedState,
+    ) -> Result<Plan, AppError> {
+        let servers = state.db.get_all_mcp_servers()?;
+        let variables = state.db.get_prompt_stack_config()?.variables;
+        let mut plan = Plan::new();
+
+        for app in AppType::all() {
+            let Some(layout) = project_layout(&app) else {
+                continue;
+            };
+            let selection = project.apps.get(app.as_str()).cloned().unwrap_or_default();
+
+            // MCP：只增删自己管理的条目，保留文件中的其他服务器
+            if let Some((rel, format)) = layout.mcp {
+                let previous = applied.managed_mcp.get(rel).cloned().unwrap_or_default();
+                if !selection.mcp_servers.is_empty() || !previous.is_empty() {
+                    let mut specs = Vec::new();
+                    let mut warnings = Vec::new();
+                    for id in &selection.mcp_servers {
+                        let server = servers.get(id).ok_or_else(|| {
+                            AppError::InvalidInput(format!("MCP 服务器不存在: {id}"))
+                        })?;
+                        for r in mcp::find_secret_refs(&server.server) {
+                            warnings.push(format!(
+                                "{id} 的 {} 引用了 {}，项目文件中保留占位符",
+                                r.field, r.name
+                            ));
+                        }
+                        specs.push((id.clone(), server.server.clone()));
+                    }
+                    let existing = fs::read_to_string(root.join(rel)).ok();
+                    let content = render_mcp(format, rel, existing.as_deref(), &previous, &specs)?;
+                    plan.insert(
+                        rel.to_string(),
+                        PlannedFile {
+                            kind: ProjectFileKind::Mcp,
+                            content: PlannedContent::Text(content),
+                            warnings,
+                            managed_mcp: Some(selection.mcp_servers.clone()),
+                        },
+                    );
+                }
+            }
+
+            // 提示词：取消选择时不删除仓库中的文件，只是不再跟踪
+            if let (Some(rel), Some(prompt_id)) = (layout.prompt, selection.prompt_id.as_ref()) {
+                let prompt = state
+                    .db
+                    .get_prompts(app.as_str())?
+                    .shift_remove(prompt_id)
+                    .ok_or_else(|| AppError::InvalidInput(format!("提示词不存在: {prompt_id}")))?;
+                let rendered = TemplateContext::new(&app, Some(&project.name), &variables)
+                    .render(&prompt.content);
+                // 只替换托管区块，保留仓库中已提交的内容
+                let existing = fs::read_to_string(root.join(rel)).ok();
+                let content = splice_managed_block(existing.as_deref(), &rendered);
+                // Codex 与 OpenCode 共用 AGENTS.md，内容必须一致
+                if let Some(other) = plan.get(rel) {
+                    let same = matches!(&other.content, PlannedContent::Text(c) if *c == content);
+                    if !same {
+                        return Err(AppError::InvalidInput(format!(
+                            "多个应用向 {rel} 写入了不同的提示词，请为它们选择同一个提示词"
+                        )));
+                    }
+                } else {
+                    plan.insert(
+                        rel.to_string(),
+                        PlannedFile {
+                            kind: ProjectFileKind::Prompt,
+                            content: PlannedContent::Text(content),
+                            warnings: Vec::new(),
+                            managed_mcp: None,
+                        },
+                    );
+                }
+            }
+
+            // Skills：复制而非 symlink，便于随仓库提交
+            if let Some(skills_rel) = layout.skills {
+                let mut selected = Vec::new();
+                if !selection.skills.is_empty() {
+                    let installed = state.db.get_all_installed_skills()?;
+                    let ssot = SkillService::get_ssot_dir()
+                        .map_err(|e| AppError::Message(format!("{e:#}")))?;
+                    for id in &selection.skills {
+                        let skill = installed
+                            .get(id)
+                            .ok_or_else(|| AppError::InvalidInput(format!("Skill 不存在: {id}")))?;
+                        let rel = format!("{skills_rel}/{}", skill.directory);
+                        selected.push(rel.clone());
+                        plan.insert(
+                            rel,
+                            PlannedFile {
+                                kind: ProjectFileKind::Skill,
+                                content: PlannedContent::SkillDir {
+                                    source: ssot.join(&skill.directory),
+                                    directory: skill.directory.clone(),
+                                },
+                                warnings: Vec::new(),
+                                managed_mcp: None,
+                            },
+                        );
+                    }
+                }
+
+                let prefix = format!("{skills_rel}/");
+                for rel in applied.files.keys() {
+                    if rel.starts_with(&prefix) && !selected.contains(rel) {
+                        plan.insert(
+                            rel.clone(),
+                            PlannedFile {
+                                kind: ProjectFileKind::Skill,
+                                content: PlannedContent::Remove,
+                                warnings: Vec::new(),
+                                managed_mcp: None,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    fn describe(root: &Path, plan: &Plan) -> Vec<ProjectFileChange> {
+        plan.iter()
+            .map(|(rel, planned)| {
+                let target = root.join(rel);
+                let (action, diff) = match &planned.content {
+                    PlannedContent::Text(after) => match fs::read_to_string(&target) {
+                        Ok(before) if before == *after => {
+                            (ProjectFileAction::Unchanged, String::new())
+                        }
+                        Ok(before) => (
+                            ProjectFileAction::Update,
+                            unified_diff(&before, after, &format!("a/{rel}"), &format!("b/{rel}")),
+                        ),
+                        Err(_) => (
+                            ProjectFileAction::Create,
+                            unified_diff("", after, "/dev/null", &format!("b/{rel}")),
+                        ),
+                    },
+                    PlannedContent::SkillDir { source, .. } => {
+                        let action = match hash_path(&target) {
+                            None => ProjectFileAction::Create,
+                            Some(hash) if Some(&hash) == hash_path(source).as_ref() => {
+                                ProjectFileAction::Unchanged
+                            }
+                            Some(_) => ProjectFileAction::Update,
+                        };
+                        (action, String::new())
+                    }
+                    PlannedContent::Remove if target.exists() => {
+                        (ProjectFileAction::Delete, String::new())
+                    }
+                    PlannedContent::Remove => (ProjectFileAction::Unchanged, String::new()),
+                };
+                ProjectFileChange {
+                    path: rel.clone(),
+                    kind: planned.kind,
+                    action,
+                    diff,
+                    warnings: planned.warnings.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn detect_drift(root: &Path, applied: &ProjectAppliedState, plan: &Plan) -> Vec<ProjectDrift> {
+        let mut drift: Vec<ProjectDrift> = applied
+            .files
+            .iter()
+            .filter_map(|(rel, recorded)| match hash_path(&root.join(rel)) {
+                None => Some(ProjectDrift {
+                    path: rel.clone(),
+                    kind: ProjectDriftKind::Missing,
+                }),
+                Some(current) if current != *recorded => Some(ProjectDrift {
+                    path: rel.clone(),
+                    kind: ProjectDriftKind::Modified,
+                }),
+                Some(_) => None,
+            })
+            .collect();
+
+        // MCP 文件本身按条目合并，不会覆盖他人的配置，无需确认
+        let untracked = plan.iter().filter(|(rel, planned)| {
+            if planned.kind == ProjectFileKind::Mcp || applied.files.contains_key(*rel) {
+                return false;
+            }
+            let target = root.join(rel);
+            match &planned.content {
+                PlannedContent::Text(content) => {
+                    fs::read_to_string(&target).is_ok_and(|current| current != *content)
+                }
+                PlannedContent::SkillDir { source, .. } => {
+                    hash_path(&target).is_some_and(|current| Some(current) != hash_path(source))
+                }
+                PlannedContent::Remove => false,
+            }
+        });
+        drift.extend(untracked.map(|(rel, _)| ProjectDrift {
+            path: rel.clone(),
+            kind: ProjectDriftKind::Untracked,
+        }));
+        drift
+    }
+}
+
+/// 在现有项目 MCP 文件基础上，移除上次管理的条目并写入当前选择
+fn render_mcp(
+    format: McpFileFormat,
+    rel: &str,
+    existing: Option<&str>,
+    previous: &[String],
+    servers: &[(String, Value)],
+) -> Result<String, AppError> {
+    let existing = existing.filter(|c| !c.trim().is_empty());
+
+    if format == McpFileFormat::Codex {
+        let mut doc = match existing {
+            Some(content) => content
+                .parse::<toml_edit::DocumentMut>()
+                .map_err(|e| AppError::Config(format!("解析 {rel} 失败: {e}")))?,
+            None => toml_edit::DocumentMut::new(),
+        };
+        if !doc.contains_key("mcp_servers") {
+            doc["mcp_servers"] = toml_edit::table();
+        }
+        let table = doc["mcp_servers"]
+            .as_table_mut()
+            .ok_or_else(|| AppError::Config(format!("{rel} 中 mcp_servers 不是表")))?;
+        table.set_implicit(true);
+        for id in previous {
+            table.remove(id);
+        }
+        for (id, spec) in servers {
+            table.insert(
+                id,
+                toml_edit::Item::Table(mcp::json_server_to_toml_table(spec)?),
+            );
+        }
+        return Ok(doc.to_string());
+    }
+
+    let mut root: Value = match existing {
+        Some(content) => serde_json::from_str(content)
+            .map_err(|e| AppError::Config(format!("解析 {rel} 失败: {e}")))?,
+        None => json!({}),
+    };
+    let key = if format == McpFileFormat::OpenCode {
+        "mcp"
+    } else {
+        "mcpServers"
+    };
+    let map = root
+        .as_object_mut()
+        .ok_or_else(|| AppError::Config(format!("{rel} 根必须是对象")))?
+        .entry(key)
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or_else(|| AppError::Config(format!("{rel} 中 {key} 不是对象")))?;
+    for id in previous {
+        map.remove(id);
+    }
+    for (id, spec) in servers {
+        let entry = match format {
+            McpFileFormat::Gemini => crate::gemini_mcp::to_gemini_server_spec(id, spec)?,
+            McpFileFormat::OpenCode => mcp::convert_to_opencode_format(spec)?,
+            McpFileFormat::Claude | McpFileFormat::Codex => spec.clone(),
+        };
+        map.insert(id.clone(), entry);
+    }
+
+    let mut out =
+        serde_json::to_string_pretty(&root).map_err(|e| AppError::JsonSerialize { source: e })?;
+    out.push('\n');
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::{McpApps, McpServer};
+    use crate::database::Database;
+    use crate::prompt::Prompt;
+    use std::sync::Arc;
+
+    fn setup() -> (AppState, tempfile::TempDir) {
+        let db = Arc::new(Database::memory().expect("memory db"));
+        db.save_mcp_server(&McpServer {
+            id: "github".to_string(),
+            name: "GitHub".to_string(),
+            server: json!({
+                "type": "stdio",
+                "command": "github-mcp",
+                "env": { "GITHUB_TOKEN": "${secret:github_token}" }
+            }),
+            apps: McpApps::default(),
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        })
+        .expect("save server");
+        db.save_prompt(
+            "claude",
+            &Prompt {
+                id: "repo".to_string(),
+                name: "Repo rules".to_string(),
+                content: "# Rules\n".to_string(),
+                description: None,
+                enabled: false,
+                created_at: None,
+                updated_at: None,
+            },
+        )
+        .expect("save prompt");
+        (AppState::new(db), tempfile::tempdir().expect("tempdir"))
+    }
+
+    fn register(state: &AppState, root: &Path) -> ProjectScope {
+        let mut apps = BTreeMap::new();
+        apps.insert(
+            "claude".to_string(),
+            ProjectAppSelection {
+                mcp_servers: vec!["github".to_string()],
+                prompt_id: Some("repo".to_string()),
+                skills: Vec::new(),
+            },
+        );
+        apps.insert(
+            "codex".to_string(),
+            ProjectAppSelection {
+                mcp_servers: vec!["github".to_string()],
+                ..Default::default()
+            },
+        );
+        ProjectService::save(
+            state,
+            ProjectScope {
+                id: String::new(),
+                name: "demo".to_string(),
+                path: root.to_string_lossy().to_string(),
+                apps,
+                created_at: 0,
+                updated_at: 0,
+            },
+        )
+        .expect("save project")
+    }
+
+    #[test]
+    fn preview_then_apply_writes_project_files() {
+        let (state, dir) = setup();
+        let project = register(&state, dir.path());
+
+        let preview = ProjectService::preview(&state, &project.id).expect("preview");
+        let paths: Vec<&str> = preview.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec![".codex/config.toml", ".mcp.json", "CLAUDE.md"]);
+        assert!(preview
+            .changes
+            .iter()
+            .all(|c| c.action == ProjectFileAction::Create));
+        assert!(preview.changes[1].diff.contains("+    \"github\": {"));
+        assert_eq!(preview.changes[1].warnings.len(), 1);
+        assert!(
+            !dir.path().join(".mcp.json").exists(),
+            "preview must not write"
+        );
+
+        ProjectService::apply(&state, &project.id, false).expect("apply");
+        let claude: Value = serde_json::from_str(
+            &fs::read_to_string(dir.path().join(".mcp.json")).expect("read .mcp.json"),
+        )
+        .expect("parse");
+        // 项目文件中保留占位符，不写入密钥值
+        assert_eq!(
+            claude["mcpServers"]["github"]["env"]["GITHUB_TOKEN"],
+            "${secret:github_token}"
+        );
+        let codex = fs::read_to_string(dir.path().join(".codex/config.toml")).expect("read toml");
+        assert!(codex.contains("[mcp_servers.github]"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("CLAUDE.md")).expect("read prompt"),
+            splice_managed_block(None, "# Rules\n")
+        );
+
+        let again = ProjectService::preview(&state, &project.id).expect("preview again");
+        assert!(again
+            .changes
+            .iter()
+            .all(|c| c.action == ProjectFileAction::Unchanged));
+        assert!(again.drift.is_empty());
+    }
+
+    #[test]
+    fn drift_blocks_apply_unless_forced() {
+        let (state, dir) = setup();
+        let project = register(&state, dir.path());
+        ProjectService::apply(&state, &project.id, false).expect("apply");
+
+        fs::write(dir.path().join("CLAUDE.md"), "# Edited by a teammate\n").expect("edit");
+        fs::remove_file(dir.path().join(".codex/config.toml")).expect("remove");
+
+        let drift = ProjectService::check_drift(&state, &project.id).expect("drift");
+        assert_eq!(drift.len(), 2);
+        assert_eq!(drift[0].path, ".codex/config.toml");
+        assert_eq!(drift[0].kind, ProjectDriftKind::Missing);
+        assert_eq!(drift[1].kind, ProjectDriftKind::Modified);
+
+        assert!(ProjectService::apply(&state, &project.id, false).is_err());
+        ProjectService::apply(&state, &project.id, true).expect("forced apply");
+        assert!(ProjectService::check_drift(&state, &project.id)
+            .expect("drift after force")
+            .is_empty());
+    }
+
+    #[test]
+    fn existing_prompt_file_is_drift_and_kept_on_apply() {
+        let (state, dir) = setup();
+        fs::write(dir.path().join("CLAUDE.md"), "# Team conventions\n").expect("seed");
+        let project = register(&state, dir.path());
+
+        let drift = ProjectService::check_drift(&state, &project.id).expect("drift");
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].path, "CLAUDE.md");
+        assert_eq!(drift[0].kind, ProjectDriftKind::Untracked);
+        assert!(ProjectService::apply(&state, &project.id, false).is_err());
+
+        ProjectService::apply(&state, &project.id, true).expect("forced apply");
+        let content = fs::read_to_string(dir.path().join("CLAUDE.md")).expect("read prompt");
+        assert!(content.starts_with("# Team conventions\n"));
+        assert!(content.contains("# Rules"));
+        assert!(ProjectService::check_drift(&state, &project.id)
+            .expect("drift after apply")
+            .is_empty());
+    }
+
+    #[test]
+    fn deselecting_servers_keeps_unmanaged_entries() {
+        let (state, dir) = setup();
+        fs::write(
+            dir.path().join(".mcp.json"),
+            "{\n  \"mcpServers\": {\n    \"team\": { \"command\": \"team-mcp\" }\n  }\n}\n",
+        )
+        .expect("seed");
+        let mut project = register(&state, dir.path());
+        ProjectService::apply(&state, &project.id, false).expect("apply");
+
+        project
+            .apps
+            .get_mut("claude")
+            .expect("claude")
+            .mcp_servers
+            .clear();
+        ProjectService::save(&state, project.clone()).expect("save");
+        ProjectService::apply(&state, &project.id, false).expect("apply again");
+
+        let claude: Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join(".mcp.json")).expect("read"))
+                .expect("parse");
+        assert!(claude["mcpServers"].get("github").is_none());
+        assert_eq!(claude["mcpServers"]["team"]["command"], "team-mcp");
+    }
+
+    #[test]
+    fn rejects_unsupported_apps_and_missing_dirs() {
+        let (state, dir) = setup();
+        let mut project = register(&state, dir.path());
+
+        project.apps.insert(
+            "codex".to_string(),
+            ProjectAppSelection {
+                skills: vec!["some-skill".to_string()],
+                ..Default::default()
+            },
+        );
+        assert!(ProjectService::save(&state, project.clone()).is_err());
+
+        project.apps.clear();
+        project.path = dir.path().join("missing").to_string_lossy().to_string();
+        assert!(ProjectService::save(&state, project).is_err());
+    }
+}
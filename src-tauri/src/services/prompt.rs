@@ -1,10 +1,16 @@
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
 use crate::app_config::AppType;
 use crate::config::write_text_file;
 use crate::error::AppError;
-use crate::prompt::Prompt;
+use crate::prompt::{Prompt, SharedPrompt};
 use crate::prompt_files::prompt_file_path;
+use crate::prompt_template::{
+    has_managed_block, remove_managed_block, splice_managed_block, TemplateContext,
+};
 use crate::store::AppState;
 
 /// 安全地获取当前 Unix 时间戳
@@ -15,6 +21,46 @@ fn get_unix_timestamp() -> Result<i64, AppError> {
         .map_err(|e| AppError::Message(format!("Failed to get system time: {e}")))
 }
 
+/// 共享提示词写入各应用时使用的 ID 前缀，用于区分用户在单个应用下创建的提示词
+pub const SHARED_PROMPT_ID_PREFIX: &str = "shared:";
+
+/// 共享提示词同步结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedPromptSyncResult {
+    /// 已写入副本的应用
+    pub synced: Vec<String>,
+    /// 单提示词模式下写入了副本但未启用的应用（调用方未要求 `activate`）
+    pub inactive: Vec<String>,
+    /// 因 `activate` 被替换掉的原启用提示词：应用 -> 提示词 ID
+    pub replaced: BTreeMap<String, String>,
+}
+
+/// 单个应用写入共享提示词副本后的启用状态
+enum AttachOutcome {
+    Active,
+    Inactive,
+    Replaced(String),
+}
+
+/// 多片段提示词配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptStackConfig {
+    /// 应用 ID -> 按顺序叠加的提示词 ID；不存在或为空表示单一提示词模式
+    #[serde(default)]
+    pub stacks: BTreeMap<String, Vec<String>>,
+    /// 自定义模板变量
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+}
+
+impl PromptStackConfig {
+    fn active_stack(&self, app: &AppType) -> Option<&Vec<String>> {
+        self.stacks.get(app.as_str()).filter(|ids| !ids.is_empty())
+    }
+}
+
 pub struct PromptService;
 
 impl PromptService {
@@ -29,8 +75,20 @@ impl PromptService {
         state: &AppState,
         app: AppType,
         _id: &str,
-        prompt: Prompt,
+        mut prompt: Prompt,
     ) -> Result<(), AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("promptSync");
+        // 多片段模式：启用状态由片段列表决定，片段内容变化时重新渲染托管区块
+        let config = state.db.get_prompt_stack_config()?;
+        if let Some(stack) = config.active_stack(&app) {
+            prompt.enabled = stack.contains(&prompt.id);
+            state.db.save_prompt(app.as_str(), &prompt)?;
+            if prompt.enabled {
+                Self::write_stack(state, &app, &config, None)?;
+            }
+            return Ok(());
+        }
+
         // 检查是否为已启用的提示词
         let is_enabled = prompt.enabled;
 
@@ -58,6 +116,7 @@ impl PromptService {
     }
 
     pub fn delete_prompt(state: &AppState, app: AppType, id: &str) -> Result<(), AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("promptSync");
         let prompts = state.db.get_prompts(app.as_str())?;
 
         if let Some(prompt) = prompts.get(id) {
@@ -71,6 +130,10 @@ impl PromptService {
     }
 
     pub fn enable_prompt(state: &AppState, app: AppType, id: &str) -> Result<(), AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("promptEnable");
+        // 从多片段模式切回单一提示词：先移除托管区块，剩余的用户内容按原逻辑备份
+        Self::leave_stack_mode(state, &app)?;
+
         // 回填当前 live 文件内容到已启用的提示词，或创建备份
         let target_path = prompt_file_path(&app)?;
         if target_path.exists() {
@@ -143,6 +206,315 @@ impl PromptService {
         Ok(())
     }
 
+    // ========================================================================
+    // 多片段提示词
+    // ========================================================================
+
+    pub fn get_stack_config(state: &AppState) -> Result<PromptStackConfig, AppError> {
+        state.db.get_prompt_stack_config()
+    }
+
+    /// 按顺序叠加多个提示词片段并写入托管区块；传入空列表则退出多片段模式
+    pub fn set_prompt_stack(
+        state: &AppState,
+        app: AppType,
+        ids: Vec<String>,
+    ) -> Result<(), AppError> {
+        if ids.is_empty() {
+            Self::leave_stack_mode(state, &app)?;
+            return Ok(());
+        }
+
+        let mut prompts = state.db.get_prompts(app.as_str())?;
+        for (index, id) in ids.iter().enumerate() {
+            if !prompts.contains_key(id) {
+                return Err(AppError::InvalidInput(format!("提示词 {id} 不存在")));
+            }
+            if ids[..index].contains(id) {
+                return Err(AppError::InvalidInput(format!("提示词 {id} 重复")));
+            }
+        }
+
+        let mut config = state.db.get_prompt_stack_config()?;
+        // 从单一提示词模式切换时，文件若正是此前整体写入的提示词，则整体替换而非当作用户内容保留
+        let legacy_content = if config.active_stack(&app).is_none() {
+            prompts
+                .values()
+                .find(|p| p.enabled)
+                .map(|p| p.content.clone())
+        } else {
+            None
+        };
+
+        for prompt in prompts.values_mut() {
+            let enabled = ids.contains(&prompt.id);
+            if prompt.enabled != enabled {
+                prompt.enabled = enabled;
+                state.db.save_prompt(app.as_str(), prompt)?;
+            }
+        }
+
+        config.stacks.insert(app.as_str().to_string(), ids);
+        state.db.save_prompt_stack_config(&config)?;
+        Self::write_stack(state, &app, &config, legacy_content.as_deref())
+    }
+
+    /// 保存自定义模板变量，并重新渲染所有处于多片段模式的应用
+    pub fn save_variables(
+        state: &AppState,
+        variables: BTreeMap<String, String>,
+    ) -> Result<(), AppError> {
+        let mut config = state.db.get_prompt_stack_config()?;
+        config.variables = variables;
+        state.db.save_prompt_stack_config(&config)?;
+
+        for (app_key, ids) in &config.stacks {
+            if ids.is_empty() {
+                continue;
+            }
+            let app = AppType::from_str(app_key)?;
+            Self::write_stack(state, &app, &config, None)?;
+        }
+        Ok(())
+    }
+
+    /// 预览指定片段组合渲染后的内容（不写入文件）
+    pub fn render_stack(
+        state: &AppState,
+        app: AppType,
+        ids: &[String],
+    ) -> Result<String, AppError> {
+        let config = state.db.get_prompt_stack_config()?;
+        Self::render_stack_body(state, &app, ids, &config)
+    }
+
+    fn render_stack_body(
+        state: &AppState,
+        app: &AppType,
+        ids: &[String],
+        config: &PromptStackConfig,
+    ) -> Result<String, AppError> {
+        let prompts = state.db.get_prompts(app.as_str())?;
+        let ctx = TemplateContext::new(app, None, &config.variables);
+        Ok(ctx.render_fragments(
+            ids.iter()
+                .filter_map(|id| prompts.get(id))
+                .map(|p| p.content.as_str()),
+        ))
+    }
+
+    fn write_stack(
+        state: &AppState,
+        app: &AppType,
+        config: &PromptStackConfig,
+        legacy_content: Option<&str>,
+    ) -> Result<(), AppError> {
+        let ids = config.active_stack(app).cloned().unwrap_or_default();
+        let body = Self::render_stack_body(state, app, &ids, config)?;
+
+        let target_path = prompt_file_path(app)?;
+        let existing = std::fs::read_to_string(&target_path).ok();
+        let existing = match (existing, legacy_content) {
+            (Some(current), Some(legacy))
+                if !has_managed_block(&current) && current.trim() == legacy.trim() =>
+            {
+                None
+            }
+            (current, _) => current,
+        };
+
+        write_text_file(
+            &target_path,
+            &splice_managed_block(existing.as_deref(), &body),
+        )
+    }
+
+    /// 退出多片段模式：清除片段列表、取消启用并移除托管区块
+    fn leave_stack_mode(state: &AppState, app: &AppType) -> Result<(), AppError> {
+        let mut config = state.db.get_prompt_stack_config()?;
+        let Some(stack) = config.stacks.remove(app.as_str()) else {
+            return Ok(());
+        };
+        state.db.save_prompt_stack_config(&config)?;
+        if stack.is_empty() {
+            return Ok(());
+        }
+
+        for prompt in state.db.get_prompts(app.as_str())?.values_mut() {
+            if prompt.enabled {
+                prompt.enabled = false;
+                state.db.save_prompt(app.as_str(), prompt)?;
+            }
+        }
+
+        let target_path = prompt_file_path(app)?;
+        if let Ok(current) = std::fs::read_to_string(&target_path) {
+            if has_managed_block(&current) {
+                write_text_file(&target_path, &remove_managed_block(&current))?;
+            }
+        }
+        Ok(())
+    }
+
+    // ========================================================================
+    // 跨应用共享提示词
+    // ========================================================================
+
+    pub fn get_shared_prompts(
+        state: &AppState,
+    ) -> Result<IndexMap<String, SharedPrompt>, AppError> {
+        state.db.get_shared_prompts()
+    }
+
+    /// 保存共享提示词并同步到所选应用
+    pub fn upsert_shared_prompt(
+        state: &AppState,
+        mut prompt: SharedPrompt,
+        activate: bool,
+    ) -> Result<SharedPromptSyncResult, AppError> {
+        if prompt.id.trim().is_empty() {
+            return Err(AppError::InvalidInput("共享提示词 ID 不能为空".to_string()));
+        }
+        for app_key in prompt.overrides.keys() {
+            let app = AppType::from_str(app_key)?;
+            if matches!(app, AppType::ClaudeDesktop) {
+                return Err(AppError::InvalidInput(format!(
+                    "应用 {app_key} 不支持提示词"
+                )));
+            }
+        }
+
+        let now = get_unix_timestamp()?;
+        let previous = state.db.get_shared_prompt(&prompt.id)?;
+        prompt.created_at = previous
+            .and_then(|p| p.created_at)
+            .or(prompt.created_at)
+            .or(Some(now));
+        prompt.updated_at = Some(now);
+        state.db.save_shared_prompt(&prompt)?;
+
+        Self::sync_shared_prompt(state, &prompt.id, activate)
+    }
+
+    /// 删除共享提示词，并从所有应用中撤下
+    pub fn delete_shared_prompt(state: &AppState, id: &str) -> Result<(), AppError> {
+        for app in AppType::all().filter(|app| !matches!(app, AppType::ClaudeDesktop)) {
+            Self::detach_shared_prompt(state, &app, id)?;
+        }
+        state.db.delete_shared_prompt(id)?;
+        Ok(())
+    }
+
+    /// 一次性写入所有选中应用的提示词文件；未选中的应用撤下此前同步的副本
+    ///
+    /// 单提示词模式下不会隐式替换用户当前启用的提示词：只有 `activate` 为 true 时才启用
+    /// 副本，被替换的提示词记录在返回结果中。
+    pub fn sync_shared_prompt(
+        state: &AppState,
+        id: &str,
+        activate: bool,
+    ) -> Result<SharedPromptSyncResult, AppError> {
+        let shared = state
+            .db
+            .get_shared_prompt(id)?
+            .ok_or_else(|| AppError::InvalidInput(format!("共享提示词 {id} 不存在")))?;
+
+        let mut result = SharedPromptSyncResult::default();
+        for app in AppType::all().filter(|app| !matches!(app, AppType::ClaudeDesktop)) {
+            if shared.apps.is_enabled_for(&app) {
+                let key = app.as_str().to_string();
+                match Self::attach_shared_prompt(state, &app, &shared, activate)? {
+                    AttachOutcome::Active => {}
+                    AttachOutcome::Inactive => result.inactive.push(key.clone()),
+                    AttachOutcome::Replaced(previous) => {
+                        result.replaced.insert(key.clone(), previous);
+                    }
+                }
+                result.synced.push(key);
+            } else {
+                Self::detach_shared_prompt(state, &app, id)?;
+            }
+        }
+        log::info!("共享提示词 {id} 已同步到: {:?}", result.synced);
+        Ok(result)
+    }
+
+    /// 将共享提示词写为应用下的提示词副本
+    ///
+    /// 多片段模式下追加到片段末尾；单提示词模式下副本已启用时保持启用，否则仅在
+    /// `activate` 时启用（并替换原启用项）。
+    fn attach_shared_prompt(
+        state: &AppState,
+        app: &AppType,
+        shared: &SharedPrompt,
+        activate: bool,
+    ) -> Result<AttachOutcome, AppError> {
+        let copy_id = format!("{SHARED_PROMPT_ID_PREFIX}{}", shared.id);
+        let existing = state.db.get_prompts(app.as_str())?.shift_remove(&copy_id);
+        let prompt = Prompt {
+            id: copy_id.clone(),
+            name: shared.name.clone(),
+            content: shared.content_for(app),
+            description: shared.description.clone(),
+            enabled: existing.as_ref().is_some_and(|p| p.enabled),
+            created_at: existing.and_then(|p| p.created_at).or(shared.created_at),
+            updated_at: shared.updated_at,
+        };
+
+        let config = state.db.get_prompt_stack_config()?;
+        match config.active_stack(app) {
+            Some(stack) if stack.contains(&copy_id) => {
+                Self::upsert_prompt(state, app.clone(), &copy_id, prompt)?;
+            }
+            Some(stack) => {
+                let mut ids = stack.clone();
+                ids.push(copy_id);
+                state.db.save_prompt(app.as_str(), &prompt)?;
+                Self::set_prompt_stack(state, app.clone(), ids)?;
+            }
+            None if prompt.enabled => Self::upsert_prompt(state, app.clone(), &copy_id, prompt)?,
+            None if !activate => {
+                state.db.save_prompt(app.as_str(), &prompt)?;
+                return Ok(AttachOutcome::Inactive);
+            }
+            None => {
+                let previous = state
+                    .db
+                    .get_prompts(app.as_str())?
+                    .into_values()
+                    .find(|p| p.enabled)
+                    .map(|p| p.id);
+                state.db.save_prompt(app.as_str(), &prompt)?;
+                Self::enable_prompt(state, app.clone(), &copy_id)?;
+                if let Some(previous) = previous {
+                    return Ok(AttachOutcome::Replaced(previous));
+                }
+            }
+        }
+        Ok(AttachOutcome::Active)
+    }
+
+    /// 撤下应用下的共享提示词副本
+    fn detach_shared_prompt(state: &AppState, app: &AppType, id: &str) -> Result<(), AppError> {
+        let copy_id = format!("{SHARED_PROMPT_ID_PREFIX}{id}");
+        let Some(mut prompt) = state.db.get_prompts(app.as_str())?.shift_remove(&copy_id) else {
+            return Ok(());
+        };
+
+        let config = state.db.get_prompt_stack_config()?;
+        if let Some(stack) = config.active_stack(app) {
+            if stack.contains(&copy_id) {
+                let ids = stack.iter().filter(|s| **s != copy_id).cloned().collect();
+                Self::set_prompt_stack(state, app.clone(), ids)?;
+            }
+        } else if prompt.enabled {
+            prompt.enabled = false;
+            Self::upsert_prompt(state, app.clone(), &copy_id, prompt)?;
+        }
+
+        state.db.delete_prompt(app.as_str(), &copy_id)
+    }
+
     pub fn import_from_file(state: &AppState, app: AppType) -> Result<String, AppError> {
         let file_path = prompt_file_path(&app)?;
 
@@ -9,7 +9,9 @@ use toml_edit::{DocumentMut, Item, TableLike};
 
 use crate::app_config::AppType;
 use crate::codex_config::{get_codex_auth_path, get_codex_config_path};
-use crate::config::{delete_file, get_claude_settings_path, read_json_file, write_json_file};
+use crate::config::{
+    delete_file, get_claude_settings_path, live_path_exists, read_json_file, write_json_file,
+};
 use crate::database::Database;
 use crate::error::AppError;
 use crate::provider::Provider;
@@ -44,6 +46,10 @@ pub(crate) fn provider_exists_in_live_config(
             .map(|providers| providers.contains_key(provider_id)),
         AppType::Hermes => crate::hermes_config::get_providers()
             .map(|providers| providers.contains_key(provider_id)),
+        AppType::Goose => crate::goose_config::get_providers()
+            .map(|providers| providers.contains_key(provider_id)),
+        AppType::Crush => crate::crush_config::get_providers()
+            .map(|providers| providers.contains_key(provider_id)),
         _ => Ok(false),
     }
 }
@@ -334,7 +340,8 @@ fn settings_contain_common_config(app_type: &AppType, settings: &Value, snippet:
 
             toml_item_is_subset(target_doc.as_item(), source_doc.as_item())
         }
-        AppType::Gemini => match serde_json::from_str::<Value>(trimmed) {
+        // Gemini / Qwen Code 的通用配置片段都合并到 env（.env）
+        AppType::Gemini | AppType::Qwen => match serde_json::from_str::<Value>(trimmed) {
             Ok(Value::Object(source_map)) => {
                 let Some(target_map) = settings.get("env").and_then(Value::as_object) else {
                     return false;
@@ -347,7 +354,13 @@ fn settings_contain_common_config(app_type: &AppType, settings: &Value, snippet:
             }
             _ => false,
         },
-        AppType::OpenCode | AppType::OpenClaw | AppType::Hermes | AppType::ClaudeDesktop => false,
+        AppType::OpenCode
+        | AppType::OpenClaw
+        | AppType::Hermes
+        | AppType::Goose
+        | AppType::Crush
+        | AppType::Aider
+        | AppType::ClaudeDesktop => false,
     }
 }
 
@@ -408,18 +421,23 @@ pub(crate) fn remove_common_config_from_settings(
             }
             Ok(result)
         }
-        AppType::Gemini => {
-            let source = serde_json::from_str::<Value>(trimmed)
-                .map_err(|e| AppError::Message(format!("Invalid Gemini common config: {e}")))?;
+        AppType::Gemini | AppType::Qwen => {
+            let source = serde_json::from_str::<Value>(trimmed).map_err(|e| {
+                AppError::Message(format!("Invalid {app_type:?} common config: {e}"))
+            })?;
             let mut result = settings.clone();
             if let Some(env) = result.get_mut("env") {
                 json_deep_remove(env, &source);
             }
             Ok(result)
         }
-        AppType::OpenCode | AppType::OpenClaw | AppType::Hermes | AppType::ClaudeDesktop => {
-            Ok(settings.clone())
-        }
+        AppType::OpenCode
+        | AppType::OpenClaw
+        | AppType::Hermes
+        | AppType::Goose
+        | AppType::Crush
+        | AppType::Aider
+        | AppType::ClaudeDesktop => Ok(settings.clone()),
     }
 }
 
@@ -463,9 +481,10 @@ fn apply_common_config_to_settings(
             }
             Ok(result)
         }
-        AppType::Gemini => {
-            let source = serde_json::from_str::<Value>(trimmed)
-                .map_err(|e| AppError::Message(format!("Invalid Gemini common config: {e}")))?;
+        AppType::Gemini | AppType::Qwen => {
+            let source = serde_json::from_str::<Value>(trimmed).map_err(|e| {
+                AppError::Message(format!("Invalid {app_type:?} common config: {e}"))
+            })?;
             let mut result = settings.clone();
             if let Some(env) = result.get_mut("env") {
                 json_deep_merge(env, &source);
@@ -474,9 +493,13 @@ fn apply_common_config_to_settings(
             }
             Ok(result)
         }
-        AppType::OpenCode | AppType::OpenClaw | AppType::Hermes | AppType::ClaudeDesktop => {
-            Ok(settings.clone())
-        }
+        AppType::OpenCode
+        | AppType::OpenClaw
+        | AppType::Hermes
+        | AppType::Goose
+        | AppType::Crush
+        | AppType::Aider
+        | AppType::ClaudeDesktop => Ok(settings.clone()),
     }
 }
 
@@ -503,9 +526,57 @@ pub(crate) fn build_effective_settings_with_common_config(
         }
     }
 
+    if matches!(app_type, AppType::Codex) {
+        if let Err(err) = apply_codex_policy_defaults(provider, &mut effective_settings) {
+            log::warn!(
+                "Failed to apply Codex policy defaults for provider '{}': {err}",
+                provider.id
+            );
+        }
+    }
+
     Ok(effective_settings)
 }
 
+/// 将供应商级 Codex 默认策略补入 `config`（仅填充未显式设置的顶层键）
+fn apply_codex_policy_defaults(provider: &Provider, settings: &mut Value) -> Result<(), AppError> {
+    update_codex_config_with_policy_defaults(
+        provider,
+        settings,
+        crate::codex_profiles::apply_policy_defaults,
+    )
+}
+
+/// 回填时移除与默认策略相同的顶层键，避免默认值固化进供应商配置
+fn strip_codex_policy_defaults(provider: &Provider, settings: &mut Value) -> Result<(), AppError> {
+    update_codex_config_with_policy_defaults(
+        provider,
+        settings,
+        crate::codex_profiles::strip_policy_defaults,
+    )
+}
+
+fn update_codex_config_with_policy_defaults(
+    provider: &Provider,
+    settings: &mut Value,
+    update: fn(&str, &crate::codex_profiles::CodexPolicySettings) -> Result<String, AppError>,
+) -> Result<(), AppError> {
+    let Some(defaults) = provider
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.codex_defaults.as_ref())
+        .filter(|defaults| !defaults.is_empty())
+    else {
+        return Ok(());
+    };
+    let config_text = settings.get("config").and_then(Value::as_str).unwrap_or("");
+    let updated = update(config_text, defaults)?;
+    if let Some(obj) = settings.as_object_mut() {
+        obj.insert("config".to_string(), Value::String(updated));
+    }
+    Ok(())
+}
+
 pub(crate) fn write_live_with_common_config(
     db: &Database,
     app_type: &AppType,
@@ -595,6 +666,12 @@ fn restore_live_settings_for_provider_backfill(
             provider.id
         );
     }
+    if let Err(err) = strip_codex_policy_defaults(provider, &mut settings) {
+        log::warn!(
+            "Failed to strip Codex policy defaults while backfilling '{}': {err}",
+            provider.id
+        );
+    }
 
     // `modelCatalog` is a cc-switch–private field whose SSOT is the DB. Live's
     // `config.toml` only carries a lossy projection (`model_catalog_json` →
@@ -758,6 +835,12 @@ pub(crate) fn write_live_snapshot(app_type: &AppType, provider: &Provider) -> Re
             // Delegate to write_gemini_live which handles env file writing correctly
             write_gemini_live(provider)?;
         }
+        AppType::Qwen => {
+            crate::qwen_config::write_qwen_live(&provider.settings_config)?;
+        }
+        AppType::Aider => {
+            crate::aider_config::write_aider_live(&provider.settings_config)?;
+        }
         AppType::OpenCode => {
             // OpenCode uses additive mode - write provider to config
             use crate::opencode_config;
@@ -862,6 +945,14 @@ pub(crate) fn write_live_snapshot(app_type: &AppType, provider: &Provider) -> Re
             crate::hermes_config::set_provider(&provider.id, provider.settings_config.clone())?;
             log::debug!("Hermes provider '{}' written to live config", provider.id);
         }
+        AppType::Goose => {
+            crate::goose_config::set_provider(&provider.id, &provider.settings_config)?;
+            log::debug!("Goose provider '{}' written to live config", provider.id);
+        }
+        AppType::Crush => {
+            crate::crush_config::set_provider(&provider.id, &provider.settings_config)?;
+            log::debug!("Crush provider '{}' written to live config", provider.id);
+        }
     }
     Ok(())
 }
@@ -1068,6 +1159,8 @@ pub fn read_live_settings(app_type: AppType) -> Result<Value, AppError> {
                 "config": config_obj
             }))
         }
+        AppType::Qwen => crate::qwen_config::read_qwen_live_settings(),
+        AppType::Aider => crate::aider_config::read_aider_live_settings(),
         AppType::OpenCode => {
             use crate::opencode_config::{get_opencode_config_path, read_opencode_config};
 
@@ -1111,6 +1204,28 @@ pub fn read_live_settings(app_type: AppType) -> Result<Value, AppError> {
             let config = crate::hermes_config::yaml_to_json(&yaml_config)?;
             Ok(config)
         }
+        AppType::Goose => {
+            let config_path = crate::goose_config::get_goose_config_path();
+            if !config_path.exists() {
+                return Err(AppError::localized(
+                    "goose.config.missing",
+                    "Goose 配置文件不存在",
+                    "Goose configuration file not found",
+                ));
+            }
+            crate::goose_config::read_goose_live_snapshot()
+        }
+        AppType::Crush => {
+            let config_path = crate::crush_config::get_crush_config_path();
+            if !config_path.exists() {
+                return Err(AppError::localized(
+                    "crush.config.missing",
+                    "Crush 配置文件不存在",
+                    "Crush configuration file not found",
+                ));
+            }
+            crate::crush_config::read_crush_config()
+        }
     }
 }
 
@@ -1204,8 +1319,14 @@ pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<bool
                 "config": config_obj
             })
         }
-        // OpenCode, OpenClaw and Hermes use additive mode and are handled by early return above
-        AppType::OpenCode | AppType::OpenClaw | AppType::Hermes => {
+        AppType::Qwen => crate::qwen_config::read_qwen_live_settings()?,
+        AppType::Aider => crate::aider_config::read_aider_live_settings()?,
+        // OpenCode, OpenClaw, Hermes, Goose and Crush use additive mode and are handled by early return above
+        AppType::OpenCode
+        | AppType::OpenClaw
+        | AppType::Hermes
+        | AppType::Goose
+        | AppType::Crush => {
             unreachable!("additive mode apps are handled by early return")
         }
     };
@@ -1290,7 +1411,7 @@ pub(crate) fn write_gemini_live(provider: &Provider) -> Result<(), AppError> {
     if let Some(config_value) = provider.settings_config.get("config") {
         if config_value.is_object() {
             // Merge with existing settings to preserve mcpServers and other fields
-            let mut merged = if settings_path.exists() {
+            let mut merged = if live_path_exists(&settings_path) {
                 read_json_file::<Value>(&settings_path).unwrap_or_else(|_| json!({}))
             } else {
                 json!({})
@@ -1316,7 +1437,7 @@ pub(crate) fn write_gemini_live(provider: &Provider) -> Result<(), AppError> {
     }
 
     // If no config specified or config is null, preserve existing file
-    if config_to_write.is_none() && settings_path.exists() {
+    if config_to_write.is_none() && live_path_exists(&settings_path) {
         config_to_write = Some(read_json_file(&settings_path)?);
     }
 
@@ -1563,6 +1684,156 @@ pub fn remove_hermes_provider_from_live(provider_id: &str) -> Result<(), AppErro
     Ok(())
 }
 
+/// Import all providers from Goose live config to database
+///
+/// This imports existing providers from ~/.config/goose/custom_providers/*.json
+/// into the CC Switch database. Each provider found will be added to the
+/// database with is_current set to false.
+pub fn import_goose_providers_from_live(state: &AppState) -> Result<usize, AppError> {
+    use crate::goose_config;
+
+    let providers = goose_config::get_providers()?;
+    if providers.is_empty() {
+        return Ok(0);
+    }
+
+    let mut imported = 0;
+    let existing_ids = state.db.get_provider_ids("goose")?;
+
+    for (id, config) in providers {
+        // Validate: skip entries with empty id
+        if id.trim().is_empty() {
+            log::warn!("Skipping Goose provider with empty id");
+            continue;
+        }
+
+        // Skip if already exists in database
+        if existing_ids.contains(&id) {
+            log::debug!("Goose provider '{id}' already exists in database, skipping");
+            continue;
+        }
+
+        // Use the display name from the live config when available
+        let name = config
+            .get("display_name")
+            .and_then(Value::as_str)
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or(&id)
+            .to_string();
+
+        let mut provider = Provider::with_id(id.clone(), name, config, None);
+        provider.meta = Some(crate::provider::ProviderMeta {
+            live_config_managed: Some(true),
+            ..Default::default()
+        });
+
+        // Save to database
+        if let Err(e) = state.db.save_provider("goose", &provider) {
+            log::warn!("Failed to import Goose provider '{id}': {e}");
+            continue;
+        }
+
+        imported += 1;
+        log::info!("Imported Goose provider '{id}' from live config");
+    }
+
+    Ok(imported)
+}
+
+/// Remove a Goose provider from live config
+///
+/// This removes a specific provider from ~/.config/goose/custom_providers (and its key in secrets.yaml)
+/// without affecting other providers.
+pub fn remove_goose_provider_from_live(provider_id: &str) -> Result<(), AppError> {
+    use crate::goose_config;
+
+    // Check if Goose config directory exists
+    if !goose_config::get_goose_dir().exists() {
+        log::debug!("Goose config directory doesn't exist, skipping removal of '{provider_id}'");
+        return Ok(());
+    }
+
+    goose_config::remove_provider(provider_id)?;
+    log::info!("Goose provider '{provider_id}' removed from live config");
+
+    Ok(())
+}
+
+/// Import all providers from Crush live config to database
+///
+/// This imports existing providers from ~/.config/crush/crush.json
+/// into the CC Switch database. Each provider found will be added to the
+/// database with is_current set to false.
+pub fn import_crush_providers_from_live(state: &AppState) -> Result<usize, AppError> {
+    use crate::crush_config;
+
+    let providers = crush_config::get_providers()?;
+    if providers.is_empty() {
+        return Ok(0);
+    }
+
+    let mut imported = 0;
+    let existing_ids = state.db.get_provider_ids("crush")?;
+
+    for (id, config) in providers {
+        // Validate: skip entries with empty id
+        if id.trim().is_empty() {
+            log::warn!("Skipping Crush provider with empty id");
+            continue;
+        }
+
+        // Skip if already exists in database
+        if existing_ids.contains(&id) {
+            log::debug!("Crush provider '{id}' already exists in database, skipping");
+            continue;
+        }
+
+        // Use the display name from the live config when available
+        let name = config
+            .get("name")
+            .and_then(Value::as_str)
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or(&id)
+            .to_string();
+
+        let mut provider = Provider::with_id(id.clone(), name, config, None);
+        provider.meta = Some(crate::provider::ProviderMeta {
+            live_config_managed: Some(true),
+            ..Default::default()
+        });
+
+        // Save to database
+        if let Err(e) = state.db.save_provider("crush", &provider) {
+            log::warn!("Failed to import Crush provider '{id}': {e}");
+            continue;
+        }
+
+        imported += 1;
+        log::info!("Imported Crush provider '{id}' from live config");
+    }
+
+    Ok(imported)
+}
+
+/// Remove a Crush provider from live config
+///
+/// This removes a specific provider from ~/.config/crush/crush.json
+/// without affecting other providers.
+pub fn remove_crush_provider_from_live(provider_id: &str) -> Result<(), AppError> {
+    use crate::crush_config;
+
+    // Check if Crush config directory exists
+    if !crush_config::get_crush_dir().exists() {
+        log::debug!("Crush config directory doesn't exist, skipping removal of '{provider_id}'");
+        return Ok(());
+    }
+
+    crush_config::remove_provider(provider_id)?;
+    log::info!("Crush provider '{provider_id}' removed from live config");
+
+    Ok(())
+}
+
 /// Remove an OpenClaw provider from live config
 ///
 /// This removes a specific provider from ~/.openclaw/openclaw.json
@@ -1631,6 +1902,36 @@ mod tests {
         assert_eq!(stripped, settings);
     }
 
+    #[test]
+    fn codex_policy_defaults_round_trip_through_backfill() {
+        let config = "model = \"gpt-5\"\nsandbox_mode = \"read-only\"\n";
+        let mut provider = Provider::with_id(
+            "codex-test".to_string(),
+            "Codex Test".to_string(),
+            json!({ "auth": {}, "config": config }),
+            None,
+        );
+        provider.meta = Some(crate::provider::ProviderMeta {
+            codex_defaults: Some(crate::codex_profiles::CodexPolicySettings {
+                model_reasoning_effort: Some(crate::codex_profiles::CodexReasoningEffort::High),
+                sandbox_mode: Some(crate::codex_profiles::CodexSandboxMode::WorkspaceWrite),
+                approval_policy: None,
+            }),
+            ..Default::default()
+        });
+
+        let mut live = provider.settings_config.clone();
+        apply_codex_policy_defaults(&provider, &mut live).unwrap();
+        let live_config = live["config"].as_str().unwrap();
+        assert!(live_config.contains("model_reasoning_effort = \"high\""));
+        // 供应商显式设置的值优先于默认策略
+        assert!(live_config.contains("sandbox_mode = \"read-only\""));
+
+        let backfilled =
+            restore_live_settings_for_provider_backfill(&AppType::Codex, &provider, live);
+        assert_eq!(backfilled["config"].as_str(), Some(config));
+    }
+
     #[test]
     fn explicit_common_config_flag_overrides_legacy_subset_detection() {
         let mut provider = Provider::with_id(
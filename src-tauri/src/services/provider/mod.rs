@@ -5,6 +5,7 @@
 mod endpoints;
 mod gemini_auth;
 mod live;
+mod preview;
 mod usage;
 
 use indexmap::IndexMap;
@@ -22,11 +23,14 @@ use crate::store::AppState;
 
 // Re-export sub-module functions for external access
 pub use live::{
-    import_default_config, import_hermes_providers_from_live, import_openclaw_providers_from_live,
+    import_crush_providers_from_live, import_default_config, import_goose_providers_from_live,
+    import_hermes_providers_from_live, import_openclaw_providers_from_live,
     import_opencode_providers_from_live, read_live_settings,
     should_import_default_config_on_startup, sync_current_to_live,
 };
 
+pub use preview::{SwitchPreview, SwitchPreviewChange, SwitchPreviewFile};
+
 // Internal re-exports (pub(crate))
 pub(crate) use live::sanitize_claude_settings_for_live;
 pub(crate) use live::{
@@ -37,6 +41,7 @@ pub(crate) use live::{
 
 // Internal re-exports
 use live::{
+    remove_crush_provider_from_live, remove_goose_provider_from_live,
     remove_hermes_provider_from_live, remove_openclaw_provider_from_live,
     remove_opencode_provider_from_live, write_gemini_live,
 };
@@ -629,6 +634,76 @@ base_url = "http://localhost:8080"
         );
     }
 
+    /// 目录下所有文件及其内容，用于断言预览不落盘
+    fn snapshot_dir(root: &Path) -> Vec<(PathBuf, Vec<u8>)> {
+        let mut files = Vec::new();
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(&dir).expect("read dir") {
+                let path = entry.expect("dir entry").path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else {
+                    let content = fs::read(&path).expect("read file");
+                    files.push((path, content));
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    #[test]
+    #[serial]
+    fn preview_switch_leaves_hermes_and_openclaw_dirs_untouched() {
+        with_test_home(|state, home| {
+            let hermes_dir = home.join(".hermes");
+            fs::create_dir_all(&hermes_dir).expect("create hermes dir");
+            fs::write(
+                hermes_dir.join("config.yaml"),
+                "model:\n  default: old-model\n  provider: old\n",
+            )
+            .expect("seed hermes config");
+            let openclaw_dir = home.join(".openclaw");
+            fs::create_dir_all(&openclaw_dir).expect("create openclaw dir");
+            fs::write(
+                openclaw_dir.join("openclaw.json"),
+                "{ models: { providers: {} } }\n",
+            )
+            .expect("seed openclaw config");
+
+            let mut hermes = openclaw_provider("hermes-preview");
+            hermes.settings_config = json!({
+                "name": "hermes-preview",
+                "base_url": "https://api.example.com/v1",
+                "api_key": "test-key",
+                "models": [{ "id": "new-model" }],
+            });
+            ProviderService::add(state, AppType::Hermes, hermes, false)
+                .expect("seed hermes provider");
+            ProviderService::add(
+                state,
+                AppType::OpenClaw,
+                openclaw_provider("openclaw-preview"),
+                false,
+            )
+            .expect("seed openclaw provider");
+
+            let before = snapshot_dir(home);
+            let hermes_preview =
+                ProviderService::preview_switch(state, AppType::Hermes, "hermes-preview")
+                    .expect("preview hermes switch");
+            let openclaw_preview =
+                ProviderService::preview_switch(state, AppType::OpenClaw, "openclaw-preview")
+                    .expect("preview openclaw switch");
+
+            assert!(!hermes_preview.files.is_empty());
+            assert!(!openclaw_preview.files.is_empty());
+            assert_eq!(snapshot_dir(home), before, "preview must not touch disk");
+            assert!(!home.join(".cc-switch").join("backups").exists());
+        });
+    }
+
     #[test]
     #[serial]
     fn rename_rejects_missing_original_provider() {
@@ -1184,6 +1259,7 @@ impl ProviderService {
         provider: Provider,
         add_to_live: bool,
     ) -> Result<bool, AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("providerUpdate");
         let mut provider = provider;
         // Normalize Claude model keys
         Self::normalize_provider_if_claude(&app_type, &mut provider);
@@ -1233,6 +1309,7 @@ impl ProviderService {
         original_id: Option<&str>,
         provider: Provider,
     ) -> Result<bool, AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("providerUpdate");
         let mut provider = provider;
         let original_id = original_id.unwrap_or(provider.id.as_str()).to_string();
         let provider_id_changed = original_id != provider.id;
@@ -1436,6 +1513,7 @@ impl ProviderService {
     /// 同时检查本地 settings 和数据库的当前供应商，防止删除任一端正在使用的供应商。
     /// 对于累加模式应用（OpenCode, OpenClaw），可以随时删除任意供应商，同时从 live 配置中移除。
     pub fn delete(state: &AppState, app_type: AppType, id: &str) -> Result<(), AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("providerUpdate");
         // Additive mode apps - no current provider concept
         if app_type.is_additive_mode() {
             // Single DB read shared across all additive-mode sub-paths below.
@@ -1477,6 +1555,8 @@ impl ProviderService {
                     AppType::OpenCode => remove_opencode_provider_from_live(id)?,
                     AppType::OpenClaw => remove_openclaw_provider_from_live(id)?,
                     AppType::Hermes => remove_hermes_provider_from_live(id)?,
+                    AppType::Goose => remove_goose_provider_from_live(id)?,
+                    AppType::Crush => remove_crush_provider_from_live(id)?,
                     _ => {}
                 }
             }
@@ -1542,6 +1622,12 @@ impl ProviderService {
             AppType::Hermes => {
                 remove_hermes_provider_from_live(id)?;
             }
+            AppType::Goose => {
+                remove_goose_provider_from_live(id)?;
+            }
+            AppType::Crush => {
+                remove_crush_provider_from_live(id)?;
+            }
             _ => {
                 return Err(AppError::Message(format!(
                     "App {} does not support remove from live config",
@@ -1571,6 +1657,7 @@ impl ProviderService {
     ///    d. Write target provider config to live files
     ///    e. Sync MCP configuration
     pub fn switch(state: &AppState, app_type: AppType, id: &str) -> Result<SwitchResult, AppError> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("switch");
         // Check if provider exists
         let providers = state.db.get_all_providers(app_type.as_str())?;
         let _provider = providers
@@ -1606,6 +1693,43 @@ impl ProviderService {
                 None
             };
 
+        let should_hot_switch = Self::should_hot_switch(state, &app_type, _provider)?;
+
+        if should_hot_switch {
+            // Proxy takeover mode: hot-switch without restoring upstream Live config.
+            // The proxy layer may still refresh proxy-safe Live fields so client labels
+            // follow the selected provider while endpoints remain local.
+            log::info!(
+                "代理接管模式：热切换 {} 的目标供应商为 {}",
+                app_type.as_str(),
+                id
+            );
+
+            futures::executor::block_on(
+                state
+                    .proxy_service
+                    .hot_switch_provider_inner(app_type.as_str(), id),
+            )
+            .map_err(|e| AppError::Message(format!("热切换失败: {e}")))?;
+
+            // The proxy server will route requests to the new provider via is_current.
+            // MCP sync is intentionally skipped while Live config is owned by takeover.
+            return Ok(SwitchResult::default());
+        }
+
+        // Normal mode: full switch with Live config write
+        Self::switch_normal(state, app_type, id, &providers)
+    }
+
+    /// Whether a switch must hot-switch the proxy instead of writing live config.
+    ///
+    /// Errors when the target is an official provider, which must never be
+    /// reached through the proxy.
+    fn should_hot_switch(
+        state: &AppState,
+        app_type: &AppType,
+        provider: &Provider,
+    ) -> Result<bool, AppError> {
         // Backup or live placeholders mean the live file is owned by proxy
         // takeover, even if the proxy server is temporarily stopped or is in the
         // activation window before enabled=true is committed.
@@ -1616,13 +1740,13 @@ impl ProviderService {
                 .is_some();
         let live_taken_over = state
             .proxy_service
-            .detect_takeover_in_live_config_for_app(&app_type);
+            .detect_takeover_in_live_config_for_app(app_type);
 
         let should_hot_switch = is_app_taken_over || live_taken_over;
 
         // Block switching to official providers when proxy takeover is active.
         // Using a proxy with official APIs (Anthropic/OpenAI/Google) may cause account bans.
-        if should_hot_switch && _provider.category.as_deref() == Some("official") {
+        if should_hot_switch && provider.category.as_deref() == Some("official") {
             return Err(AppError::localized(
                 "switch.official_blocked_by_proxy",
                 "代理接管模式下不能切换到官方供应商，使用代理访问官方 API 可能导致账号被封禁。请先关闭代理接管，或选择第三方供应商。",
@@ -1630,30 +1754,124 @@ impl ProviderService {
             ));
         }
 
-        if should_hot_switch {
-            // Proxy takeover mode: hot-switch without restoring upstream Live config.
-            // The proxy layer may still refresh proxy-safe Live fields so client labels
-            // follow the selected provider while endpoints remain local.
-            log::info!(
-                "代理接管模式：热切换 {} 的目标供应商为 {}",
-                app_type.as_str(),
-                id
-            );
+        Ok(should_hot_switch)
+    }
 
-            futures::executor::block_on(
-                state
-                    .proxy_service
-                    .hot_switch_provider_inner(app_type.as_str(), id),
-            )
-            .map_err(|e| AppError::Message(format!("热切换失败: {e}")))?;
+    /// Dry-run a provider switch: run the same live write path as `switch`
+    /// with writes staged in memory, and return per-file diffs against disk.
+    ///
+    /// Database state (current provider, backfill) is left untouched; the
+    /// backfill only rewrites the outgoing provider's stored settings and does
+    /// not affect what is written to live files.
+    pub fn preview_switch(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+    ) -> Result<SwitchPreview, AppError> {
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let provider = providers
+            .get(id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
 
-            // The proxy server will route requests to the new provider via is_current.
-            // MCP sync is intentionally skipped while Live config is owned by takeover.
-            return Ok(SwitchResult::default());
+        let omo_category = matches!(app_type, AppType::OpenCode)
+            && matches!(provider.category.as_deref(), Some("omo" | "omo-slim"));
+        if omo_category || matches!(app_type, AppType::ClaudeDesktop) {
+            return Err(AppError::localized(
+                "switch.preview_unsupported",
+                "该供应商的切换不支持预览",
+                "Switch preview is not supported for this provider",
+            ));
         }
 
-        // Normal mode: full switch with Live config write
-        Self::switch_normal(state, app_type, id, &providers)
+        let _switch_guard =
+            if matches!(app_type, AppType::Claude | AppType::Codex | AppType::Gemini) {
+                Some(futures::executor::block_on(
+                    state.proxy_service.lock_switch_for_app(app_type.as_str()),
+                ))
+            } else {
+                None
+            };
+
+        let mut switch_preview = SwitchPreview {
+            app: app_type.as_str().to_string(),
+            provider_id: id.to_string(),
+            hot_switch: Self::should_hot_switch(state, &app_type, provider)?,
+            files: Vec::new(),
+            warnings: Vec::new(),
+        };
+        if switch_preview.hot_switch {
+            return Ok(switch_preview);
+        }
+
+        let mut result = SwitchResult::default();
+        let staging = crate::config::StagedWrites::begin();
+        let outcome = Self::write_switch_live(state, &app_type, provider, &mut result)
+            .and_then(|()| McpService::sync_all_enabled(state));
+        let staged = staging.finish();
+        outcome?;
+
+        switch_preview.files = preview::diff_staged_files(staged);
+        switch_preview.warnings = result.warnings;
+        Ok(switch_preview)
+    }
+
+    /// Live writes for the switched-to provider, shared by `switch` and
+    /// `preview_switch`
+    fn write_switch_live(
+        state: &AppState,
+        app_type: &AppType,
+        provider: &Provider,
+        result: &mut SwitchResult,
+    ) -> Result<(), AppError> {
+        // Sync to live (write_gemini_live handles security flag internally for Gemini)
+        write_live_with_common_config(state.db.as_ref(), app_type, provider)?;
+
+        // Hermes is additive, so "switching" doesn't overwrite a live config file
+        // — we instead update the top-level `model:` section to point at this
+        // provider's first declared model. Without this, clicking "switch" would
+        // only shuffle entries in custom_providers[] while Hermes keeps using
+        // whatever `model.provider` was set before.
+        if matches!(app_type, AppType::Hermes) {
+            if let Err(e) =
+                crate::hermes_config::apply_switch_defaults(&provider.id, &provider.settings_config)
+            {
+                log::warn!(
+                    "Failed to update Hermes model defaults after switching to '{}': {e}",
+                    provider.id
+                );
+                result
+                    .warnings
+                    .push(format!("hermes_model_defaults_failed:{}", provider.id));
+            }
+        }
+
+        // Goose / Crush 同理：切换只改写默认供应商指针
+        // （Goose: GOOSE_PROVIDER/GOOSE_MODEL；Crush: models.large）
+        let switch_defaults = match app_type {
+            AppType::Goose => Some(crate::goose_config::apply_switch_defaults(
+                &provider.id,
+                &provider.settings_config,
+            )),
+            AppType::Crush => Some(crate::crush_config::apply_switch_defaults(
+                &provider.id,
+                &provider.settings_config,
+            )),
+            _ => None,
+        };
+        if let Some(Err(e)) = switch_defaults {
+            log::warn!(
+                "Failed to update {} model defaults after switching to '{}': {e}",
+                app_type.as_str(),
+                provider.id
+            );
+            result.warnings.push(format!(
+                "{}_model_defaults_failed:{}",
+                app_type.as_str(),
+                provider.id
+            ));
+        }
+
+        Ok(())
     }
 
     /// Normal switch flow (non-proxy mode)
@@ -1730,27 +1948,7 @@ impl ProviderService {
             state.db.set_current_provider(app_type.as_str(), id)?;
         }
 
-        // Sync to live (write_gemini_live handles security flag internally for Gemini)
-        write_live_with_common_config(state.db.as_ref(), &app_type, provider)?;
-
-        // Hermes is additive, so "switching" doesn't overwrite a live config file
-        // — we instead update the top-level `model:` section to point at this
-        // provider's first declared model. Without this, clicking "switch" would
-        // only shuffle entries in custom_providers[] while Hermes keeps using
-        // whatever `model.provider` was set before.
-        if matches!(app_type, AppType::Hermes) {
-            if let Err(e) =
-                crate::hermes_config::apply_switch_defaults(&provider.id, &provider.settings_config)
-            {
-                log::warn!(
-                    "Failed to update Hermes model defaults after switching to '{}': {e}",
-                    provider.id
-                );
-                result
-                    .warnings
-                    .push(format!("hermes_model_defaults_failed:{}", provider.id));
-            }
-        }
+        Self::write_switch_live(state, &app_type, provider, &mut result)?;
 
         // For additive-mode providers that were DB-only (live_config_managed == Some(false)),
         // flip the flag to true now that the provider has been successfully written to the live
@@ -1767,6 +1965,8 @@ impl ProviderService {
                     AppType::OpenCode => remove_opencode_provider_from_live(&provider.id),
                     AppType::OpenClaw => remove_openclaw_provider_from_live(&provider.id),
                     AppType::Hermes => remove_hermes_provider_from_live(&provider.id),
+                    AppType::Goose => remove_goose_provider_from_live(&provider.id),
+                    AppType::Crush => remove_crush_provider_from_live(&provider.id),
                     _ => Ok(()),
                 };
 
@@ -1944,9 +2144,12 @@ impl ProviderService {
             AppType::ClaudeDesktop => Ok(String::new()),
             AppType::Codex => Self::extract_codex_common_config(&provider.settings_config),
             AppType::Gemini => Self::extract_gemini_common_config(&provider.settings_config),
+            AppType::Qwen => Self::extract_qwen_common_config(&provider.settings_config),
             AppType::OpenCode => Self::extract_opencode_common_config(&provider.settings_config),
             AppType::OpenClaw => Self::extract_openclaw_common_config(&provider.settings_config),
             AppType::Hermes => Ok(String::new()), // Hermes doesn't use common config snippets
+            AppType::Goose | AppType::Crush => Ok(String::new()),
+            AppType::Aider => Ok(String::new()), // Aider keeps non-model settings in its own conf file
         }
     }
 
@@ -1960,9 +2163,12 @@ impl ProviderService {
             AppType::ClaudeDesktop => Ok(String::new()),
             AppType::Codex => Self::extract_codex_common_config(settings_config),
             AppType::Gemini => Self::extract_gemini_common_config(settings_config),
+            AppType::Qwen => Self::extract_qwen_common_config(settings_config),
             AppType::OpenCode => Self::extract_opencode_common_config(settings_config),
             AppType::OpenClaw => Self::extract_openclaw_common_config(settings_config),
             AppType::Hermes => Ok(String::new()), // Hermes doesn't use common config snippets
+            AppType::Goose | AppType::Crush => Ok(String::new()),
+            AppType::Aider => Ok(String::new()), // Aider keeps non-model settings in its own conf file
         }
     }
 
@@ -2073,12 +2279,24 @@ impl ProviderService {
     /// - GOOGLE_GEMINI_BASE_URL
     /// - GEMINI_API_KEY
     fn extract_gemini_common_config(settings: &Value) -> Result<String, AppError> {
+        Self::extract_env_common_config(settings, &["GOOGLE_GEMINI_BASE_URL", "GEMINI_API_KEY"])
+    }
+
+    /// Extract common config for Qwen Code (.env, same shape as Gemini)
+    fn extract_qwen_common_config(settings: &Value) -> Result<String, AppError> {
+        Self::extract_env_common_config(
+            settings,
+            &["OPENAI_BASE_URL", "OPENAI_API_KEY", "OPENAI_MODEL"],
+        )
+    }
+
+    fn extract_env_common_config(settings: &Value, excludes: &[&str]) -> Result<String, AppError> {
         let env = settings.get("env").and_then(|v| v.as_object());
 
         let mut snippet = serde_json::Map::new();
         if let Some(env) = env {
             for (key, value) in env {
-                if key == "GOOGLE_GEMINI_BASE_URL" || key == "GEMINI_API_KEY" {
+                if excludes.contains(&key.as_str()) {
                     continue;
                 }
                 let Value::String(v) = value else {
@@ -2314,11 +2532,20 @@ impl ProviderService {
                         crate::codex_config::validate_config_toml(cfg_text)?;
                     }
                 }
+
+                if let Some(defaults) = provider
+                    .meta
+                    .as_ref()
+                    .and_then(|meta| meta.codex_defaults.as_ref())
+                {
+                    defaults.ensure_known()?;
+                }
             }
             AppType::Gemini => {
                 use crate::gemini_config::validate_gemini_settings;
                 validate_gemini_settings(&provider.settings_config)?
             }
+            AppType::Qwen => crate::qwen_config::validate_qwen_settings(&provider.settings_config)?,
             AppType::OpenCode => {
                 // OpenCode uses a different config structure: { npm, options, models }
                 // Basic validation - must be an object
@@ -2351,6 +2578,15 @@ impl ProviderService {
                     ));
                 }
             }
+            AppType::Goose => {
+                crate::goose_config::validate_goose_settings(&provider.settings_config)?;
+            }
+            AppType::Crush => {
+                crate::crush_config::validate_crush_settings(&provider.settings_config)?;
+            }
+            AppType::Aider => {
+                crate::aider_config::validate_aider_settings(&provider.settings_config)?;
+            }
         }
 
         // Validate and clean UsageScript configuration (common for all app types)
@@ -2499,6 +2735,61 @@ impl ProviderService {
 
                 Ok((api_key, base_url))
             }
+            AppType::Qwen => {
+                use crate::gemini_config::json_to_env;
+
+                let env_map = json_to_env(&provider.settings_config)?;
+
+                let api_key = env_map.get("OPENAI_API_KEY").cloned().ok_or_else(|| {
+                    AppError::localized(
+                        "qwen.validation.missing_api_key",
+                        "缺少 OPENAI_API_KEY",
+                        "Missing OPENAI_API_KEY",
+                    )
+                })?;
+
+                let base_url = env_map.get("OPENAI_BASE_URL").cloned().unwrap_or_else(|| {
+                    "https://dashscope.aliyuncs.com/compatible-mode/v1".to_string()
+                });
+
+                Ok((api_key, base_url))
+            }
+            AppType::Aider => {
+                // Aider: API Key 位于 .env，openai-api-base 只对 OpenAI 兼容接口生效
+                let env = provider.settings_config.get("env");
+                let env_value = |key: &str| {
+                    env.and_then(|e| e.get(key))
+                        .and_then(|v| v.as_str())
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(str::to_string)
+                };
+
+                let (api_key, default_base_url) = if let Some(key) = env_value("OPENAI_API_KEY") {
+                    (key, "https://api.openai.com/v1")
+                } else if let Some(key) = env_value("OPENROUTER_API_KEY") {
+                    (key, "https://openrouter.ai/api/v1")
+                } else if let Some(key) = env_value("ANTHROPIC_API_KEY") {
+                    (key, "https://api.anthropic.com")
+                } else {
+                    return Err(AppError::localized(
+                        "provider.api_key.missing",
+                        "缺少 API Key",
+                        "API key is missing",
+                    ));
+                };
+
+                let base_url = provider
+                    .settings_config
+                    .get("config")
+                    .and_then(|c| c.get("openai-api-base"))
+                    .and_then(|v| v.as_str())
+                    .filter(|_| env_value("OPENAI_API_KEY").is_some())
+                    .unwrap_or(default_base_url)
+                    .to_string();
+
+                Ok((api_key, base_url))
+            }
             AppType::OpenCode => {
                 // OpenCode uses options.apiKey and options.baseURL
                 let options = provider
@@ -2557,6 +2848,30 @@ impl ProviderService {
 
                 Ok((api_key, base_url))
             }
+            AppType::Goose | AppType::Crush => {
+                // Goose/Crush use api_key and base_url directly on the object
+                let api_key = provider
+                    .settings_config
+                    .get("api_key")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        AppError::localized(
+                            "provider.api_key.missing",
+                            "缺少 API Key",
+                            "API key is missing",
+                        )
+                    })?
+                    .to_string();
+
+                let base_url = provider
+                    .settings_config
+                    .get("base_url")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+
+                Ok((api_key, base_url))
+            }
         }
     }
 }
@@ -0,0 +1,193 @@
+//! Provider switch preview
+//!
+//! Turns the writes staged by a dry-run switch into per-file unified diffs,
+//! with secret values masked so the preview is safe to show and share.
+
+use std::fs;
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::config::{get_app_config_dir, StagedFiles};
+use crate::services::bundle::SECRET_KEY_RE;
+use crate::services::text_diff::unified_diff;
+
+/// `key: value` / `key = value` lines in JSON, TOML, YAML and `.env` files
+static KEY_VALUE_LINE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^(\s*(?:-\s+)?(?:export\s+)?["']?)([A-Za-z0-9_.\-]+)(["']?\s*[:=]\s*)(.*)$"#)
+        .expect("Invalid key/value line regex")
+});
+
+/// How a live file would change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SwitchPreviewChange {
+    Create,
+    Modify,
+    Delete,
+}
+
+/// A live file the switch would write
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchPreviewFile {
+    pub path: String,
+    pub change: SwitchPreviewChange,
+    /// Unified diff against the file on disk, secrets masked. Empty when only
+    /// masked secret values differ.
+    pub diff: String,
+}
+
+/// Result of a dry-run provider switch
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchPreview {
+    pub app: String,
+    pub provider_id: String,
+    /// Proxy takeover owns the live config: the switch only re-routes the
+    /// proxy and writes no live files.
+    pub hot_switch: bool,
+    pub files: Vec<SwitchPreviewFile>,
+    pub warnings: Vec<String>,
+}
+
+/// Diff staged writes against the files currently on disk.
+///
+/// cc-switch's own data directory (backups, catalogs, history) is skipped, as
+/// are writes that would leave a file unchanged.
+pub(crate) fn diff_staged_files(staged: StagedFiles) -> Vec<SwitchPreviewFile> {
+    let app_dir = get_app_config_dir();
+    staged
+        .into_iter()
+        .filter(|(path, _)| !path.starts_with(&app_dir))
+        .filter_map(|(path, new)| {
+            let old = fs::read(&path).ok();
+            if old == new {
+                return None;
+            }
+            let change = match (&old, &new) {
+                (None, _) => SwitchPreviewChange::Create,
+                (Some(_), None) => SwitchPreviewChange::Delete,
+                (Some(_), Some(_)) => SwitchPreviewChange::Modify,
+            };
+            Some(SwitchPreviewFile {
+                diff: masked_diff(&path, old.as_deref(), new.as_deref()),
+                path: path.to_string_lossy().to_string(),
+                change,
+            })
+        })
+        .collect()
+}
+
+fn masked_diff(path: &Path, old: Option<&[u8]>, new: Option<&[u8]>) -> String {
+    let text = |bytes: Option<&[u8]>| mask_secrets(&String::from_utf8_lossy(bytes.unwrap_or(&[])));
+    let label = path.to_string_lossy();
+    unified_diff(&text(old), &text(new), &label, &label)
+}
+
+/// Mask the values of secret-looking keys, line by line
+pub(crate) fn mask_secrets(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let (body, newline) = match line.strip_suffix('\n') {
+            Some(body) => (body, "\n"),
+            None => (line, ""),
+        };
+        out.push_str(&mask_line(body));
+        out.push_str(newline);
+    }
+    out
+}
+
+fn mask_line(line: &str) -> String {
+    let Some(caps) = KEY_VALUE_LINE_RE.captures(line) else {
+        return line.to_string();
+    };
+    if !SECRET_KEY_RE.is_match(&caps[2]) {
+        return line.to_string();
+    }
+    let prefix = &line[..caps.get(4).map_or(line.len(), |m| m.start())];
+    let rest = &caps[4];
+
+    let masked_rest = match rest.chars().next() {
+        Some(quote @ ('"' | '\'')) => match rest[1..].find(quote) {
+            Some(end) => format!(
+                "{quote}{}{}",
+                mask_value(&rest[1..1 + end]),
+                &rest[1 + end..]
+            ),
+            None => return line.to_string(),
+        },
+        // Objects / arrays under a secret-looking key are left for their own lines
+        Some('{' | '[') | None => return line.to_string(),
+        Some(_) => {
+            let value = rest.trim_end().trim_end_matches(',');
+            format!("{}{}", mask_value(value), &rest[value.len()..])
+        }
+    };
+    format!("{prefix}{masked_rest}")
+}
+
+/// Keep a short prefix/suffix so a changed key still shows up in the diff
+fn mask_value(value: &str) -> String {
+    if value.is_empty() {
+        return String::new();
+    }
+    let count = value.chars().count();
+    if count <= 12 {
+        return "***".to_string();
+    }
+    let prefix: String = value.chars().take(4).collect();
+    let suffix: String = value.chars().skip(count - 4).collect();
+    format!("{prefix}...{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_secret_values_across_formats() {
+        let text = concat!(
+            "{\n",
+            "  \"env\": {\n",
+            "    \"ANTHROPIC_AUTH_TOKEN\": \"sk-ant-1234567890abcdef\",\n",
+            "    \"ANTHROPIC_BASE_URL\": \"https://api.example.com\"\n",
+            "  }\n",
+            "}\n",
+            "experimental_bearer_token = \"sk-proj-abcdefghijklmnop\"\n",
+            "GEMINI_API_KEY=AIzaSyExampleKey12345\n",
+            "    api_key: short\n",
+            "secret = \"\"\n",
+        );
+        let masked = mask_secrets(text);
+        assert_eq!(
+            masked,
+            concat!(
+                "{\n",
+                "  \"env\": {\n",
+                "    \"ANTHROPIC_AUTH_TOKEN\": \"sk-a...cdef\",\n",
+                "    \"ANTHROPIC_BASE_URL\": \"https://api.example.com\"\n",
+                "  }\n",
+                "}\n",
+                "experimental_bearer_token = \"sk-p...mnop\"\n",
+                "GEMINI_API_KEY=AIza...2345\n",
+                "    api_key: ***\n",
+                "secret = \"\"\n",
+            )
+        );
+    }
+
+    #[test]
+    fn masked_diff_hides_secrets_but_shows_changes() {
+        let old = b"{\n  \"apiKey\": \"old-secret-value-0001\",\n  \"model\": \"a\"\n}\n";
+        let new = b"{\n  \"apiKey\": \"new-secret-value-0002\",\n  \"model\": \"b\"\n}\n";
+        let diff = masked_diff(Path::new("config.json"), Some(old), Some(new));
+        assert!(!diff.contains("secret-value"));
+        assert!(diff.contains("-  \"apiKey\": \"old-...0001\","));
+        assert!(diff.contains("+  \"apiKey\": \"new-...0002\","));
+        assert!(diff.contains("+  \"model\": \"b\""));
+    }
+}
@@ -598,6 +598,18 @@ impl ProxyService {
             .await
             .map(|c| c.enabled)
             .unwrap_or(false);
+        let qwen_enabled = self
+            .db
+            .get_proxy_config_for_app("qwen")
+            .await
+            .map(|c| c.enabled)
+            .unwrap_or(false);
+        let aider_enabled = self
+            .db
+            .get_proxy_config_for_app("aider")
+            .await
+            .map(|c| c.enabled)
+            .unwrap_or(false);
         // OpenCode and OpenClaw don't support proxy features, always return false
         let opencode_enabled = false;
         let openclaw_enabled = false;
@@ -606,6 +618,8 @@ impl ProxyService {
             claude: claude_enabled,
             codex: codex_enabled,
             gemini: gemini_enabled,
+            qwen: qwen_enabled,
+            aider: aider_enabled,
             opencode: opencode_enabled,
             openclaw: openclaw_enabled,
         })
@@ -803,6 +817,8 @@ impl ProxyService {
             AppType::Claude => self.read_claude_live()?,
             AppType::Codex => self.read_codex_live()?,
             AppType::Gemini => self.read_gemini_live()?,
+            AppType::Qwen => self.read_qwen_live()?,
+            AppType::Aider => self.read_aider_live()?,
             _ => return Err("该应用不支持代理功能".to_string()),
         };
 
@@ -1017,6 +1033,52 @@ impl ProxyService {
                     }
                 }
             }
+            // Qwen Code / Aider 的真实 Token 都在 env.OPENAI_API_KEY
+            AppType::Qwen | AppType::Aider => {
+                let app = app_type.as_str();
+                let provider_id =
+                    crate::settings::get_effective_current_provider(&self.db, app_type)
+                        .map_err(|e| format!("获取 {app} 当前供应商失败: {e}"))?;
+
+                if let Some(provider_id) = provider_id {
+                    if let Ok(Some(mut provider)) = self.db.get_provider_by_id(&provider_id, app) {
+                        if let Some(token) = live_config
+                            .get("env")
+                            .and_then(|v| v.get("OPENAI_API_KEY"))
+                            .and_then(|v| v.as_str())
+                            .map(|s| s.trim())
+                            .filter(|s| !s.is_empty() && *s != PROXY_TOKEN_PLACEHOLDER)
+                        {
+                            if provider.settings_config.is_null() {
+                                provider.settings_config = json!({});
+                            }
+                            let Some(root) = provider.settings_config.as_object_mut() else {
+                                log::warn!(
+                                    "{app} provider settings_config 格式异常（非对象），跳过写入 Token (provider: {provider_id})"
+                                );
+                                return Ok(());
+                            };
+                            let env = root.entry("env").or_insert_with(|| json!({}));
+                            if !env.is_object() {
+                                *env = json!({});
+                            }
+                            if let Some(env_obj) = env.as_object_mut() {
+                                env_obj.insert("OPENAI_API_KEY".to_string(), json!(token));
+                            }
+
+                            if let Err(e) = self.db.update_provider_settings_config(
+                                app,
+                                &provider_id,
+                                &provider.settings_config,
+                            ) {
+                                log::warn!("同步 {app} Token 到数据库失败: {e}");
+                            } else {
+                                log::info!("已同步 {app} Token 到数据库 (provider: {provider_id})");
+                            }
+                        }
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -1039,6 +1101,16 @@ impl ProxyService {
                 .await?;
         }
 
+        if let Ok(live_config) = self.read_qwen_live() {
+            self.sync_live_config_to_provider(&AppType::Qwen, &live_config)
+                .await?;
+        }
+
+        if let Ok(live_config) = self.read_aider_live() {
+            self.sync_live_config_to_provider(&AppType::Aider, &live_config)
+                .await?;
+        }
+
         log::info!("Live 配置 Token 同步完成");
         Ok(())
     }
@@ -1091,7 +1163,7 @@ impl ProxyService {
             .map_err(|e| format!("清除接管状态失败: {e}"))?;
 
         // 4. 清除所有应用的 enabled 状态（用户手动关闭，不需要下次自动恢复）
-        for app_type in ["claude", "codex", "gemini"] {
+        for app_type in ["claude", "codex", "gemini", "qwen", "aider"] {
             if let Ok(mut config) = self.db.get_proxy_config_for_app(app_type).await {
                 if config.enabled {
                     config.enabled = false;
@@ -1202,6 +1274,34 @@ impl ProxyService {
             }
         }
 
+        // Qwen Code
+        if let Ok(config) = self.read_qwen_live() {
+            if Self::live_has_proxy_placeholder_for_app(&AppType::Qwen, &config) {
+                log::warn!("qwen Live 已被代理接管，不备份（避免把代理配置固化进备份槽）；下次 stop 会从 SSOT 重建 Live");
+            } else {
+                let json_str = serde_json::to_string(&config)
+                    .map_err(|e| format!("序列化 Qwen 配置失败: {e}"))?;
+                self.db
+                    .save_live_backup("qwen", &json_str)
+                    .await
+                    .map_err(|e| format!("备份 Qwen 配置失败: {e}"))?;
+            }
+        }
+
+        // Aider
+        if let Ok(config) = self.read_aider_live() {
+            if Self::live_has_proxy_placeholder_for_app(&AppType::Aider, &config) {
+                log::warn!("aider Live 已被代理接管，不备份（避免把代理配置固化进备份槽）；下次 stop 会从 SSOT 重建 Live");
+            } else {
+                let json_str = serde_json::to_string(&config)
+                    .map_err(|e| format!("序列化 Aider 配置失败: {e}"))?;
+                self.db
+                    .save_live_backup("aider", &json_str)
+                    .await
+                    .map_err(|e| format!("备份 Aider 配置失败: {e}"))?;
+            }
+        }
+
         log::info!("已备份所有应用的 Live 配置");
         Ok(())
     }
@@ -1212,6 +1312,8 @@ impl ProxyService {
             AppType::Claude => ("claude", self.read_claude_live()?),
             AppType::Codex => ("codex", self.read_codex_live()?),
             AppType::Gemini => ("gemini", self.read_gemini_live()?),
+            AppType::Qwen => ("qwen", self.read_qwen_live()?),
+            AppType::Aider => ("aider", self.read_aider_live()?),
             _ => return Err("该应用不支持代理功能".to_string()),
         };
 
@@ -1280,7 +1382,8 @@ impl ProxyService {
     /// - `/v1/chat/completions`, `/v1/responses` → Codex
     /// - `/v1beta/*` → Gemini
     ///
-    /// 因此不需要在 URL 中添加应用前缀。
+    /// 因此不需要在 URL 中添加应用前缀。Qwen Code、Aider 与 Codex 同为 Chat Completions
+    /// 格式，例外地使用 `/qwen/v1`、`/aider/v1` 前缀来区分供应商队列。
     async fn takeover_live_configs(&self) -> Result<(), String> {
         let (proxy_url, proxy_codex_base_url) = self.build_proxy_urls().await?;
 
@@ -1344,6 +1447,22 @@ impl ProxyService {
             log::info!("Gemini Live 配置已接管，代理地址: {proxy_url}");
         }
 
+        // Qwen Code: 修改 .env 的 OPENAI_BASE_URL，使用占位符替代真实 Token（代理会注入真实 Token）
+        if let Ok(mut live_config) = self.read_qwen_live() {
+            let proxy_qwen_base_url = Self::qwen_proxy_base_url(&proxy_url);
+            Self::apply_qwen_takeover_fields(&mut live_config, &proxy_qwen_base_url);
+            self.write_qwen_live(&live_config)?;
+            log::info!("Qwen Live 配置已接管，代理地址: {proxy_qwen_base_url}");
+        }
+
+        // Aider: 修改 .aider.conf.yml 的 openai-api-base，.env 中使用占位符替代真实 Token
+        if let Ok(mut live_config) = self.read_aider_live() {
+            let proxy_aider_base_url = Self::aider_proxy_base_url(&proxy_url);
+            Self::apply_aider_takeover_fields(&mut live_config, &proxy_aider_base_url);
+            self.write_aider_live(&live_config)?;
+            log::info!("Aider Live 配置已接管，代理地址: {proxy_aider_base_url}");
+        }
+
         Ok(())
     }
 
@@ -1407,6 +1526,20 @@ impl ProxyService {
                 self.write_gemini_live(&live_config)?;
                 log::info!("Gemini Live 配置已接管，代理地址: {proxy_url}");
             }
+            AppType::Qwen => {
+                let mut live_config = self.read_qwen_live()?;
+                let proxy_qwen_base_url = Self::qwen_proxy_base_url(&proxy_url);
+                Self::apply_qwen_takeover_fields(&mut live_config, &proxy_qwen_base_url);
+                self.write_qwen_live(&live_config)?;
+                log::info!("Qwen Live 配置已接管，代理地址: {proxy_qwen_base_url}");
+            }
+            AppType::Aider => {
+                let mut live_config = self.read_aider_live()?;
+                let proxy_aider_base_url = Self::aider_proxy_base_url(&proxy_url);
+                Self::apply_aider_takeover_fields(&mut live_config, &proxy_aider_base_url);
+                self.write_aider_live(&live_config)?;
+                log::info!("Aider Live 配置已接管，代理地址: {proxy_aider_base_url}");
+            }
             _ => return Err("该应用不支持代理功能".to_string()),
         }
 
@@ -1488,6 +1621,24 @@ impl ProxyService {
                     let _ = self.write_gemini_live(&live_config);
                 }
             }
+            AppType::Qwen => {
+                if let Ok(mut live_config) = self.read_qwen_live() {
+                    Self::apply_qwen_takeover_fields(
+                        &mut live_config,
+                        &Self::qwen_proxy_base_url(&proxy_url),
+                    );
+                    let _ = self.write_qwen_live(&live_config);
+                }
+            }
+            AppType::Aider => {
+                if let Ok(mut live_config) = self.read_aider_live() {
+                    Self::apply_aider_takeover_fields(
+                        &mut live_config,
+                        &Self::aider_proxy_base_url(&proxy_url),
+                    );
+                    let _ = self.write_aider_live(&live_config);
+                }
+            }
             _ => {}
         }
 
@@ -1520,6 +1671,22 @@ impl ProxyService {
                     log::info!("Gemini Live 配置已恢复");
                 }
             }
+            AppType::Qwen => {
+                if let Ok(Some(backup)) = self.db.get_live_backup("qwen").await {
+                    let config: Value = serde_json::from_str(&backup.original_config)
+                        .map_err(|e| format!("解析 Qwen 备份失败: {e}"))?;
+                    self.write_qwen_live(&config)?;
+                    log::info!("Qwen Live 配置已恢复");
+                }
+            }
+            AppType::Aider => {
+                if let Ok(Some(backup)) = self.db.get_live_backup("aider").await {
+                    let config: Value = serde_json::from_str(&backup.original_config)
+                        .map_err(|e| format!("解析 Aider 备份失败: {e}"))?;
+                    self.write_aider_live(&config)?;
+                    log::info!("Aider Live 配置已恢复");
+                }
+            }
             _ => {}
         }
 
@@ -1530,7 +1697,13 @@ impl ProxyService {
     async fn restore_live_configs(&self) -> Result<(), String> {
         let mut errors = Vec::new();
 
-        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        for app_type in [
+            AppType::Claude,
+            AppType::Codex,
+            AppType::Gemini,
+            AppType::Qwen,
+            AppType::Aider,
+        ] {
             if let Err(e) = self
                 .restore_live_config_for_app_with_fallback(&app_type)
                 .await
@@ -1619,6 +1792,8 @@ impl ProxyService {
             AppType::Claude => self.write_claude_live(config),
             AppType::Codex => self.write_codex_live(config),
             AppType::Gemini => self.write_gemini_live(config),
+            AppType::Qwen => self.write_qwen_live(config),
+            AppType::Aider => self.write_aider_live(config),
             _ => Err("该应用不支持代理功能".to_string()),
         }
     }
@@ -1637,6 +1812,14 @@ impl ProxyService {
                 Ok(config) => Self::is_gemini_live_taken_over(&config),
                 Err(_) => false,
             },
+            AppType::Qwen => match self.read_qwen_live() {
+                Ok(config) => Self::is_qwen_live_taken_over(&config),
+                Err(_) => false,
+            },
+            AppType::Aider => match self.read_aider_live() {
+                Ok(config) => Self::is_aider_live_taken_over(&config),
+                Err(_) => false,
+            },
             _ => false,
         }
     }
@@ -1687,6 +1870,8 @@ impl ProxyService {
             AppType::Claude => self.cleanup_claude_takeover_placeholders_in_live(),
             AppType::Codex => self.cleanup_codex_takeover_placeholders_in_live(),
             AppType::Gemini => self.cleanup_gemini_takeover_placeholders_in_live(),
+            AppType::Qwen => self.cleanup_qwen_takeover_placeholders_in_live(),
+            AppType::Aider => self.cleanup_aider_takeover_placeholders_in_live(),
             _ => Ok(()),
         }
     }
@@ -1778,6 +1963,26 @@ impl ProxyService {
                     .is_some_and(|url| Self::proxy_urls_match(url, &proxy_url));
                 Ok(Self::is_gemini_live_taken_over(&config) && base_url_matches)
             }
+            AppType::Qwen => {
+                let config = self.read_qwen_live()?;
+                let proxy_qwen_base_url = Self::qwen_proxy_base_url(&proxy_url);
+                let base_url_matches = config
+                    .get("env")
+                    .and_then(|value| value.get("OPENAI_BASE_URL"))
+                    .and_then(|value| value.as_str())
+                    .is_some_and(|url| Self::proxy_urls_match(url, &proxy_qwen_base_url));
+                Ok(Self::is_qwen_live_taken_over(&config) && base_url_matches)
+            }
+            AppType::Aider => {
+                let config = self.read_aider_live()?;
+                let proxy_aider_base_url = Self::aider_proxy_base_url(&proxy_url);
+                let base_url_matches = config
+                    .get("config")
+                    .and_then(|value| value.get("openai-api-base"))
+                    .and_then(|value| value.as_str())
+                    .is_some_and(|url| Self::proxy_urls_match(url, &proxy_aider_base_url));
+                Ok(Self::is_aider_live_taken_over(&config) && base_url_matches)
+            }
             _ => Ok(false),
         }
     }
@@ -1866,10 +2071,58 @@ impl ProxyService {
         Ok(())
     }
 
+    fn cleanup_qwen_takeover_placeholders_in_live(&self) -> Result<(), String> {
+        let mut config = self.read_qwen_live()?;
+
+        let Some(env) = config.get_mut("env").and_then(|v| v.as_object_mut()) else {
+            return Ok(());
+        };
+
+        if env.get("OPENAI_API_KEY").and_then(|v| v.as_str()) == Some(PROXY_TOKEN_PLACEHOLDER) {
+            env.remove("OPENAI_API_KEY");
+        }
+
+        if env
+            .get("OPENAI_BASE_URL")
+            .and_then(|v| v.as_str())
+            .map(Self::is_local_proxy_url)
+            .unwrap_or(false)
+        {
+            env.remove("OPENAI_BASE_URL");
+        }
+
+        self.write_qwen_live(&config)?;
+        Ok(())
+    }
+
+    fn cleanup_aider_takeover_placeholders_in_live(&self) -> Result<(), String> {
+        let mut config = self.read_aider_live()?;
+
+        if let Some(env) = config.get_mut("env").and_then(|v| v.as_object_mut()) {
+            if env.get("OPENAI_API_KEY").and_then(|v| v.as_str()) == Some(PROXY_TOKEN_PLACEHOLDER) {
+                env.remove("OPENAI_API_KEY");
+            }
+        }
+
+        if let Some(aider_config) = config.get_mut("config").and_then(|v| v.as_object_mut()) {
+            if aider_config
+                .get("openai-api-base")
+                .and_then(|v| v.as_str())
+                .map(Self::is_local_proxy_url)
+                .unwrap_or(false)
+            {
+                aider_config.remove("openai-api-base");
+            }
+        }
+
+        self.write_aider_live(&config)?;
+        Ok(())
+    }
+
     /// 检查是否处于 Live 接管模式
     pub async fn is_takeover_active(&self) -> Result<bool, String> {
         let status = self.get_takeover_status().await?;
-        Ok(status.claude || status.codex || status.gemini)
+        Ok(status.claude || status.codex || status.gemini || status.qwen || status.aider)
     }
 
     /// 从异常退出中恢复（启动时调用）
@@ -1919,6 +2172,18 @@ impl ProxyService {
             }
         }
 
+        if let Ok(config) = self.read_qwen_live() {
+            if Self::is_qwen_live_taken_over(&config) {
+                return true;
+            }
+        }
+
+        if let Ok(config) = self.read_aider_live() {
+            if Self::is_aider_live_taken_over(&config) {
+                return true;
+            }
+        }
+
         false
     }
 
@@ -1973,6 +2238,62 @@ impl ProxyService {
         env.get("GEMINI_API_KEY").and_then(|v| v.as_str()) == Some(PROXY_TOKEN_PLACEHOLDER)
     }
 
+    fn is_qwen_live_taken_over(config: &Value) -> bool {
+        config
+            .get("env")
+            .and_then(|env| env.get("OPENAI_API_KEY"))
+            .and_then(|v| v.as_str())
+            == Some(PROXY_TOKEN_PLACEHOLDER)
+    }
+
+    /// Qwen Code 与 Codex 共用 Chat Completions 路由格式，用 `/qwen` 前缀区分供应商队列
+    fn qwen_proxy_base_url(proxy_url: &str) -> String {
+        format!("{}/qwen/v1", proxy_url.trim_end_matches('/'))
+    }
+
+    /// Qwen Code 接管仅改写 .env；settings.json（含 mcpServers、认证方式）保持原样
+    fn apply_qwen_takeover_fields(live_config: &mut Value, proxy_base_url: &str) {
+        if let Some(env) = live_config.get_mut("env").and_then(|v| v.as_object_mut()) {
+            env.insert("OPENAI_BASE_URL".to_string(), json!(proxy_base_url));
+            env.insert("OPENAI_API_KEY".to_string(), json!(PROXY_TOKEN_PLACEHOLDER));
+        } else {
+            live_config["env"] = json!({
+                "OPENAI_BASE_URL": proxy_base_url,
+                "OPENAI_API_KEY": PROXY_TOKEN_PLACEHOLDER
+            });
+        }
+    }
+
+    fn is_aider_live_taken_over(config: &Value) -> bool {
+        config
+            .get("env")
+            .and_then(|env| env.get("OPENAI_API_KEY"))
+            .and_then(|v| v.as_str())
+            == Some(PROXY_TOKEN_PLACEHOLDER)
+    }
+
+    /// Aider 同样走 Chat Completions，用 `/aider` 前缀区分供应商队列
+    fn aider_proxy_base_url(proxy_url: &str) -> String {
+        format!("{}/aider/v1", proxy_url.trim_end_matches('/'))
+    }
+
+    /// Aider 接管只改写连接字段：`.env` 的 OPENAI_API_KEY 与 `.aider.conf.yml` 的 openai-api-base
+    fn apply_aider_takeover_fields(live_config: &mut Value, proxy_base_url: &str) {
+        if let Some(env) = live_config.get_mut("env").and_then(|v| v.as_object_mut()) {
+            env.insert("OPENAI_API_KEY".to_string(), json!(PROXY_TOKEN_PLACEHOLDER));
+        } else {
+            live_config["env"] = json!({ "OPENAI_API_KEY": PROXY_TOKEN_PLACEHOLDER });
+        }
+        if let Some(config) = live_config
+            .get_mut("config")
+            .and_then(|v| v.as_object_mut())
+        {
+            config.insert("openai-api-base".to_string(), json!(proxy_base_url));
+        } else {
+            live_config["config"] = json!({ "openai-api-base": proxy_base_url });
+        }
+    }
+
     /// 判断给定的 Live/备份配置是否已被代理接管（包含占位符）
     ///
     /// 用途：检测"备份里存的其实是代理配置"这种异常历史状态。
@@ -1984,6 +2305,8 @@ impl ProxyService {
             AppType::Claude => Self::is_claude_live_taken_over(config),
             AppType::Codex => Self::codex_live_has_proxy_placeholder(config),
             AppType::Gemini => Self::is_gemini_live_taken_over(config),
+            AppType::Qwen => Self::is_qwen_live_taken_over(config),
+            AppType::Aider => Self::is_aider_live_taken_over(config),
             _ => false,
         }
     }
@@ -2040,15 +2363,30 @@ impl ProxyService {
                 .map_err(|e| format!("序列化 Claude 配置失败: {e}"))?,
             AppType::Codex => serde_json::to_string(&effective_settings)
                 .map_err(|e| format!("序列化 Codex 配置失败: {e}"))?,
-            AppType::Gemini => {
-                // Gemini takeover 仅修改 .env；settings.json（含 mcpServers）保持原样。
+            AppType::Gemini | AppType::Qwen => {
+                // Gemini / Qwen Code takeover 仅修改 .env；settings.json（含 mcpServers）保持原样。
                 let env_backup = if let Some(env) = effective_settings.get("env") {
                     json!({ "env": env })
                 } else {
                     json!({ "env": {} })
                 };
                 serde_json::to_string(&env_backup)
-                    .map_err(|e| format!("序列化 Gemini 配置失败: {e}"))?
+                    .map_err(|e| format!("序列化 {app_type} 配置失败: {e}"))?
+            }
+            AppType::Aider => {
+                // Aider takeover 仅修改连接字段；模型与 model settings 保持原样。
+                let endpoint = json!({
+                    "env": effective_settings.get("env").cloned().unwrap_or_else(|| json!({})),
+                    "config": {
+                        "openai-api-base": effective_settings
+                            .get("config")
+                            .and_then(|config| config.get("openai-api-base"))
+                            .cloned()
+                            .unwrap_or(Value::Null),
+                    },
+                });
+                serde_json::to_string(&endpoint)
+                    .map_err(|e| format!("序列化 {app_type} 配置失败: {e}"))?
             }
             _ => return Err(format!("未知的应用类型: {app_type}")),
         };
@@ -2356,6 +2694,7 @@ impl ProxyService {
     }
 
     fn write_claude_live(&self, config: &Value) -> Result<(), String> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("proxy");
         let path = get_claude_settings_path();
         let settings = crate::services::provider::sanitize_claude_settings_for_live(config);
         write_json_file(&path, &settings).map_err(|e| format!("写入 Claude 配置失败: {e}"))
@@ -2375,6 +2714,7 @@ impl ProxyService {
         config: &Value,
         provider: Option<&Provider>,
     ) -> Result<(), String> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("proxy");
         let Some(provider) = provider else {
             if crate::settings::preserve_codex_official_auth_on_switch() {
                 if let (Some(auth), Some(config_str)) = (
@@ -2421,6 +2761,7 @@ impl ProxyService {
         config: &Value,
         provider: Option<&Provider>,
     ) -> Result<(), String> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("proxy");
         if crate::settings::preserve_codex_official_auth_on_switch() {
             if let Some(auth) = config
                 .get("auth")
@@ -2445,6 +2786,7 @@ impl ProxyService {
     }
 
     fn write_codex_live_verbatim(&self, config: &Value) -> Result<(), String> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("proxy");
         use crate::codex_config::{get_codex_auth_path, get_codex_config_path};
 
         let auth = config.get("auth");
@@ -2514,6 +2856,7 @@ impl ProxyService {
     }
 
     fn write_gemini_live(&self, config: &Value) -> Result<(), String> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("proxy");
         use crate::gemini_config::{json_to_env, write_gemini_env_atomic};
 
         let env_map = json_to_env(config).map_err(|e| format!("转换 Gemini 配置失败: {e}"))?;
@@ -2521,6 +2864,43 @@ impl ProxyService {
         Ok(())
     }
 
+    fn read_qwen_live(&self) -> Result<Value, String> {
+        use crate::gemini_config::env_to_json;
+        use crate::qwen_config::{get_qwen_env_path, read_qwen_env};
+
+        let env_path = get_qwen_env_path();
+        if !env_path.exists() {
+            return Err("Qwen .env 文件不存在".to_string());
+        }
+
+        let env_map = read_qwen_env().map_err(|e| format!("读取 Qwen env 失败: {e}"))?;
+        Ok(env_to_json(&env_map))
+    }
+
+    fn write_qwen_live(&self, config: &Value) -> Result<(), String> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("proxy");
+        use crate::gemini_config::json_to_env;
+        use crate::qwen_config::write_qwen_env_atomic;
+
+        let env_map = json_to_env(config).map_err(|e| format!("转换 Qwen 配置失败: {e}"))?;
+        write_qwen_env_atomic(&env_map).map_err(|e| format!("写入 Qwen env 失败: {e}"))?;
+        Ok(())
+    }
+
+    /// Aider 的 Live 只涉及连接字段（见 `aider_config::read_aider_endpoint`）
+    fn read_aider_live(&self) -> Result<Value, String> {
+        if !crate::aider_config::get_aider_config_path().exists() {
+            return Err("Aider .aider.conf.yml 文件不存在".to_string());
+        }
+        crate::aider_config::read_aider_endpoint().map_err(|e| format!("读取 Aider 配置失败: {e}"))
+    }
+
+    fn write_aider_live(&self, config: &Value) -> Result<(), String> {
+        let _scope = crate::services::live_history::LiveWriteScope::enter("proxy");
+        crate::aider_config::write_aider_endpoint(config)
+            .map_err(|e| format!("写入 Aider 配置失败: {e}"))
+    }
+
     // ==================== 原有方法 ====================
 
     /// 获取服务器状态
@@ -2617,6 +2997,16 @@ impl ProxyService {
                         .await?;
                     updated_any = true;
                 }
+                if takeover.qwen {
+                    self.takeover_live_config_best_effort(&AppType::Qwen)
+                        .await?;
+                    updated_any = true;
+                }
+                if takeover.aider {
+                    self.takeover_live_config_best_effort(&AppType::Aider)
+                        .await?;
+                    updated_any = true;
+                }
 
                 if updated_any {
                     log::info!("已同步更新 Live 配置中的代理地址");
@@ -0,0 +1,716 @@
+//! 会话归档与保留策略
+//!
+//! 按规则（闲置天数 / 项目总大小 / 保留最新 N 个）把旧会话压缩进
+//! `~/.cc-switch/session-archive/`，在 `session_archives` 表里记录索引，
+//! 并支持把归档会话恢复回原生位置。
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{get_app_config_dir, read_json_file, write_json_file};
+use crate::database::Database;
+use crate::error::AppError;
+use crate::session_manager::{self, archive, SessionMeta};
+
+/// 闲置不足该时长的会话一律不归档，避免和正在写入的 CLI 抢文件
+const MIN_IDLE_MS: i64 = 60 * 60 * 1000;
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+const ARCHIVE_DIR_NAME: &str = "session-archive";
+const RETENTION_STATE_FILE: &str = "retention-state.json";
+/// 定时检查间隔；实际执行频率由 `interval_hours` 决定
+const WORKER_CHECK_INTERVAL_SECS: u64 = 60 * 60;
+
+fn default_interval_hours() -> u32 {
+    24
+}
+
+/// 单条保留规则
+///
+/// `provider_id` / `project_dir` 为空表示匹配全部；`project_dir` 按前缀匹配。
+/// 大小与 `keep_latest` 限制按项目目录分别计算。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRetentionRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_dir: Option<String>,
+    /// 最后活跃时间早于 N 天前的会话被归档
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u32>,
+    /// 单个项目会话总大小上限（MB），超出时从最旧的开始归档
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_size_mb: Option<u64>,
+    /// 每个项目始终保留最新的 N 个会话
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_latest: Option<u32>,
+}
+
+impl SessionRetentionRule {
+    fn matches(&self, meta: &SessionMeta) -> bool {
+        if let Some(provider_id) = self.provider_id.as_deref() {
+            if meta.provider_id != provider_id {
+                return false;
+            }
+        }
+        if let Some(prefix) = self.project_dir.as_deref() {
+            match meta.project_dir.as_deref() {
+                Some(dir) if dir.starts_with(prefix) => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+/// 会话保留策略配置（存储在 settings 表）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRetentionConfig {
+    /// 是否启用定时执行
+    #[serde(default)]
+    pub enabled: bool,
+    /// 定时执行间隔（小时）
+    #[serde(default = "default_interval_hours")]
+    pub interval_hours: u32,
+    #[serde(default)]
+    pub rules: Vec<SessionRetentionRule>,
+}
+
+impl Default for SessionRetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: default_interval_hours(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// 归档索引记录
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedSession {
+    pub id: String,
+    pub provider_id: String,
+    pub session_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_dir: Option<String>,
+    /// 原始会话文件路径（恢复目标）
+    pub source_path: String,
+    pub archive_path: String,
+    pub original_size: u64,
+    pub archived_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_active_at: Option<i64>,
+    pub archived_at: i64,
+    /// 触发原因：manual / age / size
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveReason {
+    Manual,
+    Age,
+    Size,
+}
+
+impl ArchiveReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Age => "age",
+            Self::Size => "size",
+        }
+    }
+}
+
+/// 保留策略命中的会话
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionCandidate {
+    pub provider_id: String,
+    pub session_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_dir: Option<String>,
+    pub source_path: String,
+    pub size_bytes: u64,
+    pub last_active_at: i64,
+    pub reason: ArchiveReason,
+    /// 命中的规则下标
+    pub rule_index: usize,
+}
+
+/// 保留策略执行报告（dry-run 时只有 candidates）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub generated_at: i64,
+    pub candidates: Vec<RetentionCandidate>,
+    pub archived: Vec<ArchivedSession>,
+    /// 不支持归档的会话数量（SQLite 会话、OpenClaw 等）
+    pub skipped_unsupported: usize,
+    pub errors: Vec<String>,
+    pub total_bytes: u64,
+}
+
+/// 批量手动归档的单条结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveSessionOutcome {
+    pub provider_id: String,
+    pub session_id: String,
+    pub source_path: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive: Option<ArchivedSession>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 定时执行状态，单独落盘以免频繁写 settings 表触发云同步
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_report: Option<RetentionReport>,
+}
+
+/// 参与规划的会话（已计算大小与有效活跃时间）
+#[derive(Debug, Clone)]
+pub struct RetentionInput {
+    pub meta: SessionMeta,
+    pub source_path: String,
+    pub size_bytes: u64,
+    pub last_active_at: i64,
+}
+
+/// 根据规则计算需要归档的会话
+///
+/// 多条规则命中同一会话时以第一条为准；闲置不足 [`MIN_IDLE_MS`] 的会话
+/// 以及每个项目最新的 `keep_latest` 个会话不会被选中。
+pub fn plan_retention(
+    inputs: &[RetentionInput],
+    rules: &[SessionRetentionRule],
+    now_ms: i64,
+) -> Vec<RetentionCandidate> {
+    let mut selected: HashSet<(String, String)> = HashSet::new();
+    let mut candidates = Vec::new();
+
+    for (rule_index, rule) in rules.iter().enumerate() {
+        if rule.max_age_days.is_none() && rule.max_total_size_mb.is_none() {
+            continue;
+        }
+
+        let mut groups: HashMap<Option<&str>, Vec<&RetentionInput>> = HashMap::new();
+        for input in inputs.iter().filter(|i| rule.matches(&i.meta)) {
+            groups
+                .entry(input.meta.project_dir.as_deref())
+                .or_default()
+                .push(input);
+        }
+
+        let mut group_keys: Vec<_> = groups.keys().copied().collect();
+        group_keys.sort();
+
+        for key in group_keys {
+            let mut group = groups.remove(&key).unwrap_or_default();
+            // 最新的在前
+            group.sort_by(|a, b| {
+                b.last_active_at
+                    .cmp(&a.last_active_at)
+                    .then_with(|| a.source_path.cmp(&b.source_path))
+            });
+
+            let keep_latest = rule.keep_latest.unwrap_or(0) as usize;
+            let size_limit = rule
+                .max_total_size_mb
+                .map(|mb| mb.saturating_mul(1024 * 1024));
+            let age_cutoff = rule
+                .max_age_days
+                .map(|days| now_ms - i64::from(days) * DAY_MS);
+
+            let mut kept_bytes = 0u64;
+            for (idx, input) in group.into_iter().enumerate() {
+                let key = (input.meta.provider_id.clone(), input.source_path.clone());
+                if selected.contains(&key) {
+                    continue;
+                }
+
+                let protected = idx < keep_latest || now_ms - input.last_active_at < MIN_IDLE_MS;
+                let reason = if protected {
+                    None
+                } else if age_cutoff.is_some_and(|cutoff| input.last_active_at < cutoff) {
+                    Some(ArchiveReason::Age)
+                } else if size_limit.is_some_and(|limit| kept_bytes + input.size_bytes > limit) {
+                    Some(ArchiveReason::Size)
+                } else {
+                    None
+                };
+
+                match reason {
+                    Some(reason) => {
+                        selected.insert(key);
+                        candidates.push(RetentionCandidate {
+                            provider_id: input.meta.provider_id.clone(),
+                            session_id: input.meta.session_id.clone(),
+                            title: input.meta.title.clone(),
+                            project_dir: input.meta.project_dir.clone(),
+                            source_path: input.source_path.clone(),
+                            size_bytes: input.size_bytes,
+                            last_active_at: input.last_active_at,
+                            reason,
+                            rule_index,
+                        });
+                    }
+                    None => kept_bytes += input.size_bytes,
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+pub struct SessionArchiveService;
+
+impl SessionArchiveService {
+    /// 归档根目录 (~/.cc-switch/session-archive)
+    pub fn archive_root() -> PathBuf {
+        get_app_config_dir().join(ARCHIVE_DIR_NAME)
+    }
+
+    /// 把扫描结果转换为规划输入，返回 (可归档会话, 不支持归档的数量)
+    fn collect_inputs(sessions: Vec<SessionMeta>) -> (Vec<RetentionInput>, usize) {
+        let mut inputs = Vec::new();
+        let mut unsupported = 0usize;
+
+        for meta in sessions {
+            let Some(source_path) = meta.source_path.clone() else {
+                unsupported += 1;
+                continue;
+            };
+            if !archive::is_archivable(&meta.provider_id, &source_path) {
+                unsupported += 1;
+                continue;
+            }
+
+            let path = Path::new(&source_path);
+            let Ok(files) = archive::session_files(&meta.provider_id, path) else {
+                continue;
+            };
+            let mtime_ms = std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as i64);
+            let Some(last_active_at) = meta.last_active_at.or(mtime_ms).or(meta.created_at) else {
+                continue;
+            };
+
+            inputs.push(RetentionInput {
+                size_bytes: archive::total_size(&files),
+                source_path,
+                last_active_at,
+                meta,
+            });
+        }
+
+        (inputs, unsupported)
+    }
+
+    /// 归档单个会话：写 zip → 写索引 → 删除原文件；删除失败时回滚
+    pub fn archive_session(
+        db: &Database,
+        meta: &SessionMeta,
+        source_path: &str,
+        last_active_at: Option<i64>,
+        reason: ArchiveReason,
+    ) -> Result<ArchivedSession, AppError> {
+        if !archive::is_archivable(&meta.provider_id, source_path) {
+            return Err(AppError::Message(format!(
+                "不支持归档该会话来源: {}",
+                meta.provider_id
+            )));
+        }
+
+        let source = Path::new(source_path);
+        let base_dir = source
+            .parent()
+            .ok_or_else(|| AppError::Message(format!("无效的会话路径: {source_path}")))?;
+        let files = archive::session_files(&meta.provider_id, source).map_err(AppError::Message)?;
+
+        let archived_at = chrono::Utc::now().timestamp_millis();
+        let archive_path = Self::archive_root()
+            .join(&meta.provider_id)
+            .join(format!("{}-{archived_at}.zip", meta.session_id));
+        let original_size =
+            archive::write_archive(base_dir, &files, &archive_path).map_err(AppError::Message)?;
+        let archived_size = std::fs::metadata(&archive_path)
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        let record = ArchivedSession {
+            id: uuid::Uuid::new_v4().to_string(),
+            provider_id: meta.provider_id.clone(),
+            session_id: meta.session_id.clone(),
+            title: meta.title.clone(),
+            project_dir: meta.project_dir.clone(),
+            source_path: source_path.to_string(),
+            archive_path: archive_path.to_string_lossy().to_string(),
+            original_size,
+            archived_size,
+            last_active_at,
+            archived_at,
+            reason: reason.as_str().to_string(),
+        };
+
+        if let Err(e) = db.insert_session_archive(&record) {
+            let _ = std::fs::remove_file(&archive_path);
+            return Err(e);
+        }
+
+        if let Err(e) =
+            session_manager::delete_session(&meta.provider_id, &meta.session_id, source_path)
+        {
+            let _ = db.delete_session_archive(&record.id);
+            let _ = std::fs::remove_file(&archive_path);
+            return Err(AppError::Message(format!("删除原会话失败: {e}")));
+        }
+
+        log::info!(
+            "已归档会话 {}/{} ({} -> {} bytes)",
+            record.provider_id,
+            record.session_id,
+            original_size,
+            archived_size
+        );
+        Ok(record)
+    }
+
+    /// 手动归档：按来源路径在当前扫描结果中查找会话元数据
+    pub fn archive_sessions(
+        db: &Database,
+        requests: &[session_manager::DeleteSessionRequest],
+    ) -> Vec<ArchiveSessionOutcome> {
+        let sessions = session_manager::scan_sessions();
+        requests
+            .iter()
+            .map(|request| {
+                let meta = sessions
+                    .iter()
+                    .find(|s| {
+                        s.provider_id == request.provider_id
+                            && s.source_path.as_deref() == Some(request.source_path.as_str())
+                    })
+                    .cloned()
+                    .unwrap_or_else(|| SessionMeta {
+                        provider_id: request.provider_id.clone(),
+                        session_id: request.session_id.clone(),
+                        title: None,
+                        summary: None,
+                        project_dir: None,
+                        created_at: None,
+                        last_active_at: None,
+                        source_path: Some(request.source_path.clone()),
+                        resume_command: None,
+                        usage: None,
+                    });
+                let last_active_at = meta.last_active_at;
+                let result = Self::archive_session(
+                    db,
+                    &meta,
+                    &request.source_path,
+                    last_active_at,
+                    ArchiveReason::Manual,
+                );
+                ArchiveSessionOutcome {
+                    provider_id: request.provider_id.clone(),
+                    session_id: request.session_id.clone(),
+                    source_path: request.source_path.clone(),
+                    success: result.is_ok(),
+                    error: result.as_ref().err().map(|e| e.to_string()),
+                    archive: result.ok(),
+                }
+            })
+            .collect()
+    }
+
+    /// 恢复归档会话到原始位置，成功后删除归档文件与索引
+    pub fn restore_archive(db: &Database, id: &str) -> Result<ArchivedSession, AppError> {
+        let record = db
+            .get_session_archive(id)?
+            .ok_or_else(|| AppError::Message(format!("归档不存在: {id}")))?;
+
+        let dest_dir = Path::new(&record.source_path)
+            .parent()
+            .ok_or_else(|| AppError::Message(format!("无效的会话路径: {}", record.source_path)))?;
+        std::fs::create_dir_all(dest_dir).map_err(|e| AppError::io(dest_dir, e))?;
+        archive::extract_archive(Path::new(&record.archive_path), dest_dir)
+            .map_err(AppError::Message)?;
+
+        db.delete_session_archive(&record.id)?;
+        if let Err(e) = std::fs::remove_file(&record.archive_path) {
+            log::warn!("删除归档文件失败 {}: {e}", record.archive_path);
+        }
+
+        log::info!(
+            "已恢复会话 {}/{} -> {}",
+            record.provider_id,
+            record.session_id,
+            record.source_path
+        );
+        Ok(record)
+    }
+
+    /// 永久删除归档（不恢复）
+    pub fn delete_archive(db: &Database, id: &str) -> Result<bool, AppError> {
+        let Some(record) = db.get_session_archive(id)? else {
+            return Ok(false);
+        };
+        match std::fs::remove_file(&record.archive_path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(AppError::io(&record.archive_path, e)),
+        }
+        db.delete_session_archive(id)
+    }
+
+    /// 按当前配置执行保留策略；`dry_run` 时只返回候选列表
+    pub fn run_retention(db: &Database, dry_run: bool) -> Result<RetentionReport, AppError> {
+        let config = db.get_session_retention_config()?;
+        let now_ms = chrono::Utc::now().timestamp_millis();
+
+        let (inputs, skipped_unsupported) = Self::collect_inputs(session_manager::scan_sessions());
+        let candidates = plan_retention(&inputs, &config.rules, now_ms);
+        let total_bytes = candidates.iter().map(|c| c.size_bytes).sum();
+
+        let mut report = RetentionReport {
+            dry_run,
+            generated_at: now_ms,
+            candidates,
+            archived: Vec::new(),
+            skipped_unsupported,
+            errors: Vec::new(),
+            total_bytes,
+        };
+        if dry_run {
+            return Ok(report);
+        }
+
+        for candidate in &report.candidates {
+            let Some(input) = inputs.iter().find(|i| {
+                i.meta.provider_id == candidate.provider_id
+                    && i.source_path == candidate.source_path
+            }) else {
+                continue;
+            };
+            match Self::archive_session(
+                db,
+                &input.meta,
+                &candidate.source_path,
+                Some(candidate.last_active_at),
+                candidate.reason,
+            ) {
+                Ok(record) => report.archived.push(record),
+                Err(e) => report.errors.push(format!(
+                    "{}/{}: {e}",
+                    candidate.provider_id, candidate.session_id
+                )),
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn state_path() -> PathBuf {
+        Self::archive_root().join(RETENTION_STATE_FILE)
+    }
+
+    /// 读取定时执行状态（上次执行时间与报告）
+    pub fn load_state() -> RetentionState {
+        let path = Self::state_path();
+        if !path.exists() {
+            return RetentionState::default();
+        }
+        read_json_file(&path).unwrap_or_else(|e| {
+            log::warn!("读取会话保留状态失败: {e}");
+            RetentionState::default()
+        })
+    }
+
+    fn save_state(state: &RetentionState) -> Result<(), AppError> {
+        let path = Self::state_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        write_json_file(&path, state)
+    }
+
+    /// 定时任务入口：仅在启用且到期时执行
+    pub fn run_scheduled(db: &Database) -> Result<Option<RetentionReport>, AppError> {
+        let config = db.get_session_retention_config()?;
+        if !config.enabled || config.rules.is_empty() {
+            return Ok(None);
+        }
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let interval_ms = i64::from(config.interval_hours.max(1)) * 60 * 60 * 1000;
+        if let Some(last_run_at) = Self::load_state().last_run_at {
+            if now_ms - last_run_at < interval_ms {
+                return Ok(None);
+            }
+        }
+
+        let report = Self::run_retention(db, false)?;
+        Self::save_state(&RetentionState {
+            last_run_at: Some(now_ms),
+            last_report: Some(report.clone()),
+        })?;
+        Ok(Some(report))
+    }
+
+    /// 启动后台定时器（每小时检查一次是否到期）
+    pub fn start_worker(db: Arc<Database>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(WORKER_CHECK_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                let db = db.clone();
+                let result =
+                    tauri::async_runtime::spawn_blocking(move || Self::run_scheduled(&db)).await;
+                match result {
+                    Ok(Ok(Some(report))) => log::info!(
+                        "会话保留策略执行完成: 归档 {} 个, 失败 {} 个",
+                        report.archived.len(),
+                        report.errors.len()
+                    ),
+                    Ok(Ok(None)) => {}
+                    Ok(Err(e)) => log::warn!("会话保留策略执行失败: {e}"),
+                    Err(e) => log::warn!("会话保留策略任务异常: {e}"),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_800_000_000_000;
+
+    fn input(provider: &str, project: &str, id: &str, age_days: i64, size: u64) -> RetentionInput {
+        let source_path = format!("/sessions/{project}/{id}.jsonl");
+        RetentionInput {
+            meta: SessionMeta {
+                provider_id: provider.to_string(),
+                session_id: id.to_string(),
+                title: None,
+                summary: None,
+                project_dir: Some(format!("/work/{project}")),
+                created_at: None,
+                last_active_at: None,
+                source_path: Some(source_path.clone()),
+                resume_command: None,
+                usage: None,
+            },
+            source_path,
+            size_bytes: size,
+            last_active_at: NOW - age_days * DAY_MS,
+        }
+    }
+
+    fn ids(candidates: &[RetentionCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.session_id.as_str()).collect()
+    }
+
+    #[test]
+    fn age_rule_respects_keep_latest_and_provider_filter() {
+        let inputs = vec![
+            input("claude", "a", "old-1", 40, 10),
+            input("claude", "a", "old-2", 50, 10),
+            input("claude", "a", "fresh", 1, 10),
+            input("codex", "a", "codex-old", 90, 10),
+        ];
+        let rules = vec![SessionRetentionRule {
+            provider_id: Some("claude".to_string()),
+            max_age_days: Some(30),
+            keep_latest: Some(2),
+            ..Default::default()
+        }];
+
+        let candidates = plan_retention(&inputs, &rules, NOW);
+        assert_eq!(ids(&candidates), vec!["old-2"]);
+        assert_eq!(candidates[0].reason, ArchiveReason::Age);
+    }
+
+    #[test]
+    fn size_rule_archives_oldest_sessions_per_project() {
+        let mb = 1024 * 1024;
+        let inputs = vec![
+            input("claude", "a", "newest", 1, mb),
+            input("claude", "a", "middle", 2, mb),
+            input("claude", "a", "oldest", 3, mb),
+            input("claude", "b", "other", 3, mb),
+        ];
+        let rules = vec![SessionRetentionRule {
+            max_total_size_mb: Some(2),
+            ..Default::default()
+        }];
+
+        let candidates = plan_retention(&inputs, &rules, NOW);
+        assert_eq!(ids(&candidates), vec!["oldest"]);
+        assert_eq!(candidates[0].reason, ArchiveReason::Size);
+    }
+
+    #[test]
+    fn recently_active_sessions_are_never_selected() {
+        let mut busy = input("claude", "a", "busy", 0, 10);
+        busy.last_active_at = NOW - 60 * 1000;
+        let rules = vec![SessionRetentionRule {
+            max_total_size_mb: Some(0),
+            ..Default::default()
+        }];
+
+        assert!(plan_retention(&[busy], &rules, NOW).is_empty());
+    }
+
+    #[test]
+    fn project_prefix_and_first_matching_rule_win() {
+        let inputs = vec![
+            input("claude", "scratch-1", "s1", 40, 10),
+            input("claude", "keep", "k1", 40, 10),
+        ];
+        let rules = vec![
+            SessionRetentionRule {
+                project_dir: Some("/work/scratch".to_string()),
+                max_age_days: Some(7),
+                ..Default::default()
+            },
+            SessionRetentionRule {
+                max_age_days: Some(7),
+                ..Default::default()
+            },
+        ];
+
+        let candidates = plan_retention(&inputs, &rules, NOW);
+        assert_eq!(candidates.len(), 2);
+        let s1 = candidates.iter().find(|c| c.session_id == "s1").unwrap();
+        assert_eq!(s1.rule_index, 0);
+        let k1 = candidates.iter().find(|c| c.session_id == "k1").unwrap();
+        assert_eq!(k1.rule_index, 1);
+    }
+}
@@ -0,0 +1,545 @@
+//! 会话成本关联
+//!
+//! 把 `proxy_request_logs` 中带 `session_id` 的用量行（代理日志 + 会话日志导入）
+//! 按 `(app_type, session_id)` 聚合，再与会话管理器扫描到的会话关联，
+//! 得到每个会话的请求数、token、缓存命中、费用、使用过的供应商和时间跨度。
+//!
+//! 注意：`usage_daily_rollups` 不保留 session 维度，被 prune 的明细不会计入会话成本。
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::sql_helpers::fresh_input_sql;
+use crate::services::usage_stats::{
+    derive_real_total_and_hit_rate, effective_usage_log_filter, provider_name_coalesce,
+};
+use crate::session_manager::SessionMeta;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// 单个会话内某个供应商的用量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionProviderUsage {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub request_count: u64,
+    pub total_cost_usd: String,
+}
+
+/// 单个会话的用量汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionUsage {
+    pub request_count: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    /// input + output + cache_creation + cache_read（input 已做缓存归一化）
+    pub real_total_tokens: u64,
+    /// cache_read / (input + cache_creation + cache_read)，范围 0.0–1.0
+    pub cache_hit_rate: f64,
+    pub total_cost_usd: String,
+    /// 按费用降序排列
+    pub providers: Vec<SessionProviderUsage>,
+    pub first_request_at: i64,
+    pub last_request_at: i64,
+}
+
+/// 按项目目录聚合的会话成本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectCostRollup {
+    /// None 表示会话未记录项目目录（或会话文件已不存在）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_dir: Option<String>,
+    pub session_count: u64,
+    pub request_count: u64,
+    pub real_total_tokens: u64,
+    pub total_cost_usd: String,
+    /// 该项目下使用过的供应商 ID（去重，按首次出现顺序）
+    pub provider_ids: Vec<String>,
+}
+
+/// 「最贵会话」查询结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCostReport {
+    /// 按费用降序，最多 `limit` 条；每条都带有 `usage`
+    pub sessions: Vec<SessionMeta>,
+    /// 覆盖全部有用量的会话（不受 `limit` 截断），按费用降序
+    pub projects: Vec<ProjectCostRollup>,
+    pub total_cost_usd: String,
+}
+
+/// 会话成本查询参数
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCostQuery {
+    pub app_type: Option<String>,
+    pub start_date: Option<i64>,
+    pub end_date: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+const DEFAULT_TOP_SESSIONS_LIMIT: usize = 20;
+
+/// `(app_type, session_id)`
+pub type SessionUsageKey = (String, String);
+
+fn parse_cost(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap_or(Decimal::ZERO)
+}
+
+fn format_cost(value: Decimal) -> String {
+    format!("{:.6}", value.round_dp(6))
+}
+
+impl Database {
+    /// 按 `(app_type, session_id)` 聚合带 session_id 的用量行
+    pub fn get_session_usage_map(
+        &self,
+        app_type: Option<&str>,
+        start_date: Option<i64>,
+        end_date: Option<i64>,
+    ) -> Result<HashMap<SessionUsageKey, SessionUsage>, AppError> {
+        let conn = lock_conn!(self.conn);
+
+        let mut conditions = vec![
+            effective_usage_log_filter("l"),
+            "l.session_id IS NOT NULL".to_string(),
+            "l.session_id != ''".to_string(),
+        ];
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        if let Some(at) = app_type {
+            conditions.push("l.app_type = ?".to_string());
+            params.push(Box::new(at.to_string()));
+        }
+        if let Some(start) = start_date {
+            conditions.push("l.created_at >= ?".to_string());
+            params.push(Box::new(start));
+        }
+        if let Some(end) = end_date {
+            conditions.push("l.created_at <= ?".to_string());
+            params.push(Box::new(end));
+        }
+        let where_clause = format!("WHERE {}", conditions.join(" AND "));
+
+        // 按 (app_type, session_id, provider_id) 分组，供应商维度在 Rust 侧折叠，
+        // 避免 GROUP_CONCAT 对供应商名中的分隔符敏感。
+        let pname = provider_name_coalesce("l", "p");
+        let fresh_input = fresh_input_sql("l");
+        let sql = format!(
+            "SELECT l.app_type, l.session_id, l.provider_id, {pname} as provider_name,
+                    COUNT(*),
+                    COALESCE(SUM({fresh_input}), 0),
+                    COALESCE(SUM(l.output_tokens), 0),
+                    COALESCE(SUM(l.cache_read_tokens), 0),
+                    COALESCE(SUM(l.cache_creation_tokens), 0),
+                    COALESCE(SUM(CAST(l.total_cost_usd AS REAL)), 0),
+                    MIN(l.created_at),
+                    MAX(l.created_at)
+             FROM proxy_request_logs l
+             LEFT JOIN providers p ON l.provider_id = p.id AND l.app_type = p.app_type
+             {where_clause}
+             GROUP BY l.app_type, l.session_id, l.provider_id"
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let rows = stmt.query_map(param_refs.as_slice(), |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, i64>(5)?,
+                row.get::<_, i64>(6)?,
+                row.get::<_, i64>(7)?,
+                row.get::<_, i64>(8)?,
+                row.get::<_, f64>(9)?,
+                row.get::<_, i64>(10)?,
+                row.get::<_, i64>(11)?,
+            ))
+        })?;
+
+        let mut map: HashMap<SessionUsageKey, SessionUsage> = HashMap::new();
+        for row in rows {
+            let (
+                app_type,
+                session_id,
+                provider_id,
+                provider_name,
+                request_count,
+                input_tokens,
+                output_tokens,
+                cache_read_tokens,
+                cache_creation_tokens,
+                total_cost,
+                first_at,
+                last_at,
+            ) = row?;
+
+            let provider_cost = Decimal::from_f64_retain(total_cost).unwrap_or(Decimal::ZERO);
+            let entry = map
+                .entry((app_type, session_id))
+                .or_insert_with(|| SessionUsage {
+                    request_count: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cache_read_tokens: 0,
+                    cache_creation_tokens: 0,
+                    real_total_tokens: 0,
+                    cache_hit_rate: 0.0,
+                    total_cost_usd: "0".to_string(),
+                    providers: Vec::new(),
+                    first_request_at: first_at,
+                    last_request_at: last_at,
+                });
+
+            entry.request_count += request_count as u64;
+            entry.input_tokens += input_tokens as u64;
+            entry.output_tokens += output_tokens as u64;
+            entry.cache_read_tokens += cache_read_tokens as u64;
+            entry.cache_creation_tokens += cache_creation_tokens as u64;
+            entry.total_cost_usd = format_cost(parse_cost(&entry.total_cost_usd) + provider_cost);
+            entry.first_request_at = entry.first_request_at.min(first_at);
+            entry.last_request_at = entry.last_request_at.max(last_at);
+            entry.providers.push(SessionProviderUsage {
+                provider_id,
+                provider_name,
+                request_count: request_count as u64,
+                total_cost_usd: format_cost(provider_cost),
+            });
+        }
+
+        for usage in map.values_mut() {
+            let (real_total, hit_rate) = derive_real_total_and_hit_rate(
+                usage.input_tokens,
+                usage.output_tokens,
+                usage.cache_creation_tokens,
+                usage.cache_read_tokens,
+            );
+            usage.real_total_tokens = real_total;
+            usage.cache_hit_rate = hit_rate;
+            usage.providers.sort_by(|a, b| {
+                parse_cost(&b.total_cost_usd)
+                    .cmp(&parse_cost(&a.total_cost_usd))
+                    .then_with(|| b.request_count.cmp(&a.request_count))
+            });
+        }
+
+        Ok(map)
+    }
+}
+
+/// 为扫描到的会话填充 `usage` 字段
+///
+/// 会话的 `provider_id`（claude/codex/...）与用量行的 `app_type` 同名。
+pub fn attach_session_usage(
+    sessions: &mut [SessionMeta],
+    usage: &HashMap<SessionUsageKey, SessionUsage>,
+) {
+    for session in sessions.iter_mut() {
+        let key = (session.provider_id.clone(), session.session_id.clone());
+        session.usage = usage.get(&key).cloned();
+    }
+}
+
+/// 由会话列表 + 用量表构建「最贵会话」报告
+///
+/// 有用量但会话文件已不存在的记录也会计入（仅含 ID，无标题和项目目录），
+/// 否则被删除的会话花掉的钱会从项目汇总里消失。
+pub fn build_session_cost_report(
+    sessions: Vec<SessionMeta>,
+    mut usage: HashMap<SessionUsageKey, SessionUsage>,
+    limit: usize,
+) -> SessionCostReport {
+    let mut costed: Vec<SessionMeta> = Vec::new();
+
+    for mut session in sessions {
+        let key = (session.provider_id.clone(), session.session_id.clone());
+        if let Some(u) = usage.remove(&key) {
+            session.usage = Some(u);
+            costed.push(session);
+        }
+    }
+
+    for ((app_type, session_id), u) in usage {
+        costed.push(SessionMeta {
+            provider_id: app_type,
+            session_id,
+            title: None,
+            summary: None,
+            project_dir: None,
+            created_at: Some(u.first_request_at * 1000),
+            last_active_at: Some(u.last_request_at * 1000),
+            source_path: None,
+            resume_command: None,
+            usage: Some(u),
+        });
+    }
+
+    let session_cost = |s: &SessionMeta| s.usage.as_ref().map(|u| parse_cost(&u.total_cost_usd));
+    costed.sort_by(|a, b| {
+        session_cost(b)
+            .cmp(&session_cost(a))
+            .then_with(|| b.session_id.cmp(&a.session_id))
+    });
+
+    let mut total_cost = Decimal::ZERO;
+    let mut projects: Vec<(ProjectCostRollup, Decimal)> = Vec::new();
+    let mut project_index: HashMap<Option<String>, usize> = HashMap::new();
+
+    for session in &costed {
+        let Some(u) = session.usage.as_ref() else {
+            continue;
+        };
+        let cost = parse_cost(&u.total_cost_usd);
+        total_cost += cost;
+
+        let idx = *project_index
+            .entry(session.project_dir.clone())
+            .or_insert_with(|| {
+                projects.push((
+                    ProjectCostRollup {
+                        project_dir: session.project_dir.clone(),
+                        session_count: 0,
+                        request_count: 0,
+                        real_total_tokens: 0,
+                        total_cost_usd: "0".to_string(),
+                        provider_ids: Vec::new(),
+                    },
+                    Decimal::ZERO,
+                ));
+                projects.len() - 1
+            });
+
+        let (rollup, project_cost) = &mut projects[idx];
+        rollup.session_count += 1;
+        rollup.request_count += u.request_count;
+        rollup.real_total_tokens += u.real_total_tokens;
+        *project_cost += cost;
+        for provider in &u.providers {
+            if !rollup.provider_ids.contains(&provider.provider_id) {
+                rollup.provider_ids.push(provider.provider_id.clone());
+            }
+        }
+    }
+
+    projects.sort_by(|a, b| b.1.cmp(&a.1));
+    let projects = projects
+        .into_iter()
+        .map(|(mut rollup, cost)| {
+            rollup.total_cost_usd = format_cost(cost);
+            rollup
+        })
+        .collect();
+
+    costed.truncate(limit);
+
+    SessionCostReport {
+        sessions: costed,
+        projects,
+        total_cost_usd: format_cost(total_cost),
+    }
+}
+
+/// 扫描本地会话并关联用量，返回最贵的会话及按项目的汇总
+pub fn get_session_cost_report(
+    db: &Database,
+    query: &SessionCostQuery,
+) -> Result<SessionCostReport, AppError> {
+    let usage =
+        db.get_session_usage_map(query.app_type.as_deref(), query.start_date, query.end_date)?;
+    let sessions = crate::session_manager::scan_sessions()
+        .into_iter()
+        .filter(|s| {
+            query
+                .app_type
+                .as_deref()
+                .map(|at| s.provider_id == at)
+                .unwrap_or(true)
+        })
+        .collect();
+    let limit = query.limit.unwrap_or(DEFAULT_TOP_SESSIONS_LIMIT);
+    Ok(build_session_cost_report(sessions, usage, limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+
+    #[allow(clippy::too_many_arguments)]
+    fn insert_session_log(
+        db: &Database,
+        request_id: &str,
+        app_type: &str,
+        provider_id: &str,
+        session_id: Option<&str>,
+        input_tokens: i64,
+        output_tokens: i64,
+        cache_read_tokens: i64,
+        total_cost_usd: &str,
+        created_at: i64,
+    ) -> Result<(), AppError> {
+        let conn = lock_conn!(db.conn);
+        conn.execute(
+            "INSERT INTO proxy_request_logs (
+                request_id, provider_id, app_type, model, input_tokens, output_tokens,
+                cache_read_tokens, total_cost_usd, latency_ms, status_code, session_id,
+                created_at
+            ) VALUES (?1, ?2, ?3, 'm', ?4, ?5, ?6, ?7, 10, 200, ?8, ?9)",
+            params![
+                request_id,
+                provider_id,
+                app_type,
+                input_tokens,
+                output_tokens,
+                cache_read_tokens,
+                total_cost_usd,
+                session_id,
+                created_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn session(app: &str, id: &str, project: Option<&str>) -> SessionMeta {
+        SessionMeta {
+            provider_id: app.to_string(),
+            session_id: id.to_string(),
+            title: Some(id.to_string()),
+            summary: None,
+            project_dir: project.map(|p| p.to_string()),
+            created_at: None,
+            last_active_at: None,
+            source_path: None,
+            resume_command: None,
+            usage: None,
+        }
+    }
+
+    #[test]
+    fn test_session_usage_map_groups_by_app_and_session() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        insert_session_log(
+            &db,
+            "r1",
+            "claude",
+            "p1",
+            Some("s1"),
+            100,
+            10,
+            300,
+            "0.5",
+            1000,
+        )?;
+        insert_session_log(
+            &db,
+            "r2",
+            "claude",
+            "p2",
+            Some("s1"),
+            50,
+            5,
+            0,
+            "1.25",
+            2000,
+        )?;
+        insert_session_log(&db, "r3", "codex", "p1", Some("s1"), 10, 1, 0, "0.1", 1500)?;
+        insert_session_log(&db, "r4", "claude", "p1", None, 10, 1, 0, "9", 1500)?;
+
+        let map = db.get_session_usage_map(None, None, None)?;
+        assert_eq!(map.len(), 2);
+
+        let claude = map
+            .get(&("claude".to_string(), "s1".to_string()))
+            .expect("claude session usage");
+        assert_eq!(claude.request_count, 2);
+        assert_eq!(claude.input_tokens, 150);
+        assert_eq!(claude.cache_read_tokens, 300);
+        assert_eq!(claude.total_cost_usd, "1.750000");
+        assert_eq!(claude.first_request_at, 1000);
+        assert_eq!(claude.last_request_at, 2000);
+        assert_eq!(claude.providers.len(), 2);
+        assert_eq!(claude.providers[0].provider_id, "p2");
+        assert!((claude.cache_hit_rate - 300.0 / 450.0).abs() < 1e-9);
+
+        let filtered = db.get_session_usage_map(Some("claude"), Some(1500), None)?;
+        let claude = filtered
+            .get(&("claude".to_string(), "s1".to_string()))
+            .expect("filtered claude usage");
+        assert_eq!(claude.request_count, 1);
+        assert_eq!(filtered.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_attach_session_usage_matches_provider_and_session_id() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        insert_session_log(&db, "r1", "codex", "p1", Some("abc"), 10, 1, 0, "0.2", 1000)?;
+
+        let mut sessions = vec![
+            session("codex", "abc", None),
+            session("claude", "abc", None),
+        ];
+        let map = db.get_session_usage_map(None, None, None)?;
+        attach_session_usage(&mut sessions, &map);
+
+        assert_eq!(sessions[0].usage.as_ref().map(|u| u.request_count), Some(1));
+        assert!(sessions[1].usage.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cost_report_ranks_sessions_and_rolls_up_projects() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        insert_session_log(&db, "r1", "claude", "p1", Some("a"), 10, 1, 0, "1", 1000)?;
+        insert_session_log(&db, "r2", "claude", "p2", Some("b"), 10, 1, 0, "3", 1000)?;
+        insert_session_log(&db, "r3", "codex", "p3", Some("c"), 10, 1, 0, "2", 1000)?;
+        insert_session_log(
+            &db,
+            "r4",
+            "claude",
+            "p1",
+            Some("gone"),
+            10,
+            1,
+            0,
+            "0.5",
+            1000,
+        )?;
+
+        let sessions = vec![
+            session("claude", "a", Some("/repo/x")),
+            session("claude", "b", Some("/repo/x")),
+            session("codex", "c", Some("/repo/y")),
+            session("claude", "idle", Some("/repo/z")),
+        ];
+        let usage = db.get_session_usage_map(None, None, None)?;
+        let report = build_session_cost_report(sessions, usage, 2);
+
+        assert_eq!(report.total_cost_usd, "6.500000");
+        let ids: Vec<&str> = report
+            .sessions
+            .iter()
+            .map(|s| s.session_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["b", "c"]);
+
+        assert_eq!(report.projects.len(), 3);
+        assert_eq!(report.projects[0].project_dir.as_deref(), Some("/repo/x"));
+        assert_eq!(report.projects[0].session_count, 2);
+        assert_eq!(report.projects[0].total_cost_usd, "4.000000");
+        assert_eq!(report.projects[0].provider_ids, vec!["p2", "p1"]);
+        assert_eq!(report.projects[2].project_dir, None);
+        assert_eq!(report.projects[2].total_cost_usd, "0.500000");
+        Ok(())
+    }
+}
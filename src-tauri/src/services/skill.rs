@@ -15,10 +15,14 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::timeout;
 
-use crate::app_config::{AppType, InstalledSkill, SkillApps, UnmanagedSkill};
+use crate::app_config::{
+    AppType, InstalledSkill, SkillApps, SkillSource, SkillSourceKind, UnmanagedSkill,
+};
 use crate::config::get_app_config_dir;
 use crate::database::Database;
 use crate::error::format_skill_error;
+use crate::services::skill_git::{self, ResolvedRef};
+use crate::services::skill_lint;
 
 // ========== 数据结构 ==========
 
@@ -182,10 +186,52 @@ pub struct SkillUpdateInfo {
     pub name: String,
     /// 当前本地哈希
     pub current_hash: Option<String>,
-    /// 远程最新哈希
+    /// 远程最新哈希（git 来源的 Skill 为可用的 commit）
     pub remote_hash: String,
+    /// 固定的 ref（tag / commit），仅固定版本的 Skill 有值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_ref: Option<String>,
+    /// 可用的新版本（tag 或分支名）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_ref: Option<String>,
 }
 
+/// 从任意 git 仓库或本地目录安装 Skill 的请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillSourceRequest {
+    /// git 仓库地址（https / ssh / 本地裸仓库）或本地目录
+    pub url: String,
+    /// 分支、tag 或 commit；为空时跟随默认分支
+    #[serde(default, rename = "ref")]
+    pub git_ref: Option<String>,
+    /// 仓库内的 Skill 目录；为空时安装仓库中找到的全部 Skill
+    #[serde(default)]
+    pub path: Option<String>,
+    /// 已确认安装前检查的警告
+    #[serde(default)]
+    pub acknowledge_warnings: bool,
+}
+
+/// 已获取到本地的来源内容；git 来源的临时克隆目录在释放时清理
+struct FetchedSource {
+    root: PathBuf,
+    temporary: bool,
+    resolved: Option<ResolvedRef>,
+    commit: Option<String>,
+}
+
+impl Drop for FetchedSource {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+}
+
+/// git 来源操作的超时时间（秒）
+const SOURCE_TIMEOUT_SECS: u64 = 120;
+
 /// Skill 存储位置迁移结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -457,7 +503,7 @@ impl SkillService {
     }
 
     /// 从旧 readme_url 中提取仓库内文档路径，兼容 `blob`/`tree` 两种格式
-    fn extract_doc_path_from_url(url: &str) -> Option<String> {
+    pub(crate) fn extract_doc_path_from_url(url: &str) -> Option<String> {
         let marker = if url.contains("/blob/") {
             "/blob/"
         } else if url.contains("/tree/") {
@@ -510,7 +556,11 @@ impl SkillService {
                     return Ok(custom.join("skills"));
                 }
             }
-            AppType::ClaudeDesktop => {}
+            AppType::ClaudeDesktop
+            | AppType::Qwen
+            | AppType::Goose
+            | AppType::Crush
+            | AppType::Aider => {}
             AppType::Codex => {
                 if let Some(custom) = crate::settings::get_codex_override_dir() {
                     return Ok(custom.join("skills"));
@@ -553,6 +603,13 @@ impl SkillService {
             AppType::OpenCode => home.join(".config").join("opencode").join("skills"),
             AppType::OpenClaw => home.join(".openclaw").join("skills"),
             AppType::Hermes => crate::hermes_config::get_hermes_dir().join("skills"),
+            AppType::Qwen => crate::qwen_config::get_qwen_dir().join("skills"),
+            // Goose / Crush / Aider 不读取 skills 目录，同步入口已提前返回
+            AppType::Goose => crate::goose_config::get_goose_dir().join("skills"),
+            AppType::Crush => crate::crush_config::get_crush_dir().join("skills"),
+            AppType::Aider => crate::aider_config::get_aider_dir()
+                .join(".aider")
+                .join("skills"),
         })
     }
 
@@ -575,6 +632,7 @@ impl SkillService {
         db: &Arc<Database>,
         skill: &DiscoverableSkill,
         current_app: &AppType,
+        acknowledge_warnings: bool,
     ) -> Result<InstalledSkill> {
         let ssot_dir = Self::get_ssot_dir()?;
 
@@ -703,6 +761,13 @@ impl SkillService {
                 )));
             }
 
+            // 安装前检查：有错误时拒绝，有警告时需要用户确认
+            let report = skill_lint::lint_skill_dir(&canonical_source, &install_name);
+            if let Err(e) = skill_lint::ensure_installable(&[report], acknowledge_warnings) {
+                let _ = fs::remove_dir_all(&temp_dir);
+                return Err(e);
+            }
+
             Self::copy_dir_recursive(&canonical_source, &dest)?;
             let _ = fs::remove_dir_all(&temp_dir);
 
@@ -762,6 +827,7 @@ impl SkillService {
             installed_at: chrono::Utc::now().timestamp(),
             content_hash,
             updated_at: 0,
+            source: None,
         };
 
         // 保存到数据库
@@ -882,7 +948,13 @@ impl SkillService {
         let mut repo_groups: HashMap<(String, String, String), Vec<InstalledSkill>> =
             HashMap::new();
 
+        let mut sourced = Vec::new();
+
         for skill in skills.into_values() {
+            if skill.source.is_some() {
+                sourced.push(skill);
+                continue;
+            }
             let (owner, name, branch) =
                 match (&skill.repo_owner, &skill.repo_name, &skill.repo_branch) {
                     (Some(o), Some(n), Some(b)) => (o.clone(), n.clone(), b.clone()),
@@ -977,6 +1049,8 @@ impl SkillService {
                         name: skill.name.clone(),
                         current_hash: local_hash,
                         remote_hash,
+                        pinned_ref: None,
+                        available_ref: None,
                     });
                 }
             }
@@ -984,15 +1058,49 @@ impl SkillService {
             let _ = fs::remove_dir_all(&temp_dir);
         }
 
+        if !sourced.is_empty() {
+            match Self::run_source_task(move || Ok(Self::check_source_updates(&sourced))).await {
+                Ok(found) => updates.extend(found),
+                Err(e) => log::warn!("检查 git 来源 Skill 更新失败: {e}"),
+            }
+        }
+
         Ok(updates)
     }
 
     /// 更新单个 Skill（重新下载并替换本地文件）
-    pub async fn update_skill(&self, db: &Arc<Database>, skill_id: &str) -> Result<InstalledSkill> {
+    ///
+    /// 新内容同样要通过安装前检查；有警告且未确认时返回 `SKILL_LINT_WARNINGS` 错误（附检查报告）。
+    pub async fn update_skill(
+        &self,
+        db: &Arc<Database>,
+        skill_id: &str,
+        acknowledge_warnings: bool,
+    ) -> Result<InstalledSkill> {
         let skill = db
             .get_installed_skill(skill_id)?
             .ok_or_else(|| anyhow!("Skill not found: {skill_id}"))?;
 
+        if let Some(source) = skill.source.clone() {
+            if source.pinned {
+                let pinned_ref = source
+                    .git_ref
+                    .clone()
+                    .or(source.resolved_commit.clone())
+                    .unwrap_or_default();
+                return Err(anyhow!(format_skill_error(
+                    "SKILL_PINNED",
+                    &[("name", &skill.name), ("ref", &pinned_ref)],
+                    Some("repinSkill"),
+                )));
+            }
+            let db = db.clone();
+            return Self::run_source_task(move || {
+                Self::refresh_from_source(&db, &skill, source, acknowledge_warnings)
+            })
+            .await;
+        }
+
         let (owner, name, branch) = match (&skill.repo_owner, &skill.repo_name) {
             (Some(o), Some(n)) => (
                 o.clone(),
@@ -1058,6 +1166,13 @@ impl SkillService {
                 ))
             })?;
 
+        // 更新内容同样需要通过检查：有错误时拒绝，有警告时需要用户确认
+        let report = skill_lint::lint_skill_dir(&source, &skill.directory);
+        if let Err(e) = skill_lint::ensure_installable(&[report], acknowledge_warnings) {
+            let _ = fs::remove_dir_all(&temp_dir);
+            return Err(e);
+        }
+
         // 备份旧文件
         let _ = Self::create_uninstall_backup(&skill);
 
@@ -1100,6 +1215,7 @@ impl SkillService {
             installed_at: skill.installed_at,
             content_hash: new_hash,
             updated_at: chrono::Utc::now().timestamp(),
+            source: skill.source.clone(),
         };
 
         db.save_skill(&updated_skill)?;
@@ -1115,6 +1231,389 @@ impl SkillService {
         Ok(updated_skill)
     }
 
+    // ========== 任意 git 仓库 / 本地目录来源 ==========
+
+    /// 在阻塞线程中执行 git / 文件操作，并限制总耗时
+    async fn run_source_task<T, F>(task: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        timeout(
+            std::time::Duration::from_secs(SOURCE_TIMEOUT_SECS),
+            tokio::task::spawn_blocking(task),
+        )
+        .await
+        .map_err(|_| {
+            anyhow!(format_skill_error(
+                "GIT_FAILED",
+                &[
+                    ("command", "fetch"),
+                    ("message", &format!("timeout ({SOURCE_TIMEOUT_SECS}s)"))
+                ],
+                Some("checkNetwork"),
+            ))
+        })?
+        .map_err(|e| anyhow!("Skill 来源任务异常退出: {e}"))?
+    }
+
+    /// 获取来源内容：git 仓库克隆并检出指定 ref，本地目录直接使用
+    fn fetch_source(
+        kind: SkillSourceKind,
+        url: &str,
+        git_ref: Option<&str>,
+    ) -> Result<FetchedSource> {
+        match kind {
+            SkillSourceKind::Git => {
+                let refs = skill_git::ls_remote(url)?;
+                let resolved = skill_git::resolve_ref(&refs, git_ref)?;
+                let (root, commit) = skill_git::checkout(url, &resolved)?;
+                Ok(FetchedSource {
+                    root,
+                    temporary: true,
+                    resolved: Some(resolved),
+                    commit: Some(commit),
+                })
+            }
+            SkillSourceKind::Local => {
+                if git_ref.is_some_and(|r| !r.trim().is_empty()) {
+                    return Err(anyhow!("本地目录不是 git 仓库，无法指定 ref: {url}"));
+                }
+                let root = Path::new(url);
+                if !root.is_dir() {
+                    return Err(anyhow!(format_skill_error(
+                        "SKILL_DIR_NOT_FOUND",
+                        &[("path", url)],
+                        Some("checkRepoUrl"),
+                    )));
+                }
+                Ok(FetchedSource {
+                    root: root.to_path_buf(),
+                    temporary: false,
+                    resolved: None,
+                    commit: None,
+                })
+            }
+        }
+    }
+
+    /// 来源中某个 Skill 的目录（`path` 为空表示来源根目录）
+    fn source_skill_dir(root: &Path, path: Option<&str>) -> Result<PathBuf> {
+        let dir = match path {
+            Some(raw) => Self::sanitize_skill_source_path(raw).map(|rel| root.join(rel)),
+            None => Some(root.to_path_buf()),
+        };
+        dir.filter(|d| d.join("SKILL.md").is_file()).ok_or_else(|| {
+            anyhow!(format_skill_error(
+                "SKILL_DIR_NOT_FOUND",
+                &[("path", path.unwrap_or("SKILL.md"))],
+                Some("checkRepoUrl"),
+            ))
+        })
+    }
+
+    /// 从任意 git 仓库（可固定 tag / commit）或本地目录安装 Skill
+    pub async fn install_from_source(
+        db: &Arc<Database>,
+        request: SkillSourceRequest,
+        current_app: &AppType,
+    ) -> Result<Vec<InstalledSkill>> {
+        let db = db.clone();
+        let app = current_app.clone();
+        Self::run_source_task(move || Self::install_from_source_blocking(&db, &request, &app)).await
+    }
+
+    fn install_from_source_blocking(
+        db: &Arc<Database>,
+        request: &SkillSourceRequest,
+        current_app: &AppType,
+    ) -> Result<Vec<InstalledSkill>> {
+        let url = request.url.trim();
+        if url.is_empty() {
+            return Err(anyhow!("Skill 来源地址不能为空"));
+        }
+        let kind = if skill_git::is_git_source(url) {
+            SkillSourceKind::Git
+        } else {
+            SkillSourceKind::Local
+        };
+        let git_ref = request
+            .git_ref
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty());
+
+        let fetched = Self::fetch_source(kind, url, git_ref)?;
+        let skill_dirs = match request
+            .path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            Some(path) => vec![Self::source_skill_dir(&fetched.root, Some(path))?],
+            None => Self::scan_skills_in_dir(&fetched.root)?,
+        };
+        if skill_dirs.is_empty() {
+            return Err(anyhow!(format_skill_error(
+                "SKILL_DIR_NOT_FOUND",
+                &[("path", url)],
+                Some("checkRepoUrl"),
+            )));
+        }
+
+        let reports: Vec<_> = skill_dirs
+            .iter()
+            .map(|dir| {
+                let name = dir
+                    .strip_prefix(&fetched.root)
+                    .ok()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .filter(|p| !p.is_empty())
+                    .unwrap_or_else(|| "SKILL.md".to_string());
+                skill_lint::lint_skill_dir(dir, &name)
+            })
+            .collect();
+        skill_lint::ensure_installable(&reports, request.acknowledge_warnings)?;
+
+        let ssot_dir = Self::get_ssot_dir()?;
+        let existing_skills = db.get_all_installed_skills()?;
+        let url_stem = url
+            .trim_end_matches('/')
+            .rsplit(['/', '\\', ':'])
+            .next()
+            .map(|s| s.trim_end_matches(".git").to_string());
+        let mut installed = Vec::new();
+
+        for skill_dir in skill_dirs {
+            let skill_md = skill_dir.join("SKILL.md");
+            let meta = Self::parse_skill_metadata_static(&skill_md).ok();
+            let rel_path = skill_dir
+                .strip_prefix(&fetched.root)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .filter(|p| !p.is_empty());
+
+            // 根目录的 Skill 用元数据 name 或仓库名作为安装名
+            let install_name = match &rel_path {
+                Some(rel) => Self::sanitize_install_name(rel.rsplit('/').next().unwrap_or(rel)),
+                None => meta
+                    .as_ref()
+                    .and_then(|m| m.name.as_deref())
+                    .and_then(Self::sanitize_install_name)
+                    .or_else(|| url_stem.as_deref().and_then(Self::sanitize_install_name)),
+            }
+            .ok_or_else(|| {
+                anyhow!(format_skill_error(
+                    "INVALID_SKILL_DIRECTORY",
+                    &[("path", &skill_dir.display().to_string())],
+                    Some("checkRepoUrl"),
+                ))
+            })?;
+
+            if let Some(existing) = existing_skills
+                .values()
+                .find(|s| s.directory.eq_ignore_ascii_case(&install_name))
+            {
+                log::warn!(
+                    "Skill directory '{}' already exists (from {}), skipping",
+                    install_name,
+                    existing.id
+                );
+                continue;
+            }
+
+            let (name, description) = Self::read_skill_name_desc(&skill_md, &install_name);
+            let dest = ssot_dir.join(&install_name);
+            Self::replace_dest_with_copy(&skill_dir, &dest, &install_name)?;
+
+            let prefix = match kind {
+                SkillSourceKind::Git => "git",
+                SkillSourceKind::Local => "local",
+            };
+            let skill = InstalledSkill {
+                id: format!("{prefix}:{install_name}"),
+                name,
+                description,
+                directory: install_name.clone(),
+                repo_owner: None,
+                repo_name: None,
+                repo_branch: None,
+                readme_url: None,
+                apps: SkillApps::only(current_app),
+                installed_at: chrono::Utc::now().timestamp(),
+                content_hash: Self::compute_dir_hash(&dest).ok(),
+                updated_at: 0,
+                source: Some(SkillSource {
+                    kind,
+                    url: url.to_string(),
+                    path: rel_path,
+                    git_ref: git_ref.map(str::to_string),
+                    pinned: fetched
+                        .resolved
+                        .as_ref()
+                        .is_some_and(ResolvedRef::is_pinned),
+                    resolved_commit: fetched.commit.clone(),
+                }),
+            };
+
+            db.save_skill(&skill)?;
+            Self::sync_to_app_dir(&install_name, current_app)?;
+
+            log::info!(
+                "Skill {} installed from {} ({}), enabled for {:?}",
+                skill.name,
+                url,
+                fetched.commit.as_deref().unwrap_or("working tree"),
+                current_app
+            );
+            installed.push(skill);
+        }
+
+        Ok(installed)
+    }
+
+    /// 把 git 来源的 Skill 固定到新的 ref（tag / commit），`None` 表示改为跟随默认分支
+    pub async fn pin_skill(
+        db: &Arc<Database>,
+        skill_id: &str,
+        git_ref: Option<String>,
+        acknowledge_warnings: bool,
+    ) -> Result<InstalledSkill> {
+        let skill = db
+            .get_installed_skill(skill_id)?
+            .ok_or_else(|| anyhow!("Skill not found: {skill_id}"))?;
+        let mut source = match &skill.source {
+            Some(source) if source.kind == SkillSourceKind::Git => source.clone(),
+            _ => {
+                return Err(anyhow!(
+                    "只有从 git 仓库安装的 Skill 可以固定版本: {skill_id}"
+                ))
+            }
+        };
+        source.git_ref = git_ref
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty());
+
+        let db = db.clone();
+        Self::run_source_task(move || {
+            Self::refresh_from_source(&db, &skill, source, acknowledge_warnings)
+        })
+        .await
+    }
+
+    /// 按来源重新获取 Skill 内容并替换 SSOT 目录
+    fn refresh_from_source(
+        db: &Arc<Database>,
+        skill: &InstalledSkill,
+        mut source: SkillSource,
+        acknowledge_warnings: bool,
+    ) -> Result<InstalledSkill> {
+        let fetched = Self::fetch_source(source.kind, &source.url, source.git_ref.as_deref())?;
+        let skill_dir = Self::source_skill_dir(&fetched.root, source.path.as_deref())?;
+        let report = skill_lint::lint_skill_dir(&skill_dir, &skill.directory);
+        skill_lint::ensure_installable(&[report], acknowledge_warnings)?;
+
+        // 备份旧文件后替换
+        let _ = Self::create_uninstall_backup(skill);
+        let dest = Self::get_ssot_dir()?.join(&skill.directory);
+        Self::replace_dest_with_copy(&skill_dir, &dest, &skill.directory)?;
+
+        let (name, description) =
+            Self::read_skill_name_desc(&dest.join("SKILL.md"), &skill.directory);
+        source.pinned = fetched
+            .resolved
+            .as_ref()
+            .is_some_and(ResolvedRef::is_pinned);
+        source.resolved_commit = fetched.commit.clone();
+
+        let updated_skill = InstalledSkill {
+            name,
+            description,
+            content_hash: Self::compute_dir_hash(&dest).ok(),
+            updated_at: chrono::Utc::now().timestamp(),
+            source: Some(source),
+            ..skill.clone()
+        };
+        db.save_skill(&updated_skill)?;
+
+        for app in updated_skill.apps.enabled_apps() {
+            if let Err(e) = Self::sync_to_app_dir(&updated_skill.directory, &app) {
+                log::warn!("同步更新后的 skill 到 {:?} 失败: {e}", app);
+            }
+        }
+
+        log::info!(
+            "Skill {} 已从来源更新到 {}",
+            updated_skill.name,
+            fetched.commit.as_deref().unwrap_or("working tree")
+        );
+        Ok(updated_skill)
+    }
+
+    /// 检查 git / 本地来源 Skill 的更新：
+    /// - 跟随分支：分支有新提交
+    /// - 固定 tag：有版本号更高的 tag（只提示，不自动更新）
+    /// - 固定 commit：默认分支有新提交（只提示）
+    /// - 本地目录：目录内容哈希变化
+    fn check_source_updates(skills: &[InstalledSkill]) -> Vec<SkillUpdateInfo> {
+        let mut remote_refs: HashMap<String, Option<skill_git::RemoteRefs>> = HashMap::new();
+        let mut updates = Vec::new();
+
+        for skill in skills {
+            let Some(source) = &skill.source else {
+                continue;
+            };
+
+            if source.kind == SkillSourceKind::Local {
+                let remote_hash =
+                    Self::source_skill_dir(Path::new(&source.url), source.path.as_deref())
+                        .and_then(|dir| Self::compute_dir_hash(&dir));
+                match remote_hash {
+                    Ok(remote_hash) if skill.content_hash.as_deref() != Some(&remote_hash) => {
+                        updates.push(SkillUpdateInfo {
+                            id: skill.id.clone(),
+                            name: skill.name.clone(),
+                            current_hash: skill.content_hash.clone(),
+                            remote_hash,
+                            pinned_ref: None,
+                            available_ref: None,
+                        });
+                    }
+                    Ok(_) => {}
+                    Err(e) => log::warn!("检查本地来源 Skill {} 失败: {e}", skill.id),
+                }
+                continue;
+            }
+
+            let refs = remote_refs.entry(source.url.clone()).or_insert_with(|| {
+                skill_git::ls_remote(&source.url)
+                    .map_err(|e| log::warn!("检查更新时读取 {} 失败: {e}", source.url))
+                    .ok()
+            });
+            let Some(refs) = refs else {
+                continue;
+            };
+            let current = source.resolved_commit.clone().unwrap_or_default();
+            let available = skill_git::available_for(refs, source.git_ref.as_deref(), &current);
+            match available {
+                Ok(Some((available_ref, commit))) => updates.push(SkillUpdateInfo {
+                    id: skill.id.clone(),
+                    name: skill.name.clone(),
+                    current_hash: source.resolved_commit.clone(),
+                    remote_hash: commit,
+                    pinned_ref: source
+                        .pinned
+                        .then(|| source.git_ref.clone().unwrap_or_else(|| current.clone())),
+                    available_ref: Some(available_ref),
+                }),
+                Ok(None) => {}
+                Err(e) => log::warn!("检查 git 来源 Skill {} 失败: {e}", skill.id),
+            }
+        }
+
+        updates
+    }
+
     /// 为缺少 content_hash 的已安装 Skill 补算哈希
     pub fn backfill_content_hashes(db: &Arc<Database>) -> Result<usize> {
         let skills = db.get_all_installed_skills()?;
@@ -1534,6 +2033,7 @@ impl SkillService {
                 installed_at: chrono::Utc::now().timestamp(),
                 content_hash,
                 updated_at: 0,
+                source: None,
             };
 
             // 保存到数据库
@@ -1584,7 +2084,14 @@ impl SkillService {
     /// - Symlink: 仅使用 symlink
     /// - Copy: 仅使用文件复制
     pub fn sync_to_app_dir(directory: &str, app: &AppType) -> Result<()> {
-        if matches!(app, AppType::ClaudeDesktop) {
+        if matches!(
+            app,
+            AppType::ClaudeDesktop
+                | AppType::Qwen
+                | AppType::Goose
+                | AppType::Crush
+                | AppType::Aider
+        ) {
             return Ok(());
         }
 
@@ -1653,7 +2160,7 @@ impl SkillService {
     }
 
     /// 删除路径（支持 symlink 和真实目录）
-    fn remove_path(path: &Path) -> Result<()> {
+    pub(crate) fn remove_path(path: &Path) -> Result<()> {
         if Self::is_symlink(path) {
             // 符号链接：仅删除链接本身，不影响源文件
             #[cfg(unix)]
@@ -1686,7 +2193,11 @@ impl SkillService {
         Ok(())
     }
 
-    fn replace_dest_with_copy(source: &Path, dest: &Path, directory: &str) -> Result<()> {
+    pub(crate) fn replace_dest_with_copy(
+        source: &Path,
+        dest: &Path,
+        directory: &str,
+    ) -> Result<()> {
         Self::validate_sync_source_dir(source, directory)?;
 
         let parent = dest
@@ -1756,7 +2267,14 @@ impl SkillService {
 
     /// 从应用目录删除 Skill（支持 symlink 和真实目录）
     pub fn remove_from_app(directory: &str, app: &AppType) -> Result<()> {
-        if matches!(app, AppType::ClaudeDesktop) {
+        if matches!(
+            app,
+            AppType::ClaudeDesktop
+                | AppType::Qwen
+                | AppType::Goose
+                | AppType::Crush
+                | AppType::Aider
+        ) {
             return Ok(());
         }
 
@@ -1773,7 +2291,14 @@ impl SkillService {
 
     /// 同步所有已启用的 Skills 到指定应用
     pub fn sync_to_app(db: &Arc<Database>, app: &AppType) -> Result<()> {
-        if matches!(app, AppType::ClaudeDesktop) {
+        if matches!(
+            app,
+            AppType::ClaudeDesktop
+                | AppType::Qwen
+                | AppType::Goose
+                | AppType::Crush
+                | AppType::Aider
+        ) {
             return Ok(());
         }
 
@@ -2539,6 +3064,7 @@ impl SkillService {
         db: &Arc<Database>,
         zip_path: &Path,
         current_app: &AppType,
+        acknowledge_warnings: bool,
     ) -> Result<Vec<InstalledSkill>> {
         // 解压到临时目录
         let temp_dir = Self::extract_local_zip(zip_path)?;
@@ -2555,6 +3081,24 @@ impl SkillService {
             )));
         }
 
+        // 安装前统一检查所有 Skill，确认一次即可
+        let reports: Vec<_> = skill_dirs
+            .iter()
+            .map(|dir| {
+                let name = dir
+                    .strip_prefix(&temp_dir)
+                    .ok()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .filter(|p| !p.is_empty())
+                    .unwrap_or_else(|| "SKILL.md".to_string());
+                skill_lint::lint_skill_dir(dir, &name)
+            })
+            .collect();
+        if let Err(e) = skill_lint::ensure_installable(&reports, acknowledge_warnings) {
+            let _ = fs::remove_dir_all(&temp_dir);
+            return Err(e);
+        }
+
         let ssot_dir = Self::get_ssot_dir()?;
         let mut installed = Vec::new();
         let existing_skills = db.get_all_installed_skills()?;
@@ -2655,6 +3199,7 @@ impl SkillService {
                 installed_at: chrono::Utc::now().timestamp(),
                 content_hash,
                 updated_at: 0,
+                source: None,
             };
 
             // 保存到数据库
@@ -3042,6 +3587,7 @@ pub fn migrate_skills_to_ssot(db: &Arc<Database>) -> Result<usize> {
             installed_at: chrono::Utc::now().timestamp(),
             content_hash,
             updated_at: 0,
+            source: None,
         };
 
         db.save_skill(&skill)?;
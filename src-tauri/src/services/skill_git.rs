@@ -0,0 +1,492 @@
+//! Skill 的 git 来源
+//!
+//! 通过系统 git 命令从任意 git 仓库（GitLab、Gitea、自建服务、本地裸仓库）获取 Skill：
+//! - 未指定 ref 或 ref 为分支时跟随分支
+//! - ref 为 tag 或 commit 时固定版本，更新检测只报告可用的新版本，不会自动移动
+
+use anyhow::{anyhow, Result};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::format_skill_error;
+
+/// ref 的解析结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedRef {
+    /// 跟随分支
+    Branch { name: String, commit: String },
+    /// 固定在 tag
+    Tag { name: String, commit: String },
+    /// 固定在 commit（可能是缩写，检出后以完整 SHA 为准）
+    Commit { sha: String },
+}
+
+impl ResolvedRef {
+    pub fn is_pinned(&self) -> bool {
+        !matches!(self, ResolvedRef::Branch { .. })
+    }
+
+    /// 用于展示与记录的 ref 名称
+    pub fn name(&self) -> &str {
+        match self {
+            ResolvedRef::Branch { name, .. } | ResolvedRef::Tag { name, .. } => name,
+            ResolvedRef::Commit { sha } => sha,
+        }
+    }
+
+    fn checkout_target(&self) -> String {
+        match self {
+            ResolvedRef::Branch { commit, .. } | ResolvedRef::Tag { commit, .. } => commit.clone(),
+            ResolvedRef::Commit { sha } => sha.clone(),
+        }
+    }
+}
+
+/// 远程 ref 列表
+#[derive(Debug, Default)]
+pub struct RemoteRefs {
+    /// 默认分支（HEAD 指向的分支）
+    pub default_branch: Option<String>,
+    pub head: Option<String>,
+    pub branches: Vec<(String, String)>,
+    /// tag -> commit（附注 tag 已解引用到 commit）
+    pub tags: Vec<(String, String)>,
+}
+
+impl RemoteRefs {
+    pub fn branch(&self, name: &str) -> Option<&str> {
+        self.branches
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, c)| c.as_str())
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, c)| c.as_str())
+    }
+}
+
+/// 执行 git 命令，返回标准输出
+fn run_git(args: &[&str], cwd: Option<&Path>) -> Result<String> {
+    // 错误信息中展示子命令名（跳过 `-c key=value` 之类的全局参数）
+    let command = args
+        .iter()
+        .copied()
+        .find(|a| !a.starts_with('-') && !a.contains('='))
+        .unwrap_or("git");
+    let mut cmd = Command::new("git");
+    cmd.args(args)
+        // 禁止交互式认证提示，避免后台任务卡住
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_ASKPASS", "")
+        .env("LC_ALL", "C");
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+
+    let output = cmd.output().map_err(|e| {
+        anyhow!(format_skill_error(
+            "GIT_FAILED",
+            &[("command", command), ("message", &e.to_string())],
+            Some("checkGitInstalled"),
+        ))
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow!(format_skill_error(
+            "GIT_FAILED",
+            &[("command", command), ("message", &stderr)],
+            Some("checkRepoUrl"),
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 拒绝以 `-` 开头的地址，避免被 git 当作选项（如 `--upload-pack=...`）解析
+fn ensure_safe_url(url: &str) -> Result<()> {
+    if url.starts_with('-') {
+        return Err(anyhow!(format_skill_error(
+            "GIT_FAILED",
+            &[("command", "git"), ("message", "invalid repository url")],
+            Some("checkRepoUrl"),
+        )));
+    }
+    Ok(())
+}
+
+/// 列出远程分支、tag 与默认分支
+pub fn ls_remote(url: &str) -> Result<RemoteRefs> {
+    ensure_safe_url(url)?;
+    let output = run_git(&["ls-remote", "--symref", "--", url], None)?;
+    let mut refs = RemoteRefs::default();
+    let mut peeled: Vec<(String, String)> = Vec::new();
+
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("ref: ") {
+            // "ref: refs/heads/main\tHEAD"
+            if let Some((target, "HEAD")) = rest.split_once('\t') {
+                refs.default_branch = target.strip_prefix("refs/heads/").map(str::to_string);
+            }
+            continue;
+        }
+        let Some((sha, name)) = line.split_once('\t') else {
+            continue;
+        };
+        let sha = sha.trim().to_string();
+        if name == "HEAD" {
+            refs.head = Some(sha);
+        } else if let Some(branch) = name.strip_prefix("refs/heads/") {
+            refs.branches.push((branch.to_string(), sha));
+        } else if let Some(tag) = name.strip_prefix("refs/tags/") {
+            match tag.strip_suffix("^{}") {
+                Some(tag) => peeled.push((tag.to_string(), sha)),
+                None => refs.tags.push((tag.to_string(), sha)),
+            }
+        }
+    }
+
+    // 附注 tag 使用解引用后的 commit
+    for (tag, commit) in peeled {
+        if let Some(entry) = refs.tags.iter_mut().find(|(n, _)| *n == tag) {
+            entry.1 = commit;
+        }
+    }
+    Ok(refs)
+}
+
+fn looks_like_commit(value: &str) -> bool {
+    (7..=40).contains(&value.len()) && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// 解析 ref：分支优先，其次 tag，最后按 commit SHA 处理
+pub fn resolve_ref(refs: &RemoteRefs, git_ref: Option<&str>) -> Result<ResolvedRef> {
+    let git_ref = git_ref.map(str::trim).filter(|r| !r.is_empty());
+    let Some(git_ref) = git_ref else {
+        let name = refs
+            .default_branch
+            .clone()
+            .ok_or_else(|| anyhow!("无法确定远程仓库的默认分支，请指定 ref"))?;
+        let commit = refs
+            .branch(&name)
+            .map(str::to_string)
+            .or_else(|| refs.head.clone())
+            .ok_or_else(|| anyhow!("远程仓库为空"))?;
+        return Ok(ResolvedRef::Branch { name, commit });
+    };
+
+    if let Some(commit) = refs.branch(git_ref) {
+        return Ok(ResolvedRef::Branch {
+            name: git_ref.to_string(),
+            commit: commit.to_string(),
+        });
+    }
+    if let Some(commit) = refs.tag(git_ref) {
+        return Ok(ResolvedRef::Tag {
+            name: git_ref.to_string(),
+            commit: commit.to_string(),
+        });
+    }
+    if looks_like_commit(git_ref) {
+        return Ok(ResolvedRef::Commit {
+            sha: git_ref.to_lowercase(),
+        });
+    }
+    Err(anyhow!(format_skill_error(
+        "GIT_REF_NOT_FOUND",
+        &[("ref", git_ref)],
+        Some("checkRepoUrl"),
+    )))
+}
+
+/// 克隆仓库到临时目录并检出指定 ref，返回 (目录, 完整 commit)
+pub fn checkout(url: &str, resolved: &ResolvedRef) -> Result<(PathBuf, String)> {
+    ensure_safe_url(url)?;
+    let temp_dir = tempfile::tempdir()?;
+    let temp_path = temp_dir.path().to_path_buf();
+    let _ = temp_dir.keep();
+
+    let result = (|| {
+        let path = temp_path.to_string_lossy().to_string();
+        run_git(
+            &["clone", "--quiet", "--no-checkout", "--", url, &path],
+            None,
+        )?;
+        run_git(
+            &[
+                "checkout",
+                "--quiet",
+                "--detach",
+                &resolved.checkout_target(),
+            ],
+            Some(&temp_path),
+        )?;
+        let commit = run_git(&["rev-parse", "HEAD"], Some(&temp_path))?
+            .trim()
+            .to_string();
+        Ok(commit)
+    })();
+
+    match result {
+        Ok(commit) => Ok((temp_path, commit)),
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+/// 把 tag 解析为数字版本（可带 `v` 前缀），无法解析时返回 None
+fn parse_version(tag: &str) -> Option<Vec<u64>> {
+    let core = tag.trim_start_matches(['v', 'V']);
+    let core = core.split(['-', '+']).next()?;
+    core.split('.')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()
+        .filter(|parts| !parts.is_empty())
+}
+
+fn compare_versions(a: &[u64], b: &[u64]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let ord = a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+/// 可用的新版本：(ref 名称, commit)
+///
+/// - 跟随分支：分支最新 commit 与当前不同
+/// - 固定 tag：存在版本号更高的 tag
+/// - 固定 commit：默认分支最新 commit 与当前不同
+pub fn available_update(
+    refs: &RemoteRefs,
+    branch: Option<&str>,
+    pinned_tag: Option<&str>,
+    current_commit: &str,
+) -> Option<(String, String)> {
+    if let Some(tag) = pinned_tag {
+        let current = parse_version(tag)?;
+        return refs
+            .tags
+            .iter()
+            .filter_map(|(name, commit)| parse_version(name).map(|v| (v, name, commit)))
+            .filter(|(v, _, _)| compare_versions(v, &current) == Ordering::Greater)
+            .max_by(|a, b| compare_versions(&a.0, &b.0))
+            .map(|(_, name, commit)| (name.clone(), commit.clone()));
+    }
+
+    let branch = branch
+        .map(str::to_string)
+        .or_else(|| refs.default_branch.clone())?;
+    let commit = refs.branch(&branch)?;
+    (!commit.starts_with(current_commit)).then(|| (branch, commit.to_string()))
+}
+
+/// 按安装时的 ref 检查可用的新版本
+pub fn available_for(
+    refs: &RemoteRefs,
+    git_ref: Option<&str>,
+    current_commit: &str,
+) -> Result<Option<(String, String)>> {
+    Ok(match resolve_ref(refs, git_ref)? {
+        ResolvedRef::Branch { name, .. } => {
+            available_update(refs, Some(&name), None, current_commit)
+        }
+        ResolvedRef::Tag { name, .. } => available_update(refs, None, Some(&name), current_commit),
+        ResolvedRef::Commit { .. } => available_update(refs, None, None, current_commit),
+    })
+}
+
+/// 判断来源是否为 git 仓库（URL、scp 风格地址，或本地 git 仓库目录）
+pub fn is_git_source(url: &str) -> bool {
+    if url.starts_with('-') {
+        return false;
+    }
+    if url.contains("://") {
+        return true;
+    }
+    let path = Path::new(url);
+    if path.is_dir() {
+        // 本地裸仓库或带 .git 的工作区
+        return path.join(".git").exists()
+            || (path.join("HEAD").is_file() && path.join("objects").is_dir());
+    }
+    // git@host:group/repo.git
+    url.split_once(':')
+        .is_some_and(|(host, rest)| host.contains('@') && !rest.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let mut full = vec![
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "-c",
+            "commit.gpgsign=false",
+            "-c",
+            "tag.gpgsign=false",
+        ];
+        full.extend_from_slice(args);
+        run_git(&full, Some(dir)).expect("git command");
+    }
+
+    /// 创建带两个版本的本地裸仓库：v1.0.0 与 v1.2.0（附注 tag），main 之后还有一次提交
+    fn bare_repo(root: &Path) -> (PathBuf, String, String) {
+        let work = root.join("work");
+        fs::create_dir_all(work.join("skills/demo")).unwrap();
+        git(&work, &["init", "--quiet", "--initial-branch=main"]);
+
+        fs::write(
+            work.join("skills/demo/SKILL.md"),
+            "---\nname: demo\n---\nv1\n",
+        )
+        .unwrap();
+        git(&work, &["add", "."]);
+        git(&work, &["commit", "--quiet", "-m", "v1"]);
+        git(&work, &["tag", "v1.0.0"]);
+        let v1 = run_git(&["rev-parse", "HEAD"], Some(&work))
+            .unwrap()
+            .trim()
+            .to_string();
+
+        fs::write(
+            work.join("skills/demo/SKILL.md"),
+            "---\nname: demo\n---\nv2\n",
+        )
+        .unwrap();
+        git(&work, &["commit", "--quiet", "-am", "v2"]);
+        git(&work, &["tag", "-a", "v1.2.0", "-m", "release"]);
+        let v2 = run_git(&["rev-parse", "HEAD"], Some(&work))
+            .unwrap()
+            .trim()
+            .to_string();
+
+        fs::write(
+            work.join("skills/demo/SKILL.md"),
+            "---\nname: demo\n---\nwip\n",
+        )
+        .unwrap();
+        git(&work, &["commit", "--quiet", "-am", "wip"]);
+
+        let bare = root.join("demo.git");
+        run_git(
+            &[
+                "clone",
+                "--quiet",
+                "--bare",
+                &work.to_string_lossy(),
+                &bare.to_string_lossy(),
+            ],
+            None,
+        )
+        .unwrap();
+        (bare, v1, v2)
+    }
+
+    #[test]
+    fn resolves_branches_tags_and_commits_from_bare_repo() {
+        let root = tempfile::tempdir().unwrap();
+        let (bare, v1, v2) = bare_repo(root.path());
+        let url = bare.to_string_lossy().to_string();
+        assert!(is_git_source(&url));
+
+        let refs = ls_remote(&url).unwrap();
+        assert_eq!(refs.default_branch.as_deref(), Some("main"));
+        // 附注 tag 解引用到 commit
+        assert_eq!(refs.tag("v1.2.0"), Some(v2.as_str()));
+
+        let tracking = resolve_ref(&refs, None).unwrap();
+        assert!(!tracking.is_pinned());
+        assert_eq!(tracking.name(), "main");
+
+        let tag = resolve_ref(&refs, Some("v1.0.0")).unwrap();
+        assert!(tag.is_pinned());
+        let (dir, commit) = checkout(&url, &tag).unwrap();
+        assert_eq!(commit, v1);
+        let content = fs::read_to_string(dir.join("skills/demo/SKILL.md")).unwrap();
+        assert!(content.contains("v1"));
+        let _ = fs::remove_dir_all(&dir);
+
+        let short = resolve_ref(&refs, Some(&v2[..10])).unwrap();
+        assert!(short.is_pinned());
+        let (dir, commit) = checkout(&url, &short).unwrap();
+        assert_eq!(commit, v2);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(resolve_ref(&refs, Some("no-such-ref")).is_err());
+    }
+
+    #[test]
+    fn pinned_refs_report_available_versions_without_moving() {
+        let root = tempfile::tempdir().unwrap();
+        let (bare, v1, v2) = bare_repo(root.path());
+        let refs = ls_remote(&bare.to_string_lossy()).unwrap();
+        let head = refs.branch("main").unwrap().to_string();
+
+        // 固定在 v1.0.0：报告 v1.2.0 可用
+        assert_eq!(
+            available_update(&refs, None, Some("v1.0.0"), &v1),
+            Some(("v1.2.0".to_string(), v2.clone()))
+        );
+        // 已是最新 tag
+        assert_eq!(available_update(&refs, None, Some("v1.2.0"), &v2), None);
+        // 固定 commit：报告默认分支最新 commit
+        assert_eq!(
+            available_update(&refs, None, None, &v1[..12]),
+            Some(("main".to_string(), head.clone()))
+        );
+        // 跟随分支且已是最新
+        assert_eq!(available_update(&refs, Some("main"), None, &head), None);
+
+        // 按安装时的 ref 检查：tag 固定报告新 tag，分支跟随报告新提交
+        assert_eq!(
+            available_for(&refs, Some("v1.0.0"), &v1).unwrap(),
+            Some(("v1.2.0".to_string(), v2.clone()))
+        );
+        assert_eq!(
+            available_for(&refs, None, &v1).unwrap(),
+            Some(("main".to_string(), head))
+        );
+    }
+
+    #[test]
+    fn version_tags_compare_numerically() {
+        assert_eq!(
+            compare_versions(
+                &parse_version("v1.10.0").unwrap(),
+                &parse_version("1.9").unwrap()
+            ),
+            Ordering::Greater
+        );
+        assert_eq!(parse_version("release-candidate"), None);
+        assert_eq!(parse_version("v2.0.0-rc.1"), Some(vec![2, 0, 0]));
+    }
+
+    #[test]
+    fn leading_dash_urls_are_not_passed_to_git_as_options() {
+        let temp = tempfile::tempdir().unwrap();
+        let marker = temp.path().join("pwned");
+        let url = format!("--upload-pack=touch {};a@b:c", marker.display());
+
+        assert!(!is_git_source(&url));
+        assert!(ls_remote(&url).is_err());
+        let resolved = ResolvedRef::Branch {
+            name: "main".to_string(),
+            commit: String::new(),
+        };
+        assert!(checkout(&url, &resolved).is_err());
+        assert!(!marker.exists());
+    }
+}
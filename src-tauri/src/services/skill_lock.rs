@@ -0,0 +1,593 @@
+//! Skills 锁文件（skills.lock）
+//!
+//! 把已安装 Skill 的来源、解析后的 ref、安装目录、内容哈希与各应用启用状态导出为 TOML，
+//! 团队成员或 CI 导入后即可复现同一套 Skill；也可以拿锁文件检查本地 Skill 是否发生漂移
+//! （目录内容哈希或来源 commit 与锁文件不一致）。
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::app_config::{AppType, InstalledSkill, SkillApps, SkillSourceKind};
+use crate::config::write_text_file;
+use crate::database::Database;
+use crate::services::skill::{DiscoverableSkill, SkillService, SkillSourceRequest};
+
+pub const SKILLS_LOCK_VERSION: u32 = 1;
+
+/// 锁文件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillsLock {
+    pub version: u32,
+    #[serde(default, rename = "skill")]
+    pub skills: Vec<SkillLockEntry>,
+}
+
+/// 锁文件中的单个 Skill
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillLockEntry {
+    pub id: String,
+    pub name: String,
+    /// SSOT 中的安装目录名
+    pub directory: String,
+    /// 目录内容哈希（与 `SkillService::compute_dir_hash` 一致）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    pub source: LockedSource,
+    #[serde(default)]
+    pub apps: SkillApps,
+}
+
+/// Skill 来源
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum LockedSource {
+    /// GitHub 仓库（按分支下载 ZIP，ref 即分支名）
+    Github {
+        owner: String,
+        repo: String,
+        #[serde(rename = "ref")]
+        branch: String,
+        /// 仓库内的 Skill 目录
+        path: String,
+    },
+    /// 任意 git 仓库
+    Git {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        /// 安装时指定的分支 / tag / commit
+        #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+        /// 解析后的 commit，导入时按此 commit 检出
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        commit: Option<String>,
+        #[serde(default)]
+        pinned: bool,
+    },
+    /// 本地目录；`url` 为空表示从 ZIP 或应用目录导入，无法在其他机器复现
+    Local {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+}
+
+/// 漂移类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SkillLockDriftKind {
+    /// 锁文件中有，本地未安装
+    Missing,
+    /// 本地目录内容与锁文件哈希不一致
+    Modified,
+    /// 来源 commit 与锁文件不一致
+    SourceChanged,
+    /// 本地已安装，但不在锁文件中
+    Untracked,
+}
+
+/// 漂移条目
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillLockDrift {
+    pub directory: String,
+    pub kind: SkillLockDriftKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<String>,
+}
+
+/// 导入时跳过的 Skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillLockSkipped {
+    pub directory: String,
+    pub reason: String,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillLockImportResult {
+    pub installed: Vec<String>,
+    pub already_installed: Vec<String>,
+    pub skipped: Vec<SkillLockSkipped>,
+    /// 导入完成后仍与锁文件不一致的条目
+    pub drift: Vec<SkillLockDrift>,
+}
+
+impl LockedSource {
+    fn from_skill(skill: &InstalledSkill) -> Self {
+        if let Some(source) = &skill.source {
+            return match source.kind {
+                SkillSourceKind::Git => LockedSource::Git {
+                    url: source.url.clone(),
+                    path: source.path.clone(),
+                    git_ref: source.git_ref.clone(),
+                    commit: source.resolved_commit.clone(),
+                    pinned: source.pinned,
+                },
+                SkillSourceKind::Local => LockedSource::Local {
+                    url: Some(source.url.clone()),
+                    path: source.path.clone(),
+                },
+            };
+        }
+
+        match (&skill.repo_owner, &skill.repo_name) {
+            (Some(owner), Some(repo)) => {
+                // 仓库内路径取自 README 地址（…/blob/<branch>/<path>/SKILL.md）
+                let path = skill
+                    .readme_url
+                    .as_deref()
+                    .and_then(SkillService::extract_doc_path_from_url)
+                    .and_then(|doc| {
+                        doc.strip_suffix("/SKILL.md")
+                            .map(str::to_string)
+                            .or_else(|| (doc == "SKILL.md").then(String::new))
+                    })
+                    .unwrap_or_else(|| skill.directory.clone());
+                LockedSource::Github {
+                    owner: owner.clone(),
+                    repo: repo.clone(),
+                    branch: skill
+                        .repo_branch
+                        .clone()
+                        .unwrap_or_else(|| "main".to_string()),
+                    path,
+                }
+            }
+            _ => LockedSource::Local {
+                url: None,
+                path: None,
+            },
+        }
+    }
+
+    /// 来源记录的 commit（仅 git 来源）
+    fn commit(&self) -> Option<&str> {
+        match self {
+            LockedSource::Git { commit, .. } => commit.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+pub struct SkillLockService;
+
+impl SkillLockService {
+    /// 由已安装的 Skill 生成锁文件（按目录名排序，便于审阅差异）
+    pub fn build(db: &Arc<Database>) -> Result<SkillsLock> {
+        let ssot_dir = SkillService::get_ssot_dir()?;
+        let mut skills: Vec<SkillLockEntry> = db
+            .get_all_installed_skills()?
+            .into_values()
+            .map(|skill| {
+                let content_hash = SkillService::compute_dir_hash(&ssot_dir.join(&skill.directory))
+                    .ok()
+                    .or(skill.content_hash.clone());
+                SkillLockEntry {
+                    source: LockedSource::from_skill(&skill),
+                    id: skill.id,
+                    name: skill.name,
+                    directory: skill.directory,
+                    content_hash,
+                    apps: skill.apps,
+                }
+            })
+            .collect();
+        skills.sort_by(|a, b| a.directory.cmp(&b.directory));
+
+        Ok(SkillsLock {
+            version: SKILLS_LOCK_VERSION,
+            skills,
+        })
+    }
+
+    pub fn to_toml(lock: &SkillsLock) -> Result<String> {
+        let body = toml::to_string_pretty(lock).context("序列化 skills.lock 失败")?;
+        Ok(format!(
+            "# 由 CC Switch 生成，请勿手动修改。\n# 导入此文件可复现同一套 Skill。\n\n{body}"
+        ))
+    }
+
+    pub fn parse(content: &str) -> Result<SkillsLock> {
+        let lock: SkillsLock = toml::from_str(content).context("解析 skills.lock 失败")?;
+        if lock.version > SKILLS_LOCK_VERSION {
+            return Err(anyhow!(
+                "skills.lock 版本 {} 高于当前支持的版本 {SKILLS_LOCK_VERSION}，请升级 CC Switch",
+                lock.version
+            ));
+        }
+        Ok(lock)
+    }
+
+    pub fn export(db: &Arc<Database>, path: &Path) -> Result<SkillsLock> {
+        let lock = Self::build(db)?;
+        write_text_file(path, &Self::to_toml(&lock)?)?;
+        log::info!(
+            "已导出 {} 个 Skill 到 {}",
+            lock.skills.len(),
+            path.display()
+        );
+        Ok(lock)
+    }
+
+    pub fn read(path: &Path) -> Result<SkillsLock> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("读取 {} 失败", path.display()))?;
+        Self::parse(&content)
+    }
+
+    /// 检查本地 Skill 与锁文件的差异
+    pub fn check(db: &Arc<Database>, lock: &SkillsLock) -> Result<Vec<SkillLockDrift>> {
+        let ssot_dir = SkillService::get_ssot_dir()?;
+        let installed: Vec<InstalledSkill> = db.get_all_installed_skills()?.into_values().collect();
+        Ok(Self::diff(lock, &installed, |skill| {
+            SkillService::compute_dir_hash(&ssot_dir.join(&skill.directory)).ok()
+        }))
+    }
+
+    /// 比较锁文件与已安装的 Skill；`actual_hash` 计算本地目录的实际哈希
+    fn diff(
+        lock: &SkillsLock,
+        installed: &[InstalledSkill],
+        actual_hash: impl Fn(&InstalledSkill) -> Option<String>,
+    ) -> Vec<SkillLockDrift> {
+        let find = |directory: &str| {
+            installed
+                .iter()
+                .find(|s| s.directory.eq_ignore_ascii_case(directory))
+        };
+        let mut drift = Vec::new();
+
+        for entry in &lock.skills {
+            let Some(skill) = find(&entry.directory) else {
+                drift.push(SkillLockDrift {
+                    directory: entry.directory.clone(),
+                    kind: SkillLockDriftKind::Missing,
+                    expected: entry.content_hash.clone(),
+                    actual: None,
+                });
+                continue;
+            };
+
+            if let Some(expected) = entry.source.commit() {
+                let actual = skill
+                    .source
+                    .as_ref()
+                    .and_then(|s| s.resolved_commit.as_deref());
+                if actual != Some(expected) {
+                    drift.push(SkillLockDrift {
+                        directory: entry.directory.clone(),
+                        kind: SkillLockDriftKind::SourceChanged,
+                        expected: Some(expected.to_string()),
+                        actual: actual.map(str::to_string),
+                    });
+                }
+            }
+
+            if let Some(expected) = &entry.content_hash {
+                let actual = actual_hash(skill);
+                if actual.as_ref() != Some(expected) {
+                    drift.push(SkillLockDrift {
+                        directory: entry.directory.clone(),
+                        kind: SkillLockDriftKind::Modified,
+                        expected: Some(expected.clone()),
+                        actual,
+                    });
+                }
+            }
+        }
+
+        for skill in installed {
+            let tracked = lock
+                .skills
+                .iter()
+                .any(|e| e.directory.eq_ignore_ascii_case(&skill.directory));
+            if !tracked {
+                drift.push(SkillLockDrift {
+                    directory: skill.directory.clone(),
+                    kind: SkillLockDriftKind::Untracked,
+                    expected: None,
+                    actual: skill.content_hash.clone(),
+                });
+            }
+        }
+
+        drift
+    }
+
+    /// 按锁文件安装缺失的 Skill，并把各应用启用状态设为锁文件中的值
+    ///
+    /// git 来源按锁定的 commit 检出，安装后恢复锁文件中记录的 ref 与固定状态；
+    /// 已安装的同名 Skill 不会被覆盖，内容不一致时在 `drift` 中报告。
+    /// 安装前检查的错误仍会阻止安装，警告视为已确认（锁文件中的 Skill 已由团队审阅）。
+    pub async fn import(
+        db: &Arc<Database>,
+        service: &SkillService,
+        lock: &SkillsLock,
+        current_app: &AppType,
+    ) -> Result<SkillLockImportResult> {
+        let mut result = SkillLockImportResult::default();
+
+        for entry in &lock.skills {
+            let existing = db
+                .get_all_installed_skills()?
+                .into_values()
+                .find(|s| s.directory.eq_ignore_ascii_case(&entry.directory));
+            let skill = match existing {
+                Some(skill) => {
+                    result.already_installed.push(entry.directory.clone());
+                    skill
+                }
+                None => match Self::install_entry(db, service, entry, current_app).await {
+                    Ok(skill) => {
+                        result.installed.push(entry.directory.clone());
+                        skill
+                    }
+                    Err(e) => {
+                        log::warn!("按 skills.lock 安装 {} 失败: {e}", entry.directory);
+                        result.skipped.push(SkillLockSkipped {
+                            directory: entry.directory.clone(),
+                            reason: e.to_string(),
+                        });
+                        continue;
+                    }
+                },
+            };
+
+            Self::apply_apps(db, &skill, &entry.apps)?;
+        }
+
+        result.drift = Self::check(db, lock)?
+            .into_iter()
+            .filter(|d| d.kind != SkillLockDriftKind::Untracked)
+            .collect();
+        Ok(result)
+    }
+
+    async fn install_entry(
+        db: &Arc<Database>,
+        service: &SkillService,
+        entry: &SkillLockEntry,
+        current_app: &AppType,
+    ) -> Result<InstalledSkill> {
+        match &entry.source {
+            LockedSource::Github {
+                owner,
+                repo,
+                branch,
+                path,
+            } => {
+                let directory = if path.is_empty() {
+                    entry.directory.clone()
+                } else {
+                    path.clone()
+                };
+                let discoverable = DiscoverableSkill {
+                    key: format!("{owner}/{repo}:{directory}"),
+                    name: entry.name.clone(),
+                    description: String::new(),
+                    directory,
+                    readme_url: None,
+                    repo_owner: owner.clone(),
+                    repo_name: repo.clone(),
+                    repo_branch: branch.clone(),
+                };
+                service.install(db, &discoverable, current_app, true).await
+            }
+            LockedSource::Git {
+                url,
+                path,
+                git_ref,
+                commit,
+                pinned,
+            } => {
+                let request = SkillSourceRequest {
+                    url: url.clone(),
+                    git_ref: commit.clone().or(git_ref.clone()),
+                    path: path.clone(),
+                    acknowledge_warnings: true,
+                };
+                let mut skill = Self::install_single(db, request, entry, current_app).await?;
+                // 按 commit 检出后恢复原来的 ref，跟随分支的 Skill 之后仍可正常更新
+                if let Some(source) = skill.source.as_mut() {
+                    source.git_ref = git_ref.clone();
+                    source.pinned = *pinned;
+                }
+                db.save_skill(&skill)?;
+                Ok(skill)
+            }
+            LockedSource::Local {
+                url: Some(url),
+                path,
+            } => {
+                let request = SkillSourceRequest {
+                    url: url.clone(),
+                    git_ref: None,
+                    path: path.clone(),
+                    acknowledge_warnings: true,
+                };
+                Self::install_single(db, request, entry, current_app).await
+            }
+            LockedSource::Local { url: None, .. } => Err(anyhow!(
+                "{} 从 ZIP 或应用目录导入，没有可复现的来源",
+                entry.directory
+            )),
+        }
+    }
+
+    async fn install_single(
+        db: &Arc<Database>,
+        request: SkillSourceRequest,
+        entry: &SkillLockEntry,
+        current_app: &AppType,
+    ) -> Result<InstalledSkill> {
+        SkillService::install_from_source(db, request, current_app)
+            .await?
+            .into_iter()
+            .find(|s| s.directory.eq_ignore_ascii_case(&entry.directory))
+            .ok_or_else(|| anyhow!("来源中没有找到 Skill 目录 {}", entry.directory))
+    }
+
+    fn apply_apps(db: &Arc<Database>, skill: &InstalledSkill, apps: &SkillApps) -> Result<()> {
+        for app in AppType::all() {
+            let wanted = apps.is_enabled_for(&app);
+            if skill.apps.is_enabled_for(&app) != wanted {
+                SkillService::toggle_app(db, &skill.id, &app, wanted)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::SkillSource;
+
+    fn installed(directory: &str, hash: &str) -> InstalledSkill {
+        InstalledSkill {
+            id: format!("acme/skills:{directory}"),
+            name: directory.to_string(),
+            description: None,
+            directory: directory.to_string(),
+            repo_owner: Some("acme".to_string()),
+            repo_name: Some("skills".to_string()),
+            repo_branch: Some("main".to_string()),
+            readme_url: Some(format!(
+                "https://github.com/acme/skills/blob/main/tools/{directory}/SKILL.md"
+            )),
+            apps: SkillApps {
+                claude: true,
+                ..Default::default()
+            },
+            installed_at: 0,
+            content_hash: Some(hash.to_string()),
+            updated_at: 0,
+            source: None,
+        }
+    }
+
+    fn entry(skill: &InstalledSkill) -> SkillLockEntry {
+        SkillLockEntry {
+            id: skill.id.clone(),
+            name: skill.name.clone(),
+            directory: skill.directory.clone(),
+            content_hash: skill.content_hash.clone(),
+            source: LockedSource::from_skill(skill),
+            apps: skill.apps.clone(),
+        }
+    }
+
+    #[test]
+    fn lock_roundtrips_through_toml() {
+        let github = installed("review", "h1");
+        let mut git = installed("lint", "h2");
+        git.source = Some(SkillSource {
+            kind: SkillSourceKind::Git,
+            url: "https://gitlab.example.com/team/skills.git".to_string(),
+            path: Some("skills/lint".to_string()),
+            git_ref: Some("v1.2.0".to_string()),
+            pinned: true,
+            resolved_commit: Some("0123456789abcdef".to_string()),
+        });
+        let lock = SkillsLock {
+            version: SKILLS_LOCK_VERSION,
+            skills: vec![entry(&github), entry(&git)],
+        };
+
+        assert_eq!(
+            lock.skills[0].source,
+            LockedSource::Github {
+                owner: "acme".to_string(),
+                repo: "skills".to_string(),
+                branch: "main".to_string(),
+                path: "tools/review".to_string(),
+            }
+        );
+
+        let text = SkillLockService::to_toml(&lock).expect("serialize");
+        assert!(text.contains("[[skill]]"));
+        assert!(text.contains("kind = \"git\""));
+        assert_eq!(SkillLockService::parse(&text).expect("parse"), lock);
+
+        let future = text.replace("version = 1", "version = 99");
+        assert!(SkillLockService::parse(&future).is_err());
+    }
+
+    #[test]
+    fn diff_reports_missing_modified_source_changed_and_untracked() {
+        let kept = installed("kept", "same");
+        let modified = installed("modified", "old");
+        let mut moved = installed("moved", "x");
+        moved.source = Some(SkillSource {
+            kind: SkillSourceKind::Git,
+            url: "/srv/skills.git".to_string(),
+            path: None,
+            git_ref: None,
+            pinned: false,
+            resolved_commit: Some("bbbb".to_string()),
+        });
+        let missing = installed("missing", "m");
+        let extra = installed("extra", "e");
+
+        let mut moved_entry = entry(&moved);
+        if let LockedSource::Git { commit, .. } = &mut moved_entry.source {
+            *commit = Some("aaaa".to_string());
+        }
+        let lock = SkillsLock {
+            version: SKILLS_LOCK_VERSION,
+            skills: vec![entry(&kept), entry(&modified), moved_entry, entry(&missing)],
+        };
+
+        let local = vec![kept, modified, moved, extra];
+        let drift = SkillLockService::diff(&lock, &local, |skill| {
+            Some(if skill.directory == "modified" {
+                "new".to_string()
+            } else {
+                skill.content_hash.clone().unwrap_or_default()
+            })
+        });
+
+        let kinds: Vec<_> = drift
+            .iter()
+            .map(|d| (d.directory.as_str(), d.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("modified", SkillLockDriftKind::Modified),
+                ("moved", SkillLockDriftKind::SourceChanged),
+                ("missing", SkillLockDriftKind::Missing),
+                ("extra", SkillLockDriftKind::Untracked),
+            ]
+        );
+        assert_eq!(drift[0].actual.as_deref(), Some("new"));
+    }
+}
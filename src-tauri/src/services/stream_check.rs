@@ -210,7 +210,13 @@ impl StreamCheckService {
         // 或 `npm` 字段显式指定。它们不走 get_adapter 路径，而是直接分发。
         if matches!(
             app_type,
-            AppType::OpenCode | AppType::OpenClaw | AppType::Hermes
+            AppType::OpenCode
+                | AppType::OpenClaw
+                | AppType::Hermes
+                | AppType::Qwen
+                | AppType::Goose
+                | AppType::Crush
+                | AppType::Aider
         ) {
             return Self::check_once_without_adapter(app_type, provider, config, start).await;
         }
@@ -278,9 +284,17 @@ impl StreamCheckService {
                 )
                 .await
             }
-            AppType::OpenCode | AppType::OpenClaw | AppType::Hermes => {
+            AppType::OpenCode
+            | AppType::OpenClaw
+            | AppType::Hermes
+            | AppType::Qwen
+            | AppType::Goose
+            | AppType::Crush
+            | AppType::Aider => {
                 // Already handled via early dispatch above
-                unreachable!("OpenCode/OpenClaw/Hermes 已通过 check_once_without_adapter 处理")
+                unreachable!(
+                    "OpenCode/OpenClaw/Hermes/Qwen/Goose/Crush/Aider 已通过 check_once_without_adapter 处理"
+                )
             }
         };
 
@@ -777,7 +791,40 @@ impl StreamCheckService {
                 )
                 .await
             }
-            _ => unreachable!("check_once_without_adapter 只处理 OpenCode/OpenClaw/Hermes"),
+            AppType::Qwen => {
+                Self::check_qwen_stream(
+                    &client,
+                    provider,
+                    &model_to_test,
+                    test_prompt,
+                    request_timeout,
+                )
+                .await
+            }
+            AppType::Goose | AppType::Crush => {
+                Self::check_goose_crush_stream(
+                    &client,
+                    app_type,
+                    provider,
+                    &model_to_test,
+                    test_prompt,
+                    request_timeout,
+                )
+                .await
+            }
+            AppType::Aider => {
+                Self::check_aider_stream(
+                    &client,
+                    provider,
+                    &model_to_test,
+                    test_prompt,
+                    request_timeout,
+                )
+                .await
+            }
+            _ => unreachable!(
+                "check_once_without_adapter 只处理 OpenCode/OpenClaw/Hermes/Qwen/Goose/Crush/Aider"
+            ),
         };
 
         let response_time = start.elapsed().as_millis() as u64;
@@ -1161,6 +1208,183 @@ impl StreamCheckService {
         .await
     }
 
+    /// Qwen Code 流式检查
+    ///
+    /// Qwen Code 只使用 OpenAI 兼容接口，凭据位于 `env.OPENAI_BASE_URL` / `env.OPENAI_API_KEY`。
+    /// env 为空表示 Qwen OAuth 登录，cc-switch 拿不到 token，无法检查。
+    async fn check_qwen_stream(
+        client: &Client,
+        provider: &Provider,
+        model: &str,
+        test_prompt: &str,
+        timeout: std::time::Duration,
+    ) -> Result<(u16, String), AppError> {
+        let api_key = Self::extract_env_value(provider, "OPENAI_API_KEY").ok_or_else(|| {
+            AppError::localized(
+                "qwen_api_key_missing",
+                "Qwen Code 供应商缺少 OPENAI_API_KEY（OAuth 登录不支持健康检查）",
+                "Qwen Code provider is missing OPENAI_API_KEY (OAuth login cannot be health-checked)",
+            )
+        })?;
+        let base_url = Self::extract_env_value(provider, "OPENAI_BASE_URL").ok_or_else(|| {
+            AppError::localized(
+                "qwen_base_url_missing",
+                "Qwen Code 供应商缺少 OPENAI_BASE_URL",
+                "Qwen Code provider is missing OPENAI_BASE_URL",
+            )
+        })?;
+        let auth = AuthInfo::new(api_key, AuthStrategy::Bearer);
+        Self::check_claude_stream(
+            client,
+            &base_url,
+            &auth,
+            model,
+            test_prompt,
+            timeout,
+            provider,
+            Some("openai_chat"),
+            None,
+        )
+        .await
+    }
+
+    /// Goose / Crush 流式检查
+    ///
+    /// 两者的凭据都以 `base_url` / `api_key` 平铺在根对象上，协议分别由
+    /// Goose 的 `engine` 与 Crush 的 `type` 指定：
+    /// - `openai` / `openai-compat` / `ollama` → api_format="openai_chat"
+    /// - `anthropic`                          → api_format="anthropic"
+    /// - 其余（gemini / azure / vertexai）暂不支持
+    ///
+    /// Crush 的 `api_key` 可写成 `$ENV_NAME`，此处按进程环境变量展开。
+    async fn check_goose_crush_stream(
+        client: &Client,
+        app_type: &AppType,
+        provider: &Provider,
+        model: &str,
+        test_prompt: &str,
+        timeout: std::time::Duration,
+    ) -> Result<(u16, String), AppError> {
+        let (protocol_field, default_protocol) = match app_type {
+            AppType::Goose => ("engine", "openai"),
+            _ => ("type", "openai-compat"),
+        };
+        let protocol = provider
+            .settings_config
+            .get(protocol_field)
+            .and_then(|v| v.as_str())
+            .unwrap_or(default_protocol);
+        let (api_format, auth_strategy) = match protocol {
+            "openai" | "openai-compat" | "ollama" => ("openai_chat", AuthStrategy::Bearer),
+            "anthropic" => ("anthropic", AuthStrategy::ClaudeAuth),
+            other => {
+                return Err(AppError::localized(
+                    "stream_check_protocol_not_supported",
+                    format!("{} 暂不支持协议: {other}", app_type.as_str()),
+                    format!("{} protocol not yet supported: {other}", app_type.as_str()),
+                ));
+            }
+        };
+
+        // 字段布局与 Hermes 相同（snake_case 平铺）
+        let base_url = Self::extract_hermes_base_url(provider)?;
+        let raw_key = Self::extract_hermes_api_key(provider)?;
+        let api_key = match raw_key.strip_prefix('$') {
+            Some(var) => std::env::var(var).unwrap_or_else(|_| raw_key.clone()),
+            None => raw_key,
+        };
+        let auth = AuthInfo::new(api_key, auth_strategy);
+        Self::check_claude_stream(
+            client,
+            &base_url,
+            &auth,
+            model,
+            test_prompt,
+            timeout,
+            provider,
+            Some(api_format),
+            None,
+        )
+        .await
+    }
+
+    /// Aider 流式检查
+    ///
+    /// Aider 的凭据放在 `env` 中，按 OpenAI → OpenRouter → Anthropic 的顺序取第一个非空密钥：
+    /// - `OPENAI_API_KEY`     → `config.openai-api-base`（缺省 OpenAI 官方），api_format="openai_chat"
+    /// - `OPENROUTER_API_KEY` → OpenRouter，api_format="openai_chat"
+    /// - `ANTHROPIC_API_KEY`  → Anthropic 官方，api_format="anthropic"
+    async fn check_aider_stream(
+        client: &Client,
+        provider: &Provider,
+        model: &str,
+        test_prompt: &str,
+        timeout: std::time::Duration,
+    ) -> Result<(u16, String), AppError> {
+        let (base_url, auth, api_format) =
+            if let Some(key) = Self::extract_env_value(provider, "OPENAI_API_KEY") {
+                let base_url = provider
+                    .settings_config
+                    .get("config")
+                    .and_then(|config| config.get("openai-api-base"))
+                    .and_then(|value| value.as_str())
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+                    .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
+                (
+                    base_url,
+                    AuthInfo::new(key, AuthStrategy::Bearer),
+                    "openai_chat",
+                )
+            } else if let Some(key) = Self::extract_env_value(provider, "OPENROUTER_API_KEY") {
+                (
+                    "https://openrouter.ai/api/v1".to_string(),
+                    AuthInfo::new(key, AuthStrategy::Bearer),
+                    "openai_chat",
+                )
+            } else if let Some(key) = Self::extract_env_value(provider, "ANTHROPIC_API_KEY") {
+                (
+                    "https://api.anthropic.com".to_string(),
+                    AuthInfo::new(key, AuthStrategy::ClaudeAuth),
+                    "anthropic",
+                )
+            } else {
+                return Err(AppError::localized(
+                "aider_api_key_missing",
+                "Aider 供应商缺少 OPENAI_API_KEY / OPENROUTER_API_KEY / ANTHROPIC_API_KEY",
+                "Aider provider is missing OPENAI_API_KEY / OPENROUTER_API_KEY / ANTHROPIC_API_KEY",
+            ));
+            };
+        Self::check_claude_stream(
+            client,
+            &base_url,
+            &auth,
+            model,
+            test_prompt,
+            timeout,
+            provider,
+            Some(api_format),
+            None,
+        )
+        .await
+    }
+
+    /// Aider 的模型名带 LiteLLM 路由前缀（如 `openai/gpt-4o`），探测上游时需去掉
+    fn extract_aider_model(provider: &Provider) -> Option<String> {
+        let model = provider
+            .settings_config
+            .get("config")
+            .and_then(|config| config.get("model"))
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())?;
+        let stripped = ["openai/", "openrouter/", "anthropic/"]
+            .iter()
+            .find_map(|prefix| model.strip_prefix(prefix))
+            .unwrap_or(model);
+        Some(stripped.to_string())
+    }
+
     /// OpenCode 流式检查分发器
     ///
     /// OpenCode 用 `npm` 字段（AI SDK 包名）隐式指定协议。映射关系参见
@@ -1422,24 +1646,41 @@ impl StreamCheckService {
     ) -> String {
         match app_type {
             AppType::Claude | AppType::ClaudeDesktop => {
-                Self::extract_env_model(provider, "ANTHROPIC_MODEL")
+                Self::extract_env_value(provider, "ANTHROPIC_MODEL")
                     .unwrap_or_else(|| config.claude_model.clone())
             }
             AppType::Codex => {
                 Self::extract_codex_model(provider).unwrap_or_else(|| config.codex_model.clone())
             }
-            AppType::Gemini => Self::extract_env_model(provider, "GEMINI_MODEL")
+            AppType::Gemini => Self::extract_env_value(provider, "GEMINI_MODEL")
                 .unwrap_or_else(|| config.gemini_model.clone()),
+            AppType::Qwen => Self::extract_env_value(provider, "OPENAI_MODEL")
+                .unwrap_or_else(|| "qwen3-coder-plus".to_string()),
+            AppType::Aider => {
+                Self::extract_aider_model(provider).unwrap_or_else(|| "gpt-4o".to_string())
+            }
             AppType::OpenCode => {
                 // OpenCode uses models map in settings_config
                 // Try to extract first model from the models object
                 Self::extract_opencode_model(provider).unwrap_or_else(|| "gpt-4o".to_string())
             }
-            AppType::OpenClaw | AppType::Hermes => {
-                // OpenClaw/Hermes use models array in settings_config
+            AppType::OpenClaw | AppType::Hermes | AppType::Crush => {
+                // OpenClaw/Hermes/Crush use models array in settings_config
                 // Try to extract first model from the models array
                 Self::extract_openclaw_model(provider).unwrap_or_else(|| "gpt-4o".to_string())
             }
+            AppType::Goose => {
+                // Goose custom providers list models as [{ "name": "model-id" }]
+                provider
+                    .settings_config
+                    .get("models")
+                    .and_then(|m| m.as_array())
+                    .and_then(|models| models.first())
+                    .and_then(|m| m.get("name"))
+                    .and_then(|name| name.as_str())
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "gpt-4o".to_string())
+            }
         }
     }
 
@@ -1468,7 +1709,7 @@ impl StreamCheckService {
             .map(|s| s.to_string())
     }
 
-    fn extract_env_model(provider: &Provider, key: &str) -> Option<String> {
+    fn extract_env_value(provider: &Provider, key: &str) -> Option<String> {
         provider
             .settings_config
             .get("env")
@@ -2238,4 +2479,22 @@ mod tests {
 
         assert_eq!(urls, vec!["https://api.deepseek.com/v1/chat/completions"]);
     }
+
+    #[test]
+    fn test_extract_aider_model_strips_litellm_prefix() {
+        let p = make_provider(serde_json::json!({
+            "env": { "OPENAI_API_KEY": "k" },
+            "config": { "model": "openai/deepseek-chat" },
+        }));
+        assert_eq!(
+            StreamCheckService::extract_aider_model(&p).as_deref(),
+            Some("deepseek-chat")
+        );
+
+        let p = make_provider(serde_json::json!({ "config": { "model": "gpt-4o-mini" } }));
+        assert_eq!(
+            StreamCheckService::extract_aider_model(&p).as_deref(),
+            Some("gpt-4o-mini")
+        );
+    }
 }
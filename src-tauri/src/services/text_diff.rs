@@ -0,0 +1,202 @@
+//! 文本差异
+//!
+//! 基于行级 LCS 生成 unified diff，用于写入配置文件前的预览。
+
+/// 每个变更块前后保留的上下文行数
+const CONTEXT_LINES: usize = 3;
+
+/// 超过该规模（行数乘积）时不再计算 LCS，直接按整体替换输出
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+struct Edit<'a> {
+    op: Op,
+    line: &'a str,
+    /// 该行之前的旧文件行数（0 起）
+    old_pos: usize,
+    /// 该行之前的新文件行数（0 起）
+    new_pos: usize,
+}
+
+/// 生成 unified diff；内容相同时返回空字符串
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let edits = diff_lines(old, new);
+    let changes: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.op != Op::Equal)
+        .map(|(i, _)| i)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // 合并相邻变更为 hunk（按 edits 下标，左闭右开）
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for i in changes {
+        let start = i.saturating_sub(CONTEXT_LINES);
+        let end = (i + 1 + CONTEXT_LINES).min(edits.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = format!("--- {old_label}\n+++ {new_label}\n");
+    for (start, end) in hunks {
+        let slice = &edits[start..end];
+        let old_len = slice.iter().filter(|e| e.op != Op::Insert).count();
+        let new_len = slice.iter().filter(|e| e.op != Op::Delete).count();
+        let old_start = slice[0].old_pos + usize::from(old_len > 0);
+        let new_start = slice[0].new_pos + usize::from(new_len > 0);
+        out.push_str(&format!(
+            "@@ -{old_start},{old_len} +{new_start},{new_len} @@\n"
+        ));
+        for edit in slice {
+            let prefix = match edit.op {
+                Op::Equal => ' ',
+                Op::Delete => '-',
+                Op::Insert => '+',
+            };
+            out.push(prefix);
+            out.push_str(edit.line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<Edit<'a>> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // 去掉公共前后缀，缩小 LCS 规模
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+
+    let mut ops = vec![Op::Equal; prefix];
+    ops.extend(lcs_ops(a_mid, b_mid));
+    ops.extend(std::iter::repeat(Op::Equal).take(suffix));
+
+    let (mut i, mut j) = (0, 0);
+    let mut edits = Vec::with_capacity(ops.len());
+    for op in ops {
+        let line = match op {
+            Op::Equal | Op::Delete => a[i],
+            Op::Insert => b[j],
+        };
+        edits.push(Edit {
+            op,
+            line,
+            old_pos: i,
+            new_pos: j,
+        });
+        match op {
+            Op::Equal => {
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => i += 1,
+            Op::Insert => j += 1,
+        }
+    }
+    edits
+}
+
+fn lcs_ops(a: &[&str], b: &[&str]) -> Vec<Op> {
+    let (n, m) = (a.len(), b.len());
+    if n == 0 || m == 0 || (n + 1) * (m + 1) > MAX_LCS_CELLS {
+        let mut ops = vec![Op::Delete; n];
+        ops.extend(std::iter::repeat(Op::Insert).take(m));
+        return ops;
+    }
+
+    // dp[i][j]：a[i..] 与 b[j..] 的 LCS 长度
+    let width = m + 1;
+    let mut dp = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[i * width + j] = if a[i] == b[j] {
+                dp[(i + 1) * width + j + 1] + 1
+            } else {
+                dp[(i + 1) * width + j].max(dp[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::with_capacity(n + m);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            ops.push(Op::Equal);
+            i += 1;
+            j += 1;
+        } else if dp[(i + 1) * width + j] >= dp[i * width + j + 1] {
+            ops.push(Op::Delete);
+            i += 1;
+        } else {
+            ops.push(Op::Insert);
+            j += 1;
+        }
+    }
+    ops.extend(std::iter::repeat(Op::Delete).take(n - i));
+    ops.extend(std::iter::repeat(Op::Insert).take(m - j));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_text_has_no_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "old", "new"), "");
+    }
+
+    #[test]
+    fn produces_hunks_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n";
+        let diff = unified_diff(old, new, "a/CLAUDE.md", "b/CLAUDE.md");
+        let expected = [
+            "--- a/CLAUDE.md",
+            "+++ b/CLAUDE.md",
+            "@@ -2,7 +2,7 @@",
+            " 2",
+            " 3",
+            " 4",
+            "-5",
+            "+five",
+            " 6",
+            " 7",
+            " 8",
+            "@@ -13,3 +13,4 @@",
+            " 13",
+            " 14",
+            " 15",
+            "+16",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(diff, expected);
+    }
+
+    #[test]
+    fn new_file_is_all_insertions() {
+        let diff = unified_diff("", "x\ny\n", "/dev/null", "b/.mcp.json");
+        assert!(diff.contains("@@ -0,0 +1,2 @@\n+x\n+y\n"));
+    }
+}
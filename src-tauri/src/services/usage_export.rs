@@ -0,0 +1,684 @@
+//! 用量导出与定期报表
+//!
+//! 将请求日志、`usage_daily_rollups` 以及按供应商/模型汇总的花费导出为
+//! CSV 或 NDJSON（每行一个 JSON 对象，便于 DuckDB/Parquet 工具直接读取），
+//! 并支持按周/按月把上一完整周期的报表写入指定目录。
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{Datelike, Duration as ChronoDuration, Local, NaiveDate, TimeZone};
+use rusqlite::types::ValueRef;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::{get_app_config_dir, read_json_file, write_json_file};
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::usage_stats::{
+    compute_rollup_date_bounds, effective_usage_log_filter, provider_name_coalesce,
+    push_rollup_date_filters,
+};
+
+const REPORT_SCHEDULE_KEY: &str = "usage_report_schedule";
+const REPORT_STATE_FILE: &str = "usage-report-state.json";
+const WORKER_CHECK_INTERVAL_SECS: u64 = 60 * 60;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl UsageExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UsageExportDataset {
+    /// 逐条请求日志
+    RequestLogs,
+    /// 日聚合表原始数据
+    DailyRollups,
+    /// 按 app / 供应商 / 模型汇总的花费（明细 + 日聚合合并）
+    Spend,
+}
+
+impl UsageExportDataset {
+    fn file_stem(self) -> &'static str {
+        match self {
+            Self::RequestLogs => "request-logs",
+            Self::DailyRollups => "daily-rollups",
+            Self::Spend => "spend",
+        }
+    }
+}
+
+/// 导出过滤条件（时间为 Unix 秒）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageExportFilter {
+    #[serde(default)]
+    pub start_date: Option<i64>,
+    #[serde(default)]
+    pub end_date: Option<i64>,
+    #[serde(default)]
+    pub app_type: Option<String>,
+    #[serde(default)]
+    pub provider_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageExportResult {
+    pub path: String,
+    pub dataset: UsageExportDataset,
+    pub format: UsageExportFormat,
+    pub row_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFrequency {
+    Weekly,
+    #[default]
+    Monthly,
+}
+
+fn default_report_datasets() -> Vec<UsageExportDataset> {
+    vec![UsageExportDataset::Spend]
+}
+
+/// 定期报表配置（存储在 settings 表）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReportSchedule {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub frequency: ReportFrequency,
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    #[serde(default)]
+    pub format: UsageExportFormat,
+    #[serde(default = "default_report_datasets")]
+    pub datasets: Vec<UsageExportDataset>,
+    #[serde(default)]
+    pub app_type: Option<String>,
+}
+
+impl Default for UsageReportSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            frequency: ReportFrequency::default(),
+            output_dir: None,
+            format: UsageExportFormat::default(),
+            datasets: default_report_datasets(),
+            app_type: None,
+        }
+    }
+}
+
+/// 一个完整的报表周期
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportPeriod {
+    /// 周期标识，如 `2026-W41` / `2026-09`
+    pub key: String,
+    pub start: NaiveDate,
+    /// 不含
+    pub end: NaiveDate,
+}
+
+/// 定期报表执行状态（单独落盘，避免写 settings 表触发云同步）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReportState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_period: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<i64>,
+    #[serde(default)]
+    pub last_files: Vec<String>,
+}
+
+/// 计算 `today` 之前最近一个已结束的周期
+pub fn last_completed_period(frequency: ReportFrequency, today: NaiveDate) -> ReportPeriod {
+    match frequency {
+        ReportFrequency::Weekly => {
+            let this_monday =
+                today - ChronoDuration::days(i64::from(today.weekday().num_days_from_monday()));
+            let start = this_monday - ChronoDuration::days(7);
+            let iso = start.iso_week();
+            ReportPeriod {
+                key: format!("{}-W{:02}", iso.year(), iso.week()),
+                start,
+                end: this_monday,
+            }
+        }
+        ReportFrequency::Monthly => {
+            let this_month = today.with_day(1).unwrap_or(today);
+            let start = (this_month - ChronoDuration::days(1))
+                .with_day(1)
+                .unwrap_or(this_month);
+            ReportPeriod {
+                key: start.format("%Y-%m").to_string(),
+                start,
+                end: this_month,
+            }
+        }
+    }
+}
+
+fn local_midnight_ts(day: NaiveDate) -> Result<i64, AppError> {
+    let naive = day
+        .and_hms_opt(0, 0, 0)
+        .ok_or_else(|| AppError::Message(format!("无效日期: {day}")))?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.timestamp())
+        .ok_or_else(|| AppError::Message(format!("无法转换本地时间: {day}")))
+}
+
+impl ReportPeriod {
+    /// 转换为导出过滤条件（闭区间，单位秒）
+    pub fn to_filter(&self, app_type: Option<String>) -> Result<UsageExportFilter, AppError> {
+        Ok(UsageExportFilter {
+            start_date: Some(local_midnight_ts(self.start)?),
+            end_date: Some(local_midnight_ts(self.end)? - 1),
+            app_type,
+            provider_id: None,
+        })
+    }
+}
+
+fn sql_value_to_json(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => Value::from(f),
+        ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).into_owned()),
+        ValueRef::Blob(b) => Value::String(String::from_utf8_lossy(b).into_owned()),
+    }
+}
+
+/// 导出用的表格数据（列名 + 行）
+#[derive(Debug, Clone, Default)]
+pub struct ExportTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+fn query_table(
+    conn: &rusqlite::Connection,
+    sql: &str,
+    params: &[Box<dyn rusqlite::ToSql>],
+) -> Result<ExportTable, AppError> {
+    let mut stmt = conn.prepare(sql)?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    let column_count = columns.len();
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    let mut rows = stmt.query(params_refs.as_slice())?;
+
+    let mut out = Vec::new();
+    while let Some(row) = rows.next()? {
+        let mut values = Vec::with_capacity(column_count);
+        for idx in 0..column_count {
+            values.push(sql_value_to_json(row.get_ref(idx)?));
+        }
+        out.push(values);
+    }
+    Ok(ExportTable { columns, rows: out })
+}
+
+/// 以这些字符开头的单元格会被 Excel 等表格软件当作公式执行
+const CSV_FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+fn csv_field(value: &Value) -> String {
+    let raw = match value {
+        Value::Null => return String::new(),
+        // 模型名、供应商名可能来自第三方：按 OWASP 建议加 `'` 前缀阻止公式注入，
+        // 纯数字（如负数金额）不会被当作公式，保持原样
+        Value::String(s)
+            if s.starts_with(CSV_FORMULA_PREFIXES) && s.trim().parse::<f64>().is_err() =>
+        {
+            format!("'{s}")
+        }
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if raw.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", raw.replace('"', "\"\""))
+    } else {
+        raw
+    }
+}
+
+/// 按格式写出表格
+pub fn write_table<W: Write>(
+    writer: &mut W,
+    table: &ExportTable,
+    format: UsageExportFormat,
+) -> std::io::Result<()> {
+    match format {
+        UsageExportFormat::Csv => {
+            let header: Vec<String> = table
+                .columns
+                .iter()
+                .map(|c| csv_field(&Value::String(c.clone())))
+                .collect();
+            writeln!(writer, "{}", header.join(","))?;
+            for row in &table.rows {
+                let fields: Vec<String> = row.iter().map(csv_field).collect();
+                writeln!(writer, "{}", fields.join(","))?;
+            }
+        }
+        UsageExportFormat::Ndjson => {
+            for row in &table.rows {
+                let object: serde_json::Map<String, Value> = table
+                    .columns
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned())
+                    .collect();
+                serde_json::to_writer(&mut *writer, &Value::Object(object))?;
+                writeln!(writer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    }
+}
+
+impl Database {
+    /// 查询待导出的数据集
+    pub fn query_usage_export(
+        &self,
+        dataset: UsageExportDataset,
+        filter: &UsageExportFilter,
+    ) -> Result<ExportTable, AppError> {
+        let conn = lock_conn!(self.conn);
+
+        let mut detail_conditions = vec![effective_usage_log_filter("l")];
+        let mut detail_params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        if let Some(start) = filter.start_date {
+            detail_conditions.push("l.created_at >= ?".to_string());
+            detail_params.push(Box::new(start));
+        }
+        if let Some(end) = filter.end_date {
+            detail_conditions.push("l.created_at <= ?".to_string());
+            detail_params.push(Box::new(end));
+        }
+        if let Some(app_type) = &filter.app_type {
+            detail_conditions.push("l.app_type = ?".to_string());
+            detail_params.push(Box::new(app_type.clone()));
+        }
+        if let Some(provider_id) = &filter.provider_id {
+            detail_conditions.push("l.provider_id = ?".to_string());
+            detail_params.push(Box::new(provider_id.clone()));
+        }
+
+        let mut rollup_conditions = Vec::new();
+        let mut rollup_params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        let bounds = compute_rollup_date_bounds(filter.start_date, filter.end_date)?;
+        push_rollup_date_filters(
+            &mut rollup_conditions,
+            &mut rollup_params,
+            "r.date",
+            &bounds,
+        );
+        if let Some(app_type) = &filter.app_type {
+            rollup_conditions.push("r.app_type = ?".to_string());
+            rollup_params.push(Box::new(app_type.clone()));
+        }
+        if let Some(provider_id) = &filter.provider_id {
+            rollup_conditions.push("r.provider_id = ?".to_string());
+            rollup_params.push(Box::new(provider_id.clone()));
+        }
+
+        let detail_where = where_clause(&detail_conditions);
+        let rollup_where = where_clause(&rollup_conditions);
+        let log_pname = provider_name_coalesce("l", "p");
+        let rollup_pname = provider_name_coalesce("r", "p");
+
+        match dataset {
+            UsageExportDataset::RequestLogs => {
+                let sql = format!(
+                    "SELECT l.request_id,
+                            l.created_at,
+                            strftime('%Y-%m-%dT%H:%M:%S', l.created_at, 'unixepoch', 'localtime')
+                                AS created_at_local,
+                            l.app_type, l.provider_id, {log_pname} AS provider_name,
+                            l.model, l.request_model, l.pricing_model, l.status_code,
+                            l.input_tokens, l.output_tokens, l.cache_read_tokens,
+                            l.cache_creation_tokens, l.input_cost_usd, l.output_cost_usd,
+                            l.cache_read_cost_usd, l.cache_creation_cost_usd, l.total_cost_usd,
+                            l.cost_multiplier, l.latency_ms, l.first_token_ms, l.duration_ms,
+                            l.is_streaming, l.session_id, l.data_source
+                     FROM proxy_request_logs l
+                     LEFT JOIN providers p ON l.provider_id = p.id AND l.app_type = p.app_type
+                     {detail_where}
+                     ORDER BY l.created_at ASC, l.request_id ASC"
+                );
+                query_table(&conn, &sql, &detail_params)
+            }
+            UsageExportDataset::DailyRollups => {
+                let sql = format!(
+                    "SELECT r.date, r.app_type, r.provider_id, {rollup_pname} AS provider_name,
+                            r.model, r.request_model, r.pricing_model, r.request_count,
+                            r.success_count, r.input_tokens, r.output_tokens,
+                            r.cache_read_tokens, r.cache_creation_tokens, r.total_cost_usd,
+                            r.avg_latency_ms
+                     FROM usage_daily_rollups r
+                     LEFT JOIN providers p ON r.provider_id = p.id AND r.app_type = p.app_type
+                     {rollup_where}
+                     ORDER BY r.date ASC, r.app_type, r.provider_id, r.model"
+                );
+                query_table(&conn, &sql, &rollup_params)
+            }
+            UsageExportDataset::Spend => {
+                // 与统计页一致：明细 + 日聚合 UNION，模型按「有效计价模型」分组
+                let sql = format!(
+                    "SELECT app_type, provider_id, MAX(provider_name) AS provider_name, model,
+                            SUM(request_count) AS request_count,
+                            SUM(input_tokens) AS input_tokens,
+                            SUM(output_tokens) AS output_tokens,
+                            SUM(cache_read_tokens) AS cache_read_tokens,
+                            SUM(cache_creation_tokens) AS cache_creation_tokens,
+                            printf('%.6f', SUM(total_cost)) AS total_cost_usd
+                     FROM (
+                        SELECT l.app_type, l.provider_id, {log_pname} AS provider_name,
+                               COALESCE(NULLIF(l.pricing_model, ''), l.model) AS model,
+                               COUNT(*) AS request_count,
+                               COALESCE(SUM(l.input_tokens), 0) AS input_tokens,
+                               COALESCE(SUM(l.output_tokens), 0) AS output_tokens,
+                               COALESCE(SUM(l.cache_read_tokens), 0) AS cache_read_tokens,
+                               COALESCE(SUM(l.cache_creation_tokens), 0) AS cache_creation_tokens,
+                               COALESCE(SUM(CAST(l.total_cost_usd AS REAL)), 0) AS total_cost
+                        FROM proxy_request_logs l
+                        LEFT JOIN providers p ON l.provider_id = p.id AND l.app_type = p.app_type
+                        {detail_where}
+                        GROUP BY l.app_type, l.provider_id,
+                                 COALESCE(NULLIF(l.pricing_model, ''), l.model)
+                        UNION ALL
+                        SELECT r.app_type, r.provider_id, {rollup_pname},
+                               COALESCE(NULLIF(r.pricing_model, ''), r.model),
+                               COALESCE(SUM(r.request_count), 0),
+                               COALESCE(SUM(r.input_tokens), 0),
+                               COALESCE(SUM(r.output_tokens), 0),
+                               COALESCE(SUM(r.cache_read_tokens), 0),
+                               COALESCE(SUM(r.cache_creation_tokens), 0),
+                               COALESCE(SUM(CAST(r.total_cost_usd AS REAL)), 0)
+                        FROM usage_daily_rollups r
+                        LEFT JOIN providers p ON r.provider_id = p.id AND r.app_type = p.app_type
+                        {rollup_where}
+                        GROUP BY r.app_type, r.provider_id,
+                                 COALESCE(NULLIF(r.pricing_model, ''), r.model)
+                     )
+                     GROUP BY app_type, provider_id, model
+                     ORDER BY SUM(total_cost) DESC, app_type, provider_id, model"
+                );
+                let mut params = detail_params;
+                params.extend(rollup_params);
+                query_table(&conn, &sql, &params)
+            }
+        }
+    }
+
+    /// 获取定期报表配置
+    pub fn get_usage_report_schedule(&self) -> Result<UsageReportSchedule, AppError> {
+        match self.get_setting(REPORT_SCHEDULE_KEY)? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Message(format!("解析用量报表配置失败: {e}"))),
+            None => Ok(UsageReportSchedule::default()),
+        }
+    }
+
+    /// 保存定期报表配置
+    pub fn save_usage_report_schedule(
+        &self,
+        schedule: &UsageReportSchedule,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(schedule)
+            .map_err(|e| AppError::Message(format!("序列化用量报表配置失败: {e}")))?;
+        self.set_setting(REPORT_SCHEDULE_KEY, &json)
+    }
+}
+
+/// 导出单个数据集到文件
+pub fn export_usage(
+    db: &Database,
+    dataset: UsageExportDataset,
+    format: UsageExportFormat,
+    filter: &UsageExportFilter,
+    path: &Path,
+) -> Result<UsageExportResult, AppError> {
+    let table = db.query_usage_export(dataset, filter)?;
+
+    let mut buf = Vec::new();
+    write_table(&mut buf, &table, format).map_err(|e| AppError::io(path, e))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+    }
+    crate::config::atomic_write(path, &buf)?;
+
+    Ok(UsageExportResult {
+        path: path.to_string_lossy().to_string(),
+        dataset,
+        format,
+        row_count: table.rows.len(),
+    })
+}
+
+fn report_state_path() -> PathBuf {
+    get_app_config_dir().join(REPORT_STATE_FILE)
+}
+
+pub fn load_report_state() -> UsageReportState {
+    let path = report_state_path();
+    if !path.exists() {
+        return UsageReportState::default();
+    }
+    read_json_file(&path).unwrap_or_else(|e| {
+        log::warn!("读取用量报表状态失败: {e}");
+        UsageReportState::default()
+    })
+}
+
+/// 生成指定周期的报表文件（每个数据集一个文件）
+pub fn generate_report(
+    db: &Database,
+    schedule: &UsageReportSchedule,
+    period: &ReportPeriod,
+) -> Result<Vec<UsageExportResult>, AppError> {
+    let output_dir = schedule
+        .output_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .ok_or_else(|| AppError::InvalidInput("未配置用量报表输出目录".to_string()))?;
+
+    let filter = period.to_filter(schedule.app_type.clone())?;
+    let mut results = Vec::new();
+    for dataset in &schedule.datasets {
+        let file_name = format!(
+            "cc-switch-usage-{}-{}.{}",
+            period.key,
+            dataset.file_stem(),
+            schedule.format.extension()
+        );
+        let path = Path::new(output_dir).join(file_name);
+        results.push(export_usage(db, *dataset, schedule.format, &filter, &path)?);
+    }
+    Ok(results)
+}
+
+/// 立即生成上一完整周期的报表并记录状态
+pub fn run_report_now(db: &Database) -> Result<Vec<UsageExportResult>, AppError> {
+    let schedule = db.get_usage_report_schedule()?;
+    let period = last_completed_period(schedule.frequency, Local::now().date_naive());
+    let results = generate_report(db, &schedule, &period)?;
+
+    let state = UsageReportState {
+        last_period: Some(format!("{:?}:{}", schedule.frequency, period.key)),
+        last_run_at: Some(chrono::Utc::now().timestamp_millis()),
+        last_files: results.iter().map(|r| r.path.clone()).collect(),
+    };
+    write_json_file(&report_state_path(), &state)?;
+    Ok(results)
+}
+
+/// 定时任务入口：启用且上一周期尚未生成时执行
+fn run_scheduled(db: &Database) -> Result<Option<Vec<UsageExportResult>>, AppError> {
+    let schedule = db.get_usage_report_schedule()?;
+    if !schedule.enabled || schedule.datasets.is_empty() {
+        return Ok(None);
+    }
+    let period = last_completed_period(schedule.frequency, Local::now().date_naive());
+    let period_id = format!("{:?}:{}", schedule.frequency, period.key);
+    if load_report_state().last_period.as_deref() == Some(period_id.as_str()) {
+        return Ok(None);
+    }
+    run_report_now(db).map(Some)
+}
+
+/// 启动后台定时器（每小时检查一次是否需要生成报表）
+pub fn start_worker(db: Arc<Database>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(WORKER_CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            let db = db.clone();
+            match tauri::async_runtime::spawn_blocking(move || run_scheduled(&db)).await {
+                Ok(Ok(Some(results))) => {
+                    log::info!("用量报表已生成: {} 个文件", results.len());
+                }
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => log::warn!("生成用量报表失败: {e}"),
+                Err(e) => log::warn!("用量报表任务异常: {e}"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+
+    fn insert_log(db: &Database, id: &str, provider: &str, model: &str, cost: &str, ts: i64) {
+        let conn = lock_conn!(db.conn);
+        conn.execute(
+            "INSERT INTO proxy_request_logs (
+                request_id, provider_id, app_type, model, input_tokens, output_tokens,
+                total_cost_usd, latency_ms, status_code, created_at
+            ) VALUES (?1, ?2, 'claude', ?3, 100, 50, ?4, 10, 200, ?5)",
+            params![id, provider, model, cost, ts],
+        )
+        .expect("insert log");
+    }
+
+    #[test]
+    fn last_completed_period_handles_weeks_and_months() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+
+        let week = last_completed_period(ReportFrequency::Weekly, today);
+        assert_eq!(week.start, NaiveDate::from_ymd_opt(2026, 10, 5).unwrap());
+        assert_eq!(week.end, NaiveDate::from_ymd_opt(2026, 10, 12).unwrap());
+        assert_eq!(week.key, "2026-W41");
+
+        let january = NaiveDate::from_ymd_opt(2026, 1, 3).unwrap();
+        let month = last_completed_period(ReportFrequency::Monthly, january);
+        assert_eq!(month.key, "2025-12");
+        assert_eq!(month.start, NaiveDate::from_ymd_opt(2025, 12, 1).unwrap());
+        assert_eq!(month.end, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+    }
+
+    #[test]
+    fn csv_output_escapes_fields() {
+        let table = ExportTable {
+            columns: vec!["model".to_string(), "cost".to_string()],
+            rows: vec![vec![Value::from("a,\"b\""), Value::from(1.5)]],
+        };
+        let mut out = Vec::new();
+        write_table(&mut out, &table, UsageExportFormat::Csv).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "model,cost\n\"a,\"\"b\"\"\",1.5\n"
+        );
+    }
+
+    #[test]
+    fn csv_output_neutralizes_formula_prefixes() {
+        let table = ExportTable {
+            columns: vec!["model".to_string(), "cost".to_string()],
+            rows: vec![
+                vec![Value::from("=HYPERLINK(\"http://x\")"), Value::from("-0.5")],
+                vec![Value::from("@SUM(A1)"), Value::from(-1)],
+                vec![Value::from("\tcmd"), Value::from("+1")],
+            ],
+        };
+        let mut out = Vec::new();
+        write_table(&mut out, &table, UsageExportFormat::Csv).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "model,cost\n\"'=HYPERLINK(\"\"http://x\"\")\",-0.5\n'@SUM(A1),-1\n'\tcmd,+1\n"
+        );
+    }
+
+    #[test]
+    fn spend_export_groups_by_provider_and_model() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        insert_log(&db, "r1", "p1", "claude-sonnet-4-5", "0.10", 1_000);
+        insert_log(&db, "r2", "p1", "claude-sonnet-4-5", "0.20", 2_000);
+        insert_log(&db, "r3", "p2", "claude-haiku-4-5", "0.05", 3_000);
+
+        let table = db.query_usage_export(
+            UsageExportDataset::Spend,
+            &UsageExportFilter {
+                provider_id: Some("p1".to_string()),
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(table.rows.len(), 1);
+        let cost_idx = table
+            .columns
+            .iter()
+            .position(|c| c == "total_cost_usd")
+            .unwrap();
+        assert_eq!(table.rows[0][cost_idx], Value::from("0.300000"));
+
+        let mut out = Vec::new();
+        let logs = db.query_usage_export(
+            UsageExportDataset::RequestLogs,
+            &UsageExportFilter {
+                start_date: Some(1_500),
+                ..Default::default()
+            },
+        )?;
+        write_table(&mut out, &logs, UsageExportFormat::Ndjson).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        let first: Value = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(first["request_id"], "r2");
+        Ok(())
+    }
+}
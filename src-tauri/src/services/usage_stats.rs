@@ -42,7 +42,7 @@ pub struct UsageSummaryByApp {
 
 /// Helper: compute (real_total, hit_rate) from the four token counters.
 /// All inputs must already be cache-normalized (i.e. input excludes cache).
-fn derive_real_total_and_hit_rate(
+pub(crate) fn derive_real_total_and_hit_rate(
     fresh_input: u64,
     output: u64,
     cache_creation: u64,
@@ -201,7 +201,7 @@ fn row_to_request_log_detail(row: &rusqlite::Row<'_>) -> rusqlite::Result<Reques
 /// Session logs use placeholder provider_ids (e.g., `_session`, `_<app>_session`)
 /// that don't exist in the providers table — the CASE expression below is the
 /// authoritative mapping from placeholder to readable name.
-fn provider_name_coalesce(log_alias: &str, provider_alias: &str) -> String {
+pub(crate) fn provider_name_coalesce(log_alias: &str, provider_alias: &str) -> String {
     format!(
         "COALESCE({provider_alias}.name, CASE {log_alias}.provider_id \
          WHEN '_session' THEN 'Claude (Session)' \
@@ -346,7 +346,7 @@ pub(crate) fn has_matching_proxy_usage_log(
 }
 
 #[derive(Debug, Clone, Default)]
-struct RollupDateBounds {
+pub(crate) struct RollupDateBounds {
     start: Option<String>,
     end: Option<String>,
     is_empty: bool,
@@ -359,7 +359,7 @@ fn local_datetime_from_timestamp(ts: i64) -> Result<chrono::DateTime<Local>, App
         .ok_or_else(|| AppError::Database(format!("无法解析本地时间戳: {ts}")))
 }
 
-fn compute_rollup_date_bounds(
+pub(crate) fn compute_rollup_date_bounds(
     start_ts: Option<i64>,
     end_ts: Option<i64>,
 ) -> Result<RollupDateBounds, AppError> {
@@ -400,7 +400,7 @@ fn compute_rollup_date_bounds(
     })
 }
 
-fn push_rollup_date_filters(
+pub(crate) fn push_rollup_date_filters(
     conditions: &mut Vec<String>,
     params: &mut Vec<Box<dyn rusqlite::ToSql>>,
     column: &str,
@@ -1454,13 +1454,8 @@ pub struct ProviderLimitStatus {
     pub monthly_exceeded: bool,
 }
 
-#[derive(Clone)]
-struct PricingInfo {
-    input: rust_decimal::Decimal,
-    output: rust_decimal::Decimal,
-    cache_read: rust_decimal::Decimal,
-    cache_creation: rust_decimal::Decimal,
-}
+/// 回填时的定价缓存（键含供应商覆盖维度，`None` 表示已确认无定价）
+type PricingCache = HashMap<String, Option<ModelPricing>>;
 
 impl Database {
     /// Recalculate stored zero-cost usage rows once pricing becomes available.
@@ -1542,7 +1537,7 @@ impl Database {
     fn maybe_backfill_log_costs(
         conn: &Connection,
         log: &mut RequestLogDetail,
-        pricing_cache: &mut HashMap<String, PricingInfo>,
+        pricing_cache: &mut PricingCache,
     ) -> Result<bool, AppError> {
         let existing_cost = rust_decimal::Decimal::from_str(&log.total_cost_usd)
             .unwrap_or(rust_decimal::Decimal::ZERO);
@@ -1576,21 +1571,32 @@ impl Database {
         // 1. Codex/Gemini 的 input_tokens 包含 cache_read_tokens，需要扣除后按输入价计费
         // 2. Claude/Anthropic 的 input_tokens 已经是 fresh input，不能再次扣减
         // 3. 各项成本是基础成本（不含倍率），倍率只作用于最终总价
+        // 4. 分档定价按整次请求的提示 token 总量选择档位
         let input_includes_cache_read = matches!(log.app_type.as_str(), "codex" | "gemini");
         let billable_input_tokens = if input_includes_cache_read {
             (log.input_tokens as u64).saturating_sub(log.cache_read_tokens as u64)
         } else {
             log.input_tokens as u64
         };
-        let input_cost =
-            rust_decimal::Decimal::from(billable_input_tokens) * pricing.input / million;
-        let output_cost =
-            rust_decimal::Decimal::from(log.output_tokens as u64) * pricing.output / million;
+        let prompt_tokens = if input_includes_cache_read {
+            log.input_tokens as u64 + log.cache_creation_tokens as u64
+        } else {
+            log.input_tokens as u64
+                + log.cache_read_tokens as u64
+                + log.cache_creation_tokens as u64
+        };
+        let pricing = pricing.for_prompt_tokens(prompt_tokens);
+        let input_cost = rust_decimal::Decimal::from(billable_input_tokens)
+            * pricing.input_cost_per_million
+            / million;
+        let output_cost = rust_decimal::Decimal::from(log.output_tokens as u64)
+            * pricing.output_cost_per_million
+            / million;
         let cache_read_cost = rust_decimal::Decimal::from(log.cache_read_tokens as u64)
-            * pricing.cache_read
+            * pricing.cache_read_cost_per_million
             / million;
         let cache_creation_cost = rust_decimal::Decimal::from(log.cache_creation_tokens as u64)
-            * pricing.cache_creation
+            * pricing.cache_creation_cost_per_million
             / million;
         // 总成本 = 基础成本之和 × 倍率
         let base_total = input_cost + output_cost + cache_read_cost + cache_creation_cost;
@@ -1626,38 +1632,52 @@ impl Database {
 
     fn get_model_pricing_cached(
         conn: &Connection,
-        cache: &mut HashMap<String, PricingInfo>,
+        cache: &mut PricingCache,
         model: &str,
-    ) -> Result<Option<PricingInfo>, AppError> {
+    ) -> Result<Option<ModelPricing>, AppError> {
         if let Some(info) = cache.get(model) {
-            return Ok(Some(info.clone()));
+            return Ok(info.clone());
         }
 
-        let row = find_model_pricing_row(conn, model)?;
-        let Some((input, output, cache_read, cache_creation)) = row else {
-            return Ok(None);
-        };
+        let pricing = lookup_model_pricing(conn, model)?;
+        cache.insert(model.to_string(), pricing.clone());
+        Ok(pricing)
+    }
 
-        let pricing = PricingInfo {
-            input: rust_decimal::Decimal::from_str(&input)
-                .map_err(|e| AppError::Database(format!("解析输入价格失败: {e}")))?,
-            output: rust_decimal::Decimal::from_str(&output)
-                .map_err(|e| AppError::Database(format!("解析输出价格失败: {e}")))?,
-            cache_read: rust_decimal::Decimal::from_str(&cache_read)
-                .map_err(|e| AppError::Database(format!("解析缓存读取价格失败: {e}")))?,
-            cache_creation: rust_decimal::Decimal::from_str(&cache_creation)
-                .map_err(|e| AppError::Database(format!("解析缓存写入价格失败: {e}")))?,
-        };
+    fn get_provider_pricing_cached(
+        conn: &Connection,
+        cache: &mut PricingCache,
+        log: &RequestLogDetail,
+        model: &str,
+    ) -> Result<Option<ModelPricing>, AppError> {
+        let key = format!("{}\u{1f}{}\u{1f}{model}", log.provider_id, log.app_type);
+        if let Some(info) = cache.get(&key) {
+            return Ok(info.clone());
+        }
 
-        cache.insert(model.to_string(), pricing.clone());
-        Ok(Some(pricing))
+        let pricing = find_provider_model_pricing(conn, &log.provider_id, &log.app_type, model)?;
+        cache.insert(key, pricing.clone());
+        Ok(pricing)
+    }
+
+    /// 供应商定价覆盖优先，未覆盖时回退全局模型定价
+    fn get_effective_pricing_cached(
+        conn: &Connection,
+        cache: &mut PricingCache,
+        log: &RequestLogDetail,
+        model: &str,
+    ) -> Result<Option<ModelPricing>, AppError> {
+        if let Some(pricing) = Self::get_provider_pricing_cached(conn, cache, log, model)? {
+            return Ok(Some(pricing));
+        }
+        Self::get_model_pricing_cached(conn, cache, model)
     }
 
     fn get_log_model_pricing_cached(
         conn: &Connection,
-        cache: &mut HashMap<String, PricingInfo>,
+        cache: &mut PricingCache,
         log: &RequestLogDetail,
-    ) -> Result<Option<PricingInfo>, AppError> {
+    ) -> Result<Option<ModelPricing>, AppError> {
         // 写入时的计价基准已落库（v11+）：回填只按它重算，找不到就保持 0 成本
         // 等补价。不能换用 model/request_model 猜——路由接管 + request 计价模式下
         // 三者可能各不相同（model=上游回显、request_model=客户端别名、
@@ -1668,10 +1688,10 @@ impl Database {
             .as_deref()
             .filter(|pm| !is_placeholder_pricing_model(pm))
         {
-            return Self::get_model_pricing_cached(conn, cache, pricing_model);
+            return Self::get_effective_pricing_cached(conn, cache, log, pricing_model);
         }
 
-        if let Some(pricing) = Self::get_model_pricing_cached(conn, cache, &log.model)? {
+        if let Some(pricing) = Self::get_effective_pricing_cached(conn, cache, log, &log.model)? {
             return Ok(Some(pricing));
         }
 
@@ -1691,23 +1711,67 @@ impl Database {
             return Ok(None);
         }
 
-        Self::get_model_pricing_cached(conn, cache, request_model)
+        Self::get_effective_pricing_cached(conn, cache, log, request_model)
     }
 }
 
 pub(crate) fn find_model_pricing(conn: &Connection, model_id: &str) -> Option<ModelPricing> {
-    find_model_pricing_row(conn, model_id)
-        .ok()
-        .flatten()
-        .and_then(|(input, output, cache_read, cache_creation)| {
-            ModelPricing::from_strings(&input, &output, &cache_read, &cache_creation).ok()
-        })
+    lookup_model_pricing(conn, model_id).ok().flatten()
+}
+
+/// 查找模型定价（含分档定价）
+pub(crate) fn lookup_model_pricing(
+    conn: &Connection,
+    model_id: &str,
+) -> Result<Option<ModelPricing>, AppError> {
+    let Some(row) = find_model_pricing_match(conn, model_id)? else {
+        return Ok(None);
+    };
+    ModelPricing::from_strings(
+        &row.input,
+        &row.output,
+        &row.cache_read,
+        &row.cache_creation,
+    )
+    .map(|pricing| Some(pricing.with_tiers_json(row.tiers.as_deref())))
+    .map_err(|e| AppError::Database(format!("解析定价数据失败: {e}")))
 }
 
 pub(crate) fn find_model_pricing_row(
     conn: &Connection,
     model_id: &str,
 ) -> Result<Option<(String, String, String, String)>, AppError> {
+    Ok(find_model_pricing_match(conn, model_id)?
+        .map(|row| (row.input, row.output, row.cache_read, row.cache_creation)))
+}
+
+/// 命中的 model_pricing 行
+struct PricingRow {
+    input: String,
+    output: String,
+    cache_read: String,
+    cache_creation: String,
+    tiers: Option<String>,
+}
+
+fn row_to_pricing_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<PricingRow> {
+    Ok(PricingRow {
+        input: row.get(0)?,
+        output: row.get(1)?,
+        cache_read: row.get(2)?,
+        cache_creation: row.get(3)?,
+        tiers: row.get(4)?,
+    })
+}
+
+const PRICING_ROW_COLUMNS: &str = "input_cost_per_million, output_cost_per_million,
+                cache_read_cost_per_million, cache_creation_cost_per_million, pricing_tiers";
+
+/// 匹配顺序：精确 → 前缀（`{id}-*` 变体）→ 通配符模式（`*` / `?`，最具体者优先）
+fn find_model_pricing_match(
+    conn: &Connection,
+    model_id: &str,
+) -> Result<Option<PricingRow>, AppError> {
     let candidates = model_pricing_candidates(model_id);
     if candidates.is_empty() {
         return Ok(None);
@@ -1727,6 +1791,16 @@ pub(crate) fn find_model_pricing_row(
         }
     }
 
+    let patterns = query_model_pricing_patterns(conn)?;
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    for candidate in &candidates {
+        if let Some(pattern) = best_pattern_match(patterns.iter().map(|p| p.as_str()), candidate) {
+            return query_model_pricing_exact(conn, pattern);
+        }
+    }
+
     Ok(None)
 }
 
@@ -1738,21 +1812,11 @@ pub(crate) fn is_placeholder_pricing_model(model_id: &str) -> bool {
 fn query_model_pricing_exact(
     conn: &Connection,
     model_id: &str,
-) -> Result<Option<(String, String, String, String)>, AppError> {
+) -> Result<Option<PricingRow>, AppError> {
     conn.query_row(
-        "SELECT input_cost_per_million, output_cost_per_million,
-                cache_read_cost_per_million, cache_creation_cost_per_million
-         FROM model_pricing
-         WHERE model_id = ?1",
+        &format!("SELECT {PRICING_ROW_COLUMNS} FROM model_pricing WHERE model_id = ?1"),
         [model_id],
-        |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-                row.get::<_, String>(3)?,
-            ))
-        },
+        row_to_pricing_row,
     )
     .optional()
     .map_err(|e| AppError::Database(format!("查询模型定价失败: {e}")))
@@ -1761,29 +1825,137 @@ fn query_model_pricing_exact(
 fn query_model_pricing_prefix(
     conn: &Connection,
     model_id: &str,
-) -> Result<Option<(String, String, String, String)>, AppError> {
+) -> Result<Option<PricingRow>, AppError> {
     let pattern = format!("{model_id}-%");
     conn.query_row(
-        "SELECT input_cost_per_million, output_cost_per_million,
-                cache_read_cost_per_million, cache_creation_cost_per_million
-         FROM model_pricing
-         WHERE model_id LIKE ?1
-         ORDER BY LENGTH(model_id) ASC
-         LIMIT 1",
+        &format!(
+            "SELECT {PRICING_ROW_COLUMNS}
+             FROM model_pricing
+             WHERE model_id LIKE ?1
+               AND instr(model_id, '*') = 0 AND instr(model_id, '?') = 0
+             ORDER BY LENGTH(model_id) ASC
+             LIMIT 1"
+        ),
         [pattern],
-        |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-                row.get::<_, String>(3)?,
-            ))
-        },
+        row_to_pricing_row,
     )
     .optional()
     .map_err(|e| AppError::Database(format!("查询模型前缀定价失败: {e}")))
 }
 
+fn query_model_pricing_patterns(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT model_id FROM model_pricing
+             WHERE instr(model_id, '*') > 0 OR instr(model_id, '?') > 0",
+        )
+        .map_err(|e| AppError::Database(format!("查询模型定价模式失败: {e}")))?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| AppError::Database(format!("查询模型定价模式失败: {e}")))?;
+    let mut patterns = Vec::new();
+    for row in rows {
+        patterns.push(row?);
+    }
+    Ok(patterns)
+}
+
+/// 在多个通配符模式中选出匹配 `model_id` 且字面字符最多（最具体）的一个
+pub(crate) fn best_pattern_match<'a>(
+    patterns: impl Iterator<Item = &'a str>,
+    model_id: &str,
+) -> Option<&'a str> {
+    patterns
+        .filter(|pattern| glob_match(&pattern.to_ascii_lowercase(), model_id))
+        .max_by(|a, b| literal_len(a).cmp(&literal_len(b)).then_with(|| b.cmp(a)))
+}
+
+fn literal_len(pattern: &str) -> usize {
+    pattern.chars().filter(|c| !matches!(c, '*' | '?')).count()
+}
+
+/// 简单通配符匹配：`*` 匹配任意长度，`?` 匹配单个字符
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0usize, 0usize);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star_pi, star_ti)) = star {
+            pi = star_pi + 1;
+            ti = star_ti + 1;
+            star = Some((star_pi, star_ti + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|c| *c == '*')
+}
+
+/// 查找供应商级定价覆盖（`provider_model_pricing`），模式支持精确 ID 与通配符
+///
+/// 覆盖价替换全局模型定价，供应商 `cost_multiplier` 仍作用于最终总价。
+pub(crate) fn find_provider_model_pricing(
+    conn: &Connection,
+    provider_id: &str,
+    app_type: &str,
+    model_id: &str,
+) -> Result<Option<ModelPricing>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT model_pattern, input_cost_per_million, output_cost_per_million,
+                    cache_read_cost_per_million, cache_creation_cost_per_million
+             FROM provider_model_pricing
+             WHERE provider_id = ?1 AND app_type = ?2",
+        )
+        .map_err(|e| AppError::Database(format!("查询供应商定价覆盖失败: {e}")))?;
+    let rows = stmt
+        .query_map(params![provider_id, app_type], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                (
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ),
+            ))
+        })
+        .map_err(|e| AppError::Database(format!("查询供应商定价覆盖失败: {e}")))?;
+    let mut overrides = HashMap::new();
+    for row in rows {
+        let (pattern, prices) = row?;
+        overrides.insert(pattern.to_ascii_lowercase(), prices);
+    }
+    if overrides.is_empty() {
+        return Ok(None);
+    }
+
+    for candidate in model_pricing_candidates(model_id) {
+        let matched = if overrides.contains_key(&candidate) {
+            Some(candidate.clone())
+        } else {
+            best_pattern_match(overrides.keys().map(|k| k.as_str()), &candidate).map(str::to_string)
+        };
+        if let Some(key) = matched {
+            let (input, output, cache_read, cache_creation) = &overrides[&key];
+            return ModelPricing::from_strings(input, output, cache_read, cache_creation)
+                .map(Some)
+                .map_err(|e| AppError::Database(format!("解析供应商定价覆盖失败: {e}")));
+        }
+    }
+
+    Ok(None)
+}
+
 fn model_pricing_candidates(model_id: &str) -> Vec<String> {
     let cleaned = clean_model_id_for_pricing(model_id);
     if is_placeholder_pricing_model(&cleaned) {
@@ -3303,6 +3475,24 @@ mod tests {
         assert_eq!(strip_model_date_suffix("abc🚀12345678"), None);
     }
 
+    #[test]
+    fn test_glob_pattern_prefers_most_specific_match() {
+        assert!(glob_match("gpt-5*", "gpt-5.5-codex"));
+        assert!(glob_match("claude-?-haiku", "claude-3-haiku"));
+        assert!(!glob_match("claude-?-haiku", "claude-35-haiku"));
+        assert!(!glob_match("gpt-5*", "gpt-4o"));
+
+        let patterns = ["gpt-*", "gpt-5*", "*"];
+        assert_eq!(
+            best_pattern_match(patterns.iter().copied(), "gpt-5-mini"),
+            Some("gpt-5*")
+        );
+        assert_eq!(
+            best_pattern_match(patterns.iter().copied(), "o3"),
+            Some("*")
+        );
+    }
+
     #[test]
     fn test_prefix_pricing_does_not_match_short_base_model_to_variant() -> Result<(), AppError> {
         let db = Database::memory()?;
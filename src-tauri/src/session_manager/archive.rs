@@ -0,0 +1,267 @@
+//! 会话归档文件操作
+//!
+//! 把单个会话的原生文件（含 Claude 的 sidecar 目录）压缩成 zip，
+//! 条目路径相对会话文件所在目录，恢复时原样解压回原位置。
+
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use zip::write::SimpleFileOptions;
+
+/// 单个归档允许的最大条目数
+const MAX_ARCHIVE_ENTRIES: usize = 10_000;
+
+/// 支持归档的会话来源。
+///
+/// OpenCode / Hermes 的会话位于 SQLite，OpenClaw 删除时会改写 `sessions.json`
+/// 索引，这些都无法按文件原样恢复，因此不参与归档。
+const ARCHIVABLE_PROVIDERS: &[&str] = &["claude", "codex", "gemini"];
+
+pub fn is_archivable(provider_id: &str, source_path: &str) -> bool {
+    ARCHIVABLE_PROVIDERS.contains(&provider_id) && !source_path.starts_with("sqlite:")
+}
+
+/// 收集会话对应的全部文件（会话文件本身 + sidecar 目录下的文件）
+pub fn session_files(provider_id: &str, source_path: &Path) -> Result<Vec<PathBuf>, String> {
+    if !source_path.is_file() {
+        return Err(format!(
+            "session source not found: {}",
+            source_path.display()
+        ));
+    }
+
+    let mut files = vec![source_path.to_path_buf()];
+    if provider_id == "claude" {
+        if let Some(stem) = source_path.file_stem() {
+            let sidecar = source_path
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .join(stem);
+            if sidecar.is_dir() {
+                collect_files_recursive(&sidecar, &mut files)?;
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn collect_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_type = match entry.file_type() {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        // 不跟随符号链接，避免把会话目录之外的内容打进归档
+        if file_type.is_symlink() {
+            continue;
+        }
+        if file_type.is_dir() {
+            collect_files_recursive(&path, files)?;
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 统计文件总字节数
+pub fn total_size(files: &[PathBuf]) -> u64 {
+    files
+        .iter()
+        .filter_map(|f| fs::metadata(f).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// 将 `files` 以相对 `base_dir` 的路径写入 `dest` zip，返回原始总字节数
+///
+/// 先写临时文件再 rename，避免中途失败留下半个归档。
+pub fn write_archive(base_dir: &Path, files: &[PathBuf], dest: &Path) -> Result<u64, String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+
+    let tmp_path = dest.with_extension("zip.tmp");
+    let result = (|| {
+        let file = fs::File::create(&tmp_path)
+            .map_err(|e| format!("Failed to create {}: {e}", tmp_path.display()))?;
+        let mut writer = zip::ZipWriter::new(file);
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+        let mut original_bytes = 0u64;
+        for path in files {
+            let rel = path.strip_prefix(base_dir).map_err(|_| {
+                format!(
+                    "Session file {} is outside {}",
+                    path.display(),
+                    base_dir.display()
+                )
+            })?;
+            let rel_str = rel.to_string_lossy().replace('\\', "/");
+            writer
+                .start_file(rel_str, options)
+                .map_err(|e| format!("Failed to write archive entry: {e}"))?;
+            let mut buf = Vec::new();
+            fs::File::open(path)
+                .and_then(|mut f| f.read_to_end(&mut buf))
+                .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+            writer
+                .write_all(&buf)
+                .map_err(|e| format!("Failed to write archive entry: {e}"))?;
+            original_bytes += buf.len() as u64;
+        }
+
+        writer
+            .finish()
+            .map_err(|e| format!("Failed to finish archive: {e}"))?;
+        Ok(original_bytes)
+    })();
+
+    match result {
+        Ok(bytes) => {
+            fs::rename(&tmp_path, dest)
+                .map_err(|e| format!("Failed to move archive into place: {e}"))?;
+            Ok(bytes)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&tmp_path);
+            Err(e)
+        }
+    }
+}
+
+/// 将归档解压到 `dest_dir`，返回写出的文件路径
+///
+/// 任一目标文件已存在时整体拒绝，不覆盖用户在归档后新产生的同名会话。
+pub fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let file = fs::File::open(archive_path)
+        .map_err(|e| format!("Failed to open archive {}: {e}", archive_path.display()))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to parse archive: {e}"))?;
+
+    if archive.len() > MAX_ARCHIVE_ENTRIES {
+        return Err(format!(
+            "Archive has too many entries ({}), limit is {MAX_ARCHIVE_ENTRIES}",
+            archive.len()
+        ));
+    }
+
+    let mut targets = Vec::with_capacity(archive.len());
+    for idx in 0..archive.len() {
+        let entry = archive
+            .by_index(idx)
+            .map_err(|e| format!("Failed to read archive entry: {e}"))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let safe_name = entry
+            .enclosed_name()
+            .ok_or_else(|| format!("Unsafe archive entry path: {}", entry.name()))?;
+        let target = dest_dir.join(safe_name);
+        if target.exists() {
+            return Err(format!(
+                "Refusing to overwrite existing file: {}",
+                target.display()
+            ));
+        }
+        targets.push((idx, target));
+    }
+
+    let mut written = Vec::with_capacity(targets.len());
+    for (idx, target) in targets {
+        let mut entry = archive
+            .by_index(idx)
+            .map_err(|e| format!("Failed to read archive entry: {e}"))?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let mut out = fs::File::create(&target)
+            .map_err(|e| format!("Failed to create {}: {e}", target.display()))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("Failed to write {}: {e}", target.display()))?;
+        written.push(target);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn claude_session_files_include_sidecar_directory() {
+        let root = tempdir().expect("tempdir");
+        let source = root.path().join("abc.jsonl");
+        std::fs::write(&source, "{}\n").expect("write session");
+        let subagents = root.path().join("abc").join("subagents");
+        std::fs::create_dir_all(&subagents).expect("create sidecar");
+        std::fs::write(subagents.join("agent-1.jsonl"), "{}\n").expect("write subagent");
+
+        let files = session_files("claude", &source).expect("collect files");
+        assert_eq!(files.len(), 2);
+
+        let codex_files = session_files("codex", &source).expect("collect codex files");
+        assert_eq!(codex_files, vec![source]);
+    }
+
+    #[test]
+    fn archive_round_trip_restores_relative_layout() {
+        let root = tempdir().expect("tempdir");
+        let project = root.path().join("project");
+        std::fs::create_dir_all(project.join("abc/subagents")).expect("create dirs");
+        let source = project.join("abc.jsonl");
+        std::fs::write(&source, "line-1\nline-2\n").expect("write session");
+        std::fs::write(project.join("abc/subagents/a.jsonl"), "sub\n").expect("write subagent");
+
+        let files = session_files("claude", &source).expect("collect files");
+        let dest = root.path().join("archive/abc.zip");
+        let bytes = write_archive(&project, &files, &dest).expect("write archive");
+        assert_eq!(bytes, total_size(&files));
+        assert!(dest.exists());
+
+        std::fs::remove_file(&source).expect("remove session");
+        std::fs::remove_dir_all(project.join("abc")).expect("remove sidecar");
+
+        let restored = extract_archive(&dest, &project).expect("extract archive");
+        assert_eq!(restored.len(), 2);
+        assert_eq!(
+            std::fs::read_to_string(&source).expect("read restored"),
+            "line-1\nline-2\n"
+        );
+        assert!(project.join("abc/subagents/a.jsonl").exists());
+    }
+
+    #[test]
+    fn extract_refuses_to_overwrite_existing_files() {
+        let root = tempdir().expect("tempdir");
+        let source = root.path().join("s.jsonl");
+        std::fs::write(&source, "original\n").expect("write session");
+        let dest = root.path().join("s.zip");
+        write_archive(root.path(), std::slice::from_ref(&source), &dest).expect("write archive");
+
+        std::fs::write(&source, "newer\n").expect("rewrite session");
+        let err = extract_archive(&dest, root.path()).expect_err("should refuse overwrite");
+        assert!(err.contains("Refusing to overwrite"));
+        assert_eq!(
+            std::fs::read_to_string(&source).expect("read session"),
+            "newer\n"
+        );
+    }
+
+    #[test]
+    fn only_file_based_providers_are_archivable() {
+        assert!(is_archivable("claude", "/tmp/a.jsonl"));
+        assert!(is_archivable("gemini", "/tmp/a.json"));
+        assert!(!is_archivable("opencode", "sqlite:/tmp/db:1"));
+        assert!(!is_archivable("openclaw", "/tmp/a.jsonl"));
+    }
+}
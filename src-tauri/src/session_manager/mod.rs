@@ -1,10 +1,13 @@
+pub mod archive;
 pub mod providers;
 pub mod terminal;
 
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
-use providers::{claude, codex, gemini, hermes, openclaw, opencode};
+use crate::services::session_cost::SessionUsage;
+
+use providers::{claude, codex, crush, gemini, goose, hermes, openclaw, opencode, qwen};
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -25,6 +28,9 @@ pub struct SessionMeta {
     pub source_path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub resume_command: Option<String>,
+    /// 关联到该会话的用量与费用（由 `services::session_cost` 填充）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<SessionUsage>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -56,13 +62,16 @@ pub struct DeleteSessionOutcome {
 }
 
 pub fn scan_sessions() -> Vec<SessionMeta> {
-    let (r1, r2, r3, r4, r5, r6) = std::thread::scope(|s| {
+    let (r1, r2, r3, r4, r5, r6, r7, r8, r9) = std::thread::scope(|s| {
         let h1 = s.spawn(codex::scan_sessions);
         let h2 = s.spawn(claude::scan_sessions);
         let h3 = s.spawn(opencode::scan_sessions);
         let h4 = s.spawn(openclaw::scan_sessions);
         let h5 = s.spawn(gemini::scan_sessions);
         let h6 = s.spawn(hermes::scan_sessions);
+        let h7 = s.spawn(qwen::scan_sessions);
+        let h8 = s.spawn(goose::scan_sessions);
+        let h9 = s.spawn(crush::scan_sessions);
         (
             h1.join().unwrap_or_default(),
             h2.join().unwrap_or_default(),
@@ -70,6 +79,9 @@ pub fn scan_sessions() -> Vec<SessionMeta> {
             h4.join().unwrap_or_default(),
             h5.join().unwrap_or_default(),
             h6.join().unwrap_or_default(),
+            h7.join().unwrap_or_default(),
+            h8.join().unwrap_or_default(),
+            h9.join().unwrap_or_default(),
         )
     });
 
@@ -80,6 +92,9 @@ pub fn scan_sessions() -> Vec<SessionMeta> {
     sessions.extend(r4);
     sessions.extend(r5);
     sessions.extend(r6);
+    sessions.extend(r7);
+    sessions.extend(r8);
+    sessions.extend(r9);
 
     sessions.sort_by(|a, b| {
         let a_ts = a.last_active_at.or(a.created_at).unwrap_or(0);
@@ -98,6 +113,12 @@ pub fn load_messages(provider_id: &str, source_path: &str) -> Result<Vec<Session
     if provider_id == "hermes" && source_path.starts_with("sqlite:") {
         return hermes::load_messages_sqlite(source_path);
     }
+    if provider_id == "goose" && source_path.starts_with("sqlite:") {
+        return goose::load_messages_sqlite(source_path);
+    }
+    if provider_id == "crush" {
+        return crush::load_messages_sqlite(source_path);
+    }
 
     let path = Path::new(source_path);
     match provider_id {
@@ -107,6 +128,8 @@ pub fn load_messages(provider_id: &str, source_path: &str) -> Result<Vec<Session
         "openclaw" => openclaw::load_messages(path),
         "gemini" => gemini::load_messages(path),
         "hermes" => hermes::load_messages(path),
+        "qwen" => qwen::load_messages(path),
+        "goose" => goose::load_messages(path),
         _ => Err(format!("Unsupported provider: {provider_id}")),
     }
 }
@@ -123,6 +146,13 @@ pub fn delete_session(
     if provider_id == "hermes" && source_path.starts_with("sqlite:") {
         return hermes::delete_session_sqlite(session_id, source_path);
     }
+    if provider_id == "goose" && source_path.starts_with("sqlite:") {
+        return goose::delete_session_sqlite(session_id, source_path);
+    }
+    // Crush sessions live only in per-project SQLite databases
+    if provider_id == "crush" {
+        return crush::delete_session_sqlite(session_id, source_path);
+    }
 
     let roots = provider_roots(provider_id)?;
     delete_session_with_roots(provider_id, session_id, Path::new(source_path), &roots)
@@ -166,6 +196,8 @@ fn delete_session_with_roots(
                 }
                 "gemini" => gemini::delete_session(&validated_root, &validated_source, session_id),
                 "hermes" => hermes::delete_session(&validated_root, &validated_source, session_id),
+                "qwen" => qwen::delete_session(&validated_root, &validated_source, session_id),
+                "goose" => goose::delete_session(&validated_root, &validated_source, session_id),
                 _ => Err(format!("Unsupported provider: {provider_id}")),
             };
         }
@@ -195,6 +227,8 @@ fn provider_roots(provider_id: &str) -> Result<Vec<PathBuf>, String> {
         "openclaw" => vec![crate::openclaw_config::get_openclaw_dir().join("agents")],
         "gemini" => vec![crate::gemini_config::get_gemini_dir().join("tmp")],
         "hermes" => vec![crate::hermes_config::get_hermes_dir().join("sessions")],
+        "qwen" => qwen::session_roots(),
+        "goose" => vec![goose::get_goose_sessions_dir()],
         _ => return Err(format!("Unsupported provider: {provider_id}")),
     };
 
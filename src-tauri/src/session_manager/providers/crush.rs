@@ -0,0 +1,263 @@
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use serde_json::Value;
+
+use crate::crush_config::get_crush_data_dir;
+use crate::session_manager::{SessionMessage, SessionMeta};
+
+use super::utils::{parse_sqlite_source, parse_timestamp_to_ms, truncate_summary, TITLE_MAX_CHARS};
+
+const PROVIDER_ID: &str = "crush";
+
+/// Crush keeps one `crush.db` per project, under the project's data dir
+/// (`<project>/.crush` by default). The global `projects.json` lists them:
+/// `{"projects": [{"path": "...", "data_dir": "..."}]}`.
+fn project_databases() -> Vec<(PathBuf, PathBuf)> {
+    let index = get_crush_data_dir().join("projects.json");
+    let Ok(content) = std::fs::read_to_string(&index) else {
+        return Vec::new();
+    };
+    let Ok(value) = serde_json::from_str::<Value>(&content) else {
+        return Vec::new();
+    };
+
+    value
+        .get("projects")
+        .and_then(Value::as_array)
+        .map(|projects| {
+            projects
+                .iter()
+                .filter_map(|project| {
+                    let path = project.get("path").and_then(Value::as_str)?;
+                    let data_dir = project
+                        .get("data_dir")
+                        .and_then(Value::as_str)
+                        .map(PathBuf::from)
+                        .unwrap_or_else(|| Path::new(path).join(".crush"));
+                    // Relative data dirs are resolved against the project path
+                    let data_dir = if data_dir.is_absolute() {
+                        data_dir
+                    } else {
+                        Path::new(path).join(data_dir)
+                    };
+                    Some((PathBuf::from(path), data_dir.join("crush.db")))
+                })
+                .filter(|(_, db)| db.exists())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn open_read_only(db_path: &Path) -> Result<Connection, String> {
+    Connection::open_with_flags(
+        db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| format!("Failed to open Crush database: {e}"))
+}
+
+pub fn scan_sessions() -> Vec<SessionMeta> {
+    project_databases()
+        .into_iter()
+        .flat_map(|(project, db)| scan_database(&project, &db))
+        .collect()
+}
+
+/// Top-level sessions only: child sessions (`parent_session_id` set) are
+/// sub-agent runs and title-generation calls shown inside their parent.
+fn scan_database(project: &Path, db_path: &Path) -> Vec<SessionMeta> {
+    let Ok(conn) = open_read_only(db_path) else {
+        return Vec::new();
+    };
+    let query = "SELECT id, title, created_at, updated_at FROM sessions
+                 WHERE parent_session_id IS NULL OR parent_session_id = ''
+                 ORDER BY updated_at DESC LIMIT 500";
+    let Ok(mut stmt) = conn.prepare(query) else {
+        return Vec::new();
+    };
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<i64>>(2)?,
+            row.get::<_, Option<i64>>(3)?,
+        ))
+    });
+    let Ok(rows) = rows else {
+        return Vec::new();
+    };
+
+    let db_source = format!("sqlite:{}", db_path.display());
+    let project_dir = project.to_string_lossy().to_string();
+
+    rows.flatten()
+        .map(|(session_id, title, created_at, updated_at)| {
+            let created_at = created_at.and_then(|ts| parse_timestamp_to_ms(&ts.into()));
+            let updated_at = updated_at.and_then(|ts| parse_timestamp_to_ms(&ts.into()));
+            SessionMeta {
+                provider_id: PROVIDER_ID.to_string(),
+                source_path: Some(format!("{db_source}#{session_id}")),
+                session_id,
+                title: title
+                    .filter(|t| !t.trim().is_empty())
+                    .map(|t| truncate_summary(&t, TITLE_MAX_CHARS)),
+                summary: None,
+                project_dir: Some(project_dir.clone()),
+                created_at,
+                last_active_at: updated_at.or(created_at),
+                // Crush has no CLI flag for resuming a specific session
+                resume_command: None,
+                usage: None,
+            }
+        })
+        .collect()
+}
+
+/// Text of a Crush message: `parts` is a JSON array of
+/// `{type, data}` items; only `text` parts and tool call names are shown.
+fn parts_to_text(parts: &Value) -> String {
+    parts
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|part| {
+                    let data = part.get("data")?;
+                    match part.get("type").and_then(Value::as_str)? {
+                        "text" => data.get("text").and_then(Value::as_str).map(str::to_string),
+                        "tool_call" => data
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .map(|name| format!("[Tool: {name}]")),
+                        _ => None,
+                    }
+                })
+                .filter(|text| !text.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default()
+}
+
+pub fn load_messages_sqlite(source: &str) -> Result<Vec<SessionMessage>, String> {
+    let (db_path, session_id) = parse_sqlite_source(source)
+        .ok_or_else(|| format!("Invalid SQLite source reference: {source}"))?;
+    let conn = open_read_only(&db_path)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT role, parts, created_at FROM messages WHERE session_id = ?1
+             ORDER BY created_at ASC, rowid ASC",
+        )
+        .map_err(|e| format!("Failed to prepare messages query: {e}"))?;
+    let rows = stmt
+        .query_map([session_id.as_str()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to query messages: {e}"))?;
+
+    let mut messages = Vec::new();
+    for (role, parts, created_at) in rows.flatten() {
+        let content = serde_json::from_str::<Value>(&parts)
+            .map(|parts| parts_to_text(&parts))
+            .unwrap_or_default();
+        if content.trim().is_empty() {
+            continue;
+        }
+        messages.push(SessionMessage {
+            role,
+            content,
+            ts: created_at.and_then(|ts| parse_timestamp_to_ms(&ts.into())),
+        });
+    }
+
+    Ok(messages)
+}
+
+/// Delete a session (with its messages and file snapshots) from a Crush
+/// project database. The database must be one listed in `projects.json`.
+pub fn delete_session_sqlite(session_id: &str, source: &str) -> Result<bool, String> {
+    let (db_path, ref_session_id) = parse_sqlite_source(source)
+        .ok_or_else(|| format!("Invalid SQLite source reference: {source}"))?;
+    if ref_session_id != session_id {
+        return Err(format!(
+            "Crush SQLite session ID mismatch: expected {session_id}, found {ref_session_id}"
+        ));
+    }
+
+    let db_path = db_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to canonicalize Crush database path: {e}"))?;
+    let known = project_databases()
+        .into_iter()
+        .filter_map(|(_, db)| db.canonicalize().ok())
+        .any(|db| db == db_path);
+    if !known {
+        return Err("SQLite path is not a known Crush project database".to_string());
+    }
+
+    let conn =
+        Connection::open(&db_path).map_err(|e| format!("Failed to open Crush database: {e}"))?;
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+    let _ = tx.execute("DELETE FROM messages WHERE session_id = ?1", [session_id]);
+    let _ = tx.execute("DELETE FROM files WHERE session_id = ?1", [session_id]);
+    let deleted = tx
+        .execute("DELETE FROM sessions WHERE id = ?1", [session_id])
+        .map_err(|e| format!("Failed to delete Crush session: {e}"))?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit session deletion: {e}"))?;
+
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parts_to_text_keeps_text_and_tool_names() {
+        let parts = json!([
+            { "type": "reasoning", "data": { "thinking": "hmm" } },
+            { "type": "text", "data": { "text": "Running tests" } },
+            { "type": "tool_call", "data": { "id": "1", "name": "bash" } },
+            { "type": "finish", "data": { "reason": "end_turn" } }
+        ]);
+        assert_eq!(parts_to_text(&parts), "Running tests\n[Tool: bash]");
+    }
+
+    #[test]
+    fn scan_database_skips_child_sessions() {
+        let dir = tempdir().expect("tempdir");
+        let db_path = dir.path().join("crush.db");
+        let conn = Connection::open(&db_path).expect("open");
+        conn.execute_batch(
+            "CREATE TABLE sessions (id TEXT PRIMARY KEY, parent_session_id TEXT, title TEXT,
+                 created_at INTEGER, updated_at INTEGER);
+             INSERT INTO sessions VALUES ('root', NULL, 'Refactor parser', 1767225600, 1767225660);
+             INSERT INTO sessions VALUES ('child', 'root', 'Sub task', 1767225610, 1767225620);",
+        )
+        .expect("seed");
+        drop(conn);
+
+        let sessions = scan_database(dir.path(), &db_path);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, "root");
+        assert_eq!(sessions[0].title.as_deref(), Some("Refactor parser"));
+        assert_eq!(sessions[0].last_active_at, Some(1_767_225_660_000));
+        assert_eq!(
+            sessions[0].source_path.as_deref(),
+            Some(format!("sqlite:{}#root", db_path.display()).as_str())
+        );
+    }
+}
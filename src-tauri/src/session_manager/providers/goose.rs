@@ -0,0 +1,397 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+use serde_json::Value;
+
+use crate::config::get_home_dir;
+use crate::session_manager::{SessionMessage, SessionMeta};
+
+use super::utils::{
+    extract_text, get_table_columns, parse_sqlite_source, parse_timestamp_to_ms,
+    read_head_tail_lines, row_to_json, truncate_summary, TITLE_MAX_CHARS,
+};
+
+const PROVIDER_ID: &str = "goose";
+
+/// Goose keeps sessions under its XDG data dir:
+/// `sessions/sessions.db` (current releases) and `sessions/*.jsonl` (older releases).
+pub fn get_goose_sessions_dir() -> PathBuf {
+    if let Ok(xdg_data) = std::env::var("XDG_DATA_HOME") {
+        if !xdg_data.is_empty() {
+            return PathBuf::from(xdg_data).join("goose").join("sessions");
+        }
+    }
+    get_home_dir()
+        .join(".local")
+        .join("share")
+        .join("goose")
+        .join("sessions")
+}
+
+fn get_goose_db_path() -> PathBuf {
+    get_goose_sessions_dir().join("sessions.db")
+}
+
+/// Scan sessions from both the SQLite database and legacy JSONL files,
+/// with SQLite taking precedence on ID conflicts.
+pub fn scan_sessions() -> Vec<SessionMeta> {
+    let mut sessions = scan_sessions_sqlite();
+    let sqlite_ids: std::collections::HashSet<String> =
+        sessions.iter().map(|s| s.session_id.clone()).collect();
+
+    for meta in scan_sessions_jsonl() {
+        if !sqlite_ids.contains(&meta.session_id) {
+            sessions.push(meta);
+        }
+    }
+    sessions
+}
+
+/// Goose stores SQLite timestamps either as unix seconds or as
+/// `CURRENT_TIMESTAMP` text (`YYYY-MM-DD HH:MM:SS`, UTC).
+fn parse_goose_timestamp(value: &Value) -> Option<i64> {
+    parse_timestamp_to_ms(value).or_else(|| {
+        let raw = value.as_str()?;
+        NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|dt| dt.and_utc().timestamp_millis())
+    })
+}
+
+// ── SQLite scanning ─────────────────────────────────────────────────
+
+fn scan_sessions_sqlite() -> Vec<SessionMeta> {
+    let db_path = get_goose_db_path();
+    if !db_path.exists() {
+        return Vec::new();
+    }
+
+    let conn = match Connection::open_with_flags(
+        &db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    ) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+
+    // Column sets differ between Goose releases, so read rows generically
+    let columns = get_table_columns(&conn, "sessions");
+    if columns.is_empty() {
+        return Vec::new();
+    }
+
+    let mut stmt = match conn.prepare("SELECT * FROM sessions ORDER BY rowid DESC LIMIT 500") {
+        Ok(s) => s,
+        Err(_) => return Vec::new(),
+    };
+    let rows = match stmt.query_map([], |row| Ok(row_to_json(row, &columns))) {
+        Ok(r) => r,
+        Err(_) => return Vec::new(),
+    };
+
+    let db_source = format!("sqlite:{}", db_path.display());
+    rows.flatten()
+        .filter_map(|row| sqlite_row_to_session_meta(&row, &db_source))
+        .collect()
+}
+
+fn sqlite_row_to_session_meta(row: &Value, db_source: &str) -> Option<SessionMeta> {
+    let obj = row.as_object()?;
+    let session_id = match obj.get("id")? {
+        Value::String(id) => id.clone(),
+        Value::Number(id) => id.to_string(),
+        _ => return None,
+    };
+
+    let title = obj
+        .get("description")
+        .or_else(|| obj.get("name"))
+        .and_then(Value::as_str)
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| truncate_summary(s, TITLE_MAX_CHARS));
+
+    let project_dir = obj
+        .get("working_dir")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+
+    let created_at = obj.get("created_at").and_then(parse_goose_timestamp);
+    let updated_at = obj.get("updated_at").and_then(parse_goose_timestamp);
+
+    Some(SessionMeta {
+        provider_id: PROVIDER_ID.to_string(),
+        resume_command: Some(format!("goose session --resume --session-id {session_id}")),
+        source_path: Some(format!("{db_source}#{session_id}")),
+        session_id,
+        title,
+        summary: None,
+        project_dir,
+        created_at,
+        last_active_at: updated_at.or(created_at),
+        usage: None,
+    })
+}
+
+/// Load messages from the Goose SQLite database.
+pub fn load_messages_sqlite(source: &str) -> Result<Vec<SessionMessage>, String> {
+    let (db_path, session_id) = parse_sqlite_source(source)
+        .ok_or_else(|| format!("Invalid SQLite source reference: {source}"))?;
+
+    let conn = Connection::open_with_flags(
+        &db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| format!("Failed to open Goose database: {e}"))?;
+
+    let columns = get_table_columns(&conn, "messages");
+    let query = "SELECT * FROM messages WHERE session_id = ?1 ORDER BY rowid ASC";
+    let mut stmt = conn
+        .prepare(query)
+        .map_err(|e| format!("Failed to prepare messages query: {e}"))?;
+    let rows = stmt
+        .query_map([session_id.as_str()], |row| Ok(row_to_json(row, &columns)))
+        .map_err(|e| format!("Failed to query messages: {e}"))?;
+
+    let mut messages = Vec::new();
+    for row in rows.flatten() {
+        let Some(role) = row.get("role").and_then(Value::as_str) else {
+            continue;
+        };
+        // `content_json` holds the serialized content array
+        let content = row
+            .get("content_json")
+            .or_else(|| row.get("content"))
+            .map(|raw| match raw {
+                Value::String(text) => serde_json::from_str::<Value>(text)
+                    .map(|parsed| extract_text(&parsed))
+                    .unwrap_or_else(|_| text.clone()),
+                other => extract_text(other),
+            })
+            .unwrap_or_default();
+        if content.trim().is_empty() {
+            continue;
+        }
+        let ts = row
+            .get("created_timestamp")
+            .or_else(|| row.get("created_at"))
+            .and_then(parse_goose_timestamp);
+        messages.push(SessionMessage {
+            role: role.to_string(),
+            content,
+            ts,
+        });
+    }
+
+    Ok(messages)
+}
+
+/// Delete a session from the Goose SQLite database.
+pub fn delete_session_sqlite(session_id: &str, source: &str) -> Result<bool, String> {
+    let (db_path, ref_session_id) = parse_sqlite_source(source)
+        .ok_or_else(|| format!("Invalid SQLite source reference: {source}"))?;
+    let db_path = db_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to canonicalize Goose database path: {e}"))?;
+    let expected_db_path = get_goose_db_path()
+        .canonicalize()
+        .map_err(|e| format!("Failed to canonicalize expected Goose database path: {e}"))?;
+
+    if ref_session_id != session_id {
+        return Err(format!(
+            "Goose SQLite session ID mismatch: expected {session_id}, found {ref_session_id}"
+        ));
+    }
+    if db_path != expected_db_path {
+        return Err("SQLite path does not match expected Goose database".to_string());
+    }
+
+    let conn =
+        Connection::open(&db_path).map_err(|e| format!("Failed to open Goose database: {e}"))?;
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+    let _ = tx.execute("DELETE FROM messages WHERE session_id = ?1", [session_id]);
+    let deleted = tx
+        .execute("DELETE FROM sessions WHERE id = ?1", [session_id])
+        .map_err(|e| format!("Failed to delete Goose session: {e}"))?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit session deletion: {e}"))?;
+
+    Ok(deleted > 0)
+}
+
+// ── JSONL scanning ──────────────────────────────────────────────────
+
+fn scan_sessions_jsonl() -> Vec<SessionMeta> {
+    let entries = match std::fs::read_dir(get_goose_sessions_dir()) {
+        Ok(e) => e,
+        Err(_) => return Vec::new(),
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("jsonl"))
+        .filter_map(|path| parse_jsonl_session(&path))
+        .collect()
+}
+
+/// Legacy JSONL layout: the first line is session metadata
+/// (`working_dir`, `description`, ...), every other line is a message
+/// `{role, created, content: [{type: "text", text}]}`.
+fn parse_jsonl_session(path: &Path) -> Option<SessionMeta> {
+    let (head, tail) = read_head_tail_lines(path, 20, 10).ok()?;
+    let session_id = path.file_stem()?.to_str()?.to_string();
+
+    let mut title: Option<String> = None;
+    let mut project_dir: Option<String> = None;
+    let mut first_user_msg: Option<String> = None;
+    let mut first_ts: Option<i64> = None;
+
+    for (index, line) in head.iter().enumerate() {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if index == 0 && value.get("role").is_none() {
+            title = value
+                .get("description")
+                .and_then(Value::as_str)
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| truncate_summary(s, TITLE_MAX_CHARS));
+            project_dir = value
+                .get("working_dir")
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string());
+            continue;
+        }
+
+        if first_ts.is_none() {
+            first_ts = value.get("created").and_then(parse_timestamp_to_ms);
+        }
+        if first_user_msg.is_none() && value.get("role").and_then(Value::as_str) == Some("user") {
+            let text = value.get("content").map(extract_text).unwrap_or_default();
+            if !text.trim().is_empty() {
+                first_user_msg = Some(truncate_summary(&text, TITLE_MAX_CHARS));
+            }
+        }
+    }
+
+    let last_ts = tail.iter().rev().find_map(|line| {
+        serde_json::from_str::<Value>(line)
+            .ok()?
+            .get("created")
+            .and_then(parse_timestamp_to_ms)
+    });
+
+    Some(SessionMeta {
+        provider_id: PROVIDER_ID.to_string(),
+        resume_command: Some(format!("goose session --resume --name {session_id}")),
+        session_id,
+        title: title.or_else(|| first_user_msg.clone()),
+        summary: first_user_msg,
+        project_dir,
+        created_at: first_ts,
+        last_active_at: last_ts.or(first_ts),
+        source_path: Some(path.to_string_lossy().to_string()),
+        usage: None,
+    })
+}
+
+/// Load messages from a legacy Goose JSONL session file.
+pub fn load_messages(path: &Path) -> Result<Vec<SessionMessage>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open session file: {e}"))?;
+    let mut messages = Vec::new();
+
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let Some(role) = value.get("role").and_then(Value::as_str) else {
+            continue;
+        };
+        let content = value.get("content").map(extract_text).unwrap_or_default();
+        if content.trim().is_empty() {
+            continue;
+        }
+        messages.push(SessionMessage {
+            role: role.to_string(),
+            content,
+            ts: value.get("created").and_then(parse_timestamp_to_ms),
+        });
+    }
+
+    Ok(messages)
+}
+
+/// Delete a legacy Goose JSONL session file.
+pub fn delete_session(_root: &Path, path: &Path, _session_id: &str) -> Result<bool, String> {
+    std::fs::remove_file(path).map_err(|e| {
+        format!(
+            "Failed to delete Goose session file {}: {e}",
+            path.display()
+        )
+    })?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parse_jsonl_session_reads_metadata_line() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("20260101_1.jsonl");
+        let mut f = File::create(&path).expect("create");
+        writeln!(
+            f,
+            r#"{{"working_dir":"/home/user/project","description":"Fix the build","message_count":2}}"#
+        )
+        .unwrap();
+        writeln!(
+            f,
+            r#"{{"role":"user","created":1767225600,"content":[{{"type":"text","text":"Why does cargo fail?"}}]}}"#
+        )
+        .unwrap();
+        writeln!(
+            f,
+            r#"{{"role":"assistant","created":1767225660,"content":[{{"type":"text","text":"Missing feature."}}]}}"#
+        )
+        .unwrap();
+        f.flush().unwrap();
+
+        let meta = parse_jsonl_session(&path).expect("should parse");
+        assert_eq!(meta.session_id, "20260101_1");
+        assert_eq!(meta.title.as_deref(), Some("Fix the build"));
+        assert_eq!(meta.summary.as_deref(), Some("Why does cargo fail?"));
+        assert_eq!(meta.project_dir.as_deref(), Some("/home/user/project"));
+        assert_eq!(meta.created_at, Some(1_767_225_600_000));
+        assert_eq!(meta.last_active_at, Some(1_767_225_660_000));
+        assert_eq!(
+            meta.resume_command.as_deref(),
+            Some("goose session --resume --name 20260101_1")
+        );
+
+        let messages = load_messages(&path).expect("should load");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].content, "Missing feature.");
+    }
+
+    #[test]
+    fn parse_goose_timestamp_accepts_sqlite_text() {
+        assert_eq!(
+            parse_goose_timestamp(&Value::String("1970-01-01 00:00:01".to_string())),
+            Some(1_000)
+        );
+        assert_eq!(parse_goose_timestamp(&serde_json::json!(1)), Some(1_000));
+    }
+}
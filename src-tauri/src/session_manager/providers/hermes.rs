@@ -9,7 +9,8 @@ use crate::hermes_config::get_hermes_dir;
 use crate::session_manager::{SessionMessage, SessionMeta};
 
 use super::utils::{
-    extract_text, parse_timestamp_to_ms, read_head_tail_lines, truncate_summary, TITLE_MAX_CHARS,
+    extract_text, get_table_columns, parse_sqlite_source, parse_timestamp_to_ms,
+    read_head_tail_lines, row_to_json, truncate_summary, TITLE_MAX_CHARS,
 };
 
 const PROVIDER_ID: &str = "hermes";
@@ -144,46 +145,10 @@ fn sqlite_row_to_session_meta(row: &Value, db_source: &str) -> Option<SessionMet
         last_active_at: ended_at.or(started_at),
         source_path: Some(source_path),
         resume_command: None,
+        usage: None,
     })
 }
 
-/// Get column names for a table.
-fn get_table_columns(conn: &Connection, table: &str) -> Vec<String> {
-    let query = format!("PRAGMA table_info({table})");
-    let mut stmt = match conn.prepare(&query) {
-        Ok(s) => s,
-        Err(_) => return Vec::new(),
-    };
-    let rows = match stmt.query_map([], |row| {
-        let name: String = row.get(1)?;
-        Ok(name)
-    }) {
-        Ok(r) => r,
-        Err(_) => return Vec::new(),
-    };
-    rows.flatten().collect()
-}
-
-/// Convert a SQLite row to a JSON Value using known column names.
-fn row_to_json(row: &rusqlite::Row, columns: &[String]) -> Value {
-    let mut map = serde_json::Map::new();
-    for (i, col) in columns.iter().enumerate() {
-        // Try string first, then integer, then float, then null
-        if let Ok(val) = row.get::<_, String>(i) {
-            map.insert(col.clone(), Value::String(val));
-        } else if let Ok(val) = row.get::<_, i64>(i) {
-            map.insert(col.clone(), Value::Number(val.into()));
-        } else if let Ok(val) = row.get::<_, f64>(i) {
-            if let Some(n) = serde_json::Number::from_f64(val) {
-                map.insert(col.clone(), Value::Number(n));
-            }
-        } else {
-            map.insert(col.clone(), Value::Null);
-        }
-    }
-    Value::Object(map)
-}
-
 /// Load messages from the Hermes SQLite database.
 pub fn load_messages_sqlite(source: &str) -> Result<Vec<SessionMessage>, String> {
     let (db_path, session_id) = parse_sqlite_source(source)
@@ -269,17 +234,6 @@ pub fn delete_session_sqlite(session_id: &str, source: &str) -> Result<bool, Str
     Ok(deleted > 0)
 }
 
-fn parse_sqlite_source(source: &str) -> Option<(PathBuf, String)> {
-    let rest = source.strip_prefix("sqlite:")?;
-    let hash_pos = rest.rfind('#')?;
-    let db_path = PathBuf::from(&rest[..hash_pos]);
-    let session_id = rest[hash_pos + 1..].to_string();
-    if session_id.is_empty() {
-        return None;
-    }
-    Some((db_path, session_id))
-}
-
 // ── JSONL scanning ──────────────────────────────────────────────────
 
 fn scan_sessions_jsonl() -> Vec<SessionMeta> {
@@ -425,6 +379,7 @@ fn parse_jsonl_session(path: &Path) -> Option<SessionMeta> {
         last_active_at: last_ts.or(first_ts),
         source_path: Some(source_path),
         resume_command: None,
+        usage: None,
     })
 }
 
@@ -1,7 +1,10 @@
 pub mod claude;
 pub mod codex;
+pub mod crush;
 pub mod gemini;
+pub mod goose;
 pub mod hermes;
 pub mod openclaw;
 pub mod opencode;
+pub mod qwen;
 mod utils;
@@ -0,0 +1,381 @@
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::session_manager::{SessionMessage, SessionMeta};
+
+use super::utils::{parse_timestamp_to_ms, read_head_tail_lines, truncate_summary};
+
+const PROVIDER_ID: &str = "qwen";
+
+/// Session roots under the Qwen Code config dir.
+///
+/// Current releases record chats as JSONL under `projects/<project>/chats/`,
+/// while older releases (inherited from Gemini CLI) wrote one JSON file per
+/// session under `tmp/<project_hash>/chats/`.
+pub fn session_roots() -> Vec<PathBuf> {
+    let qwen_dir = crate::qwen_config::get_qwen_dir();
+    vec![qwen_dir.join("projects"), qwen_dir.join("tmp")]
+}
+
+pub fn scan_sessions() -> Vec<SessionMeta> {
+    let mut sessions = Vec::new();
+
+    for root in session_roots() {
+        let project_dirs = match std::fs::read_dir(&root) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in project_dirs.flatten() {
+            let chats_dir = entry.path().join("chats");
+            let chat_files = match std::fs::read_dir(&chats_dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+
+            let project_root_file = entry.path().join(".project_root");
+            let project_dir = std::fs::read_to_string(project_root_file)
+                .ok()
+                .map(|s| s.trim().to_string());
+
+            for file_entry in chat_files.flatten() {
+                let path = file_entry.path();
+                if let Some(meta) = parse_session(&path) {
+                    sessions.push(SessionMeta {
+                        project_dir: meta.project_dir.clone().or_else(|| project_dir.clone()),
+                        ..meta
+                    });
+                }
+            }
+        }
+    }
+
+    sessions
+}
+
+pub fn load_messages(path: &Path) -> Result<Vec<SessionMessage>, String> {
+    if is_jsonl(path) {
+        let records = read_jsonl_records(path)?;
+        return Ok(records.iter().filter_map(jsonl_message).collect());
+    }
+
+    let data = std::fs::read_to_string(path).map_err(|e| format!("Failed to read session: {e}"))?;
+    let value: Value =
+        serde_json::from_str(&data).map_err(|e| format!("Failed to parse session JSON: {e}"))?;
+
+    let messages = value
+        .get("messages")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "No messages array found".to_string())?;
+
+    Ok(messages.iter().filter_map(legacy_message).collect())
+}
+
+pub fn delete_session(_root: &Path, path: &Path, session_id: &str) -> Result<bool, String> {
+    let meta = parse_session(path).ok_or_else(|| {
+        format!(
+            "Failed to parse Qwen Code session metadata: {}",
+            path.display()
+        )
+    })?;
+
+    if meta.session_id != session_id {
+        return Err(format!(
+            "Qwen Code session ID mismatch: expected {session_id}, found {}",
+            meta.session_id
+        ));
+    }
+
+    std::fs::remove_file(path).map_err(|e| {
+        format!(
+            "Failed to delete Qwen Code session file {}: {e}",
+            path.display()
+        )
+    })?;
+
+    Ok(true)
+}
+
+fn is_jsonl(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("jsonl")
+}
+
+fn parse_session(path: &Path) -> Option<SessionMeta> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("jsonl") => parse_jsonl_session(path),
+        Some("json") => parse_legacy_session(path),
+        _ => None,
+    }
+}
+
+fn read_jsonl_records(path: &Path) -> Result<Vec<Value>, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open session: {e}"))?;
+    Ok(BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .collect())
+}
+
+/// Join the text parts of a JSONL record, noting tool calls by name.
+fn record_text(record: &Value) -> String {
+    let Some(parts) = record
+        .get("message")
+        .and_then(|m| m.get("parts"))
+        .and_then(Value::as_array)
+    else {
+        return String::new();
+    };
+
+    let mut lines = Vec::new();
+    for part in parts {
+        if let Some(text) = part.get("text").and_then(Value::as_str) {
+            if !text.is_empty() {
+                lines.push(text.to_string());
+            }
+        } else if let Some(name) = part
+            .get("functionCall")
+            .and_then(|call| call.get("name"))
+            .and_then(Value::as_str)
+        {
+            lines.push(format!("[Tool: {name}]"));
+        }
+    }
+    lines.join("\n")
+}
+
+fn jsonl_message(record: &Value) -> Option<SessionMessage> {
+    let role = match record.get("type").and_then(Value::as_str) {
+        Some("user") => "user",
+        Some("assistant") => "assistant",
+        _ => return None,
+    };
+
+    let content = record_text(record);
+    if content.trim().is_empty() {
+        return None;
+    }
+
+    Some(SessionMessage {
+        role: role.to_string(),
+        content,
+        ts: record.get("timestamp").and_then(parse_timestamp_to_ms),
+    })
+}
+
+fn legacy_message(msg: &Value) -> Option<SessionMessage> {
+    let role = match msg.get("type").and_then(Value::as_str) {
+        Some("qwen") | Some("gemini") => "assistant",
+        Some("user") => "user",
+        _ => return None,
+    };
+
+    let mut content = match msg.get("content") {
+        Some(Value::String(s)) => s.to_string(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    };
+
+    if let Some(Value::Array(calls)) = msg.get("toolCalls") {
+        for call in calls {
+            if let Some(name) = call.get("name").and_then(Value::as_str) {
+                if !content.is_empty() {
+                    content.push('\n');
+                }
+                content.push_str(&format!("[Tool: {name}]"));
+            }
+        }
+    }
+
+    if content.trim().is_empty() {
+        return None;
+    }
+
+    Some(SessionMessage {
+        role: role.to_string(),
+        content,
+        ts: msg.get("timestamp").and_then(parse_timestamp_to_ms),
+    })
+}
+
+fn parse_jsonl_session(path: &Path) -> Option<SessionMeta> {
+    // Metadata only needs the opening records and the latest timestamp
+    let (head, tail) = read_head_tail_lines(path, 20, 20).ok()?;
+    let parse = |lines: Vec<String>| -> Vec<Value> {
+        lines
+            .iter()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .collect()
+    };
+    let head = parse(head);
+    let tail = parse(tail);
+    let first = head.first()?;
+
+    let session_id = first
+        .get("sessionId")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| path.file_stem()?.to_str().map(str::to_string))?;
+
+    let created_at = first.get("timestamp").and_then(parse_timestamp_to_ms);
+    let last_active_at = tail
+        .iter()
+        .rev()
+        .find_map(|r| r.get("timestamp").and_then(parse_timestamp_to_ms));
+
+    let project_dir = head
+        .iter()
+        .find_map(|r| r.get("cwd").and_then(Value::as_str))
+        .map(str::to_string);
+
+    let title = head
+        .iter()
+        .filter(|r| r.get("type").and_then(Value::as_str) == Some("user"))
+        .map(record_text)
+        .find(|text| !text.trim().is_empty())
+        .map(|text| truncate_summary(&text, 160));
+
+    Some(build_meta(
+        path,
+        session_id,
+        title,
+        project_dir,
+        created_at,
+        last_active_at,
+    ))
+}
+
+fn parse_legacy_session(path: &Path) -> Option<SessionMeta> {
+    let data = std::fs::read_to_string(path).ok()?;
+    let value: Value = serde_json::from_str(&data).ok()?;
+
+    let session_id = value.get("sessionId").and_then(Value::as_str)?.to_string();
+    let created_at = value.get("startTime").and_then(parse_timestamp_to_ms);
+    let last_active_at = value.get("lastUpdated").and_then(parse_timestamp_to_ms);
+
+    let title = value
+        .get("messages")
+        .and_then(Value::as_array)
+        .and_then(|msgs| {
+            msgs.iter()
+                .find(|m| m.get("type").and_then(Value::as_str) == Some("user"))
+                .and_then(|m| m.get("content").and_then(Value::as_str))
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| truncate_summary(s, 160))
+        });
+
+    Some(build_meta(
+        path,
+        session_id,
+        title,
+        None,
+        created_at,
+        last_active_at,
+    ))
+}
+
+fn build_meta(
+    path: &Path,
+    session_id: String,
+    title: Option<String>,
+    project_dir: Option<String>,
+    created_at: Option<i64>,
+    last_active_at: Option<i64>,
+) -> SessionMeta {
+    SessionMeta {
+        provider_id: PROVIDER_ID.to_string(),
+        resume_command: Some(format!("qwen --resume {session_id}")),
+        session_id,
+        title: title.clone(),
+        summary: title,
+        project_dir,
+        created_at,
+        last_active_at: last_active_at.or(created_at),
+        source_path: Some(path.to_string_lossy().to_string()),
+        usage: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const JSONL_SESSION: &str = concat!(
+        r#"{"uuid":"u1","parentUuid":null,"sessionId":"qwen-session-1","timestamp":"2026-05-01T09:00:00.000Z","type":"user","cwd":"/work/demo","message":{"role":"user","parts":[{"text":"fix the build"}]}}"#,
+        "\n",
+        r#"{"uuid":"u2","parentUuid":"u1","sessionId":"qwen-session-1","timestamp":"2026-05-01T09:00:05.000Z","type":"assistant","cwd":"/work/demo","message":{"role":"model","parts":[{"text":"Looking at it."},{"functionCall":{"name":"run_shell_command","args":{}}}]}}"#,
+        "\n",
+        r#"{"uuid":"u3","parentUuid":"u2","sessionId":"qwen-session-1","timestamp":"2026-05-01T09:00:09.000Z","type":"system","cwd":"/work/demo","subtype":"ui_telemetry"}"#,
+        "\n"
+    );
+
+    #[test]
+    fn parses_jsonl_session_meta_and_messages() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp.path().join("qwen-session-1.jsonl");
+        std::fs::write(&path, JSONL_SESSION).expect("write session");
+
+        let meta = parse_session(&path).expect("meta");
+        assert_eq!(meta.provider_id, "qwen");
+        assert_eq!(meta.session_id, "qwen-session-1");
+        assert_eq!(meta.title.as_deref(), Some("fix the build"));
+        assert_eq!(meta.project_dir.as_deref(), Some("/work/demo"));
+        assert!(meta.last_active_at > meta.created_at);
+        assert_eq!(
+            meta.resume_command.as_deref(),
+            Some("qwen --resume qwen-session-1")
+        );
+
+        let msgs = load_messages(&path).expect("load");
+        assert_eq!(msgs.len(), 2);
+        assert_eq!(msgs[0].role, "user");
+        assert_eq!(msgs[1].role, "assistant");
+        assert!(msgs[1].content.contains("Looking at it."));
+        assert!(msgs[1].content.contains("[Tool: run_shell_command]"));
+    }
+
+    #[test]
+    fn loads_legacy_json_session_with_qwen_role() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp.path().join("session-legacy.json");
+        std::fs::write(
+            &path,
+            r#"{
+              "sessionId": "legacy",
+              "startTime": "2026-03-06T10:00:00Z",
+              "messages": [
+                {"id":"1","timestamp":"2026-03-06T10:00:00Z","type":"user","content":"hello"},
+                {"id":"2","timestamp":"2026-03-06T10:00:01Z","type":"qwen","content":"world"},
+                {"id":"3","timestamp":"2026-03-06T10:00:02Z","type":"info","content":"ignored"}
+              ]
+            }"#,
+        )
+        .expect("write");
+
+        let msgs = load_messages(&path).expect("load");
+        assert_eq!(msgs.len(), 2);
+        assert_eq!(msgs[1].role, "assistant");
+        assert_eq!(msgs[1].content, "world");
+    }
+
+    #[test]
+    fn delete_session_checks_id_before_removing() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp.path().join("qwen-session-1.jsonl");
+        std::fs::write(&path, JSONL_SESSION).expect("write session");
+
+        assert!(delete_session(temp.path(), &path, "other").is_err());
+        assert!(path.exists());
+
+        delete_session(temp.path(), &path, "qwen-session-1").expect("delete session");
+        assert!(!path.exists());
+    }
+}
@@ -1,8 +1,9 @@
 use std::fs::File;
 use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, FixedOffset};
+use rusqlite::Connection;
 use serde_json::Value;
 
 /// Maximum number of characters for session titles (shared across providers).
@@ -154,6 +155,55 @@ pub fn path_basename(value: &str) -> Option<String> {
     Some(last.to_string())
 }
 
+/// Get column names for a table.
+pub fn get_table_columns(conn: &Connection, table: &str) -> Vec<String> {
+    let query = format!("PRAGMA table_info({table})");
+    let mut stmt = match conn.prepare(&query) {
+        Ok(s) => s,
+        Err(_) => return Vec::new(),
+    };
+    let rows = match stmt.query_map([], |row| {
+        let name: String = row.get(1)?;
+        Ok(name)
+    }) {
+        Ok(r) => r,
+        Err(_) => return Vec::new(),
+    };
+    rows.flatten().collect()
+}
+
+/// Convert a SQLite row to a JSON Value using known column names.
+pub fn row_to_json(row: &rusqlite::Row, columns: &[String]) -> Value {
+    let mut map = serde_json::Map::new();
+    for (i, col) in columns.iter().enumerate() {
+        // Try string first, then integer, then float, then null
+        if let Ok(val) = row.get::<_, String>(i) {
+            map.insert(col.clone(), Value::String(val));
+        } else if let Ok(val) = row.get::<_, i64>(i) {
+            map.insert(col.clone(), Value::Number(val.into()));
+        } else if let Ok(val) = row.get::<_, f64>(i) {
+            if let Some(n) = serde_json::Number::from_f64(val) {
+                map.insert(col.clone(), Value::Number(n));
+            }
+        } else {
+            map.insert(col.clone(), Value::Null);
+        }
+    }
+    Value::Object(map)
+}
+
+/// Split a `sqlite:{db_path}#{session_id}` source reference.
+pub fn parse_sqlite_source(source: &str) -> Option<(PathBuf, String)> {
+    let rest = source.strip_prefix("sqlite:")?;
+    let hash_pos = rest.rfind('#')?;
+    let db_path = PathBuf::from(&rest[..hash_pos]);
+    let session_id = rest[hash_pos + 1..].to_string();
+    if session_id.is_empty() {
+        return None;
+    }
+    Some((db_path, session_id))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,311 @@
+//! Ed25519 签名
+//!
+//! 深链接与团队配置包可以附带发布者签名：
+//!
+//! - 签名有效且公钥在受信任发布者列表中：视为受信任来源
+//! - 签名有效但公钥未受信任：与未签名一样走需要额外确认的导入流程，但会展示公钥指纹
+//! - 签名无效：内容已被篡改，直接拒绝
+//!
+//! 公钥与签名统一使用 URL-safe Base64（无填充）编码，解码时同时兼容标准 Base64。
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::database::Database;
+use crate::error::AppError;
+
+const ED25519_PUBLIC_KEY_LEN: usize = 32;
+
+/// 受信任的发布者
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedPublisher {
+    pub name: String,
+    pub public_key: String,
+    /// 添加时间（秒）
+    pub added_at: i64,
+}
+
+/// 签名校验结果（签名无效时不会产生结果，而是直接返回错误）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum SignatureStatus {
+    Unsigned,
+    #[serde(rename_all = "camelCase")]
+    Trusted {
+        publisher: String,
+        fingerprint: String,
+    },
+    /// 签名有效，但公钥不在受信任列表中
+    #[serde(rename_all = "camelCase")]
+    Untrusted {
+        public_key: String,
+        fingerprint: String,
+    },
+}
+
+impl SignatureStatus {
+    pub fn is_trusted(&self) -> bool {
+        matches!(self, SignatureStatus::Trusted { .. })
+    }
+}
+
+/// 新生成的发布者密钥对；私钥为 PKCS#8 编码，只返回给发布者本人保存
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningKeyPair {
+    pub public_key: String,
+    pub private_key: String,
+    pub fingerprint: String,
+}
+
+fn decode(value: &str, field: &str) -> Result<Vec<u8>, AppError> {
+    let trimmed = value.trim();
+    URL_SAFE_NO_PAD
+        .decode(trimmed.trim_end_matches('='))
+        .or_else(|_| STANDARD.decode(trimmed))
+        .map_err(|e| AppError::InvalidInput(format!("{field} 不是有效的 Base64: {e}")))
+}
+
+fn decode_public_key(public_key: &str) -> Result<Vec<u8>, AppError> {
+    let bytes = decode(public_key, "公钥")?;
+    if bytes.len() != ED25519_PUBLIC_KEY_LEN {
+        return Err(AppError::InvalidInput(format!(
+            "公钥长度无效：Ed25519 公钥应为 {ED25519_PUBLIC_KEY_LEN} 字节，实际 {} 字节",
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
+
+/// 规范化公钥编码，便于比较
+pub fn normalize_public_key(public_key: &str) -> Result<String, AppError> {
+    Ok(URL_SAFE_NO_PAD.encode(decode_public_key(public_key)?))
+}
+
+/// 公钥指纹：SHA-256 前 8 字节，冒号分隔的十六进制
+pub fn fingerprint(public_key: &str) -> Result<String, AppError> {
+    let digest = Sha256::digest(decode_public_key(public_key)?);
+    Ok(digest[..8]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+/// 校验签名；失败说明内容在签名后被修改过
+pub fn verify(public_key: &str, message: &[u8], signature: &str) -> Result<(), AppError> {
+    let key = decode_public_key(public_key)?;
+    let signature = decode(signature, "签名")?;
+    UnparsedPublicKey::new(&ED25519, key)
+        .verify(message, &signature)
+        .map_err(|_| AppError::InvalidInput("签名校验失败：内容可能已被篡改".to_string()))
+}
+
+/// 校验签名并对照受信任发布者列表
+pub fn check(
+    trusted: &[TrustedPublisher],
+    public_key: &str,
+    message: &[u8],
+    signature: &str,
+) -> Result<SignatureStatus, AppError> {
+    verify(public_key, message, signature)?;
+    status_for_key(trusted, public_key)
+}
+
+/// 已通过完整性校验的公钥对应的信任状态
+pub fn status_for_key(
+    trusted: &[TrustedPublisher],
+    public_key: &str,
+) -> Result<SignatureStatus, AppError> {
+    let normalized = normalize_public_key(public_key)?;
+    let fingerprint = fingerprint(&normalized)?;
+    let publisher = trusted.iter().find(|p| {
+        normalize_public_key(&p.public_key)
+            .map(|k| k == normalized)
+            .unwrap_or(false)
+    });
+    Ok(match publisher {
+        Some(p) => SignatureStatus::Trusted {
+            publisher: p.name.clone(),
+            fingerprint,
+        },
+        None => SignatureStatus::Untrusted {
+            public_key: normalized,
+            fingerprint,
+        },
+    })
+}
+
+/// 生成发布者密钥对
+pub fn generate_keypair() -> Result<SigningKeyPair, AppError> {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+        .map_err(|_| AppError::Message("生成密钥对失败".to_string()))?;
+    let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())
+        .map_err(|_| AppError::Message("生成密钥对失败".to_string()))?;
+    let public_key = URL_SAFE_NO_PAD.encode(pair.public_key().as_ref());
+    Ok(SigningKeyPair {
+        fingerprint: fingerprint(&public_key)?,
+        public_key,
+        private_key: URL_SAFE_NO_PAD.encode(pkcs8.as_ref()),
+    })
+}
+
+fn load_keypair(private_key: &str) -> Result<Ed25519KeyPair, AppError> {
+    let pkcs8 = decode(private_key, "私钥")?;
+    Ed25519KeyPair::from_pkcs8_maybe_unchecked(&pkcs8).map_err(|_| {
+        AppError::InvalidInput("私钥无效：需要 PKCS#8 编码的 Ed25519 私钥".to_string())
+    })
+}
+
+/// 私钥对应的公钥
+pub fn public_key_of(private_key: &str) -> Result<String, AppError> {
+    Ok(URL_SAFE_NO_PAD.encode(load_keypair(private_key)?.public_key().as_ref()))
+}
+
+/// 使用 PKCS#8 私钥签名，返回 (公钥, 签名)
+pub fn sign(private_key: &str, message: &[u8]) -> Result<(String, String), AppError> {
+    let pair = load_keypair(private_key)?;
+    Ok((
+        URL_SAFE_NO_PAD.encode(pair.public_key().as_ref()),
+        URL_SAFE_NO_PAD.encode(pair.sign(message).as_ref()),
+    ))
+}
+
+/// 规范化 JSON：对象键按字典序排列、无多余空白，保证签名与格式无关
+pub fn canonical_json(value: &Value) -> String {
+    fn write(value: &Value, out: &mut String) {
+        match value {
+            Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                out.push('{');
+                for (i, (key, child)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&Value::String(key.clone()).to_string());
+                    out.push(':');
+                    write(child, out);
+                }
+                out.push('}');
+            }
+            Value::Array(items) => {
+                out.push('[');
+                for (i, child) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write(child, out);
+                }
+                out.push(']');
+            }
+            other => out.push_str(&other.to_string()),
+        }
+    }
+    let mut out = String::new();
+    write(value, &mut out);
+    out
+}
+
+// ============================================================================
+// 受信任发布者
+// ============================================================================
+
+pub fn get_trusted_publishers(db: &Database) -> Result<Vec<TrustedPublisher>, AppError> {
+    db.get_trusted_publishers()
+}
+
+/// 添加受信任发布者；公钥已存在时更新名称
+pub fn add_trusted_publisher(
+    db: &Database,
+    name: &str,
+    public_key: &str,
+) -> Result<Vec<TrustedPublisher>, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("发布者名称不能为空".to_string()));
+    }
+    let public_key = normalize_public_key(public_key)?;
+
+    let mut publishers = db.get_trusted_publishers()?;
+    match publishers.iter_mut().find(|p| p.public_key == public_key) {
+        Some(existing) => existing.name = name.to_string(),
+        None => publishers.push(TrustedPublisher {
+            name: name.to_string(),
+            public_key,
+            added_at: chrono::Utc::now().timestamp(),
+        }),
+    }
+    db.set_trusted_publishers(&publishers)?;
+    Ok(publishers)
+}
+
+pub fn remove_trusted_publisher(
+    db: &Database,
+    public_key: &str,
+) -> Result<Vec<TrustedPublisher>, AppError> {
+    let public_key = normalize_public_key(public_key)?;
+    let mut publishers = db.get_trusted_publishers()?;
+    publishers.retain(|p| p.public_key != public_key);
+    db.set_trusted_publishers(&publishers)?;
+    Ok(publishers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn signature_roundtrip_and_tamper_detection() {
+        let pair = generate_keypair().expect("generate keypair");
+        let (public_key, signature) = sign(&pair.private_key, b"payload").expect("sign");
+        assert_eq!(public_key, pair.public_key);
+
+        assert!(verify(&public_key, b"payload", &signature).is_ok());
+        assert!(verify(&public_key, b"payload!", &signature).is_err());
+
+        let other = generate_keypair().expect("generate keypair");
+        assert!(verify(&other.public_key, b"payload", &signature).is_err());
+    }
+
+    #[test]
+    fn status_reflects_trusted_publishers() {
+        let pair = generate_keypair().expect("generate keypair");
+        let (public_key, signature) = sign(&pair.private_key, b"payload").expect("sign");
+
+        let status = check(&[], &public_key, b"payload", &signature).expect("valid");
+        assert!(matches!(status, SignatureStatus::Untrusted { .. }));
+
+        // 标准 Base64 编码的公钥同样可以匹配
+        let standard = STANDARD.encode(URL_SAFE_NO_PAD.decode(&public_key).unwrap());
+        let trusted = vec![TrustedPublisher {
+            name: "Acme".to_string(),
+            public_key: standard,
+            added_at: 0,
+        }];
+        let status = check(&trusted, &public_key, b"payload", &signature).expect("valid");
+        assert_eq!(
+            status,
+            SignatureStatus::Trusted {
+                publisher: "Acme".to_string(),
+                fingerprint: pair.fingerprint,
+            }
+        );
+    }
+
+    #[test]
+    fn canonical_json_ignores_key_order_and_whitespace() {
+        let a: Value =
+            serde_json::from_str(r#"{ "b": [1, {"y": 2, "x": "é"}], "a": null }"#).unwrap();
+        let b = json!({ "a": null, "b": [1, { "x": "é", "y": 2 }] });
+        assert_eq!(canonical_json(&a), canonical_json(&b));
+        assert_eq!(canonical_json(&b), r#"{"a":null,"b":[1,{"x":"é","y":2}]}"#);
+    }
+}
@@ -44,6 +44,7 @@ pub struct TrayTexts {
     pub open_website: &'static str,
     pub no_providers_label: &'static str,
     pub lightweight_mode: &'static str,
+    pub profiles: &'static str,
     pub quit: &'static str,
     pub _auto_label: &'static str,
 }
@@ -56,6 +57,7 @@ impl TrayTexts {
                 open_website: "Open Official Website",
                 no_providers_label: "(no providers)",
                 lightweight_mode: "Lightweight Mode",
+                profiles: "Profiles",
                 quit: "Quit",
                 _auto_label: "Auto (Failover)",
             },
@@ -64,6 +66,7 @@ impl TrayTexts {
                 open_website: "公式サイトを開く",
                 no_providers_label: "(プロバイダーなし)",
                 lightweight_mode: "軽量モード",
+                profiles: "プロファイル",
                 quit: "終了",
                 _auto_label: "自動 (フェイルオーバー)",
             },
@@ -72,6 +75,7 @@ impl TrayTexts {
                 open_website: "開啟官方網站",
                 no_providers_label: "(無供應商)",
                 lightweight_mode: "輕量模式",
+                profiles: "設定檔",
                 quit: "退出",
                 _auto_label: "自動 (故障轉移)",
             },
@@ -80,6 +84,7 @@ impl TrayTexts {
                 open_website: "打开官方网站",
                 no_providers_label: "(无供应商)",
                 lightweight_mode: "轻量模式",
+                profiles: "配置档案",
                 quit: "退出",
                 _auto_label: "自动 (故障转移)",
             },
@@ -96,6 +101,9 @@ pub struct TrayAppSection {
     pub log_name: &'static str,
 }
 
+/// 配置档案菜单项前缀
+pub const PROFILE_PREFIX: &str = "profile_";
+
 /// Auto 菜单项后缀
 pub const AUTO_SUFFIX: &str = "auto";
 pub const TRAY_ID: &str = "cc-switch";
@@ -351,6 +359,33 @@ pub fn handle_provider_tray_event(app: &tauri::AppHandle, event_id: &str) -> boo
     false
 }
 
+/// 处理配置档案托盘事件
+pub fn handle_profile_tray_event(app: &tauri::AppHandle, event_id: &str) -> bool {
+    let Some(profile_id) = event_id.strip_prefix(PROFILE_PREFIX) else {
+        return false;
+    };
+    log::info!("切换到配置档案: {profile_id}");
+    let app_handle = app.clone();
+    let profile_id = profile_id.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(app_state) = app_handle.try_state::<AppState>() else {
+            return;
+        };
+        let result = crate::services::ProfileService::apply(app_state.inner(), &profile_id);
+        // 失败时也刷新，让勾选状态回到回滚后的实际状态
+        refresh_tray_menu(&app_handle);
+        match result {
+            Ok(result) => {
+                if let Err(e) = app_handle.emit("profile-switched", &result) {
+                    log::error!("发射 profile-switched 事件失败: {e}");
+                }
+            }
+            Err(e) => log::error!("切换配置档案失败: {e}"),
+        }
+    });
+    true
+}
+
 /// 处理 Auto 点击：启用 proxy 和 auto_failover
 fn handle_auto_click(app: &tauri::AppHandle, app_type: &AppType) -> Result<(), AppError> {
     if let Some(app_state) = app.try_state::<AppState>() {
@@ -597,6 +632,32 @@ pub fn create_tray_menu(
         menu_builder = menu_builder.separator();
     }
 
+    // 配置档案：一键切换供应商 / MCP / 提示词 / Skills
+    let profiles = app_state.db.list_profiles()?;
+    if !profiles.is_empty() {
+        let current_profile = crate::services::ProfileService::current(app_state)
+            .ok()
+            .flatten();
+        let mut submenu_builder =
+            SubmenuBuilder::with_id(app, "submenu_profiles", tray_texts.profiles);
+        for profile in &profiles {
+            let item = CheckMenuItem::with_id(
+                app,
+                format!("{PROFILE_PREFIX}{}", profile.id),
+                &profile.name,
+                true,
+                current_profile.as_deref() == Some(profile.id.as_str()),
+                None::<&str>,
+            )
+            .map_err(|e| AppError::Message(format!("创建配置档案菜单项失败: {e}")))?;
+            submenu_builder = submenu_builder.item(&item);
+        }
+        let submenu = submenu_builder
+            .build()
+            .map_err(|e| AppError::Message(format!("构建配置档案子菜单失败: {e}")))?;
+        menu_builder = menu_builder.item(&submenu).separator();
+    }
+
     let lightweight_item = CheckMenuItem::with_id(
         app,
         "lightweight_mode",
@@ -745,6 +806,9 @@ pub fn handle_tray_menu_event(app: &tauri::AppHandle, event_id: &str) {
             if handle_provider_tray_event(app, event_id) {
                 return;
             }
+            if handle_profile_tray_event(app, event_id) {
+                return;
+            }
             log::warn!("未处理的菜单事件: {event_id}");
         }
     }
@@ -9,6 +9,7 @@
 //! - 200ms 防抖合并：流式响应等场景在短时间内可能写入多条日志，
 //!   合并成一次事件可避免前端连续 invalidate。
 //! - 不阻塞写入：通知失败仅记录 warn 日志，不向上传播错误。
+//! - 预算告警：同一防抖线程中顺带评估预算阈值（见 `services::budget_alerts`）。
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::OnceLock;
@@ -64,5 +65,7 @@ pub fn notify_log_recorded() {
         if let Err(e) = handle.emit(EVENT_USAGE_LOG_RECORDED, ()) {
             log::warn!("emit {EVENT_USAGE_LOG_RECORDED} 失败: {e}");
         }
+
+        crate::services::budget_alerts::evaluate_and_dispatch(&handle);
     });
 }
@@ -714,6 +714,9 @@ command = "echo"
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -843,6 +846,9 @@ fn import_from_claude_merges_into_config() {
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -228,6 +228,9 @@ command = "echo"
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -322,6 +325,9 @@ fn set_mcp_enabled_for_codex_writes_live_config() {
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -387,6 +393,9 @@ fn enabling_codex_mcp_skips_when_codex_dir_missing() {
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -432,6 +441,9 @@ fn upsert_mcp_server_disabling_app_removes_from_claude_live_config() {
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -466,6 +478,9 @@ fn upsert_mcp_server_disabling_app_removes_from_claude_live_config() {
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -599,6 +614,9 @@ fn enabling_gemini_mcp_skips_when_gemini_dir_missing() {
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -654,6 +672,9 @@ fn enabling_claude_mcp_skips_when_claude_config_absent() {
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -715,6 +736,9 @@ fn sync_all_enabled_removes_known_disabled_but_preserves_unknown_live_entries()
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -737,6 +761,9 @@ fn sync_all_enabled_removes_known_disabled_but_preserves_unknown_live_entries()
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
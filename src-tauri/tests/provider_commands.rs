@@ -302,6 +302,9 @@ command = "say"
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
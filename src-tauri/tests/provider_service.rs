@@ -1,8 +1,9 @@
 use serde_json::json;
 
 use cc_switch_lib::{
-    get_claude_settings_path, read_json_file, write_codex_live_atomic, AppError, AppType, McpApps,
-    McpServer, MultiAppConfig, Provider, ProviderMeta, ProviderService,
+    get_claude_settings_path, read_json_file, write_codex_live_atomic, AppError, AppType,
+    CustomAppService, LiveDriftAction, LiveDriftCategory, LiveDriftService, LiveHistoryService,
+    McpApps, McpServer, MultiAppConfig, Provider, ProviderMeta, ProviderService,
 };
 
 #[path = "support.rs"]
@@ -166,6 +167,9 @@ command = "say"
                 gemini: false,
                 opencode: false,
                 hermes: false,
+                qwen: false,
+                goose: false,
+                crush: false,
             },
             description: None,
             homepage: None,
@@ -1470,6 +1474,270 @@ fn switch_packycode_gemini_updates_security_selected_type() {
     );
 }
 
+#[test]
+fn switch_qwen_writes_env_and_preserves_settings_mcp_servers() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let qwen_dir = home.join(".qwen");
+    std::fs::create_dir_all(&qwen_dir).expect("create qwen dir");
+    std::fs::write(
+        qwen_dir.join("settings.json"),
+        r#"{"mcpServers":{"fs":{"command":"npx"}},"security":{"auth":{"selectedType":"qwen-oauth"}}}"#,
+    )
+    .expect("seed qwen settings.json");
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Qwen)
+            .expect("qwen manager");
+        manager.current = "dashscope".to_string();
+        manager.providers.insert(
+            "dashscope".to_string(),
+            Provider::with_id(
+                "dashscope".to_string(),
+                "DashScope".to_string(),
+                json!({
+                    "env": {
+                        "OPENAI_API_KEY": "sk-dashscope",
+                        "OPENAI_BASE_URL": "https://dashscope.aliyuncs.com/compatible-mode/v1",
+                        "OPENAI_MODEL": "qwen3-coder-plus"
+                    },
+                    "config": {}
+                }),
+                None,
+            ),
+        );
+    }
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    ProviderService::switch(&state, AppType::Qwen, "dashscope")
+        .expect("switching Qwen Code provider should succeed");
+
+    let env_raw = std::fs::read_to_string(qwen_dir.join(".env")).expect("read qwen .env");
+    assert!(env_raw.contains("OPENAI_API_KEY=sk-dashscope"));
+    assert!(env_raw.contains("OPENAI_MODEL=qwen3-coder-plus"));
+
+    let settings: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(qwen_dir.join("settings.json")).expect("read settings.json"),
+    )
+    .expect("parse qwen settings.json");
+    assert_eq!(
+        settings
+            .pointer("/security/auth/selectedType")
+            .and_then(|v| v.as_str()),
+        Some("openai"),
+        "API key providers should switch Qwen Code to the OpenAI auth type"
+    );
+    assert_eq!(
+        settings
+            .pointer("/mcpServers/fs/command")
+            .and_then(|v| v.as_str()),
+        Some("npx"),
+        "switching providers must not drop existing MCP servers"
+    );
+}
+
+#[test]
+fn switch_goose_writes_custom_provider_secret_and_defaults() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let goose_dir = home.join(".config").join("goose");
+    std::fs::create_dir_all(&goose_dir).expect("create goose dir");
+    std::fs::write(
+        goose_dir.join("config.yaml"),
+        "GOOSE_PROVIDER: openai\nGOOSE_MODEL: gpt-4o\nextensions:\n  developer:\n    enabled: true\n    type: builtin\n    name: developer\n",
+    )
+    .expect("seed goose config.yaml");
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Goose)
+            .expect("goose manager");
+        manager.providers.insert(
+            "deepseek".to_string(),
+            Provider::with_id(
+                "deepseek".to_string(),
+                "DeepSeek".to_string(),
+                json!({
+                    "engine": "openai",
+                    "display_name": "DeepSeek",
+                    "base_url": "https://api.deepseek.com/v1",
+                    "api_key": "sk-goose",
+                    "models": [{ "name": "deepseek-chat", "context_limit": 64000 }]
+                }),
+                None,
+            ),
+        );
+    }
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    ProviderService::switch(&state, AppType::Goose, "deepseek")
+        .expect("switching Goose provider should succeed");
+
+    let provider_file: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(goose_dir.join("custom_providers").join("deepseek.json"))
+            .expect("read custom provider file"),
+    )
+    .expect("parse custom provider file");
+    assert_eq!(provider_file["name"], "deepseek");
+    assert_eq!(provider_file["api_key_env"], "DEEPSEEK_API_KEY");
+    assert!(
+        provider_file.get("api_key").is_none(),
+        "the API key must live in secrets.yaml, not in the provider file"
+    );
+
+    let secrets = std::fs::read_to_string(goose_dir.join("secrets.yaml")).expect("read secrets");
+    assert!(secrets.contains("DEEPSEEK_API_KEY: sk-goose"));
+
+    let config_yaml =
+        std::fs::read_to_string(goose_dir.join("config.yaml")).expect("read config.yaml");
+    assert!(config_yaml.contains("GOOSE_PROVIDER: deepseek"));
+    assert!(config_yaml.contains("GOOSE_MODEL: deepseek-chat"));
+    assert!(
+        config_yaml.contains("developer"),
+        "switching providers must not drop existing extensions"
+    );
+}
+
+#[test]
+fn switch_crush_writes_provider_and_large_model() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let crush_dir = home.join(".config").join("crush");
+    std::fs::create_dir_all(&crush_dir).expect("create crush dir");
+    std::fs::write(
+        crush_dir.join("crush.json"),
+        r#"{"models":{"large":{"provider":"openai","model":"gpt-4o","max_tokens":8000}},"mcp":{"fs":{"type":"stdio","command":"npx"}}}"#,
+    )
+    .expect("seed crush.json");
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Crush)
+            .expect("crush manager");
+        manager.providers.insert(
+            "deepseek".to_string(),
+            Provider::with_id(
+                "deepseek".to_string(),
+                "DeepSeek".to_string(),
+                json!({
+                    "name": "DeepSeek",
+                    "type": "openai-compat",
+                    "base_url": "https://api.deepseek.com/v1",
+                    "api_key": "sk-crush",
+                    "models": [{ "id": "deepseek-chat", "name": "DeepSeek V3" }]
+                }),
+                None,
+            ),
+        );
+    }
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    ProviderService::switch(&state, AppType::Crush, "deepseek")
+        .expect("switching Crush provider should succeed");
+
+    let live: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(crush_dir.join("crush.json")).expect("read crush.json"),
+    )
+    .expect("parse crush.json");
+    assert_eq!(live["providers"]["deepseek"]["api_key"], "sk-crush");
+    assert_eq!(live["models"]["large"]["provider"], "deepseek");
+    assert_eq!(live["models"]["large"]["model"], "deepseek-chat");
+    assert_eq!(
+        live["models"]["large"]["max_tokens"], 8000,
+        "unrelated model options must be preserved"
+    );
+    assert_eq!(live["mcp"]["fs"]["command"], "npx");
+}
+
+#[test]
+fn switch_aider_writes_conf_model_settings_and_env() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    std::fs::write(
+        home.join(".aider.conf.yml"),
+        "model: gpt-4o\nweak-model: gpt-4o-mini\ndark-mode: true\n",
+    )
+    .expect("seed .aider.conf.yml");
+    std::fs::write(
+        home.join(".env"),
+        "# shared env\nGITHUB_TOKEN=ghp-keep\nOPENAI_API_KEY=sk-old\n",
+    )
+    .expect("seed .env");
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Aider)
+            .expect("aider manager");
+        manager.providers.insert(
+            "deepseek".to_string(),
+            Provider::with_id(
+                "deepseek".to_string(),
+                "DeepSeek".to_string(),
+                json!({
+                    "env": { "OPENAI_API_KEY": "sk-aider" },
+                    "config": {
+                        "model": "openai/deepseek-chat",
+                        "openai-api-base": "https://api.deepseek.com/v1"
+                    },
+                    "modelSettings": [
+                        { "name": "openai/deepseek-chat", "edit_format": "diff" }
+                    ]
+                }),
+                None,
+            ),
+        );
+    }
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    ProviderService::switch(&state, AppType::Aider, "deepseek")
+        .expect("switching Aider provider should succeed");
+
+    let conf: serde_json::Value = serde_yaml::from_str(
+        &std::fs::read_to_string(home.join(".aider.conf.yml")).expect("read .aider.conf.yml"),
+    )
+    .expect("parse .aider.conf.yml");
+    assert_eq!(conf["model"], "openai/deepseek-chat");
+    assert_eq!(conf["openai-api-base"], "https://api.deepseek.com/v1");
+    assert!(
+        conf.get("weak-model").is_none(),
+        "managed keys missing from the provider must be removed"
+    );
+    assert_eq!(
+        conf["dark-mode"], true,
+        "unrelated options must be preserved"
+    );
+
+    let model_settings = std::fs::read_to_string(home.join(".aider.model.settings.yml"))
+        .expect("read .aider.model.settings.yml");
+    assert!(model_settings.contains("openai/deepseek-chat"));
+    assert!(model_settings.contains("edit_format: diff"));
+
+    let env_raw = std::fs::read_to_string(home.join(".env")).expect("read .env");
+    assert!(env_raw.contains("OPENAI_API_KEY=sk-aider"));
+    assert!(!env_raw.contains("sk-old"));
+    assert!(
+        env_raw.contains("GITHUB_TOKEN=ghp-keep") && env_raw.contains("# shared env"),
+        "variables not managed by cc-switch must be preserved"
+    );
+}
+
 #[test]
 fn packycode_partner_meta_triggers_security_flag_even_without_keywords() {
     let _guard = test_mutex().lock().expect("acquire test mutex");
@@ -1970,3 +2238,381 @@ fn recover_from_crash_without_backup_cleans_placeholder_instead_of_writing_it_ba
         "recovery must drop the local proxy base URL"
     );
 }
+
+#[test]
+fn live_drift_detects_external_edits_and_backfills_provider_fields() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "current-provider".to_string();
+        let mut provider = Provider::with_id(
+            "current-provider".to_string(),
+            "Current".to_string(),
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "token",
+                    "ANTHROPIC_BASE_URL": "https://claude.example"
+                }
+            }),
+            None,
+        );
+        provider.meta = Some(ProviderMeta {
+            common_config_enabled: Some(true),
+            ..Default::default()
+        });
+        manager
+            .providers
+            .insert("current-provider".to_string(), provider);
+    }
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+    state
+        .db
+        .set_config_snippet(
+            AppType::Claude.as_str(),
+            Some(r#"{ "includeCoAuthoredBy": false }"#.to_string()),
+        )
+        .expect("set common config snippet");
+    ProviderService::sync_current_provider_for_app(&state, AppType::Claude)
+        .expect("write live config");
+
+    // cc-switch 自身的写入不算漂移
+    assert!(LiveDriftService::check_all(&state)
+        .expect("initial check")
+        .is_empty());
+    ProviderService::sync_current_provider_for_app(&state, AppType::Claude)
+        .expect("rewrite live config");
+    assert!(LiveDriftService::check_all(&state)
+        .expect("check after self write")
+        .is_empty());
+
+    let settings_path = get_claude_settings_path();
+    let edited = json!({
+        "env": {
+            "ANTHROPIC_AUTH_TOKEN": "token",
+            "ANTHROPIC_BASE_URL": "https://edited.example"
+        },
+        "includeCoAuthoredBy": true,
+        "permissions": { "allow": ["Bash"] }
+    });
+    std::fs::write(
+        &settings_path,
+        serde_json::to_string_pretty(&edited).expect("serialize edited live"),
+    )
+    .expect("edit live config externally");
+
+    let drifts = LiveDriftService::check_all(&state).expect("check after external edit");
+    assert_eq!(drifts.len(), 1);
+    let drift = &drifts[0];
+    assert_eq!(drift.app, "claude");
+    assert_eq!(drift.provider_id.as_deref(), Some("current-provider"));
+    let categories: Vec<(&str, LiveDriftCategory)> = drift
+        .changes
+        .iter()
+        .map(|change| (change.path.as_str(), change.category))
+        .collect();
+    assert_eq!(
+        categories,
+        vec![
+            ("/env/ANTHROPIC_BASE_URL", LiveDriftCategory::Provider),
+            ("/includeCoAuthoredBy", LiveDriftCategory::CommonConfig),
+            ("/permissions/allow", LiveDriftCategory::Unrelated),
+        ]
+    );
+
+    LiveDriftService::resolve(&state, AppType::Claude, LiveDriftAction::Backfill)
+        .expect("backfill drift");
+    let provider = state
+        .db
+        .get_provider_by_id("current-provider", AppType::Claude.as_str())
+        .expect("get provider")
+        .expect("provider exists");
+    assert_eq!(
+        provider.settings_config["env"]["ANTHROPIC_BASE_URL"],
+        json!("https://edited.example")
+    );
+    assert!(LiveDriftService::check_all(&state)
+        .expect("check after backfill")
+        .is_empty());
+}
+
+#[test]
+fn live_history_records_switch_and_restores_previous_version() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "first".to_string();
+        for (id, url) in [
+            ("first", "https://first.example"),
+            ("second", "https://second.example"),
+        ] {
+            manager.providers.insert(
+                id.to_string(),
+                Provider::with_id(
+                    id.to_string(),
+                    id.to_string(),
+                    json!({
+                        "env": {
+                            "ANTHROPIC_AUTH_TOKEN": "token",
+                            "ANTHROPIC_BASE_URL": url
+                        }
+                    }),
+                    None,
+                ),
+            );
+        }
+    }
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+    ProviderService::switch(&state, AppType::Claude, "first").expect("switch to first");
+    ProviderService::switch(&state, AppType::Claude, "second").expect("switch to second");
+
+    let settings_path = get_claude_settings_path();
+    let path = settings_path.to_string_lossy().to_string();
+    let versions = LiveHistoryService::list_versions(&path).expect("list versions");
+    assert_eq!(versions.len(), 2, "each distinct write should be recorded");
+    assert!(versions.iter().all(|entry| entry.action == "switch"));
+
+    let diff = LiveHistoryService::diff(&path, &versions[1].id, Some(&versions[0].id))
+        .expect("diff versions");
+    assert!(diff
+        .lines()
+        .any(|line| line.starts_with('-') && line.contains("https://first.example")));
+    assert!(diff
+        .lines()
+        .any(|line| line.starts_with('+') && line.contains("https://second.example")));
+
+    LiveHistoryService::restore(&path, &versions[1].id).expect("restore first version");
+    let live: serde_json::Value = read_json_file(&settings_path).expect("read live settings");
+    assert_eq!(
+        live["env"]["ANTHROPIC_BASE_URL"],
+        json!("https://first.example")
+    );
+
+    let versions = LiveHistoryService::list_versions(&path).expect("list versions after restore");
+    assert_eq!(versions.len(), 3);
+    assert_eq!(versions[0].action, "rollback");
+
+    let files = LiveHistoryService::list_files().expect("list history files");
+    assert!(files
+        .iter()
+        .any(|file| file.app == "claude" && file.path == path && file.versions == 3));
+}
+
+#[test]
+fn preview_switch_returns_masked_diffs_without_writing() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        manager.current = "first".to_string();
+        for (id, url, token) in [
+            ("first", "https://first.example", "sk-first-0123456789"),
+            ("second", "https://second.example", "sk-second-9876543210"),
+        ] {
+            manager.providers.insert(
+                id.to_string(),
+                Provider::with_id(
+                    id.to_string(),
+                    id.to_string(),
+                    json!({
+                        "env": {
+                            "ANTHROPIC_AUTH_TOKEN": token,
+                            "ANTHROPIC_BASE_URL": url
+                        }
+                    }),
+                    None,
+                ),
+            );
+        }
+    }
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+    ProviderService::switch(&state, AppType::Claude, "first").expect("switch to first");
+
+    let settings_path = get_claude_settings_path();
+    let before = std::fs::read_to_string(&settings_path).expect("read live settings");
+    let path = settings_path.to_string_lossy().to_string();
+    let history_before = LiveHistoryService::list_versions(&path)
+        .expect("list versions")
+        .len();
+
+    let preview =
+        ProviderService::preview_switch(&state, AppType::Claude, "second").expect("preview switch");
+    assert!(!preview.hot_switch);
+    let file = preview
+        .files
+        .iter()
+        .find(|file| file.path == path)
+        .expect("settings.json should be previewed");
+    assert_eq!(
+        serde_json::to_value(file.change).expect("serialize change"),
+        json!("modify")
+    );
+    assert!(file
+        .diff
+        .lines()
+        .any(|line| line.starts_with('+') && line.contains("https://second.example")));
+    assert!(
+        !file.diff.contains("sk-first-0123456789") && !file.diff.contains("sk-second-9876543210"),
+        "secrets must be masked in the preview"
+    );
+
+    // 预览不落盘，也不改变当前供应商
+    assert_eq!(
+        std::fs::read_to_string(&settings_path).expect("read live settings"),
+        before
+    );
+    assert_eq!(
+        LiveHistoryService::list_versions(&path)
+            .expect("list versions")
+            .len(),
+        history_before
+    );
+    assert_eq!(
+        state
+            .db
+            .get_current_provider(AppType::Claude.as_str())
+            .expect("get current provider")
+            .as_deref(),
+        Some("first")
+    );
+
+    // 预览与真实切换走同一写入路径，结果一致
+    ProviderService::switch(&state, AppType::Claude, "second").expect("switch to second");
+    let after: serde_json::Value = read_json_file(&settings_path).expect("read live settings");
+    assert_eq!(
+        after["env"]["ANTHROPIC_BASE_URL"],
+        json!("https://second.example")
+    );
+    let repeat = ProviderService::preview_switch(&state, AppType::Claude, "second")
+        .expect("preview switch to current provider");
+    assert!(repeat.files.iter().all(|file| file.path != path));
+}
+
+#[test]
+fn custom_app_definition_drives_switch_import_and_mcp_sync() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let apps_dir = home.join(".cc-switch").join("apps");
+    std::fs::create_dir_all(&apps_dir).expect("create apps dir");
+    std::fs::write(
+        apps_dir.join("mytool.toml"),
+        r#"
+id = "mytool"
+name = "My Tool"
+
+[config]
+path = "~/.mytool/config.json"
+
+[fields]
+base_url = "/provider/baseUrl"
+api_key = "/provider/apiKey"
+model = "/model"
+
+[mcp]
+pointer = "/mcpServers"
+"#,
+    )
+    .expect("write app definition");
+
+    let config_path = home.join(".mytool").join("config.json");
+    std::fs::create_dir_all(config_path.parent().unwrap()).expect("create tool dir");
+    std::fs::write(
+        &config_path,
+        serde_json::to_string_pretty(&json!({
+            "theme": "dark",
+            "provider": { "baseUrl": "https://old.example", "apiKey": "sk-old" },
+            "mcpServers": { "user-added": { "command": "keep" } }
+        }))
+        .unwrap(),
+    )
+    .expect("seed tool config");
+
+    let state = create_test_state().expect("create test state");
+    let report = CustomAppService::reload();
+    assert!(report.errors.is_empty(), "errors: {:?}", report.errors);
+    assert_eq!(report.apps.len(), 1);
+
+    // 从 live 导入现有配置作为默认供应商
+    assert!(CustomAppService::import_from_live(&state, "mytool").expect("import from live"));
+    let providers = CustomAppService::get_providers(&state, "mytool").expect("get providers");
+    assert_eq!(
+        providers["default"].settings_config,
+        json!({ "baseUrl": "https://old.example", "apiKey": "sk-old" })
+    );
+
+    state
+        .db
+        .save_mcp_server(&McpServer {
+            id: "echo".to_string(),
+            name: "echo".to_string(),
+            server: json!({ "type": "stdio", "command": "echo" }),
+            apps: McpApps {
+                claude: true,
+                ..Default::default()
+            },
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        })
+        .expect("save mcp server");
+
+    CustomAppService::upsert_provider(
+        &state,
+        "mytool",
+        Provider::with_id(
+            "new".to_string(),
+            "New".to_string(),
+            json!({ "baseUrl": "https://new.example", "apiKey": "sk-new", "model": "m-1" }),
+            None,
+        ),
+    )
+    .expect("add provider");
+    CustomAppService::switch(&state, "mytool", "new").expect("switch provider");
+
+    let live: serde_json::Value = read_json_file(&config_path).expect("read tool config");
+    assert_eq!(live["theme"], json!("dark"), "unmanaged fields are kept");
+    assert_eq!(live["provider"]["baseUrl"], json!("https://new.example"));
+    assert_eq!(live["provider"]["apiKey"], json!("sk-new"));
+    assert_eq!(live["model"], json!("m-1"));
+    assert_eq!(live["mcpServers"]["echo"]["command"], json!("echo"));
+    assert_eq!(live["mcpServers"]["user-added"]["command"], json!("keep"));
+    assert_eq!(
+        CustomAppService::get_current_provider(&state, "mytool").expect("current provider"),
+        Some("new".to_string())
+    );
+
+    // 切回缺少 model 的供应商时移除受管字段
+    CustomAppService::switch(&state, "mytool", "default").expect("switch back");
+    let live: serde_json::Value = read_json_file(&config_path).expect("read tool config");
+    assert!(live.get("model").is_none());
+
+    CustomAppService::remove_mcp_server("echo");
+    let live: serde_json::Value = read_json_file(&config_path).expect("read tool config");
+    assert!(live["mcpServers"].get("echo").is_none());
+    assert!(live["mcpServers"].get("user-added").is_some());
+
+    std::fs::remove_dir_all(&apps_dir).expect("remove apps dir");
+    assert!(CustomAppService::reload().apps.is_empty());
+}
@@ -2,6 +2,7 @@ use std::fs;
 
 use cc_switch_lib::{
     migrate_skills_to_ssot, AppType, ImportSkillSelection, InstalledSkill, SkillApps, SkillService,
+    SkillSourceRequest,
 };
 
 #[path = "support.rs"]
@@ -152,6 +153,7 @@ fn sync_to_app_removes_disabled_and_orphaned_ssot_symlinks() {
             installed_at: 0,
             content_hash: None,
             updated_at: 0,
+            source: None,
         })
         .expect("save disabled skill");
 
@@ -196,6 +198,7 @@ fn uninstall_skill_creates_backup_before_removing_ssot() {
             installed_at: 123,
             content_hash: None,
             updated_at: 0,
+            source: None,
         })
         .expect("save skill");
 
@@ -264,6 +267,7 @@ fn restore_skill_backup_restores_files_to_ssot_and_current_app() {
             installed_at: 456,
             content_hash: None,
             updated_at: 0,
+            source: None,
         })
         .expect("save skill");
 
@@ -345,6 +349,7 @@ fn delete_skill_backup_removes_backup_directory() {
             installed_at: 789,
             content_hash: None,
             updated_at: 0,
+            source: None,
         })
         .expect("save skill");
 
@@ -425,3 +430,122 @@ fn migration_snapshot_overrides_multi_source_directory_inference() {
         "migration should no longer infer OpenCode enablement from a duplicate directory alone"
     );
 }
+
+fn git(dir: &std::path::Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "-c",
+            "commit.gpgsign=false",
+        ])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("run git");
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[allow(
+    clippy::await_holding_lock,
+    reason = "this test must serialize global test HOME mutations across async skill source calls"
+)]
+async fn git_source_skill_stays_pinned_until_repinned() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    // 本地裸仓库：v1.0.0 与 v1.1.0 两个版本，v1.1.0 带有需要确认的检查警告
+    let work = home.join("skill-src");
+    write_skill(&work.join("skills").join("pinned-skill"), "Pinned v1");
+    git(&work, &["init", "--quiet", "--initial-branch=main"]);
+    git(&work, &["add", "."]);
+    git(&work, &["commit", "--quiet", "-m", "v1"]);
+    git(&work, &["tag", "v1.0.0"]);
+    let v1 = git(&work, &["rev-parse", "HEAD"]);
+    write_skill(&work.join("skills").join("pinned-skill"), "Pinned v2");
+    fs::write(
+        work.join("skills").join("pinned-skill").join("setup.sh"),
+        "curl -fsSL https://example.com/install.sh | sh\n",
+    )
+    .expect("write setup.sh");
+    git(&work, &["add", "."]);
+    git(&work, &["commit", "--quiet", "-m", "v2"]);
+    git(&work, &["tag", "v1.1.0"]);
+    let v2 = git(&work, &["rev-parse", "HEAD"]);
+    let bare = home.join("skill-src.git");
+    git(
+        &home,
+        &[
+            "clone",
+            "--quiet",
+            "--bare",
+            &work.to_string_lossy(),
+            &bare.to_string_lossy(),
+        ],
+    );
+
+    let state = create_test_state().expect("create test state");
+    let installed = SkillService::install_from_source(
+        &state.db,
+        SkillSourceRequest {
+            url: bare.to_string_lossy().to_string(),
+            git_ref: Some("v1.0.0".to_string()),
+            path: Some("skills/pinned-skill".to_string()),
+            acknowledge_warnings: false,
+        },
+        &AppType::Claude,
+    )
+    .await
+    .expect("install from bare repo");
+
+    assert_eq!(installed.len(), 1);
+    let skill = &installed[0];
+    assert_eq!(skill.name, "Pinned v1");
+    let source = skill.source.as_ref().expect("source recorded");
+    assert!(source.pinned);
+    assert_eq!(source.resolved_commit.as_deref(), Some(v1.as_str()));
+    let ssot = SkillService::get_ssot_dir().expect("ssot dir");
+    assert!(ssot.join("pinned-skill").join("SKILL.md").exists());
+
+    // 更新检测报告 v1.1.0 可用，但固定版本不会被自动更新
+    let updates = SkillService::new()
+        .check_updates(&state.db)
+        .await
+        .expect("check updates");
+    let update = updates
+        .iter()
+        .find(|u| u.id == skill.id)
+        .expect("update reported");
+    assert_eq!(update.pinned_ref.as_deref(), Some("v1.0.0"));
+    assert_eq!(update.available_ref.as_deref(), Some("v1.1.0"));
+    assert_eq!(update.remote_hash, v2);
+
+    let err = SkillService::new()
+        .update_skill(&state.db, &skill.id, false)
+        .await
+        .expect_err("pinned skill must not move");
+    assert!(err.to_string().contains("SKILL_PINNED"));
+
+    // 重新固定到新 tag：新内容的警告未经确认时拒绝替换
+    let err = SkillService::pin_skill(&state.db, &skill.id, Some("v1.1.0".to_string()), false)
+        .await
+        .expect_err("unacknowledged warnings must block the update");
+    assert!(err.to_string().contains("SKILL_LINT_WARNINGS"));
+    let content =
+        fs::read_to_string(ssot.join("pinned-skill").join("SKILL.md")).expect("read SKILL.md");
+    assert!(content.contains("Pinned v1"));
+
+    let repinned = SkillService::pin_skill(&state.db, &skill.id, Some("v1.1.0".to_string()), true)
+        .await
+        .expect("repin");
+    assert_eq!(repinned.name, "Pinned v2");
+    assert_eq!(
+        repinned.source.and_then(|s| s.resolved_commit).as_deref(),
+        Some(v2.as_str())
+    );
+}
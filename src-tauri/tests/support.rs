@@ -33,6 +33,7 @@ pub fn reset_test_fs() {
         ".gemini",
         ".config",
         ".openclaw",
+        ".qwen",
     ] {
         let path = home.join(sub);
         if path.exists() {
@@ -41,9 +42,16 @@ pub fn reset_test_fs() {
             }
         }
     }
-    let claude_json = home.join(".claude.json");
-    if claude_json.exists() {
-        let _ = std::fs::remove_file(&claude_json);
+    for file in [
+        ".claude.json",
+        ".aider.conf.yml",
+        ".aider.model.settings.yml",
+        ".env",
+    ] {
+        let path = home.join(file);
+        if path.exists() {
+            let _ = std::fs::remove_file(&path);
+        }
     }
 
     // 重置内存中的设置缓存，确保测试环境不受上一次调用影响
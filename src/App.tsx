@@ -30,11 +30,14 @@ import {
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import type { Provider, VisibleApps } from "@/types";
 import type { EnvConflict } from "@/types/env";
+import type { BudgetAlert } from "@/types/usage";
 import { useProvidersQuery, useSettingsQuery } from "@/lib/query";
 import {
   providersApi,
   settingsApi,
   type AppId,
+  type LiveDrift,
+  type LiveDriftAction,
   type ProviderSwitchEvent,
 } from "@/lib/api";
 import { checkAllEnvConflicts, checkEnvConflicts } from "@/lib/api/env";
@@ -61,6 +64,7 @@ import { AppSwitcher } from "@/components/AppSwitcher";
 import { ProviderList } from "@/components/providers/ProviderList";
 import { AddProviderDialog } from "@/components/providers/AddProviderDialog";
 import { EditProviderDialog } from "@/components/providers/EditProviderDialog";
+import { LiveDriftDialog } from "@/components/providers/LiveDriftDialog";
 import { ConfirmDialog } from "@/components/ConfirmDialog";
 import { SettingsPage } from "@/components/settings/SettingsPage";
 import { UpdateBadge } from "@/components/UpdateBadge";
@@ -202,6 +206,10 @@ function App() {
       opencode: true,
       openclaw: true,
       hermes: true,
+      qwen: false,
+      goose: false,
+      crush: false,
+      aider: false,
     };
     // 构建期隐藏 Claude：强制隐藏，优先级高于用户设置
     return HIDE_CLAUDE
@@ -247,6 +255,7 @@ function App() {
     provider: Provider;
     action: "remove" | "delete";
   } | null>(null);
+  const [liveDrift, setLiveDrift] = useState<LiveDrift | null>(null);
   const [envConflicts, setEnvConflicts] = useState<EnvConflict[]>([]);
   const [showEnvBanner, setShowEnvBanner] = useState(false);
 
@@ -293,14 +302,20 @@ function App() {
       currentView === "openclawAgents");
   const { data: openclawHealthWarnings = [] } =
     useOpenClawHealth(isOpenClawView);
-  const hasSkillsSupport = sharedFeatureApp !== "openclaw";
+  const hasSkillsSupport =
+    sharedFeatureApp !== "openclaw" &&
+    sharedFeatureApp !== "goose" &&
+    sharedFeatureApp !== "crush" &&
+    sharedFeatureApp !== "aider";
   const hasSessionSupport =
     sharedFeatureApp === "claude" ||
     sharedFeatureApp === "codex" ||
     sharedFeatureApp === "opencode" ||
     sharedFeatureApp === "openclaw" ||
     sharedFeatureApp === "gemini" ||
-    sharedFeatureApp === "hermes";
+    sharedFeatureApp === "hermes" ||
+    sharedFeatureApp === "goose" ||
+    sharedFeatureApp === "crush";
 
   const {
     addProvider,
@@ -433,6 +448,39 @@ function App() {
     },
   );
 
+  useTauriEvent<BudgetAlert>("budget-alert", (payload) => {
+    const message = t("notifications.budgetAlert", {
+      name: payload.budgetName,
+      percent: Math.round(payload.percent),
+      period: payload.periodKey,
+      defaultValue: `预算 ${payload.budgetName}（${payload.periodKey}）已达到 ${Math.round(payload.percent)}%`,
+    });
+    const notify = payload.threshold >= 100 ? toast.error : toast.warning;
+    // 系统通知由后端发送，这里只做应用内提示
+    notify(message, { duration: 10000 });
+  });
+
+  // live 配置被外部修改：提示回填到供应商或重新写入
+  useTauriEvent<LiveDrift>("live-config-drift", (payload) => {
+    setLiveDrift(payload);
+  });
+
+  const handleResolveLiveDrift = async (
+    drift: LiveDrift,
+    action: LiveDriftAction,
+  ) => {
+    try {
+      await providersApi.resolveLiveDrift(drift.app, action);
+      setLiveDrift(null);
+      await queryClient.invalidateQueries({ queryKey: ["providers"] });
+      toast.success(t(`liveDrift.resolved.${action}`));
+    } catch (error) {
+      toast.error(t("liveDrift.resolveFailed"), {
+        description: extractErrorMessage(error) || undefined,
+      });
+    }
+  };
+
   useEffect(() => {
     let active = true;
     let unlistenResize: (() => void) | undefined;
@@ -1606,6 +1654,12 @@ function App() {
         onCancel={() => setConfirmAction(null)}
       />
 
+      <LiveDriftDialog
+        drift={liveDrift}
+        onResolve={handleResolveLiveDrift}
+        onClose={() => setLiveDrift(null)}
+      />
+
       <ConfirmDialog
         isOpen={launchDashboardOpen}
         title={t("hermes.webui.launchConfirmTitle")}
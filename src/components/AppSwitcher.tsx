@@ -26,6 +26,10 @@ const ALL_APPS: AppId[] = [
   "opencode",
   "openclaw",
   "hermes",
+  "qwen",
+  "goose",
+  "crush",
+  "aider",
 ];
 const STORAGE_KEY = "cc-switch-last-app";
 
@@ -49,6 +53,10 @@ export function AppSwitcher({
     opencode: "opencode",
     openclaw: "openclaw",
     hermes: "hermes",
+    qwen: "qwen",
+    goose: "goose",
+    crush: "crush",
+    aider: "aider",
   };
   const appDisplayName: Record<AppId, string> = {
     claude: "Claude Code",
@@ -58,6 +66,10 @@ export function AppSwitcher({
     opencode: "OpenCode",
     openclaw: "OpenClaw",
     hermes: "Hermes",
+    qwen: "Qwen Code",
+    goose: "Goose",
+    crush: "Crush",
+    aider: "Aider",
   };
 
   // Filter apps based on visibility settings (default all visible)
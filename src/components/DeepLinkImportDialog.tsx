@@ -10,6 +10,7 @@ import {
   DialogTitle,
 } from "@/components/ui/dialog";
 import { Button } from "@/components/ui/button";
+import { Checkbox } from "@/components/ui/checkbox";
 import { toast } from "sonner";
 import { useTranslation } from "react-i18next";
 import { useQueryClient } from "@tanstack/react-query";
@@ -29,6 +30,9 @@ export function DeepLinkImportDialog() {
   const [request, setRequest] = useState<DeepLinkImportRequest | null>(null);
   const [isImporting, setIsImporting] = useState(false);
   const [isOpen, setIsOpen] = useState(false);
+  // 未经受信任发布者签名的链接需要用户额外确认来源
+  const [sourceConfirmed, setSourceConfirmed] = useState(false);
+  const isTrusted = request?.signature?.status === "trusted";
 
   // 容错判断：MCP 导入结果可能缺少 type 字段
   const isMcpImportResult = (
@@ -73,6 +77,7 @@ export function DeepLinkImportDialog() {
           setRequest(event.payload);
         }
 
+        setSourceConfirmed(false);
         setIsOpen(true);
       },
     );
@@ -330,6 +335,35 @@ export function DeepLinkImportDialog() {
 
             {/* 主体内容整体右移，略大于标题内边距，让内容看起来不贴边 */}
             <div className="space-y-4 px-8 py-4 max-h-[60vh] overflow-y-auto [scrollbar-width:thin] [&::-webkit-scrollbar]:w-1.5 [&::-webkit-scrollbar]:block [&::-webkit-scrollbar-thumb]:rounded-full [&::-webkit-scrollbar-thumb]:bg-gray-200 dark:[&::-webkit-scrollbar-thumb]:bg-gray-700">
+              {/* Signature status */}
+              {request.signature?.status === "trusted" ? (
+                <div className="rounded-lg bg-green-50 dark:bg-green-900/20 p-3 text-sm text-green-800 dark:text-green-200">
+                  {t("deeplink.signatureTrusted", {
+                    publisher: request.signature.publisher,
+                    fingerprint: request.signature.fingerprint,
+                  })}
+                </div>
+              ) : (
+                <div className="space-y-3 rounded-lg border border-red-200 dark:border-red-900/50 bg-red-50 dark:bg-red-900/20 p-3 text-sm text-red-800 dark:text-red-200">
+                  <div>
+                    {request.signature?.status === "untrusted"
+                      ? t("deeplink.signatureUntrusted", {
+                          fingerprint: request.signature.fingerprint,
+                        })
+                      : t("deeplink.signatureUnsigned")}
+                  </div>
+                  <label className="flex items-center gap-2 cursor-pointer select-none font-medium">
+                    <Checkbox
+                      checked={sourceConfirmed}
+                      onCheckedChange={(checked) =>
+                        setSourceConfirmed(checked === true)
+                      }
+                    />
+                    <span>{t("deeplink.confirmUntrustedSource")}</span>
+                  </label>
+                </div>
+              )}
+
               {request.resource === "prompt" && (
                 <PromptConfirmation request={request} />
               )}
@@ -718,7 +752,10 @@ export function DeepLinkImportDialog() {
               >
                 {t("common.cancel")}
               </Button>
-              <Button onClick={handleImport} disabled={isImporting}>
+              <Button
+                onClick={handleImport}
+                disabled={isImporting || (!isTrusted && !sourceConfirmed)}
+              >
                 {isImporting ? t("deeplink.importing") : t("deeplink.import")}
               </Button>
             </DialogFooter>
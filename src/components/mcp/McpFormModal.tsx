@@ -68,6 +68,9 @@ const McpFormModal: React.FC<McpFormModalProps> = ({
     opencode: boolean;
     openclaw: boolean;
     hermes: boolean;
+    qwen: boolean;
+    goose: boolean;
+    crush: boolean;
   }>(() => {
     if (initialData?.apps) {
       return { ...initialData.apps };
@@ -79,6 +82,9 @@ const McpFormModal: React.FC<McpFormModalProps> = ({
       opencode: defaultEnabledApps.includes("opencode"),
       openclaw: defaultEnabledApps.includes("openclaw"),
       hermes: defaultEnabledApps.includes("hermes"),
+      qwen: defaultEnabledApps.includes("qwen"),
+      goose: defaultEnabledApps.includes("goose"),
+      crush: defaultEnabledApps.includes("crush"),
     };
   });
 
@@ -32,6 +32,10 @@ const PromptFormPanel: React.FC<PromptFormPanelProps> = ({
     opencode: "AGENTS.md",
     openclaw: "AGENTS.md",
     hermes: "AGENTS.md",
+    qwen: "QWEN.md",
+    goose: ".goosehints",
+    crush: "CRUSH.md",
+    aider: "CONVENTIONS.md",
   };
   const filename = filenameMap[appId];
   const [name, setName] = useState("");
@@ -0,0 +1,127 @@
+import { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { AlertTriangle } from "lucide-react";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import type { LiveDrift, LiveDriftAction } from "@/lib/api";
+
+interface LiveDriftDialogProps {
+  drift: LiveDrift | null;
+  onResolve: (drift: LiveDrift, action: LiveDriftAction) => Promise<void>;
+  onClose: () => void;
+}
+
+const formatValue = (value: unknown): string => {
+  if (value === undefined || value === null) return "—";
+  if (typeof value === "string") return value;
+  return JSON.stringify(value);
+};
+
+/**
+ * live 配置被外部修改时的处理对话框：回填到供应商、重新写入或保留修改
+ */
+export function LiveDriftDialog({
+  drift,
+  onResolve,
+  onClose,
+}: LiveDriftDialogProps) {
+  const { t } = useTranslation();
+  const [pending, setPending] = useState<LiveDriftAction | null>(null);
+
+  const handleResolve = async (action: LiveDriftAction) => {
+    if (!drift) return;
+    setPending(action);
+    try {
+      await onResolve(drift, action);
+    } finally {
+      setPending(null);
+    }
+  };
+
+  const canBackfill = drift?.changes.some(
+    (change) => change.category === "provider",
+  );
+
+  return (
+    <Dialog
+      open={drift !== null}
+      onOpenChange={(open) => {
+        if (!open) onClose();
+      }}
+    >
+      <DialogContent className="max-w-2xl" zIndex="alert">
+        <DialogHeader className="space-y-3 border-b-0 bg-transparent pb-0">
+          <DialogTitle className="flex items-center gap-2 text-lg font-semibold">
+            <AlertTriangle className="h-5 w-5 text-amber-500" />
+            {t("liveDrift.title", {
+              app: drift ? t(`apps.${drift.app}`) : "",
+            })}
+          </DialogTitle>
+          <DialogDescription className="whitespace-pre-line text-sm leading-relaxed">
+            {t("liveDrift.description", {
+              files: drift?.files.join("\n") ?? "",
+            })}
+          </DialogDescription>
+        </DialogHeader>
+        <div className="max-h-80 space-y-2 overflow-y-auto px-6">
+          {drift?.changes.map((change) => (
+            <div
+              key={change.path}
+              className="rounded-md border border-border-default p-2 text-xs"
+            >
+              <div className="flex items-center gap-2">
+                <code className="font-mono">{change.path}</code>
+                <Badge
+                  variant={
+                    change.category === "unrelated" ? "outline" : "secondary"
+                  }
+                >
+                  {t(`liveDrift.category.${change.category}`)}
+                </Badge>
+              </div>
+              <div className="mt-1 break-all font-mono text-muted-foreground">
+                <span className="line-through">
+                  {formatValue(change.expected)}
+                </span>
+                {" → "}
+                <span className="text-foreground">
+                  {formatValue(change.actual)}
+                </span>
+              </div>
+            </div>
+          ))}
+        </div>
+        <DialogFooter className="flex gap-2 border-t-0 bg-transparent pt-2 sm:justify-end">
+          <Button
+            variant="outline"
+            disabled={pending !== null}
+            onClick={() => void handleResolve("accept")}
+          >
+            {t("liveDrift.accept")}
+          </Button>
+          <Button
+            variant="outline"
+            disabled={pending !== null}
+            onClick={() => void handleResolve("reapply")}
+          >
+            {t("liveDrift.reapply")}
+          </Button>
+          <Button
+            disabled={pending !== null || !canBackfill}
+            onClick={() => void handleResolve("backfill")}
+          >
+            {t("liveDrift.backfill")}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
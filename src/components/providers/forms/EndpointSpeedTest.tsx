@@ -17,6 +17,10 @@ const ENDPOINT_TIMEOUT_SECS: Record<AppId, number> = {
   opencode: 8,
   openclaw: 8,
   hermes: 8,
+  qwen: 8,
+  goose: 8,
+  crush: 8,
+  aider: 8,
 };
 
 interface TestResult {
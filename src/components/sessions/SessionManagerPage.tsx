@@ -66,7 +66,10 @@ type ProviderFilter =
   | "opencode"
   | "openclaw"
   | "gemini"
-  | "hermes";
+  | "hermes"
+  | "qwen"
+  | "goose"
+  | "crush";
 
 export function SessionManagerPage({ appId }: { appId: string }) {
   const { t } = useTranslation();
@@ -680,6 +683,36 @@ export function SessionManagerPage({ appId }: { appId: string }) {
                                 <span>Gemini CLI</span>
                               </div>
                             </SelectItem>
+                            <SelectItem value="qwen">
+                              <div className="flex items-center gap-2">
+                                <ProviderIcon
+                                  icon="qwen"
+                                  name="qwen"
+                                  size={14}
+                                />
+                                <span>Qwen Code</span>
+                              </div>
+                            </SelectItem>
+                            <SelectItem value="goose">
+                              <div className="flex items-center gap-2">
+                                <ProviderIcon
+                                  icon="goose"
+                                  name="goose"
+                                  size={14}
+                                />
+                                <span>Goose</span>
+                              </div>
+                            </SelectItem>
+                            <SelectItem value="crush">
+                              <div className="flex items-center gap-2">
+                                <ProviderIcon
+                                  icon="crush"
+                                  name="crush"
+                                  size={14}
+                                />
+                                <span>Crush</span>
+                              </div>
+                            </SelectItem>
                           </SelectContent>
                         </Select>
 
@@ -27,6 +27,10 @@ const APP_CONFIG: Array<{
   { id: "opencode", icon: "opencode", nameKey: "apps.opencode" },
   { id: "openclaw", icon: "openclaw", nameKey: "apps.openclaw" },
   { id: "hermes", icon: "hermes", nameKey: "apps.hermes" },
+  { id: "qwen", icon: "qwen", nameKey: "apps.qwen" },
+  { id: "goose", icon: "goose", nameKey: "apps.goose" },
+  { id: "crush", icon: "crush", nameKey: "apps.crush" },
+  { id: "aider", icon: "aider", nameKey: "apps.aider" },
 ];
 
 export function AppVisibilitySettings({
@@ -43,6 +47,10 @@ export function AppVisibilitySettings({
     opencode: true,
     openclaw: true,
     hermes: true,
+    qwen: false,
+    goose: false,
+    crush: false,
+    aider: false,
   };
 
   // Count how many apps are currently visible
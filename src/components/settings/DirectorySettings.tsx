@@ -20,6 +20,10 @@ interface DirectorySettingsProps {
   opencodeDir?: string;
   openclawDir?: string;
   hermesDir?: string;
+  qwenDir?: string;
+  gooseDir?: string;
+  crushDir?: string;
+  aiderDir?: string;
   onDirectoryChange: (app: DirectoryAppId, value?: string) => void;
   onBrowseDirectory: (app: DirectoryAppId) => Promise<void>;
   onResetDirectory: (app: DirectoryAppId) => Promise<void>;
@@ -37,6 +41,10 @@ export function DirectorySettings({
   opencodeDir,
   openclawDir,
   hermesDir,
+  qwenDir,
+  gooseDir,
+  crushDir,
+  aiderDir,
   onDirectoryChange,
   onBrowseDirectory,
   onResetDirectory,
@@ -158,6 +166,50 @@ export function DirectorySettings({
           onBrowse={() => onBrowseDirectory("hermes")}
           onReset={() => onResetDirectory("hermes")}
         />
+
+        <DirectoryInput
+          label={t("settings.qwenConfigDir")}
+          description={undefined}
+          value={qwenDir}
+          resolvedValue={resolvedDirs.qwen}
+          placeholder={t("settings.browsePlaceholderQwen")}
+          onChange={(val) => onDirectoryChange("qwen", val)}
+          onBrowse={() => onBrowseDirectory("qwen")}
+          onReset={() => onResetDirectory("qwen")}
+        />
+
+        <DirectoryInput
+          label={t("settings.gooseConfigDir")}
+          description={undefined}
+          value={gooseDir}
+          resolvedValue={resolvedDirs.goose}
+          placeholder={t("settings.browsePlaceholderGoose")}
+          onChange={(val) => onDirectoryChange("goose", val)}
+          onBrowse={() => onBrowseDirectory("goose")}
+          onReset={() => onResetDirectory("goose")}
+        />
+
+        <DirectoryInput
+          label={t("settings.crushConfigDir")}
+          description={undefined}
+          value={crushDir}
+          resolvedValue={resolvedDirs.crush}
+          placeholder={t("settings.browsePlaceholderCrush")}
+          onChange={(val) => onDirectoryChange("crush", val)}
+          onBrowse={() => onBrowseDirectory("crush")}
+          onReset={() => onResetDirectory("crush")}
+        />
+
+        <DirectoryInput
+          label={t("settings.aiderConfigDir")}
+          description={undefined}
+          value={aiderDir}
+          resolvedValue={resolvedDirs.aider}
+          placeholder={t("settings.browsePlaceholderAider")}
+          onChange={(val) => onDirectoryChange("aider", val)}
+          onBrowse={() => onBrowseDirectory("aider")}
+          onReset={() => onResetDirectory("aider")}
+        />
       </section>
     </div>
   );
@@ -323,6 +323,10 @@ export function SettingsPage({
                             opencodeDir={settings.opencodeConfigDir}
                             openclawDir={settings.openclawConfigDir}
                             hermesDir={settings.hermesConfigDir}
+                            qwenDir={settings.qwenConfigDir}
+                            gooseDir={settings.gooseConfigDir}
+                            crushDir={settings.crushConfigDir}
+                            aiderDir={settings.aiderConfigDir}
                             onDirectoryChange={updateDirectory}
                             onBrowseDirectory={browseDirectory}
                             onResetDirectory={resetDirectory}
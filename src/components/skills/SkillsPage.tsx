@@ -17,6 +17,7 @@ import {
 } from "@/components/ui/select";
 import { RefreshCw, Search, Loader2 } from "lucide-react";
 import { toast } from "sonner";
+import { ConfirmDialog } from "@/components/ConfirmDialog";
 import { SkillCard } from "./SkillCard";
 import { RepoManagerPanel } from "./RepoManagerPanel";
 import {
@@ -34,7 +35,11 @@ import type {
   SkillRepo,
   SkillsShDiscoverableSkill,
 } from "@/lib/api/skills";
-import { formatSkillError } from "@/lib/errors/skillErrorParser";
+import {
+  formatSkillError,
+  formatSkillLintReports,
+  getSkillLintWarnings,
+} from "@/lib/errors/skillErrorParser";
 
 interface SkillsPageProps {
   initialApp?: AppId;
@@ -57,6 +62,11 @@ export const SkillsPage = forwardRef<SkillsPageHandle, SkillsPageProps>(
   ({ initialApp = "claude" }, ref) => {
     const { t } = useTranslation();
     const [repoManagerOpen, setRepoManagerOpen] = useState(false);
+    // 安装前检查有警告时，等待用户确认后重新安装
+    const [lintConfirm, setLintConfirm] = useState<{
+      message: string;
+      onConfirm: () => void;
+    } | null>(null);
     const [searchQuery, setSearchQuery] = useState("");
     const [filterRepo, setFilterRepo] = useState<string>("all");
     const [filterStatus, setFilterStatus] = useState<
@@ -211,10 +221,18 @@ export const SkillsPage = forwardRef<SkillsPageHandle, SkillsPageProps>(
         return;
       }
 
+      await installSkill(skill);
+    };
+
+    const installSkill = async (
+      skill: DiscoverableSkill,
+      acknowledgeWarnings?: boolean,
+    ) => {
       try {
         await installMutation.mutateAsync({
           skill,
           currentApp,
+          acknowledgeWarnings,
         });
         toast.success(t("skills.installSuccess", { name: skill.name }), {
           closeButton: true,
@@ -222,6 +240,17 @@ export const SkillsPage = forwardRef<SkillsPageHandle, SkillsPageProps>(
       } catch (error) {
         const errorMessage =
           error instanceof Error ? error.message : String(error);
+        const lintWarnings = getSkillLintWarnings(errorMessage);
+        if (lintWarnings) {
+          setLintConfirm({
+            message: `${t("skills.lint.confirmMessage")}\n\n${formatSkillLintReports(lintWarnings, t)}`,
+            onConfirm: () => {
+              setLintConfirm(null);
+              void installSkill(skill, true);
+            },
+          });
+          return;
+        }
         const { title, description } = formatSkillError(
           errorMessage,
           t,
@@ -610,6 +639,18 @@ export const SkillsPage = forwardRef<SkillsPageHandle, SkillsPageProps>(
             onClose={() => setRepoManagerOpen(false)}
           />
         )}
+
+        {lintConfirm && (
+          <ConfirmDialog
+            isOpen={true}
+            title={t("skills.lint.confirmTitle")}
+            message={lintConfirm.message}
+            confirmText={t("skills.lint.installAnyway")}
+            variant="destructive"
+            onConfirm={lintConfirm.onConfirm}
+            onCancel={() => setLintConfirm(null)}
+          />
+        )}
       </div>
     );
   },
@@ -30,6 +30,11 @@ import {
 import type { AppId } from "@/lib/api/types";
 import { ConfirmDialog } from "@/components/ConfirmDialog";
 import { settingsApi, skillsApi } from "@/lib/api";
+import {
+  formatSkillError,
+  formatSkillLintReports,
+  getSkillLintWarnings,
+} from "@/lib/errors/skillErrorParser";
 import { toast } from "sonner";
 import { SKILLS_APP_IDS } from "@/config/appConfig";
 import { AppCountBar } from "@/components/common/AppCountBar";
@@ -196,13 +201,20 @@ const UnifiedSkillsPanel = React.forwardRef<
   };
 
   const handleInstallFromZip = async () => {
-    try {
-      const filePath = await skillsApi.openZipFileDialog();
-      if (!filePath) return;
+    const filePath = await skillsApi.openZipFileDialog();
+    if (!filePath) return;
+    await installFromZip(filePath);
+  };
 
+  const installFromZip = async (
+    filePath: string,
+    acknowledgeWarnings?: boolean,
+  ) => {
+    try {
       const installed = await installFromZipMutation.mutateAsync({
         filePath,
         currentApp,
+        acknowledgeWarnings,
       });
 
       if (installed.length === 0) {
@@ -223,7 +235,28 @@ const UnifiedSkillsPanel = React.forwardRef<
         );
       }
     } catch (error) {
-      toast.error(t("skills.installFailed"), { description: String(error) });
+      const errorMessage = String(error);
+      const lintWarnings = getSkillLintWarnings(errorMessage);
+      if (lintWarnings) {
+        setConfirmDialog({
+          isOpen: true,
+          title: t("skills.lint.confirmTitle"),
+          message: `${t("skills.lint.confirmMessage")}\n\n${formatSkillLintReports(lintWarnings, t)}`,
+          confirmText: t("skills.lint.installAnyway"),
+          variant: "destructive",
+          onConfirm: () => {
+            setConfirmDialog(null);
+            void installFromZip(filePath, true);
+          },
+        });
+        return;
+      }
+      const { title, description } = formatSkillError(
+        errorMessage,
+        t,
+        "skills.installFailed",
+      );
+      toast.error(title, { description });
     }
   };
 
@@ -243,13 +276,34 @@ const UnifiedSkillsPanel = React.forwardRef<
     }
   };
 
-  const handleUpdateSkill = async (skill: InstalledSkill) => {
+  const handleUpdateSkill = async (
+    skill: InstalledSkill,
+    acknowledgeWarnings?: boolean,
+  ) => {
     try {
-      const updated = await updateSkillMutation.mutateAsync(skill.id);
+      const updated = await updateSkillMutation.mutateAsync({
+        id: skill.id,
+        acknowledgeWarnings,
+      });
       toast.success(t("skills.updateSuccess", { name: updated.name }), {
         closeButton: true,
       });
     } catch (error) {
+      const lintWarnings = getSkillLintWarnings(String(error));
+      if (lintWarnings) {
+        setConfirmDialog({
+          isOpen: true,
+          title: t("skills.lint.confirmUpdateTitle"),
+          message: `${t("skills.lint.confirmMessage")}\n\n${formatSkillLintReports(lintWarnings, t)}`,
+          confirmText: t("skills.lint.updateAnyway"),
+          variant: "destructive",
+          onConfirm: () => {
+            setConfirmDialog(null);
+            void handleUpdateSkill(skill, true);
+          },
+        });
+        return;
+      }
       toast.error(t("skills.updateFailed"), { description: String(error) });
     }
   };
@@ -260,7 +314,8 @@ const UnifiedSkillsPanel = React.forwardRef<
     let successCount = 0;
     for (const update of skillUpdates) {
       try {
-        await updateSkillMutation.mutateAsync(update.id);
+        // 批量更新不弹确认框；带警告的 Skill 需要单独更新并确认
+        await updateSkillMutation.mutateAsync({ id: update.id });
         successCount++;
       } catch (error) {
         toast.error(t("skills.updateFailed"), {
@@ -426,7 +481,7 @@ const UnifiedSkillsPanel = React.forwardRef<
                   hasUpdate={!!updatesMap[skill.id]}
                   isUpdating={
                     updateSkillMutation.isPending &&
-                    updateSkillMutation.variables === skill.id
+                    updateSkillMutation.variables?.id === skill.id
                   }
                   onToggleApp={handleToggleApp}
                   onUninstall={() => handleUninstall(skill)}
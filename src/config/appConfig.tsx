@@ -23,6 +23,10 @@ export const APP_IDS: AppId[] = [
   "opencode",
   "openclaw",
   "hermes",
+  "qwen",
+  "goose",
+  "crush",
+  "aider",
 ];
 
 /** App IDs shown in Skills panels (excludes OpenClaw — it doesn't support Skills) */
@@ -34,8 +38,13 @@ export const SKILLS_APP_IDS: AppId[] = [
   "hermes",
 ];
 
-/** App IDs shown in MCP panels (excludes OpenClaw) */
-export const MCP_APP_IDS: AppId[] = [...SKILLS_APP_IDS];
+/** App IDs shown in MCP panels (excludes OpenClaw; Qwen Code, Goose and Crush have MCP but no Skills) */
+export const MCP_APP_IDS: AppId[] = [
+  ...SKILLS_APP_IDS,
+  "qwen",
+  "goose",
+  "crush",
+];
 
 export const APP_ICON_MAP: Record<AppId, AppConfig> = {
   claude: {
@@ -108,4 +117,43 @@ export const APP_ICON_MAP: Record<AppId, AppConfig> = {
     badgeClass:
       "bg-violet-500/10 text-violet-700 dark:text-violet-300 hover:bg-violet-500/20 border-0 gap-1.5",
   },
+  qwen: {
+    label: "Qwen Code",
+    icon: (
+      <ProviderIcon
+        icon="qwen"
+        name="Qwen Code"
+        size={14}
+        showFallback={false}
+      />
+    ),
+    activeClass:
+      "bg-purple-500/10 ring-1 ring-purple-500/20 hover:bg-purple-500/20 text-purple-600 dark:text-purple-400",
+    badgeClass:
+      "bg-purple-500/10 text-purple-700 dark:text-purple-300 hover:bg-purple-500/20 border-0 gap-1.5",
+  },
+  goose: {
+    label: "Goose",
+    icon: <ProviderIcon icon="goose" name="Goose" size={14} />,
+    activeClass:
+      "bg-zinc-500/10 ring-1 ring-zinc-500/20 hover:bg-zinc-500/20 text-zinc-600 dark:text-zinc-400",
+    badgeClass:
+      "bg-zinc-500/10 text-zinc-700 dark:text-zinc-300 hover:bg-zinc-500/20 border-0 gap-1.5",
+  },
+  crush: {
+    label: "Crush",
+    icon: <ProviderIcon icon="crush" name="Crush" size={14} />,
+    activeClass:
+      "bg-pink-500/10 ring-1 ring-pink-500/20 hover:bg-pink-500/20 text-pink-600 dark:text-pink-400",
+    badgeClass:
+      "bg-pink-500/10 text-pink-700 dark:text-pink-300 hover:bg-pink-500/20 border-0 gap-1.5",
+  },
+  aider: {
+    label: "Aider",
+    icon: <ProviderIcon icon="aider" name="Aider" size={14} />,
+    activeClass:
+      "bg-lime-500/10 ring-1 ring-lime-500/20 hover:bg-lime-500/20 text-lime-600 dark:text-lime-400",
+    badgeClass:
+      "bg-lime-500/10 text-lime-700 dark:text-lime-300 hover:bg-lime-500/20 border-0 gap-1.5",
+  },
 };
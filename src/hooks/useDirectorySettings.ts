@@ -12,7 +12,11 @@ type AppDirectoryKey =
   | "gemini"
   | "opencode"
   | "openclaw"
-  | "hermes";
+  | "hermes"
+  | "qwen"
+  | "goose"
+  | "crush"
+  | "aider";
 type DirectoryKey = "appConfig" | AppDirectoryKey;
 
 export interface ResolvedDirectories {
@@ -23,6 +27,10 @@ export interface ResolvedDirectories {
   opencode: string;
   openclaw: string;
   hermes: string;
+  qwen: string;
+  goose: string;
+  crush: string;
+  aider: string;
 }
 
 // Single source of truth for per-app directory metadata.
@@ -36,6 +44,11 @@ const APP_DIRECTORY_META: Record<
   opencode: { key: "opencode", defaultFolder: ".config/opencode" },
   openclaw: { key: "openclaw", defaultFolder: ".openclaw" },
   hermes: { key: "hermes", defaultFolder: ".hermes" },
+  qwen: { key: "qwen", defaultFolder: ".qwen" },
+  goose: { key: "goose", defaultFolder: ".config/goose" },
+  crush: { key: "crush", defaultFolder: ".config/crush" },
+  // Aider 的配置文件直接放在主目录下
+  aider: { key: "aider", defaultFolder: "" },
 };
 
 const DIRECTORY_KEY_TO_SETTINGS_FIELD: Record<
@@ -48,6 +61,10 @@ const DIRECTORY_KEY_TO_SETTINGS_FIELD: Record<
   opencode: "opencodeConfigDir",
   openclaw: "openclawConfigDir",
   hermes: "hermesConfigDir",
+  qwen: "qwenConfigDir",
+  goose: "gooseConfigDir",
+  crush: "crushConfigDir",
+  aider: "aiderConfigDir",
 };
 
 const sanitizeDir = (value?: string | null): string | undefined => {
@@ -74,7 +91,8 @@ const computeDefaultConfigDir = async (
 ): Promise<string | undefined> => {
   try {
     const home = await homeDir();
-    return await join(home, APP_DIRECTORY_META[app].defaultFolder);
+    const folder = APP_DIRECTORY_META[app].defaultFolder;
+    return folder ? await join(home, folder) : home;
   } catch (error) {
     console.error(
       "[useDirectorySettings] Failed to resolve default config dir",
@@ -133,6 +151,10 @@ export function useDirectorySettings({
     opencode: "",
     openclaw: "",
     hermes: "",
+    qwen: "",
+    goose: "",
+    crush: "",
+    aider: "",
   });
   const [isLoading, setIsLoading] = useState(true);
 
@@ -144,6 +166,10 @@ export function useDirectorySettings({
     opencode: "",
     openclaw: "",
     hermes: "",
+    qwen: "",
+    goose: "",
+    crush: "",
+    aider: "",
   });
   const initialAppConfigDirRef = useRef<string | undefined>(undefined);
 
@@ -162,6 +188,10 @@ export function useDirectorySettings({
           opencodeDir,
           openclawDir,
           hermesDir,
+          qwenDir,
+          gooseDir,
+          crushDir,
+          aiderDir,
           defaultAppConfig,
           defaultClaudeDir,
           defaultCodexDir,
@@ -169,6 +199,10 @@ export function useDirectorySettings({
           defaultOpencodeDir,
           defaultOpenclawDir,
           defaultHermesDir,
+          defaultQwenDir,
+          defaultGooseDir,
+          defaultCrushDir,
+          defaultAiderDir,
         ] = await Promise.all([
           settingsApi.getAppConfigDirOverride(),
           settingsApi.getConfigDir("claude"),
@@ -177,6 +211,10 @@ export function useDirectorySettings({
           settingsApi.getConfigDir("opencode"),
           settingsApi.getConfigDir("openclaw"),
           settingsApi.getConfigDir("hermes"),
+          settingsApi.getConfigDir("qwen"),
+          settingsApi.getConfigDir("goose"),
+          settingsApi.getConfigDir("crush"),
+          settingsApi.getConfigDir("aider"),
           computeDefaultAppConfigDir(),
           computeDefaultConfigDir("claude"),
           computeDefaultConfigDir("codex"),
@@ -184,6 +222,10 @@ export function useDirectorySettings({
           computeDefaultConfigDir("opencode"),
           computeDefaultConfigDir("openclaw"),
           computeDefaultConfigDir("hermes"),
+          computeDefaultConfigDir("qwen"),
+          computeDefaultConfigDir("goose"),
+          computeDefaultConfigDir("crush"),
+          computeDefaultConfigDir("aider"),
         ]);
 
         if (!active) return;
@@ -198,6 +240,10 @@ export function useDirectorySettings({
           opencode: defaultOpencodeDir ?? "",
           openclaw: defaultOpenclawDir ?? "",
           hermes: defaultHermesDir ?? "",
+          qwen: defaultQwenDir ?? "",
+          goose: defaultGooseDir ?? "",
+          crush: defaultCrushDir ?? "",
+          aider: defaultAiderDir ?? "",
         };
 
         setAppConfigDir(normalizedOverride);
@@ -211,6 +257,10 @@ export function useDirectorySettings({
           opencode: opencodeDir || defaultsRef.current.opencode,
           openclaw: openclawDir || defaultsRef.current.openclaw,
           hermes: hermesDir || defaultsRef.current.hermes,
+          qwen: qwenDir || defaultsRef.current.qwen,
+          goose: gooseDir || defaultsRef.current.goose,
+          crush: crushDir || defaultsRef.current.crush,
+          aider: aiderDir || defaultsRef.current.aider,
         });
       } catch (error) {
         console.error(
@@ -352,6 +402,10 @@ export function useDirectorySettings({
         opencode: overrides?.opencode ?? defaultsRef.current.opencode,
         openclaw: overrides?.openclaw ?? defaultsRef.current.openclaw,
         hermes: overrides?.hermes ?? defaultsRef.current.hermes,
+        qwen: overrides?.qwen ?? defaultsRef.current.qwen,
+        goose: overrides?.goose ?? defaultsRef.current.goose,
+        crush: overrides?.crush ?? defaultsRef.current.crush,
+        aider: overrides?.aider ?? defaultsRef.current.aider,
       });
     },
     [],
@@ -113,6 +113,10 @@ export function useSettings(): UseSettingsResult {
       opencode: sanitizeDir(data?.opencodeConfigDir),
       openclaw: sanitizeDir(data?.openclawConfigDir),
       hermes: sanitizeDir(data?.hermesConfigDir),
+      qwen: sanitizeDir(data?.qwenConfigDir),
+      goose: sanitizeDir(data?.gooseConfigDir),
+      crush: sanitizeDir(data?.crushConfigDir),
+      aider: sanitizeDir(data?.aiderConfigDir),
     });
     setRequiresRestart(false);
   }, [
@@ -72,10 +72,12 @@ export function useInstallSkill() {
     mutationFn: ({
       skill,
       currentApp,
+      acknowledgeWarnings,
     }: {
       skill: DiscoverableSkill;
       currentApp: AppId;
-    }) => skillsApi.installUnified(skill, currentApp),
+      acknowledgeWarnings?: boolean;
+    }) => skillsApi.installUnified(skill, currentApp, acknowledgeWarnings),
     onSuccess: (installedSkill, _vars, _ctx) => {
       const { skill } = _vars;
       // 直接更新 installed 缓存
@@ -266,10 +268,12 @@ export function useInstallSkillsFromZip() {
     mutationFn: ({
       filePath,
       currentApp,
+      acknowledgeWarnings,
     }: {
       filePath: string;
       currentApp: AppId;
-    }) => skillsApi.installFromZip(filePath, currentApp),
+      acknowledgeWarnings?: boolean;
+    }) => skillsApi.installFromZip(filePath, currentApp, acknowledgeWarnings),
     onSuccess: (installedSkills) => {
       // 直接更新 installed 缓存
       queryClient.setQueryData<InstalledSkill[]>(
@@ -303,7 +307,13 @@ export function useCheckSkillUpdates() {
 export function useUpdateSkill() {
   const queryClient = useQueryClient();
   return useMutation({
-    mutationFn: (id: string) => skillsApi.updateSkill(id),
+    mutationFn: ({
+      id,
+      acknowledgeWarnings,
+    }: {
+      id: string;
+      acknowledgeWarnings?: boolean;
+    }) => skillsApi.updateSkill(id, acknowledgeWarnings),
     onSuccess: (updatedSkill) => {
       queryClient.setQueryData<InstalledSkill[]>(
         ["skills", "installed"],
@@ -0,0 +1,134 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AppId } from "./types";
+import type { SignatureStatus } from "./signing";
+
+export interface BundleExportOptions {
+  id: string;
+  name: string;
+  version: string;
+  description?: string;
+  publisher?: string;
+  providers?: Partial<Record<AppId, string[]>>;
+  mcpServers?: string[];
+  prompts?: Partial<Record<AppId, string[]>>;
+  skillRepos?: boolean;
+  modelPricing?: string[];
+  proxySettings?: AppId[];
+  signingKey?: string; // 发布者私钥，仅用于本次签名
+}
+
+export interface BundleProvider {
+  app: AppId;
+  provider: Record<string, unknown>;
+  requiredSecrets?: string[]; // JSON Pointer，导入时需自行填写
+}
+
+export interface TeamBundle {
+  format: "cc-switch-bundle";
+  formatVersion: number;
+  id: string;
+  name: string;
+  version: string;
+  description?: string;
+  publisher?: string;
+  createdAt: number; // 秒
+  providers?: BundleProvider[];
+  mcpServers?: Record<string, unknown>[];
+  prompts?: { app: AppId; prompt: Record<string, unknown> }[];
+  skillRepos?: {
+    owner: string;
+    name: string;
+    branch: string;
+    enabled: boolean;
+  }[];
+  modelPricing?: Record<string, unknown>[];
+  proxySettings?: Record<string, unknown>[];
+  signature?: { publicKey: string; signature: string };
+}
+
+export type BundleItemKind =
+  | "provider"
+  | "mcpServer"
+  | "prompt"
+  | "skillRepo"
+  | "modelPricing"
+  | "proxySettings";
+
+export type BundleItemAction =
+  | "add"
+  | "update"
+  | "remove"
+  | "unchanged"
+  | "conflict";
+
+export interface BundleItemChange {
+  kind: BundleItemKind;
+  key: string; // 供应商/提示词为 `<app>/<id>`
+  action: BundleItemAction;
+  diff: string;
+  conflicts?: string[]; // 保留本地值的字段
+  missingSecrets?: string[]; // 供应商为 JSON Pointer，MCP 为 `secret:<name>`
+}
+
+export interface BundlePreview {
+  bundleId: string;
+  name: string;
+  version: string;
+  publisher?: string;
+  previousVersion?: string;
+  signature: SignatureStatus;
+  changes: BundleItemChange[];
+}
+
+export interface BundleSecretInput {
+  providers?: Record<string, Record<string, string>>;
+  mcpSecrets?: Record<string, string>;
+}
+
+export interface BundleImportResult {
+  bundleId: string;
+  version: string;
+  changes: BundleItemChange[];
+  errors: string[];
+}
+
+export interface BundleImportRecord {
+  bundleId: string;
+  name: string;
+  version: string;
+  publisher?: string;
+  importedAt: number; // 秒
+}
+
+export const bundlesApi = {
+  async export(
+    options: BundleExportOptions,
+    filePath: string,
+  ): Promise<TeamBundle> {
+    return await invoke("export_team_bundle", { options, filePath });
+  },
+
+  async preview(
+    filePath: string,
+    secrets?: BundleSecretInput,
+  ): Promise<BundlePreview> {
+    return await invoke("preview_team_bundle", { filePath, secrets });
+  },
+
+  // 未经受信任发布者签名时需要 acceptUntrusted
+  async import(
+    filePath: string,
+    secrets?: BundleSecretInput,
+    acceptUntrusted = false,
+  ): Promise<BundleImportResult> {
+    return await invoke("import_team_bundle", {
+      filePath,
+      secrets,
+      acceptUntrusted,
+    });
+  },
+
+  async listImports(): Promise<BundleImportRecord[]> {
+    return await invoke("list_team_bundle_imports");
+  },
+};
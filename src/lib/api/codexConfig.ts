@@ -0,0 +1,58 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { CodexPolicySettings } from "@/types";
+
+export interface CodexProfile extends CodexPolicySettings {
+  model?: string;
+  modelProvider?: string; // 自定义供应商必须已在 [model_providers] 中定义
+}
+
+export interface CodexShellEnvironmentPolicy {
+  inherit?: "all" | "core" | "none" | (string & {});
+  ignoreDefaultExcludes?: boolean;
+  exclude?: string[]; // 变量名 glob
+  includeOnly?: string[]; // 非空时只保留匹配的变量
+  set?: Record<string, string>;
+}
+
+// 配置中未识别的枚举取值；只能原样保留，保存新的未识别取值会被后端拒绝
+export interface CodexUnknownValue {
+  path: string; // 如 profiles.fast.sandbox_mode
+  value: string;
+}
+
+// config.toml 中由结构化编辑器管理的部分；其余键与注释由后端原样保留
+export interface CodexConfigSettings extends CodexPolicySettings {
+  profile?: string; // 默认启用的 profile
+  shellEnvironmentPolicy?: CodexShellEnvironmentPolicy;
+  profiles: Record<string, CodexProfile>;
+  unknownValues?: CodexUnknownValue[]; // 只读，供界面提示
+}
+
+export const codexConfigApi = {
+  async get(): Promise<CodexConfigSettings> {
+    return await invoke("get_codex_config_settings");
+  },
+
+  /** 保存全部设置；未出现在 profiles 中的 profile 会被删除 */
+  async save(settings: CodexConfigSettings): Promise<CodexConfigSettings> {
+    return await invoke("save_codex_config_settings", { settings });
+  },
+
+  async upsertProfile(
+    name: string,
+    profile: CodexProfile,
+  ): Promise<CodexConfigSettings> {
+    return await invoke("upsert_codex_profile", { name, profile });
+  },
+
+  async deleteProfile(name: string): Promise<CodexConfigSettings> {
+    return await invoke("delete_codex_profile", { name });
+  },
+
+  async renameProfile(
+    oldName: string,
+    newName: string,
+  ): Promise<CodexConfigSettings> {
+    return await invoke("rename_codex_profile", { oldName, newName });
+  },
+};
@@ -0,0 +1,86 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Provider } from "@/types";
+import type { Prompt } from "./prompts";
+
+export type AppDefinitionFormat = "json" | "toml" | "yaml" | "env";
+
+/** `~/.cc-switch/apps/*.toml` 中声明的应用 */
+export interface AppDefinition {
+  id: string;
+  name: string;
+  config: { path: string; format?: AppDefinitionFormat };
+  /** 供应商字段在配置文件中的 JSON Pointer */
+  fields: { baseUrl?: string; apiKey?: string; model?: string };
+  mcp?: {
+    path?: string;
+    format?: AppDefinitionFormat;
+    pointer: string;
+    /** 同步在该内置应用下启用的 MCP 服务器 */
+    inherit: string;
+    /** 规范字段名 -> 该工具使用的键名，空字符串表示不写 */
+    keys?: Record<string, string>;
+  };
+  prompt?: { path: string };
+  /** 定义文件路径 */
+  source: string;
+}
+
+export interface AppDefinitionLoadReport {
+  apps: AppDefinition[];
+  /** 被跳过的定义文件及原因 */
+  errors: string[];
+}
+
+export const customAppsApi = {
+  async list(): Promise<AppDefinition[]> {
+    return await invoke("list_app_definitions");
+  },
+
+  async reload(): Promise<AppDefinitionLoadReport> {
+    return await invoke("reload_app_definitions");
+  },
+
+  async getProviders(app: string): Promise<Record<string, Provider>> {
+    return await invoke("get_custom_app_providers", { app });
+  },
+
+  async getCurrentProvider(app: string): Promise<string | null> {
+    return await invoke("get_custom_app_current_provider", { app });
+  },
+
+  async upsertProvider(app: string, provider: Provider): Promise<void> {
+    return await invoke("upsert_custom_app_provider", { app, provider });
+  },
+
+  async deleteProvider(app: string, id: string): Promise<void> {
+    return await invoke("delete_custom_app_provider", { app, id });
+  },
+
+  async switchProvider(app: string, id: string): Promise<void> {
+    return await invoke("switch_custom_app_provider", { app, id });
+  },
+
+  async importFromLive(app: string): Promise<boolean> {
+    return await invoke("import_custom_app_from_live", { app });
+  },
+
+  async syncMcp(app: string): Promise<void> {
+    return await invoke("sync_custom_app_mcp", { app });
+  },
+
+  async getPrompts(app: string): Promise<Record<string, Prompt>> {
+    return await invoke("get_custom_app_prompts", { app });
+  },
+
+  async upsertPrompt(app: string, prompt: Prompt): Promise<void> {
+    return await invoke("upsert_custom_app_prompt", { app, prompt });
+  },
+
+  async deletePrompt(app: string, id: string): Promise<void> {
+    return await invoke("delete_custom_app_prompt", { app, id });
+  },
+
+  async enablePrompt(app: string, id: string): Promise<void> {
+    return await invoke("enable_custom_app_prompt", { app, id });
+  },
+};
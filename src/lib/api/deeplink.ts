@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import type { SignatureStatus } from "./signing";
 
 export type ResourceType = "provider" | "prompt" | "mcp" | "skill";
 
@@ -47,6 +48,9 @@ export interface DeepLinkImportRequest {
   usageAccessToken?: string;
   usageUserId?: string;
   usageAutoInterval?: number;
+
+  // Publisher signature (tampered links are rejected while parsing)
+  signature?: SignatureStatus;
 }
 
 export interface McpImportResult {
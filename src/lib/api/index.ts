@@ -4,6 +4,10 @@ export { settingsApi } from "./settings";
 export { backupsApi } from "./settings";
 export { mcpApi } from "./mcp";
 export { promptsApi } from "./prompts";
+export { bundlesApi } from "./bundles";
+export { profilesApi } from "./profiles";
+export { projectsApi } from "./projects";
+export { signingApi } from "./signing";
 export { skillsApi } from "./skills";
 export { usageApi } from "./usage";
 export { subscriptionApi } from "./subscription";
@@ -12,11 +16,61 @@ export { proxyApi } from "./proxy";
 export { openclawApi } from "./openclaw";
 export { sessionsApi } from "./sessions";
 export { workspaceApi } from "./workspace";
+export { customAppsApi } from "./customApps";
+export { codexConfigApi } from "./codexConfig";
 export * as configApi from "./config";
 export * as authApi from "./auth";
 export * as copilotApi from "./copilot";
-export type { ProviderSwitchEvent } from "./providers";
+export type {
+  LiveDrift,
+  LiveDriftAction,
+  LiveDriftCategory,
+  LiveFieldChange,
+  LiveHistoryEntry,
+  LiveHistoryFile,
+  ProviderSwitchEvent,
+  SwitchPreview,
+  SwitchPreviewChange,
+  SwitchPreviewFile,
+} from "./providers";
 export type { Prompt } from "./prompts";
+export type {
+  AppDefinition,
+  AppDefinitionFormat,
+  AppDefinitionLoadReport,
+} from "./customApps";
+export type {
+  CodexConfigSettings,
+  CodexProfile,
+  CodexShellEnvironmentPolicy,
+  CodexUnknownValue,
+} from "./codexConfig";
+export type {
+  BundleExportOptions,
+  BundleImportRecord,
+  BundleImportResult,
+  BundleItemChange,
+  BundlePreview,
+  BundleSecretInput,
+  TeamBundle,
+} from "./bundles";
+export type {
+  SignatureStatus,
+  SigningKeyPair,
+  TrustedPublisher,
+} from "./signing";
+export type {
+  Profile,
+  ProfileAppSelection,
+  ProfileSwitchResult,
+} from "./profiles";
+export type {
+  ProjectScope,
+  ProjectAppSelection,
+  ProjectPreview,
+  ProjectFileChange,
+  ProjectDrift,
+} from "./projects";
 export type {
   CopilotDeviceCodeResponse,
   CopilotAuthStatus,
@@ -1,6 +1,10 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   McpConfigResponse,
+  McpGatewayConfig,
+  McpProbeResult,
+  McpSecretInfo,
+  McpSecretReport,
   McpServer,
   McpServerSpec,
   McpServersMap,
@@ -126,4 +130,81 @@ export const mcpApi = {
   async importFromApps(): Promise<number> {
     return await invoke("import_mcp_from_apps");
   },
+
+  /**
+   * 探测已保存的 MCP 服务器（握手并列出 tools/prompts/resources），结果会被记录
+   */
+  async probeServer(id: string, timeoutSecs?: number): Promise<McpProbeResult> {
+    return await invoke("probe_mcp_server", { id, timeoutSecs });
+  },
+
+  /**
+   * 探测尚未保存的 MCP 服务器连接定义
+   */
+  async probeSpec(
+    spec: McpServerSpec,
+    timeoutSecs?: number,
+  ): Promise<McpProbeResult> {
+    return await invoke("probe_mcp_spec", { spec, timeoutSecs });
+  },
+
+  /**
+   * 获取所有 MCP 服务器的最近一次探测结果
+   */
+  async getProbeResults(): Promise<Record<string, McpProbeResult>> {
+    return await invoke("get_mcp_probe_results");
+  },
+
+  /**
+   * 获取内置 MCP 网关配置
+   */
+  async getGatewayConfig(): Promise<McpGatewayConfig> {
+    return await invoke("get_mcp_gateway_config");
+  },
+
+  /**
+   * 保存网关配置，并重新写入各应用的 MCP 配置
+   */
+  async saveGatewayConfig(config: McpGatewayConfig): Promise<void> {
+    return await invoke("save_mcp_gateway_config", { config });
+  },
+
+  /**
+   * 预览网关向指定应用暴露的工具
+   */
+  async listGatewayTools(app: AppId): Promise<Record<string, any>[]> {
+    return await invoke("list_mcp_gateway_tools", { app });
+  },
+
+  /**
+   * 列出本地密钥（不返回值）
+   */
+  async listSecrets(): Promise<McpSecretInfo[]> {
+    return await invoke("list_mcp_secrets");
+  },
+
+  /**
+   * 新增或更新本地密钥，引用它的服务器会被重新同步
+   */
+  async setSecret(
+    name: string,
+    value: string,
+    description?: string,
+  ): Promise<void> {
+    return await invoke("set_mcp_secret", { name, value, description });
+  },
+
+  /**
+   * 删除本地密钥
+   */
+  async deleteSecret(name: string): Promise<boolean> {
+    return await invoke("delete_mcp_secret", { name });
+  },
+
+  /**
+   * 按应用列出无法解析的密钥 / 环境变量引用
+   */
+  async validateSecretRefs(): Promise<McpSecretReport> {
+    return await invoke("validate_mcp_secret_refs");
+  },
 };
@@ -0,0 +1,52 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AppId } from "./types";
+
+// 档案中某个应用的选择；字段缺省表示切换时保持不变
+export interface ProfileAppSelection {
+  providerId?: string;
+  mcpServers?: string[]; // 其余 MCP 服务器会被关闭
+  prompts?: string[]; // 一个 ID 为单一提示词，多个为片段叠加，空数组为不启用
+  skills?: string[]; // 其余 Skills 会被关闭
+}
+
+export interface Profile {
+  id: string; // 新建时留空
+  name: string;
+  apps: Partial<Record<AppId, ProfileAppSelection>>;
+  sortIndex?: number;
+  createdAt: number; // 秒
+  updatedAt: number; // 秒
+}
+
+export interface ProfileSwitchResult {
+  profileId: string;
+  warnings: string[];
+}
+
+export const profilesApi = {
+  async list(): Promise<Profile[]> {
+    return await invoke("list_profiles");
+  },
+
+  async save(profile: Profile): Promise<Profile> {
+    return await invoke("save_profile", { profile });
+  },
+
+  async delete(id: string): Promise<boolean> {
+    return await invoke("delete_profile", { id });
+  },
+
+  /**
+   * 与当前状态完全一致的档案 ID
+   */
+  async getCurrent(): Promise<string | null> {
+    return await invoke("get_current_profile");
+  },
+
+  /**
+   * 切换到档案；任一步失败时后端会回滚到切换前状态
+   */
+  async apply(id: string): Promise<ProfileSwitchResult> {
+    return await invoke("apply_profile", { id });
+  },
+};
@@ -0,0 +1,69 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AppId } from "./types";
+
+// 项目中某个应用的启用集合
+export interface ProjectAppSelection {
+  mcpServers: string[];
+  promptId?: string;
+  skills: string[]; // 目前仅 Claude 支持项目级 Skills（.claude/skills）
+}
+
+export interface ProjectScope {
+  id: string; // 新建时留空
+  name: string;
+  path: string; // 项目根目录（绝对路径）
+  apps: Partial<Record<AppId, ProjectAppSelection>>;
+  createdAt: number; // 秒
+  updatedAt: number; // 秒
+}
+
+export type ProjectFileKind = "mcp" | "prompt" | "skill";
+export type ProjectFileAction = "create" | "update" | "delete" | "unchanged";
+
+export interface ProjectFileChange {
+  path: string; // 相对项目根目录
+  kind: ProjectFileKind;
+  action: ProjectFileAction;
+  diff: string; // unified diff，Skill 目录为空
+  warnings?: string[];
+}
+
+export interface ProjectDrift {
+  path: string;
+  kind: "modified" | "missing" | "untracked"; // untracked：已存在但尚未由 CC Switch 写入
+}
+
+export interface ProjectPreview {
+  projectId: string;
+  changes: ProjectFileChange[];
+  drift: ProjectDrift[];
+}
+
+export const projectsApi = {
+  async list(): Promise<ProjectScope[]> {
+    return await invoke("list_projects");
+  },
+
+  async save(project: ProjectScope): Promise<ProjectScope> {
+    return await invoke("save_project", { project });
+  },
+
+  async delete(id: string): Promise<boolean> {
+    return await invoke("delete_project", { id });
+  },
+
+  async preview(id: string): Promise<ProjectPreview> {
+    return await invoke("preview_project_sync", { id });
+  },
+
+  /**
+   * 写入项目文件；文件在上次写入后被改动时需要 force
+   */
+  async apply(id: string, force = false): Promise<ProjectFileChange[]> {
+    return await invoke("apply_project_sync", { id, force });
+  },
+
+  async checkDrift(id: string): Promise<ProjectDrift[]> {
+    return await invoke("check_project_drift", { id });
+  },
+};
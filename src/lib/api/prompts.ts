@@ -11,6 +11,45 @@ export interface Prompt {
   updatedAt?: number;
 }
 
+export interface PromptApps {
+  claude: boolean;
+  codex: boolean;
+  gemini: boolean;
+  opencode: boolean;
+  openclaw: boolean;
+  hermes: boolean;
+  qwen: boolean;
+}
+
+/** 跨应用共享的提示词，各应用下以 `shared:<id>` 的副本形式启用 */
+export interface SharedPrompt {
+  id: string;
+  name: string;
+  content: string;
+  description?: string;
+  apps: PromptApps;
+  /** 应用 ID -> 追加在公共内容之后的专属段落 */
+  overrides: Record<string, string>;
+  createdAt?: number;
+  updatedAt?: number;
+}
+
+export interface SharedPromptSyncResult {
+  /** 已写入副本的应用 */
+  synced: AppId[];
+  /** 单提示词模式下写入了副本但未启用的应用 */
+  inactive: AppId[];
+  /** 应用 ID -> 因 activate 被替换的原启用提示词 ID */
+  replaced: Record<string, string>;
+}
+
+export interface PromptStackConfig {
+  /** 应用 ID -> 按顺序叠加的提示词 ID */
+  stacks: Record<string, string[]>;
+  /** 自定义模板变量，可在提示词中以 {{name}} 引用 */
+  variables: Record<string, string>;
+}
+
 export const promptsApi = {
   async getPrompts(app: AppId): Promise<Record<string, Prompt>> {
     return await invoke("get_prompts", { app });
@@ -35,4 +74,44 @@ export const promptsApi = {
   async getCurrentFileContent(app: AppId): Promise<string | null> {
     return await invoke("get_current_prompt_file_content", { app });
   },
+
+  async getStackConfig(): Promise<PromptStackConfig> {
+    return await invoke("get_prompt_stack_config");
+  },
+
+  /** 按顺序启用多个片段；传入空数组退出多片段模式 */
+  async setStack(app: AppId, ids: string[]): Promise<void> {
+    return await invoke("set_prompt_stack", { app, ids });
+  },
+
+  async saveVariables(variables: Record<string, string>): Promise<void> {
+    return await invoke("save_prompt_variables", { variables });
+  },
+
+  async previewStack(app: AppId, ids: string[]): Promise<string> {
+    return await invoke("preview_prompt_stack", { app, ids });
+  },
+
+  async getSharedPrompts(): Promise<Record<string, SharedPrompt>> {
+    return await invoke("get_shared_prompts");
+  },
+
+  /** 保存并同步到所选应用；仅在 activate 时替换单提示词模式下的启用项 */
+  async upsertSharedPrompt(
+    prompt: SharedPrompt,
+    activate = false,
+  ): Promise<SharedPromptSyncResult> {
+    return await invoke("upsert_shared_prompt", { prompt, activate });
+  },
+
+  async deleteSharedPrompt(id: string): Promise<void> {
+    return await invoke("delete_shared_prompt", { id });
+  },
+
+  async syncSharedPrompt(
+    id: string,
+    activate = false,
+  ): Promise<SharedPromptSyncResult> {
+    return await invoke("sync_shared_prompt", { id, activate });
+  },
 };
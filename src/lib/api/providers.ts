@@ -21,6 +21,66 @@ export interface SwitchResult {
   warnings: string[];
 }
 
+export type SwitchPreviewChange = "create" | "modify" | "delete";
+
+export interface SwitchPreviewFile {
+  path: string;
+  change: SwitchPreviewChange;
+  /** 与磁盘内容对比的 unified diff，密钥已遮蔽 */
+  diff: string;
+}
+
+/** 切换供应商的预览结果（不写入任何文件） */
+export interface SwitchPreview {
+  app: AppId;
+  providerId: string;
+  /** 代理接管中：切换只改变代理路由，不写入 live 文件 */
+  hotSwitch: boolean;
+  files: SwitchPreviewFile[];
+  warnings: string[];
+}
+
+export type LiveDriftCategory = "provider" | "commonConfig" | "unrelated";
+
+export interface LiveFieldChange {
+  /** JSON Pointer 形式的字段路径 */
+  path: string;
+  category: LiveDriftCategory;
+  expected?: unknown;
+  actual?: unknown;
+}
+
+/** live 配置相对 cc-switch 上次写入的外部修改 */
+export interface LiveDrift {
+  app: AppId;
+  providerId?: string | null;
+  files: string[];
+  changes: LiveFieldChange[];
+  detectedAt: number;
+}
+
+export type LiveDriftAction = "backfill" | "reapply" | "accept";
+
+/** live 配置文件的一个历史版本 */
+export interface LiveHistoryEntry {
+  id: string;
+  /** 触发动作，如 switch、mcpSync、promptEnable、rollback */
+  action: string;
+  createdAt: number;
+  size: number;
+  deleted: boolean;
+  hash?: string;
+}
+
+/** 有写入历史的 live 配置文件 */
+export interface LiveHistoryFile {
+  app: string;
+  path: string;
+  versions: number;
+  latestAt?: number | null;
+  retention: number;
+}
+
 export interface OpenTerminalOptions {
   cwd?: string;
 }
@@ -91,6 +151,13 @@ export const providersApi = {
     return await invoke("switch_provider", { id, app: appId });
   },
 
+  /**
+   * 预览切换供应商将写入的 live 文件变更
+   */
+  async previewSwitch(id: string, appId: AppId): Promise<SwitchPreview> {
+    return await invoke("preview_switch_provider", { id, app: appId });
+  },
+
   async importDefault(appId: AppId): Promise<boolean> {
     return await invoke("import_default_config", { app: appId });
   },
@@ -196,6 +263,62 @@ export const providersApi = {
   async importHermesFromLive(): Promise<number> {
     return await invoke("import_hermes_providers_from_live");
   },
+
+  /**
+   * 检测各应用 live 配置的外部修改（漂移）
+   */
+  async getLiveDrifts(): Promise<LiveDrift[]> {
+    return await invoke("get_live_config_drifts");
+  },
+
+  /**
+   * 处理 live 配置漂移：回填到供应商、重新写入或接受外部修改
+   */
+  async resolveLiveDrift(
+    appId: AppId,
+    action: LiveDriftAction,
+  ): Promise<void> {
+    return await invoke("resolve_live_config_drift", { app: appId, action });
+  },
+
+  /**
+   * 列出有写入历史的 live 配置文件
+   */
+  async listLiveHistoryFiles(): Promise<LiveHistoryFile[]> {
+    return await invoke("list_live_history_files");
+  },
+
+  /**
+   * 列出某个 live 配置文件的历史版本（新到旧）
+   */
+  async listLiveHistoryVersions(path: string): Promise<LiveHistoryEntry[]> {
+    return await invoke("list_live_history_versions", { path });
+  },
+
+  /**
+   * 对比两个历史版本，toId 为空时与当前文件对比
+   */
+  async diffLiveHistory(
+    path: string,
+    fromId: string,
+    toId?: string,
+  ): Promise<string> {
+    return await invoke("diff_live_history", { path, fromId, toId });
+  },
+
+  /**
+   * 将 live 配置文件回滚到指定历史版本
+   */
+  async restoreLiveHistory(path: string, id: string): Promise<void> {
+    return await invoke("restore_live_history", { path, id });
+  },
+
+  /**
+   * 设置某个 live 配置文件保留的历史版本数
+   */
+  async setLiveHistoryRetention(path: string, limit: number): Promise<void> {
+    return await invoke("set_live_history_retention", { path, limit });
+  },
 };
 
 // ============================================================================
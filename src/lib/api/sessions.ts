@@ -1,5 +1,13 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { SessionMessage, SessionMeta } from "@/types";
+import type {
+  ArchivedSession,
+  RetentionReport,
+  RetentionState,
+  SessionCostReport,
+  SessionMessage,
+  SessionMeta,
+  SessionRetentionConfig,
+} from "@/types";
 
 export interface DeleteSessionOptions {
   providerId: string;
@@ -12,11 +20,28 @@ export interface DeleteSessionResult extends DeleteSessionOptions {
   error?: string;
 }
 
+export interface ArchiveSessionResult extends DeleteSessionOptions {
+  success: boolean;
+  archive?: ArchivedSession;
+  error?: string;
+}
+
+export interface SessionCostQuery {
+  appType?: string;
+  startDate?: number;
+  endDate?: number;
+  limit?: number;
+}
+
 export const sessionsApi = {
   async list(): Promise<SessionMeta[]> {
     return await invoke("list_sessions");
   },
 
+  async getCostReport(query?: SessionCostQuery): Promise<SessionCostReport> {
+    return await invoke("get_session_cost_report", { query });
+  },
+
   async getMessages(
     providerId: string,
     sourcePath: string,
@@ -39,6 +64,40 @@ export const sessionsApi = {
     return await invoke("delete_sessions", { items });
   },
 
+  async archiveMany(
+    items: DeleteSessionOptions[],
+  ): Promise<ArchiveSessionResult[]> {
+    return await invoke("archive_sessions", { items });
+  },
+
+  async listArchives(): Promise<ArchivedSession[]> {
+    return await invoke("list_session_archives");
+  },
+
+  async restoreArchive(id: string): Promise<ArchivedSession> {
+    return await invoke("restore_session_archive", { id });
+  },
+
+  async deleteArchive(id: string): Promise<boolean> {
+    return await invoke("delete_session_archive", { id });
+  },
+
+  async getRetentionConfig(): Promise<SessionRetentionConfig> {
+    return await invoke("get_session_retention_config");
+  },
+
+  async saveRetentionConfig(config: SessionRetentionConfig): Promise<void> {
+    await invoke("save_session_retention_config", { config });
+  },
+
+  async runRetention(dryRun = true): Promise<RetentionReport> {
+    return await invoke("run_session_retention", { dryRun });
+  },
+
+  async getRetentionState(): Promise<RetentionState> {
+    return await invoke("get_session_retention_state");
+  },
+
   async launchTerminal(options: {
     command: string;
     cwd?: string | null;
@@ -0,0 +1,44 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// 深链接与配置包的签名状态；签名无效的内容在解析时即被拒绝
+export type SignatureStatus =
+  | { status: "unsigned" }
+  | { status: "trusted"; publisher: string; fingerprint: string }
+  | { status: "untrusted"; publicKey: string; fingerprint: string };
+
+export interface TrustedPublisher {
+  name: string;
+  publicKey: string;
+  addedAt: number; // 秒
+}
+
+export interface SigningKeyPair {
+  publicKey: string;
+  privateKey: string; // 不会被保存，请自行妥善保管
+  fingerprint: string;
+}
+
+export const signingApi = {
+  async getTrustedPublishers(): Promise<TrustedPublisher[]> {
+    return await invoke("get_trusted_publishers");
+  },
+
+  async addTrustedPublisher(
+    name: string,
+    publicKey: string,
+  ): Promise<TrustedPublisher[]> {
+    return await invoke("add_trusted_publisher", { name, publicKey });
+  },
+
+  async removeTrustedPublisher(publicKey: string): Promise<TrustedPublisher[]> {
+    return await invoke("remove_trusted_publisher", { publicKey });
+  },
+
+  async generateKey(): Promise<SigningKeyPair> {
+    return await invoke("generate_publisher_key");
+  },
+
+  async signDeeplink(url: string, privateKey: string): Promise<string> {
+    return await invoke("sign_deeplink", { url, privateKey });
+  },
+};
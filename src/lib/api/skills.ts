@@ -36,6 +36,44 @@ export interface InstalledSkill {
   installedAt: number;
   contentHash?: string;
   updatedAt: number;
+  /** 从任意 git 仓库或本地目录安装时的来源 */
+  source?: SkillSource;
+}
+
+/** Skill 安装来源 */
+export interface SkillSource {
+  kind: "git" | "local";
+  url: string;
+  path?: string;
+  /** 安装时指定的分支、tag 或 commit */
+  ref?: string;
+  /** 固定在 tag / commit 时为 true，更新检测只提示不自动跟随 */
+  pinned: boolean;
+  resolvedCommit?: string;
+}
+
+/** 从 git 仓库或本地目录安装 Skill 的请求 */
+export interface SkillSourceRequest {
+  url: string;
+  ref?: string;
+  path?: string;
+  /** 已确认安装前检查的警告 */
+  acknowledgeWarnings?: boolean;
+}
+
+/** 安装前检查发现的问题 */
+export interface SkillLintIssue {
+  severity: "warning" | "error";
+  code: string;
+  /** 相对 Skill 目录的路径，可带行号 */
+  path?: string;
+  detail?: string;
+}
+
+/** 单个 Skill 的安装前检查报告 */
+export interface SkillLintReport {
+  directory: string;
+  issues: SkillLintIssue[];
 }
 
 export interface SkillUninstallResult {
@@ -95,6 +133,55 @@ export interface SkillUpdateInfo {
   name: string;
   currentHash?: string;
   remoteHash: string;
+  /** 固定的 tag / commit */
+  pinnedRef?: string;
+  /** 可用的新版本（tag 或分支名） */
+  availableRef?: string;
+}
+
+/** skills.lock 中的来源 */
+export type LockedSkillSource =
+  | { kind: "github"; owner: string; repo: string; ref: string; path: string }
+  | {
+      kind: "git";
+      url: string;
+      path?: string;
+      ref?: string;
+      commit?: string;
+      pinned: boolean;
+    }
+  | { kind: "local"; url?: string; path?: string };
+
+/** skills.lock 中的单个 Skill */
+export interface SkillLockEntry {
+  id: string;
+  name: string;
+  directory: string;
+  content_hash?: string;
+  source: LockedSkillSource;
+  apps: SkillApps;
+}
+
+/** skills.lock */
+export interface SkillsLock {
+  version: number;
+  skill: SkillLockEntry[];
+}
+
+/** 本地 Skill 与 skills.lock 的差异 */
+export interface SkillLockDrift {
+  directory: string;
+  kind: "missing" | "modified" | "sourceChanged" | "untracked";
+  expected?: string;
+  actual?: string;
+}
+
+/** 按 skills.lock 导入的结果 */
+export interface SkillLockImportResult {
+  installed: string[];
+  alreadyInstalled: string[];
+  skipped: { directory: string; reason: string }[];
+  drift: SkillLockDrift[];
 }
 
 /** 存储位置迁移结果 */
@@ -155,8 +242,13 @@ export const skillsApi = {
   async installUnified(
     skill: DiscoverableSkill,
     currentApp: AppId,
+    acknowledgeWarnings?: boolean,
   ): Promise<InstalledSkill> {
-    return await invoke("install_skill_unified", { skill, currentApp });
+    return await invoke("install_skill_unified", {
+      skill,
+      currentApp,
+      acknowledgeWarnings,
+    });
   },
 
   /** 卸载 Skill（统一卸载） */
@@ -200,8 +292,32 @@ export const skillsApi = {
   },
 
   /** 更新单个 Skill */
-  async updateSkill(id: string): Promise<InstalledSkill> {
-    return await invoke("update_skill", { id });
+  async updateSkill(
+    id: string,
+    acknowledgeWarnings?: boolean,
+  ): Promise<InstalledSkill> {
+    return await invoke("update_skill", { id, acknowledgeWarnings });
+  },
+
+  /** 从任意 git 仓库（可指定分支 / tag / commit）或本地目录安装 Skills */
+  async installFromSource(
+    request: SkillSourceRequest,
+    currentApp: AppId,
+  ): Promise<InstalledSkill[]> {
+    return await invoke("install_skill_from_source", { request, currentApp });
+  },
+
+  /** 固定 Skill 到指定 ref，不传 ref 时改为跟随默认分支 */
+  async pinSkill(
+    id: string,
+    ref?: string,
+    acknowledgeWarnings?: boolean,
+  ): Promise<InstalledSkill> {
+    return await invoke("pin_skill", {
+      id,
+      gitRef: ref ?? null,
+      acknowledgeWarnings,
+    });
   },
 
   /** 迁移 Skill 存储位置 */
@@ -277,7 +393,30 @@ export const skillsApi = {
   async installFromZip(
     filePath: string,
     currentApp: AppId,
+    acknowledgeWarnings?: boolean,
   ): Promise<InstalledSkill[]> {
-    return await invoke("install_skills_from_zip", { filePath, currentApp });
+    return await invoke("install_skills_from_zip", {
+      filePath,
+      currentApp,
+      acknowledgeWarnings,
+    });
+  },
+
+  /** 导出 skills.lock */
+  async exportLock(filePath: string): Promise<SkillsLock> {
+    return await invoke("export_skills_lock", { filePath });
+  },
+
+  /** 检查本地 Skill 与 skills.lock 的差异 */
+  async checkLock(filePath: string): Promise<SkillLockDrift[]> {
+    return await invoke("check_skills_lock", { filePath });
+  },
+
+  /** 按 skills.lock 复现 Skill 集合 */
+  async importLock(
+    filePath: string,
+    currentApp: AppId,
+  ): Promise<SkillLockImportResult> {
+    return await invoke("import_skills_lock", { filePath, currentApp });
   },
 };
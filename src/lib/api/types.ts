@@ -6,4 +6,8 @@ export type AppId =
   | "gemini"
   | "opencode"
   | "openclaw"
-  | "hermes";
+  | "hermes"
+  | "qwen"
+  | "goose"
+  | "crush"
+  | "aider";
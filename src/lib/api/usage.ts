@@ -8,6 +8,19 @@ import type {
   RequestLog,
   LogFilters,
   ModelPricing,
+  PricingImportOptions,
+  PricingImportReport,
+  ProviderModelPricing,
+  UnpricedModel,
+  UsageExportDataset,
+  UsageExportFilter,
+  UsageExportFormat,
+  UsageExportResult,
+  UsageReportSchedule,
+  UsageReportState,
+  BudgetAlert,
+  BudgetAlertConfig,
+  BudgetUsage,
   ProviderLimitStatus,
   PaginatedLogs,
   SessionSyncResult,
@@ -129,6 +142,92 @@ export const usageApi = {
     return invoke("delete_model_pricing", { modelId });
   },
 
+  importPricingCatalog: async (source: {
+    filePath?: string;
+    url?: string;
+    options?: PricingImportOptions;
+  }): Promise<PricingImportReport> => {
+    return invoke("import_model_pricing_catalog", {
+      filePath: source.filePath,
+      url: source.url,
+      options: source.options,
+    });
+  },
+
+  exportUsage: async (
+    dataset: UsageExportDataset,
+    format: UsageExportFormat,
+    filePath: string,
+    filter?: UsageExportFilter,
+  ): Promise<UsageExportResult> => {
+    return invoke("export_usage_data", { dataset, format, filePath, filter });
+  },
+
+  getReportSchedule: async (): Promise<UsageReportSchedule> => {
+    return invoke("get_usage_report_schedule");
+  },
+
+  saveReportSchedule: async (schedule: UsageReportSchedule): Promise<void> => {
+    return invoke("save_usage_report_schedule", { schedule });
+  },
+
+  runReportNow: async (): Promise<UsageExportResult[]> => {
+    return invoke("run_usage_report_now");
+  },
+
+  getReportState: async (): Promise<UsageReportState> => {
+    return invoke("get_usage_report_state");
+  },
+
+  getBudgetAlertConfig: async (): Promise<BudgetAlertConfig> => {
+    return invoke("get_budget_alert_config");
+  },
+
+  saveBudgetAlertConfig: async (config: BudgetAlertConfig): Promise<void> => {
+    return invoke("save_budget_alert_config", { config });
+  },
+
+  getBudgetUsages: async (): Promise<BudgetUsage[]> => {
+    return invoke("get_budget_usages");
+  },
+
+  getBudgetAlertHistory: async (): Promise<BudgetAlert[]> => {
+    return invoke("get_budget_alert_history");
+  },
+
+  testBudgetWebhook: async (url: string): Promise<void> => {
+    return invoke("test_budget_webhook", { url });
+  },
+
+  getUnpricedModels: async (): Promise<UnpricedModel[]> => {
+    return invoke("get_unpriced_models");
+  },
+
+  getProviderModelPricing: async (
+    providerId?: string,
+    appType?: string,
+  ): Promise<ProviderModelPricing[]> => {
+    return invoke("get_provider_model_pricing", { providerId, appType });
+  },
+
+  upsertProviderModelPricing: async (
+    pricing: ProviderModelPricing,
+  ): Promise<void> => {
+    return invoke("upsert_provider_model_pricing", { pricing });
+  },
+
+  deleteProviderModelPricing: async (
+    providerId: string,
+    appType: string,
+    modelPattern: string,
+  ): Promise<boolean> => {
+    return invoke("delete_provider_model_pricing", {
+      providerId,
+      appType,
+      modelPattern,
+    });
+  },
+
   checkProviderLimits: async (
     providerId: string,
     appType: string,
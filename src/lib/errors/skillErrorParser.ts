@@ -1,4 +1,5 @@
 import { TFunction } from "i18next";
+import type { SkillLintReport } from "@/lib/api/skills";
 
 /**
  * 结构化错误对象
@@ -39,6 +40,11 @@ function getErrorI18nKey(code: string): string {
     EMPTY_ARCHIVE: "skills.error.emptyArchive",
     GET_HOME_DIR_FAILED: "skills.error.getHomeDirFailed",
     NO_SKILLS_IN_ZIP: "skills.error.noSkillsInZip",
+    SKILL_PINNED: "skills.error.skillPinned",
+    GIT_FAILED: "skills.error.gitFailed",
+    GIT_REF_NOT_FOUND: "skills.error.gitRefNotFound",
+    SKILL_LINT_FAILED: "skills.error.lintFailed",
+    SKILL_LINT_WARNINGS: "skills.error.lintWarnings",
   };
 
   return mapping[code] || "skills.error.unknownError";
@@ -56,6 +62,10 @@ function getSuggestionI18nKey(suggestion: string): string {
     checkPermission: "skills.error.suggestion.checkPermission",
     uninstallFirst: "skills.error.suggestion.uninstallFirst",
     checkZipContent: "skills.error.suggestion.checkZipContent",
+    checkGitInstalled: "skills.error.suggestion.checkGitInstalled",
+    repinSkill: "skills.error.suggestion.repinSkill",
+    reviewSkillContent: "skills.error.suggestion.reviewSkillContent",
+    acknowledgeWarnings: "skills.error.suggestion.acknowledgeWarnings",
     http403: "skills.error.http403",
     http404: "skills.error.http404",
     http429: "skills.error.http429",
@@ -64,6 +74,58 @@ function getSuggestionI18nKey(suggestion: string): string {
   return mapping[suggestion] || suggestion;
 }
 
+/**
+ * 从安装前检查错误中取出检查报告
+ */
+export function parseSkillLintReports(
+  error: SkillError,
+): SkillLintReport[] | null {
+  if (!error.context.report) return null;
+  try {
+    return JSON.parse(error.context.report) as SkillLintReport[];
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * 安装前检查有警告、需要用户确认时返回检查报告
+ */
+export function getSkillLintWarnings(
+  errorString: string,
+): SkillLintReport[] | null {
+  const parsed = parseSkillError(errorString);
+  if (parsed?.code !== "SKILL_LINT_WARNINGS") return null;
+  return parseSkillLintReports(parsed);
+}
+
+/**
+ * 把检查报告格式化为逐行列出的问题
+ */
+export function formatSkillLintReports(
+  reports: SkillLintReport[],
+  t: TFunction,
+): string {
+  return reports
+    .map((report) => {
+      const lines = report.issues.map((issue) => {
+        const message = t(`skills.lint.${issue.code}`, {
+          detail: issue.detail ?? "",
+          defaultValue: issue.code,
+        });
+        const severity = t(`skills.lint.severity.${issue.severity}`);
+        const location = issue.path ? ` (${issue.path})` : "";
+        const detail =
+          issue.detail && !message.includes(issue.detail)
+            ? `: ${issue.detail}`
+            : "";
+        return `• [${severity}] ${message}${location}${detail}`;
+      });
+      return [report.directory, ...lines].join("\n");
+    })
+    .join("\n\n");
+}
+
 /**
  * 格式化技能错误为用户友好的消息
  * @param errorString 后端返回的错误字符串
@@ -94,6 +156,12 @@ export function formatSkillError(
   // 构建描述（错误消息 + 建议）
   let description = t(errorKey, context);
 
+  // 安装前检查错误：附上问题列表
+  const lintReports = parseSkillLintReports(parsedError);
+  if (lintReports) {
+    description += `\n\n${formatSkillLintReports(lintReports, t)}`;
+  }
+
   // 如果有建议，追加到描述中
   if (suggestion) {
     const suggestionKey = getSuggestionI18nKey(suggestion);
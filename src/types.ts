@@ -173,6 +173,33 @@ export interface CodexChatReasoning {
   outputFormat?: CodexChatReasoningOutputFormat;
 }
 
+export type CodexReasoningEffort =
+  | "none"
+  | "minimal"
+  | "low"
+  | "medium"
+  | "high"
+  | "xhigh"
+  | (string & {});
+export type CodexSandboxMode =
+  | "read-only"
+  | "workspace-write"
+  | "danger-full-access"
+  | (string & {});
+export type CodexApprovalPolicy =
+  | "untrusted"
+  | "on-failure"
+  | "on-request"
+  | "never"
+  | (string & {});
+
+// Codex 运行策略：顶层、profile 内或供应商级默认值共用；未识别的取值原样保留
+export interface CodexPolicySettings {
+  modelReasoningEffort?: CodexReasoningEffort;
+  sandboxMode?: CodexSandboxMode;
+  approvalPolicy?: CodexApprovalPolicy;
+}
+
 // 供应商元数据（字段名与后端一致，保持 snake_case）
 export interface ProviderMeta {
   // 自定义端点：以 URL 为键，值为端点信息
@@ -219,6 +246,8 @@ export interface ProviderMeta {
   codexFastMode?: boolean;
   // Codex Responses -> Chat Completions reasoning capability metadata
   codexChatReasoning?: CodexChatReasoning;
+  // Codex 默认策略：仅在供应商配置未显式设置时写入 config.toml
+  codexDefaults?: CodexPolicySettings;
   // Custom User-Agent for local proxy routing. Only applied by the local proxy.
   customUserAgent?: string;
   // 供应商类型（用于识别 Copilot 等特殊供应商）
@@ -267,6 +296,10 @@ export interface VisibleApps {
   opencode: boolean;
   openclaw: boolean;
   hermes: boolean;
+  qwen: boolean;
+  goose: boolean;
+  crush: boolean;
+  aider: boolean;
 }
 
 // WebDAV 同步状态
@@ -378,6 +411,14 @@ export interface Settings {
   openclawConfigDir?: string;
   // 覆盖 Hermes 配置目录（可选）
   hermesConfigDir?: string;
+  // 覆盖 Qwen Code 配置目录（可选）
+  qwenConfigDir?: string;
+  // 覆盖 Goose 配置目录（可选）
+  gooseConfigDir?: string;
+  // 覆盖 Crush 配置目录（可选）
+  crushConfigDir?: string;
+  // 覆盖 Aider 配置目录（可选，默认为主目录）
+  aiderConfigDir?: string;
 
   // ===== 当前供应商 ID（设备级）=====
   // 当前 Claude 供应商 ID（优先于数据库 is_current）
@@ -436,6 +477,99 @@ export interface SessionMeta {
   lastActiveAt?: number;
   sourcePath?: string;
   resumeCommand?: string;
+  usage?: SessionUsage;
+}
+
+export interface SessionProviderUsage {
+  providerId: string;
+  providerName: string;
+  requestCount: number;
+  totalCostUsd: string;
+}
+
+export interface SessionUsage {
+  requestCount: number;
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadTokens: number;
+  cacheCreationTokens: number;
+  realTotalTokens: number;
+  cacheHitRate: number;
+  totalCostUsd: string;
+  providers: SessionProviderUsage[];
+  firstRequestAt: number;
+  lastRequestAt: number;
+}
+
+export interface ProjectCostRollup {
+  projectDir?: string;
+  sessionCount: number;
+  requestCount: number;
+  realTotalTokens: number;
+  totalCostUsd: string;
+  providerIds: string[];
+}
+
+export interface SessionCostReport {
+  sessions: SessionMeta[];
+  projects: ProjectCostRollup[];
+  totalCostUsd: string;
+}
+
+export interface ArchivedSession {
+  id: string;
+  providerId: string;
+  sessionId: string;
+  title?: string;
+  projectDir?: string;
+  sourcePath: string;
+  archivePath: string;
+  originalSize: number;
+  archivedSize: number;
+  lastActiveAt?: number;
+  archivedAt: number;
+  reason: "manual" | "age" | "size";
+}
+
+export interface SessionRetentionRule {
+  providerId?: string;
+  projectDir?: string;
+  maxAgeDays?: number;
+  maxTotalSizeMb?: number;
+  keepLatest?: number;
+}
+
+export interface SessionRetentionConfig {
+  enabled: boolean;
+  intervalHours: number;
+  rules: SessionRetentionRule[];
+}
+
+export interface RetentionCandidate {
+  providerId: string;
+  sessionId: string;
+  title?: string;
+  projectDir?: string;
+  sourcePath: string;
+  sizeBytes: number;
+  lastActiveAt: number;
+  reason: "age" | "size";
+  ruleIndex: number;
+}
+
+export interface RetentionReport {
+  dryRun: boolean;
+  generatedAt: number;
+  candidates: RetentionCandidate[];
+  archived: ArchivedSession[];
+  skippedUnsupported: number;
+  errors: string[];
+  totalBytes: number;
+}
+
+export interface RetentionState {
+  lastRunAt?: number;
+  lastReport?: RetentionReport;
 }
 
 export interface SessionMessage {
@@ -469,6 +603,9 @@ export interface McpApps {
   opencode: boolean;
   openclaw: boolean;
   hermes: boolean;
+  qwen: boolean;
+  goose: boolean;
+  crush: boolean;
 }
 
 // MCP 服务器条目（v3.7.0 统一结构）
@@ -497,6 +634,73 @@ export interface McpStatus {
   serverCount: number;
 }
 
+// MCP 服务器探测结果（initialize + tools/prompts/resources 列表）
+export interface McpToolInfo {
+  name: string;
+  description?: string;
+  inputSchema?: Record<string, any>;
+}
+
+export interface McpPromptInfo {
+  name: string;
+  description?: string;
+  arguments?: Record<string, any>[];
+}
+
+export interface McpResourceInfo {
+  uri: string;
+  name?: string;
+  description?: string;
+  mimeType?: string;
+}
+
+export interface McpProbeResult {
+  status: "ok" | "error";
+  transport: string;
+  probedAt: number; // 毫秒
+  durationMs: number;
+  serverName?: string;
+  serverVersion?: string;
+  protocolVersion?: string;
+  tools: McpToolInfo[];
+  prompts: McpPromptInfo[];
+  resources: McpResourceInfo[];
+  warnings?: string[];
+  error?: string;
+  stderr?: string;
+}
+
+// 内置 MCP 网关配置
+export interface McpToolPolicy {
+  allow: string[]; // 为空表示允许全部，模式匹配 `<服务器ID>__<工具名>`
+  deny: string[];
+}
+
+export interface McpGatewayConfig {
+  enabled: boolean;
+  transport: "stdio" | "http";
+  policies: Record<string, McpToolPolicy>; // 应用 ID -> 规则
+}
+
+// MCP 本地密钥库（env / headers 中以 `${secret:name}` 引用，不参与同步与导出）
+export interface McpSecretInfo {
+  name: string;
+  description?: string;
+  createdAt: number; // 秒
+  updatedAt: number; // 秒
+}
+
+export interface McpUnresolvedSecretRef {
+  serverId: string;
+  serverName: string;
+  kind: "secret" | "env";
+  name: string;
+  field: string; // 如 env.GITHUB_TOKEN、headers.Authorization
+}
+
+// 应用 ID -> 未能解析的引用
+export type McpSecretReport = Record<string, McpUnresolvedSecretRef[]>;
+
 // 新：来自 config.json 的 MCP 列表响应
 export interface McpConfigResponse {
   configPath: string;
@@ -46,6 +46,8 @@ export interface ProxyTakeoverStatus {
   "claude-desktop"?: boolean;
   codex: boolean;
   gemini: boolean;
+  qwen: boolean;
+  aider: boolean;
   opencode: boolean;
   openclaw: boolean;
   hermes: boolean;
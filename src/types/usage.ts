@@ -63,6 +63,128 @@ export interface ModelPricing {
   outputCostPerMillion: string;
   cacheReadCostPerMillion: string;
   cacheCreationCostPerMillion: string;
+  pricingTiers?: PricingTier[];
+  source?: "builtin" | "catalog" | "manual";
+}
+
+export interface PricingTier {
+  aboveTokens: number;
+  inputCostPerMillion: string;
+  outputCostPerMillion: string;
+  cacheReadCostPerMillion: string;
+  cacheCreationCostPerMillion: string;
+}
+
+export interface PricingImportOptions {
+  overwrite?: boolean;
+  overwriteManual?: boolean;
+  providers?: string[];
+}
+
+export interface PricingImportReport {
+  totalEntries: number;
+  inserted: number;
+  updated: number;
+  skippedExisting: number;
+  skippedInvalid: number;
+  backfilled: number;
+}
+
+export interface ProviderModelPricing {
+  providerId: string;
+  appType: string;
+  modelPattern: string;
+  inputCostPerMillion: string;
+  outputCostPerMillion: string;
+  cacheReadCostPerMillion: string;
+  cacheCreationCostPerMillion: string;
+}
+
+export type UsageExportFormat = "csv" | "ndjson";
+
+export type UsageExportDataset = "requestLogs" | "dailyRollups" | "spend";
+
+export interface UsageExportFilter {
+  startDate?: number;
+  endDate?: number;
+  appType?: string;
+  providerId?: string;
+}
+
+export interface UsageExportResult {
+  path: string;
+  dataset: UsageExportDataset;
+  format: UsageExportFormat;
+  rowCount: number;
+}
+
+export interface UsageReportSchedule {
+  enabled: boolean;
+  frequency: "weekly" | "monthly";
+  outputDir?: string;
+  format: UsageExportFormat;
+  datasets: UsageExportDataset[];
+  appType?: string;
+}
+
+export interface UsageReportState {
+  lastPeriod?: string;
+  lastRunAt?: number;
+  lastFiles: string[];
+}
+
+export type BudgetPeriod = "daily" | "weekly" | "monthly";
+
+export type BudgetMetric = "cost" | "tokens";
+
+export interface BudgetRule {
+  id: string;
+  name: string;
+  enabled: boolean;
+  /** 为空表示全局预算 */
+  appType?: string;
+  period: BudgetPeriod;
+  limitUsd?: number;
+  limitTokens?: number;
+}
+
+export interface BudgetAlertConfig {
+  enabled: boolean;
+  budgets: BudgetRule[];
+  /** 预警阈值（百分比） */
+  thresholds: number[];
+  desktopNotification: boolean;
+  webhookUrl?: string;
+}
+
+export interface BudgetUsage {
+  budgetId: string;
+  periodKey: string;
+  costUsd: number;
+  tokens: number;
+}
+
+export interface BudgetAlert {
+  budgetId: string;
+  budgetName: string;
+  appType?: string;
+  period: BudgetPeriod;
+  periodKey: string;
+  metric: BudgetMetric;
+  threshold: number;
+  usage: number;
+  limit: number;
+  percent: number;
+  triggeredAt: number;
+}
+
+export interface UnpricedModel {
+  model: string;
+  appType: string;
+  requestCount: number;
+  totalTokens: number;
+  firstSeenAt: number;
+  lastSeenAt: number;
 }
 
 export interface UsageSummary {